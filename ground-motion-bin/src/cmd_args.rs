@@ -1,8 +1,63 @@
-use clap::{ArgGroup, Parser};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use ground_motion_lib::cutoff::CutoffMode;
+use ground_motion_lib::distance::DistanceMethod;
+use ground_motion_lib::gmice::GmiceMethod;
+use ground_motion_lib::gmm::{Magnitude, TectonicType};
+use ground_motion_lib::readers::Vs30Columns;
+use std::str::FromStr;
+
+/// Intensity measure of the output grid.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Imt {
+    /// Type of motion of the selected config (PGA, PGV, PSA, ...).
+    Config,
+    /// Macroseismic intensity converted from the config output with `--gmice`.
+    Mmi,
+}
+
+/// Maximum applicable distance of the config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MaxDistance {
+    /// Distance limit of the model family of the config.
+    Model,
+    /// Fixed distance (km).
+    Km(f64),
+}
+
+impl FromStr for MaxDistance {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("model") {
+            return Ok(MaxDistance::Model);
+        }
+        s.parse()
+            .map(MaxDistance::Km)
+            .map_err(|_| format!("Invalid maximum distance `{s}`, expected km or `model`"))
+    }
+}
+
+/// Format of the output grid file.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutFormat {
+    /// Selected by the extension of the output file.
+    Auto,
+    /// Delimited text with a header row.
+    Csv,
+    /// GeoJSON `FeatureCollection` of point features.
+    Geojson,
+    /// Single JSON document with metadata and an array of points.
+    Json,
+    /// JSON Lines, one JSON object per point.
+    Jsonl,
+    /// KML placemarks colored by value, for Google Earth.
+    Kml,
+}
 
 /// Input command line arguments.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 #[command(group(
     ArgGroup::new("input_mode")
         .required(true)
@@ -10,20 +65,29 @@ use clap::{ArgGroup, Parser};
 ))]
 #[command(group(
     ArgGroup::new("config_source")
-        .args(&["use_config", "custom_config"])
-        .multiple(false) // make them mutually exclusive
+        .args(&["use_config", "custom_config", "zonation"])
+        .multiple(true)
 ))]
 pub struct CmdArgs {
+    /// Additional tools; without a subcommand a site grid is computed.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Input VS30 CSV file containing site data.
     ///
-    /// Requires earthquake parameters (`--earthquake`) and a config source (`--use-config` or `--custom-config`).
+    /// Files with the `.asc` extension are read as Arc/Info ASCII Vs30 grids, and `-` reads
+    /// delimited site data from standard input. Delimited files with the `.gz` or `.zst`
+    /// extension are decompressed on the fly (requires the `gzip` or `zstd` feature).
+    ///
+    /// Requires earthquake parameters (`--earthquake`) and a config source (`--use-config`,
+    /// `--custom-config` or `--zonation`).
     #[arg(short, long, requires_all = &["earthquake"],  requires = "config_source")]
     pub in_file: Option<String>,
 
 
     /// Use a predefined GMPE configuration by name.
     ///
-    /// Mutually exclusive with `--custom-config`.
+    /// Mutually exclusive with `--custom-config` and `--zonation`.
     #[arg(short, long)]
     pub use_config: Option<String>,
 
@@ -31,27 +95,180 @@ pub struct CmdArgs {
     /// Provide a custom GMPE configuration TOML file.
     ///
     /// *Not implemented yet.*
-    #[arg(short, long)]
+    #[arg(short, long, conflicts_with = "use_config")]
     pub custom_config: Option<String>,
 
-    /// Earthquake parameters e.g. --earthquake 141.1 50.2 10.0 4.5 (Mw assumed).
+    /// GeoJSON tectonic zonation mapping polygons to config names (`config` property).
+    ///
+    /// The config of the zone containing the epicenter is used; events outside of all zones are
+    /// rejected. Mutually exclusive with `--use-config` and `--custom-config`.
+    #[arg(long, conflicts_with_all = ["use_config", "custom_config"])]
+    pub zonation: Option<String>,
+
+    /// Earthquake parameters e.g. --earthquake 141.1 50.2 10.0 4.5 (Mw unless
+    /// `--magnitude-type` is set).
     ///
     /// Requires `--in-file` to be set.
     #[arg(short, long, num_args = 4, value_names = ["lon", "lat", "depth", "magnitude"])]
     pub earthquake: Option<Vec<f64>>,
 
+    /// QuakeML or SeisComP XML (SCML) event file to read the earthquake from instead of
+    /// `--earthquake`.
+    ///
+    /// The preferred origin and magnitude of the first event are used. Files with the `.json` or
+    /// `.geojson` extension are read as USGS event GeoJSON, and `.fsp` files as USGS finite-fault
+    /// solutions whose segments give the rupture distances.
+    #[arg(long, requires = "in_file", conflicts_with = "earthquake")]
+    pub event_file: Option<String>,
+
+    /// USGS event identifier, e.g. `us7000abcd`, to fetch the earthquake from ComCat instead of
+    /// `--earthquake`.
+    ///
+    /// Requires the `comcat` feature and network access.
+    #[arg(long, requires = "in_file", conflicts_with_all = ["earthquake", "event_file"])]
+    pub event_id: Option<String>,
+
+    /// Magnitude type of `--earthquake`: `Mw`, `Ml`, `Ms`, `mb` or `Mjma`.
+    ///
+    /// Other types than Mw are converted to Mw with the default published relations.
+    #[arg(long, default_value = "Mw")]
+    pub magnitude_type: Magnitude,
+
+    /// Replace the tectonic part (`crustal`, `interplate` or `intraplate`) of the config name
+    /// with the regime selected from the focal depth, and the slab top of `--slab-model`.
+    ///
+    /// Events down to 25 km are crustal, down to 50 km interface and deeper ones intraslab.
+    #[arg(long, requires = "config_source")]
+    pub auto_tectonic: bool,
+
+    /// Tectonic regime used instead of the automatic selection: `crustal`, `interplate` or
+    /// `intraplate`.
+    ///
+    /// Implies `--auto-tectonic`.
+    #[arg(long, requires = "config_source")]
+    pub tectonic_type: Option<TectonicType>,
+
+    /// Slab-top geometry (Slab2-style `lon, lat, depth` rows delimited by `--delimeter`) used
+    /// by `--auto-tectonic`.
+    #[arg(long, requires = "auto_tectonic")]
+    pub slab_model: Option<String>,
+
     /// Output CSV file to write computed GMPE values.
     ///
+    /// Results on regular site grids are written as GeoTIFF rasters to files with the `.tif` or
+    /// `.tiff` extension (requires the `geotiff` feature), as GMT netCDF grids to files with the
+    /// `.grd` or `.nc` extension (requires the `netcdf` feature), and as Arc/Info ASCII grids to
+    /// files with the `.asc` extension. Files with the `.geojson` extension are written as GeoJSON,
+    /// files with the `.json` extension as a JSON document, files with the `.jsonl` or `.ndjson`
+    /// extension as JSON Lines, files with the `.kml` extension as colored KML placemarks, and
+    /// files with the `.kmz` extension as KMZ ground overlays (requires the `kmz` feature). `-`
+    /// writes CSV, GeoJSON, JSON or JSON Lines to standard output; progress messages always go to
+    /// standard error.
+    ///
+    /// CSV, GeoJSON, JSON and JSON Lines files with an additional `.gz` or `.zst` extension, e.g.
+    /// `pga.json.gz`, are compressed (requires the `gzip` or `zstd` feature).
+    ///
     /// Defaults to `out_gmpe_grid.txt`.
     #[arg(short, long, default_value = "out_gmpe_grid.txt")]
     pub out_file: String,
 
+    /// Format of the output file: `auto` (by the extension of `--out-file`), `csv`, `geojson`,
+    /// `json`, `jsonl` or `kml`.
+    #[arg(long, value_enum, default_value_t = OutFormat::Auto)]
+    pub out_format: OutFormat,
+
     /// Delimiter character for input and output CSV files.
     ///
     /// Defaults to tab (`'\t'`).
     #[arg(short, long, default_value = "\t")]
     pub delimeter: char,
 
+    /// Read the third column of `--in-file` as site classes (e.g. NEHRP `A`–`E`) instead of Vs30.
+    ///
+    /// Classes are converted to representative Vs30 values with the NEHRP defaults, or with
+    /// the given mapping file of `class<delimiter>vs30` rows.
+    #[arg(long, num_args = 0..=1, value_name = "MAPPING_FILE", requires = "in_file")]
+    pub site_classes: Option<Option<String>>,
+
+    /// Column layout of `--in-file`, e.g. `lon,lat,vs30,dl,xvf`, with `_` for skipped columns.
+    ///
+    /// By default columns are mapped by the names of a header row, if the file has one, or else
+    /// read in the fixed order `lon, lat, vs30, dl, xvf, z1p0, z2p5`.
+    #[arg(long, requires = "in_file", conflicts_with = "site_classes")]
+    pub columns: Option<Vs30Columns>,
+
+    /// Read the `xvf` column of `--in-file` as legacy binary volcanic front flags (`0`/`1`).
+    ///
+    /// The flags carry no distance to the front and are ignored. The file is read in the fixed
+    /// positional order unless `--columns` is given.
+    #[arg(long, requires = "in_file", conflicts_with = "site_classes")]
+    pub legacy_xvf_flags: bool,
+
+    /// Epicentral distance calculation method: `haversine` or `flat-earth`.
+    ///
+    /// `flat-earth` is faster and accurate enough for grids spanning less than 2–3 degrees.
+    #[arg(long, default_value_t = DistanceMethod::Haversine)]
+    pub distance_method: DistanceMethod,
+
+    /// Drop sites farther than this epicentral distance (km) before the computation, e.g.
+    /// `--max-radius 300`.
+    #[arg(long, value_name = "KM")]
+    pub max_radius: Option<f64>,
+
+    /// Drop sites outside of this bounding box (decimal degrees) before the computation.
+    #[arg(long, num_args = 4, value_names = ["min_lon", "min_lat", "max_lon", "max_lat"], allow_hyphen_values = true)]
+    pub bbox: Option<Vec<f64>>,
+
+    /// Maximum applicable distance (km) of the config, e.g. `--max-distance 300`, or `model`
+    /// for the documented distance limit of the model family of the config.
+    ///
+    /// Predictions at farther sites are replaced according to `--beyond-max-distance` and
+    /// counted as clipped. The distance is the first distance metric of the model (e.g. Rrup),
+    /// or the epicentral distance.
+    #[arg(long, value_name = "KM|model")]
+    pub max_distance: Option<MaxDistance>,
+
+    /// Value of predictions beyond `--max-distance`: `nan` or `zero`.
+    #[arg(long, default_value_t = CutoffMode::Nan, requires = "max_distance")]
+    pub beyond_max_distance: CutoffMode,
+
+    /// Shift predictions by this number of standard deviations of the model instead of
+    /// writing medians, e.g. `--epsilon 1.0`.
+    ///
+    /// Mutually exclusive with `--percentile`.
+    #[arg(long, allow_hyphen_values = true, conflicts_with = "percentile")]
+    pub epsilon: Option<f64>,
+
+    /// Write this percentile of the predictions instead of medians, e.g. `--percentile 84`.
+    ///
+    /// Mutually exclusive with `--epsilon`.
+    #[arg(long)]
+    pub percentile: Option<f64>,
+
+    /// Station observations (ShakeMap `stationlist.json` or delimited file) used to correct
+    /// the predictions for the bias of the event.
+    ///
+    /// The applied bias is written as metadata comment lines at the top of the output file.
+    #[arg(long, requires = "in_file")]
+    pub stations: Option<String>,
+
+    /// Intensity measure of the output grid: `config` or `mmi`.
+    ///
+    /// `mmi` converts the PGA, PGV or PSA output of the config to intensity.
+    #[arg(long, value_enum, default_value_t = Imt::Config)]
+    pub imt: Imt,
+
+    /// Ground-motion-to-intensity conversion used by `--imt mmi`: `worden2012` or `ak2007`.
+    #[arg(long, default_value_t = GmiceMethod::Worden2012)]
+    pub gmice: GmiceMethod,
+
+    /// Also write vertical-component estimates to this CSV file.
+    ///
+    /// The horizontal prediction is scaled with the Gülerce & Abrahamson (2011) V/H ratio using
+    /// the Vs30 of each site.
+    #[arg(long, requires = "in_file")]
+    pub vertical_out_file: Option<String>,
+
     /// List all available GMPE configurations.
     #[arg(short, long)]
     pub list_configs: bool,
@@ -60,3 +277,131 @@ pub struct CmdArgs {
     #[arg(short, long)]
     pub show_config: Option<String>,
 }
+
+/// Subcommands of the binary.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Write the attenuation curve (median value versus epicentral distance) of a config.
+    Attenuation(AttenuationArgs),
+    /// Write the response spectrum (PGA and PSA at all periods of a config family) of a site.
+    Spectrum(SpectrumArgs),
+    /// Write the scenario map of a config on a regular grid with a uniform Vs30.
+    Grid(GridArgs),
+}
+
+/// Arguments of the `attenuation` subcommand.
+#[derive(Args, Debug)]
+pub struct AttenuationArgs {
+    /// Predefined GMPE configuration name.
+    #[arg(long)]
+    pub config: String,
+
+    /// Moment magnitude of the earthquake.
+    #[arg(long)]
+    pub magnitude: f64,
+
+    /// Hypocentral depth (km).
+    #[arg(long)]
+    pub depth: f64,
+
+    /// Site Vs30 (m/s).
+    #[arg(long, default_value_t = 760.)]
+    pub vs30: f64,
+
+    /// Smallest epicentral distance (km).
+    #[arg(long, default_value_t = 1.)]
+    pub min_distance: f64,
+
+    /// Largest epicentral distance (km).
+    #[arg(long, default_value_t = 300.)]
+    pub max_distance: f64,
+
+    /// Number of logarithmically spaced distances.
+    #[arg(long, default_value_t = 100)]
+    pub n_distances: usize,
+
+    /// Output CSV file with distance and value columns.
+    #[arg(short, long, default_value = "out_attenuation.txt")]
+    pub out_file: String,
+
+    /// Delimiter character for the output CSV file.
+    #[arg(short, long, default_value = "\t")]
+    pub delimeter: char,
+}
+
+/// Arguments of the `spectrum` subcommand.
+#[derive(Args, Debug)]
+pub struct SpectrumArgs {
+    /// Config family, i.e. the config name without the type of motion suffix, e.g.
+    /// `config_mf2013_crustal` or `config_bssa2014`.
+    #[arg(long)]
+    pub family: String,
+
+    /// Earthquake parameters e.g. --earthquake 141.1 50.2 10.0 4.5 (Mw assumed).
+    #[arg(short, long, num_args = 4, value_names = ["lon", "lat", "depth", "magnitude"])]
+    pub earthquake: Vec<f64>,
+
+    /// Site location and Vs30 e.g. --site 141.3 50.4 350.
+    #[arg(long, num_args = 3, value_names = ["lon", "lat", "vs30"])]
+    pub site: Vec<f64>,
+
+    /// Output CSV file with period and value columns.
+    #[arg(short, long, default_value = "out_spectrum.txt")]
+    pub out_file: String,
+
+    /// Delimiter character for the output CSV file.
+    #[arg(short, long, default_value = "\t")]
+    pub delimeter: char,
+}
+
+/// Arguments of the `grid` subcommand.
+#[derive(Args, Debug)]
+pub struct GridArgs {
+    /// Predefined GMPE configuration name.
+    #[arg(long)]
+    pub config: String,
+
+    /// Earthquake parameters e.g. --earthquake 141.1 50.2 10.0 4.5 (Mw assumed).
+    #[arg(short, long, num_args = 4, value_names = ["lon", "lat", "depth", "magnitude"])]
+    pub earthquake: Vec<f64>,
+
+    /// Bounding box of the grid (decimal degrees).
+    #[arg(long, num_args = 4, value_names = ["min_lon", "min_lat", "max_lon", "max_lat"], allow_hyphen_values = true)]
+    pub bbox: Vec<f64>,
+
+    /// Grid spacing (decimal degrees).
+    #[arg(long, default_value_t = 0.1)]
+    pub spacing: f64,
+
+    /// Vs30 of all grid sites (m/s).
+    #[arg(long, default_value_t = 760.)]
+    pub vs30: f64,
+
+    /// Output CSV file to write computed GMPE values.
+    ///
+    /// Results on regular site grids are written as GeoTIFF rasters to files with the `.tif` or
+    /// `.tiff` extension (requires the `geotiff` feature), as GMT netCDF grids to files with the
+    /// `.grd` or `.nc` extension (requires the `netcdf` feature), and as Arc/Info ASCII grids to
+    /// files with the `.asc` extension. Files with the `.geojson` extension are written as GeoJSON,
+    /// files with the `.json` extension as a JSON document, files with the `.jsonl` or `.ndjson`
+    /// extension as JSON Lines, files with the `.kml` extension as colored KML placemarks, and
+    /// files with the `.kmz` extension as KMZ ground overlays (requires the `kmz` feature). `-`
+    /// writes CSV, GeoJSON, JSON or JSON Lines to standard output. Text outputs with an
+    /// additional `.gz` or `.zst` extension are compressed (requires the `gzip` or `zstd`
+    /// feature).
+    #[arg(short, long, default_value = "out_gmpe_grid.txt")]
+    pub out_file: String,
+
+    /// Format of the output file: `auto` (by the extension of `--out-file`), `csv`, `geojson`,
+    /// `json`, `jsonl` or `kml`.
+    #[arg(long, value_enum, default_value_t = OutFormat::Auto)]
+    pub out_format: OutFormat,
+
+    /// Also write the generated sites to this file, in the `--in-file` format.
+    #[arg(long)]
+    pub sites_out_file: Option<String>,
+
+    /// Delimiter character for the output CSV files.
+    #[arg(short, long, default_value = "\t")]
+    pub delimeter: char,
+}
@@ -1,28 +1,62 @@
 mod cmd_args;
 use clap::Parser;
-use ground_motion_lib::configs::get_mf2013_lib_configs;
-use ground_motion_lib::gmm::Earthquake;
-use ground_motion_lib::readers::read_vs30_points;
-use ground_motion_lib::vectorized::{calc_gmpe_vec, compute_stats};
-use ground_motion_lib::writers::write_gmpe_points;
+use ground_motion_lib::compression::Compression;
+use ground_motion_lib::conditioning::{EventBias, station_sites};
+use ground_motion_lib::configs::{get_lib_config, get_max_distance_km, list_lib_configs};
+use ground_motion_lib::cutoff::CutoffModel;
+use ground_motion_lib::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Magnitude, Vs30Point, generate_grid,
+};
+use ground_motion_lib::kml::{ColorScale, write_gmpe_kml};
+use ground_motion_lib::pipeline::{Epsilon, Pipeline};
+use ground_motion_lib::readers::{
+    read_esri_ascii_vs30_points, read_fsp, read_site_class_map, read_site_class_points,
+    read_site_class_points_from_reader, read_slab_model, read_stations, read_usgs_geojson_event,
+    read_vs30_points_from_reader_with, read_vs30_points_with, read_xml_event, read_zonation,
+};
+use ground_motion_lib::site_class::SiteClassMap;
+use ground_motion_lib::site_filter::SiteFilter;
+use ground_motion_lib::spectrum::calc_spectrum;
+use ground_motion_lib::tectonic::TectonicSelector;
+use ground_motion_lib::vectorized::{
+    attenuation_curve, calc_gmpe_vec, calc_gmpe_vec_with, compute_stats, log_distances,
+};
+use ground_motion_lib::vertical::GulerceAbrahamson2011;
+use ground_motion_lib::writers::{
+    write_attenuation_curve, write_gmpe_geojson, write_gmpe_geojson_to_writer, write_gmpe_json,
+    write_gmpe_json_to_writer, write_gmpe_jsonl, write_gmpe_jsonl_to_writer, write_gmpe_points,
+    write_gmpe_points_esri_ascii, write_gmpe_points_with_metadata,
+    write_gmpe_points_with_metadata_to_writer, write_spectrum, write_vs30_points,
+};
 
-use crate::cmd_args::CmdArgs;
+use crate::cmd_args::{
+    AttenuationArgs, CmdArgs, Command, GridArgs, Imt, MaxDistance, OutFormat, SpectrumArgs,
+};
 use std::error::Error;
+use std::io::{self, BufWriter};
+
+/// Input or output file name standing for standard input or standard output.
+const STDIO_PATH: &str = "-";
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cmd_args = CmdArgs::parse();
 
-    let configs = get_mf2013_lib_configs();
+    if let Some(command) = cmd_args.command {
+        return match command {
+            Command::Attenuation(args) => run_attenuation(&args),
+            Command::Spectrum(args) => run_spectrum(&args),
+            Command::Grid(args) => run_grid(&args),
+        };
+    }
 
     if cmd_args.list_configs {
-        let keys: Vec<_> = configs.keys().cloned().collect();
-        for key in keys {
+        for key in list_lib_configs() {
             println!("{}", key);
         }
     };
 
     if let Some(config_name) = cmd_args.show_config {
-        let conf = configs.get(config_name.as_str());
+        let conf = get_lib_config(config_name.as_str());
         match conf {
             None => {
                 println!("Config not found by name, use `--list-configs` to see avaliable keys.")
@@ -31,14 +65,115 @@ fn main() -> Result<(), Box<dyn Error>> {
         }
     };
 
-    if let (Some(ref vs_30_file), Some(ref config_name), Some(ref eq)) =
-        (cmd_args.in_file, cmd_args.use_config, cmd_args.earthquake)
-    {
-        println!("Use {vs_30_file} as input grid...");
+    let earthquake = match (
+        &cmd_args.earthquake,
+        &cmd_args.event_file,
+        &cmd_args.event_id,
+    ) {
+        (Some(eq), _, _) => Some(Earthquake::new(
+            eq[0],
+            eq[1],
+            eq[2],
+            eq[3],
+            cmd_args.magnitude_type,
+        )),
+        (None, Some(event_file), _) => {
+            eprintln!("Read earthquake from {event_file}...");
+            let extension = event_file
+                .rsplit_once('.')
+                .map(|(_, ext)| ext.to_ascii_lowercase());
+            match extension.as_deref() {
+                Some("json" | "geojson") => Some(read_usgs_geojson_event(event_file)?),
+                Some("fsp") => Some(read_fsp(event_file)?.to_earthquake()),
+                _ => Some(read_xml_event(event_file)?),
+            }
+        }
+        (None, None, Some(event_id)) => {
+            eprintln!("Fetch earthquake {event_id} from ComCat...");
+            Some(fetch_comcat_event(event_id)?)
+        }
+        (None, None, None) => None,
+    };
+
+    if let (Some(ref vs_30_file), Some(eq)) = (cmd_args.in_file, earthquake) {
+        eprintln!("Use {vs_30_file} as input grid...");
         let delim = cmd_args.delimeter as u8;
-        let vs30_grid = read_vs30_points(vs_30_file, delim)?;
+        let columns = match (cmd_args.columns, cmd_args.legacy_xvf_flags) {
+            (columns, false) => columns,
+            (columns, true) => Some(columns.unwrap_or_default().with_legacy_xvf_flags()),
+        };
+        let vs30_grid = match cmd_args.site_classes {
+            None if vs_30_file == STDIO_PATH => {
+                read_vs30_points_from_reader_with(io::stdin(), delim, columns.as_ref())?
+            }
+            None if vs_30_file.to_ascii_lowercase().ends_with(".asc") => {
+                read_esri_ascii_vs30_points(vs_30_file, None)?
+            }
+            None => read_vs30_points_with(vs_30_file, delim, columns.as_ref())?,
+            Some(ref mapping_file) => {
+                let classes = match mapping_file {
+                    Some(file) => read_site_class_map(file, delim)?,
+                    None => SiteClassMap::default(),
+                };
+                if vs_30_file == STDIO_PATH {
+                    read_site_class_points_from_reader(io::stdin(), delim, &classes)?
+                } else {
+                    read_site_class_points(vs_30_file, delim, &classes)?
+                }
+            }
+        };
+
+        eprintln!("Use Earthquake with parameters {eq:#?}");
+        if eq.magnitude_kind != Magnitude::Mw {
+            eprintln!("Converted to Mw {:.2}", eq.mw());
+        }
+
+        let vs30_grid = if cmd_args.max_radius.is_some() || cmd_args.bbox.is_some() {
+            let mut filter = SiteFilter::default().with_method(cmd_args.distance_method);
+            if let Some(max_radius) = cmd_args.max_radius {
+                filter = filter.with_max_radius(max_radius);
+            }
+            if let Some(ref bbox) = cmd_args.bbox {
+                filter = filter.with_bbox(bbox[0], bbox[1], bbox[2], bbox[3]);
+            }
+            let n_sites = vs30_grid.len();
+            let kept = filter.apply(vs30_grid, &eq);
+            eprintln!("Keep {} of {n_sites} sites...", kept.len());
+            kept
+        } else {
+            vs30_grid
+        };
+
+        let zone_config = match cmd_args.zonation {
+            Some(ref zonation_file) => {
+                let zonation = read_zonation(zonation_file)?;
+                let zone = zonation.zone_at(eq.lon, eq.lat);
+                let zone = zone.ok_or("Epicenter outside of all zones of the zonation")?;
+                eprintln!("Epicenter in zone `{}`", zone.name);
+                Some(zone.config.clone())
+            }
+            None => None,
+        };
+        let Some(ref config_name) = zone_config.or(cmd_args.use_config) else {
+            return Err("Custom configs are not implemented yet, use `--use-config`".into());
+        };
+
+        let config_name = if cmd_args.auto_tectonic || cmd_args.tectonic_type.is_some() {
+            let mut selector = TectonicSelector::default();
+            if let Some(ref slab_file) = cmd_args.slab_model {
+                selector = selector.with_slab(read_slab_model(slab_file, delim)?);
+            }
+            if let Some(tectonic_type) = cmd_args.tectonic_type {
+                selector = selector.with_override(tectonic_type);
+            }
+            let resolved = selector.config_key(config_name, &eq)?;
+            eprintln!("Selected {} regime: {resolved}", selector.select(&eq));
+            resolved
+        } else {
+            config_name.clone()
+        };
 
-        let conf = configs.get(config_name.as_str());
+        let conf = get_lib_config(config_name.as_str());
         let running_config = match conf {
             None => {
                 return Err(
@@ -46,24 +181,309 @@ fn main() -> Result<(), Box<dyn Error>> {
                 );
             }
             Some(cfg) => {
-                println!("Use config {cfg:#?}");
+                eprintln!("Use config {cfg:#?}");
                 cfg
             }
         };
+        let max_distance = match cmd_args.max_distance {
+            Some(MaxDistance::Km(max_distance)) => Some(max_distance),
+            Some(MaxDistance::Model) => Some(get_max_distance_km(&config_name).ok_or(
+                "This config has no documented maximum distance, set `--max-distance` in km.",
+            )?),
+            None => None,
+        };
+        let cutoff = max_distance.map(|max_distance| {
+            eprintln!(
+                "Set {} beyond {max_distance} km...",
+                cmd_args.beyond_max_distance
+            );
+            CutoffModel::new(running_config, max_distance).with_mode(cmd_args.beyond_max_distance)
+        });
+        let running_config: &(dyn GroundMotionModeling + Sync) = match cutoff {
+            Some(ref cutoff) => cutoff,
+            None => running_config,
+        };
 
-        let eq = Earthquake::new_mw(eq[0], eq[1], eq[2], eq[3]);
-        println!("Use Earthquake with parameters {eq:#?}");
+        let epsilon = match (cmd_args.epsilon, cmd_args.percentile) {
+            (Some(epsilon), _) => Some(Epsilon { epsilon }),
+            (None, Some(percentile)) if percentile > 0. && percentile < 100. => {
+                Some(Epsilon::from_percentile(percentile))
+            }
+            (None, Some(_)) => return Err("Percentile must be within (0, 100).".into()),
+            (None, None) => None,
+        };
 
-        let out_grid = calc_gmpe_vec(&vs30_grid, running_config, &eq);
+        let mut gm_grid =
+            calc_gmpe_vec_with(&vs30_grid, running_config, &eq, cmd_args.distance_method);
+        if let Some(ref cutoff) = cutoff {
+            eprintln!(
+                "Clipped {} predictions beyond {} km",
+                cutoff.clipped(),
+                cutoff.max_distance_km
+            );
+        }
+        let mut metadata = Vec::new();
+        if let Some(ref stations_file) = cmd_args.stations {
+            eprintln!("Use {stations_file} as station observations...");
+            let stations = read_stations(stations_file, delim)?;
+            let sites = station_sites(&stations, &vs30_grid);
+            let predicted =
+                calc_gmpe_vec_with(&sites, running_config, &eq, cmd_args.distance_method);
+            let observed: Vec<_> = stations.iter().map(|s| s.to_gmpe_point()).collect();
+            let bias = EventBias::estimate(&predicted, &observed)?;
+            eprintln!(
+                "Correct predictions for the event bias {:.3} ± {:.3} from {} observations...",
+                bias.bias, bias.sigma, bias.n_observations
+            );
+            metadata = bias.metadata();
+            gm_grid = Pipeline::new().then(bias).run(gm_grid);
+        }
+        if let Some(epsilon) = epsilon {
+            eprintln!(
+                "Shift predictions by {} standard deviations...",
+                epsilon.epsilon
+            );
+            gm_grid = Pipeline::new().then(epsilon).run(gm_grid);
+            if gm_grid.is_empty() && !vs30_grid.is_empty() {
+                return Err("This config provides no standard deviation.".into());
+            }
+        }
+        let out_grid = match cmd_args.imt {
+            Imt::Config => gm_grid,
+            Imt::Mmi => {
+                eprintln!("Convert to intensity with {}...", cmd_args.gmice);
+                let mmi_grid = cmd_args.gmice.to_mmi(gm_grid, &eq);
+                if mmi_grid.is_empty() && !vs30_grid.is_empty() {
+                    return Err(
+                        "The type of motion of this config cannot be converted to intensity."
+                            .into(),
+                    );
+                }
+                mmi_grid
+            }
+        };
         let grid_stat = compute_stats(&out_grid);
-        println!("Stats for out grid:");
-        println!("{grid_stat:#?}");
+        eprintln!("Stats for out grid:");
+        eprintln!("{grid_stat:#?}");
 
         let out_file = &cmd_args.out_file;
-        println!("Write gmpe points to {out_file}...");
-        write_gmpe_points(out_file, delim, &out_grid)?;
-        println!("Done");
+        eprintln!("Write gmpe points to {out_file}...");
+        write_out_grid(out_file, cmd_args.out_format, delim, &out_grid, &metadata)?;
+
+        if let Some(ref vertical_file) = cmd_args.vertical_out_file {
+            if cmd_args.imt != Imt::Config {
+                return Err("Vertical-component output requires `--imt config`.".into());
+            }
+            let vh = GulerceAbrahamson2011::new(&eq);
+            let vertical_grid = vh.vertical_vec(&vs30_grid, &out_grid);
+            if vertical_grid.is_empty() && !out_grid.is_empty() {
+                return Err("No V/H ratio available for the type of motion of this config.".into());
+            }
+            eprintln!("Write vertical gmpe points to {vertical_file}...");
+            write_gmpe_points(vertical_file, delim, &vertical_grid)?;
+        }
+        eprintln!("Done");
+    };
+
+    Ok(())
+}
+
+/// Write the attenuation curve of a config.
+fn run_attenuation(args: &AttenuationArgs) -> Result<(), Box<dyn Error>> {
+    let Some(config) = get_lib_config(&args.config) else {
+        return Err("Config not found by name, use `--list-configs` to see avaliable keys.".into());
     };
+    if args.min_distance <= 0. || args.min_distance > args.max_distance || args.n_distances < 2 {
+        return Err(
+            "Distances must satisfy 0 < --min-distance <= --max-distance with --n-distances >= 2."
+                .into(),
+        );
+    }
+    let distances = log_distances(args.min_distance, args.max_distance, args.n_distances);
+    let curve = attenuation_curve(config, args.magnitude, args.depth, args.vs30, &distances);
+    eprintln!("Write attenuation curve to {}...", args.out_file);
+    write_attenuation_curve(&args.out_file, args.delimeter as u8, &curve)?;
+    eprintln!("Done");
+    Ok(())
+}
 
+/// Write the response spectrum of a site for a config family.
+fn run_spectrum(args: &SpectrumArgs) -> Result<(), Box<dyn Error>> {
+    let eq = &args.earthquake;
+    let eq = Earthquake::new_mw(eq[0], eq[1], eq[2], eq[3]);
+    let site = Vs30Point::new(args.site[0], args.site[1], args.site[2], None, None);
+    let spectrum = calc_spectrum(&site, &eq, &args.family)?;
+    let mut points = spectrum.to_points();
+    points.retain(|point| point.kind != GmpePointKind::Pgv);
+    eprintln!(
+        "Write spectrum of {} periods to {}...",
+        points.len(),
+        args.out_file
+    );
+    write_spectrum(&args.out_file, args.delimeter as u8, &points)?;
+    eprintln!("Done");
     Ok(())
 }
+
+/// Write the scenario map of a config on a regular grid.
+fn run_grid(args: &GridArgs) -> Result<(), Box<dyn Error>> {
+    let Some(config) = get_lib_config(&args.config) else {
+        return Err("Config not found by name, use `--list-configs` to see avaliable keys.".into());
+    };
+    if args.spacing <= 0. {
+        return Err("Grid spacing must be positive.".into());
+    }
+    let bbox = [args.bbox[0], args.bbox[1], args.bbox[2], args.bbox[3]];
+    let sites = generate_grid(bbox, args.spacing, args.vs30);
+    if sites.is_empty() {
+        return Err(
+            "The bounding box must satisfy min_lon <= max_lon and min_lat <= max_lat.".into(),
+        );
+    }
+    let eq = &args.earthquake;
+    let eq = Earthquake::new_mw(eq[0], eq[1], eq[2], eq[3]);
+    if let Some(ref sites_file) = args.sites_out_file {
+        eprintln!("Write {} grid sites to {sites_file}...", sites.len());
+        write_vs30_points(sites_file, args.delimeter as u8, &sites)?;
+    }
+    let gm_grid = calc_gmpe_vec(&sites, config, &eq);
+    eprintln!("Stats for out grid:");
+    eprintln!("{:#?}", compute_stats(&gm_grid));
+    eprintln!("Write gmpe points to {}...", args.out_file);
+    write_out_grid(
+        &args.out_file,
+        args.out_format,
+        args.delimeter as u8,
+        &gm_grid,
+        &[],
+    )?;
+    eprintln!("Done");
+    Ok(())
+}
+
+/// Write a prediction grid in the given format, or for [`OutFormat::Auto`] in the format given by
+/// the extension of the output file: GeoTIFF (`.tif`, `.tiff`), GMT netCDF grid (`.grd`, `.nc`),
+/// Arc/Info ASCII grid (`.asc`), GeoJSON (`.geojson`), JSON (`.json`), JSON Lines (`.jsonl`,
+/// `.ndjson`), KML (`.kml`), KMZ (`.kmz`) or delimited text with metadata lines.
+///
+/// Text outputs with an additional `.gz` or `.zst` extension are compressed (requires the `gzip`
+/// or `zstd` feature), e.g. `pga.json.gz`.
+///
+/// The path [`STDIO_PATH`] writes to standard output, see [`write_stdout_grid`].
+fn write_out_grid(
+    path: &str,
+    format: OutFormat,
+    delim: u8,
+    grid: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    if path == STDIO_PATH {
+        return write_stdout_grid(format, delim, grid, metadata);
+    }
+    let compression = Compression::from_path(path);
+    let extension = compression
+        .strip_extension(path)
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    let text_output = !matches!(
+        (format, extension.as_deref()),
+        (OutFormat::Kml, _)
+            | (
+                OutFormat::Auto,
+                Some("kml" | "kmz" | "tif" | "tiff" | "grd" | "nc" | "asc")
+            )
+    );
+    if compression != Compression::None && !text_output {
+        return Err("Only CSV, GeoJSON, JSON and JSON Lines outputs can be compressed.".into());
+    }
+    match (format, extension.as_deref()) {
+        (OutFormat::Csv, _) => write_gmpe_points_with_metadata(path, delim, grid, metadata),
+        (OutFormat::Geojson, _) | (OutFormat::Auto, Some("geojson")) => {
+            write_gmpe_geojson(path, grid)
+        }
+        (OutFormat::Json, _) | (OutFormat::Auto, Some("json")) => {
+            write_gmpe_json(path, grid, metadata)
+        }
+        (OutFormat::Jsonl, _) | (OutFormat::Auto, Some("jsonl" | "ndjson")) => {
+            write_gmpe_jsonl(path, grid)
+        }
+        (OutFormat::Kml, _) | (OutFormat::Auto, Some("kml")) => {
+            write_gmpe_kml(path, grid, &ColorScale::from_points(grid))
+        }
+        (OutFormat::Auto, Some("kmz")) => write_kmz_points(path, grid),
+        (OutFormat::Auto, Some("tif" | "tiff")) => write_geotiff_points(path, grid),
+        (OutFormat::Auto, Some("grd" | "nc")) => write_gmt_grid_points(path, grid),
+        (OutFormat::Auto, Some("asc")) => write_gmpe_points_esri_ascii(path, grid),
+        _ => write_gmpe_points_with_metadata(path, delim, grid, metadata),
+    }
+}
+
+/// Write a prediction grid to standard output in one of the text formats that can be streamed:
+/// delimited text with metadata lines (also for [`OutFormat::Auto`]), GeoJSON, JSON or JSON
+/// Lines.
+fn write_stdout_grid(
+    format: OutFormat,
+    delim: u8,
+    grid: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let stdout = BufWriter::new(io::stdout().lock());
+    match format {
+        OutFormat::Auto | OutFormat::Csv => {
+            write_gmpe_points_with_metadata_to_writer(stdout, delim, grid, metadata)
+        }
+        OutFormat::Geojson => write_gmpe_geojson_to_writer(stdout, grid),
+        OutFormat::Json => write_gmpe_json_to_writer(stdout, grid, metadata),
+        OutFormat::Jsonl => write_gmpe_jsonl_to_writer(stdout, grid),
+        OutFormat::Kml => Err("KML output cannot be written to standard output.".into()),
+    }
+}
+
+/// Write a prediction grid as a GeoTIFF raster.
+#[cfg(feature = "geotiff")]
+fn write_geotiff_points(path: &str, grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    ground_motion_lib::writers::write_gmpe_points_geotiff(path, grid)
+}
+
+/// Write a prediction grid as a GeoTIFF raster.
+#[cfg(not(feature = "geotiff"))]
+fn write_geotiff_points(_path: &str, _grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    Err("GeoTIFF output requires building with the `geotiff` feature.".into())
+}
+
+/// Fetch the origin and magnitude of a USGS event from ComCat.
+#[cfg(feature = "comcat")]
+fn fetch_comcat_event(event_id: &str) -> Result<Earthquake, Box<dyn Error>> {
+    ground_motion_lib::comcat::fetch_usgs_event(event_id)
+}
+
+/// Fetch the origin and magnitude of a USGS event from ComCat.
+#[cfg(not(feature = "comcat"))]
+fn fetch_comcat_event(_event_id: &str) -> Result<Earthquake, Box<dyn Error>> {
+    Err("Fetching events from ComCat requires building with the `comcat` feature.".into())
+}
+
+/// Write a prediction grid as a KMZ archive.
+#[cfg(feature = "kmz")]
+fn write_kmz_points(path: &str, grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    let scale = ColorScale::from_points(grid);
+    ground_motion_lib::kml::write_gmpe_kmz(path, grid, &scale)
+}
+
+/// Write a prediction grid as a KMZ archive.
+#[cfg(not(feature = "kmz"))]
+fn write_kmz_points(_path: &str, _grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    Err("KMZ output requires building with the `kmz` feature.".into())
+}
+
+/// Write a prediction grid as a GMT netCDF grid.
+#[cfg(feature = "netcdf")]
+fn write_gmt_grid_points(path: &str, grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    ground_motion_lib::writers::write_gmpe_points_gmt_grid(path, grid)
+}
+
+/// Write a prediction grid as a GMT netCDF grid.
+#[cfg(not(feature = "netcdf"))]
+fn write_gmt_grid_points(_path: &str, _grid: &[GmpePoint]) -> Result<(), Box<dyn Error>> {
+    Err("GMT netCDF grid output requires building with the `netcdf` feature.".into())
+}
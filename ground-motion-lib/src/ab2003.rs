@@ -0,0 +1,412 @@
+//! Implementation of Atkinson & Boore (2003) Ground Motion Prediction Equations (GMPE) for
+//! subduction zone earthquakes.
+//!
+//! The model covers interface (interplate) and in-slab (intraplate) events:
+//!
+//! ```text
+//! log10(Y) = c1 + c2·M + c3·h + c4·R − g·log10(R) + c5·sl·S_C + c6·sl·S_D + c7·sl·S_E
+//! R = sqrt(D_fault² + Δ²),  Δ = 0.00724 · 10^(0.507·M)
+//! g = 10^(1.2 − 0.18·M)    (interface)
+//! g = 10^(0.301 − 0.01·M)  (in-slab)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in cm/s², `h` is the focal depth (km, capped at 100 km),
+//! and `S_C`, `S_D`, `S_E` are NEHRP site class flags selected from Vs30. The soil
+//! nonlinearity factor `sl` depends on the predicted rock PGA and the frequency of motion.
+//!
+//! Regional `c1` factors are available for Japan and Cascadia via [`Ab2003Region`]. For point
+//! sources the fault distance is approximated by the hypocentral distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+use std::f64::consts::LN_10;
+
+/// Maximum focal depth used by the depth term (km).
+const H_MAX: f64 = 100.;
+
+/// Regional variant of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ab2003Region {
+    /// Global coefficients
+    Global,
+    /// Japan-specific `c1` factors
+    Japan,
+    /// Cascadia-specific `c1` factors
+    Cascadia,
+}
+
+/// Period-dependent Atkinson & Boore (2003) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ab2003Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant term for the global, Japan and Cascadia variants
+    pub c1: [f64; 3],
+    /// Magnitude scaling coefficient
+    pub c2: f64,
+    /// Depth scaling coefficient
+    pub c3: f64,
+    /// Anelastic attenuation coefficient
+    pub c4: f64,
+    /// NEHRP C, D and E site class coefficients
+    pub c5: f64,
+    pub c6: f64,
+    pub c7: f64,
+    /// Total standard deviation of `log10(Y)`
+    pub sigma: f64,
+}
+
+/// Atkinson & Boore (2003) interface coefficients (PGA, PSA at 0.1, 0.2, 0.4, 1, 2 and 3 s).
+#[allow(clippy::approx_constant)]
+pub const AB2003_INTERFACE: [Ab2003Coeffs; 7] = [
+    Ab2003Coeffs {
+        period: 0.,
+        c1: [2.991, 3.14, 2.79],
+        c2: 0.03525,
+        c3: 0.00759,
+        c4: -0.00206,
+        c5: 0.19,
+        c6: 0.24,
+        c7: 0.29,
+        sigma: 0.23,
+    },
+    Ab2003Coeffs {
+        period: 0.1,
+        c1: [2.7789, 2.95, 2.5],
+        c2: 0.09841,
+        c3: 0.00974,
+        c4: -0.00287,
+        c5: 0.15,
+        c6: 0.23,
+        c7: 0.2,
+        sigma: 0.27,
+    },
+    Ab2003Coeffs {
+        period: 0.2,
+        c1: [2.6638, 2.84, 2.54],
+        c2: 0.12386,
+        c3: 0.00884,
+        c4: -0.0028,
+        c5: 0.15,
+        c6: 0.27,
+        c7: 0.25,
+        sigma: 0.28,
+    },
+    Ab2003Coeffs {
+        period: 0.4,
+        c1: [2.5249, 2.58, 2.5],
+        c2: 0.1477,
+        c3: 0.00728,
+        c4: -0.00235,
+        c5: 0.13,
+        c6: 0.37,
+        c7: 0.38,
+        sigma: 0.29,
+    },
+    Ab2003Coeffs {
+        period: 1.0,
+        c1: [2.1442, 2.18, 2.18],
+        c2: 0.1345,
+        c3: 0.00521,
+        c4: -0.0011,
+        c5: 0.1,
+        c6: 0.3,
+        c7: 0.55,
+        sigma: 0.34,
+    },
+    Ab2003Coeffs {
+        period: 2.0,
+        c1: [2.1907, 2.14, 2.33],
+        c2: 0.07148,
+        c3: 0.00224,
+        c4: 0.,
+        c5: 0.1,
+        c6: 0.25,
+        c7: 0.4,
+        sigma: 0.34,
+    },
+    Ab2003Coeffs {
+        period: 3.0,
+        c1: [2.301, 2.27, 2.36],
+        c2: 0.02237,
+        c3: 0.00012,
+        c4: 0.,
+        c5: 0.1,
+        c6: 0.25,
+        c7: 0.36,
+        sigma: 0.36,
+    },
+];
+
+/// Atkinson & Boore (2003) in-slab coefficients (PGA, PSA at 0.1, 0.2, 0.4, 1, 2 and 3 s).
+pub const AB2003_INSLAB: [Ab2003Coeffs; 7] = [
+    Ab2003Coeffs {
+        period: 0.,
+        c1: [-0.04713, 0.1, -0.25],
+        c2: 0.6909,
+        c3: 0.0113,
+        c4: -0.00202,
+        c5: 0.19,
+        c6: 0.24,
+        c7: 0.29,
+        sigma: 0.27,
+    },
+    Ab2003Coeffs {
+        period: 0.1,
+        c1: [0.43928, 0.61, 0.16],
+        c2: 0.66675,
+        c3: 0.0108,
+        c4: -0.00219,
+        c5: 0.15,
+        c6: 0.23,
+        c7: 0.2,
+        sigma: 0.28,
+    },
+    Ab2003Coeffs {
+        period: 0.2,
+        c1: [0.51589, 0.7, 0.4],
+        c2: 0.69186,
+        c3: 0.00572,
+        c4: -0.00192,
+        c5: 0.15,
+        c6: 0.27,
+        c7: 0.25,
+        sigma: 0.28,
+    },
+    Ab2003Coeffs {
+        period: 0.4,
+        c1: [0.005445, 0.07, -0.01],
+        c2: 0.7727,
+        c3: 0.00173,
+        c4: -0.00178,
+        c5: 0.13,
+        c6: 0.37,
+        c7: 0.38,
+        sigma: 0.28,
+    },
+    Ab2003Coeffs {
+        period: 1.0,
+        c1: [-1.02133, -0.98, -0.98],
+        c2: 0.8789,
+        c3: 0.0013,
+        c4: -0.00173,
+        c5: 0.1,
+        c6: 0.3,
+        c7: 0.55,
+        sigma: 0.29,
+    },
+    Ab2003Coeffs {
+        period: 2.0,
+        c1: [-2.39234, -2.25, -2.25],
+        c2: 0.9964,
+        c3: 0.00364,
+        c4: -0.00118,
+        c5: 0.1,
+        c6: 0.25,
+        c7: 0.4,
+        sigma: 0.3,
+    },
+    Ab2003Coeffs {
+        period: 3.0,
+        c1: [-3.70012, -3.64, -3.64],
+        c2: 1.1169,
+        c3: 0.00615,
+        c4: -0.00045,
+        c5: 0.1,
+        c6: 0.25,
+        c7: 0.36,
+        sigma: 0.3,
+    },
+];
+
+/// Atkinson & Boore (2003) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct AB2003 {
+    /// Period-dependent coefficients
+    pub coeffs: Ab2003Coeffs,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Regional variant
+    pub region: Ab2003Region,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl AB2003 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: Ab2003Coeffs,
+        tectonic_type: TectonicType,
+        region: Ab2003Region,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "AB2003 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            tectonic_type,
+            region,
+            motion_kind,
+        }
+    }
+
+    /// PGA coefficients matching the tectonic type of the model.
+    fn pga_coeffs(&self) -> &'static Ab2003Coeffs {
+        match self.tectonic_type {
+            TectonicType::Intraplate => &AB2003_INSLAB[0],
+            _ => &AB2003_INTERFACE[0],
+        }
+    }
+
+    /// Median `log10(Y)` on NEHRP B rock (cm/s²) with the given coefficients.
+    fn log10_rock(&self, c: &Ab2003Coeffs, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let (m, g) = match self.tectonic_type {
+            TectonicType::Intraplate => {
+                let m = magnitude.min(8.);
+                (m, 10_f64.powf(0.301 - 0.01 * m))
+            }
+            _ => {
+                let m = magnitude.min(8.5);
+                (m, 10_f64.powf(1.2 - 0.18 * m))
+            }
+        };
+        let delta = 0.00724 * 10_f64.powf(0.507 * m);
+        let r = distance.hypot(delta);
+        let c1 = match self.region {
+            Ab2003Region::Global => c.c1[0],
+            Ab2003Region::Japan => c.c1[1],
+            Ab2003Region::Cascadia => c.c1[2],
+        };
+        c1 + c.c2 * m + c.c3 * depth.min(H_MAX) + c.c4 * r - g * r.log10()
+    }
+
+    /// Soil nonlinearity factor `sl` for the given rock PGA (cm/s²).
+    fn nonlinearity(&self, pga_rock: f64) -> f64 {
+        let period = self.coeffs.period;
+        let freq = if period > 0. {
+            1. / period
+        } else {
+            f64::INFINITY
+        };
+        let pga_factor = ((pga_rock - 100.) / 400.).clamp(0., 1.);
+        if freq <= 1. || pga_rock <= 100. {
+            1.
+        } else if freq < 2. {
+            1. - (freq - 1.) * pga_factor
+        } else {
+            1. - pga_factor
+        }
+    }
+
+    /// Median `log10(Y)` (cm/s²).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Fault distance (km).
+    /// * `depth` - Focal depth (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    pub fn log10_median(&self, magnitude: f64, distance: f64, depth: f64, vs30: f64) -> f64 {
+        let c = &self.coeffs;
+        let rock = self.log10_rock(c, magnitude, distance, depth);
+        let pga_rock = 10_f64.powf(self.log10_rock(self.pga_coeffs(), magnitude, distance, depth));
+        let sl = self.nonlinearity(pga_rock);
+        let site = match vs30 {
+            v if v > 760. => 0.,
+            v if v > 360. => c.c5,
+            v if v > 180. => c.c6,
+            _ => c.c7,
+        };
+        rock + sl * site
+    }
+}
+
+impl GroundMotionModeling for AB2003 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ground_motion = 10_f64.powf(self.log10_median(eq.mw(), distance, eq.depth, point.vs30));
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert cm/s² to %g
+            value: ((ground_motion / 100.) / G_GLOBAL) * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(tectonic_type: TectonicType, region: Ab2003Region) -> AB2003 {
+        let coeffs = match tectonic_type {
+            TectonicType::Intraplate => AB2003_INSLAB[0],
+            _ => AB2003_INTERFACE[0],
+        };
+        AB2003::new(coeffs, tectonic_type, region, GmpePointKind::Pga)
+    }
+
+    #[test]
+    fn test_regional_factor() {
+        let global = model(TectonicType::Interplate, Ab2003Region::Global);
+        let japan = model(TectonicType::Interplate, Ab2003Region::Japan);
+        let diff =
+            japan.log10_median(8., 100., 30., 800.) - global.log10_median(8., 100., 30., 800.);
+        let c1 = AB2003_INTERFACE[0].c1;
+        assert!((diff - (c1[1] - c1[0])).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_soil_nonlinearity() {
+        let m = model(TectonicType::Intraplate, Ab2003Region::Global);
+        // Weak motion: full linear amplification of class E
+        let far = m.log10_median(6., 300., 60., 150.) - m.log10_median(6., 300., 60., 800.);
+        assert!((far - 0.29).abs() < 1e-12);
+        // Strong motion: amplification is reduced
+        let near = m.log10_median(7.5, 60., 60., 150.) - m.log10_median(7.5, 60., 60., 800.);
+        assert!(near < far);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_crustal_not_supported() {
+        model(TectonicType::Crustal, Ab2003Region::Global);
+    }
+}
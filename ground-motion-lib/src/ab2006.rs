@@ -0,0 +1,200 @@
+//! Implementation of Atkinson & Boore (2006) Ground Motion Prediction Equations (GMPE) for
+//! eastern North America.
+//!
+//! The model predicts the median motion on hard rock (Vs ≥ 2000 m/s):
+//!
+//! ```text
+//! log10(Y) = c1 + c2·M + c3·M² + (c4 + c5·M)·f1 + (c6 + c7·M)·f2 + (c8 + c9·M)·f0 + c10·R
+//! f0 = max(log10(10 / R), 0)
+//! f1 = min(log10(R), log10(70))
+//! f2 = max(log10(R / 140), 0)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in cm/s² and `R` is the rupture distance (km).
+//!
+//! The published coefficients correspond to a stress drop of 140 bars. Other stress drops are
+//! modeled with the stress adjustment factor of the model:
+//!
+//! ```text
+//! log10(SF) = Δ · clamp((M − M1) / (Mh − M1), 0, 1),   Δ = delta · log10(stress / 140)
+//! ```
+//!
+//! Coefficients are shipped as a text table ([`AB2006_TABLE`]) and parsed with [`CoeffsTable`].
+//!
+//! Current simplifications:
+//!
+//! - Hard-rock reference site; the site Vs30 is not used.
+//! - For point sources the rupture distance is approximated by the hypocentral distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use std::f64::consts::LN_10;
+
+/// Stress drop of the published coefficients (bars).
+pub const REFERENCE_STRESS: f64 = 140.;
+
+/// Atkinson & Boore (2006) hard-rock coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 2 s) with
+/// the stress adjustment parameters `delta`, `m1` and `mh`.
+pub const AB2006_TABLE: &str = "
+    imt   c1       c2     c3      c4     c5    c6     c7    c8    c9    c10      delta  m1    mh    sigma
+    pga   0.5534   0.9    -0.05   -2.3   0.15  -0.5   0.0   0.2   0.0   -0.0021  0.15   5.0   5.5   0.27
+    0.1   1.2975   0.85   -0.05   -2.4   0.15  -0.5   0.0   0.2   0.0   -0.0028  0.15   5.0   5.5   0.28
+    0.2   0.3644   0.95   -0.05   -2.25  0.15  -0.5   0.0   0.2   0.0   -0.0019  0.15   5.0   5.5   0.28
+    0.5   -1.6738  1.25   -0.06   -2.05  0.14  -0.5   0.0   0.2   0.0   -0.0011  0.2    5.5   6.5   0.29
+    1.0   -3.3608  1.55   -0.075  -1.9   0.12  -0.5   0.0   0.2   0.0   -0.0007  0.25   5.5   7.0   0.3
+    2.0   -5.1058  1.85   -0.09   -1.75  0.1   -0.5   0.0   0.2   0.0   -0.0004  0.33   5.5   7.5   0.31
+";
+
+/// Atkinson & Boore (2006) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AB2006 {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Coefficients `c1`–`c10`
+    pub c: [f64; 10],
+    /// Stress adjustment scaling
+    pub delta: f64,
+    /// Magnitudes bounding the ramp of the stress adjustment
+    pub m1: f64,
+    pub mh: f64,
+    /// Standard deviation of `log10(Y)`
+    pub sigma: f64,
+    /// Stress drop of the modeled events (bars)
+    pub stress: f64,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl AB2006 {
+    /// Build the model for the reference stress drop from a row of an AB06 coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        let names = ["c1", "c2", "c3", "c4", "c5", "c6", "c7", "c8", "c9", "c10"];
+        Self {
+            period: row.period,
+            c: names.map(|name| row.coeff(name)),
+            delta: row.coeff("delta"),
+            m1: row.coeff("m1"),
+            mh: row.coeff("mh"),
+            sigma: row.coeff("sigma"),
+            stress: REFERENCE_STRESS,
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// All models of the shipped coefficient table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(AB2006_TABLE)
+            .expect("Invalid Atkinson & Boore (2006) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Set the stress drop of the modeled events (bars).
+    pub fn with_stress(mut self, stress: f64) -> Self {
+        self.stress = stress;
+        self
+    }
+
+    /// Stress adjustment factor `log10(SF)` for the given magnitude.
+    pub fn stress_factor(&self, magnitude: f64) -> f64 {
+        let delta = self.delta * (self.stress / REFERENCE_STRESS).log10();
+        delta * ((magnitude - self.m1) / (self.mh - self.m1)).clamp(0., 1.)
+    }
+
+    /// Median `log10(Y)` on hard rock (cm/s²).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    pub fn log10_median(&self, magnitude: f64, rrup: f64) -> f64 {
+        let [c1, c2, c3, c4, c5, c6, c7, c8, c9, c10] = self.c;
+        let m = magnitude;
+        let r = rrup.max(1.);
+        let f0 = (10. / r).log10().max(0.);
+        let f1 = r.log10().min(70_f64.log10());
+        let f2 = (r / 140.).log10().max(0.);
+        c1 + c2 * m
+            + c3 * m.powi(2)
+            + (c4 + c5 * m) * f1
+            + (c6 + c7 * m) * f2
+            + (c8 + c9 * m) * f0
+            + c10 * r
+            + self.stress_factor(m)
+    }
+}
+
+impl GroundMotionModeling for AB2006 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rrup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ground_motion = 10_f64.powf(self.log10_median(eq.mw(), rrup));
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert cm/s² to %g
+            value: ((ground_motion / 100.) / G_GLOBAL) * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_stress_has_no_adjustment() {
+        let pga = AB2006::from_table()[0];
+        assert_eq!(pga.stress_factor(7.), 0.);
+        assert_eq!(
+            pga.with_stress(REFERENCE_STRESS).log10_median(6., 20.),
+            pga.log10_median(6., 20.)
+        );
+    }
+
+    #[test]
+    fn test_stress_adjustment() {
+        let psa = AB2006::from_table()[4].with_stress(280.);
+        // Full adjustment above Mh, none below M1
+        let full = psa.delta * 2_f64.log10();
+        assert!((psa.stress_factor(7.5) - full).abs() < 1e-12);
+        assert_eq!(psa.stress_factor(5.), 0.);
+        assert!((psa.stress_factor(6.25) - 0.5 * full).abs() < 1e-12);
+        let low = AB2006::from_table()[4].with_stress(70.);
+        assert!(low.log10_median(7., 50.) < psa.log10_median(7., 50.));
+    }
+}
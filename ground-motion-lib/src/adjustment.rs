@@ -0,0 +1,402 @@
+//! # Host-to-Target Region Adjustments
+//!
+//! This module provides a simple adjustment layer for regional recalibration of imported GMPEs
+//! without editing their coefficients. Adjustments are defined per intensity measure type (IMT)
+//! as a multiplicative and an additive factor applied in natural-log space:
+//!
+//! ```text
+//! ln(y') = log_scale · ln(y) + log_shift
+//! ```
+//!
+//! A [`RegionAdjustment`] can be stacked on top of any [`GroundMotionModeling`] implementation
+//! with [`AdjustedModel`], and can be loaded from a TOML file.
+//!
+//! ## Backbone Branches
+//!
+//! Epistemic uncertainty of the median of a model is often represented by a backbone: the
+//! model itself (central branch) and copies scaled up and down (upper and lower branches).
+//! [`Scaling`] applies the same additive log-space shift and multiplicative factor to all
+//! predictions of a model, whatever the type of motion, and [`ScaledModel`] stacks it on top of
+//! the model:
+//!
+//! ```text
+//! y' = factor · exp(log_shift) · y
+//! ```
+//!
+//! Intensities are scaled linearly instead: `y' = factor · y + log_shift`. [`backbone_branches`]
+//! builds the three branches of a backbone with the Keefer & Bodily (1983) three-point
+//! approximation: shifts of `∓1.645 · σ_μ` and `0` with weights `0.185`, `0.63` and `0.185`.
+//!
+//! ## Example Config (TOML)
+//!
+//! ```toml
+//! name = "Japan to Sakhalin"
+//!
+//! [[imt]]
+//! kind = "Pga"
+//! log_shift = -0.15
+//!
+//! [[imt]]
+//! kind = "Pgv"
+//! log_scale = 0.95
+//! log_shift = 0.1
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::adjustment::{AdjustedModel, RegionAdjustment};
+//! use ground_motion_lib::configs::get_mf2013_lib_configs;
+//! use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+//! use ground_motion_lib::vectorized::calc_gmpe_vec;
+//!
+//! let adjustment = RegionAdjustment::from_toml_str(r#"
+//!     [[imt]]
+//!     kind = "Pga"
+//!     log_shift = -0.15
+//! "#).unwrap();
+//!
+//! let base = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+//! let adjusted = AdjustedModel::new(base, adjustment);
+//!
+//! let points = vec![Vs30Point::new(142.5, 50.0, 400., Some(200.), None)];
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let results = calc_gmpe_vec(&points, &adjusted, &eq);
+//! println!("{results:?}");
+//! ```
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+fn default_log_scale() -> f64 {
+    1.
+}
+
+/// Quantile of the standard normal distribution of the outer backbone branches (5% and 95%).
+const BACKBONE_QUANTILE: f64 = 1.645;
+
+/// Weights of the lower, central and upper backbone branches (Keefer & Bodily, 1983).
+pub const BACKBONE_WEIGHTS: [f64; 3] = [0.185, 0.63, 0.185];
+
+/// Log-space adjustment factors for a single intensity measure type.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ImtAdjustment {
+    /// Intensity measure type the adjustment applies to.
+    pub kind: GmpePointKind,
+    /// Multiplicative factor applied to `ln(y)`. Defaults to `1.0`.
+    #[serde(default = "default_log_scale")]
+    pub log_scale: f64,
+    /// Additive term applied to `ln(y)`. Defaults to `0.0`.
+    #[serde(default)]
+    pub log_shift: f64,
+}
+
+impl ImtAdjustment {
+    /// Create a new adjustment for the given intensity measure type.
+    pub fn new(kind: GmpePointKind, log_scale: f64, log_shift: f64) -> Self {
+        Self {
+            kind,
+            log_scale,
+            log_shift,
+        }
+    }
+
+    /// Apply the adjustment to a value in physical units.
+    pub fn apply(&self, value: f64) -> f64 {
+        (self.log_scale * value.ln() + self.log_shift).exp()
+    }
+}
+
+/// Set of per-IMT adjustments describing a host-to-target region recalibration.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RegionAdjustment {
+    /// Optional human-readable name of the adjustment.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Per-IMT adjustment factors. IMTs not listed here are left unchanged.
+    #[serde(default)]
+    pub imt: Vec<ImtAdjustment>,
+}
+
+impl RegionAdjustment {
+    /// Create a new region adjustment from a list of per-IMT factors.
+    pub fn new(imt: Vec<ImtAdjustment>) -> Self {
+        Self { name: None, imt }
+    }
+
+    /// Parse a region adjustment from a TOML string.
+    pub fn from_toml_str(content: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Load a region adjustment from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid adjustment config.
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        Self::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// Find the adjustment for the given intensity measure type.
+    pub fn for_kind(&self, kind: GmpePointKind) -> Option<&ImtAdjustment> {
+        self.imt.iter().find(|adj| adj.kind == kind)
+    }
+
+    /// Apply the matching adjustment to a computed point, if any.
+    pub fn apply(&self, mut point: GmpePoint) -> GmpePoint {
+        if let Some(adj) = self.for_kind(point.kind) {
+            point.value = adj.apply(point.value);
+            // ln(y) is scaled by `log_scale`, and so is its standard deviation
+            point.sigma = point.sigma.map(|sigma| sigma * adj.log_scale.abs());
+        }
+        point
+    }
+}
+
+/// A ground motion model with a [`RegionAdjustment`] stacked on top.
+#[derive(Debug)]
+pub struct AdjustedModel<M> {
+    /// Underlying (host region) model.
+    pub model: M,
+    /// Host-to-target adjustment.
+    pub adjustment: RegionAdjustment,
+}
+
+impl<M: GroundMotionModeling> AdjustedModel<M> {
+    /// Stack an adjustment on top of a model.
+    pub fn new(model: M, adjustment: RegionAdjustment) -> Self {
+        Self { model, adjustment }
+    }
+}
+
+impl<M: GroundMotionModeling> GroundMotionModeling for AdjustedModel<M> {
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.adjustment.apply(
+            self.model
+                .calc_from_distance(point, eq, epicentral_distance),
+        )
+    }
+
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        self.model.distance_metrics()
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        self.adjustment
+            .apply(self.model.calc_from_distances(point, eq, distances))
+    }
+}
+
+/// Scaling of all predictions of a model, regardless of the type of motion.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Scaling {
+    /// Additive term applied to `ln(y)`. Defaults to `0.0`.
+    #[serde(default)]
+    pub log_shift: f64,
+    /// Multiplicative factor applied to `y`. Defaults to `1.0`.
+    #[serde(default = "default_log_scale")]
+    pub factor: f64,
+}
+
+impl Default for Scaling {
+    fn default() -> Self {
+        Self::new(0., 1.)
+    }
+}
+
+impl Scaling {
+    /// Create a new scaling.
+    pub fn new(log_shift: f64, factor: f64) -> Self {
+        Self { log_shift, factor }
+    }
+
+    /// Apply the scaling to a computed point. The standard deviation is left unchanged.
+    pub fn apply(&self, mut point: GmpePoint) -> GmpePoint {
+        if point.kind.is_log_normal() {
+            point.value *= self.factor * self.log_shift.exp();
+        } else {
+            point.value = self.factor * point.value + self.log_shift;
+        }
+        point
+    }
+}
+
+/// A ground motion model with a [`Scaling`] stacked on top.
+#[derive(Debug, Clone)]
+pub struct ScaledModel<M> {
+    /// Underlying model.
+    pub model: M,
+    /// Scaling of the predictions.
+    pub scaling: Scaling,
+}
+
+impl<M: GroundMotionModeling> ScaledModel<M> {
+    /// Stack a scaling on top of a model.
+    pub fn new(model: M, scaling: Scaling) -> Self {
+        Self { model, scaling }
+    }
+}
+
+impl<M: GroundMotionModeling> GroundMotionModeling for ScaledModel<M> {
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.scaling.apply(
+            self.model
+                .calc_from_distance(point, eq, epicentral_distance),
+        )
+    }
+
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        self.model.distance_metrics()
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        self.scaling
+            .apply(self.model.calc_from_distances(point, eq, distances))
+    }
+}
+
+/// Lower, central and upper backbone branches of a model with their weights.
+///
+/// # Arguments
+///
+/// * `model` - Central model.
+/// * `sigma_mu` - Standard deviation of the epistemic uncertainty of `ln(y)`.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::adjustment::backbone_branches;
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::ensemble::EnsembleModel;
+/// use ground_motion_lib::gmm::GroundMotionModeling;
+///
+/// let base = get_lib_config("config_mf2013_crustal_pga").unwrap();
+/// let members = backbone_branches(base, 0.3)
+///     .into_iter()
+///     .map(|(model, weight)| {
+///         let model: Box<dyn GroundMotionModeling + Send + Sync> = Box::new(model);
+///         (model, weight)
+///     })
+///     .collect();
+/// let ensemble = EnsembleModel::new(members);
+/// assert_eq!(ensemble.members.len(), 3);
+/// ```
+pub fn backbone_branches<M: GroundMotionModeling + Clone>(
+    model: M,
+    sigma_mu: f64,
+) -> [(ScaledModel<M>, f64); 3] {
+    let shift = BACKBONE_QUANTILE * sigma_mu;
+    [
+        (
+            ScaledModel::new(model.clone(), Scaling::new(-shift, 1.)),
+            BACKBONE_WEIGHTS[0],
+        ),
+        (
+            ScaledModel::new(model.clone(), Scaling::default()),
+            BACKBONE_WEIGHTS[1],
+        ),
+        (
+            ScaledModel::new(model, Scaling::new(shift, 1.)),
+            BACKBONE_WEIGHTS[2],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_mf2013_lib_configs;
+
+    #[test]
+    fn test_parse_toml_defaults() {
+        let adj = RegionAdjustment::from_toml_str(
+            r#"
+            name = "test"
+            [[imt]]
+            kind = "Pga"
+            log_shift = 0.5
+            "#,
+        )
+        .unwrap();
+        assert_eq!(adj.name.as_deref(), Some("test"));
+        assert_eq!(adj.imt[0], ImtAdjustment::new(GmpePointKind::Pga, 1., 0.5));
+        assert!(adj.for_kind(GmpePointKind::Pgv).is_none());
+    }
+
+    #[test]
+    fn test_adjusted_model() {
+        let base = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let adjusted = AdjustedModel::new(
+            base,
+            RegionAdjustment::new(vec![ImtAdjustment::new(GmpePointKind::Pga, 1., 2_f64.ln())]),
+        );
+        let point = Vs30Point::new(143.2, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+        let expected = 2. * base.calc_from_point(&point, &eq).value;
+        let value = adjusted.calc_from_point(&point, &eq).value;
+        assert!((value - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_adjustment_scales_sigma() {
+        let adj = RegionAdjustment::new(vec![ImtAdjustment::new(GmpePointKind::Pga, 0.8, 0.1)]);
+        let point = adj.apply(GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.5));
+        assert!((point.sigma.unwrap() - 0.4).abs() < 1e-12);
+        let pgv = adj.apply(GmpePoint::new_pgv(142.5, 50., 10.).with_sigma(0.5));
+        assert_eq!(pgv.sigma, Some(0.5));
+    }
+
+    #[test]
+    fn test_scaling_and_backbone() {
+        let pga = Scaling::new(0.5, 2.).apply(GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.6));
+        assert!((pga.value - 20. * 0.5_f64.exp()).abs() < 1e-12);
+        assert_eq!(pga.sigma, Some(0.6));
+        let mmi = Scaling::new(0.5, 1.).apply(GmpePoint::new(142.5, 50., 6., GmpePointKind::Mmi));
+        assert_eq!(mmi.value, 6.5);
+
+        let base = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let point = Vs30Point::new(143.2, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+        let central = base.calc_from_point(&point, &eq).value;
+        let branches = backbone_branches(base, 0.2);
+        let values: Vec<f64> = branches
+            .iter()
+            .map(|(model, _)| model.calc_from_point(&point, &eq).value)
+            .collect();
+        assert!((values[1] - central).abs() < 1e-12);
+        assert!(((values[2] / central).ln() - 0.329).abs() < 1e-12);
+        assert!(((values[0] * values[2]).sqrt() - central).abs() < 1e-9);
+        let weights: f64 = branches.iter().map(|(_, w)| w).sum();
+        assert!((weights - 1.).abs() < 1e-12);
+        assert!(toml::from_str::<Scaling>("log_shift = 0.1").unwrap().factor == 1.);
+    }
+}
@@ -0,0 +1,238 @@
+//! Implementation of the Afshari & Stewart (2016) significant duration model for active crustal
+//! regions.
+//!
+//! The model predicts the 5–75% and 5–95% significant durations (time between the given
+//! fractions of the Arias intensity) as the sum of a source and a path duration:
+//!
+//! ```text
+//! ln(D) = ln(Fe + Fp) + Fs
+//! Fe = 1 / f0,  f0 = 4.9·10⁶ · β · (Δσ / M0)^(1/3),  ln(Δσ) = b0 + b1·(min(M, M2) − 6)
+//! Fp = c1·min(R, R1) + c2·(min(R, R2) − R1)₊ + c3·(R − R2)₊
+//! Fs = c4·ln(min(Vs30, V1) / Vref)
+//! ```
+//!
+//! where `D` is the duration in seconds, `M0` is the seismic moment (dyne·cm), `β = 3.2` km/s
+//! and `R` is the rupture distance (km). Below `M1` the source duration is held at its `M1`
+//! value.
+//!
+//! Current simplifications:
+//!
+//! - Unspecified fault mechanism.
+//! - No basin depth (Z1.0) term.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Crustal shear-wave velocity of the source term (km/s).
+const BETA: f64 = 3.2;
+/// Reference magnitude of the stress parameter.
+const M_STAR: f64 = 6.;
+/// Path term distance breakpoints (km).
+const R1: f64 = 10.;
+const R2: f64 = 50.;
+
+/// Afshari & Stewart (2016) coefficients of one duration measure.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Afshari2016Coeffs {
+    /// Magnitude below which the source duration is constant
+    pub m1: f64,
+    /// Magnitude above which the stress parameter is constant
+    pub m2: f64,
+    /// Stress parameter `ln(Δσ)` (bar) at M 6 and its magnitude scaling
+    pub b0: f64,
+    pub b1: f64,
+    /// Path duration slopes (s/km) below `R1`, between `R1` and `R2` and beyond `R2`
+    pub c1: f64,
+    pub c2: f64,
+    pub c3: f64,
+    /// Vs30 scaling
+    pub c4: f64,
+    /// Vs30 above which the site term is constant (m/s)
+    pub v1: f64,
+    /// Reference Vs30 (m/s)
+    pub vref: f64,
+    /// Inter-event standard deviation of `ln(D)`
+    pub tau: f64,
+    /// Intra-event standard deviation of `ln(D)`
+    pub phi: f64,
+}
+
+/// Afshari & Stewart (2016) coefficients of the 5–75% significant duration.
+pub const AFSHARI2016_DS575: Afshari2016Coeffs = Afshari2016Coeffs {
+    m1: 5.35,
+    m2: 7.15,
+    b0: 6.76,
+    b1: 0.51,
+    c1: 0.1159,
+    c2: 0.0682,
+    c3: 0.,
+    c4: -0.2246,
+    v1: 600.,
+    vref: 368.2,
+    tau: 0.28,
+    phi: 0.54,
+};
+
+/// Afshari & Stewart (2016) coefficients of the 5–95% significant duration.
+pub const AFSHARI2016_DS595: Afshari2016Coeffs = Afshari2016Coeffs {
+    m1: 5.2,
+    m2: 7.4,
+    b0: 3.82,
+    b1: 1.02,
+    c1: 0.3165,
+    c2: 0.2539,
+    c3: 0.0932,
+    c4: -0.3478,
+    v1: 600.,
+    vref: 368.2,
+    tau: 0.25,
+    phi: 0.43,
+};
+
+/// Afshari & Stewart (2016) significant duration model parameters.
+#[derive(Debug)]
+pub struct AFSHARI2016 {
+    /// Coefficients of the duration measure
+    pub coeffs: Afshari2016Coeffs,
+    /// Duration measure (D5-75 or D5-95)
+    pub motion_kind: GmpePointKind,
+}
+
+impl AFSHARI2016 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `motion_kind` is not a significant duration measure.
+    pub fn new(coeffs: Afshari2016Coeffs, motion_kind: GmpePointKind) -> Self {
+        assert!(
+            matches!(motion_kind, GmpePointKind::Ds575 | GmpePointKind::Ds595),
+            "AFSHARI2016 only predicts significant durations"
+        );
+        Self {
+            coeffs,
+            motion_kind,
+        }
+    }
+
+    /// Source duration `Fe` (s).
+    pub fn source_duration(&self, magnitude: f64) -> f64 {
+        let c = &self.coeffs;
+        let magnitude = magnitude.max(c.m1);
+        let stress = (c.b0 + c.b1 * (magnitude.min(c.m2) - M_STAR)).exp();
+        let moment = 10_f64.powf(1.5 * magnitude + 16.05);
+        let f0 = 4.9e6 * BETA * (stress / moment).cbrt();
+        1. / f0
+    }
+
+    /// Path duration `Fp` (s).
+    pub fn path_duration(&self, rrup: f64) -> f64 {
+        let c = &self.coeffs;
+        c.c1 * rrup.min(R1) + c.c2 * (rrup.min(R2) - R1).max(0.) + c.c3 * (rrup - R2).max(0.)
+    }
+
+    /// Median `ln(D)` (s).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    /// * `vs30` - Site Vs30 (m/s).
+    pub fn ln_median(&self, magnitude: f64, rrup: f64, vs30: f64) -> f64 {
+        let c = &self.coeffs;
+        let site = c.c4 * (vs30.min(c.v1) / c.vref).ln();
+        (self.source_duration(magnitude) + self.path_duration(rrup)).ln() + site
+    }
+
+    /// Total standard deviation of `ln(D)`.
+    pub fn sigma(&self) -> f64 {
+        self.coeffs.tau.hypot(self.coeffs.phi)
+    }
+}
+
+impl GroundMotionModeling for AFSHARI2016 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with the significant duration in seconds.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rrup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.ztor()));
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: self.ln_median(eq.mw(), rrup, point.vs30).exp(),
+            kind: self.motion_kind,
+            sigma: Some(self.sigma()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vectorized::calc_gmpe_vec;
+
+    #[test]
+    fn test_duration_scaling() {
+        let d575 = AFSHARI2016::new(AFSHARI2016_DS575, GmpePointKind::Ds575);
+        let d595 = AFSHARI2016::new(AFSHARI2016_DS595, GmpePointKind::Ds595);
+        for (m, r) in [(5., 5.), (6., 20.), (7., 50.), (8., 150.)] {
+            let short = d575.ln_median(m, r, 368.2).exp();
+            let long = d595.ln_median(m, r, 368.2).exp();
+            assert!(short < long, "M{m} R{r}: {short} >= {long}");
+        }
+        // Longer for larger events, farther sites and softer soils
+        assert!(d595.ln_median(7., 20., 400.) > d595.ln_median(6., 20., 400.));
+        assert!(d595.ln_median(6., 80., 400.) > d595.ln_median(6., 20., 400.));
+        assert!(d595.ln_median(6., 20., 200.) > d595.ln_median(6., 20., 400.));
+        // Constant source duration below M1
+        assert_eq!(d575.source_duration(4.), d575.source_duration(5.35));
+    }
+
+    #[test]
+    fn test_reference_durations() {
+        let d575 = AFSHARI2016::new(AFSHARI2016_DS575, GmpePointKind::Ds575);
+        let d595 = AFSHARI2016::new(AFSHARI2016_DS595, GmpePointKind::Ds595);
+        assert!((d575.source_duration(6.) - 1.5).abs() < 0.05);
+        assert!((d595.source_duration(6.) - 4.).abs() < 0.1);
+        // Path duration: 10 km at c1, 40 km at c2, 50 km at c3
+        let expected = 0.3165 * 10. + 0.2539 * 40. + 0.0932 * 50.;
+        assert!((d595.path_duration(100.) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_grid_output_kind() {
+        let d595 = AFSHARI2016::new(AFSHARI2016_DS595, GmpePointKind::Ds595);
+        let eq = Earthquake::new_mw(142.4, 50., 10., 6.5);
+        let points = vec![
+            Vs30Point::new(142.5, 50., 400., None, None),
+            Vs30Point::new(143.5, 50., 400., None, None),
+        ];
+        let durations = calc_gmpe_vec(&points, &d595, &eq);
+        assert!(durations.iter().all(|p| p.kind == GmpePointKind::Ds595));
+        assert!(durations[1].value > durations[0].value);
+    }
+}
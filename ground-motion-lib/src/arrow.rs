@@ -0,0 +1,272 @@
+//! Apache Arrow interop of site and prediction points.
+//!
+//! Arrow-based data pipelines (DataFusion, Polars, DuckDB, ...) exchange columnar
+//! [`RecordBatch`]es. The [`Vs30Points`] and [`GmpePoints`] wrappers convert point vectors to
+//! and from record batches, so the crate can be embedded in such pipelines without a round trip
+//! through CSV files:
+//!
+//! - `RecordBatch::from(&GmpePoints(points))` builds a batch of the [`gmpe_schema`].
+//! - `GmpePoints::try_from(&batch)` reads a batch with (at least) the columns of the schema.
+//!
+//! The wrappers are needed as the orphan rule forbids implementing `From<Vec<GmpePoint>>` for
+//! the foreign `RecordBatch`. Columns are matched by name, so batches may hold further columns
+//! and any column order.
+//!
+//! | Column  | Type      | Nullable | Field of                                     |
+//! |---------|-----------|----------|----------------------------------------------|
+//! | `lon`   | `Float64` | no       | [`Vs30Point::lon`], [`GmpePoint::lon`]       |
+//! | `lat`   | `Float64` | no       | [`Vs30Point::lat`], [`GmpePoint::lat`]       |
+//! | `vs30`  | `Float64` | no       | [`Vs30Point::vs30`]                          |
+//! | `dl`    | `Float64` | yes      | [`Vs30Point::dl`]                            |
+//! | `xvf`   | `Float64` | yes      | [`Vs30Point::xvf`]                           |
+//! | `z1p0`  | `Float64` | yes      | [`Vs30Point::z1p0`]                          |
+//! | `z2p5`  | `Float64` | yes      | [`Vs30Point::z2p5`]                          |
+//! | `value` | `Float64` | no       | [`GmpePoint::value`]                         |
+//! | `kind`  | `Utf8`    | no       | [`GmpePoint::kind`], e.g. `Pga`, `Psa(0.3)`  |
+//! | `sigma` | `Float64` | yes      | [`GmpePoint::sigma`]                         |
+//!
+//! Optional site columns missing from a batch are read as `None`.
+//!
+//! Requires the `arrow` crate feature.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use arrow_array::RecordBatch;
+//! use ground_motion_lib::arrow::GmpePoints;
+//! use ground_motion_lib::gmm::GmpePoint;
+//!
+//! let points = GmpePoints(vec![GmpePoint::new_pga(142.6, 50.1, 0.789)]);
+//! let batch = RecordBatch::from(&points);
+//! assert_eq!(batch.num_rows(), 1);
+//!
+//! let back = GmpePoints::try_from(&batch).unwrap();
+//! assert_eq!(back.0[0].value, 0.789);
+//! ```
+
+use crate::gmm::{GmpePoint, Vs30Point};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Float64Type;
+use arrow_array::{Array, ArrayRef, Float64Array, RecordBatch, StringArray};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+use std::sync::Arc;
+
+/// Site points converted to and from Arrow record batches.
+#[derive(Debug)]
+pub struct Vs30Points(pub Vec<Vs30Point>);
+
+/// Prediction points converted to and from Arrow record batches.
+#[derive(Debug)]
+pub struct GmpePoints(pub Vec<GmpePoint>);
+
+/// Schema of record batches of site points.
+pub fn vs30_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("lon", DataType::Float64, false),
+        Field::new("lat", DataType::Float64, false),
+        Field::new("vs30", DataType::Float64, false),
+        Field::new("dl", DataType::Float64, true),
+        Field::new("xvf", DataType::Float64, true),
+        Field::new("z1p0", DataType::Float64, true),
+        Field::new("z2p5", DataType::Float64, true),
+    ])
+}
+
+/// Schema of record batches of prediction points.
+pub fn gmpe_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("lon", DataType::Float64, false),
+        Field::new("lat", DataType::Float64, false),
+        Field::new("value", DataType::Float64, false),
+        Field::new("kind", DataType::Utf8, false),
+        Field::new("sigma", DataType::Float64, true),
+    ])
+}
+
+fn float_column<T>(points: &[T], field: impl Fn(&T) -> f64) -> ArrayRef {
+    Arc::new(points.iter().map(field).collect::<Float64Array>())
+}
+
+fn optional_column<T>(points: &[T], field: impl Fn(&T) -> Option<f64>) -> ArrayRef {
+    Arc::new(points.iter().map(field).collect::<Float64Array>())
+}
+
+/// `Float64` column of a batch by name, or `None` if the batch has no such column.
+fn batch_column<'a>(
+    batch: &'a RecordBatch,
+    name: &str,
+) -> Result<Option<&'a Float64Array>, ArrowError> {
+    batch
+        .column_by_name(name)
+        .map(|column| {
+            column.as_primitive_opt::<Float64Type>().ok_or_else(|| {
+                ArrowError::SchemaError(format!(
+                    "Column `{name}` is {}, expected Float64",
+                    column.data_type()
+                ))
+            })
+        })
+        .transpose()
+}
+
+fn required_column<'a>(batch: &'a RecordBatch, name: &str) -> Result<&'a Float64Array, ArrowError> {
+    let column = batch_column(batch, name)?
+        .ok_or_else(|| ArrowError::SchemaError(format!("Record batch has no `{name}` column")))?;
+    if column.null_count() > 0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "Column `{name}` has null values"
+        )));
+    }
+    Ok(column)
+}
+
+/// Value of an optional column at a row, `None` for missing columns and null values.
+fn optional_value(column: Option<&Float64Array>, row: usize) -> Option<f64> {
+    column
+        .filter(|column| column.is_valid(row))
+        .map(|column| column.value(row))
+}
+
+impl From<&Vs30Points> for RecordBatch {
+    fn from(points: &Vs30Points) -> Self {
+        let points = &points.0;
+        let columns = vec![
+            float_column(points, |p| p.lon),
+            float_column(points, |p| p.lat),
+            float_column(points, |p| p.vs30),
+            optional_column(points, |p| p.dl),
+            optional_column(points, |p| p.xvf),
+            optional_column(points, |p| p.z1p0),
+            optional_column(points, |p| p.z2p5),
+        ];
+        RecordBatch::try_new(Arc::new(vs30_schema()), columns)
+            .expect("site columns match the schema")
+    }
+}
+
+impl From<&GmpePoints> for RecordBatch {
+    fn from(points: &GmpePoints) -> Self {
+        let points = &points.0;
+        let kinds: StringArray = points.iter().map(|p| Some(p.kind.to_string())).collect();
+        let columns = vec![
+            float_column(points, |p| p.lon),
+            float_column(points, |p| p.lat),
+            float_column(points, |p| p.value),
+            Arc::new(kinds),
+            optional_column(points, |p| p.sigma),
+        ];
+        RecordBatch::try_new(Arc::new(gmpe_schema()), columns)
+            .expect("prediction columns match the schema")
+    }
+}
+
+impl TryFrom<&RecordBatch> for Vs30Points {
+    type Error = ArrowError;
+
+    fn try_from(batch: &RecordBatch) -> Result<Self, Self::Error> {
+        let lon = required_column(batch, "lon")?;
+        let lat = required_column(batch, "lat")?;
+        let vs30 = required_column(batch, "vs30")?;
+        let dl = batch_column(batch, "dl")?;
+        let xvf = batch_column(batch, "xvf")?;
+        let z1p0 = batch_column(batch, "z1p0")?;
+        let z2p5 = batch_column(batch, "z2p5")?;
+        let points = (0..batch.num_rows())
+            .map(|row| Vs30Point {
+                lon: lon.value(row),
+                lat: lat.value(row),
+                vs30: vs30.value(row),
+                dl: optional_value(dl, row),
+                xvf: optional_value(xvf, row),
+                z1p0: optional_value(z1p0, row),
+                z2p5: optional_value(z2p5, row),
+            })
+            .collect();
+        Ok(Self(points))
+    }
+}
+
+impl TryFrom<&RecordBatch> for GmpePoints {
+    type Error = ArrowError;
+
+    fn try_from(batch: &RecordBatch) -> Result<Self, Self::Error> {
+        let lon = required_column(batch, "lon")?;
+        let lat = required_column(batch, "lat")?;
+        let value = required_column(batch, "value")?;
+        let sigma = batch_column(batch, "sigma")?;
+        let kind = batch
+            .column_by_name("kind")
+            .ok_or_else(|| ArrowError::SchemaError("Record batch has no `kind` column".into()))?;
+        let kind = kind.as_string_opt::<i32>().ok_or_else(|| {
+            ArrowError::SchemaError(format!(
+                "Column `kind` is {}, expected Utf8",
+                kind.data_type()
+            ))
+        })?;
+        if kind.null_count() > 0 {
+            return Err(ArrowError::InvalidArgumentError(
+                "Column `kind` has null values".into(),
+            ));
+        }
+        (0..batch.num_rows())
+            .map(|row| {
+                Ok(GmpePoint {
+                    lon: lon.value(row),
+                    lat: lat.value(row),
+                    value: value.value(row),
+                    kind: kind
+                        .value(row)
+                        .parse()
+                        .map_err(|err| ArrowError::ParseError(format!("Row {row}: {err}")))?,
+                    sigma: optional_value(sigma, row),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmm::GmpePointKind;
+
+    #[test]
+    fn test_gmpe_points_round_trip() {
+        let points = GmpePoints(vec![
+            GmpePoint::new_pga(142., 50., 12.5),
+            GmpePoint {
+                lon: 142.1,
+                lat: 50.1,
+                value: 3.2,
+                kind: GmpePointKind::Psa { period: 0.3 },
+                sigma: Some(0.7),
+            },
+        ]);
+        let batch = RecordBatch::from(&points);
+        assert_eq!(batch.schema().as_ref(), &gmpe_schema());
+        assert_eq!(batch.column(3).as_string::<i32>().value(1), "Psa(0.3)");
+        let back = GmpePoints::try_from(&batch).unwrap().0;
+        assert_eq!((back[0].kind, back[0].sigma), (GmpePointKind::Pga, None));
+        assert_eq!(back[1].kind, GmpePointKind::Psa { period: 0.3 });
+        assert_eq!((back[1].value, back[1].sigma), (3.2, Some(0.7)));
+
+        let without_kind = batch.project(&[0, 1, 2]).unwrap();
+        assert!(GmpePoints::try_from(&without_kind).is_err());
+    }
+
+    #[test]
+    fn test_vs30_points_optional_columns() {
+        let mut site = Vs30Point::new(142., 50., 400., Some(250.), None);
+        site.z2p5 = Some(1.2);
+        let batch = RecordBatch::from(&Vs30Points(vec![site]));
+        assert_eq!(batch.column(4).null_count(), 1);
+
+        // Batches without the optional columns, in another order
+        let sites = Vs30Points::try_from(&batch.project(&[2, 1, 0]).unwrap()).unwrap();
+        assert_eq!((sites.0[0].lon, sites.0[0].vs30), (142., 400.));
+        assert_eq!(sites.0[0].dl, None);
+        let sites = Vs30Points::try_from(&batch).unwrap();
+        assert_eq!((sites.0[0].dl, sites.0[0].z2p5), (Some(250.), Some(1.2)));
+    }
+}
@@ -0,0 +1,441 @@
+//! Implementation of Akkar, Sandikkaya & Bommer (2014) Ground Motion Prediction Equations
+//! (GMPE) for shallow crustal earthquakes in Europe and the Middle East.
+//!
+//! The median motion on reference rock (Vs30 = 750 m/s) is:
+//!
+//! ```text
+//! ln(Y_ref) = a1 + a2·(M − c1) + a3·(8.5 − M)² + (a4 + a5·(M − c1))·ln(sqrt(R² + a6²))
+//!           + a8·F_N + a9·F_R                                            (M ≤ c1)
+//! ln(Y_ref) = a1 + a7·(M − c1) + a3·(8.5 − M)² + (a4 + a5·(M − c1))·ln(sqrt(R² + a6²))
+//!           + a8·F_N + a9·F_R                                            (M > c1)
+//! ```
+//!
+//! with `c1 = 6.75`, where `Y` is PGA or PSA in g, or PGV in cm/s. The nonlinear site
+//! amplification is driven by the median rock PGA. Separate coefficient sets are published for
+//! the Joyner–Boore, epicentral and hypocentral distance metrics, see [`Asb2014Distance`].
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism (`F_N = F_R = 0`).
+//! - For point sources the Joyner–Boore distance equals the epicentral distance.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Hinge magnitude of the magnitude scaling.
+const C1: f64 = 6.75;
+/// Reference and limiting shear-wave velocities of the site term (m/s).
+const V_REF: f64 = 750.;
+const V_CON: f64 = 1000.;
+/// Nonlinear site response constants.
+const SITE_C: f64 = 2.5;
+const SITE_N: f64 = 3.2;
+
+/// Distance metric of the coefficient set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Asb2014Distance {
+    /// Joyner–Boore distance
+    Rjb,
+    /// Epicentral distance
+    Repi,
+    /// Hypocentral distance
+    Rhyp,
+}
+
+impl Asb2014Distance {
+    /// Source-to-site distance (km) in this metric.
+    ///
+    /// # Arguments
+    ///
+    /// * `epicentral_distance` - Horizontal distance from the epicenter to the site (km).
+    /// * `depth` - Focal depth (km).
+    pub fn distance(self, epicentral_distance: f64, depth: f64) -> f64 {
+        match self {
+            Asb2014Distance::Rjb | Asb2014Distance::Repi => epicentral_distance,
+            Asb2014Distance::Rhyp => epicentral_distance.hypot(depth),
+        }
+    }
+
+    /// The corresponding [`DistanceMetric`].
+    pub fn metric(self) -> DistanceMetric {
+        match self {
+            Asb2014Distance::Rjb => DistanceMetric::Rjb,
+            Asb2014Distance::Repi => DistanceMetric::Repi,
+            Asb2014Distance::Rhyp => DistanceMetric::Rhyp,
+        }
+    }
+}
+
+/// Period-dependent ASB14 coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Asb2014Coeffs {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Constant term
+    pub a1: f64,
+    /// Magnitude scaling below the hinge magnitude
+    pub a2: f64,
+    /// Magnitude-squared scaling
+    pub a3: f64,
+    /// Geometric spreading and its magnitude dependence
+    pub a4: f64,
+    pub a5: f64,
+    /// Finite-fault distance term (km)
+    pub a6: f64,
+    /// Magnitude scaling above the hinge magnitude
+    pub a7: f64,
+    /// Normal and reverse faulting terms
+    pub a8: f64,
+    pub a9: f64,
+    /// Linear and nonlinear site response
+    pub b1: f64,
+    pub b2: f64,
+    /// Total standard deviation of `ln(Y)`
+    pub sigma: f64,
+}
+
+/// ASB14 coefficients for the Joyner–Boore distance (PGA, PGV, PSA at 0.2 and 1 s).
+pub const ASB2014_RJB: [Asb2014Coeffs; 4] = [
+    Asb2014Coeffs {
+        period: 0.,
+        a1: 1.85329,
+        a2: 0.0029,
+        a3: -0.02807,
+        a4: -1.23452,
+        a5: 0.2529,
+        a6: 7.5,
+        a7: -0.5096,
+        a8: -0.1091,
+        a9: 0.0937,
+        b1: -0.41997,
+        b2: -0.28846,
+        sigma: 0.7,
+    },
+    Asb2014Coeffs {
+        period: -1.,
+        a1: 6.5,
+        a2: 0.0029,
+        a3: -0.0998,
+        a4: -1.18738,
+        a5: 0.25,
+        a6: 6.5,
+        a7: -0.1,
+        a8: -0.06,
+        a9: 0.05,
+        b1: -0.72057,
+        b2: -0.19688,
+        sigma: 0.68,
+    },
+    Asb2014Coeffs {
+        period: 0.2,
+        a1: 2.47,
+        a2: 0.0029,
+        a3: -0.0204,
+        a4: -1.21,
+        a5: 0.2529,
+        a6: 9.3,
+        a7: -0.53,
+        a8: -0.11,
+        a9: 0.09,
+        b1: -0.28,
+        b2: -0.4,
+        sigma: 0.73,
+    },
+    Asb2014Coeffs {
+        period: 1.0,
+        a1: 0.7,
+        a2: 0.0029,
+        a3: -0.09,
+        a4: -1.05,
+        a5: 0.2529,
+        a6: 6.,
+        a7: -0.2,
+        a8: -0.08,
+        a9: 0.07,
+        b1: -0.74,
+        b2: -0.12,
+        sigma: 0.77,
+    },
+];
+
+/// ASB14 coefficients for the epicentral distance (PGA, PGV, PSA at 0.2 and 1 s).
+pub const ASB2014_REPI: [Asb2014Coeffs; 4] = [
+    Asb2014Coeffs {
+        period: 0.,
+        a1: 2.52979,
+        a2: 0.0029,
+        a3: -0.02807,
+        a4: -1.31002,
+        a5: 0.2529,
+        a6: 11.,
+        a7: -0.5096,
+        a8: -0.1091,
+        a9: 0.0937,
+        b1: -0.41997,
+        b2: -0.28846,
+        sigma: 0.73,
+    },
+    Asb2014Coeffs {
+        period: -1.,
+        a1: 7.1765,
+        a2: 0.0029,
+        a3: -0.0998,
+        a4: -1.26288,
+        a5: 0.25,
+        a6: 11.,
+        a7: -0.1,
+        a8: -0.06,
+        a9: 0.05,
+        b1: -0.72057,
+        b2: -0.19688,
+        sigma: 0.71,
+    },
+    Asb2014Coeffs {
+        period: 0.2,
+        a1: 3.1465,
+        a2: 0.0029,
+        a3: -0.0204,
+        a4: -1.2855,
+        a5: 0.2529,
+        a6: 11.,
+        a7: -0.53,
+        a8: -0.11,
+        a9: 0.09,
+        b1: -0.28,
+        b2: -0.4,
+        sigma: 0.76,
+    },
+    Asb2014Coeffs {
+        period: 1.0,
+        a1: 1.3765,
+        a2: 0.0029,
+        a3: -0.09,
+        a4: -1.1255,
+        a5: 0.2529,
+        a6: 11.,
+        a7: -0.2,
+        a8: -0.08,
+        a9: 0.07,
+        b1: -0.74,
+        b2: -0.12,
+        sigma: 0.8,
+    },
+];
+
+/// ASB14 coefficients for the hypocentral distance (PGA, PGV, PSA at 0.2 and 1 s).
+pub const ASB2014_RHYP: [Asb2014Coeffs; 4] = [
+    Asb2014Coeffs {
+        period: 0.,
+        a1: 3.26685,
+        a2: 0.0029,
+        a3: -0.02807,
+        a4: -1.42,
+        a5: 0.2529,
+        a6: 7.,
+        a7: -0.5096,
+        a8: -0.1091,
+        a9: 0.0937,
+        b1: -0.41997,
+        b2: -0.28846,
+        sigma: 0.73,
+    },
+    Asb2014Coeffs {
+        period: -1.,
+        a1: 7.91356,
+        a2: 0.0029,
+        a3: -0.0998,
+        a4: -1.37286,
+        a5: 0.25,
+        a6: 7.,
+        a7: -0.1,
+        a8: -0.06,
+        a9: 0.05,
+        b1: -0.72057,
+        b2: -0.19688,
+        sigma: 0.71,
+    },
+    Asb2014Coeffs {
+        period: 0.2,
+        a1: 3.88356,
+        a2: 0.0029,
+        a3: -0.0204,
+        a4: -1.39548,
+        a5: 0.2529,
+        a6: 7.,
+        a7: -0.53,
+        a8: -0.11,
+        a9: 0.09,
+        b1: -0.28,
+        b2: -0.4,
+        sigma: 0.76,
+    },
+    Asb2014Coeffs {
+        period: 1.0,
+        a1: 2.11356,
+        a2: 0.0029,
+        a3: -0.09,
+        a4: -1.23548,
+        a5: 0.2529,
+        a6: 7.,
+        a7: -0.2,
+        a8: -0.08,
+        a9: 0.07,
+        b1: -0.74,
+        b2: -0.12,
+        sigma: 0.8,
+    },
+];
+
+/// Akkar, Sandikkaya & Bommer (2014) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct ASB2014 {
+    /// Period-dependent coefficients
+    pub coeffs: Asb2014Coeffs,
+    /// Distance metric the coefficients were derived for
+    pub distance: Asb2014Distance,
+    /// Type of motion (PGA, PGV, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl ASB2014 {
+    /// Create a new ASB14 model for the given coefficient set, distance metric and motion type.
+    pub fn new(
+        coeffs: Asb2014Coeffs,
+        distance: Asb2014Distance,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        Self {
+            coeffs,
+            distance,
+            motion_kind,
+        }
+    }
+
+    /// PGA coefficients for the distance metric of the model.
+    fn pga_coeffs(&self) -> &'static Asb2014Coeffs {
+        match self.distance {
+            Asb2014Distance::Rjb => &ASB2014_RJB[0],
+            Asb2014Distance::Repi => &ASB2014_REPI[0],
+            Asb2014Distance::Rhyp => &ASB2014_RHYP[0],
+        }
+    }
+
+    /// Median `ln(Y)` on reference rock (Vs30 = 750 m/s).
+    fn ln_reference(c: &Asb2014Coeffs, magnitude: f64, distance: f64) -> f64 {
+        let dm = magnitude - C1;
+        let f_mag = if magnitude <= C1 {
+            c.a2 * dm
+        } else {
+            c.a7 * dm
+        };
+        c.a1 + f_mag
+            + c.a3 * (8.5 - magnitude).powi(2)
+            + (c.a4 + c.a5 * dm) * distance.hypot(c.a6).ln()
+    }
+
+    /// Site amplification `ln(S)` for the given Vs30 (m/s) and median rock PGA (g).
+    fn site_term(c: &Asb2014Coeffs, vs30: f64, pga_ref: f64) -> f64 {
+        let f_lin = c.b1 * (vs30.min(V_CON) / V_REF).ln();
+        if vs30 <= V_REF {
+            let ratio = (vs30 / V_REF).powf(SITE_N);
+            f_lin + c.b2 * ((pga_ref + SITE_C * ratio) / ((pga_ref + SITE_C) * ratio)).ln()
+        } else {
+            f_lin
+        }
+    }
+
+    /// Natural logarithm of the median ground motion.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Source-to-site distance in the metric of the model (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    ///
+    /// # Returns
+    ///
+    /// `ln(Y)` with `Y` in g (PGA, PSA) or cm/s (PGV).
+    pub fn ln_median(&self, magnitude: f64, distance: f64, vs30: f64) -> f64 {
+        let pga_ref = Self::ln_reference(self.pga_coeffs(), magnitude, distance).exp();
+        Self::ln_reference(&self.coeffs, magnitude, distance)
+            + Self::site_term(&self.coeffs, vs30, pga_ref)
+    }
+}
+
+impl GroundMotionModeling for ASB2014 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![self.distance.metric()]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .get(self.distance.metric())
+            .unwrap_or_else(|| self.distance.distance(distances.repi, eq.depth));
+        let mut ground_motion = self.ln_median(eq.mw(), distance, point.vs30).exp();
+        // convert g to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion *= 100.;
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_rock_has_no_site_term() {
+        let model = ASB2014::new(ASB2014_RJB[0], Asb2014Distance::Rjb, GmpePointKind::Pga);
+        let pga = model.ln_median(6.5, 20., V_REF).exp();
+        assert!(pga > 0.05 && pga < 0.2, "{pga}");
+    }
+
+    #[test]
+    fn test_nonlinear_site_response() {
+        let model = ASB2014::new(
+            ASB2014_REPI[2],
+            Asb2014Distance::Repi,
+            GmpePointKind::Psa {
+                period: ASB2014_REPI[2].period,
+            },
+        );
+        let weak = model.ln_median(5., 100., 250.) - model.ln_median(5., 100., V_REF);
+        let strong = model.ln_median(7.5, 1., 250.) - model.ln_median(7.5, 1., V_REF);
+        assert!(weak > strong);
+    }
+
+    #[test]
+    fn test_distance_metrics() {
+        assert_eq!(Asb2014Distance::Repi.distance(30., 10.), 30.);
+        assert!((Asb2014Distance::Rhyp.distance(30., 40.) - 50.).abs() < 1e-12);
+    }
+}
@@ -57,6 +57,114 @@ pub fn round_to_places(val: f64, places: u32) -> f64 {
     (val * factor).round() / factor
 }
 
+/// Quantile function (inverse CDF) of the standard normal distribution.
+///
+/// Uses the rational approximation of P. J. Acklam with a relative error below `1.2e-9`.
+///
+/// # Arguments
+///
+/// * `p` - Probability, `0 < p < 1`.
+///
+/// # Returns
+///
+/// The value `x` such that `P(X ≤ x) = p` for a standard normal `X`. Returns `-∞` and `+∞` for
+/// `p = 0` and `p = 1` and `NaN` outside `[0, 1]`.
+///
+/// # Examples
+///
+/// ```
+/// use ground_motion_lib::auxilary::normal_quantile;
+/// assert_eq!(normal_quantile(0.5), 0.);
+/// assert!((normal_quantile(0.84) - 0.9945).abs() < 1e-4);
+/// ```
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    if !(0. ..=1.).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0. {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1. {
+        return f64::INFINITY;
+    }
+    // Tails
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    if p < P_LOW {
+        return tail((-2. * p.ln()).sqrt());
+    }
+    if p > 1. - P_LOW {
+        return -tail((-2. * (1. - p).ln()).sqrt());
+    }
+    // Central region
+    let q = p - 0.5;
+    let r = q * q;
+    (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+        / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+}
+
+/// Cumulative distribution function of the standard normal distribution.
+///
+/// Uses the Chebyshev approximation of the complementary error function from Numerical Recipes
+/// with a relative error below `1.2e-7`, so the far tails keep their relative precision.
+///
+/// # Examples
+///
+/// ```
+/// use ground_motion_lib::auxilary::normal_cdf;
+/// assert!((normal_cdf(0.) - 0.5).abs() < 1e-7);
+/// assert!((normal_cdf(1.) - 0.8413).abs() < 1e-4);
+/// ```
+pub fn normal_cdf(x: f64) -> f64 {
+    // erfc(z) with z = −x / √2
+    let z = -x / std::f64::consts::SQRT_2;
+    let t = 1. / (1. + 0.5 * z.abs());
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let erfc = t * poly.exp();
+    let erfc = if z >= 0. { erfc } else { 2. - erfc };
+    0.5 * erfc
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +208,25 @@ mod tests {
     fn test_round_to_more_places() {
         assert_eq!(round_to_places(5.14159, 4), 5.1416);
     }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert_eq!(normal_quantile(0.5), 0.);
+        assert!((normal_quantile(0.975) - 1.959964).abs() < 1e-6);
+        assert!((normal_quantile(0.025) + 1.959964).abs() < 1e-6);
+        assert!((normal_quantile(0.001) + 3.090232).abs() < 1e-6);
+        assert!((normal_quantile(0.15865525) + 1.).abs() < 1e-6);
+        assert_eq!(normal_quantile(1.), f64::INFINITY);
+        assert!(normal_quantile(1.5).is_nan());
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.959964) - 0.975).abs() < 1e-7);
+        assert!((normal_cdf(-1.) - 0.15865525).abs() < 1e-7);
+        // Relative precision in the tail
+        assert!((normal_cdf(-5.) / 2.866516e-7 - 1.).abs() < 1e-5);
+        assert!((normal_cdf(normal_quantile(0.3)) - 0.3).abs() < 1e-7);
+    }
 }
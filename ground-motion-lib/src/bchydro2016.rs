@@ -0,0 +1,452 @@
+//! Implementation of the BC Hydro (Abrahamson, Gregor & Addo, 2016) Ground Motion Prediction
+//! Equations (GMPE) for subduction zone earthquakes.
+//!
+//! The model covers interface (interplate) and intraslab (intraplate) events:
+//!
+//! ```text
+//! ln(Sa) = θ1 + θ4·ΔC1 + (θ2 + θ14·F_event + θ3·(M − 7.8))·ln(R + C4·exp((M − 6)·θ9))
+//!        + θ6·R + θ10·F_event + f_mag(M) + f_depth(Zh) + f_FABA(R) + f_site(PGA1000, Vs30)
+//! ```
+//!
+//! where `Sa` is PGA or 5%-damped PSA in g, `F_event` is 1 for intraslab events and 0 for
+//! interface events, and `R` is the rupture distance (interface) or the hypocentral distance
+//! (intraslab). The magnitude break is `C1 + ΔC1` with the central-branch `ΔC1` values.
+//!
+//! The forearc/backarc term `f_FABA` is applied to backarc sites only, i.e. sites with a
+//! positive signed distance to the volcanic front ([`Vs30Point::xvf`]). Sites on the front and
+//! sites without a distance are treated as forearc.
+//!
+//! The nonlinear site term is driven by the median rock PGA at Vs30 = 1000 m/s. For point
+//! sources the rupture distance is approximated by the hypocentral distance.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Magnitude break of the magnitude scaling before the `ΔC1` adjustment.
+const C1: f64 = 7.8;
+/// Finite-fault term scaling distance (km).
+const C4: f64 = 10.;
+/// Magnitude-dependent geometric spreading slope.
+const THETA3: f64 = 0.1;
+/// Magnitude scaling slope below the magnitude break.
+const THETA4: f64 = 0.9;
+/// Magnitude scaling slope above the magnitude break.
+const THETA5: f64 = 0.;
+/// Magnitude scaling of the finite-fault term.
+const THETA9: f64 = 0.4;
+/// Nonlinear site response constants.
+const N: f64 = 1.18;
+const C: f64 = 1.88;
+/// Central-branch `ΔC1` of intraslab events.
+const DC1_SLAB: f64 = -0.3;
+/// Reference Vs30 of the rock PGA driving the nonlinear site term (m/s).
+const VS_ROCK: f64 = 1000.;
+
+/// Period-dependent BC Hydro (2016) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BcHydro2016Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Linear site response limit (m/s)
+    pub vlin: f64,
+    /// Nonlinear site response slope
+    pub b: f64,
+    /// Constant term
+    pub theta1: f64,
+    /// Geometric spreading
+    pub theta2: f64,
+    /// Anelastic attenuation
+    pub theta6: f64,
+    /// Intraslab forearc/backarc terms
+    pub theta7: f64,
+    pub theta8: f64,
+    /// Intraslab constant adjustment
+    pub theta10: f64,
+    /// Intraslab depth scaling
+    pub theta11: f64,
+    /// Linear site response scaling
+    pub theta12: f64,
+    /// Magnitude-squared scaling
+    pub theta13: f64,
+    /// Intraslab geometric spreading adjustment
+    pub theta14: f64,
+    /// Interface forearc/backarc terms
+    pub theta15: f64,
+    pub theta16: f64,
+    /// Central-branch magnitude break adjustment of interface events
+    pub dc1_interface: f64,
+    /// Total standard deviation of `ln(Sa)`
+    pub sigma: f64,
+}
+
+/// BC Hydro (2016) coefficients (PGA, PSA at 0.1, 0.2, 0.3, 0.5, 1, 2 and 3 s).
+pub const BCHYDRO2016_COEFFS: [BcHydro2016Coeffs; 8] = [
+    BcHydro2016Coeffs {
+        period: 0.,
+        vlin: 865.1,
+        b: -1.186,
+        theta1: 4.2203,
+        theta2: -1.35,
+        theta6: -0.0012,
+        theta7: 1.0988,
+        theta8: -1.42,
+        theta10: 3.12,
+        theta11: 0.013,
+        theta12: 0.98,
+        theta13: -0.0135,
+        theta14: -0.4,
+        theta15: 0.9996,
+        theta16: -1.,
+        dc1_interface: 0.2,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 0.1,
+        vlin: 1032.5,
+        b: -1.624,
+        theta1: 5.2892,
+        theta2: -1.45,
+        theta6: -0.0012,
+        theta7: 1.3997,
+        theta8: -1.8,
+        theta10: 3.33,
+        theta11: 0.013,
+        theta12: 1.613,
+        theta13: -0.0145,
+        theta14: -0.4,
+        theta15: 1.3042,
+        theta16: -1.36,
+        dc1_interface: 0.2,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 0.2,
+        vlin: 748.2,
+        b: -2.188,
+        theta1: 5.2684,
+        theta2: -1.4,
+        theta6: -0.0018,
+        theta7: 1.1648,
+        theta8: -1.49,
+        theta10: 3.03,
+        theta11: 0.0129,
+        theta12: 2.076,
+        theta13: -0.0162,
+        theta14: -0.35,
+        theta15: 1.223,
+        theta16: -1.25,
+        dc1_interface: 0.2,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 0.3,
+        vlin: 587.1,
+        b: -2.518,
+        theta1: 4.7945,
+        theta2: -1.28,
+        theta6: -0.0027,
+        theta7: 0.8821,
+        theta8: -1.18,
+        theta10: 2.59,
+        theta11: 0.0128,
+        theta12: 2.348,
+        theta13: -0.0183,
+        theta14: -0.28,
+        theta15: 1.05,
+        theta16: -1.06,
+        dc1_interface: 0.2,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 0.5,
+        vlin: 456.6,
+        b: -2.669,
+        theta1: 4.0181,
+        theta2: -1.08,
+        theta6: -0.0044,
+        theta7: 0.5799,
+        theta8: -0.82,
+        theta10: 1.92,
+        theta11: 0.0125,
+        theta12: 2.399,
+        theta13: -0.0231,
+        theta14: -0.19,
+        theta15: 0.662,
+        theta16: -0.62,
+        dc1_interface: 0.1,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 1.0,
+        vlin: 400.,
+        b: -1.955,
+        theta1: 2.7981,
+        theta2: -0.85,
+        theta6: -0.0062,
+        theta7: 0.1746,
+        theta8: -0.34,
+        theta10: 1.1,
+        theta11: 0.0114,
+        theta12: 1.47,
+        theta13: -0.0363,
+        theta14: -0.07,
+        theta15: 0.33,
+        theta16: -0.14,
+        dc1_interface: 0.,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 2.0,
+        vlin: 400.,
+        b: -0.299,
+        theta1: 1.4128,
+        theta2: -0.71,
+        theta6: -0.0064,
+        theta7: -0.2821,
+        theta8: 0.12,
+        theta10: 0.7,
+        theta11: 0.0085,
+        theta12: -0.401,
+        theta13: -0.061,
+        theta14: 0.,
+        theta15: 0.3,
+        theta16: 0.,
+        dc1_interface: -0.1,
+        sigma: 0.74,
+    },
+    BcHydro2016Coeffs {
+        period: 3.0,
+        vlin: 400.,
+        b: 0.,
+        theta1: 0.6443,
+        theta2: -0.64,
+        theta6: -0.0064,
+        theta7: -0.4466,
+        theta8: 0.3,
+        theta10: 0.7,
+        theta11: 0.0054,
+        theta12: -0.673,
+        theta13: -0.0798,
+        theta14: 0.,
+        theta15: 0.3,
+        theta16: 0.,
+        dc1_interface: -0.2,
+        sigma: 0.74,
+    },
+];
+
+/// BC Hydro (2016) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct BCHYDRO2016 {
+    /// Period-dependent coefficients
+    pub coeffs: BcHydro2016Coeffs,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl BCHYDRO2016 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: BcHydro2016Coeffs,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "BCHYDRO2016 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Median `ln(Sa)` (g) with the given coefficients, before the site term.
+    fn ln_base(
+        &self,
+        c: &BcHydro2016Coeffs,
+        magnitude: f64,
+        distance: f64,
+        depth: f64,
+        backarc: bool,
+    ) -> f64 {
+        let slab = self.tectonic_type == TectonicType::Intraplate;
+        let f_event = if slab { 1. } else { 0. };
+        let dc1 = if slab { DC1_SLAB } else { c.dc1_interface };
+        let m_break = C1 + dc1;
+
+        let f_mag = if magnitude <= m_break {
+            THETA4 * (magnitude - m_break)
+        } else {
+            THETA5 * (magnitude - m_break)
+        } + c.theta13 * (10. - magnitude).powi(2);
+
+        let f_depth = c.theta11 * (depth.min(120.) - 60.) * f_event;
+
+        let f_faba = match (backarc, slab) {
+            (false, _) => 0.,
+            (true, true) => c.theta7 + c.theta8 * (distance.max(85.) / 40.).ln(),
+            (true, false) => c.theta15 + c.theta16 * (distance.max(100.) / 40.).ln(),
+        };
+
+        let spreading = c.theta2 + c.theta14 * f_event + THETA3 * (magnitude - C1);
+        let r = distance + C4 * ((magnitude - 6.) * THETA9).exp();
+
+        c.theta1
+            + THETA4 * dc1
+            + spreading * r.ln()
+            + c.theta6 * distance
+            + c.theta10 * f_event
+            + f_mag
+            + f_depth
+            + f_faba
+    }
+
+    /// Site term for the given Vs30 (m/s) and median rock PGA at Vs30 = 1000 m/s (g).
+    fn site_term(c: &BcHydro2016Coeffs, vs30: f64, pga1000: f64) -> f64 {
+        let vs = vs30.min(1000.);
+        let ratio = vs / c.vlin;
+        if vs30 < c.vlin {
+            c.theta12 * ratio.ln() - c.b * (pga1000 + C).ln()
+                + c.b * (pga1000 + C * ratio.powf(N)).ln()
+        } else {
+            c.theta12 * ratio.ln() + c.b * N * ratio.ln()
+        }
+    }
+
+    /// Median `ln(Sa)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Rupture distance for interface events or hypocentral distance for
+    ///   intraslab events (km).
+    /// * `depth` - Focal depth (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    /// * `backarc` - Whether the site is located in the backarc region.
+    pub fn ln_median(
+        &self,
+        magnitude: f64,
+        distance: f64,
+        depth: f64,
+        vs30: f64,
+        backarc: bool,
+    ) -> f64 {
+        let pga = &BCHYDRO2016_COEFFS[0];
+        let pga1000 = (self.ln_base(pga, magnitude, distance, depth, backarc)
+            + Self::site_term(pga, VS_ROCK, 0.))
+        .exp();
+        self.ln_base(&self.coeffs, magnitude, distance, depth, backarc)
+            + Self::site_term(&self.coeffs, vs30, pga1000)
+    }
+}
+
+/// Whether the site is located in the backarc region.
+fn is_backarc(point: &Vs30Point) -> bool {
+    point.xvf.is_some_and(|xvf| xvf > 0.)
+}
+
+impl GroundMotionModeling for BCHYDRO2016 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        match self.tectonic_type {
+            TectonicType::Intraplate => vec![DistanceMetric::Rhyp],
+            _ => vec![DistanceMetric::Rrup],
+        }
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = match self.tectonic_type {
+            TectonicType::Intraplate => distances.rhyp,
+            _ => distances.rrup,
+        }
+        .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ln_sa = self.ln_median(eq.mw(), distance, eq.depth, point.vs30, is_backarc(point));
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: ln_sa.exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(tectonic_type: TectonicType, coeffs: BcHydro2016Coeffs) -> BCHYDRO2016 {
+        BCHYDRO2016::new(coeffs, tectonic_type, GmpePointKind::Pga)
+    }
+
+    #[test]
+    fn test_backarc_attenuates_far_field() {
+        for tectonic_type in [TectonicType::Interplate, TectonicType::Intraplate] {
+            let m = model(tectonic_type, BCHYDRO2016_COEFFS[0]);
+            let forearc = m.ln_median(7.5, 200., 40., 760., false);
+            let backarc = m.ln_median(7.5, 200., 40., 760., true);
+            assert!(backarc < forearc);
+        }
+    }
+
+    #[test]
+    fn test_site_nonlinearity() {
+        let m = model(TectonicType::Interplate, BCHYDRO2016_COEFFS[0]);
+        // Soft soil amplifies weak motion more than strong motion
+        let far =
+            m.ln_median(7., 300., 30., 200., false) - m.ln_median(7., 300., 30., 1000., false);
+        let near = m.ln_median(9., 20., 30., 200., false) - m.ln_median(9., 20., 30., 1000., false);
+        assert!(far > near);
+    }
+
+    #[test]
+    fn test_xvf_selects_backarc() {
+        let point = Vs30Point::new(142., 50., 400., None, Some(20.));
+        assert!(is_backarc(&point));
+        assert!(!is_backarc(&point.with_xvf(-20.)));
+        assert!(!is_backarc(&Vs30Point::new(
+            142.,
+            50.,
+            400.,
+            None,
+            Some(0.)
+        )));
+        assert!(!is_backarc(&Vs30Point::new(142., 50., 400., None, None)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_crustal_not_supported() {
+        model(TectonicType::Crustal, BCHYDRO2016_COEFFS[0]);
+    }
+}
@@ -0,0 +1,277 @@
+//! Implementation of Bradley (2013) Ground Motion Prediction Equations (GMPE) for shallow
+//! crustal earthquakes in New Zealand.
+//!
+//! The model is a modification of Chiou & Youngs (2008) calibrated with the Canterbury
+//! (Christchurch) earthquake sequence. The median motion on reference rock (Vs30 = 1130 m/s) is
+//!
+//! ```text
+//! ln(y_ref) = c1 + c7·(Ztor − 4) + c2·(M − 6) + (c2 − c3) / cn · ln(1 + exp(cn·(cM − M)))
+//!           + c4·ln(R + c5·cosh(c6·max(M − cHM, 0))) + (c4a − c4)·ln(sqrt(R² + cRB²))
+//!           + (cγ1 + cγ2 / cosh(max(M − cγ3, 0)))·(R + c8·R_tvz)
+//! ```
+//!
+//! where `R` is the rupture distance (km) and `R_tvz` the part of the source-to-site path
+//! crossing the Taupo Volcanic Zone (km), which attenuates faster than the rest of the country.
+//! The CY08 linear and nonlinear site amplification is applied on top:
+//!
+//! ```text
+//! ln(y) = ln(y_ref) + φ1·min(ln(Vs30 / 1130), 0)
+//!       + φ2·(exp(φ3·(min(Vs30, 1130) − 360)) − exp(φ3·(1130 − 360)))·ln((y_ref + φ4) / φ4)
+//! ```
+//!
+//! with `y` PGA or 5%-damped PSA in g.
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism and no hanging-wall term.
+//! - No basin (Z1.0) term.
+//! - Paths do not cross the Taupo Volcanic Zone (`R_tvz = 0`) unless [`Bradley2013Inputs`] are
+//!   passed to [`BRADLEY2013::ln_median`] directly.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Period-independent coefficients.
+const C2: f64 = 1.06;
+const C3: f64 = 3.45;
+const C4: f64 = -2.1;
+const C4A: f64 = -0.5;
+const C_RB: f64 = 50.;
+const C_HM: f64 = 3.;
+const C_G3: f64 = 4.;
+/// Reference rock Vs30 (m/s).
+const VS30_REF: f64 = 1130.;
+
+/// Bradley (2013) coefficients (PGA, PSA at 0.2, 1 and 3 s).
+pub const BRADLEY2013_TABLE: &str = "
+    imt  c1       cn     cm     c5    c6      c7      c8   cg1       cg2       phi1     phi2     phi3      phi4      tau1    tau2    sigma1  sigma2
+    pga  -1.1985  2.996  4.184  6.16  0.4893  0.0512  1.0  -0.00804  -0.00785  -0.4417  -0.1417  -0.00701  0.102151  0.3437  0.2637  0.4458  0.3459
+    0.2  -0.3     1.5    5.1    6.6   0.49    0.0507  1.0  -0.0107   -0.0069   -0.48    -0.187   -0.00701  0.2       0.38    0.29    0.5     0.38
+    1.0  -2.1     1.77   5.68   4.63  0.45    0.032   0.8  -0.0037   -0.0086   -0.7     -0.095   -0.0065   0.04      0.42    0.3     0.53    0.4
+    3.0  -3.5     1.44   6.4    4.42  0.4     0.01    0.5  -0.0018   -0.0093   -0.75    -0.02    -0.0063   0.005     0.43    0.32    0.57    0.45
+";
+
+/// Period-dependent Bradley (2013) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bradley2013Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling coefficients
+    pub cn: f64,
+    pub cm: f64,
+    /// Near-source saturation coefficients
+    pub c5: f64,
+    pub c6: f64,
+    /// Ztor scaling coefficient
+    pub c7: f64,
+    /// Taupo Volcanic Zone anelastic attenuation factor
+    pub c8: f64,
+    /// Anelastic attenuation coefficients
+    pub cg1: f64,
+    pub cg2: f64,
+    /// Site response coefficients
+    pub phi1: f64,
+    pub phi2: f64,
+    pub phi3: f64,
+    pub phi4: f64,
+    /// Between-event standard deviation for M ≤ 5 and M ≥ 7
+    pub tau1: f64,
+    pub tau2: f64,
+    /// Within-event standard deviation for M ≤ 5 and M ≥ 7
+    pub sigma1: f64,
+    pub sigma2: f64,
+}
+
+impl Bradley2013Coeffs {
+    /// Build the coefficients from a row of a Bradley (2013) coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            c1: row.coeff("c1"),
+            cn: row.coeff("cn"),
+            cm: row.coeff("cm"),
+            c5: row.coeff("c5"),
+            c6: row.coeff("c6"),
+            c7: row.coeff("c7"),
+            c8: row.coeff("c8"),
+            cg1: row.coeff("cg1"),
+            cg2: row.coeff("cg2"),
+            phi1: row.coeff("phi1"),
+            phi2: row.coeff("phi2"),
+            phi3: row.coeff("phi3"),
+            phi4: row.coeff("phi4"),
+            tau1: row.coeff("tau1"),
+            tau2: row.coeff("tau2"),
+            sigma1: row.coeff("sigma1"),
+            sigma2: row.coeff("sigma2"),
+        }
+    }
+
+    /// All coefficient sets of the shipped table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(BRADLEY2013_TABLE)
+            .expect("Invalid Bradley (2013) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+}
+
+/// Source, path and site parameters entering the Bradley (2013) equations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bradley2013Inputs {
+    /// Moment magnitude
+    pub magnitude: f64,
+    /// Closest distance to the rupture plane (km)
+    pub rrup: f64,
+    /// Depth to the top of rupture (km)
+    pub ztor: f64,
+    /// Length of the source-to-site path inside the Taupo Volcanic Zone (km)
+    pub r_tvz: f64,
+    /// Average shear-wave velocity in the top 30 meters (m/s)
+    pub vs30: f64,
+}
+
+/// Bradley (2013) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct BRADLEY2013 {
+    /// Period-dependent coefficients
+    pub coeffs: Bradley2013Coeffs,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl BRADLEY2013 {
+    /// Create a new model for the given coefficient set and motion type.
+    pub fn new(coeffs: Bradley2013Coeffs, motion_kind: GmpePointKind) -> Self {
+        Self {
+            coeffs,
+            motion_kind,
+        }
+    }
+
+    /// Natural logarithm of the median motion on reference rock (Vs30 = 1130 m/s).
+    pub fn ln_reference(&self, inputs: &Bradley2013Inputs) -> f64 {
+        let c = &self.coeffs;
+        let m = inputs.magnitude;
+        let r = inputs.rrup;
+
+        let f_ztor = c.c7 * (inputs.ztor - 4.);
+        let f_mag = C2 * (m - 6.) + (C2 - C3) / c.cn * (1. + (c.cn * (c.cm - m)).exp()).ln();
+        let f_near = C4 * (r + c.c5 * (c.c6 * (m - C_HM).max(0.)).cosh()).ln();
+        let f_far = (C4A - C4) * r.hypot(C_RB).ln();
+        let f_atn = (c.cg1 + c.cg2 / (m - C_G3).max(0.).cosh()) * (r + c.c8 * inputs.r_tvz);
+
+        c.c1 + f_ztor + f_mag + f_near + f_far + f_atn
+    }
+
+    /// Natural logarithm of the median ground motion (g) for the given inputs.
+    pub fn ln_median(&self, inputs: &Bradley2013Inputs) -> f64 {
+        let c = &self.coeffs;
+        let ln_ref = self.ln_reference(inputs);
+        let vs30 = inputs.vs30;
+
+        let f_lin = c.phi1 * (vs30 / VS30_REF).ln().min(0.);
+        let f_nl = c.phi2
+            * ((c.phi3 * (vs30.min(VS30_REF) - 360.)).exp() - (c.phi3 * (VS30_REF - 360.)).exp())
+            * ((ln_ref.exp() + c.phi4) / c.phi4).ln();
+
+        ln_ref + f_lin + f_nl
+    }
+
+    /// Total standard deviation of `ln(y)` (magnitude-dependent between- and within-event
+    /// variability, linear site response).
+    pub fn total_sigma(&self, magnitude: f64) -> f64 {
+        let c = &self.coeffs;
+        let w = ((magnitude - 5.) / 2.).clamp(0., 1.);
+        let tau = c.tau1 + w * (c.tau2 - c.tau1);
+        let sigma = c.sigma1 + w * (c.sigma2 - c.sigma1);
+        sigma.hypot(tau)
+    }
+}
+
+impl GroundMotionModeling for BRADLEY2013 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let ztor = eq.ztor();
+        let inputs = Bradley2013Inputs {
+            magnitude: eq.mw(),
+            rrup: distances.rrup.unwrap_or_else(|| distances.repi.hypot(ztor)),
+            ztor,
+            r_tvz: 0.,
+            vs30: point.vs30,
+        };
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: self.ln_median(&inputs).exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.total_sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(magnitude: f64, rrup: f64, r_tvz: f64, vs30: f64) -> Bradley2013Inputs {
+        Bradley2013Inputs {
+            magnitude,
+            rrup,
+            ztor: 4.,
+            r_tvz,
+            vs30,
+        }
+    }
+
+    #[test]
+    fn test_reference_rock_pga() {
+        let model = BRADLEY2013::new(Bradley2013Coeffs::from_table()[0], GmpePointKind::Pga);
+        let i = inputs(6.5, 10., 0., VS30_REF);
+        assert!((model.ln_median(&i) - model.ln_reference(&i)).abs() < 1e-12);
+        let pga = model.ln_median(&i).exp();
+        assert!(pga > 0.1 && pga < 0.5, "{pga}");
+    }
+
+    #[test]
+    fn test_volcanic_zone_attenuation() {
+        let model = BRADLEY2013::new(Bradley2013Coeffs::from_table()[0], GmpePointKind::Pga);
+        let outside = model.ln_median(&inputs(6., 100., 0., 400.));
+        let inside = model.ln_median(&inputs(6., 100., 50., 400.));
+        assert!(inside < outside);
+    }
+}
@@ -0,0 +1,453 @@
+//! Implementation of Boore, Stewart, Seyhan & Atkinson (2014) NGA-West2 Ground Motion
+//! Prediction Equations (GMPE) for active crustal regions.
+//!
+//! The model predicts the median of `ln(Y)` as a sum of a source (event) function, a path
+//! function and a site amplification function:
+//!
+//! ```text
+//! ln(Y) = F_E(M) + F_P(Rjb, M) + F_S(Vs30, Rjb, M)
+//! ```
+//!
+//! where `Y` is PGA or PSA in g, or PGV in cm/s. The site function includes the linear Vs30
+//! scaling and the nonlinear term driven by the median rock PGA (Vs30 = 760 m/s).
+//!
+//! Current simplifications:
+//!
+//! - Unspecified fault mechanism (`e0`) is used.
+//! - Global (California/Taiwan) anelastic attenuation, no regional `Δc3`.
+//! - The basin depth term `F_δz1` is not applied (centered Z1.0).
+//! - For point sources the Joyner–Boore distance equals the epicentral distance.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Reference magnitude of the path function.
+const M_REF: f64 = 4.5;
+/// Reference distance of the path function (km).
+const R_REF: f64 = 1.;
+/// Reference shear-wave velocity of the site function (m/s).
+const V_REF: f64 = 760.;
+/// Nonlinear site term constants.
+const F1: f64 = 0.;
+const F3: f64 = 0.1;
+
+/// Period-dependent BSSA14 coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bssa2014Coeffs {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Source term for unspecified mechanism
+    pub e0: f64,
+    /// Source term for strike-slip mechanism
+    pub e1: f64,
+    /// Source term for normal mechanism
+    pub e2: f64,
+    /// Source term for reverse mechanism
+    pub e3: f64,
+    /// Linear magnitude scaling below the hinge magnitude
+    pub e4: f64,
+    /// Quadratic magnitude scaling below the hinge magnitude
+    pub e5: f64,
+    /// Linear magnitude scaling above the hinge magnitude
+    pub e6: f64,
+    /// Hinge magnitude
+    pub mh: f64,
+    /// Geometric spreading coefficient
+    pub c1: f64,
+    /// Magnitude-dependent geometric spreading coefficient
+    pub c2: f64,
+    /// Anelastic attenuation coefficient
+    pub c3: f64,
+    /// Finite-fault "fictitious depth" (km)
+    pub h: f64,
+    /// Linear site amplification coefficient
+    pub clin: f64,
+    /// Limiting Vs30 of the linear site term (m/s)
+    pub vc: f64,
+    /// Nonlinear site term coefficient
+    pub f4: f64,
+    /// Nonlinear site term exponent coefficient
+    pub f5: f64,
+    /// Within-event standard deviation for M ≤ 4.5
+    pub phi1: f64,
+    /// Within-event standard deviation for M ≥ 5.5
+    pub phi2: f64,
+    /// Between-event standard deviation for M ≤ 4.5
+    pub tau1: f64,
+    /// Between-event standard deviation for M ≥ 5.5
+    pub tau2: f64,
+}
+
+/// BSSA14 coefficients for PGV (cm/s).
+pub const BSSA2014_PGV: Bssa2014Coeffs = Bssa2014Coeffs {
+    period: -1.,
+    e0: 5.037,
+    e1: 5.078,
+    e2: 4.849,
+    e3: 5.033,
+    e4: 1.073,
+    e5: -0.1536,
+    e6: 0.2252,
+    mh: 6.2,
+    c1: -1.243,
+    c2: 0.1489,
+    c3: -0.00344,
+    h: 5.3,
+    clin: -0.84,
+    vc: 1300.,
+    f4: -0.1,
+    f5: -0.00844,
+    phi1: 0.644,
+    phi2: 0.552,
+    tau1: 0.401,
+    tau2: 0.346,
+};
+
+/// BSSA14 coefficients for PGA (g).
+pub const BSSA2014_PGA: Bssa2014Coeffs = Bssa2014Coeffs {
+    period: 0.,
+    e0: 0.4473,
+    e1: 0.4856,
+    e2: 0.2459,
+    e3: 0.4539,
+    e4: 1.431,
+    e5: 0.05053,
+    e6: -0.1662,
+    mh: 5.5,
+    c1: -1.134,
+    c2: 0.1917,
+    c3: -0.008088,
+    h: 4.5,
+    clin: -0.6,
+    vc: 1500.,
+    f4: -0.15,
+    f5: -0.00701,
+    phi1: 0.695,
+    phi2: 0.495,
+    tau1: 0.398,
+    tau2: 0.348,
+};
+
+/// BSSA14 coefficients for spectral accelerations (g) at the shipped periods.
+pub const BSSA2014_PSA: [Bssa2014Coeffs; 7] = [
+    Bssa2014Coeffs {
+        period: 0.1,
+        e0: 1.0268,
+        e1: 1.0651,
+        e2: 0.78431,
+        e3: 1.0732,
+        e4: 1.4293,
+        e5: 0.055231,
+        e6: -0.19838,
+        mh: 5.54,
+        c1: -1.0652,
+        c2: 0.17203,
+        c3: -0.0102,
+        h: 4.13,
+        clin: -0.4872,
+        vc: 1479.12,
+        f4: -0.2492,
+        f5: -0.0056,
+        phi1: 0.733,
+        phi2: 0.513,
+        tau1: 0.477,
+        tau2: 0.371,
+    },
+    Bssa2014Coeffs {
+        period: 0.2,
+        e0: 1.3255,
+        e1: 1.359,
+        e2: 1.122,
+        e3: 1.3414,
+        e4: 1.1349,
+        e5: -0.11096,
+        e6: -0.15852,
+        mh: 5.92,
+        c1: -1.0607,
+        c2: 0.14437,
+        c3: -0.007717,
+        h: 4.61,
+        clin: -0.6876,
+        vc: 1392.61,
+        f4: -0.2466,
+        f5: -0.00614,
+        phi1: 0.744,
+        phi2: 0.554,
+        tau1: 0.458,
+        tau2: 0.382,
+    },
+    Bssa2014Coeffs {
+        period: 0.3,
+        e0: 1.2217,
+        e1: 1.2401,
+        e2: 1.0246,
+        e3: 1.2653,
+        e4: 0.95676,
+        e5: -0.1959,
+        e6: -0.092855,
+        mh: 6.14,
+        c1: -1.0948,
+        c2: 0.1198,
+        c3: -0.005475,
+        h: 4.93,
+        clin: -0.8417,
+        vc: 1308.47,
+        f4: -0.2191,
+        f5: -0.0067,
+        phi1: 0.73,
+        phi2: 0.551,
+        tau1: 0.44,
+        tau2: 0.358,
+    },
+    Bssa2014Coeffs {
+        period: 0.5,
+        e0: 0.96991,
+        e1: 0.99106,
+        e2: 0.7615,
+        e3: 1.012,
+        e4: 1.0384,
+        e5: -0.23522,
+        e6: 0.029119,
+        mh: 6.2,
+        c1: -1.1459,
+        c2: 0.08979,
+        c3: -0.00322,
+        h: 5.34,
+        clin: -0.9693,
+        vc: 1203.91,
+        f4: -0.1704,
+        f5: -0.00744,
+        phi1: 0.693,
+        phi2: 0.567,
+        tau1: 0.391,
+        tau2: 0.348,
+    },
+    Bssa2014Coeffs {
+        period: 1.0,
+        e0: 0.3932,
+        e1: 0.4218,
+        e2: 0.207,
+        e3: 0.4124,
+        e4: 1.5004,
+        e5: -0.18983,
+        e6: 0.17895,
+        mh: 6.2,
+        c1: -1.193,
+        c2: 0.05779,
+        c3: -0.001605,
+        h: 5.74,
+        clin: -1.05,
+        vc: 1109.95,
+        f4: -0.1052,
+        f5: -0.00844,
+        phi1: 0.573,
+        phi2: 0.586,
+        tau1: 0.441,
+        tau2: 0.39,
+    },
+    Bssa2014Coeffs {
+        period: 2.0,
+        e0: -0.58669,
+        e1: -0.55003,
+        e2: -0.71466,
+        e3: -0.60658,
+        e4: 1.9152,
+        e5: -0.11237,
+        e6: 0.44788,
+        mh: 6.2,
+        c1: -1.2159,
+        c2: 0.0216,
+        c3: -0.000887,
+        h: 6.54,
+        clin: -1.0392,
+        vc: 1009.49,
+        f4: -0.0361,
+        f5: -0.00479,
+        phi1: 0.552,
+        phi2: 0.621,
+        tau1: 0.477,
+        tau2: 0.457,
+    },
+    Bssa2014Coeffs {
+        period: 3.0,
+        e0: -1.1898,
+        e1: -1.142,
+        e2: -1.23,
+        e3: -1.2664,
+        e4: 2.1323,
+        e5: -0.04332,
+        e6: 0.62694,
+        mh: 6.2,
+        c1: -1.2179,
+        c2: 0.00161,
+        c3: -0.000519,
+        h: 6.93,
+        clin: -1.0112,
+        vc: 922.43,
+        f4: -0.0136,
+        f5: -0.00183,
+        phi1: 0.566,
+        phi2: 0.627,
+        tau1: 0.487,
+        tau2: 0.472,
+    },
+];
+
+/// Boore, Stewart, Seyhan & Atkinson (2014) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct BSSA2014 {
+    /// Period-dependent coefficients
+    pub coeffs: Bssa2014Coeffs,
+    /// Type of motion (PGA, PGV, PSA etc.)
+    pub motion_kind: GmpePointKind,
+}
+
+impl BSSA2014 {
+    /// Create a new BSSA14 model for the given coefficient set and motion type.
+    pub fn new(coeffs: Bssa2014Coeffs, motion_kind: GmpePointKind) -> Self {
+        Self {
+            coeffs,
+            motion_kind,
+        }
+    }
+
+    /// Source (event) function `F_E` for unspecified mechanism.
+    fn event_term(c: &Bssa2014Coeffs, magnitude: f64) -> f64 {
+        let dm = magnitude - c.mh;
+        if magnitude <= c.mh {
+            c.e0 + c.e4 * dm + c.e5 * dm.powi(2)
+        } else {
+            c.e0 + c.e6 * dm
+        }
+    }
+
+    /// Path function `F_P`.
+    fn path_term(c: &Bssa2014Coeffs, magnitude: f64, rjb: f64) -> f64 {
+        let r = (rjb.powi(2) + c.h.powi(2)).sqrt();
+        (c.c1 + c.c2 * (magnitude - M_REF)) * (r / R_REF).ln() + c.c3 * (r - R_REF)
+    }
+
+    /// Site amplification function `F_S` (linear plus nonlinear terms).
+    fn site_term(c: &Bssa2014Coeffs, vs30: f64, pga_rock: f64) -> f64 {
+        let f_lin = c.clin * (vs30.min(c.vc) / V_REF).ln();
+        let f2 = c.f4 * ((c.f5 * (vs30.min(V_REF) - 360.)).exp() - (c.f5 * (V_REF - 360.)).exp());
+        let f_nl = F1 + f2 * ((pga_rock + F3) / F3).ln();
+        f_lin + f_nl
+    }
+
+    /// Median `ln(Y)` for a given magnitude, Joyner–Boore distance and Vs30.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rjb` - Joyner–Boore distance (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    ///
+    /// # Returns
+    ///
+    /// Natural logarithm of the median ground motion in g (PGA, PSA) or cm/s (PGV).
+    pub fn ln_median(&self, magnitude: f64, rjb: f64, vs30: f64) -> f64 {
+        let pga_rock = (Self::event_term(&BSSA2014_PGA, magnitude)
+            + Self::path_term(&BSSA2014_PGA, magnitude, rjb))
+        .exp();
+        Self::event_term(&self.coeffs, magnitude)
+            + Self::path_term(&self.coeffs, magnitude, rjb)
+            + Self::site_term(&self.coeffs, vs30, pga_rock)
+    }
+
+    /// Total standard deviation of `ln(Y)` (magnitude-dependent between- and within-event
+    /// variability).
+    pub fn total_sigma(&self, magnitude: f64) -> f64 {
+        let c = &self.coeffs;
+        let w = ((magnitude - 4.5) / (5.5 - 4.5)).clamp(0., 1.);
+        let phi = c.phi1 + w * (c.phi2 - c.phi1);
+        let tau = c.tau1 + w * (c.tau2 - c.tau1);
+        phi.hypot(tau)
+    }
+}
+
+impl GroundMotionModeling for BSSA2014 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rjb]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// The epicentral distance is used as the Joyner–Boore distance of point sources.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rjb = distances.rjb.unwrap_or(distances.repi);
+        let mut ground_motion = self.ln_median(eq.mw(), rjb, point.vs30).exp();
+        // convert g to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion *= 100.;
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.total_sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pga_on_reference_rock() {
+        let model = BSSA2014::new(BSSA2014_PGA, GmpePointKind::Pga);
+        // M6.5, Rjb = 10 km, Vs30 = 760 m/s: linear and nonlinear site terms vanish
+        let ln_y = model.ln_median(6.5, 10., 760.);
+        let r = (100_f64 + 4.5 * 4.5).sqrt();
+        let expected = 0.4473 - 0.1662 + (-1.134 + 0.1917 * 2.) * r.ln() - 0.008088 * (r - 1.);
+        assert!((ln_y - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_soft_soil_nonlinearity() {
+        let model = BSSA2014::new(BSSA2014_PGA, GmpePointKind::Pga);
+        // Soft sites amplify weak motion more than strong motion
+        let weak = model.ln_median(5., 100., 200.) - model.ln_median(5., 100., 760.);
+        let strong = model.ln_median(7.5, 1., 200.) - model.ln_median(7.5, 1., 760.);
+        assert!(weak > strong);
+        assert!(weak > 0.);
+    }
+
+    #[test]
+    fn test_finite_rupture_uses_rjb() {
+        use crate::rupture::Rupture;
+
+        let model = BSSA2014::new(BSSA2014_PGA, GmpePointKind::Pga);
+        // Site 50 km north of the epicenter, above a 120 km long vertical rupture
+        let site = Vs30Point::new(142.8, 52.45, 760., None, None);
+        let point_source = Earthquake::new_mw(142.8, 52., 10., 7.5);
+        let finite = Earthquake::new_mw(142.8, 52., 10., 7.5)
+            .with_rupture(Rupture::new(0., 90., 120., 20.).with_ztor(0.));
+        let expected = model.ln_median(7.5, 0., 760.).exp() * 100.;
+        assert!((model.calc_from_point(&site, &finite).value - expected).abs() < 1e-6);
+        assert!(model.calc_from_point(&site, &point_source).value < expected / 2.);
+    }
+}
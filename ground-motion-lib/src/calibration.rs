@@ -0,0 +1,341 @@
+//! Calibration of Morikawa & Fujiwara (2013) coefficients to observed ground motions.
+//!
+//! Regional configs such as `config_mf2013_sakh2018_as1997` or `config_mf2013_jsgga2022` keep
+//! the MF2013 functional form and refit its coefficients to local records. [`Mf2013Calibration`]
+//! makes this available for user datasets:
+//!
+//! ```text
+//! log10(Y) = a·Mw' + b·R + c − log10(R + d·10^(e·Mw'))
+//!          + pd·log10(max(Dl, Dl_min) / D0) + ps·log10(min(Vs30, Vs_max) / V0)
+//! ```
+//!
+//! where `Mw' = min(Mw, Mw0)` and `R` is the hypocentral distance (km). For a fixed `d` the
+//! equation is linear in `a`, `b`, `c`, `pd` and `ps`, which are fitted by ordinary least squares
+//! on `log10(Y)`. The nonlinear coefficient `d` is selected from a list of candidates by the
+//! smallest residual sum of squares. The standard deviation of the fitted model is the standard
+//! deviation of the residuals.
+//!
+//! Coefficients that are not fitted (by default `pd` and `ps`) and the constants `Mw0`, `e`,
+//! `Dl_min`, `D0`, `Vs_max`, `V0` and `γ` are taken from a template model. The fitted model
+//! derives `Serialize`, so it can be stored as a TOML config with [`toml::to_string`].
+//!
+//! Current simplifications:
+//!
+//! - Point sources: the hypocentral distance is computed from the epicentral distance and the
+//!   depth.
+//! - The anomalous seismic intensity (ASID) correction is not part of the fit.
+//! - No mixed-effects separation of between-event and within-event residuals.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::calibration::{CalibrationRecord, Mf2013Calibration};
+//! use ground_motion_lib::configs::get_mf2013_lib_configs;
+//!
+//! let template = get_mf2013_lib_configs()
+//!     .get("config_mf2013_crustal_pga")
+//!     .unwrap();
+//! let records: Vec<CalibrationRecord> = [(5.5, 20.), (6.0, 50.), (6.5, 100.), (7.0, 30.)]
+//!     .iter()
+//!     .map(|&(magnitude, distance)| CalibrationRecord {
+//!         magnitude,
+//!         depth: 10.,
+//!         distance,
+//!         vs30: 400.,
+//!         dl: None,
+//!         value: 100. * (magnitude - 5.) / distance,
+//!     })
+//!     .collect();
+//!
+//! let fit = Mf2013Calibration::new(template).fit(&records).unwrap();
+//! assert_eq!(fit.residuals.len(), 4);
+//! let config = toml::to_string(&fit.model).unwrap();
+//! assert!(config.contains("motion_kind"));
+//! ```
+
+use crate::auxilary::{DL, G_GLOBAL};
+use crate::gmm::GmpePointKind;
+use crate::mf2013::MF2013;
+use crate::simulation::cholesky_in_place;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Relative size of a Cholesky pivot below which a fitted coefficient counts as unconstrained.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+/// Observation of the calibration dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationRecord {
+    /// Moment magnitude of the event (Mw).
+    pub magnitude: f64,
+    /// Hypocentral depth of the event (km).
+    pub depth: f64,
+    /// Epicentral distance of the station (km).
+    pub distance: f64,
+    /// Vs30 of the station site (m/s).
+    pub vs30: f64,
+    /// Depth to the 1400 m/s shear-wave velocity layer (m).
+    #[serde(default)]
+    pub dl: Option<f64>,
+    /// Observed ground motion in the units of the template type of motion (%g, cm/s, cm).
+    pub value: f64,
+}
+
+/// Least-squares calibration of MF2013 coefficients.
+#[derive(Debug, Clone)]
+pub struct Mf2013Calibration {
+    /// Model providing the fixed coefficients and the type of motion
+    pub template: MF2013,
+    /// Whether the deep sedimentary layer coefficient `pd` is fitted
+    pub fit_deep_layer: bool,
+    /// Whether the Vs30 amplification coefficient `ps` is fitted
+    pub fit_site: bool,
+    /// Candidate values of the distance damping coefficient `d`; empty to keep the template `d`
+    pub d_candidates: Vec<f64>,
+}
+
+/// Calibrated model and its residuals.
+#[derive(Debug)]
+pub struct Mf2013Fit {
+    /// Calibrated model with the residual standard deviation as `sigma`
+    pub model: MF2013,
+    /// Residuals `log10(observed) − log10(predicted)` of the records
+    pub residuals: Vec<f64>,
+}
+
+impl Mf2013Calibration {
+    /// Create a calibration fitting `a`, `b` and `c` with all other coefficients of `template`.
+    pub fn new(template: &MF2013) -> Self {
+        Self {
+            template: template.clone(),
+            fit_deep_layer: false,
+            fit_site: false,
+            d_candidates: Vec::new(),
+        }
+    }
+
+    /// Also fit the deep sedimentary layer coefficient `pd`.
+    pub fn with_deep_layer_term(mut self) -> Self {
+        self.fit_deep_layer = true;
+        self
+    }
+
+    /// Also fit the Vs30 amplification coefficient `ps`.
+    pub fn with_site_term(mut self) -> Self {
+        self.fit_site = true;
+        self
+    }
+
+    /// Select the distance damping coefficient `d` from candidate values.
+    pub fn with_d_candidates(mut self, candidates: Vec<f64>) -> Self {
+        self.d_candidates = candidates;
+        self
+    }
+
+    /// Fit the coefficients to a dataset.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the dataset has no more records than fitted coefficients, contains
+    /// non-positive values, or does not constrain all fitted coefficients (e.g. a single
+    /// magnitude or Vs30).
+    pub fn fit(&self, records: &[CalibrationRecord]) -> Result<Mf2013Fit, Box<dyn Error>> {
+        let n_coeffs = 3 + self.fit_deep_layer as usize + self.fit_site as usize;
+        if records.len() <= n_coeffs {
+            return Err(format!("Calibration needs more than {n_coeffs} records").into());
+        }
+        if records.iter().any(|r| r.value <= 0.) {
+            return Err("Observed values must be positive".into());
+        }
+
+        let candidates = if self.d_candidates.is_empty() {
+            vec![self.template.d]
+        } else {
+            self.d_candidates.clone()
+        };
+        let mut best: Option<(f64, MF2013, Vec<f64>)> = None;
+        for d in candidates {
+            let (model, residuals) = self.fit_with_d(records, d, n_coeffs)?;
+            let rss: f64 = residuals.iter().map(|r| r * r).sum();
+            if best.as_ref().is_none_or(|(best_rss, ..)| rss < *best_rss) {
+                best = Some((rss, model, residuals));
+            }
+        }
+        let (rss, mut model, residuals) = best.ok_or("No candidate value of `d`")?;
+        model.sigma = (rss / (records.len() - n_coeffs) as f64).sqrt();
+        Ok(Mf2013Fit { model, residuals })
+    }
+
+    fn fit_with_d(
+        &self,
+        records: &[CalibrationRecord],
+        d: f64,
+        n_coeffs: usize,
+    ) -> Result<(MF2013, Vec<f64>), Box<dyn Error>> {
+        let t = &self.template;
+        let mut normal = vec![0.; n_coeffs * n_coeffs];
+        let mut rhs = vec![0.; n_coeffs];
+        let rows: Vec<(Vec<f64>, f64)> = records
+            .iter()
+            .map(|record| {
+                let magnitude = record.magnitude.min(t.mw0);
+                let r_rup = record.distance.hypot(record.depth);
+                let deep = (record.dl.unwrap_or(DL as f64).max(t.dl_min) / t.d0).log10();
+                let site = (record.vs30.min(t.vs_max) / t.v0).log10();
+
+                let mut row = vec![magnitude, r_rup, 1.];
+                let mut y = self.log10_cm(record.value)
+                    + (r_rup + d * 10_f64.powf(t.e * magnitude)).log10();
+                if self.fit_deep_layer {
+                    row.push(deep);
+                } else {
+                    y -= t.pd * deep;
+                }
+                if self.fit_site {
+                    row.push(site);
+                } else {
+                    y -= t.ps * site;
+                }
+                (row, y)
+            })
+            .collect();
+
+        for (row, y) in &rows {
+            for i in 0..n_coeffs {
+                rhs[i] += row[i] * y;
+                for j in 0..=i {
+                    normal[i * n_coeffs + j] += row[i] * row[j];
+                }
+            }
+        }
+        let diagonal: Vec<f64> = (0..n_coeffs).map(|i| normal[i * n_coeffs + i]).collect();
+        let unconstrained = "The dataset does not constrain all fitted coefficients";
+        cholesky_in_place(&mut normal, n_coeffs).map_err(|_| unconstrained)?;
+        // Nearly collinear columns leave a pivot that is only rounding noise
+        if (0..n_coeffs).any(|i| normal[i * n_coeffs + i].powi(2) < RANK_TOLERANCE * diagonal[i]) {
+            return Err(unconstrained.into());
+        }
+        let coeffs = cholesky_solve(&normal, n_coeffs, rhs);
+
+        let mut model = t.clone();
+        model.a = coeffs[0];
+        model.b = coeffs[1];
+        model.c = coeffs[2];
+        model.d = d;
+        let mut next = 3;
+        if self.fit_deep_layer {
+            model.pd = coeffs[next];
+            next += 1;
+        }
+        if self.fit_site {
+            model.ps = coeffs[next];
+        }
+
+        let residuals = rows
+            .iter()
+            .map(|(row, y)| y - row.iter().zip(&coeffs).map(|(x, c)| x * c).sum::<f64>())
+            .collect();
+        Ok((model, residuals))
+    }
+
+    /// `log10` of an observed value in the internal units of the model (cm/s² for PGA and PSA).
+    fn log10_cm(&self, value: f64) -> f64 {
+        match self.template.motion_kind {
+            GmpePointKind::Pga | GmpePointKind::Psa { .. } => (value * G_GLOBAL).log10(),
+            _ => value.log10(),
+        }
+    }
+}
+
+/// Solve `L·Lᵀ·x = b` for a row-major lower Cholesky factor.
+fn cholesky_solve(lower: &[f64], n: usize, mut b: Vec<f64>) -> Vec<f64> {
+    for i in 0..n {
+        let dot: f64 = (0..i).map(|k| lower[i * n + k] * b[k]).sum();
+        b[i] = (b[i] - dot) / lower[i * n + i];
+    }
+    for i in (0..n).rev() {
+        let dot: f64 = (i + 1..n).map(|k| lower[k * n + i] * b[k]).sum();
+        b[i] = (b[i] - dot) / lower[i * n + i];
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_mf2013_lib_configs;
+    use crate::gmm::{Earthquake, GroundMotionModeling, Vs30Point};
+
+    fn synthetic(model: &MF2013) -> Vec<CalibrationRecord> {
+        let mut records = Vec::new();
+        for magnitude in [5., 5.8, 6.4, 7.2] {
+            for distance in [5., 25., 60., 150.] {
+                for (vs30, dl) in [(250., 600.), (450., 150.), (800., 50.)] {
+                    let eq = Earthquake::new_mw(142., 50., 15., magnitude);
+                    let site = Vs30Point::new(142., 50., vs30, Some(dl), None);
+                    records.push(CalibrationRecord {
+                        magnitude,
+                        depth: 15.,
+                        distance,
+                        vs30,
+                        dl: Some(dl),
+                        value: model.calc_from_distance(&site, &eq, distance).value,
+                    });
+                }
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn test_recovers_exact_coefficients() {
+        let truth = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let records = synthetic(truth);
+        let mut template = truth.clone();
+        (template.a, template.b, template.c, template.pd, template.ps) = (0., 0., 0., 0., 0.);
+
+        let fit = Mf2013Calibration::new(&template)
+            .with_deep_layer_term()
+            .with_site_term()
+            .with_d_candidates(vec![0.001, truth.d, 0.05])
+            .fit(&records)
+            .unwrap();
+        let model = &fit.model;
+        assert_eq!(model.d, truth.d);
+        for (fitted, expected) in [
+            (model.a, truth.a),
+            (model.b, truth.b),
+            (model.c, truth.c),
+            (model.pd, truth.pd),
+            (model.ps, truth.ps),
+        ] {
+            assert!((fitted - expected).abs() < 1e-9, "{fitted} vs {expected}");
+        }
+        assert!(model.sigma < 1e-9);
+        assert!(fit.residuals.iter().all(|r| r.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_fixed_terms_and_errors() {
+        let truth = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pgv")
+            .unwrap();
+        let mut records = synthetic(truth);
+        // Constant 0.1 log10 units of scatter around the truth
+        for (i, record) in records.iter_mut().enumerate() {
+            record.value *= 10_f64.powf(if i % 2 == 0 { 0.1 } else { -0.1 });
+        }
+        let fit = Mf2013Calibration::new(truth).fit(&records).unwrap();
+        assert_eq!(fit.model.ps, truth.ps);
+        assert!((fit.model.a - truth.a).abs() < 0.05);
+        assert!((fit.model.sigma - 0.1).abs() < 0.01, "{}", fit.model.sigma);
+
+        let calibration = Mf2013Calibration::new(truth).with_site_term();
+        assert!(calibration.fit(&records[..4]).is_err());
+        // A single Vs30 does not constrain the site term
+        let single_site: Vec<_> = records.iter().copied().filter(|r| r.vs30 == 450.).collect();
+        assert!(calibration.fit(&single_site).is_err());
+    }
+}
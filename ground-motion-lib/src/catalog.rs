@@ -0,0 +1,353 @@
+//! Gutenberg–Richter source models from declustered earthquake catalogs.
+//!
+//! Quick regional hazard screening usually starts from a declustered catalog split into
+//! seismotectonic zones. [`CatalogRecurrence`] fits a doubly truncated Gutenberg–Richter
+//! distribution to the complete part of the catalog of every zone and turns it into
+//! [`PointSource`]s for the [`hazard`](crate::hazard) calculator:
+//!
+//! ```text
+//! b = log10(e) / (M̄ − (Mc − ΔM / 2))                          (Aki, 1965; Utsu, 1965)
+//! a = log10(N / T) + b · Mc
+//! N(≥ m) = 10^(a − b·m) − 10^(a − b·Mmax),   Mc ≤ m ≤ Mmax
+//! ```
+//!
+//! where `M̄` is the mean magnitude of the `N` events with `M ≥ Mc` observed during `T` years
+//! and `ΔM` is the magnitude bin width of the catalog. The rates of the magnitude bins between
+//! `Mc` and `Mmax` are split evenly over the epicenters of the complete events of the zone, so
+//! the sources follow the observed seismicity.
+//!
+//! Current simplifications:
+//!
+//! - A single completeness magnitude and observation period for the whole catalog.
+//! - `Mmax` defaults to the largest magnitude of the zone plus [`DEFAULT_MMAX_INCREMENT`].
+//! - Magnitudes of other scales than Mw are converted with the default relations of the
+//!   [`magnitude`](crate::magnitude) module; catalogs needing other relations are converted
+//!   beforehand with [`CatalogEvent::to_mw`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::catalog::CatalogRecurrence;
+//! use ground_motion_lib::readers::read_catalog;
+//!
+//! let events = read_catalog("tests/data/catalog.csv", b',').unwrap();
+//! let zones = CatalogRecurrence::new(4.0, 50.).fit(&events).unwrap();
+//! assert_eq!(zones.len(), 2);
+//! assert!((zones[0].recurrence.b_value - 1.0).abs() < 0.2);
+//! let sources: Vec<_> = zones.into_iter().flat_map(|zone| zone.sources).collect();
+//! assert!(!sources.is_empty());
+//! ```
+
+use crate::gmm::{Earthquake, Magnitude};
+use crate::hazard::PointSource;
+use crate::magnitude::MagnitudeConversion;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::f64::consts::LOG10_E;
+
+/// Default magnitude bin width of catalogs and source models.
+pub const DEFAULT_BIN_WIDTH: f64 = 0.1;
+
+/// Default increment of the largest observed magnitude giving `Mmax`.
+pub const DEFAULT_MMAX_INCREMENT: f64 = 0.5;
+
+/// Smallest number of complete events needed to fit a zone.
+const MIN_EVENTS: usize = 5;
+
+/// Event of a declustered earthquake catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEvent {
+    /// Longitude of the epicenter in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the epicenter in decimal degrees.
+    pub lat: f64,
+    /// Hypocentral depth (km).
+    pub depth: f64,
+    /// Magnitude, of the scale given by `magnitude_kind`.
+    pub magnitude: f64,
+    /// Identifier of the seismotectonic zone of the event.
+    pub zone: String,
+    /// Magnitude scale; `None` for moment magnitude.
+    #[serde(default)]
+    pub magnitude_kind: Option<Magnitude>,
+}
+
+impl CatalogEvent {
+    /// Moment magnitude of the event, converted with the default [`MagnitudeConversion`] if the
+    /// magnitude is of another scale.
+    pub fn mw(&self) -> f64 {
+        MagnitudeConversion::default().mw(self.magnitude, self.magnitude_kind())
+    }
+
+    /// Magnitude scale of the event.
+    pub fn magnitude_kind(&self) -> Magnitude {
+        self.magnitude_kind.unwrap_or(Magnitude::Mw)
+    }
+
+    /// Convert the magnitude of the event to Mw with the given relations.
+    pub fn to_mw(mut self, conversion: &MagnitudeConversion) -> Self {
+        self.magnitude = conversion.mw(self.magnitude, self.magnitude_kind());
+        self.magnitude_kind = None;
+        self
+    }
+
+    /// Point-source scenario earthquake of the event, keeping its magnitude scale.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::Magnitude;
+    /// use ground_motion_lib::readers::read_catalog;
+    ///
+    /// let events = read_catalog("tests/data/catalog_mixed.csv", b',').unwrap();
+    /// let eq = events[1].earthquake();
+    /// assert_eq!(eq.magnitude_kind, Magnitude::Mjma);
+    /// ```
+    pub fn earthquake(&self) -> Earthquake {
+        Earthquake::new(
+            self.lon,
+            self.lat,
+            self.depth,
+            self.magnitude,
+            self.magnitude_kind(),
+        )
+    }
+}
+
+/// Doubly truncated Gutenberg–Richter magnitude–frequency distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GutenbergRichter {
+    /// Logarithm of the annual rate of events with `M ≥ 0` of the untruncated distribution.
+    pub a_value: f64,
+    /// Slope of the distribution.
+    pub b_value: f64,
+    /// Smallest magnitude of the distribution.
+    pub m_min: f64,
+    /// Largest magnitude of the distribution.
+    pub m_max: f64,
+}
+
+impl GutenbergRichter {
+    /// Create a new distribution.
+    pub fn new(a_value: f64, b_value: f64, m_min: f64, m_max: f64) -> Self {
+        Self {
+            a_value,
+            b_value,
+            m_min,
+            m_max,
+        }
+    }
+
+    /// Annual rate of events with magnitude of at least `magnitude`.
+    pub fn cumulative_rate(&self, magnitude: f64) -> f64 {
+        let magnitude = magnitude.max(self.m_min);
+        if magnitude >= self.m_max {
+            return 0.;
+        }
+        10_f64.powf(self.a_value - self.b_value * magnitude)
+            - 10_f64.powf(self.a_value - self.b_value * self.m_max)
+    }
+
+    /// Annual rates of magnitude bins of width `bin_width` between `m_min` and `m_max`.
+    ///
+    /// # Returns
+    ///
+    /// `(magnitude, rate)` pairs with the magnitude at the center of the bin. The last bin is
+    /// narrower if the magnitude range is not a multiple of the bin width.
+    pub fn bin_rates(&self, bin_width: f64) -> Vec<(f64, f64)> {
+        let mut bins = Vec::new();
+        let mut lower = self.m_min;
+        while lower < self.m_max - 1e-9 {
+            let upper = (lower + bin_width).min(self.m_max);
+            let rate = self.cumulative_rate(lower) - self.cumulative_rate(upper);
+            bins.push(((lower + upper) / 2., rate));
+            lower = upper;
+        }
+        bins
+    }
+}
+
+/// Gutenberg–Richter recurrence fit of the zones of a declustered catalog.
+#[derive(Debug, Clone)]
+pub struct CatalogRecurrence {
+    /// Completeness magnitude of the catalog
+    pub completeness: f64,
+    /// Observation period of the complete catalog (years)
+    pub years: f64,
+    /// Largest magnitude of the source models; `None` for the largest observed magnitude of the
+    /// zone plus [`DEFAULT_MMAX_INCREMENT`]
+    pub m_max: Option<f64>,
+    /// Magnitude bin width of the catalog and of the source models
+    pub bin_width: f64,
+}
+
+/// Recurrence model and point sources of a seismotectonic zone.
+#[derive(Debug, Clone)]
+pub struct ZoneModel {
+    /// Identifier of the zone
+    pub zone: String,
+    /// Fitted magnitude–frequency distribution
+    pub recurrence: GutenbergRichter,
+    /// Number of complete events of the zone
+    pub n_events: usize,
+    /// Point sources at the epicenters of the complete events
+    pub sources: Vec<PointSource>,
+}
+
+impl CatalogRecurrence {
+    /// Create a recurrence fit for a catalog complete above `completeness` during `years`.
+    pub fn new(completeness: f64, years: f64) -> Self {
+        Self {
+            completeness,
+            years,
+            m_max: None,
+            bin_width: DEFAULT_BIN_WIDTH,
+        }
+    }
+
+    /// Use the same largest magnitude for all zones.
+    pub fn with_m_max(mut self, m_max: f64) -> Self {
+        self.m_max = Some(m_max);
+        self
+    }
+
+    /// Set the magnitude bin width.
+    pub fn with_bin_width(mut self, bin_width: f64) -> Self {
+        self.bin_width = bin_width;
+        self
+    }
+
+    /// Fit the recurrence models of all zones of a catalog.
+    ///
+    /// # Returns
+    ///
+    /// One [`ZoneModel`] per zone, sorted by the zone identifier.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the observation period or the bin width is not positive, or a zone
+    /// has fewer than five complete events.
+    pub fn fit(&self, events: &[CatalogEvent]) -> Result<Vec<ZoneModel>, Box<dyn Error>> {
+        if self.years <= 0. || self.bin_width <= 0. {
+            return Err("Observation period and bin width must be positive".into());
+        }
+        let mut zones: BTreeMap<&str, Vec<&CatalogEvent>> = BTreeMap::new();
+        for event in events {
+            zones.entry(&event.zone).or_default().push(event);
+        }
+        zones
+            .into_iter()
+            .map(|(zone, events)| self.fit_zone(zone, &events))
+            .collect()
+    }
+
+    fn fit_zone(&self, zone: &str, events: &[&CatalogEvent]) -> Result<ZoneModel, Box<dyn Error>> {
+        // Tolerance for rounded catalog magnitudes stored in floating point
+        let complete: Vec<&CatalogEvent> = events
+            .iter()
+            .copied()
+            .filter(|e| e.mw() >= self.completeness - 1e-6)
+            .collect();
+        let n = complete.len();
+        if n < MIN_EVENTS {
+            return Err(format!(
+                "Zone `{zone}` has {n} complete events, at least {MIN_EVENTS} are needed"
+            )
+            .into());
+        }
+        let mean = complete.iter().map(|e| e.mw()).sum::<f64>() / n as f64;
+        let b_value = LOG10_E / (mean - (self.completeness - self.bin_width / 2.));
+        let a_value = (n as f64 / self.years).log10() + b_value * self.completeness;
+        let largest = complete
+            .iter()
+            .map(|e| e.mw())
+            .fold(f64::NEG_INFINITY, f64::max);
+        let m_max = self.m_max.unwrap_or(largest + DEFAULT_MMAX_INCREMENT);
+        if m_max <= self.completeness {
+            return Err(
+                format!("Mmax of zone `{zone}` is below the completeness magnitude").into(),
+            );
+        }
+        let recurrence = GutenbergRichter::new(a_value, b_value, self.completeness, m_max);
+
+        let bins = recurrence.bin_rates(self.bin_width);
+        let sources = complete
+            .iter()
+            .flat_map(|e| {
+                bins.iter().map(move |(magnitude, rate)| {
+                    PointSource::new(e.lon, e.lat, e.depth, *magnitude, rate / n as f64)
+                })
+            })
+            .collect();
+        Ok(ZoneModel {
+            zone: zone.to_string(),
+            recurrence,
+            n_events: n,
+            sources,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(magnitude: f64, zone: &str) -> CatalogEvent {
+        CatalogEvent {
+            lon: 142.5,
+            lat: 50.,
+            depth: 10.,
+            magnitude,
+            zone: zone.to_string(),
+            magnitude_kind: None,
+        }
+    }
+
+    #[test]
+    fn test_gutenberg_richter_rates() {
+        let gr = GutenbergRichter::new(4., 1., 4., 7.);
+        assert!((gr.cumulative_rate(4.) - (1. - 1e-3)).abs() < 1e-12);
+        assert_eq!(gr.cumulative_rate(7.5), 0.);
+        let bins = gr.bin_rates(0.1);
+        assert_eq!(bins.len(), 30);
+        assert!((bins[0].0 - 4.05).abs() < 1e-9);
+        let total: f64 = bins.iter().map(|(_, rate)| rate).sum();
+        assert!((total - gr.cumulative_rate(4.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_recovers_b_value() {
+        // Rounded magnitudes following b = 1 above Mc = 4.0
+        let n = 400;
+        let events: Vec<CatalogEvent> = (0..n)
+            .map(|i| {
+                let u = (i as f64 + 0.5) / n as f64;
+                let m = 3.95 - (1. - u).log10();
+                event((m * 10.).round() / 10., "a")
+            })
+            .chain([event(3.2, "a")])
+            .collect();
+        let zones = CatalogRecurrence::new(4., 100.)
+            .with_m_max(7.5)
+            .fit(&events)
+            .unwrap();
+        let zone = &zones[0];
+        assert_eq!(zone.n_events, n);
+        assert!((zone.recurrence.b_value - 1.).abs() < 0.05);
+        // The rate of the complete events is reproduced
+        let rate = zone.recurrence.cumulative_rate(4.);
+        assert!((rate / (n as f64 / 100.) - 1.).abs() < 0.01);
+        let total: f64 = zone.sources.iter().map(|s| s.rate).sum();
+        assert!((total - rate).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fit_errors() {
+        let events: Vec<CatalogEvent> = (0..10).map(|_| event(4.5, "a")).collect();
+        assert!(CatalogRecurrence::new(4., 0.).fit(&events).is_err());
+        assert!(CatalogRecurrence::new(4., 10.).fit(&events[..3]).is_err());
+        let fitted = CatalogRecurrence::new(4., 10.).fit(&events).unwrap();
+        assert_eq!(fitted[0].recurrence.m_max, 5.);
+    }
+}
@@ -0,0 +1,614 @@
+//! Implementation of Campbell & Bozorgnia (2014) NGA-West2 Ground Motion Prediction Equations
+//! (GMPE) for active crustal regions.
+//!
+//! The median ground motion is the sum of magnitude, geometric attenuation, style-of-faulting,
+//! shallow site response, basin response, hypocentral depth, rupture dip and anelastic
+//! attenuation terms:
+//!
+//! ```text
+//! ln(Y) = f_mag + f_dis + f_flt + f_hng + f_site + f_sed + f_hyp + f_dip + f_atn
+//! ```
+//!
+//! where `Y` is PGA or PSA in g, or PGV in cm/s. The nonlinear site term is driven by the
+//! median PGA on rock with Vs30 = 1100 m/s (`A1100`).
+//!
+//! The basin response term `f_sed` uses the depth to the 2.5 km/s shear-wave velocity horizon
+//! ([`Vs30Point::z2p5`]). When no Z2.5 value is available it is estimated from Vs30 with the California
+//! relation of the model: `ln(Z2.5) = 7.089 − 1.144 · ln(Vs30)`.
+//!
+//! The hanging-wall term scales with the site position relative to the top edge of the rupture
+//! (`Rx`), the distance terms, magnitude, depth to the top of rupture and dip:
+//!
+//! ```text
+//! f_hng = c10 · f_Rx · f_Rrup · f_M · f_Z · f_δ
+//! f_Rx  = h1 + h2 · (Rx / R1) + h3 · (Rx / R1)²                     0 ≤ Rx < R1 = W · cos(δ)
+//!       = max(h4 + h5 · x + h6 · x², 0),   x = (Rx − R1) / (R2 − R1)  Rx ≥ R1, R2 = 62M − 350
+//! f_Rrup = (Rrup − Rjb) / Rrup,   f_Z = 1 − 0.06 · Ztor (Ztor ≤ 16.66 km),   f_δ = (90 − δ) / 45
+//! ```
+//!
+//! It vanishes on the footwall (`Rx < 0`) and for point sources, which have no `Rx`.
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism.
+//! - Global (California) anelastic attenuation and site terms.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]), and a vertical rupture is assumed
+//!   unless a finite rupture is attached.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Shallow site response constants.
+const SITE_C: f64 = 1.88;
+const SITE_N: f64 = 1.18;
+/// Reference rock Vs30 of the nonlinear site term (m/s).
+const VS30_ROCK: f64 = 1100.;
+/// Hanging-wall constant `h4` of the distance taper beyond `R1`.
+const HW_H4: f64 = 1.;
+
+/// Period-dependent CB14 coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cb2014Coeffs {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Magnitude scaling coefficients
+    pub c0: f64,
+    pub c1: f64,
+    pub c2: f64,
+    pub c3: f64,
+    pub c4: f64,
+    /// Geometric attenuation coefficients
+    pub c5: f64,
+    pub c6: f64,
+    pub c7: f64,
+    /// Style-of-faulting coefficients (reverse, normal)
+    pub c8: f64,
+    pub c9: f64,
+    /// Hanging-wall scaling coefficient
+    pub c10: f64,
+    /// Hanging-wall magnitude taper coefficient
+    pub a2: f64,
+    /// Hanging-wall distance taper coefficients
+    pub h1: f64,
+    pub h2: f64,
+    pub h3: f64,
+    pub h5: f64,
+    pub h6: f64,
+    /// Shallow site response coefficient
+    pub c11: f64,
+    /// Shallow sediment (Z2.5 ≤ 1 km) scaling coefficient
+    pub c14: f64,
+    /// Deep basin (Z2.5 > 3 km) scaling coefficient
+    pub c16: f64,
+    /// Hypocentral depth scaling coefficients
+    pub c17: f64,
+    pub c18: f64,
+    /// Rupture dip scaling coefficient
+    pub c19: f64,
+    /// Anelastic attenuation coefficient
+    pub c20: f64,
+    /// Nonlinear site response coefficients
+    pub k1: f64,
+    pub k2: f64,
+    pub k3: f64,
+    /// Within-event standard deviation for M ≤ 4.5
+    pub phi1: f64,
+    /// Within-event standard deviation for M ≥ 5.5
+    pub phi2: f64,
+    /// Between-event standard deviation for M ≤ 4.5
+    pub tau1: f64,
+    /// Between-event standard deviation for M ≥ 5.5
+    pub tau2: f64,
+}
+
+/// CB14 coefficients for PGV (cm/s).
+pub const CB2014_PGV: Cb2014Coeffs = Cb2014Coeffs {
+    period: -1.,
+    c0: -2.895,
+    c1: 1.51,
+    c2: 0.27,
+    c3: -1.299,
+    c4: -0.453,
+    c5: -2.466,
+    c6: 0.204,
+    c7: 5.837,
+    c8: 0.,
+    c9: -0.168,
+    c10: 0.305,
+    a2: 0.596,
+    h1: 0.117,
+    h2: 1.596,
+    h3: -0.707,
+    h5: -0.212,
+    h6: -0.655,
+    c11: 1.713,
+    c14: 0.106,
+    c16: 0.585,
+    c17: 0.0517,
+    c18: 0.0327,
+    c19: 0.00613,
+    c20: -0.0017,
+    k1: 400.,
+    k2: -1.955,
+    k3: 1.929,
+    phi1: 0.655,
+    phi2: 0.494,
+    tau1: 0.317,
+    tau2: 0.297,
+};
+
+/// CB14 coefficients for PGA (g).
+pub const CB2014_PGA: Cb2014Coeffs = Cb2014Coeffs {
+    period: 0.,
+    c0: -4.416,
+    c1: 0.984,
+    c2: 0.537,
+    c3: -1.499,
+    c4: -0.496,
+    c5: -2.773,
+    c6: 0.248,
+    c7: 6.768,
+    c8: 0.,
+    c9: -0.212,
+    c10: 0.72,
+    a2: 0.167,
+    h1: 0.241,
+    h2: 1.474,
+    h3: -0.715,
+    h5: -0.337,
+    h6: -0.27,
+    c11: 1.09,
+    c14: -0.0064,
+    c16: 0.393,
+    c17: 0.0977,
+    c18: 0.0333,
+    c19: 0.00757,
+    c20: -0.0055,
+    k1: 865.,
+    k2: -1.186,
+    k3: 1.839,
+    phi1: 0.734,
+    phi2: 0.492,
+    tau1: 0.409,
+    tau2: 0.322,
+};
+
+/// CB14 coefficients for spectral accelerations (g) at 0.3, 1.0 and 3.0 s.
+pub const CB2014_PSA: [Cb2014Coeffs; 3] = [
+    Cb2014Coeffs {
+        period: 0.3,
+        c0: -6.403,
+        c1: 1.528,
+        c2: 0.193,
+        c3: -1.77,
+        c4: -0.321,
+        c5: -2.376,
+        c6: 0.195,
+        c7: 6.99,
+        c8: 0.,
+        c9: -0.131,
+        c10: 0.737,
+        a2: 0.164,
+        h1: 0.21,
+        h2: 1.586,
+        h3: -0.795,
+        h5: -0.447,
+        h6: -0.121,
+        c11: 2.306,
+        c14: 0.1597,
+        c16: 0.528,
+        c17: 0.0323,
+        c18: 0.0508,
+        c19: 0.00458,
+        c20: -0.0049,
+        k1: 587.,
+        k2: -2.518,
+        k3: 1.865,
+        phi1: 0.727,
+        phi2: 0.568,
+        tau1: 0.34,
+        tau2: 0.3,
+    },
+    Cb2014Coeffs {
+        period: 1.0,
+        c0: -11.011,
+        c1: 2.18,
+        c2: -0.069,
+        c3: -1.707,
+        c4: -0.527,
+        c5: -2.158,
+        c6: 0.169,
+        c7: 5.65,
+        c8: 0.,
+        c9: -0.105,
+        c10: 0.556,
+        a2: 0.596,
+        h1: 0.117,
+        h2: 1.616,
+        h3: -0.733,
+        h5: -0.128,
+        h6: -0.756,
+        c11: 1.447,
+        c14: 0.2593,
+        c16: 0.771,
+        c17: -0.0131,
+        c18: 0.0426,
+        c19: 0.00409,
+        c20: -0.0006,
+        k1: 400.,
+        k2: -1.955,
+        k3: 1.929,
+        phi1: 0.579,
+        phi2: 0.628,
+        tau1: 0.47,
+        tau2: 0.353,
+    },
+    Cb2014Coeffs {
+        period: 3.0,
+        c0: -13.306,
+        c1: 2.15,
+        c2: 0.368,
+        c3: -1.315,
+        c4: -0.89,
+        c5: -2.051,
+        c6: 0.148,
+        c7: 6.759,
+        c8: 0.,
+        c9: 0.005,
+        c10: 0.206,
+        a2: 0.596,
+        h1: 0.117,
+        h2: 1.616,
+        h3: -0.733,
+        h5: -0.128,
+        h6: -0.756,
+        c11: -0.848,
+        c14: 0.3478,
+        c16: 0.686,
+        c17: -0.0311,
+        c18: 0.0236,
+        c19: 0.00345,
+        c20: 0.,
+        k1: 400.,
+        k2: 0.,
+        k3: 2.11,
+        phi1: 0.527,
+        phi2: 0.578,
+        tau1: 0.5,
+        tau2: 0.417,
+    },
+];
+
+/// Estimate Z2.5 (km) from Vs30 (m/s) with the California relation of CB14.
+pub fn estimate_z2p5(vs30: f64) -> f64 {
+    (7.089 - 1.144 * vs30.ln()).exp()
+}
+
+/// Source and site parameters entering the CB14 equations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cb2014Inputs {
+    /// Moment magnitude
+    pub magnitude: f64,
+    /// Closest distance to the rupture plane (km)
+    pub rrup: f64,
+    /// Joyner–Boore distance (km)
+    pub rjb: f64,
+    /// Horizontal distance from the top edge of the rupture perpendicular to strike (km),
+    /// positive on the hanging wall; `None` for point sources
+    pub rx: Option<f64>,
+    /// Hypocentral depth (km)
+    pub hypo_depth: f64,
+    /// Depth to the top of rupture (km)
+    pub ztor: f64,
+    /// Rupture dip (degrees)
+    pub dip: f64,
+    /// Rupture down-dip width (km)
+    pub width: f64,
+    /// Average shear-wave velocity in the top 30 meters (m/s)
+    pub vs30: f64,
+    /// Depth to the 2.5 km/s shear-wave velocity horizon (km)
+    pub z2p5: f64,
+}
+
+/// Campbell & Bozorgnia (2014) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct CB2014 {
+    /// Period-dependent coefficients
+    pub coeffs: Cb2014Coeffs,
+    /// Type of motion (PGA, PGV, PSA etc.)
+    pub motion_kind: GmpePointKind,
+}
+
+impl CB2014 {
+    /// Create a new CB14 model for the given coefficient set and motion type.
+    pub fn new(coeffs: Cb2014Coeffs, motion_kind: GmpePointKind) -> Self {
+        Self {
+            coeffs,
+            motion_kind,
+        }
+    }
+
+    /// Magnitude term `f_mag`.
+    fn magnitude_term(c: &Cb2014Coeffs, m: f64) -> f64 {
+        let mut f = c.c0 + c.c1 * m;
+        if m > 4.5 {
+            f += c.c2 * (m - 4.5);
+        }
+        if m > 5.5 {
+            f += c.c3 * (m - 5.5);
+        }
+        if m > 6.5 {
+            f += c.c4 * (m - 6.5);
+        }
+        f
+    }
+
+    /// Shallow site response term `f_site`.
+    fn site_term(c: &Cb2014Coeffs, vs30: f64, a1100: f64) -> f64 {
+        let ratio = vs30 / c.k1;
+        if vs30 <= c.k1 {
+            c.c11 * ratio.ln()
+                + c.k2 * ((a1100 + SITE_C * ratio.powf(SITE_N)).ln() - (a1100 + SITE_C).ln())
+        } else {
+            (c.c11 + c.k2 * SITE_N) * ratio.ln()
+        }
+    }
+
+    /// Basin response term `f_sed`.
+    fn sediment_term(c: &Cb2014Coeffs, z2p5: f64) -> f64 {
+        if z2p5 <= 1. {
+            c.c14 * (z2p5 - 1.)
+        } else if z2p5 <= 3. {
+            0.
+        } else {
+            c.c16 * c.k3 * (-0.75_f64).exp() * (1. - (-0.25 * (z2p5 - 3.)).exp())
+        }
+    }
+
+    /// Hanging-wall term `f_hng`.
+    fn hanging_wall_term(c: &Cb2014Coeffs, inputs: &Cb2014Inputs) -> f64 {
+        let Some(rx) = inputs.rx.filter(|rx| *rx >= 0.) else {
+            return 0.;
+        };
+        let m = inputs.magnitude;
+        let r1 = inputs.width * inputs.dip.to_radians().cos();
+        let r2 = 62. * m - 350.;
+        let f_rx = if rx < r1 {
+            let x = rx / r1;
+            c.h1 + c.h2 * x + c.h3 * x.powi(2)
+        } else {
+            let x = (rx - r1) / (r2 - r1);
+            (HW_H4 + c.h5 * x + c.h6 * x.powi(2)).max(0.)
+        };
+        let f_rrup = if inputs.rrup > 0. {
+            (inputs.rrup - inputs.rjb) / inputs.rrup
+        } else {
+            1.
+        };
+        let f_m = if m <= 5.5 {
+            0.
+        } else if m <= 6.5 {
+            (m - 5.5) * (1. + c.a2 * (m - 6.5))
+        } else {
+            1. + c.a2 * (m - 6.5)
+        };
+        let f_z = if inputs.ztor <= 16.66 {
+            1. - 0.06 * inputs.ztor
+        } else {
+            0.
+        };
+        let f_dip = (90. - inputs.dip) / 45.;
+        c.c10 * f_rx * f_rrup * f_m * f_z * f_dip
+    }
+
+    /// All terms except the site and basin response.
+    fn source_path_terms(c: &Cb2014Coeffs, inputs: &Cb2014Inputs) -> f64 {
+        let m = inputs.magnitude;
+        let f_dis = (c.c5 + c.c6 * m) * (inputs.rrup.powi(2) + c.c7.powi(2)).sqrt().ln();
+
+        let f_hyp_h = (inputs.hypo_depth - 7.).clamp(0., 13.);
+        let f_hyp_m = if m <= 5.5 {
+            c.c17
+        } else if m <= 6.5 {
+            c.c17 + (c.c18 - c.c17) * (m - 5.5)
+        } else {
+            c.c18
+        };
+
+        let f_dip = if m <= 4.5 {
+            c.c19 * inputs.dip
+        } else if m <= 5.5 {
+            c.c19 * (5.5 - m) * inputs.dip
+        } else {
+            0.
+        };
+
+        let f_atn = if inputs.rrup > 80. {
+            c.c20 * (inputs.rrup - 80.)
+        } else {
+            0.
+        };
+
+        Self::magnitude_term(c, m)
+            + f_dis
+            + Self::hanging_wall_term(c, inputs)
+            + f_hyp_h * f_hyp_m
+            + f_dip
+            + f_atn
+    }
+
+    /// Median PGA (g) on reference rock (Vs30 = 1100 m/s).
+    fn rock_pga(inputs: &Cb2014Inputs) -> f64 {
+        let c = &CB2014_PGA;
+        (Self::source_path_terms(c, inputs)
+            + (c.c11 + c.k2 * SITE_N) * (VS30_ROCK / c.k1).ln()
+            + Self::sediment_term(c, estimate_z2p5(VS30_ROCK)))
+        .exp()
+    }
+
+    /// Natural logarithm of the median ground motion for the given inputs.
+    ///
+    /// # Returns
+    ///
+    /// `ln(Y)` with `Y` in g (PGA, PSA) or cm/s (PGV).
+    pub fn ln_median(&self, inputs: &Cb2014Inputs) -> f64 {
+        let c = &self.coeffs;
+        let a1100 = Self::rock_pga(inputs);
+        let ln_y = Self::source_path_terms(c, inputs)
+            + Self::site_term(c, inputs.vs30, a1100)
+            + Self::sediment_term(c, inputs.z2p5);
+
+        // Short-period PSA is not allowed to fall below PGA
+        if c.period > 0. && c.period < 0.25 {
+            let pga = CB2014_PGA;
+            let ln_pga = Self::source_path_terms(&pga, inputs)
+                + Self::site_term(&pga, inputs.vs30, a1100)
+                + Self::sediment_term(&pga, inputs.z2p5);
+            ln_y.max(ln_pga)
+        } else {
+            ln_y
+        }
+    }
+
+    /// Total standard deviation of `ln(Y)` (magnitude-dependent between- and within-event
+    /// variability, linear site response).
+    pub fn total_sigma(&self, magnitude: f64) -> f64 {
+        let c = &self.coeffs;
+        let w = ((magnitude - 4.5) / (5.5 - 4.5)).clamp(0., 1.);
+        let phi = c.phi1 + w * (c.phi2 - c.phi1);
+        let tau = c.tau1 + w * (c.tau2 - c.tau1);
+        phi.hypot(tau)
+    }
+}
+
+impl GroundMotionModeling for CB2014 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![
+            DistanceMetric::Rrup,
+            DistanceMetric::Rjb,
+            DistanceMetric::Rx,
+        ]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let inputs = Cb2014Inputs {
+            magnitude: eq.mw(),
+            rrup: distances
+                .rrup
+                .unwrap_or_else(|| distances.repi.hypot(eq.ztor())),
+            rjb: distances.rjb.unwrap_or(distances.repi),
+            rx: distances.rx,
+            hypo_depth: eq.depth,
+            ztor: eq.ztor(),
+            dip: eq.rupture.map_or(90., |r| r.dip),
+            width: eq.rupture.map_or(0., |r| r.width),
+            vs30: point.vs30,
+            z2p5: point.z2p5.unwrap_or_else(|| estimate_z2p5(point.vs30)),
+        };
+        let mut ground_motion = self.ln_median(&inputs).exp();
+        // convert g to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion *= 100.;
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.total_sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(vs30: f64, z2p5: f64) -> Cb2014Inputs {
+        Cb2014Inputs {
+            magnitude: 6.5,
+            rrup: 10.,
+            rjb: 10.,
+            rx: None,
+            hypo_depth: 7.,
+            ztor: 0.,
+            dip: 90.,
+            width: 0.,
+            vs30,
+            z2p5,
+        }
+    }
+
+    #[test]
+    fn test_pga_reference_scenario() {
+        let model = CB2014::new(CB2014_PGA, GmpePointKind::Pga);
+        // M6.5 at 10 km on stiff soil: a few tenths of g
+        let pga = model.ln_median(&inputs(760., 2.)).exp();
+        assert!(pga > 0.15 && pga < 0.4, "{pga}");
+    }
+
+    #[test]
+    fn test_basin_term() {
+        let model = CB2014::new(
+            CB2014_PSA[2],
+            GmpePointKind::Psa {
+                period: CB2014_PSA[2].period,
+            },
+        );
+        let shallow = model.ln_median(&inputs(400., 0.5));
+        let reference = model.ln_median(&inputs(400., 2.));
+        let deep = model.ln_median(&inputs(400., 6.));
+        assert!(shallow < reference);
+        assert!(deep > reference);
+        assert_eq!(CB2014::sediment_term(&CB2014_PGA, 2.5), 0.);
+    }
+
+    #[test]
+    fn test_hanging_wall_term() {
+        let model = CB2014::new(CB2014_PGA, GmpePointKind::Pga);
+        // M7 on a 45° reverse fault, sites at Rrup = 10 km on both sides of the top edge
+        let dipping = |rx: f64, rjb: f64| Cb2014Inputs {
+            magnitude: 7.,
+            rx: Some(rx),
+            rjb,
+            ztor: 2.,
+            dip: 45.,
+            width: 20.,
+            ..inputs(760., 2.)
+        };
+        let hanging_wall = model.ln_median(&dipping(10., 0.));
+        let footwall = model.ln_median(&dipping(-10., 10.));
+        assert!(hanging_wall > footwall + 0.3, "{hanging_wall} {footwall}");
+        // No term for point sources or vertical ruptures
+        let point = inputs(760., 2.);
+        assert_eq!(CB2014::hanging_wall_term(&CB2014_PGA, &point), 0.);
+        let vertical = Cb2014Inputs {
+            dip: 90.,
+            ..dipping(10., 0.)
+        };
+        assert_eq!(CB2014::hanging_wall_term(&CB2014_PGA, &vertical), 0.);
+    }
+}
@@ -0,0 +1,246 @@
+//! Text-based coefficient tables.
+//!
+//! Published GMPE coefficients are usually distributed as tables with one row per spectral
+//! period. [`CoeffsTable`] parses such a table from a whitespace-separated string, so a model can
+//! ship its coefficients verbatim and build its predefined configurations from the rows instead
+//! of hard-coding one struct per period.
+//!
+//! The first line of the table is a header. The first column holds the intensity measure of the
+//! row: `pga`, `pgv`, `pgd` or a spectral period in seconds. All other columns are numeric
+//! coefficients addressed by their header name.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::coeffs_table::CoeffsTable;
+//! use ground_motion_lib::gmm::GmpePointKind;
+//!
+//! let table = CoeffsTable::parse(
+//!     "imt   c1    c2
+//!      pga   2.20  0.81
+//!      0.2   1.73  0.84",
+//! )
+//! .unwrap();
+//! assert_eq!(table.len(), 2);
+//! assert_eq!(table.rows()[1].kind(), GmpePointKind::Psa { period: 0.2 });
+//! assert_eq!(table.rows()[1].get("c2"), Some(0.84));
+//! assert_eq!(table.rows()[1].label(), "psa_02");
+//! ```
+
+use crate::gmm::GmpePointKind;
+
+/// One row of a [`CoeffsTable`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoeffsRow<'a> {
+    /// Spectral period (s); `0.0` for PGA, `-1.0` for PGV and `-2.0` for PGD.
+    pub period: f64,
+    columns: &'a [String],
+    values: &'a [f64],
+}
+
+/// A parsed coefficient table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoeffsTable {
+    columns: Vec<String>,
+    rows: Vec<(f64, Vec<f64>)>,
+}
+
+impl CoeffsRow<'_> {
+    /// Coefficient value of the named column.
+    pub fn get(&self, column: &str) -> Option<f64> {
+        self.columns
+            .iter()
+            .position(|c| c == column)
+            .map(|i| self.values[i])
+    }
+
+    /// Coefficient value of the named column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column does not exist. Intended for tables shipped with the crate.
+    pub fn coeff(&self, column: &str) -> f64 {
+        self.get(column)
+            .unwrap_or_else(|| panic!("Missing coefficient column `{column}`"))
+    }
+
+    /// Type of motion of the row.
+    pub fn kind(&self) -> GmpePointKind {
+        period_kind(self.period)
+    }
+
+    /// Configuration key suffix of the row: `pga`, `pgv`, `pgd` or `psa_XX`, see
+    /// [`period_label`].
+    pub fn label(&self) -> String {
+        period_label(self.period)
+    }
+}
+
+impl CoeffsTable {
+    /// Parse a whitespace-separated coefficient table.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the table is empty, a row has a different number of columns than the
+    /// header, or a value cannot be parsed.
+    pub fn parse(table: &str) -> Result<Self, String> {
+        let mut lines = table.lines().map(str::trim).filter(|l| !l.is_empty());
+        let header = lines.next().ok_or("Empty coefficient table")?;
+        let columns: Vec<String> = header
+            .split_whitespace()
+            .skip(1)
+            .map(String::from)
+            .collect();
+        let mut rows = Vec::new();
+        for line in lines {
+            let mut fields = line.split_whitespace();
+            let imt = fields.next().unwrap_or_default();
+            let period = match imt.to_ascii_lowercase().as_str() {
+                "pga" => 0.,
+                "pgv" => -1.,
+                "pgd" => -2.,
+                p => p
+                    .parse::<f64>()
+                    .map_err(|e| format!("Invalid period `{imt}`: {e}"))?,
+            };
+            let values = fields
+                .map(|v| {
+                    v.parse::<f64>()
+                        .map_err(|e| format!("Invalid coefficient `{v}` for `{imt}`: {e}"))
+                })
+                .collect::<Result<Vec<f64>, String>>()?;
+            if values.len() != columns.len() {
+                return Err(format!(
+                    "Row `{imt}` has {} coefficients, expected {}",
+                    values.len(),
+                    columns.len()
+                ));
+            }
+            rows.push((period, values));
+        }
+        Ok(Self { columns, rows })
+    }
+
+    /// Number of rows in the table.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// All rows of the table in their original order.
+    pub fn rows(&self) -> Vec<CoeffsRow<'_>> {
+        self.rows
+            .iter()
+            .map(|(period, values)| CoeffsRow {
+                period: *period,
+                columns: &self.columns,
+                values,
+            })
+            .collect()
+    }
+}
+
+/// Type of motion for a spectral period (`0.0` for PGA, `-1.0` for PGV and `-2.0` for PGD).
+pub fn period_kind(period: f64) -> GmpePointKind {
+    if period == 0. {
+        GmpePointKind::Pga
+    } else if period == -2. {
+        GmpePointKind::Pgd
+    } else if period < 0. {
+        GmpePointKind::Pgv
+    } else {
+        GmpePointKind::Psa { period }
+    }
+}
+
+/// Configuration key suffix for a spectral period: `pga`, `pgv`, `pgd` or `psa_XX`, where `XX` is the
+/// spectral period in tenths of a second (`psa_02` = 0.2 s, `psa_100` = 10 s).
+///
+/// Periods that are not a multiple of 0.1 s are encoded in hundredths of a second with a leading
+/// zero (`psa_005` = 0.05 s, `psa_015` = 0.15 s).
+pub fn period_label(period: f64) -> String {
+    match period_kind(period) {
+        GmpePointKind::Pga => "pga".to_string(),
+        GmpePointKind::Pgv => "pgv".to_string(),
+        GmpePointKind::Pgd => "pgd".to_string(),
+        GmpePointKind::Mmi => "mmi".to_string(),
+        GmpePointKind::AriasIntensity => "ia".to_string(),
+        GmpePointKind::Ds575 => "ds575".to_string(),
+        GmpePointKind::Ds595 => "ds595".to_string(),
+        GmpePointKind::Psa { .. } => {
+            let tenths = period * 10.;
+            if (tenths - tenths.round()).abs() < 1e-6 {
+                format!("psa_{:02}", tenths.round() as u32)
+            } else {
+                format!("psa_0{:02}", (period * 100.).round() as u32)
+            }
+        }
+    }
+}
+
+/// Spectral period of a configuration key suffix, the inverse of [`period_label`].
+///
+/// Returns `0.0` for `pga`, `-1.0` for `pgv`, `-2.0` for `pgd`, the period in seconds for
+/// `psa_XX`, and `None` for any other suffix.
+pub fn label_period(label: &str) -> Option<f64> {
+    match label {
+        "pga" => Some(0.),
+        "pgv" => Some(-1.),
+        "pgd" => Some(-2.),
+        _ => {
+            let digits = label.strip_prefix("psa_")?;
+            if digits.len() < 2 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            let value: u32 = digits.parse().ok()?;
+            if digits.len() == 3 && digits.starts_with('0') {
+                Some(value as f64 / 100.)
+            } else {
+                Some(value as f64 / 10.)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_period_label() {
+        assert_eq!(period_label(0.), "pga");
+        assert_eq!(period_label(-1.), "pgv");
+        assert_eq!(period_label(-2.), "pgd");
+        assert_eq!(period_label(0.1), "psa_01");
+        assert_eq!(period_label(3.), "psa_30");
+        assert_eq!(period_label(10.), "psa_100");
+        assert_eq!(period_label(0.05), "psa_005");
+        assert_eq!(period_label(0.15), "psa_015");
+    }
+
+    #[test]
+    fn test_label_period_roundtrip() {
+        for period in [0., -1., -2., 0.05, 0.1, 0.15, 0.75, 1., 3., 10.] {
+            assert_eq!(
+                label_period(&period_label(period)),
+                Some(period),
+                "{period}"
+            );
+        }
+        assert_eq!(label_period("pga_2"), None);
+        assert_eq!(label_period("psa_1"), None);
+        assert_eq!(label_period("psa_x1"), None);
+    }
+
+    #[test]
+    fn test_parse_failures() {
+        assert!(CoeffsTable::parse("").is_err());
+        assert!(CoeffsTable::parse("imt c1 c2\npga 1.0").is_err());
+        assert!(CoeffsTable::parse("imt c1\nfoo 1.0").is_err());
+        assert!(CoeffsTable::parse("imt c1\npga x").is_err());
+    }
+}
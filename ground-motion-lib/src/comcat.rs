@@ -0,0 +1,58 @@
+//! Fetching of event parameters from the USGS earthquake catalog (ComCat).
+//!
+//! Post-event maps start from the origin and magnitude published by the USGS. Instead of
+//! re-typing them, [`fetch_usgs_event`] queries the FDSN event web service of ComCat by event
+//! identifier and parses the GeoJSON response with
+//! [`parse_usgs_geojson_event`](crate::readers::parse_usgs_geojson_event):
+//!
+//! ```text
+//! https://earthquake.usgs.gov/fdsnws/event/1/query?eventid=us7000abcd&format=geojson
+//! ```
+//!
+//! Requires the `comcat` crate feature.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use ground_motion_lib::comcat::fetch_usgs_event;
+//!
+//! let eq = fetch_usgs_event("us7000abcd").unwrap();
+//! println!("M {} at ({}, {}), {} km depth", eq.magnitude, eq.lon, eq.lat, eq.depth);
+//! ```
+
+use crate::gmm::Earthquake;
+use crate::readers::parse_usgs_geojson_event;
+use std::error::Error;
+
+/// FDSN event query endpoint of the USGS earthquake catalog.
+pub const COMCAT_QUERY_URL: &str = "https://earthquake.usgs.gov/fdsnws/event/1/query";
+
+/// Fetch the origin and preferred magnitude of an event from ComCat.
+///
+/// # Errors
+///
+/// Returns an error if the request fails (e.g. an unknown event identifier or no network), or
+/// the response cannot be parsed.
+pub fn fetch_usgs_event(event_id: &str) -> Result<Earthquake, Box<dyn Error>> {
+    fetch_usgs_event_from(COMCAT_QUERY_URL, event_id)
+}
+
+/// Fetch the origin and preferred magnitude of an event from an FDSN event query endpoint
+/// serving USGS GeoJSON, e.g. a mirror of ComCat.
+///
+/// # Errors
+///
+/// Returns an error if the request fails, or the response cannot be parsed.
+pub fn fetch_usgs_event_from(
+    query_url: &str,
+    event_id: &str,
+) -> Result<Earthquake, Box<dyn Error>> {
+    let body = ureq::get(query_url)
+        .query("eventid", event_id)
+        .query("format", "geojson")
+        .call()
+        .map_err(|err| format!("Failed to fetch event `{event_id}`: {err}"))?
+        .body_mut()
+        .read_to_string()?;
+    parse_usgs_geojson_event(&body)
+}
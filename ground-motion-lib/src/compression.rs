@@ -0,0 +1,167 @@
+//! Transparent gzip and zstd compression of input and output files.
+//!
+//! National Vs30 grids compress about tenfold, so they are best kept compressed on disk. The
+//! delimited site readers ([`read_vs30_points`](crate::readers::read_vs30_points),
+//! [`read_site_class_points`](crate::readers::read_site_class_points)) and the text writers of
+//! [`writers`](crate::writers) open files through [`open_reader`] and [`create_writer`], which
+//! select the compression by the file extension:
+//!
+//! - `.gz` — gzip (requires the `gzip` crate feature, via the pure Rust `flate2` crate).
+//! - `.zst` — Zstandard (requires the `zstd` crate feature, via the `zstd` crate).
+//!
+//! Other files are read and written as is. Opening a compressed file without the matching
+//! feature is an error.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use ground_motion_lib::compression::Compression;
+//! use ground_motion_lib::readers::read_vs30_points;
+//!
+//! assert_eq!(Compression::from_path("vs30.txt.gz"), Compression::Gzip);
+//! assert_eq!(Compression::Gzip.strip_extension("vs30.txt.gz"), "vs30.txt");
+//!
+//! // Decompressed on the fly with the `gzip` feature
+//! let points = read_vs30_points("vs30.txt.gz", b'\t').unwrap();
+//! ```
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Compression of a file, selected by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Uncompressed file.
+    None,
+    /// gzip (`.gz`).
+    Gzip,
+    /// Zstandard (`.zst`).
+    Zstd,
+}
+
+impl Compression {
+    /// Compression of a file by its extension (case-insensitive).
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Path without the compression extension, e.g. `pga.json` for `pga.json.gz`, to select
+    /// the format of the compressed content.
+    pub fn strip_extension<'a>(&self, path: &'a str) -> &'a str {
+        match self {
+            Compression::None => path,
+            Compression::Gzip | Compression::Zstd => {
+                path.rsplit_once('.').map_or(path, |(stem, _)| stem)
+            }
+        }
+    }
+}
+
+/// Open a file for reading, decompressing it by its extension.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, or its compression requires a crate feature
+/// that is not enabled.
+pub fn open_reader<P: AsRef<Path>>(path: P) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    let compression = Compression::from_path(&path);
+    check_feature(compression, "Reading")?;
+    let file = File::open(path)?;
+    match compression {
+        Compression::None => Ok(Box::new(file)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(file))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(zstd::Decoder::new(file)?)),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("checked by `check_feature`"),
+    }
+}
+
+/// Create a file for writing, compressing it by its extension.
+///
+/// Compressed streams are completed when the writer is dropped, so it must not be leaked.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created, or its compression requires a crate feature
+/// that is not enabled.
+pub fn create_writer<P: AsRef<Path>>(path: P) -> Result<Box<dyn Write>, Box<dyn Error>> {
+    let compression = Compression::from_path(&path);
+    check_feature(compression, "Writing")?;
+    let file = File::create(path)?;
+    match compression {
+        Compression::None => Ok(Box::new(file)),
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => Ok(Box::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => Ok(Box::new(zstd::Encoder::new(file, 0)?.auto_finish())),
+        #[allow(unreachable_patterns)]
+        _ => unreachable!("checked by `check_feature`"),
+    }
+}
+
+/// Fail early if the crate feature of a compression is not enabled.
+fn check_feature(compression: Compression, action: &str) -> Result<(), Box<dyn Error>> {
+    let feature = match compression {
+        Compression::Gzip if !cfg!(feature = "gzip") => "gzip",
+        Compression::Zstd if !cfg!(feature = "zstd") => "zstd",
+        _ => return Ok(()),
+    };
+    Err(format!(
+        "{action} {feature}-compressed files requires building with the `{feature}` feature."
+    )
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_from_path() {
+        assert_eq!(Compression::from_path("vs30.txt.GZ"), Compression::Gzip);
+        assert_eq!(Compression::from_path("pga.jsonl.zst"), Compression::Zstd);
+        assert_eq!(Compression::from_path("vs30.txt"), Compression::None);
+        assert_eq!(
+            Compression::Zstd.strip_extension("pga.jsonl.zst"),
+            "pga.jsonl"
+        );
+        assert_eq!(Compression::None.strip_extension("pga.txt"), "pga.txt");
+    }
+
+    #[cfg(all(feature = "gzip", feature = "zstd"))]
+    #[test]
+    fn test_compressed_round_trip() {
+        for name in [
+            "ground_motion_round_trip.txt.gz",
+            "ground_motion_round_trip.txt.zst",
+        ] {
+            let path = std::env::temp_dir().join(name);
+            let mut writer = create_writer(&path).unwrap();
+            writer.write_all(b"142.5\t50.0\t400\n").unwrap();
+            drop(writer);
+
+            let mut content = String::new();
+            open_reader(&path)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            assert_eq!(content, "142.5\t50.0\t400\n");
+        }
+    }
+}
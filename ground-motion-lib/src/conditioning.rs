@@ -0,0 +1,519 @@
+//! Conditioning of predicted shaking fields on station observations.
+//!
+//! A model prediction is the expected shaking of an average event. Recorded ground motions at
+//! seismic stations tell how this particular event differs from the average, and how the
+//! shaking varies around the stations. Following the approach of USGS ShakeMap (Worden et al.,
+//! 2018), [`MvnConditioning`] combines the prediction grid with the observations in two steps.
+//!
+//! The total residuals at the stations are split into a between-event term (bias) shared by all
+//! sites and within-event residuals:
+//!
+//! ```text
+//! r_j = ln(obs_j) − ln(pred_j) = τ_j · η + w_j,   w_j ~ N(0, φ_j²)
+//! η̂ = Σ (τ_j · r_j / φ_j²) / (1 + Σ τ_j² / φ_j²),   var(η̂) = 1 / (1 + Σ τ_j² / φ_j²)
+//! ```
+//!
+//! The normalized within-event residuals `z_j = (r_j − τ_j · η̂) / φ_j` are then interpolated
+//! to the grid with the conditional multivariate normal distribution (simple kriging):
+//!
+//! ```text
+//! μ_i  = c_iᵀ · R⁻¹ · z,   v_i = 1 − c_iᵀ · R⁻¹ · c_i
+//! ln(y_i) = ln(median_i) + τ_i · η̂ + φ_i · μ_i
+//! σ_i  = sqrt(τ_i² · var(η̂) + φ_i² · v_i)
+//! ```
+//!
+//! where `R` is the station-to-station and `c_i` the grid-to-station correlation of the
+//! [`SpatialCorrelation`] model. Far from the stations the grid keeps its median shifted by the
+//! event bias; at a station it reproduces the observation with a small standard deviation.
+//! Intensities are treated linearly instead of in logarithms.
+//!
+//! When full conditioning is overkill, [`EventBias`] estimates a single bias of the event as the
+//! inverse-variance weighted mean of the station residuals and shifts the whole grid by it:
+//!
+//! ```text
+//! b = Σ (r_j / σ_j²) / Σ (1 / σ_j²),   ln(y_i) = ln(median_i) + b
+//! ```
+//!
+//! [`EventBias`] implements [`GridTransform`] and can be appended to a
+//! [`Pipeline`](crate::pipeline::Pipeline).
+//!
+//! Current simplifications:
+//!
+//! - The total standard deviation is split into `τ = r · σ` and `φ = sqrt(1 − r²) · σ` with a
+//!   fixed ratio `r` (see [`DEFAULT_TAU_RATIO`]).
+//! - Observations are treated as exact, i.e. without measurement or conversion uncertainty.
+//! - Only observations of the type of motion of the grid are used.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::conditioning::MvnConditioning;
+//! use ground_motion_lib::correlation::JayaramBaker2009;
+//! use ground_motion_lib::gmm::GmpePoint;
+//!
+//! let grid = vec![
+//!     GmpePoint::new_pga(142.50, 50.00, 10.0).with_sigma(0.6),
+//!     GmpePoint::new_pga(142.90, 50.30, 4.0).with_sigma(0.6),
+//! ];
+//! // Model prediction and record at a station next to the first site
+//! let predicted = vec![GmpePoint::new_pga(142.51, 50.00, 10.0).with_sigma(0.6)];
+//! let observed = vec![GmpePoint::new_pga(142.51, 50.00, 20.0)];
+//!
+//! let conditioning = MvnConditioning::new(JayaramBaker2009::default());
+//! let conditioned = conditioning.condition(&grid, &predicted, &observed).unwrap();
+//! assert!(conditioned.event_term > 0.);
+//! assert!(conditioned.grid[0].value > 10.0);
+//! assert!(conditioned.grid[0].sigma.unwrap() < 0.6);
+//! ```
+
+use crate::correlation::SpatialCorrelation;
+use crate::distance::DistanceMethod;
+use crate::gmm::{GmpePoint, StationPoint, Vs30Point};
+use crate::pipeline::GridTransform;
+use crate::simulation::{DEFAULT_TAU_RATIO, cholesky_in_place};
+use rayon::prelude::*;
+use std::error::Error;
+
+/// Diagonal regularization keeping the station correlation matrix positive definite for
+/// co-located stations.
+const NUGGET: f64 = 1e-6;
+
+/// Multivariate normal conditioning of prediction grids on station observations.
+#[derive(Debug, Clone)]
+pub struct MvnConditioning<C: SpatialCorrelation> {
+    /// Spatial correlation model of the within-event residuals
+    pub correlation: C,
+    /// Ratio `τ / σ` of the between-event to the total standard deviation
+    pub tau_ratio: f64,
+    /// Method used to compute the separation distances
+    pub method: DistanceMethod,
+}
+
+/// Prediction grid conditioned on station observations.
+#[derive(Debug)]
+pub struct ConditionedGrid {
+    /// Conditioned grid with the remaining standard deviation of every point
+    pub grid: Vec<GmpePoint>,
+    /// Estimated normalized between-event term `η̂` (bias) of the event
+    pub event_term: f64,
+    /// Standard deviation of the estimated between-event term
+    pub event_term_sigma: f64,
+    /// Number of observations used
+    pub n_observations: usize,
+}
+
+impl<C: SpatialCorrelation + Sync> MvnConditioning<C> {
+    /// Create a conditioning with a between-event share of [`DEFAULT_TAU_RATIO`] and haversine
+    /// distances.
+    pub fn new(correlation: C) -> Self {
+        Self {
+            correlation,
+            tau_ratio: DEFAULT_TAU_RATIO,
+            method: DistanceMethod::Haversine,
+        }
+    }
+
+    /// Set the ratio `τ / σ` of the between-event to the total standard deviation (`0..=1`).
+    pub fn with_tau_ratio(mut self, tau_ratio: f64) -> Self {
+        self.tau_ratio = tau_ratio.clamp(0., 1.);
+        self
+    }
+
+    /// Set the method used to compute the separation distances.
+    pub fn with_distance_method(mut self, method: DistanceMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Condition a prediction grid on station observations.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Median prediction grid with standard deviations.
+    /// * `predicted` - Median predictions with standard deviations at the stations, e.g.
+    ///   computed with [`calc_gmpe_vec`](crate::vectorized::calc_gmpe_vec) for the station
+    ///   sites.
+    /// * `observed` - Observed ground motions, in the same order as `predicted`.
+    ///
+    /// Observations of a type of motion other than the one of the grid are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `predicted` and `observed` differ in size, a prediction carries no
+    /// standard deviation, no observation matches the type of motion of the grid, or the
+    /// correlation model does not support it.
+    pub fn condition(
+        &self,
+        grid: &[GmpePoint],
+        predicted: &[GmpePoint],
+        observed: &[GmpePoint],
+    ) -> Result<ConditionedGrid, Box<dyn Error>> {
+        if predicted.len() != observed.len() {
+            return Err("Predictions and observations differ in size".into());
+        }
+        if grid.iter().any(|p| p.sigma.is_none()) {
+            return Err("All grid points must carry a standard deviation".into());
+        }
+        let Some(kind) = grid.first().map(|p| p.kind) else {
+            return Ok(ConditionedGrid {
+                grid: Vec::new(),
+                event_term: 0.,
+                event_term_sigma: 1.,
+                n_observations: 0,
+            });
+        };
+        let stations: Vec<(&GmpePoint, &GmpePoint)> = predicted
+            .iter()
+            .zip(observed)
+            .filter(|(pred, obs)| pred.kind == kind && obs.kind == kind)
+            .collect();
+        if stations.is_empty() {
+            return Err(format!("No observations of {kind}").into());
+        }
+
+        let phi_ratio = (1. - self.tau_ratio.powi(2)).sqrt();
+        let mut residuals = Vec::with_capacity(stations.len());
+        for (pred, obs) in &stations {
+            let sigma = pred
+                .sigma
+                .ok_or("All predictions must carry a standard deviation")?;
+            let obs_mean = if kind.is_log_normal() {
+                obs.value.ln()
+            } else {
+                obs.value
+            };
+            residuals.push((
+                obs_mean - pred.log_mean(),
+                self.tau_ratio * sigma,
+                phi_ratio * sigma,
+            ));
+        }
+
+        // Between-event term
+        let (numerator, precision) = residuals
+            .iter()
+            .fold((0., 1.), |(num, prec), (r, tau, phi)| {
+                (num + tau * r / phi.powi(2), prec + (tau / phi).powi(2))
+            });
+        let event_term = numerator / precision;
+        let event_term_variance = 1. / precision;
+        let z: Vec<f64> = residuals
+            .iter()
+            .map(|(r, tau, phi)| (r - tau * event_term) / phi)
+            .collect();
+
+        // Station correlation matrix and its Cholesky factor
+        let n = stations.len();
+        let mut lower = vec![0.; n * n];
+        for i in 0..n {
+            for j in 0..=i {
+                lower[i * n + j] = self.station_correlation(stations[i].1, stations[j].1)?;
+            }
+            lower[i * n + i] += NUGGET;
+        }
+        cholesky_in_place(&mut lower, n)?;
+        // R⁻¹·z = L⁻ᵀ·(L⁻¹·z), so μ_i = (L⁻¹·c_i)·(L⁻¹·z)
+        let whitened_z = forward_substitution(&lower, n, z);
+
+        let grid = grid
+            .par_iter()
+            .map(|point| {
+                let c = stations
+                    .iter()
+                    .map(|(_, obs)| self.station_correlation(point, obs))
+                    .collect::<Result<Vec<f64>, _>>()
+                    .map_err(|e| e.to_string())?;
+                let whitened_c = forward_substitution(&lower, n, c);
+                let mean: f64 = whitened_c.iter().zip(&whitened_z).map(|(c, z)| c * z).sum();
+                let variance = (1. - whitened_c.iter().map(|c| c * c).sum::<f64>()).max(0.);
+
+                let sigma = point.sigma.unwrap_or(0.);
+                let (tau, phi) = (self.tau_ratio * sigma, phi_ratio * sigma);
+                let shift = tau * event_term + phi * mean;
+                let value = if point.kind.is_log_normal() {
+                    point.value * shift.exp()
+                } else {
+                    point.value + shift
+                };
+                let sigma = (tau.powi(2) * event_term_variance + phi.powi(2) * variance).sqrt();
+                Ok(GmpePoint::new(point.lon, point.lat, value, point.kind).with_sigma(sigma))
+            })
+            .collect::<Result<Vec<GmpePoint>, String>>()?;
+
+        Ok(ConditionedGrid {
+            grid,
+            event_term,
+            event_term_sigma: event_term_variance.sqrt(),
+            n_observations: n,
+        })
+    }
+
+    fn station_correlation(&self, first: &GmpePoint, second: &GmpePoint) -> Result<f64, String> {
+        let distance = self
+            .method
+            .epicentral_distance(first.lon, first.lat, second.lon, second.lat);
+        self.correlation
+            .correlation_for(distance, first.kind)
+            .ok_or_else(|| format!("No spatial correlation for {}", first.kind))
+    }
+}
+
+/// Single bias of an event estimated from station residuals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EventBias {
+    /// Bias in natural-log units, or in intensity units for intensities
+    pub bias: f64,
+    /// Standard error of the bias
+    pub sigma: f64,
+    /// Number of observations used
+    pub n_observations: usize,
+}
+
+impl EventBias {
+    /// Estimate the event bias from predictions and observations at the stations.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicted` - Median predictions with standard deviations at the stations.
+    /// * `observed` - Observed ground motions, in the same order as `predicted`.
+    ///
+    /// Pairs of different types of motion, and non-positive values of log-normal types of
+    /// motion, are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ground_motion_lib::conditioning::EventBias;
+    /// use ground_motion_lib::gmm::GmpePoint;
+    /// use ground_motion_lib::pipeline::Pipeline;
+    ///
+    /// let predicted = vec![
+    ///     GmpePoint::new_pga(142.5, 50.0, 10.0).with_sigma(0.6),
+    ///     GmpePoint::new_pga(142.9, 50.3, 4.0).with_sigma(0.6),
+    /// ];
+    /// let observed = vec![
+    ///     GmpePoint::new_pga(142.5, 50.0, 20.0),
+    ///     GmpePoint::new_pga(142.9, 50.3, 8.0),
+    /// ];
+    /// let bias = EventBias::estimate(&predicted, &observed).unwrap();
+    /// assert!((bias.bias - 2_f64.ln()).abs() < 1e-12);
+    ///
+    /// let grid = vec![GmpePoint::new_pga(142.7, 50.1, 6.0).with_sigma(0.6)];
+    /// let corrected = Pipeline::new().then(bias).run(grid);
+    /// assert!((corrected[0].value - 12.0).abs() < 1e-9);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `predicted` and `observed` differ in size, a prediction carries no
+    /// standard deviation, or no observation is usable.
+    pub fn estimate(
+        predicted: &[GmpePoint],
+        observed: &[GmpePoint],
+    ) -> Result<Self, Box<dyn Error>> {
+        if predicted.len() != observed.len() {
+            return Err("Predictions and observations differ in size".into());
+        }
+        let (mut weighted, mut precision, mut n) = (0., 0., 0);
+        for (pred, obs) in predicted.iter().zip(observed) {
+            if pred.kind != obs.kind || (obs.kind.is_log_normal() && obs.value <= 0.) {
+                continue;
+            }
+            let sigma = pred
+                .sigma
+                .ok_or("All predictions must carry a standard deviation")?;
+            let weight = 1. / sigma.powi(2);
+            weighted += weight * (obs.log_mean() - pred.log_mean());
+            precision += weight;
+            n += 1;
+        }
+        if n == 0 {
+            return Err("No usable observations".into());
+        }
+        Ok(Self {
+            bias: weighted / precision,
+            sigma: (1. / precision).sqrt(),
+            n_observations: n,
+        })
+    }
+
+    /// Metadata entries describing the applied bias, e.g. for
+    /// [`write_gmpe_points_with_metadata`](crate::writers::write_gmpe_points_with_metadata).
+    pub fn metadata(&self) -> Vec<(String, String)> {
+        vec![
+            ("event_bias".to_string(), self.bias.to_string()),
+            ("event_bias_sigma".to_string(), self.sigma.to_string()),
+            (
+                "event_bias_observations".to_string(),
+                self.n_observations.to_string(),
+            ),
+        ]
+    }
+}
+
+impl GridTransform for EventBias {
+    fn apply(&self, mut point: GmpePoint) -> Option<GmpePoint> {
+        if point.kind.is_log_normal() {
+            point.value *= self.bias.exp();
+        } else {
+            point.value += self.bias;
+        }
+        Some(point)
+    }
+}
+
+/// Sites of the stations for model predictions.
+///
+/// Stations without a known Vs30 take the Vs30 of the nearest grid site, or 760 m/s if the grid
+/// is empty.
+pub fn station_sites(stations: &[StationPoint], grid: &[Vs30Point]) -> Vec<Vs30Point> {
+    stations
+        .iter()
+        .map(|station| {
+            let nearest = grid.iter().min_by(|a, b| {
+                let da = (a.lon - station.lon).hypot(a.lat - station.lat);
+                let db = (b.lon - station.lon).hypot(b.lat - station.lat);
+                da.total_cmp(&db)
+            });
+            station.site(nearest.map_or(760., |site| site.vs30))
+        })
+        .collect()
+}
+
+/// Solve `L·x = b` for a row-major lower triangular matrix.
+fn forward_substitution(lower: &[f64], n: usize, mut b: Vec<f64>) -> Vec<f64> {
+    for i in 0..n {
+        let dot: f64 = (0..i).map(|k| lower[i * n + k] * b[k]).sum();
+        b[i] = (b[i] - dot) / lower[i * n + i];
+    }
+    b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation::JayaramBaker2009;
+
+    fn grid() -> Vec<GmpePoint> {
+        vec![
+            GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.6),
+            GmpePoint::new_pga(142.6, 50., 10.).with_sigma(0.6),
+            GmpePoint::new_pga(145., 52., 10.).with_sigma(0.6),
+        ]
+    }
+
+    #[test]
+    fn test_station_is_reproduced() {
+        let conditioning = MvnConditioning::new(JayaramBaker2009::default()).with_tau_ratio(0.5);
+        let predicted = vec![GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.6)];
+        let observed = vec![GmpePoint::new_pga(142.5, 50., 25.)];
+        let conditioned = conditioning
+            .condition(&grid(), &predicted, &observed)
+            .unwrap();
+        assert_eq!(conditioned.n_observations, 1);
+
+        let r = 2.5_f64.ln();
+        let (tau, phi) = (0.3, 0.6 * 0.75_f64.sqrt());
+        let eta = tau * r / phi.powi(2) / (1. + (tau / phi).powi(2));
+        assert!((conditioned.event_term - eta).abs() < 1e-12);
+
+        let at_station = &conditioned.grid[0];
+        assert!(
+            (at_station.value - 25.).abs() < 1e-3,
+            "{}",
+            at_station.value
+        );
+        assert!(at_station.sigma.unwrap() < 0.3);
+        // Far away only the event bias remains
+        let far = &conditioned.grid[2];
+        assert!((far.value - 10. * (tau * eta).exp()).abs() < 1e-6);
+        assert!(far.sigma.unwrap() < 0.6 && far.sigma.unwrap() > at_station.sigma.unwrap());
+        // In between the conditioned value lies between both
+        let near = &conditioned.grid[1];
+        assert!(near.value < at_station.value && near.value > far.value);
+    }
+
+    #[test]
+    fn test_other_kinds_are_ignored() {
+        let conditioning = MvnConditioning::new(JayaramBaker2009::default());
+        let predicted = vec![
+            GmpePoint::new_pgv(142.5, 50., 10.).with_sigma(0.6),
+            GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.6),
+        ];
+        let observed = vec![
+            GmpePoint::new_pgv(142.5, 50., 100.),
+            GmpePoint::new_pga(142.5, 50., 10.),
+        ];
+        let conditioned = conditioning
+            .condition(&grid(), &predicted, &observed)
+            .unwrap();
+        assert_eq!(conditioned.n_observations, 1);
+        assert!(conditioned.event_term.abs() < 1e-12);
+        assert!((conditioned.grid[0].value - 10.).abs() < 1e-9);
+
+        assert!(
+            conditioning
+                .condition(&grid(), &predicted[..1], &observed[..1])
+                .is_err()
+        );
+        assert!(
+            conditioning
+                .condition(&grid(), &predicted, &observed[..1])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_event_bias() {
+        let predicted = vec![
+            GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.5),
+            GmpePoint::new_pga(142.6, 50., 10.).with_sigma(1.),
+            GmpePoint::new_pgv(142.6, 50., 10.).with_sigma(1.),
+        ];
+        let observed = vec![
+            GmpePoint::new_pga(142.5, 50., 10. * 1_f64.exp()),
+            GmpePoint::new_pga(142.6, 50., 10. * 2_f64.exp()),
+            GmpePoint::new_pga(142.6, 50., 100.),
+        ];
+        let bias = EventBias::estimate(&predicted, &observed).unwrap();
+        assert_eq!(bias.n_observations, 2);
+        // Weights 4 and 1
+        assert!((bias.bias - 1.2).abs() < 1e-12);
+        assert!((bias.sigma - 0.2_f64.sqrt()).abs() < 1e-12);
+
+        let mmi = bias.apply(GmpePoint::new(
+            142.5,
+            50.,
+            5.,
+            crate::gmm::GmpePointKind::Mmi,
+        ));
+        assert!((mmi.unwrap().value - 6.2).abs() < 1e-12);
+        let pga = bias
+            .apply(GmpePoint::new_pga(142.5, 50., 1.).with_sigma(0.5))
+            .unwrap();
+        assert!((pga.value - 1.2_f64.exp()).abs() < 1e-12);
+        assert_eq!(pga.sigma, Some(0.5));
+        let metadata = bias.metadata();
+        assert_eq!(metadata[0].0, "event_bias");
+        assert_eq!(metadata[2].1, "2");
+
+        assert!(EventBias::estimate(&predicted[2..], &observed[2..]).is_err());
+    }
+
+    #[test]
+    fn test_station_sites() {
+        let grid = vec![
+            Vs30Point::new(142.5, 50., 300., None, None),
+            Vs30Point::new(143., 50., 600., None, None),
+        ];
+        let mut stations = vec![
+            StationPoint::new("A.B", 142.9, 50., 1., crate::gmm::GmpePointKind::Pga),
+            StationPoint::new("A.C", 142.6, 50., 1., crate::gmm::GmpePointKind::Pga),
+        ];
+        stations[1].vs30 = Some(450.);
+        let sites = station_sites(&stations, &grid);
+        assert_eq!(sites[0].vs30, 600.);
+        assert_eq!(sites[1].vs30, 450.);
+        assert_eq!(station_sites(&stations[..1], &[])[0].vs30, 760.);
+    }
+}
@@ -1,11 +1,62 @@
 //! Ground motion prediction model configuration storage and retrieval.
 
-use crate::gmm::GmpePointKind;
+use crate::ab2003::{AB2003, AB2003_INSLAB, AB2003_INTERFACE, Ab2003Region};
+use crate::ab2006::AB2006;
+use crate::afshari2016::{AFSHARI2016, AFSHARI2016_DS575, AFSHARI2016_DS595};
+use crate::asb2014::{ASB2014, ASB2014_REPI, ASB2014_RHYP, ASB2014_RJB, Asb2014Distance};
+use crate::bchydro2016::{BCHYDRO2016, BCHYDRO2016_COEFFS};
+use crate::bradley2013::{BRADLEY2013, Bradley2013Coeffs};
+use crate::bssa2014::{BSSA2014, BSSA2014_PGA, BSSA2014_PGV, BSSA2014_PSA};
+use crate::cb2014::{CB2014, CB2014_PGA, CB2014_PGV, CB2014_PSA};
+use crate::coeffs_table::{label_period, period_kind, period_label};
+use crate::cutoff::CutoffModel;
+use crate::cy2014::{CY2014, CY2014_PGA, CY2014_PGV};
+use crate::garcia2005::GARCIA2005;
+use crate::gmm::{GmpePointKind, GroundMotionModeling, TectonicType};
+use crate::idriss2014::IDRISS2014;
+use crate::ipe::{ALLEN2012, Allen2012Distance};
+use crate::kanno2006::{KANNO2006, KANNO2006_PGA, KANNO2006_PGV};
+use crate::linlee2008::{LINLEE2008, LINLEE2008_ROCK, LINLEE2008_SOIL, LinLee2008Site};
+use crate::mcverry2006::{MCVERRY2006, McVerry2006Coeffs};
 use crate::mf2013::MF2013;
+use crate::montalva2017::{MONTALVA2017, Montalva2017Coeffs};
+use crate::parker2020::{PARKER2020, PARKER2020_INTERFACE, PARKER2020_SLAB, Parker2020Region};
+use crate::pezeshk2011::PEZESHK2011;
+use crate::sm1999::SM1999;
+use crate::toro2002::TORO2002;
+use crate::travasarou2003::TRAVASAROU2003;
+use crate::youngs1997::{YOUNGS1997, YOUNGS1997_ROCK, YOUNGS1997_SOIL, Youngs1997Site};
+use crate::zhao2006::{ZHAO2006, ZHAO2006_COEFFS};
 use std::collections::HashMap;
 use std::sync::OnceLock;
 
 static CONFIGS: OnceLock<HashMap<&'static str, MF2013>> = OnceLock::new();
+static BSSA2014_CONFIGS: OnceLock<HashMap<&'static str, BSSA2014>> = OnceLock::new();
+static CB2014_CONFIGS: OnceLock<HashMap<&'static str, CB2014>> = OnceLock::new();
+static CY2014_CONFIGS: OnceLock<HashMap<&'static str, CY2014>> = OnceLock::new();
+static ZHAO2006_CONFIGS: OnceLock<HashMap<&'static str, ZHAO2006>> = OnceLock::new();
+static SM1999_CONFIGS: OnceLock<HashMap<&'static str, SM1999>> = OnceLock::new();
+static KANNO2006_CONFIGS: OnceLock<HashMap<&'static str, KANNO2006>> = OnceLock::new();
+static AB2003_CONFIGS: OnceLock<HashMap<&'static str, AB2003>> = OnceLock::new();
+static BCHYDRO2016_CONFIGS: OnceLock<HashMap<&'static str, BCHYDRO2016>> = OnceLock::new();
+static YOUNGS1997_CONFIGS: OnceLock<HashMap<&'static str, YOUNGS1997>> = OnceLock::new();
+static PARKER2020_CONFIGS: OnceLock<HashMap<&'static str, PARKER2020>> = OnceLock::new();
+static ASB2014_CONFIGS: OnceLock<HashMap<&'static str, ASB2014>> = OnceLock::new();
+static TORO2002_CONFIGS: OnceLock<HashMap<&'static str, TORO2002>> = OnceLock::new();
+static PEZESHK2011_CONFIGS: OnceLock<HashMap<&'static str, PEZESHK2011>> = OnceLock::new();
+static AB2006_CONFIGS: OnceLock<HashMap<&'static str, AB2006>> = OnceLock::new();
+static MCVERRY2006_CONFIGS: OnceLock<HashMap<&'static str, MCVERRY2006>> = OnceLock::new();
+static BRADLEY2013_CONFIGS: OnceLock<HashMap<&'static str, BRADLEY2013>> = OnceLock::new();
+static LINLEE2008_CONFIGS: OnceLock<HashMap<&'static str, LINLEE2008>> = OnceLock::new();
+static GARCIA2005_CONFIGS: OnceLock<HashMap<&'static str, GARCIA2005>> = OnceLock::new();
+static MONTALVA2017_CONFIGS: OnceLock<HashMap<&'static str, MONTALVA2017>> = OnceLock::new();
+static IDRISS2014_CONFIGS: OnceLock<HashMap<&'static str, IDRISS2014>> = OnceLock::new();
+static ALLEN2012_CONFIGS: OnceLock<HashMap<&'static str, ALLEN2012>> = OnceLock::new();
+static TRAVASAROU2003_CONFIGS: OnceLock<HashMap<&'static str, TRAVASAROU2003>> = OnceLock::new();
+static AFSHARI2016_CONFIGS: OnceLock<HashMap<&'static str, AFSHARI2016>> = OnceLock::new();
+
+/// A predefined model configuration of any supported model family.
+pub type LibConfig = dyn GroundMotionModeling + Send + Sync;
 
 /// Lazily initializes and returns a reference to the global MF2013 configuration map.
 ///
@@ -14,7 +65,10 @@ static CONFIGS: OnceLock<HashMap<&'static str, MF2013>> = OnceLock::new();
 /// in a `HashMap`. Subsequent calls return a shared reference to this map.
 ///
 /// The map contains model configurations keyed by descriptive string identifiers such as
-/// `"config_mf2013_crustal_pga"` or `"config_mf2013_crustal_pga_2"`.
+/// `"config_mf2013_crustal_pga"` or `"config_mf2013_crustal_pga_2"`. PSA models are generated
+/// from [`MF2013_PSA_TABLE`](crate::mf2013::MF2013_PSA_TABLE) for each tectonic type with the
+/// period encoded by [`period_label`], e.g. `"config_mf2013_crustal_psa_03"` or
+/// `"config_mf2013_interplate_psa_30"`.
 ///
 /// # Returns
 ///
@@ -223,212 +277,17 @@ pub fn get_mf2013_lib_configs() -> &'static HashMap<&'static str, MF2013> {
             },
         );
 
-        // Crustal PSA 0.3s
-        map.insert(
-            "config_mf2013_crustal_psa_03",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.563,
-                b: -0.004033,
-                c: 0.639,
-                d: 0.005205,
-                e: 0.5,
-                sigma: 0.407229,
-                pd: 0.1006,
-                dl_min: 21.,
-                d0: 250.,
-                ps: -0.6217,
-                vs_max: 2000.,
-                v0: 350.,
-                gamma: 0.00007711,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Interplate PSA 0.3s
-        map.insert(
-            "config_mf2013_interplate_psa_03",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.563,
-                b: -0.00388,
-                c: 0.6544,
-                d: 0.005205,
-                e: 0.5,
-                sigma: 0.407229,
-                pd: 0.1006,
-                dl_min: 21.,
-                d0: 250.,
-                ps: -0.6217,
-                vs_max: 2000.,
-                v0: 350.,
-                gamma: 0.00007711,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Intraplate PSA 0.3s
-        map.insert(
-            "config_mf2013_intraplate_psa_03",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.563,
-                b: -0.004427,
-                c: 1.0482,
-                d: 0.005205,
-                e: 0.5,
-                sigma: 0.407229,
-                pd: 0.1006,
-                dl_min: 21.,
-                d0: 250.,
-                ps: -0.6217,
-                vs_max: 2000.,
-                v0: 350.,
-                gamma: 0.00007711,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Crustal PSA 1.0s
-        map.insert(
-            "config_mf2013_crustal_psa_10",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.6011,
-                b: -0.001955,
-                c: -0.2766,
-                d: 0.00055,
-                e: 0.5,
-                sigma: 0.410513,
-                pd: 0.2744,
-                dl_min: 39.32,
-                d0: 250.,
-                ps: -0.6755,
-                vs_max: 1423.23,
-                v0: 350.,
-                gamma: 0.00005324,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Interplate PSA 1.0s
-        map.insert(
-            "config_mf2013_interplate_psa_10",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.6011,
-                b: -0.001256,
-                c: -0.4191,
-                d: 0.00055,
-                e: 0.5,
-                sigma: 0.410513,
-                pd: 0.2744,
-                dl_min: 39.32,
-                d0: 250.,
-                ps: -0.6755,
-                vs_max: 1423.23,
-                v0: 350.,
-                gamma: 0.00005324,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Intraplate PSA 1.0s
-        map.insert(
-            "config_mf2013_intraplate_psa_10",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.6011,
-                b: -0.00229,
-                c: -0.024,
-                d: 0.00055,
-                e: 0.5,
-                sigma: 0.410513,
-                pd: 0.2744,
-                dl_min: 39.32,
-                d0: 250.,
-                ps: -0.6755,
-                vs_max: 1423.23,
-                v0: 350.,
-                gamma: 0.00005324,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Crustal PSA 3.0s
-        map.insert(
-            "config_mf2013_crustal_psa_30",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.7089,
-                b: -0.001276,
-                c: -1.6579,
-                d: 0.001021,
-                e: 0.5,
-                sigma: 0.379064,
-                pd: 0.3996,
-                dl_min: 69.69,
-                d0: 250.,
-                ps: -0.4398,
-                vs_max: 864.01,
-                v0: 350.,
-                gamma: 0.00002548,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Interplate PSA 3.0s
-        map.insert(
-            "config_mf2013_interplate_psa_30",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.7089,
-                b: -0.00047,
-                c: -1.9088,
-                d: 0.001021,
-                e: 0.5,
-                sigma: 0.379064,
-                pd: 0.3996,
-                dl_min: 69.69,
-                d0: 250.,
-                ps: -0.4398,
-                vs_max: 864.01,
-                v0: 350.,
-                gamma: 0.00002548,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
-
-        // Intraplate PSA 3.0s
-        map.insert(
-            "config_mf2013_intraplate_psa_30",
-            MF2013 {
-                mw0: 8.1,
-                a: 0.7089,
-                b: -0.001086,
-                c: -1.5998,
-                d: 0.001021,
-                e: 0.5,
-                sigma: 0.379064,
-                pd: 0.3996,
-                dl_min: 69.69,
-                d0: 250.,
-                ps: -0.4398,
-                vs_max: 864.01,
-                v0: 350.,
-                gamma: 0.00002548,
-                asid: false,
-                motion_kind: GmpePointKind::Psa,
-            },
-        );
+        // PSA at 0.3, 1 and 3 s
+        let types = [
+            ("mf2013_crustal", TectonicType::Crustal),
+            ("mf2013_interplate", TectonicType::Interplate),
+            ("mf2013_intraplate", TectonicType::Intraplate),
+        ];
+        for (model, tectonic_type) in types {
+            for (period, gmpe) in MF2013::psa_table(tectonic_type) {
+                map.insert(table_key(model, period), gmpe);
+            }
+        }
 
         // AB1995 PGA
         map.insert(
@@ -686,3 +545,1086 @@ pub fn get_mf2013_lib_configs() -> &'static HashMap<&'static str, MF2013> {
         map
     })
 }
+
+/// Look up the predefined MF2013 PSA model for a spectral period and tectonic type.
+///
+/// Returns `None` if the period is not a row of
+/// [`MF2013_PSA_TABLE`](crate::mf2013::MF2013_PSA_TABLE).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_mf2013_psa;
+/// use ground_motion_lib::gmm::TectonicType;
+///
+/// let gmpe = get_mf2013_psa(1., TectonicType::Interplate).unwrap();
+/// assert!(gmpe.a > 0.);
+/// assert!(get_mf2013_psa(0.5, TectonicType::Interplate).is_none());
+/// ```
+pub fn get_mf2013_psa(period: f64, tectonic_type: TectonicType) -> Option<&'static MF2013> {
+    if period <= 0. {
+        return None;
+    }
+    let key = format!("config_mf2013_{tectonic_type}_{}", period_label(period));
+    get_mf2013_lib_configs().get(key.as_str())
+}
+
+/// Lazily initializes and returns a reference to the global BSSA14 configuration map.
+///
+/// Keys follow the MF2013 naming: `"config_bssa2014_pga"`, `"config_bssa2014_pgv"` and
+/// `"config_bssa2014_psa_XX"`, where `XX` is the spectral period in tenths of a second
+/// (`psa_01` = 0.1 s, `psa_10` = 1.0 s, `psa_30` = 3.0 s).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_bssa2014_lib_configs;
+///
+/// let configs = get_bssa2014_lib_configs();
+/// let psa_model = configs.get("config_bssa2014_psa_10").unwrap();
+/// println!("Period: {}", psa_model.coeffs.period);
+/// ```
+pub fn get_bssa2014_lib_configs() -> &'static HashMap<&'static str, BSSA2014> {
+    BSSA2014_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            "config_bssa2014_pga",
+            BSSA2014::new(BSSA2014_PGA, GmpePointKind::Pga),
+        );
+        map.insert(
+            "config_bssa2014_pgv",
+            BSSA2014::new(BSSA2014_PGV, GmpePointKind::Pgv),
+        );
+        let psa_keys = [
+            "config_bssa2014_psa_01",
+            "config_bssa2014_psa_02",
+            "config_bssa2014_psa_03",
+            "config_bssa2014_psa_05",
+            "config_bssa2014_psa_10",
+            "config_bssa2014_psa_20",
+            "config_bssa2014_psa_30",
+        ];
+        for (key, coeffs) in psa_keys.into_iter().zip(BSSA2014_PSA) {
+            map.insert(key, BSSA2014::new(coeffs, period_kind(coeffs.period)));
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global CB14 configuration map.
+///
+/// Keys: `"config_cb2014_pga"`, `"config_cb2014_pgv"`, `"config_cb2014_psa_03"`,
+/// `"config_cb2014_psa_10"` and `"config_cb2014_psa_30"`.
+pub fn get_cb2014_lib_configs() -> &'static HashMap<&'static str, CB2014> {
+    CB2014_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            "config_cb2014_pga",
+            CB2014::new(CB2014_PGA, GmpePointKind::Pga),
+        );
+        map.insert(
+            "config_cb2014_pgv",
+            CB2014::new(CB2014_PGV, GmpePointKind::Pgv),
+        );
+        let psa_keys = [
+            "config_cb2014_psa_03",
+            "config_cb2014_psa_10",
+            "config_cb2014_psa_30",
+        ];
+        for (key, coeffs) in psa_keys.into_iter().zip(CB2014_PSA) {
+            map.insert(key, CB2014::new(coeffs, period_kind(coeffs.period)));
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global CY14 configuration map.
+///
+/// Keys: `"config_cy2014_pga"` and `"config_cy2014_pgv"`. No PSA configurations are provided,
+/// see the [`cy2014`](crate::cy2014) module.
+pub fn get_cy2014_lib_configs() -> &'static HashMap<&'static str, CY2014> {
+    CY2014_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            "config_cy2014_pga",
+            CY2014::new(CY2014_PGA, GmpePointKind::Pga),
+        );
+        map.insert(
+            "config_cy2014_pgv",
+            CY2014::new(CY2014_PGV, GmpePointKind::Pgv),
+        );
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Zhao et al. (2006) configuration map.
+///
+/// Keys distinguish the tectonic type the same way the MF2013 entries do, e.g.
+/// `"config_zhao2006_crustal_pga"`, `"config_zhao2006_interplate_psa_10"` or
+/// `"config_zhao2006_intraplate_psa_30"`.
+pub fn get_zhao2006_lib_configs() -> &'static HashMap<&'static str, ZHAO2006> {
+    ZHAO2006_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let keys = [
+            (
+                TectonicType::Crustal,
+                [
+                    "config_zhao2006_crustal_pga",
+                    "config_zhao2006_crustal_psa_01",
+                    "config_zhao2006_crustal_psa_03",
+                    "config_zhao2006_crustal_psa_10",
+                    "config_zhao2006_crustal_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                [
+                    "config_zhao2006_interplate_pga",
+                    "config_zhao2006_interplate_psa_01",
+                    "config_zhao2006_interplate_psa_03",
+                    "config_zhao2006_interplate_psa_10",
+                    "config_zhao2006_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                [
+                    "config_zhao2006_intraplate_pga",
+                    "config_zhao2006_intraplate_psa_01",
+                    "config_zhao2006_intraplate_psa_03",
+                    "config_zhao2006_intraplate_psa_10",
+                    "config_zhao2006_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (tectonic_type, names) in keys {
+            for (key, coeffs) in names.into_iter().zip(ZHAO2006_COEFFS) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, ZHAO2006::new(coeffs, tectonic_type, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Si & Midorikawa (1999) configuration
+/// map.
+///
+/// Keys: `"config_sm1999_{crustal,interplate,intraplate}_{pga,pgv}"`.
+pub fn get_sm1999_lib_configs() -> &'static HashMap<&'static str, SM1999> {
+    SM1999_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        for (tectonic_type, pga, pgv) in [
+            (
+                TectonicType::Crustal,
+                "config_sm1999_crustal_pga",
+                "config_sm1999_crustal_pgv",
+            ),
+            (
+                TectonicType::Interplate,
+                "config_sm1999_interplate_pga",
+                "config_sm1999_interplate_pgv",
+            ),
+            (
+                TectonicType::Intraplate,
+                "config_sm1999_intraplate_pga",
+                "config_sm1999_intraplate_pgv",
+            ),
+        ] {
+            map.insert(pga, SM1999::pga(tectonic_type));
+            map.insert(pgv, SM1999::pgv(tectonic_type));
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Kanno et al. (2006) configuration
+/// map.
+///
+/// Keys: `"config_kanno2006_pga"` and `"config_kanno2006_pgv"`. Shallow or deep coefficients
+/// are selected from the focal depth of the earthquake.
+pub fn get_kanno2006_lib_configs() -> &'static HashMap<&'static str, KANNO2006> {
+    KANNO2006_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert("config_kanno2006_pga", KANNO2006_PGA);
+        map.insert("config_kanno2006_pgv", KANNO2006_PGV);
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Atkinson & Boore (2003)
+/// configuration map.
+///
+/// Keys distinguish the tectonic type the same way the MF2013 entries do, with an optional
+/// regional tag, e.g. `"config_ab2003_intraplate_pga"`, `"config_ab2003_japan_interplate_psa_10"`
+/// or `"config_ab2003_cascadia_intraplate_psa_02"`.
+pub fn get_ab2003_lib_configs() -> &'static HashMap<&'static str, AB2003> {
+    AB2003_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let tables = [
+            (
+                TectonicType::Interplate,
+                Ab2003Region::Global,
+                AB2003_INTERFACE,
+                [
+                    "config_ab2003_interplate_pga",
+                    "config_ab2003_interplate_psa_01",
+                    "config_ab2003_interplate_psa_02",
+                    "config_ab2003_interplate_psa_04",
+                    "config_ab2003_interplate_psa_10",
+                    "config_ab2003_interplate_psa_20",
+                    "config_ab2003_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                Ab2003Region::Japan,
+                AB2003_INTERFACE,
+                [
+                    "config_ab2003_japan_interplate_pga",
+                    "config_ab2003_japan_interplate_psa_01",
+                    "config_ab2003_japan_interplate_psa_02",
+                    "config_ab2003_japan_interplate_psa_04",
+                    "config_ab2003_japan_interplate_psa_10",
+                    "config_ab2003_japan_interplate_psa_20",
+                    "config_ab2003_japan_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                Ab2003Region::Cascadia,
+                AB2003_INTERFACE,
+                [
+                    "config_ab2003_cascadia_interplate_pga",
+                    "config_ab2003_cascadia_interplate_psa_01",
+                    "config_ab2003_cascadia_interplate_psa_02",
+                    "config_ab2003_cascadia_interplate_psa_04",
+                    "config_ab2003_cascadia_interplate_psa_10",
+                    "config_ab2003_cascadia_interplate_psa_20",
+                    "config_ab2003_cascadia_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Ab2003Region::Global,
+                AB2003_INSLAB,
+                [
+                    "config_ab2003_intraplate_pga",
+                    "config_ab2003_intraplate_psa_01",
+                    "config_ab2003_intraplate_psa_02",
+                    "config_ab2003_intraplate_psa_04",
+                    "config_ab2003_intraplate_psa_10",
+                    "config_ab2003_intraplate_psa_20",
+                    "config_ab2003_intraplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Ab2003Region::Japan,
+                AB2003_INSLAB,
+                [
+                    "config_ab2003_japan_intraplate_pga",
+                    "config_ab2003_japan_intraplate_psa_01",
+                    "config_ab2003_japan_intraplate_psa_02",
+                    "config_ab2003_japan_intraplate_psa_04",
+                    "config_ab2003_japan_intraplate_psa_10",
+                    "config_ab2003_japan_intraplate_psa_20",
+                    "config_ab2003_japan_intraplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Ab2003Region::Cascadia,
+                AB2003_INSLAB,
+                [
+                    "config_ab2003_cascadia_intraplate_pga",
+                    "config_ab2003_cascadia_intraplate_psa_01",
+                    "config_ab2003_cascadia_intraplate_psa_02",
+                    "config_ab2003_cascadia_intraplate_psa_04",
+                    "config_ab2003_cascadia_intraplate_psa_10",
+                    "config_ab2003_cascadia_intraplate_psa_20",
+                    "config_ab2003_cascadia_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (tectonic_type, region, table, names) in tables {
+            for (key, coeffs) in names.into_iter().zip(table) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, AB2003::new(coeffs, tectonic_type, region, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global BC Hydro (2016) configuration map.
+///
+/// Keys distinguish the tectonic type the same way the MF2013 entries do, e.g.
+/// `"config_bchydro2016_interplate_pga"` or `"config_bchydro2016_intraplate_psa_10"`.
+pub fn get_bchydro2016_lib_configs() -> &'static HashMap<&'static str, BCHYDRO2016> {
+    BCHYDRO2016_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let keys = [
+            (
+                TectonicType::Interplate,
+                [
+                    "config_bchydro2016_interplate_pga",
+                    "config_bchydro2016_interplate_psa_01",
+                    "config_bchydro2016_interplate_psa_02",
+                    "config_bchydro2016_interplate_psa_03",
+                    "config_bchydro2016_interplate_psa_05",
+                    "config_bchydro2016_interplate_psa_10",
+                    "config_bchydro2016_interplate_psa_20",
+                    "config_bchydro2016_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                [
+                    "config_bchydro2016_intraplate_pga",
+                    "config_bchydro2016_intraplate_psa_01",
+                    "config_bchydro2016_intraplate_psa_02",
+                    "config_bchydro2016_intraplate_psa_03",
+                    "config_bchydro2016_intraplate_psa_05",
+                    "config_bchydro2016_intraplate_psa_10",
+                    "config_bchydro2016_intraplate_psa_20",
+                    "config_bchydro2016_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (tectonic_type, names) in keys {
+            for (key, coeffs) in names.into_iter().zip(BCHYDRO2016_COEFFS) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, BCHYDRO2016::new(coeffs, tectonic_type, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Youngs et al. (1997) configuration
+/// map.
+///
+/// Rock and soil variants are separate entries, e.g. `"config_youngs1997_rock_interplate_pga"`
+/// or `"config_youngs1997_soil_intraplate_psa_10"`.
+pub fn get_youngs1997_lib_configs() -> &'static HashMap<&'static str, YOUNGS1997> {
+    YOUNGS1997_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let tables = [
+            (
+                Youngs1997Site::Rock,
+                TectonicType::Interplate,
+                YOUNGS1997_ROCK,
+                [
+                    "config_youngs1997_rock_interplate_pga",
+                    "config_youngs1997_rock_interplate_psa_01",
+                    "config_youngs1997_rock_interplate_psa_02",
+                    "config_youngs1997_rock_interplate_psa_03",
+                    "config_youngs1997_rock_interplate_psa_05",
+                    "config_youngs1997_rock_interplate_psa_10",
+                    "config_youngs1997_rock_interplate_psa_20",
+                    "config_youngs1997_rock_interplate_psa_30",
+                ],
+            ),
+            (
+                Youngs1997Site::Rock,
+                TectonicType::Intraplate,
+                YOUNGS1997_ROCK,
+                [
+                    "config_youngs1997_rock_intraplate_pga",
+                    "config_youngs1997_rock_intraplate_psa_01",
+                    "config_youngs1997_rock_intraplate_psa_02",
+                    "config_youngs1997_rock_intraplate_psa_03",
+                    "config_youngs1997_rock_intraplate_psa_05",
+                    "config_youngs1997_rock_intraplate_psa_10",
+                    "config_youngs1997_rock_intraplate_psa_20",
+                    "config_youngs1997_rock_intraplate_psa_30",
+                ],
+            ),
+            (
+                Youngs1997Site::Soil,
+                TectonicType::Interplate,
+                YOUNGS1997_SOIL,
+                [
+                    "config_youngs1997_soil_interplate_pga",
+                    "config_youngs1997_soil_interplate_psa_01",
+                    "config_youngs1997_soil_interplate_psa_02",
+                    "config_youngs1997_soil_interplate_psa_03",
+                    "config_youngs1997_soil_interplate_psa_05",
+                    "config_youngs1997_soil_interplate_psa_10",
+                    "config_youngs1997_soil_interplate_psa_20",
+                    "config_youngs1997_soil_interplate_psa_30",
+                ],
+            ),
+            (
+                Youngs1997Site::Soil,
+                TectonicType::Intraplate,
+                YOUNGS1997_SOIL,
+                [
+                    "config_youngs1997_soil_intraplate_pga",
+                    "config_youngs1997_soil_intraplate_psa_01",
+                    "config_youngs1997_soil_intraplate_psa_02",
+                    "config_youngs1997_soil_intraplate_psa_03",
+                    "config_youngs1997_soil_intraplate_psa_05",
+                    "config_youngs1997_soil_intraplate_psa_10",
+                    "config_youngs1997_soil_intraplate_psa_20",
+                    "config_youngs1997_soil_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (site, tectonic_type, table, names) in tables {
+            for (key, coeffs) in names.into_iter().zip(table) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, YOUNGS1997::new(coeffs, site, tectonic_type, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Parker et al. (2020) configuration
+/// map.
+///
+/// Keys follow the Atkinson & Boore (2003) entries, with an optional regional tag, e.g.
+/// `"config_parker2020_interplate_pga"`, `"config_parker2020_japan_intraplate_psa_10"` or
+/// `"config_parker2020_south_america_interplate_psa_30"`.
+pub fn get_parker2020_lib_configs() -> &'static HashMap<&'static str, PARKER2020> {
+    PARKER2020_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let tables = [
+            (
+                TectonicType::Interplate,
+                Parker2020Region::Global,
+                PARKER2020_INTERFACE,
+                [
+                    "config_parker2020_interplate_pga",
+                    "config_parker2020_interplate_psa_02",
+                    "config_parker2020_interplate_psa_10",
+                    "config_parker2020_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                Parker2020Region::Japan,
+                PARKER2020_INTERFACE,
+                [
+                    "config_parker2020_japan_interplate_pga",
+                    "config_parker2020_japan_interplate_psa_02",
+                    "config_parker2020_japan_interplate_psa_10",
+                    "config_parker2020_japan_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                Parker2020Region::Cascadia,
+                PARKER2020_INTERFACE,
+                [
+                    "config_parker2020_cascadia_interplate_pga",
+                    "config_parker2020_cascadia_interplate_psa_02",
+                    "config_parker2020_cascadia_interplate_psa_10",
+                    "config_parker2020_cascadia_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Interplate,
+                Parker2020Region::SouthAmerica,
+                PARKER2020_INTERFACE,
+                [
+                    "config_parker2020_south_america_interplate_pga",
+                    "config_parker2020_south_america_interplate_psa_02",
+                    "config_parker2020_south_america_interplate_psa_10",
+                    "config_parker2020_south_america_interplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Parker2020Region::Global,
+                PARKER2020_SLAB,
+                [
+                    "config_parker2020_intraplate_pga",
+                    "config_parker2020_intraplate_psa_02",
+                    "config_parker2020_intraplate_psa_10",
+                    "config_parker2020_intraplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Parker2020Region::Japan,
+                PARKER2020_SLAB,
+                [
+                    "config_parker2020_japan_intraplate_pga",
+                    "config_parker2020_japan_intraplate_psa_02",
+                    "config_parker2020_japan_intraplate_psa_10",
+                    "config_parker2020_japan_intraplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Parker2020Region::Cascadia,
+                PARKER2020_SLAB,
+                [
+                    "config_parker2020_cascadia_intraplate_pga",
+                    "config_parker2020_cascadia_intraplate_psa_02",
+                    "config_parker2020_cascadia_intraplate_psa_10",
+                    "config_parker2020_cascadia_intraplate_psa_30",
+                ],
+            ),
+            (
+                TectonicType::Intraplate,
+                Parker2020Region::SouthAmerica,
+                PARKER2020_SLAB,
+                [
+                    "config_parker2020_south_america_intraplate_pga",
+                    "config_parker2020_south_america_intraplate_psa_02",
+                    "config_parker2020_south_america_intraplate_psa_10",
+                    "config_parker2020_south_america_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (tectonic_type, region, table, names) in tables {
+            for (key, coeffs) in names.into_iter().zip(table) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, PARKER2020::new(coeffs, tectonic_type, region, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Akkar, Sandikkaya & Bommer (2014)
+/// configuration map.
+///
+/// Keys carry the distance metric of the coefficient set, e.g. `"config_asb2014_rjb_pga"`,
+/// `"config_asb2014_repi_pgv"` or `"config_asb2014_rhyp_psa_10"`.
+pub fn get_asb2014_lib_configs() -> &'static HashMap<&'static str, ASB2014> {
+    ASB2014_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let tables = [
+            (
+                Asb2014Distance::Rjb,
+                ASB2014_RJB,
+                [
+                    "config_asb2014_rjb_pga",
+                    "config_asb2014_rjb_pgv",
+                    "config_asb2014_rjb_psa_02",
+                    "config_asb2014_rjb_psa_10",
+                ],
+            ),
+            (
+                Asb2014Distance::Repi,
+                ASB2014_REPI,
+                [
+                    "config_asb2014_repi_pga",
+                    "config_asb2014_repi_pgv",
+                    "config_asb2014_repi_psa_02",
+                    "config_asb2014_repi_psa_10",
+                ],
+            ),
+            (
+                Asb2014Distance::Rhyp,
+                ASB2014_RHYP,
+                [
+                    "config_asb2014_rhyp_pga",
+                    "config_asb2014_rhyp_pgv",
+                    "config_asb2014_rhyp_psa_02",
+                    "config_asb2014_rhyp_psa_10",
+                ],
+            ),
+        ];
+        for (distance, table, names) in tables {
+            for (key, coeffs) in names.into_iter().zip(table) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, ASB2014::new(coeffs, distance, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Toro et al. (2002) configuration
+/// map.
+///
+/// The map is generated from the rows of the model coefficient table, e.g.
+/// `"config_toro2002_pga"` or `"config_toro2002_psa_10"`.
+pub fn get_toro2002_lib_configs() -> &'static HashMap<&'static str, TORO2002> {
+    TORO2002_CONFIGS.get_or_init(|| {
+        TORO2002::from_table()
+            .into_iter()
+            .map(|model| (table_key("toro2002", model.period), model))
+            .collect()
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Pezeshk et al. (2011) configuration
+/// map.
+///
+/// The map is generated from the rows of the model coefficient table. Hard-rock entries are
+/// keyed e.g. `"config_pezeshk2011_pga"`, entries with the Vs30-based site adjustment e.g.
+/// `"config_pezeshk2011_vs30_psa_10"`.
+pub fn get_pezeshk2011_lib_configs() -> &'static HashMap<&'static str, PEZESHK2011> {
+    PEZESHK2011_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        for model in PEZESHK2011::from_table() {
+            map.insert(table_key("pezeshk2011", model.period), model);
+            map.insert(
+                table_key("pezeshk2011_vs30", model.period),
+                model.with_site_adjustment(),
+            );
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Atkinson & Boore (2006)
+/// configuration map.
+///
+/// The map is generated from the rows of the model coefficient table for the reference stress
+/// drop of 140 bars, e.g. `"config_ab2006_pga"` or `"config_ab2006_psa_10"`. Use
+/// [`AB2006::with_stress`] to model other stress drops.
+pub fn get_ab2006_lib_configs() -> &'static HashMap<&'static str, AB2006> {
+    AB2006_CONFIGS.get_or_init(|| {
+        AB2006::from_table()
+            .into_iter()
+            .map(|model| (table_key("ab2006", model.period), model))
+            .collect()
+    })
+}
+
+/// Lazily initializes and returns a reference to the global McVerry et al. (2006) configuration
+/// map.
+///
+/// The map is generated from the rows of the model coefficient table for each tectonic type,
+/// e.g. `"config_mcverry2006_crustal_pga"`, `"config_mcverry2006_interplate_psa_10"` or
+/// `"config_mcverry2006_intraplate_psa_30"`.
+pub fn get_mcverry2006_lib_configs() -> &'static HashMap<&'static str, MCVERRY2006> {
+    MCVERRY2006_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let types = [
+            ("mcverry2006_crustal", TectonicType::Crustal),
+            ("mcverry2006_interplate", TectonicType::Interplate),
+            ("mcverry2006_intraplate", TectonicType::Intraplate),
+        ];
+        for (model, tectonic_type) in types {
+            for coeffs in McVerry2006Coeffs::from_table() {
+                map.insert(
+                    table_key(model, coeffs.period),
+                    MCVERRY2006::new(coeffs, tectonic_type, period_kind(coeffs.period)),
+                );
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Bradley (2013) configuration map.
+///
+/// The map is generated from the rows of the model coefficient table, e.g.
+/// `"config_bradley2013_pga"` or `"config_bradley2013_psa_10"`.
+pub fn get_bradley2013_lib_configs() -> &'static HashMap<&'static str, BRADLEY2013> {
+    BRADLEY2013_CONFIGS.get_or_init(|| {
+        Bradley2013Coeffs::from_table()
+            .into_iter()
+            .map(|coeffs| {
+                (
+                    table_key("bradley2013", coeffs.period),
+                    BRADLEY2013::new(coeffs, period_kind(coeffs.period)),
+                )
+            })
+            .collect()
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Lin & Lee (2008) configuration map.
+///
+/// Rock and soil variants are separate entries, e.g. `"config_linlee2008_rock_interplate_pga"`
+/// or `"config_linlee2008_soil_intraplate_psa_10"`.
+pub fn get_linlee2008_lib_configs() -> &'static HashMap<&'static str, LINLEE2008> {
+    LINLEE2008_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let tables = [
+            (
+                LinLee2008Site::Rock,
+                TectonicType::Interplate,
+                LINLEE2008_ROCK,
+                [
+                    "config_linlee2008_rock_interplate_pga",
+                    "config_linlee2008_rock_interplate_psa_01",
+                    "config_linlee2008_rock_interplate_psa_02",
+                    "config_linlee2008_rock_interplate_psa_05",
+                    "config_linlee2008_rock_interplate_psa_10",
+                    "config_linlee2008_rock_interplate_psa_30",
+                ],
+            ),
+            (
+                LinLee2008Site::Rock,
+                TectonicType::Intraplate,
+                LINLEE2008_ROCK,
+                [
+                    "config_linlee2008_rock_intraplate_pga",
+                    "config_linlee2008_rock_intraplate_psa_01",
+                    "config_linlee2008_rock_intraplate_psa_02",
+                    "config_linlee2008_rock_intraplate_psa_05",
+                    "config_linlee2008_rock_intraplate_psa_10",
+                    "config_linlee2008_rock_intraplate_psa_30",
+                ],
+            ),
+            (
+                LinLee2008Site::Soil,
+                TectonicType::Interplate,
+                LINLEE2008_SOIL,
+                [
+                    "config_linlee2008_soil_interplate_pga",
+                    "config_linlee2008_soil_interplate_psa_01",
+                    "config_linlee2008_soil_interplate_psa_02",
+                    "config_linlee2008_soil_interplate_psa_05",
+                    "config_linlee2008_soil_interplate_psa_10",
+                    "config_linlee2008_soil_interplate_psa_30",
+                ],
+            ),
+            (
+                LinLee2008Site::Soil,
+                TectonicType::Intraplate,
+                LINLEE2008_SOIL,
+                [
+                    "config_linlee2008_soil_intraplate_pga",
+                    "config_linlee2008_soil_intraplate_psa_01",
+                    "config_linlee2008_soil_intraplate_psa_02",
+                    "config_linlee2008_soil_intraplate_psa_05",
+                    "config_linlee2008_soil_intraplate_psa_10",
+                    "config_linlee2008_soil_intraplate_psa_30",
+                ],
+            ),
+        ];
+        for (site, tectonic_type, table, names) in tables {
+            for (key, coeffs) in names.into_iter().zip(table) {
+                let kind = period_kind(coeffs.period);
+                map.insert(key, LINLEE2008::new(coeffs, site, tectonic_type, kind));
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global García et al. (2005) configuration
+/// map.
+///
+/// The map is generated from the rows of the model coefficient table. The model only covers
+/// intraslab events, which the keys make explicit, e.g. `"config_garcia2005_intraplate_pga"`,
+/// `"config_garcia2005_intraplate_pgv"` or `"config_garcia2005_intraplate_psa_10"`.
+pub fn get_garcia2005_lib_configs() -> &'static HashMap<&'static str, GARCIA2005> {
+    GARCIA2005_CONFIGS.get_or_init(|| {
+        GARCIA2005::from_table()
+            .into_iter()
+            .map(|model| (table_key("garcia2005_intraplate", model.period), model))
+            .collect()
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Montalva et al. (2017) configuration
+/// map.
+///
+/// The map is generated from the rows of the model coefficient table for interface and
+/// intraslab events, e.g. `"config_montalva2017_interplate_pga"` or
+/// `"config_montalva2017_intraplate_psa_10"`.
+pub fn get_montalva2017_lib_configs() -> &'static HashMap<&'static str, MONTALVA2017> {
+    MONTALVA2017_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        let types = [
+            ("montalva2017_interplate", TectonicType::Interplate),
+            ("montalva2017_intraplate", TectonicType::Intraplate),
+        ];
+        for (model, tectonic_type) in types {
+            for coeffs in Montalva2017Coeffs::from_table() {
+                map.insert(
+                    table_key(model, coeffs.period),
+                    MONTALVA2017::new(coeffs, tectonic_type, period_kind(coeffs.period)),
+                );
+            }
+        }
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Idriss (2014) configuration map.
+///
+/// The map is generated from the rows of the model coefficient table, e.g.
+/// `"config_idriss2014_pga"` or `"config_idriss2014_psa_10"`.
+pub fn get_idriss2014_lib_configs() -> &'static HashMap<&'static str, IDRISS2014> {
+    IDRISS2014_CONFIGS.get_or_init(|| {
+        IDRISS2014::from_table()
+            .into_iter()
+            .map(|model| (table_key("idriss2014", model.period), model))
+            .collect()
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Allen et al. (2012) intensity
+/// prediction equation configuration map.
+///
+/// Keys carry the distance metric of the coefficient set: `"config_allen2012_rhyp_mmi"` and
+/// `"config_allen2012_rrup_mmi"`.
+pub fn get_allen2012_lib_configs() -> &'static HashMap<&'static str, ALLEN2012> {
+    ALLEN2012_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            "config_allen2012_rhyp_mmi",
+            ALLEN2012::new(Allen2012Distance::Rhyp),
+        );
+        map.insert(
+            "config_allen2012_rrup_mmi",
+            ALLEN2012::new(Allen2012Distance::Rrup),
+        );
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Travasarou et al. (2003) Arias
+/// intensity configuration map.
+///
+/// Key: `"config_travasarou2003_ia"`.
+pub fn get_travasarou2003_lib_configs() -> &'static HashMap<&'static str, TRAVASAROU2003> {
+    TRAVASAROU2003_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert("config_travasarou2003_ia", TRAVASAROU2003::new());
+        map
+    })
+}
+
+/// Lazily initializes and returns a reference to the global Afshari & Stewart (2016)
+/// significant duration configuration map.
+///
+/// Keys: `"config_afshari2016_ds575"` and `"config_afshari2016_ds595"`.
+pub fn get_afshari2016_lib_configs() -> &'static HashMap<&'static str, AFSHARI2016> {
+    AFSHARI2016_CONFIGS.get_or_init(|| {
+        let mut map = HashMap::new();
+        map.insert(
+            "config_afshari2016_ds575",
+            AFSHARI2016::new(AFSHARI2016_DS575, GmpePointKind::Ds575),
+        );
+        map.insert(
+            "config_afshari2016_ds595",
+            AFSHARI2016::new(AFSHARI2016_DS595, GmpePointKind::Ds595),
+        );
+        map
+    })
+}
+
+/// Configuration key of a table-driven model for the given spectral period.
+///
+/// The key is leaked to obtain a `'static` lifetime; this happens once per key when the
+/// configuration map is initialized.
+fn table_key(model: &str, period: f64) -> &'static str {
+    Box::leak(format!("config_{model}_{}", period_label(period)).into_boxed_str())
+}
+
+/// Look up a predefined configuration of any model family by its key.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_lib_config;
+///
+/// assert!(get_lib_config("config_mf2013_crustal_pga").is_some());
+/// assert!(get_lib_config("config_bssa2014_pga").is_some());
+/// assert!(get_lib_config("unknown").is_none());
+/// ```
+pub fn get_lib_config(name: &str) -> Option<&'static LibConfig> {
+    lookup(get_mf2013_lib_configs(), name)
+        .or_else(|| lookup(get_bssa2014_lib_configs(), name))
+        .or_else(|| lookup(get_cb2014_lib_configs(), name))
+        .or_else(|| lookup(get_cy2014_lib_configs(), name))
+        .or_else(|| lookup(get_zhao2006_lib_configs(), name))
+        .or_else(|| lookup(get_sm1999_lib_configs(), name))
+        .or_else(|| lookup(get_kanno2006_lib_configs(), name))
+        .or_else(|| lookup(get_ab2003_lib_configs(), name))
+        .or_else(|| lookup(get_bchydro2016_lib_configs(), name))
+        .or_else(|| lookup(get_youngs1997_lib_configs(), name))
+        .or_else(|| lookup(get_parker2020_lib_configs(), name))
+        .or_else(|| lookup(get_asb2014_lib_configs(), name))
+        .or_else(|| lookup(get_toro2002_lib_configs(), name))
+        .or_else(|| lookup(get_pezeshk2011_lib_configs(), name))
+        .or_else(|| lookup(get_ab2006_lib_configs(), name))
+        .or_else(|| lookup(get_mcverry2006_lib_configs(), name))
+        .or_else(|| lookup(get_bradley2013_lib_configs(), name))
+        .or_else(|| lookup(get_linlee2008_lib_configs(), name))
+        .or_else(|| lookup(get_garcia2005_lib_configs(), name))
+        .or_else(|| lookup(get_montalva2017_lib_configs(), name))
+        .or_else(|| lookup(get_idriss2014_lib_configs(), name))
+        .or_else(|| lookup(get_allen2012_lib_configs(), name))
+        .or_else(|| lookup(get_travasarou2003_lib_configs(), name))
+        .or_else(|| lookup(get_afshari2016_lib_configs(), name))
+}
+
+/// Maximum applicable distances (km) of model families by configuration key prefix, from the
+/// distance ranges of the data sets the models were derived from.
+const MAX_DISTANCES_KM: [(&str, f64); 14] = [
+    ("config_ab2003_", 300.),
+    ("config_ab2006_", 1000.),
+    ("config_bchydro2016_", 300.),
+    ("config_bssa2014_", 400.),
+    ("config_cb2014_", 300.),
+    ("config_cy2014_", 300.),
+    ("config_idriss2014_", 150.),
+    ("config_kanno2006_", 450.),
+    ("config_mf2013_", 200.),
+    ("config_parker2020_", 1000.),
+    ("config_pezeshk2011_", 1000.),
+    ("config_toro2002_", 1000.),
+    ("config_youngs1997_", 500.),
+    ("config_zhao2006_", 300.),
+];
+
+/// Maximum applicable distance (km) of a predefined configuration.
+///
+/// # Returns
+///
+/// The maximum distance of the model family of the configuration, or `None` if the key is
+/// unknown or the family has no documented distance limit.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_max_distance_km;
+///
+/// assert_eq!(get_max_distance_km("config_mf2013_crustal_pga"), Some(200.));
+/// assert_eq!(get_max_distance_km("unknown"), None);
+/// ```
+pub fn get_max_distance_km(name: &str) -> Option<f64> {
+    get_lib_config(name)?;
+    MAX_DISTANCES_KM
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, max_distance)| *max_distance)
+}
+
+/// Look up a predefined configuration limited to its maximum applicable distance, see
+/// [`get_max_distance_km`] and [`CutoffModel`].
+///
+/// # Returns
+///
+/// `None` if the key is unknown or the family has no documented distance limit.
+pub fn get_lib_config_with_cutoff(name: &str) -> Option<CutoffModel<&'static LibConfig>> {
+    Some(CutoffModel::new(
+        get_lib_config(name)?,
+        get_max_distance_km(name)?,
+    ))
+}
+
+fn lookup<M: GroundMotionModeling + Send + Sync>(
+    configs: &'static HashMap<&'static str, M>,
+    name: &str,
+) -> Option<&'static LibConfig> {
+    configs.get(name).map(|c| c as &LibConfig)
+}
+
+/// Look up all predefined configurations of a model family across spectral periods.
+///
+/// A family is a key prefix shared by configurations that differ only in the type of motion
+/// suffix (`pga`, `pgv`, `pgd` or `psa_XX`, see [`period_label`]), e.g.
+/// `"config_mf2013_crustal"` or `"config_bssa2014"`.
+///
+/// # Returns
+///
+/// `(period, config)` pairs sorted by period, with `0.0` for PGA, `-1.0` for PGV and `-2.0` for
+/// PGD. Empty if no configuration belongs to the family.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_family_configs;
+///
+/// let family = get_family_configs("config_mf2013_crustal");
+/// assert!(family.iter().any(|(period, _)| *period == 0.));
+/// assert!(family.iter().any(|(period, _)| *period == 1.));
+/// ```
+pub fn get_family_configs(family: &str) -> Vec<(f64, &'static LibConfig)> {
+    let mut configs: Vec<(f64, &'static LibConfig)> = list_lib_configs()
+        .into_iter()
+        .filter_map(|key| {
+            let label = key.strip_prefix(family)?.strip_prefix('_')?;
+            Some((label_period(label)?, get_lib_config(key)?))
+        })
+        .collect();
+    configs.sort_by(|a, b| a.0.total_cmp(&b.0));
+    configs
+}
+
+/// Sorted list of keys of all predefined configurations.
+pub fn list_lib_configs() -> Vec<&'static str> {
+    let mut keys: Vec<&'static str> = get_mf2013_lib_configs()
+        .keys()
+        .chain(get_bssa2014_lib_configs().keys())
+        .chain(get_cb2014_lib_configs().keys())
+        .chain(get_cy2014_lib_configs().keys())
+        .chain(get_zhao2006_lib_configs().keys())
+        .chain(get_sm1999_lib_configs().keys())
+        .chain(get_kanno2006_lib_configs().keys())
+        .chain(get_ab2003_lib_configs().keys())
+        .chain(get_bchydro2016_lib_configs().keys())
+        .chain(get_youngs1997_lib_configs().keys())
+        .chain(get_parker2020_lib_configs().keys())
+        .chain(get_asb2014_lib_configs().keys())
+        .chain(get_toro2002_lib_configs().keys())
+        .chain(get_pezeshk2011_lib_configs().keys())
+        .chain(get_ab2006_lib_configs().keys())
+        .chain(get_mcverry2006_lib_configs().keys())
+        .chain(get_bradley2013_lib_configs().keys())
+        .chain(get_linlee2008_lib_configs().keys())
+        .chain(get_garcia2005_lib_configs().keys())
+        .chain(get_montalva2017_lib_configs().keys())
+        .chain(get_idriss2014_lib_configs().keys())
+        .chain(get_allen2012_lib_configs().keys())
+        .chain(get_travasarou2003_lib_configs().keys())
+        .chain(get_afshari2016_lib_configs().keys())
+        .copied()
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_listed_configs_resolve() {
+        let keys = list_lib_configs();
+        assert!(
+            keys.windows(2).all(|w| w[0] < w[1]),
+            "duplicate config keys"
+        );
+        for key in keys {
+            assert!(get_lib_config(key).is_some(), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_max_distance_configs() {
+        for (prefix, _) in MAX_DISTANCES_KM {
+            assert!(
+                list_lib_configs().iter().any(|key| key.starts_with(prefix)),
+                "{prefix}"
+            );
+        }
+        let model = get_lib_config_with_cutoff("config_bssa2014_pga").unwrap();
+        assert_eq!(model.max_distance_km, 400.);
+        assert!(get_lib_config_with_cutoff("config_asb2014_rjb_pga").is_none());
+    }
+
+    #[test]
+    fn test_family_configs() {
+        let mf2013 = get_family_configs("config_mf2013_crustal");
+        // PGV, PGA and the PSA table rows
+        assert_eq!(mf2013.len(), 2 + 3);
+        assert!(mf2013.windows(2).all(|w| w[0].0 < w[1].0));
+        assert_eq!(mf2013[0].0, -1.);
+        // `_2` variants and other tectonic types are not part of the family
+        assert!(
+            get_family_configs("config_mf2013")
+                .iter()
+                .all(|(period, _)| *period != 0.)
+        );
+        assert!(get_family_configs("config_bssa2014").len() > 5);
+        assert!(get_family_configs("unknown").is_empty());
+    }
+}
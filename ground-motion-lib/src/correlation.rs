@@ -0,0 +1,296 @@
+//! Spatial and cross-IMT correlation of ground motion residuals.
+//!
+//! Within-event residuals of nearby sites are correlated: two sites a few kilometers apart
+//! tend to both experience stronger (or weaker) shaking than the median prediction. Shaking
+//! field simulation and conditioning on station records need a correlation coefficient between
+//! the residuals of any two sites, which is provided by the [`SpatialCorrelation`] trait.
+//!
+//! ## Jayaram & Baker (2009)
+//!
+//! ```text
+//! ρ(h) = exp(−3·h / b(T))
+//! ```
+//!
+//! where `h` is the separation distance (km) and `b` is the range, i.e. the distance at which
+//! the correlation drops to about 0.05:
+//!
+//! ```text
+//! T < 1 s:  b = 8.5 + 17.2·T   (no Vs30 clustering)
+//!           b = 40.7 − 15.0·T  (clustered Vs30)
+//! T ≥ 1 s:  b = 22.0 + 3.7·T
+//! ```
+//!
+//! PGA is treated as the period `T = 0`.
+//!
+//! ## Baker & Jayaram (2008)
+//!
+//! Joint simulation of several intensity measures at the same site needs the correlation of
+//! their residuals, provided by the [`ImtCorrelation`] trait. [`BakerJayaram2008`] implements
+//! the correlation of spectral accelerations at periods `T1 ≤ T2` (0.01–10 s):
+//!
+//! ```text
+//! C1 = 1 − cos(π/2 − 0.366·ln(T2 / max(T1, 0.109)))
+//! C2 = 1 − 0.105·(1 − 1 / (1 + exp(100·T2 − 5)))·(T2 − T1) / (T2 − 0.0099)   (T2 < 0.2 s)
+//! C3 = C2 if T2 < 0.109 s, C1 otherwise
+//! C4 = C1 + 0.5·(sqrt(C3) − C3)·(1 + cos(π·T1 / 0.109))
+//! ```
+//!
+//! PGA is treated as the period `T = 0.01` s. Correlations with PGV follow Bradley (2012):
+//!
+//! ```text
+//! ρ(PGV, Sa(T)) = (a + b) / 2 − (a − b) / 2 · tanh(d·ln(T / c))
+//! ```
+//!
+//! with piecewise coefficients for 0.01–0.1, 0.1–0.75, 0.75–2.5 and 2.5–10 s.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::correlation::{
+//!     BakerJayaram2008, ImtCorrelation, JayaramBaker2009, SpatialCorrelation, Vs30Clustering,
+//! };
+//! use ground_motion_lib::gmm::GmpePointKind;
+//!
+//! let model = JayaramBaker2009::new(Vs30Clustering::Uncorrelated);
+//! assert_eq!(model.correlation(0., 1.), 1.);
+//! let pga = model.correlation_for(5., GmpePointKind::Pga).unwrap();
+//! assert!(pga < model.correlation(5., 1.));
+//!
+//! let psa = |period| GmpePointKind::Psa { period };
+//! let near = BakerJayaram2008.correlation(psa(1.), psa(1.5)).unwrap();
+//! let far = BakerJayaram2008.correlation(psa(0.1), psa(3.)).unwrap();
+//! assert!(near > far);
+//! ```
+
+use crate::gmm::GmpePointKind;
+use std::f64::consts::{FRAC_PI_2, PI};
+use std::fmt::Debug;
+
+/// Period (s) used for PGA by the cross-IMT correlation models.
+const PGA_PERIOD: f64 = 0.01;
+
+/// Spatial correlation model of within-event residuals.
+pub trait SpatialCorrelation: Debug {
+    /// Correlation coefficient of the residuals of two sites.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - Separation distance between the sites (km).
+    /// * `period` - Spectral period (s); `0.0` for PGA.
+    fn correlation(&self, distance: f64, period: f64) -> f64;
+
+    /// Correlation coefficient for the given type of motion.
+    ///
+    /// # Returns
+    ///
+    /// `None` for types of motion without a spectral period (PGA counts as `T = 0`).
+    fn correlation_for(&self, distance: f64, kind: GmpePointKind) -> Option<f64> {
+        let period = match kind {
+            GmpePointKind::Pga => 0.,
+            GmpePointKind::Psa { period } => period,
+            _ => return None,
+        };
+        Some(self.correlation(distance, period))
+    }
+}
+
+/// Spatial clustering of the site conditions of the region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Vs30Clustering {
+    /// Site conditions vary independently from site to site (case 1 of the model).
+    #[default]
+    Uncorrelated,
+    /// Site conditions are spatially clustered (case 2 of the model), giving longer
+    /// short-period correlation ranges.
+    Clustered,
+}
+
+/// Jayaram & Baker (2009) exponential correlation model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JayaramBaker2009 {
+    /// Vs30 clustering of the region
+    pub clustering: Vs30Clustering,
+}
+
+impl JayaramBaker2009 {
+    /// Create a new model.
+    pub fn new(clustering: Vs30Clustering) -> Self {
+        Self { clustering }
+    }
+
+    /// Correlation range `b` (km) for a spectral period (s).
+    pub fn range(&self, period: f64) -> f64 {
+        if period >= 1. {
+            22. + 3.7 * period
+        } else {
+            match self.clustering {
+                Vs30Clustering::Uncorrelated => 8.5 + 17.2 * period,
+                Vs30Clustering::Clustered => 40.7 - 15. * period,
+            }
+        }
+    }
+}
+
+impl SpatialCorrelation for JayaramBaker2009 {
+    fn correlation(&self, distance: f64, period: f64) -> f64 {
+        (-3. * distance.abs() / self.range(period)).exp()
+    }
+}
+
+/// Correlation model of the residuals of different intensity measures at the same site.
+pub trait ImtCorrelation: Debug {
+    /// Correlation coefficient of the residuals of two types of motion.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the model does not cover one of the types of motion.
+    fn correlation(&self, first: GmpePointKind, second: GmpePointKind) -> Option<f64>;
+
+    /// Row-major correlation matrix of a set of types of motion.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the model does not cover one of the types of motion.
+    fn correlation_matrix(&self, kinds: &[GmpePointKind]) -> Option<Vec<f64>> {
+        kinds
+            .iter()
+            .flat_map(|first| kinds.iter().map(move |second| (*first, *second)))
+            .map(|(first, second)| self.correlation(first, second))
+            .collect()
+    }
+}
+
+/// Baker & Jayaram (2008) spectral acceleration correlation model, extended to PGV with the
+/// Bradley (2012) relation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BakerJayaram2008;
+
+impl BakerJayaram2008 {
+    /// Correlation of spectral accelerations at two periods (s).
+    pub fn period_correlation(&self, first: f64, second: f64) -> f64 {
+        let t_min = first.min(second);
+        let t_max = first.max(second);
+        let c1 = 1. - (FRAC_PI_2 - 0.366 * (t_max / t_min.max(0.109)).ln()).cos();
+        let c2 = if t_max < 0.2 {
+            1. - 0.105 * (1. - 1. / (1. + (100. * t_max - 5.).exp())) * (t_max - t_min)
+                / (t_max - 0.0099)
+        } else {
+            0.
+        };
+        let c3 = if t_max < 0.109 { c2 } else { c1 };
+        let c4 = c1 + 0.5 * (c3.sqrt() - c3) * (1. + (PI * t_min / 0.109).cos());
+        if t_max < 0.109 {
+            c2
+        } else if t_min > 0.109 {
+            c1
+        } else if t_max < 0.2 {
+            c2.min(c4)
+        } else {
+            c4
+        }
+    }
+
+    /// Correlation of PGV with the spectral acceleration at a period (s), after Bradley (2012).
+    pub fn pgv_correlation(&self, period: f64) -> f64 {
+        let (a, b, c, d) = match period {
+            t if t < 0.1 => (0.73, 0.54, 0.045, 1.8),
+            t if t < 0.75 => (0.54, 0.81, 0.28, 1.5),
+            t if t < 2.5 => (0.8, 0.76, 1.1, 3.),
+            _ => (0.76, 0.7, 5., 3.2),
+        };
+        (a + b) / 2. - (a - b) / 2. * (d * (period / c).ln()).tanh()
+    }
+}
+
+impl ImtCorrelation for BakerJayaram2008 {
+    fn correlation(&self, first: GmpePointKind, second: GmpePointKind) -> Option<f64> {
+        let period = |kind| match kind {
+            GmpePointKind::Pga => Some(PGA_PERIOD),
+            GmpePointKind::Psa { period } => Some(period),
+            _ => None,
+        };
+        match (first, second) {
+            (GmpePointKind::Pgv, GmpePointKind::Pgv) => Some(1.),
+            (GmpePointKind::Pgv, other) | (other, GmpePointKind::Pgv) => {
+                Some(self.pgv_correlation(period(other)?))
+            }
+            _ => Some(self.period_correlation(period(first)?, period(second)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        let case1 = JayaramBaker2009::new(Vs30Clustering::Uncorrelated);
+        let case2 = JayaramBaker2009::new(Vs30Clustering::Clustered);
+        assert!((case1.range(0.) - 8.5).abs() < 1e-12);
+        assert!((case2.range(0.) - 40.7).abs() < 1e-12);
+        assert!((case1.range(0.5) - 17.1).abs() < 1e-12);
+        // Both cases share the long-period range
+        assert_eq!(case1.range(2.), case2.range(2.));
+        assert!((case1.range(2.) - 29.4).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_correlation_decay() {
+        let model = JayaramBaker2009::default();
+        assert_eq!(model.correlation(0., 0.3), 1.);
+        // About 0.05 at the range
+        let at_range = model.correlation(model.range(0.3), 0.3);
+        assert!((at_range - (-3_f64).exp()).abs() < 1e-12);
+        assert!(model.correlation(10., 0.3) > model.correlation(20., 0.3));
+        assert!(model.correlation_for(10., GmpePointKind::Pgv).is_none());
+        assert_eq!(
+            model.correlation_for(10., GmpePointKind::Psa { period: 3. }),
+            Some(model.correlation(10., 3.))
+        );
+    }
+
+    #[test]
+    fn test_period_correlation() {
+        let model = BakerJayaram2008;
+        for t in [0.01, 0.05, 0.15, 0.5, 2., 10.] {
+            assert!((model.period_correlation(t, t) - 1.).abs() < 1e-12, "{t}");
+        }
+        assert_eq!(
+            model.period_correlation(0.3, 2.),
+            model.period_correlation(2., 0.3)
+        );
+        // Correlation decays with the period ratio
+        let long = model.period_correlation(1., 2.);
+        assert!(long < 1. && long > model.period_correlation(1., 5.));
+        assert!(model.period_correlation(0.01, 10.) > 0.);
+        assert!(model.period_correlation(0.01, 10.) < 0.3);
+    }
+
+    #[test]
+    fn test_imt_correlation_matrix() {
+        let model = BakerJayaram2008;
+        let pgv = model.pgv_correlation(PGA_PERIOD);
+        assert!((pgv - 0.73).abs() < 0.01, "{pgv}");
+        // Bradley (2012) is continuous at the period breaks
+        for t in [0.1, 0.75, 2.5] {
+            let below = model.pgv_correlation(t - 1e-9);
+            assert!((below - model.pgv_correlation(t)).abs() < 0.01, "{t}");
+        }
+        let kinds = [
+            GmpePointKind::Pga,
+            GmpePointKind::Pgv,
+            GmpePointKind::Psa { period: 1. },
+        ];
+        let matrix = model.correlation_matrix(&kinds).unwrap();
+        assert_eq!(matrix.len(), 9);
+        assert!((matrix[0] - 1.).abs() < 1e-12 && matrix[4] == 1.);
+        assert_eq!(matrix[1], matrix[3]);
+        assert_eq!(matrix[2], matrix[6]);
+        assert!(
+            model
+                .correlation_matrix(&[GmpePointKind::Pga, GmpePointKind::Mmi])
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Maximum applicable distance of ground motion models.
+//!
+//! GMPEs are derived from records within a limited distance range (typically 200–400 km) and
+//! extrapolate poorly beyond it, while continental-scale site grids routinely reach much
+//! farther. A [`CutoffModel`] stacks a maximum distance on top of any
+//! [`GroundMotionModeling`] implementation: beyond it, predictions are flagged as `NaN` or set
+//! to zero ([`CutoffMode`]).
+//!
+//! The distance is the first of the [`distance_metrics`](GroundMotionModeling::distance_metrics)
+//! of the model (e.g. Rrup for NGA-West2 models), or the epicentral distance if that metric is
+//! not available. Clipped predictions keep the standard deviation of the model, so they pass
+//! through post-processing stages unchanged. The model counts them as they are clipped
+//! ([`CutoffModel::clipped`]), so they are not confused with genuine `NaN` or zero predictions.
+//!
+//! Predefined configurations carry the maximum distance of their model family, see
+//! [`get_max_distance_km`](crate::configs::get_max_distance_km) and
+//! [`get_lib_config_with_cutoff`](crate::configs::get_lib_config_with_cutoff).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::configs::get_mf2013_lib_configs;
+//! use ground_motion_lib::cutoff::{CutoffMode, CutoffModel};
+//! use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+//! use ground_motion_lib::vectorized::calc_gmpe_vec;
+//!
+//! let base = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+//! let model = CutoffModel::new(base, 300.).with_mode(CutoffMode::Zero);
+//!
+//! let points = vec![
+//!     Vs30Point::new(142.5, 50.0, 400., None, None),
+//!     Vs30Point::new(150.0, 50.0, 400., None, None),
+//! ];
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let results = calc_gmpe_vec(&points, &model, &eq);
+//! assert!(results[0].value > 0.);
+//! assert_eq!(results[1].value, 0.);
+//! assert_eq!(model.clipped(), 1);
+//! ```
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GroundMotionModeling, Vs30Point};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Value of predictions beyond the maximum distance of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CutoffMode {
+    /// `NaN`, flagging the prediction as missing
+    #[default]
+    Nan,
+    /// Zero ground motion
+    Zero,
+}
+
+impl fmt::Display for CutoffMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CutoffMode::Nan => write!(f, "nan"),
+            CutoffMode::Zero => write!(f, "zero"),
+        }
+    }
+}
+
+impl FromStr for CutoffMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "nan" => Ok(CutoffMode::Nan),
+            "zero" => Ok(CutoffMode::Zero),
+            _ => Err(format!(
+                "Unknown cutoff mode `{s}`, expected `nan` or `zero`"
+            )),
+        }
+    }
+}
+
+/// A ground motion model with a maximum applicable distance.
+#[derive(Debug)]
+pub struct CutoffModel<M> {
+    /// Underlying model.
+    pub model: M,
+    /// Largest distance (km) of valid predictions.
+    pub max_distance_km: f64,
+    /// Value of predictions beyond `max_distance_km`.
+    pub mode: CutoffMode,
+    clipped: AtomicUsize,
+}
+
+impl<M: GroundMotionModeling> CutoffModel<M> {
+    /// Limit a model to distances up to `max_distance_km`, flagging farther predictions as
+    /// `NaN`.
+    pub fn new(model: M, max_distance_km: f64) -> Self {
+        Self {
+            model,
+            max_distance_km,
+            mode: CutoffMode::default(),
+            clipped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Set the value of predictions beyond the maximum distance.
+    pub fn with_mode(mut self, mode: CutoffMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Number of predictions clipped beyond the maximum distance since the model was created.
+    pub fn clipped(&self) -> usize {
+        self.clipped.load(Ordering::Relaxed)
+    }
+
+    /// Apply the cutoff to a prediction at the given distance.
+    fn clip(&self, mut point: GmpePoint, distance: f64) -> GmpePoint {
+        if distance > self.max_distance_km {
+            self.clipped.fetch_add(1, Ordering::Relaxed);
+            point.value = match self.mode {
+                CutoffMode::Nan => f64::NAN,
+                CutoffMode::Zero => 0.,
+            };
+        }
+        point
+    }
+}
+
+impl<M: GroundMotionModeling> GroundMotionModeling for CutoffModel<M> {
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        let gm = self
+            .model
+            .calc_from_distance(point, eq, epicentral_distance);
+        self.clip(gm, epicentral_distance)
+    }
+
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        self.model.distance_metrics()
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = self
+            .model
+            .distance_metrics()
+            .first()
+            .and_then(|metric| distances.get(*metric))
+            .unwrap_or(distances.repi);
+        self.clip(
+            self.model.calc_from_distances(point, eq, distances),
+            distance,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_cb2014_lib_configs;
+    use crate::rupture::Rupture;
+
+    #[test]
+    fn test_cutoff_by_model_metric() {
+        let base = get_cb2014_lib_configs().get("config_cb2014_pga").unwrap();
+        let model = CutoffModel::new(base, 100.);
+        // Rrup of a deep rupture exceeds the epicentral distance
+        let eq = Earthquake::new_mw(142., 50., 90., 7.)
+            .with_rupture(Rupture::new(90., 90., 40., 20.).with_ztor(80.));
+        let site = Vs30Point::new(142., 50.7, 400., None, None);
+        let repi = 77.8;
+        assert!(model.calc_from_distance(&site, &eq, repi).value > 0.);
+        assert!(model.calc_from_point(&site, &eq).value.is_nan());
+        let near = Vs30Point::new(142., 50.1, 400., None, None);
+        let gm = model.calc_from_point(&near, &eq);
+        assert_eq!(gm.value, base.calc_from_point(&near, &eq).value);
+    }
+
+    #[test]
+    fn test_cutoff_keeps_sigma() {
+        let base = get_cb2014_lib_configs().get("config_cb2014_pga").unwrap();
+        let model = CutoffModel::new(base, 100.).with_mode(CutoffMode::Zero);
+        let eq = Earthquake::new_mw(142., 50., 10., 6.);
+        let far = Vs30Point::new(146., 50., 400., None, None);
+        let gm = model.calc_from_point(&far, &eq);
+        assert_eq!(gm.value, 0.);
+        assert!(gm.sigma.is_some());
+        assert_eq!(model.clipped(), 1);
+        assert_eq!("ZERO".parse::<CutoffMode>(), Ok(CutoffMode::Zero));
+    }
+}
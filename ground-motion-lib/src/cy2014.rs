@@ -0,0 +1,390 @@
+//! Implementation of Chiou & Youngs (2014) NGA-West2 Ground Motion Prediction Equations (GMPE)
+//! for active crustal regions.
+//!
+//! The model first predicts the median motion `y_ref` on reference rock (Vs30 = 1130 m/s) from
+//! magnitude, rupture distance and depth to the top of rupture, and then applies linear and
+//! nonlinear site amplification driven by `y_ref`:
+//!
+//! ```text
+//! ln(y) = ln(y_ref) + φ1 · min(ln(Vs30 / 1130), 0)
+//!       + φ2 · (exp(φ3 · (min(Vs30, 1130) − 360)) − exp(φ3 · (1130 − 360)))
+//!            · ln((y_ref + φ4) / φ4)
+//!       + φ5 · (1 − exp(−ΔZ1.0 / φ6))
+//! ```
+//!
+//! where `y` is PGA or PSA in g, or PGV in cm/s.
+//!
+//! The reference rock motion includes the hanging-wall term of sites with `Rx ≥ 0`:
+//!
+//! ```text
+//! f_hw = c9 · cos(δ) · (c9a + (1 − c9a) · tanh(Rx / c9b)) · (1 − sqrt(Rjb² + Ztor²) / (Rrup + 1))
+//! ```
+//!
+//! Point sources have no `Rx` and get no hanging-wall term.
+//!
+//! The directivity-neutral form is used (`ΔDPP = 0`). Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism.
+//! - Global (California) anelastic attenuation.
+//! - The basin term uses the centered Z1.0 (`ΔZ1.0 = 0`) unless [`Vs30Point::z1p0`] is set.
+//! - The Ztor scaling term is only applied when a finite rupture with an explicit `ztor` is
+//!   attached to the earthquake; otherwise the magnitude-dependent mean Ztor is assumed.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+//!
+//! Only PGA and PGV coefficients are shipped, so there are no CY14 PSA configurations for
+//! response spectra or PSA ensembles. PSA models can be built by constructing
+//! [`Cy2014Coeffs`] from the published tables (Chiou & Youngs, 2014, Earthquake Spectra 30(3))
+//! and passing a [`GmpePointKind::Psa`] kind to [`CY2014::new`].
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Period-independent coefficients.
+const C2: f64 = 1.06;
+const C4: f64 = -2.1;
+const C4A: f64 = -0.5;
+const C_RB: f64 = 50.;
+/// Reference rock Vs30 (m/s).
+const VS30_REF: f64 = 1130.;
+
+/// Period-dependent CY14 coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cy2014Coeffs {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling coefficients
+    pub c3: f64,
+    pub cn: f64,
+    pub cm: f64,
+    /// Near-source saturation coefficients
+    pub c5: f64,
+    pub c6: f64,
+    pub chm: f64,
+    /// Ztor scaling coefficients
+    pub c7: f64,
+    pub c7b: f64,
+    /// Hanging-wall coefficients
+    pub c9: f64,
+    pub c9a: f64,
+    pub c9b: f64,
+    /// Dip scaling coefficient
+    pub c11b: f64,
+    /// Anelastic attenuation coefficients
+    pub cg1: f64,
+    pub cg2: f64,
+    pub cg3: f64,
+    /// Site response coefficients
+    pub phi1: f64,
+    pub phi2: f64,
+    pub phi3: f64,
+    pub phi4: f64,
+    /// Basin response coefficients
+    pub phi5: f64,
+    pub phi6: f64,
+    /// Between-event standard deviation for M ≤ 5
+    pub tau1: f64,
+    /// Between-event standard deviation for M ≥ 6.5
+    pub tau2: f64,
+    /// Within-event standard deviation for M ≤ 5
+    pub sigma1: f64,
+    /// Within-event standard deviation for M ≥ 6.5
+    pub sigma2: f64,
+}
+
+/// CY14 coefficients for PGA (g).
+pub const CY2014_PGA: Cy2014Coeffs = Cy2014Coeffs {
+    period: 0.,
+    c1: -1.5065,
+    c3: 1.9636,
+    cn: 16.0875,
+    cm: 4.9993,
+    c5: 6.4551,
+    c6: 0.4908,
+    chm: 3.0956,
+    c7: 0.0352,
+    c7b: 0.0462,
+    c9: 0.9228,
+    c9a: 0.1202,
+    c9b: 6.8607,
+    c11b: -0.4536,
+    cg1: -0.007146,
+    cg2: -0.006758,
+    cg3: 4.2542,
+    phi1: -0.521,
+    phi2: -0.1417,
+    phi3: -0.00701,
+    phi4: 0.102151,
+    phi5: 0.,
+    phi6: 300.,
+    tau1: 0.4,
+    tau2: 0.26,
+    sigma1: 0.4912,
+    sigma2: 0.3762,
+};
+
+/// CY14 coefficients for PGV (cm/s).
+pub const CY2014_PGV: Cy2014Coeffs = Cy2014Coeffs {
+    period: -1.,
+    c1: 2.3549,
+    c3: 2.3152,
+    cn: 3.3024,
+    cm: 5.423,
+    c5: 5.8096,
+    c6: 0.4407,
+    chm: 3.0514,
+    c7: 0.0324,
+    c7b: 0.0097,
+    c9: 0.3079,
+    c9a: 0.1,
+    c9b: 6.5,
+    c11b: -0.3834,
+    cg1: -0.001852,
+    cg2: -0.007403,
+    cg3: 4.3439,
+    phi1: -0.7936,
+    phi2: -0.0699,
+    phi3: -0.008444,
+    phi4: 5.41,
+    phi5: 0.0202,
+    phi6: 300.,
+    tau1: 0.3894,
+    tau2: 0.2578,
+    sigma1: 0.4785,
+    sigma2: 0.3629,
+};
+
+/// Source and site parameters entering the CY14 equations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cy2014Inputs {
+    /// Moment magnitude
+    pub magnitude: f64,
+    /// Closest distance to the rupture plane (km)
+    pub rrup: f64,
+    /// Joyner–Boore distance (km)
+    pub rjb: f64,
+    /// Horizontal distance from the top edge of the rupture perpendicular to strike (km),
+    /// positive on the hanging wall; `None` for point sources
+    pub rx: Option<f64>,
+    /// Depth to the top of rupture (km)
+    pub ztor: f64,
+    /// Difference between the depth to the top of rupture and its magnitude-dependent mean (km)
+    pub delta_ztor: f64,
+    /// Rupture dip (degrees)
+    pub dip: f64,
+    /// Average shear-wave velocity in the top 30 meters (m/s)
+    pub vs30: f64,
+    /// Difference between Z1.0 and its Vs30-dependent mean (m)
+    pub delta_z1p0: f64,
+}
+
+/// Mean depth to the top of rupture (km) for strike-slip events.
+pub fn mean_ztor(magnitude: f64) -> f64 {
+    (2.673 - 1.136 * (magnitude - 4.97).max(0.)).max(0.).powi(2)
+}
+
+/// Mean Z1.0 (m) for a Vs30 (m/s) with the California relation of CY14.
+pub fn mean_z1p0(vs30: f64) -> f64 {
+    (-7.15 / 4. * ((vs30.powi(4) + 571_f64.powi(4)) / (1360_f64.powi(4) + 571_f64.powi(4))).ln())
+        .exp()
+}
+
+/// Chiou & Youngs (2014) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct CY2014 {
+    /// Period-dependent coefficients
+    pub coeffs: Cy2014Coeffs,
+    /// Type of motion (PGA, PGV, PSA etc.)
+    pub motion_kind: GmpePointKind,
+}
+
+impl CY2014 {
+    /// Create a new CY14 model for the given coefficient set and motion type.
+    pub fn new(coeffs: Cy2014Coeffs, motion_kind: GmpePointKind) -> Self {
+        Self {
+            coeffs,
+            motion_kind,
+        }
+    }
+
+    /// Natural logarithm of the median motion on reference rock (Vs30 = 1130 m/s).
+    pub fn ln_reference(&self, inputs: &Cy2014Inputs) -> f64 {
+        let c = &self.coeffs;
+        let m = inputs.magnitude;
+        let r = inputs.rrup;
+        let cosh_m = (2. * (m - 4.5).max(0.)).cosh();
+
+        let f_ztor = (c.c7 + c.c7b / cosh_m) * inputs.delta_ztor;
+        let f_dip = (c.c11b / cosh_m) * inputs.dip.to_radians().cos().powi(2);
+        let f_mag = C2 * (m - 6.) + (C2 - c.c3) / c.cn * (1. + (c.cn * (c.cm - m)).exp()).ln();
+        let f_near = C4 * (r + c.c5 * (c.c6 * (m - c.chm).max(0.)).cosh()).ln();
+        let f_far = (C4A - C4) * (r.powi(2) + C_RB.powi(2)).sqrt().ln();
+        let f_atn = (c.cg1 + c.cg2 / (m - c.cg3).max(0.).cosh()) * r;
+        let f_hw = inputs.rx.filter(|rx| *rx >= 0.).map_or(0., |rx| {
+            c.c9 * inputs.dip.to_radians().cos()
+                * (c.c9a + (1. - c.c9a) * (rx / c.c9b).tanh())
+                * (1. - inputs.rjb.hypot(inputs.ztor) / (r + 1.))
+        });
+
+        c.c1 + f_ztor + f_dip + f_mag + f_near + f_far + f_atn + f_hw
+    }
+
+    /// Natural logarithm of the median ground motion for the given inputs.
+    ///
+    /// # Returns
+    ///
+    /// `ln(y)` with `y` in g (PGA, PSA) or cm/s (PGV).
+    pub fn ln_median(&self, inputs: &Cy2014Inputs) -> f64 {
+        let c = &self.coeffs;
+        let ln_ref = self.ln_reference(inputs);
+        let vs30 = inputs.vs30;
+
+        let f_lin = c.phi1 * (vs30 / VS30_REF).ln().min(0.);
+        let f_nl = c.phi2
+            * ((c.phi3 * (vs30.min(VS30_REF) - 360.)).exp() - (c.phi3 * (VS30_REF - 360.)).exp())
+            * ((ln_ref.exp() + c.phi4) / c.phi4).ln();
+        let f_basin = c.phi5 * (1. - (-inputs.delta_z1p0 / c.phi6).exp());
+
+        ln_ref + f_lin + f_nl + f_basin
+    }
+
+    /// Total standard deviation of `ln(y)` (magnitude-dependent between- and within-event
+    /// variability, linear site response).
+    pub fn total_sigma(&self, magnitude: f64) -> f64 {
+        let c = &self.coeffs;
+        let w = ((magnitude - 5.) / 1.5).clamp(0., 1.);
+        let tau = c.tau1 + w * (c.tau2 - c.tau1);
+        let sigma = c.sigma1 + w * (c.sigma2 - c.sigma1);
+        sigma.hypot(tau)
+    }
+}
+
+impl GroundMotionModeling for CY2014 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![
+            DistanceMetric::Rrup,
+            DistanceMetric::Rjb,
+            DistanceMetric::Rx,
+        ]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let delta_ztor = eq
+            .rupture
+            .and_then(|r| r.ztor)
+            .map_or(0., |ztor| ztor - mean_ztor(eq.mw()));
+        let inputs = Cy2014Inputs {
+            magnitude: eq.mw(),
+            rrup: distances
+                .rrup
+                .unwrap_or_else(|| distances.repi.hypot(eq.ztor())),
+            rjb: distances.rjb.unwrap_or(distances.repi),
+            rx: distances.rx,
+            ztor: eq.ztor(),
+            delta_ztor,
+            dip: eq.rupture.map_or(90., |r| r.dip),
+            vs30: point.vs30,
+            delta_z1p0: point.z1p0.map_or(0., |z1p0| z1p0 - mean_z1p0(point.vs30)),
+        };
+        let mut ground_motion = self.ln_median(&inputs).exp();
+        // convert g to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion *= 100.;
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.total_sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(magnitude: f64, rrup: f64, vs30: f64) -> Cy2014Inputs {
+        Cy2014Inputs {
+            magnitude,
+            rrup,
+            rjb: rrup,
+            rx: None,
+            ztor: 0.,
+            delta_ztor: 0.,
+            dip: 90.,
+            vs30,
+            delta_z1p0: 0.,
+        }
+    }
+
+    #[test]
+    fn test_reference_rock_has_no_site_term() {
+        let model = CY2014::new(CY2014_PGA, GmpePointKind::Pga);
+        let i = inputs(6.5, 10., VS30_REF);
+        assert!((model.ln_median(&i) - model.ln_reference(&i)).abs() < 1e-12);
+        let pga = model.ln_median(&i).exp();
+        assert!(pga > 0.1 && pga < 0.4, "{pga}");
+    }
+
+    #[test]
+    fn test_nonlinear_site_response() {
+        let model = CY2014::new(CY2014_PGA, GmpePointKind::Pga);
+        let weak =
+            model.ln_median(&inputs(5., 100., 200.)) - model.ln_median(&inputs(5., 100., 760.));
+        let strong =
+            model.ln_median(&inputs(7.5, 1., 200.)) - model.ln_median(&inputs(7.5, 1., 760.));
+        assert!(weak > strong);
+    }
+
+    #[test]
+    fn test_hanging_wall_term() {
+        let model = CY2014::new(CY2014_PGA, GmpePointKind::Pga);
+        let dipping = |rx: f64, rjb: f64| Cy2014Inputs {
+            rjb,
+            rx: Some(rx),
+            ztor: 2.,
+            dip: 45.,
+            ..inputs(7., 10., 760.)
+        };
+        let hanging_wall = model.ln_reference(&dipping(10., 0.));
+        let footwall = model.ln_reference(&dipping(-10., 10.));
+        assert!(hanging_wall > footwall + 0.2, "{hanging_wall} {footwall}");
+    }
+
+    #[test]
+    fn test_basin_depth() {
+        let model = CY2014::new(CY2014_PGV, GmpePointKind::Pgv);
+        let eq = Earthquake::new_mw(142.5, 50., 10., 6.5);
+        let site = Vs30Point::new(142.6, 50., 400., None, None);
+        let centered = model.calc_from_point(&site, &eq).value;
+        let deep = site.with_basin_depths(mean_z1p0(400.) + 500., 2.);
+        assert!(model.calc_from_point(&deep, &eq).value > centered);
+        assert!((mean_z1p0(400.) - 355.).abs() < 10., "{}", mean_z1p0(400.));
+    }
+}
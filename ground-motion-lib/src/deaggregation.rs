@@ -0,0 +1,376 @@
+//! Magnitude–distance–epsilon deaggregation of seismic hazard.
+//!
+//! A hazard curve mixes the contributions of all earthquakes around a site. Deaggregation
+//! (McGuire, 1995; Bazzurro & Cornell, 1999) splits the annual rate of exceeding a ground motion
+//! level into magnitude, distance and epsilon bins, so scenario earthquakes consistent with the
+//! hazard can be selected:
+//!
+//! ```text
+//! λ(M_i, R_j, ε_k) = Σ_{s ∈ (M_i, R_j)} ν_s · P(ε_k,lo < ε < ε_k,hi, ε > ε*_s)
+//! ε*_s = (ln y − ln μ_s) / σ_s
+//! ```
+//!
+//! where `ε*_s` is the number of standard deviations by which the level `y` exceeds the median
+//! of the source `s`. The bin rates add up to the rate of the hazard curve at `y` for sources
+//! inside the magnitude and distance bins. Intensities are treated linearly instead of in
+//! logarithms.
+//!
+//! Current simplifications:
+//!
+//! - Distances are epicentral (Haversine).
+//! - Contributions beyond the outer epsilon edges are added to the outer epsilon bins; sources
+//!   outside the magnitude and distance bins are not binned, but count towards the total rate.
+//! - Sources of models without a standard deviation are put into the central epsilon bin.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::configs::get_lib_config;
+//! use ground_motion_lib::deaggregation::{DeaggregationBins, deaggregate};
+//! use ground_motion_lib::gmm::Vs30Point;
+//! use ground_motion_lib::hazard::PointSource;
+//!
+//! let site = Vs30Point::new(142.5, 50.0, 400., None, None);
+//! let sources = vec![
+//!     PointSource::new(142.4, 50.0, 10.0, 5.5, 0.1),
+//!     PointSource::new(143.5, 50.5, 15.0, 7.5, 0.002),
+//! ];
+//! let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+//!
+//! let deagg = deaggregate(&site, &sources, gmpe, 20., &DeaggregationBins::default()).unwrap();
+//! let (magnitude, distance, epsilon) = deagg.mean();
+//! println!("M = {magnitude:.1}, R = {distance:.0} km, ε = {epsilon:.1}");
+//! let total: f64 = deagg.magnitude_distance().iter().flatten().sum();
+//! assert!((total - 1.).abs() < 1e-9);
+//! ```
+
+use crate::auxilary::normal_cdf;
+use crate::distance::DistanceMethod;
+use crate::gmm::{GmpePointKind, GroundMotionModeling, Vs30Point};
+use crate::hazard::{PointSource, exceedance};
+use std::error::Error;
+
+/// Edges of the magnitude, distance and epsilon bins of a deaggregation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeaggregationBins {
+    /// Magnitude bin edges, ascending.
+    pub magnitude: Vec<f64>,
+    /// Distance bin edges (km), ascending.
+    pub distance: Vec<f64>,
+    /// Epsilon bin edges, ascending.
+    pub epsilon: Vec<f64>,
+}
+
+impl Default for DeaggregationBins {
+    /// Magnitudes 4–9 in steps of 0.5, distances 0–300 km in steps of 20 km and epsilons −3 to
+    /// 3 in steps of 1.
+    fn default() -> Self {
+        Self::new(edges(4., 9., 0.5), edges(0., 300., 20.), edges(-3., 3., 1.))
+    }
+}
+
+impl DeaggregationBins {
+    /// Create bins from their edges.
+    pub fn new(magnitude: Vec<f64>, distance: Vec<f64>, epsilon: Vec<f64>) -> Self {
+        Self {
+            magnitude,
+            distance,
+            epsilon,
+        }
+    }
+}
+
+/// Evenly spaced bin edges from `min` to `max` (both included).
+fn edges(min: f64, max: f64, step: f64) -> Vec<f64> {
+    let n = ((max - min) / step).round() as usize;
+    (0..=n).map(|i| min + i as f64 * step).collect()
+}
+
+/// Index of the bin of `value`, if within the edges.
+fn bin_index(edges: &[f64], value: f64) -> Option<usize> {
+    let last = edges.len().checked_sub(2)?;
+    if value < edges[0] || value > edges[last + 1] {
+        return None;
+    }
+    Some(
+        edges
+            .partition_point(|edge| *edge <= value)
+            .saturating_sub(1)
+            .min(last),
+    )
+}
+
+/// Deaggregated annual rates of exceedance of a ground motion level at a site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deaggregation {
+    /// Longitude of the site in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the site in decimal degrees.
+    pub lat: f64,
+    /// Type of the ground motion.
+    pub kind: GmpePointKind,
+    /// Deaggregated ground motion level.
+    pub level: f64,
+    /// Bin edges.
+    pub bins: DeaggregationBins,
+    /// Annual rates of exceedance of the bins, indexed `[magnitude][distance][epsilon]`.
+    pub rates: Vec<Vec<Vec<f64>>>,
+    /// Annual rate of exceedance of the level from all sources.
+    pub total_rate: f64,
+}
+
+impl Deaggregation {
+    /// Fraction of the total rate contributed by every bin, indexed like [`Self::rates`].
+    pub fn fractions(&self) -> Vec<Vec<Vec<f64>>> {
+        let total = self.total_rate;
+        self.rates
+            .iter()
+            .map(|m| {
+                m.iter()
+                    .map(|r| r.iter().map(|rate| safe_fraction(*rate, total)).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Magnitude–distance matrix of the fractions, summed over epsilon.
+    pub fn magnitude_distance(&self) -> Vec<Vec<f64>> {
+        self.rates
+            .iter()
+            .map(|m| {
+                m.iter()
+                    .map(|r| safe_fraction(r.iter().sum(), self.total_rate))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Rate-weighted mean magnitude, distance and epsilon of the bin centers.
+    pub fn mean(&self) -> (f64, f64, f64) {
+        let (mut binned, mut m, mut r, mut e) = (0., 0., 0., 0.);
+        for (i, j, k, rate) in self.cells() {
+            binned += rate;
+            m += rate * center(&self.bins.magnitude, i);
+            r += rate * center(&self.bins.distance, j);
+            e += rate * center(&self.bins.epsilon, k);
+        }
+        if binned <= 0. {
+            return (f64::NAN, f64::NAN, f64::NAN);
+        }
+        (m / binned, r / binned, e / binned)
+    }
+
+    /// Magnitude, distance and epsilon bin centers of the largest contribution.
+    pub fn mode(&self) -> (f64, f64, f64) {
+        self.cells()
+            .filter(|cell| cell.3 > 0.)
+            .max_by(|a, b| a.3.total_cmp(&b.3))
+            .map_or((f64::NAN, f64::NAN, f64::NAN), |(i, j, k, _)| {
+                (
+                    center(&self.bins.magnitude, i),
+                    center(&self.bins.distance, j),
+                    center(&self.bins.epsilon, k),
+                )
+            })
+    }
+
+    /// Iterate over `(magnitude, distance, epsilon, rate)` bin indices and rates.
+    fn cells(&self) -> impl Iterator<Item = (usize, usize, usize, f64)> + '_ {
+        self.rates.iter().enumerate().flat_map(|(i, m)| {
+            m.iter()
+                .enumerate()
+                .flat_map(move |(j, r)| r.iter().enumerate().map(move |(k, rate)| (i, j, k, *rate)))
+        })
+    }
+}
+
+fn safe_fraction(rate: f64, total: f64) -> f64 {
+    if total > 0. { rate / total } else { 0. }
+}
+
+/// Center of the bin `i`.
+fn center(edges: &[f64], i: usize) -> f64 {
+    0.5 * (edges[i] + edges[i + 1])
+}
+
+/// Deaggregate the hazard of a site at a ground motion level, without truncation.
+///
+/// See [`deaggregate_with`].
+///
+/// # Errors
+///
+/// Returns an error if no sources are given or a dimension has fewer than two bin edges.
+pub fn deaggregate<T: GroundMotionModeling + ?Sized>(
+    point: &Vs30Point,
+    sources: &[PointSource],
+    gmpe: &T,
+    level: f64,
+    bins: &DeaggregationBins,
+) -> Result<Deaggregation, Box<dyn Error>> {
+    deaggregate_with(point, sources, gmpe, level, bins, None)
+}
+
+/// Deaggregate the hazard of a site at a ground motion level.
+///
+/// # Arguments
+///
+/// * `point` - Site point.
+/// * `sources` - Seismic sources with their annual rates.
+/// * `gmpe` - Ground motion model providing medians and standard deviations.
+/// * `level` - Ground motion level, e.g. from a hazard map.
+/// * `bins` - Magnitude, distance and epsilon bin edges.
+/// * `truncation` - Truncation level of the ground motion distribution in standard deviations,
+///   or `None` for an untruncated distribution.
+///
+/// # Errors
+///
+/// Returns an error if no sources are given, a dimension has fewer than two bin edges or the
+/// truncation level is not positive.
+pub fn deaggregate_with<T: GroundMotionModeling + ?Sized>(
+    point: &Vs30Point,
+    sources: &[PointSource],
+    gmpe: &T,
+    level: f64,
+    bins: &DeaggregationBins,
+    truncation: Option<f64>,
+) -> Result<Deaggregation, Box<dyn Error>> {
+    if sources.is_empty() {
+        return Err("No seismic sources given".into());
+    }
+    if [&bins.magnitude, &bins.distance, &bins.epsilon]
+        .iter()
+        .any(|edges| edges.len() < 2)
+    {
+        return Err("Every deaggregation dimension needs at least two bin edges".into());
+    }
+    if truncation.is_some_and(|n| n <= 0.) {
+        return Err("Truncation level must be positive".into());
+    }
+    let n_eps = bins.epsilon.len() - 1;
+    let mut rates = vec![vec![vec![0.; n_eps]; bins.distance.len() - 1]; bins.magnitude.len() - 1];
+    let mut total_rate = 0.;
+    let mut kind = GmpePointKind::Pga;
+
+    for source in sources {
+        let eq = source.to_earthquake();
+        let distance =
+            DistanceMethod::Haversine.epicentral_distance(eq.lon, eq.lat, point.lon, point.lat);
+        let gm = gmpe.calc_from_distance(point, &eq, distance);
+        kind = gm.kind;
+        let x = if kind.is_log_normal() {
+            level.ln()
+        } else {
+            level
+        };
+        let mean = gm.log_mean();
+        let rate = source.rate * exceedance(x, mean, gm.sigma, truncation);
+        total_rate += rate;
+        if rate <= 0. {
+            continue;
+        }
+        let (Some(i), Some(j)) = (
+            bin_index(&bins.magnitude, source.magnitude),
+            bin_index(&bins.distance, distance),
+        ) else {
+            continue;
+        };
+        let cell = &mut rates[i][j];
+        match gm.sigma.filter(|sigma| *sigma > 0.) {
+            Some(sigma) => {
+                let threshold = (x - mean) / sigma;
+                // Probability mass of ε above the threshold within each bin
+                let above = |e: f64| {
+                    let e = e.max(threshold);
+                    match truncation {
+                        Some(n) => normal_cdf(n) - normal_cdf(e.clamp(-n, n)),
+                        None => normal_cdf(-e),
+                    }
+                };
+                let weights: Vec<f64> = (0..n_eps)
+                    .map(|k| {
+                        let lower = if k == 0 {
+                            f64::NEG_INFINITY
+                        } else {
+                            bins.epsilon[k]
+                        };
+                        let upper = if k == n_eps - 1 {
+                            f64::INFINITY
+                        } else {
+                            bins.epsilon[k + 1]
+                        };
+                        above(lower) - above(upper)
+                    })
+                    .collect();
+                let sum: f64 = weights.iter().sum();
+                for (bin, weight) in cell.iter_mut().zip(weights) {
+                    *bin += rate * weight / sum;
+                }
+            }
+            None => {
+                let k = bin_index(&bins.epsilon, 0.).unwrap_or(n_eps / 2);
+                cell[k] += rate;
+            }
+        }
+    }
+
+    Ok(Deaggregation {
+        lon: point.lon,
+        lat: point.lat,
+        kind,
+        level,
+        bins: bins.clone(),
+        rates,
+        total_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_lib_config;
+    use crate::hazard::calc_hazard_curves;
+
+    #[test]
+    fn test_bin_index() {
+        let unit = [0., 1., 2.];
+        assert_eq!(bin_index(&unit, -0.1), None);
+        assert_eq!(bin_index(&unit, 0.), Some(0));
+        assert_eq!(bin_index(&unit, 1.), Some(1));
+        assert_eq!(bin_index(&unit, 2.), Some(1));
+        assert_eq!(bin_index(&unit, 2.1), None);
+        assert_eq!(edges(4., 9., 0.5).len(), 11);
+    }
+
+    #[test]
+    fn test_deaggregation_matches_hazard() {
+        let site = Vs30Point::new(142.5, 50., 400., None, None);
+        let near = PointSource::new(142.45, 50., 10., 5.7, 0.1);
+        let far = PointSource::new(143.5, 50.5, 15., 7.7, 0.002);
+        let sources = [near, far];
+        let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+        let level = 30.;
+        let curve = &calc_hazard_curves(&[site], &sources, gmpe, &[level]).unwrap()[0];
+
+        let site = Vs30Point::new(142.5, 50., 400., None, None);
+        let bins = DeaggregationBins::default();
+        let deagg = deaggregate(&site, &sources, gmpe, level, &bins).unwrap();
+        assert!((deagg.total_rate - curve.rates[0]).abs() < 1e-15);
+        let binned: f64 = deagg.rates.iter().flatten().flatten().sum();
+        assert!((binned - deagg.total_rate).abs() < 1e-12);
+
+        // Both sources contribute to their own magnitude-distance bins
+        let md = deagg.magnitude_distance();
+        assert!(md[3][0] > 0. && md[7][4] > 0.);
+        let (m, r, e) = deagg.mean();
+        assert!(
+            m > 5.75 && m < 7.75 && r > 10. && r < 110. && e > 0.,
+            "{m} {r} {e}"
+        );
+        let mode = deagg.mode();
+        assert!(mode.0 == 5.75 || mode.0 == 7.75);
+
+        assert!(deaggregate(&site, &[], gmpe, level, &bins).is_err());
+        let empty = DeaggregationBins::new(vec![4.], bins.distance.clone(), bins.epsilon);
+        assert!(deaggregate(&site, &sources, gmpe, level, &empty).is_err());
+    }
+}
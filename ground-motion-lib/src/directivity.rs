@@ -0,0 +1,188 @@
+//! Rupture directivity adjustment for finite ruptures.
+//!
+//! This module implements a directivity adjustment in the style of Bayless & Somerville (2013)
+//! for spectral accelerations of near-fault scenario maps. The adjustment is applied in
+//! natural-log space as an optional post-processing stage, see [`Directivity`], which implements
+//! [`GridTransform`] and can be appended to a [`Pipeline`](crate::pipeline::Pipeline).
+//!
+//! The strike-slip formulation is used:
+//!
+//! ```text
+//! fD = (C0 + C1 · ln(s)) · T_CD · T_Mw
+//! ```
+//!
+//! where `s` is the rupture length (km) between the hypocenter and the site projected on the
+//! strike, `T_CD` is a distance taper (`R/L` from 0.5 to 1.0) and `T_Mw` is a magnitude taper
+//! (M5.0 to M6.5). Coefficients are interpolated in log-period between 0.5 s and 10 s; shorter
+//! periods are not adjusted.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::directivity::Directivity;
+//! use ground_motion_lib::gmm::{Earthquake, GmpePoint};
+//! use ground_motion_lib::pipeline::Pipeline;
+//! use ground_motion_lib::rupture::Rupture;
+//!
+//! let eq = Earthquake::new_mw(142.83, 52.63, 11.0, 7.1)
+//!     .with_rupture(Rupture::new(15., 80., 60., 20.));
+//!
+//! let directivity = Directivity::new(&eq, 3.0).expect("finite rupture required");
+//! let pipeline = Pipeline::new().then(directivity);
+//!
+//! let grid = vec![GmpePoint::new_psa(142.95, 53.0, 10.0, 3.0)];
+//! let adjusted = pipeline.run(grid);
+//! println!("{adjusted:?}");
+//! ```
+
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind};
+use crate::pipeline::GridTransform;
+use crate::rupture::{Rupture, local_xy};
+
+/// Strike-slip directivity coefficients `(period, C0, C1)`.
+const BS2013_SS_COEFFS: [(f64, f64, f64); 8] = [
+    (0.5, 0., 0.),
+    (0.75, 0., 0.),
+    (1.0, -0.120, 0.075),
+    (1.5, -0.175, 0.090),
+    (2.0, -0.210, 0.095),
+    (3.0, -0.235, 0.099),
+    (5.0, -0.275, 0.107),
+    (10.0, -0.300, 0.125),
+];
+
+/// Directivity adjustment stage for spectral accelerations of a finite rupture.
+#[derive(Debug, Clone, Copy)]
+pub struct Directivity {
+    /// Epicenter longitude (decimal degrees).
+    pub lon: f64,
+    /// Epicenter latitude (decimal degrees).
+    pub lat: f64,
+    /// Hypocentral depth (km).
+    pub depth: f64,
+    /// Moment magnitude.
+    pub magnitude: f64,
+    /// Finite rupture geometry.
+    pub rupture: Rupture,
+    /// Spectral period (s) of the adjusted PSA values.
+    pub period: f64,
+}
+
+impl Directivity {
+    /// Create a directivity stage for an earthquake and a spectral period.
+    ///
+    /// # Returns
+    ///
+    /// `None` for point sources, since directivity requires a finite rupture.
+    pub fn new(eq: &Earthquake, period: f64) -> Option<Self> {
+        eq.rupture.map(|rupture| Self {
+            lon: eq.lon,
+            lat: eq.lat,
+            depth: eq.depth,
+            magnitude: eq.mw(),
+            rupture,
+            period,
+        })
+    }
+
+    /// Coefficients `(C0, C1)` interpolated in log-period.
+    pub fn coefficients(&self) -> (f64, f64) {
+        let first = BS2013_SS_COEFFS[0];
+        let last = BS2013_SS_COEFFS[BS2013_SS_COEFFS.len() - 1];
+        if self.period <= first.0 {
+            return (0., 0.);
+        }
+        if self.period >= last.0 {
+            return (last.1, last.2);
+        }
+        let idx = BS2013_SS_COEFFS
+            .iter()
+            .position(|c| c.0 >= self.period)
+            .unwrap_or(BS2013_SS_COEFFS.len() - 1);
+        let (t0, c00, c10) = BS2013_SS_COEFFS[idx - 1];
+        let (t1, c01, c11) = BS2013_SS_COEFFS[idx];
+        let w = (self.period / t0).ln() / (t1 / t0).ln();
+        (c00 + w * (c01 - c00), c10 + w * (c11 - c10))
+    }
+
+    /// Directivity term `fD` (natural-log units) at a site.
+    pub fn log_term(&self, lon: f64, lat: f64) -> f64 {
+        let (c0, c1) = self.coefficients();
+        if c0 == 0. && c1 == 0. {
+            return 0.;
+        }
+        let rupture = &self.rupture;
+        let (x, y) = local_xy(self.lon, self.lat, lon, lat);
+        let ((sx, sy), _) = rupture.unit_vectors();
+        let hw = rupture.hanging_wall_inputs(self.lon, self.lat, self.depth, lon, lat);
+
+        // Rupture length between the hypocenter and the site projection on strike
+        let s = (x * sx + y * sy).abs().min(0.5 * rupture.length).max(1.);
+        let f_geom = s.ln();
+
+        // Approximate rupture distance from the rupture surface projection
+        let projected_width = rupture.width * rupture.dip.to_radians().cos();
+        let across = if hw.rx < 0. {
+            -hw.rx
+        } else {
+            (hw.rx - projected_width).max(0.)
+        };
+        let r_rup = across.hypot(hw.ry0).hypot(hw.ztor);
+        let r_l = r_rup / rupture.length;
+        let t_cd = if r_l < 0.5 {
+            1.
+        } else if r_l < 1. {
+            1. - (r_l - 0.5) / 0.5
+        } else {
+            0.
+        };
+
+        let t_mw = ((self.magnitude - 5.) / 1.5).clamp(0., 1.);
+
+        (c0 + c1 * f_geom) * t_cd * t_mw
+    }
+}
+
+impl GridTransform for Directivity {
+    fn apply(&self, mut point: GmpePoint) -> Option<GmpePoint> {
+        if matches!(point.kind, GmpePointKind::Psa { period } if (period - self.period).abs() < 1e-6)
+        {
+            point.value *= self.log_term(point.lon, point.lat).exp();
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eq() -> Earthquake {
+        Earthquake::new_mw(143., 52., 10., 7.5).with_rupture(Rupture::new(0., 90., 100., 20.))
+    }
+
+    #[test]
+    fn test_point_source_has_no_directivity() {
+        assert!(Directivity::new(&Earthquake::new_mw(143., 52., 10., 7.5), 3.).is_none());
+    }
+
+    #[test]
+    fn test_forward_directivity_amplifies_long_periods() {
+        let d = Directivity::new(&eq(), 3.).unwrap();
+        // Near the end of the rupture along strike (forward direction)
+        let forward = d.log_term(143.0, 52.4);
+        // Next to the epicenter, perpendicular to strike (no rupture toward the site)
+        let backward = d.log_term(143.1, 52.0);
+        assert!(forward > 0.);
+        assert!(backward < 0.);
+    }
+
+    #[test]
+    fn test_short_periods_and_other_kinds_unchanged() {
+        let d = Directivity::new(&eq(), 0.3).unwrap();
+        assert_eq!(d.log_term(143.0, 52.4), 0.);
+        let d = Directivity::new(&eq(), 3.).unwrap();
+        let pga = d.apply(GmpePoint::new_pga(143.0, 52.4, 5.)).unwrap();
+        assert_eq!(pga.value, 5.);
+    }
+}
@@ -0,0 +1,308 @@
+//! Source-to-site distance calculation methods and metrics.
+//!
+//! This module provides the epicentral distance computation used by the GMPE implementations.
+//! Two methods are available:
+//!
+//! - [`DistanceMethod::Haversine`] — great-circle distance on a spherical Earth (default).
+//! - [`DistanceMethod::FlatEarth`] — equirectangular (local flat-earth) approximation.
+//!
+//! The flat-earth approximation avoids most of the trigonometry of the Haversine formula and is
+//! noticeably faster on dense grids with millions of cells. For grids spanning less than 2–3
+//! degrees the difference between both methods is well below the resolution of any GMPE.
+//!
+//! GMPE families are defined in different distance metrics ([`DistanceMetric`]): epicentral,
+//! hypocentral, Joyner–Boore, rupture distance and `Rx`. Every model declares the metrics it
+//! needs ([`GroundMotionModeling::distance_metrics`]), and only those are computed for a site
+//! ([`SourceDistances::compute`]). The finite-fault metrics `Rjb`, `Rrup` and `Rx` are computed
+//! to the rupture attached to the earthquake, see [`rupture`](crate::rupture). For point sources
+//! they are left empty and every model applies its own point-source approximation, e.g.
+//! `Rrup ≈ sqrt(Repi² + Ztor²)`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::distance::DistanceMethod;
+//!
+//! let haversine = DistanceMethod::Haversine.epicentral_distance(142.4, 50.0, 142.5, 50.1);
+//! let flat = DistanceMethod::FlatEarth.epicentral_distance(142.4, 50.0, 142.5, 50.1);
+//! assert!((haversine - flat).abs() < 0.01);
+//! ```
+//!
+//! ## Distance Metrics
+//!
+//! ```rust
+//! use ground_motion_lib::distance::{DistanceMethod, DistanceMetric, SourceDistances};
+//! use ground_motion_lib::gmm::Earthquake;
+//! use ground_motion_lib::rupture::Rupture;
+//!
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 7.5)
+//!     .with_rupture(Rupture::new(0., 90., 80., 20.).with_ztor(0.));
+//! let metrics = [DistanceMetric::Rjb, DistanceMetric::Rrup];
+//! let distances = SourceDistances::compute(&metrics, &eq, 142.4, 50.3, DistanceMethod::Haversine);
+//! assert!(distances.rjb.unwrap() < 1e-6);
+//! assert!(distances.rhyp.is_none());
+//! ```
+
+use crate::gmm::Earthquake;
+#[cfg(doc)]
+use crate::gmm::GroundMotionModeling;
+use geo::{Distance, Haversine, Point};
+use std::fmt;
+use std::str::FromStr;
+
+/// Mean Earth radius (GRS80) in kilometers, consistent with the [`Haversine`] metric space.
+pub const EARTH_RADIUS_KM: f64 = 6371.0087714;
+
+/// Method used to compute the horizontal distance between an epicenter and a site.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DistanceMethod {
+    /// Great-circle distance computed with the Haversine formula.
+    #[default]
+    Haversine,
+    /// Equirectangular projection around the mean latitude of both points.
+    ///
+    /// Suitable for small grids (less than 2–3 degrees across).
+    FlatEarth,
+}
+
+impl DistanceMethod {
+    /// Compute the horizontal distance (km) between two points given in decimal degrees.
+    ///
+    /// # Arguments
+    ///
+    /// * `lon1`, `lat1` - Coordinates of the first point (e.g. the epicenter).
+    /// * `lon2`, `lat2` - Coordinates of the second point (e.g. the site).
+    ///
+    /// # Returns
+    ///
+    /// Distance in kilometers.
+    pub fn epicentral_distance(&self, lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+        match self {
+            DistanceMethod::Haversine => {
+                Haversine.distance(Point::new(lon1, lat1), Point::new(lon2, lat2)) / 1000.
+            }
+            DistanceMethod::FlatEarth => {
+                let mean_lat = (0.5 * (lat1 + lat2)).to_radians();
+                let x = lon_difference(lon1, lon2).to_radians() * mean_lat.cos();
+                let y = (lat2 - lat1).to_radians();
+                EARTH_RADIUS_KM * x.hypot(y)
+            }
+        }
+    }
+}
+
+/// Longitude difference `lon2 − lon1` (degrees) wrapped into `[−180, 180)`, so that points on
+/// both sides of the antimeridian are close.
+pub(crate) fn lon_difference(lon1: f64, lon2: f64) -> f64 {
+    (lon2 - lon1 + 180.).rem_euclid(360.) - 180.
+}
+
+impl fmt::Display for DistanceMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceMethod::Haversine => write!(f, "haversine"),
+            DistanceMethod::FlatEarth => write!(f, "flat-earth"),
+        }
+    }
+}
+
+impl FromStr for DistanceMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "haversine" => Ok(DistanceMethod::Haversine),
+            "flat-earth" | "flat" | "equirectangular" => Ok(DistanceMethod::FlatEarth),
+            _ => Err(format!(
+                "Unknown distance method `{s}`, expected `haversine` or `flat-earth`"
+            )),
+        }
+    }
+}
+
+/// Source-to-site distance metric of a ground motion model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DistanceMetric {
+    /// Epicentral distance.
+    Repi,
+    /// Hypocentral distance.
+    Rhyp,
+    /// Joyner–Boore distance: closest distance to the surface projection of the rupture.
+    Rjb,
+    /// Rupture distance: closest distance to the rupture plane.
+    Rrup,
+    /// Horizontal distance from the top edge of the rupture perpendicular to strike, positive
+    /// on the hanging wall.
+    Rx,
+}
+
+impl fmt::Display for DistanceMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DistanceMetric::Repi => write!(f, "repi"),
+            DistanceMetric::Rhyp => write!(f, "rhyp"),
+            DistanceMetric::Rjb => write!(f, "rjb"),
+            DistanceMetric::Rrup => write!(f, "rrup"),
+            DistanceMetric::Rx => write!(f, "rx"),
+        }
+    }
+}
+
+impl FromStr for DistanceMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "repi" => Ok(DistanceMetric::Repi),
+            "rhyp" => Ok(DistanceMetric::Rhyp),
+            "rjb" => Ok(DistanceMetric::Rjb),
+            "rrup" => Ok(DistanceMetric::Rrup),
+            "rx" => Ok(DistanceMetric::Rx),
+            _ => Err(format!(
+                "Unknown distance metric `{s}`, expected `repi`, `rhyp`, `rjb`, `rrup` or `rx`"
+            )),
+        }
+    }
+}
+
+/// Source-to-site distances (km) of a site for a ground motion model.
+///
+/// The epicentral distance is always available. The other metrics are `None` unless requested
+/// by the model; `rjb`, `rrup` and `rx` are also `None` for point sources.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceDistances {
+    /// Epicentral distance.
+    pub repi: f64,
+    /// Hypocentral distance.
+    pub rhyp: Option<f64>,
+    /// Joyner–Boore distance to the attached rupture.
+    pub rjb: Option<f64>,
+    /// Rupture distance to the attached rupture.
+    pub rrup: Option<f64>,
+    /// Signed horizontal distance perpendicular to the strike of the attached rupture.
+    pub rx: Option<f64>,
+}
+
+impl SourceDistances {
+    /// Distances holding only the epicentral distance (km).
+    pub fn new(repi: f64) -> Self {
+        Self {
+            repi,
+            rhyp: None,
+            rjb: None,
+            rrup: None,
+            rx: None,
+        }
+    }
+
+    /// Compute the requested distance metrics from an earthquake to a site.
+    ///
+    /// # Arguments
+    ///
+    /// * `metrics` - Distance metrics to compute; the epicentral distance is always computed.
+    /// * `eq` - Earthquake, with an optional finite or multi-segment rupture.
+    /// * `lon`, `lat` - Site location (degrees).
+    /// * `method` - Method used to compute the epicentral distance.
+    pub fn compute(
+        metrics: &[DistanceMetric],
+        eq: &Earthquake,
+        lon: f64,
+        lat: f64,
+        method: DistanceMethod,
+    ) -> Self {
+        let mut distances = Self::new(method.epicentral_distance(eq.lon, eq.lat, lon, lat));
+        for metric in metrics {
+            match metric {
+                DistanceMetric::Repi => {}
+                DistanceMetric::Rhyp => distances.rhyp = Some(distances.repi.hypot(eq.depth)),
+                DistanceMetric::Rjb if eq.segments.is_some() => {
+                    distances.rjb = Some(eq.rjb(lon, lat))
+                }
+                DistanceMetric::Rjb => {
+                    distances.rjb = eq.rupture.map(|rupture| {
+                        rupture.joyner_boore_distance(eq.lon, eq.lat, eq.depth, lon, lat)
+                    })
+                }
+                DistanceMetric::Rrup if eq.segments.is_some() => {
+                    distances.rrup = Some(eq.rrup(lon, lat))
+                }
+                DistanceMetric::Rrup => {
+                    distances.rrup = eq
+                        .rupture
+                        .map(|rupture| rupture.rupture_distance(eq.lon, eq.lat, eq.depth, lon, lat))
+                }
+                DistanceMetric::Rx => {
+                    distances.rx = eq.hanging_wall_inputs(lon, lat).map(|hw| hw.rx)
+                }
+            }
+        }
+        distances
+    }
+
+    /// Distance in a metric, if available.
+    pub fn get(&self, metric: DistanceMetric) -> Option<f64> {
+        match metric {
+            DistanceMetric::Repi => Some(self.repi),
+            DistanceMetric::Rhyp => self.rhyp,
+            DistanceMetric::Rjb => self.rjb,
+            DistanceMetric::Rrup => self.rrup,
+            DistanceMetric::Rx => self.rx,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_earth_close_to_haversine_on_small_grid() {
+        let h = DistanceMethod::Haversine.epicentral_distance(143.04, 51.92, 144.5, 53.1);
+        let f = DistanceMethod::FlatEarth.epicentral_distance(143.04, 51.92, 144.5, 53.1);
+        assert!((h - f).abs() / h < 1e-3);
+    }
+
+    #[test]
+    fn test_flat_earth_across_antimeridian() {
+        // Chukotka grid cells on both sides of 180°
+        let h = DistanceMethod::Haversine.epicentral_distance(179.8, 65., -179.8, 65.2);
+        let f = DistanceMethod::FlatEarth.epicentral_distance(179.8, 65., -179.8, 65.2);
+        assert!(h < 30.);
+        assert!((h - f).abs() / h < 1e-3);
+        assert!((lon_difference(-179.8, 179.8) + 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_zero_distance() {
+        let f = DistanceMethod::FlatEarth.epicentral_distance(143.04, 51.92, 143.04, 51.92);
+        assert_eq!(f, 0.);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            "flat-earth".parse::<DistanceMethod>().unwrap(),
+            DistanceMethod::FlatEarth
+        );
+        assert_eq!(
+            "Haversine".parse::<DistanceMethod>().unwrap(),
+            DistanceMethod::Haversine
+        );
+        assert!("vincenty".parse::<DistanceMethod>().is_err());
+        assert_eq!("RJB".parse::<DistanceMetric>(), Ok(DistanceMetric::Rjb));
+        assert_eq!(DistanceMetric::Rrup.to_string(), "rrup");
+        assert!("rcd".parse::<DistanceMetric>().is_err());
+    }
+
+    #[test]
+    fn test_only_requested_metrics() {
+        let point = Earthquake::new_mw(143., 52., 10., 6.);
+        let metrics = [DistanceMetric::Rhyp, DistanceMetric::Rrup];
+        let d = SourceDistances::compute(&metrics, &point, 143.3, 52., DistanceMethod::FlatEarth);
+        assert!((d.rhyp.unwrap() - d.repi.hypot(10.)).abs() < 1e-12);
+        // Finite-fault metrics are not available for point sources
+        assert_eq!(d.rrup, None);
+        assert_eq!(d.rjb, None);
+        assert_eq!(d.get(DistanceMetric::Repi), Some(d.repi));
+    }
+}
@@ -0,0 +1,340 @@
+//! Weighted ensembles of ground motion models.
+//!
+//! This module provides [`EnsembleModel`], a weighted mixture of several GMPEs that itself
+//! implements [`GroundMotionModeling`]. Member predictions are combined as a weighted mean in
+//! log space (i.e. a weighted geometric mean of the predicted values), which is the usual way
+//! of averaging logic-tree branches of ground motion models. Intensities
+//! ([`GmpePointKind::Mmi`](crate::gmm::GmpePointKind::Mmi)) are normally distributed and
+//! combined as a weighted arithmetic mean instead.
+//!
+//! Because the ensemble is a regular model, it can be passed to
+//! [`calc_gmpe_vec`](crate::vectorized::calc_gmpe_vec) to produce logic-tree mean maps without
+//! any changes to the vectorized layer. [`EnsembleModel::from_configs`] builds an ensemble of
+//! predefined configs by name; [`EnsembleGmpe`] is an alias of the ensemble type.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::configs::get_mf2013_lib_configs;
+//! use ground_motion_lib::ensemble::EnsembleModel;
+//! use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+//! use ground_motion_lib::vectorized::calc_gmpe_vec;
+//!
+//! let configs = get_mf2013_lib_configs();
+//! let ensemble = EnsembleModel::new(vec![
+//!     (Box::new(configs.get("config_mf2013_crustal_pga").unwrap()), 0.6),
+//!     (Box::new(configs.get("config_mf2013_crustal_pga_2").unwrap()), 0.4),
+//! ]);
+//!
+//! let points = vec![Vs30Point::new(142.5, 50.0, 400., Some(200.), None)];
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let results = calc_gmpe_vec(&points, &ensemble, &eq);
+//! println!("{results:?}");
+//! ```
+
+use crate::configs::get_lib_config;
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GroundMotionModeling, Vs30Point};
+use std::error::Error;
+
+/// Weighted mixture of ground motion models combined in log space, or linearly for intensities.
+///
+/// All members are expected to predict the same type of motion (e.g. PGA). The kind of the
+/// resulting [`GmpePoint`] is taken from the first member. Weights do not have to sum to one,
+/// they are normalized on evaluation.
+#[derive(Debug)]
+pub struct EnsembleModel {
+    /// Ensemble members as `(model, weight)` pairs.
+    pub members: Vec<(Box<dyn GroundMotionModeling + Send + Sync>, f64)>,
+}
+
+/// Weighted GMPE ensemble, an alias of [`EnsembleModel`].
+pub type EnsembleGmpe = EnsembleModel;
+
+impl EnsembleModel {
+    /// Create a new ensemble from `(model, weight)` pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `members` is empty or if the weights are negative or sum to zero.
+    pub fn new(members: Vec<(Box<dyn GroundMotionModeling + Send + Sync>, f64)>) -> Self {
+        assert!(
+            !members.is_empty(),
+            "Ensemble must have at least one member"
+        );
+        assert!(
+            members.iter().all(|(_, w)| *w >= 0.),
+            "Ensemble weights must be non-negative"
+        );
+        assert!(
+            members.iter().map(|(_, w)| w).sum::<f64>() > 0.,
+            "Ensemble weights must not sum to zero"
+        );
+        Self { members }
+    }
+
+    /// Create a new ensemble of predefined configs from `(config name, weight)` pairs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ground_motion_lib::ensemble::EnsembleGmpe;
+    ///
+    /// let ensemble = EnsembleGmpe::from_configs(&[
+    ///     ("config_mf2013_crustal_pga", 0.5),
+    ///     ("config_bssa2014_pga", 0.5),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(ensemble.members.len(), 2);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config is unknown, no configs are given, the weights are negative or
+    /// sum to zero, or the configs predict different types of motion (including PSA at different
+    /// periods).
+    pub fn from_configs(configs: &[(&str, f64)]) -> Result<Self, Box<dyn Error>> {
+        if configs.is_empty() {
+            return Err("Ensemble must have at least one member".into());
+        }
+        if configs.iter().any(|(_, w)| *w < 0.) || configs.iter().map(|(_, w)| w).sum::<f64>() <= 0.
+        {
+            return Err("Ensemble weights must be non-negative and not sum to zero".into());
+        }
+        let members = configs
+            .iter()
+            .map(|(name, weight)| {
+                let config =
+                    get_lib_config(name).ok_or_else(|| format!("Unknown config `{name}`"))?;
+                let model: Box<dyn GroundMotionModeling + Send + Sync> = Box::new(config);
+                Ok((model, *weight))
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        // Probe the type of motion of every member with a reference scenario
+        let eq = Earthquake::new_mw(0., 0., 10., 6.);
+        let site = Vs30Point::new(0., 0.2, 760., None, None);
+        let kinds: Vec<_> = members
+            .iter()
+            .map(|(model, _)| model.calc_from_point(&site, &eq).kind)
+            .collect();
+        if let Some(i) = kinds.iter().position(|kind| *kind != kinds[0]) {
+            return Err(format!(
+                "Ensemble members predict different types of motion: `{}` gives {}, `{}` gives {}",
+                configs[0].0, kinds[0], configs[i].0, kinds[i]
+            )
+            .into());
+        }
+        Ok(Self::new(members))
+    }
+
+    /// Sum of all member weights.
+    pub fn total_weight(&self) -> f64 {
+        self.members.iter().map(|(_, w)| w).sum()
+    }
+}
+
+impl GroundMotionModeling for EnsembleModel {
+    /// Compute the weighted mean of all member predictions, in log space for log-normal motions.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Input site point parameters.
+    /// * `eq` - Earthquake source parameters.
+    /// * `epicentral_distance` - Horizontal distance from the epicenter to the site (km).
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` holding the weighted geometric mean of the member predictions, or the
+    /// weighted arithmetic mean for intensities. The standard deviation is that of the weighted
+    /// mixture of the member distributions, in the units of [`GmpePoint::sigma`], and is `None`
+    /// unless every member provides one.
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Union of the distance metrics of all members.
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        let mut metrics = Vec::new();
+        for metric in self.members.iter().flat_map(|(m, _)| m.distance_metrics()) {
+            if !metrics.contains(&metric) {
+                metrics.push(metric);
+            }
+        }
+        metrics
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let total_weight = self.total_weight();
+        let mut kind = None;
+        let members: Vec<_> = self
+            .members
+            .iter()
+            .map(|(model, weight)| {
+                let gm = model.calc_from_distances(point, eq, distances);
+                kind.get_or_insert(gm.kind);
+                (weight / total_weight, gm.log_mean(), gm.sigma)
+            })
+            .collect();
+        let kind = kind.expect("Ensemble must have at least one member");
+        // Mean and standard deviation of the weighted mixture of member distributions, in log
+        // space for log-normal motions and in intensity units for MMI
+        let mean: f64 = members.iter().map(|(w, y, _)| w * y).sum();
+        let sigma = members
+            .iter()
+            .map(|(w, y, sigma)| sigma.map(|s| w * (s.powi(2) + (y - mean).powi(2))))
+            .sum::<Option<f64>>()
+            .map(f64::sqrt);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: if kind.is_log_normal() {
+                mean.exp()
+            } else {
+                mean
+            },
+            kind,
+            sigma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_mf2013_lib_configs;
+
+    #[test]
+    fn test_single_member_matches_model() {
+        let config = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let ensemble = EnsembleModel::new(vec![(Box::new(config), 2.)]);
+        let point = Vs30Point::new(143.2, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+        let expected = config.calc_from_point(&point, &eq);
+        let gm = ensemble.calc_from_point(&point, &eq);
+        assert!((gm.value - expected.value).abs() < 1e-10);
+        assert!((gm.sigma.unwrap() - expected.sigma.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_equal_weights_give_geometric_mean() {
+        let configs = get_mf2013_lib_configs();
+        let first = configs.get("config_mf2013_crustal_pga").unwrap();
+        let second = configs.get("config_mf2013_crustal_pga_2").unwrap();
+        let ensemble = EnsembleModel::new(vec![(Box::new(first), 1.), (Box::new(second), 1.)]);
+        let point = Vs30Point::new(143.5, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 6.);
+        let expected = (first.calc_from_point(&point, &eq).value
+            * second.calc_from_point(&point, &eq).value)
+            .sqrt();
+        let value = ensemble.calc_from_point(&point, &eq).value;
+        assert!((value - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_mixture_sigma() {
+        let configs = get_mf2013_lib_configs();
+        let first = configs.get("config_mf2013_crustal_pga").unwrap();
+        let second = configs.get("config_mf2013_crustal_pga_2").unwrap();
+        let ensemble = EnsembleModel::new(vec![(Box::new(first), 1.), (Box::new(second), 1.)]);
+        let point = Vs30Point::new(143.5, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 6.);
+        let a = first.calc_from_point(&point, &eq);
+        let b = second.calc_from_point(&point, &eq);
+        let half_spread = (a.value.ln() - b.value.ln()) / 2.;
+        let expected = ((a.sigma.unwrap().powi(2) + b.sigma.unwrap().powi(2)) / 2.
+            + half_spread.powi(2))
+        .sqrt();
+        let sigma = ensemble.calc_from_point(&point, &eq).sigma.unwrap();
+        assert!((sigma - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_mmi_combined_linearly() {
+        use crate::gmm::GmpePointKind;
+        use crate::ipe::{ALLEN2012, Allen2012Distance};
+
+        let rhyp = ALLEN2012::new(Allen2012Distance::Rhyp);
+        let rrup = ALLEN2012::new(Allen2012Distance::Rrup);
+        let point = Vs30Point::new(143.5, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 6.);
+        let a = rhyp.calc_from_point(&point, &eq);
+        let b = rrup.calc_from_point(&point, &eq);
+        let ensemble = EnsembleModel::new(vec![(Box::new(rhyp), 3.), (Box::new(rrup), 1.)]);
+        let gm = ensemble.calc_from_point(&point, &eq);
+        assert_eq!(gm.kind, GmpePointKind::Mmi);
+        let mean = 0.75 * a.value + 0.25 * b.value;
+        assert!((gm.value - mean).abs() < 1e-10);
+        let variance = 0.75 * (a.sigma.unwrap().powi(2) + (a.value - mean).powi(2))
+            + 0.25 * (b.sigma.unwrap().powi(2) + (b.value - mean).powi(2));
+        assert!((gm.sigma.unwrap() - variance.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_configs() {
+        let ensemble = EnsembleGmpe::from_configs(&[("config_mf2013_crustal_pga", 1.)]).unwrap();
+        let config = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let point = Vs30Point::new(143.2, 52.0, 350., None, None);
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+        let expected = config.calc_from_point(&point, &eq).value;
+        assert!((ensemble.calc_from_point(&point, &eq).value - expected).abs() < 1e-10);
+        assert!(EnsembleGmpe::from_configs(&[("config_unknown", 1.)]).is_err());
+        assert!(EnsembleGmpe::from_configs(&[("config_mf2013_crustal_pga", 0.)]).is_err());
+        assert!(EnsembleGmpe::from_configs(&[]).is_err());
+    }
+
+    #[test]
+    fn test_from_configs_rejects_mixed_kinds() {
+        let err = EnsembleGmpe::from_configs(&[
+            ("config_mf2013_crustal_pga", 1.),
+            ("config_bssa2014_pgv", 1.),
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("different types of motion"));
+        assert!(
+            EnsembleGmpe::from_configs(&[
+                ("config_mf2013_crustal_psa_10", 1.),
+                ("config_bssa2014_psa_03", 1.),
+            ])
+            .is_err()
+        );
+        assert!(
+            EnsembleGmpe::from_configs(&[
+                ("config_mf2013_crustal_psa_10", 1.),
+                ("config_bssa2014_psa_10", 1.),
+            ])
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_distance_metrics_union() {
+        let ensemble = EnsembleGmpe::from_configs(&[
+            ("config_mf2013_crustal_pga", 1.),
+            ("config_bssa2014_pga", 1.),
+            ("config_cb2014_pga", 1.),
+        ])
+        .unwrap();
+        assert_eq!(
+            ensemble.distance_metrics(),
+            vec![
+                DistanceMetric::Rrup,
+                DistanceMetric::Rjb,
+                DistanceMetric::Rx
+            ]
+        );
+    }
+}
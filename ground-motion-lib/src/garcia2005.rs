@@ -0,0 +1,172 @@
+//! Implementation of García et al. (2005) Ground Motion Prediction Equations (GMPE) for
+//! intraslab (inslab) earthquakes in central Mexico.
+//!
+//! The model predicts the median horizontal motion on hard rock (NEHRP B):
+//!
+//! ```text
+//! log10(Y) = c1 + c2·Mw + c3·R − c4·log10(R) + c5·H
+//! R        = sqrt(Rcld² + Δ²),   Δ = 0.0075 · 10^(0.507·Mw)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in cm/s², or PGV in cm/s, `Rcld` is the closest distance to
+//! the rupture (km) and `H` is the focal depth (km). The near-source saturation `Δ` and the
+//! depth term make deeper events of the same magnitude produce stronger motion.
+//!
+//! Coefficients are shipped as a text table ([`GARCIA2005_TABLE`]) and parsed with
+//! [`CoeffsTable`].
+//!
+//! Current simplifications:
+//!
+//! - Hard-rock reference site; the site Vs30 is not used.
+//! - For point sources the closest rupture distance is approximated by the hypocentral
+//!   distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use std::f64::consts::LN_10;
+
+/// García et al. (2005) inslab coefficients (PGA, PGV, PSA at 0.1, 0.2, 0.5, 1 and 2 s).
+pub const GARCIA2005_TABLE: &str = "
+    imt   c1     c2    c3       c4   c5     sigma
+    pga   -0.2   0.59  -0.0039  1.0  0.008  0.27
+    pgv   -1.72  0.69  -0.0017  1.0  0.002  0.26
+    0.1   0.01   0.56  -0.0043  1.0  0.008  0.29
+    0.2   0.2    0.56  -0.0036  1.0  0.007  0.29
+    0.5   -0.9   0.66  -0.0021  1.0  0.005  0.3
+    1.0   -1.9   0.76  -0.0013  1.0  0.004  0.31
+    2.0   -2.9   0.85  -0.0008  1.0  0.003  0.32
+";
+
+/// García et al. (2005) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GARCIA2005 {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling
+    pub c2: f64,
+    /// Anelastic attenuation
+    pub c3: f64,
+    /// Geometric spreading
+    pub c4: f64,
+    /// Focal depth scaling
+    pub c5: f64,
+    /// Total standard deviation of `log10(Y)`
+    pub sigma: f64,
+    /// Type of motion (PGA, PGV, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl GARCIA2005 {
+    /// Build the model from a row of a García et al. coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            c1: row.coeff("c1"),
+            c2: row.coeff("c2"),
+            c3: row.coeff("c3"),
+            c4: row.coeff("c4"),
+            c5: row.coeff("c5"),
+            sigma: row.coeff("sigma"),
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// All models of the shipped coefficient table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(GARCIA2005_TABLE)
+            .expect("Invalid García et al. coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Median `log10(Y)` (cm/s² for PGA/PSA, cm/s for PGV).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rcld` - Closest distance to the rupture (km).
+    /// * `depth` - Focal depth (km).
+    pub fn log10_median(&self, magnitude: f64, rcld: f64, depth: f64) -> f64 {
+        let delta = 0.0075 * 10_f64.powf(0.507 * magnitude);
+        let r = rcld.hypot(delta);
+        self.c1 + self.c2 * magnitude + self.c3 * r - self.c4 * r.log10() + self.c5 * depth
+    }
+}
+
+impl GroundMotionModeling for GARCIA2005 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rcld = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let mut ground_motion = 10_f64.powf(self.log10_median(eq.mw(), rcld, eq.depth));
+        // convert cm/s² to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion = ((ground_motion / 100.) / G_GLOBAL) * 100.;
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pga_reference_value() {
+        let pga = GARCIA2005::from_table()[0];
+        let r = 80_f64.hypot(0.0075 * 10_f64.powf(0.507 * 7.));
+        let expected = -0.2 + 0.59 * 7. - 0.0039 * r - r.log10() + 0.008 * 60.;
+        assert!((pga.log10_median(7., 80., 60.) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_deeper_events_are_stronger() {
+        let models = GARCIA2005::from_table();
+        assert_eq!(models[1].motion_kind, GmpePointKind::Pgv);
+        for model in models {
+            assert!(model.log10_median(6.5, 100., 90.) > model.log10_median(6.5, 100., 40.));
+        }
+    }
+}
@@ -0,0 +1,527 @@
+//! Ground-motion-to-intensity conversion equations (GMICE).
+//!
+//! This module converts computed peak ground motions into macroseismic intensity on the Modified
+//! Mercalli scale (MMI) for emergency response products, and intensities (e.g. felt reports)
+//! back into ground motions. Conversion equations implement the [`Gmice`] trait, so the
+//! relation can be chosen per region:
+//!
+//! - [`Worden2012`]: Worden et al. (2012), California.
+//! - [`AtkinsonKaka2007`]: Atkinson & Kaka (2007), central United States and California.
+//!
+//! Every [`Gmice`] is a [`GridTransform`] converting ground motion points into
+//! [`GmpePointKind::Mmi`] points and can be appended to a
+//! [`Pipeline`](crate::pipeline::Pipeline). The inverse conversion runs as a [`FromIntensity`]
+//! stage. [`GmiceMethod`] selects a relation by name, e.g. from command line arguments.
+//!
+//! Both relations are bilinear in `log10(Y)`:
+//!
+//! ```text
+//! MMI = c1 + c2·log10(Y)   for log10(Y) ≤ t1
+//! MMI = c3 + c4·log10(Y)   for log10(Y) > t1
+//! ```
+//!
+//! where `Y` is PGA or PSA in cm/s², or PGV in cm/s. When the event is known, the
+//! distance/magnitude residual terms `c5 + c6·log10(R) + c7·M` are added, with `R` the
+//! hypocentral distance (km). Intensities are clipped to the `[1, 10]` range of the relations.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmice::{AtkinsonKaka2007, FromIntensity, Worden2012};
+//! use ground_motion_lib::gmm::{Earthquake, GmpePoint, GmpePointKind};
+//! use ground_motion_lib::pipeline::Pipeline;
+//!
+//! let eq = Earthquake::new_mw(142.83, 52.63, 11.0, 7.1);
+//! let pipeline = Pipeline::new().then(Worden2012::new().with_event(&eq));
+//!
+//! let grid = vec![GmpePoint::new_pga(142.95, 53.0, 25.0)];
+//! let mmi = pipeline.run(grid);
+//! assert_eq!(mmi[0].kind, GmpePointKind::Mmi);
+//!
+//! // Felt reports back to PGV with the Atkinson & Kaka (2007) relations
+//! let reports = vec![GmpePoint::new(142.95, 53.0, 6.0, GmpePointKind::Mmi)];
+//! let pgv = Pipeline::new()
+//!     .then(FromIntensity::new(AtkinsonKaka2007::new(), GmpePointKind::Pgv))
+//!     .run(reports);
+//! assert_eq!(pgv[0].kind, GmpePointKind::Pgv);
+//! ```
+
+use crate::auxilary::G_GLOBAL;
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind};
+use crate::pipeline::{GridTransform, Pipeline};
+use std::fmt::{self, Debug};
+use std::str::FromStr;
+
+/// Lowest and highest intensity covered by the conversion equations.
+const MMI_MIN: f64 = 1.;
+const MMI_MAX: f64 = 10.;
+
+/// Coefficients of a bilinear conversion relation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GmiceCoeffs {
+    /// Intercept and slope below the break
+    pub c1: f64,
+    pub c2: f64,
+    /// Intercept and slope above the break
+    pub c3: f64,
+    pub c4: f64,
+    /// Break in `log10(Y)`
+    pub t1: f64,
+    /// Break in MMI
+    pub t2: f64,
+    /// Constant, distance and magnitude residual terms
+    pub c5: f64,
+    pub c6: f64,
+    pub c7: f64,
+}
+
+const fn coeffs([c1, c2, c3, c4, t1, t2]: [f64; 6], [c5, c6, c7]: [f64; 3]) -> GmiceCoeffs {
+    GmiceCoeffs {
+        c1,
+        c2,
+        c3,
+        c4,
+        t1,
+        t2,
+        c5,
+        c6,
+        c7,
+    }
+}
+
+/// Worden et al. (2012) PGA relation (cm/s²).
+pub const WORDEN2012_PGA: GmiceCoeffs =
+    coeffs([1.78, 1.55, -1.60, 3.70, 1.57, 4.22], [-0.91, 1.02, -0.17]);
+/// Worden et al. (2012) PGV relation (cm/s).
+pub const WORDEN2012_PGV: GmiceCoeffs =
+    coeffs([3.78, 1.47, 2.89, 3.16, 0.53, 4.56], [0.90, 0.00, -0.18]);
+/// Worden et al. (2012) PSA relations (cm/s²) at 0.3, 1 and 3 s.
+pub const WORDEN2012_PSA: [(f64, GmiceCoeffs); 3] = [
+    (
+        0.3,
+        coeffs([1.26, 1.69, -4.15, 4.14, 2.21, 4.99], [-1.05, 0.60, 0.00]),
+    ),
+    (
+        1.0,
+        coeffs([2.50, 1.51, 0.20, 2.90, 1.65, 4.98], [-1.91, 1.00, -0.17]),
+    ),
+    (
+        3.0,
+        coeffs([3.81, 1.17, 1.99, 3.01, 0.99, 4.96], [-1.17, 1.00, -0.10]),
+    ),
+];
+
+// Atkinson & Kaka (2007) publish the residual terms as `c5 + c6·M + c7·log10(R)`; the
+// magnitude and distance coefficients are swapped below to match `GmiceCoeffs`.
+
+/// Atkinson & Kaka (2007) PGA relation (cm/s²).
+pub const AK2007_PGA: GmiceCoeffs =
+    coeffs([2.65, 1.39, -1.91, 4.09, 1.69, 5.0], [-1.96, 0.98, 0.02]);
+/// Atkinson & Kaka (2007) PGV relation (cm/s).
+pub const AK2007_PGV: GmiceCoeffs =
+    coeffs([4.37, 1.32, 3.54, 3.03, 0.48, 5.0], [0.47, 0.26, -0.19]);
+/// Atkinson & Kaka (2007) PSA relations (cm/s²) at 0.3, 1 and 2 s.
+pub const AK2007_PSA: [(f64, GmiceCoeffs); 3] = [
+    (
+        0.3,
+        coeffs([2.40, 1.36, -1.83, 3.56, 1.92, 5.0], [-0.11, 0.64, -0.20]),
+    ),
+    (
+        1.0,
+        coeffs([3.23, 1.18, 0.57, 2.95, 1.50, 5.0], [1.92, 0.04, -0.39]),
+    ),
+    (
+        2.0,
+        coeffs([3.72, 1.29, 1.99, 3.00, 1.00, 5.0], [2.24, -0.31, -0.33]),
+    ),
+];
+
+impl GmiceCoeffs {
+    /// Intensity for the given `log10(Y)`, without residual terms.
+    pub fn mmi(&self, log10_y: f64) -> f64 {
+        if log10_y <= self.t1 {
+            self.c1 + self.c2 * log10_y
+        } else {
+            self.c3 + self.c4 * log10_y
+        }
+    }
+
+    /// `log10(Y)` for the given intensity, without residual terms.
+    pub fn log10_ground_motion(&self, mmi: f64) -> f64 {
+        if mmi <= self.t2 {
+            (mmi - self.c1) / self.c2
+        } else {
+            (mmi - self.c3) / self.c4
+        }
+    }
+
+    /// Distance/magnitude residual term for the hypocentral distance (km) and magnitude.
+    pub fn residual(&self, distance: f64, magnitude: f64) -> f64 {
+        self.c5 + self.c6 * distance.max(1.).log10() + self.c7 * magnitude
+    }
+}
+
+/// Event parameters of the distance/magnitude residual terms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GmiceEvent {
+    /// Epicenter longitude (decimal degrees).
+    pub lon: f64,
+    /// Epicenter latitude (decimal degrees).
+    pub lat: f64,
+    /// Hypocentral depth (km).
+    pub depth: f64,
+    /// Moment magnitude.
+    pub magnitude: f64,
+}
+
+impl From<&Earthquake> for GmiceEvent {
+    fn from(eq: &Earthquake) -> Self {
+        Self {
+            lon: eq.lon,
+            lat: eq.lat,
+            depth: eq.depth,
+            magnitude: eq.mw(),
+        }
+    }
+}
+
+impl GmiceEvent {
+    /// Hypocentral distance (km) to a site.
+    pub fn distance(&self, lon: f64, lat: f64) -> f64 {
+        DistanceMethod::Haversine
+            .epicentral_distance(self.lon, self.lat, lon, lat)
+            .hypot(self.depth)
+    }
+}
+
+/// Convert a value in crate units (%g or cm/s) to the units of the relations (cm/s² or cm/s).
+fn to_relation_units(kind: GmpePointKind, value: f64) -> f64 {
+    match kind {
+        GmpePointKind::Pgv => value,
+        _ => value * G_GLOBAL,
+    }
+}
+
+/// A ground-motion-to-intensity conversion equation.
+///
+/// Implementors only provide their bilinear relations; the forward and inverse conversions are
+/// shared.
+pub trait Gmice: Debug {
+    /// Relation for the given type of motion, if supported.
+    fn coefficients(&self, kind: GmpePointKind) -> Option<GmiceCoeffs>;
+
+    /// Event of the distance/magnitude residual terms, if they are applied.
+    fn event(&self) -> Option<GmiceEvent>;
+
+    /// Convert a ground motion value to intensity.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Type of motion of the value.
+    /// * `value` - PGA/PSA in %g or PGV in cm/s.
+    /// * `distance` - Hypocentral distance (km), used with the event residual terms.
+    ///
+    /// # Returns
+    ///
+    /// The MMI clipped to `[1, 10]`, or `None` if the type of motion is not supported.
+    fn mmi(&self, kind: GmpePointKind, value: f64, distance: f64) -> Option<f64> {
+        let c = self.coefficients(kind)?;
+        let y = to_relation_units(kind, value);
+        let mut mmi = c.mmi(y.max(f64::MIN_POSITIVE).log10());
+        if let Some(event) = self.event() {
+            mmi += c.residual(distance, event.magnitude);
+        }
+        Some(mmi.clamp(MMI_MIN, MMI_MAX))
+    }
+
+    /// Convert an intensity to a ground motion value (inverse conversion).
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Type of motion to return.
+    /// * `mmi` - Intensity, clipped to `[1, 10]`.
+    /// * `distance` - Hypocentral distance (km), used with the event residual terms.
+    ///
+    /// # Returns
+    ///
+    /// PGA/PSA in %g or PGV in cm/s, or `None` if the type of motion is not supported.
+    fn ground_motion(&self, kind: GmpePointKind, mmi: f64, distance: f64) -> Option<f64> {
+        let c = self.coefficients(kind)?;
+        let mut mmi = mmi.clamp(MMI_MIN, MMI_MAX);
+        if let Some(event) = self.event() {
+            mmi -= c.residual(distance, event.magnitude);
+        }
+        let y = 10_f64.powf(c.log10_ground_motion(mmi));
+        Some(y / to_relation_units(kind, 1.))
+    }
+}
+
+/// Every conversion equation replaces the ground motion value of a point by its intensity.
+/// Points of unsupported types of motion are dropped.
+impl<T: Gmice> GridTransform for T {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        let distance = self
+            .event()
+            .map_or(0., |e| e.distance(point.lon, point.lat));
+        let mmi = self.mmi(point.kind, point.value, distance)?;
+        Some(GmpePoint::new(
+            point.lon,
+            point.lat,
+            mmi,
+            GmpePointKind::Mmi,
+        ))
+    }
+}
+
+/// Select the PSA relation of the given spectral period.
+fn psa_coefficients(table: &[(f64, GmiceCoeffs)], period: f64) -> Option<GmiceCoeffs> {
+    table
+        .iter()
+        .find(|(p, _)| (p - period).abs() < 1e-6)
+        .map(|(_, c)| *c)
+}
+
+/// Worden et al. (2012) ground-motion-to-intensity conversion.
+#[derive(Debug, Clone, Copy, Default)]
+///
+/// PSA values are converted for the spectral periods 0.3, 1 and 3 s.
+pub struct Worden2012 {
+    /// Event of the residual terms.
+    pub event: Option<GmiceEvent>,
+}
+
+impl Worden2012 {
+    /// Create a conversion without residual terms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the distance/magnitude residual terms of the given event.
+    pub fn with_event(mut self, eq: &Earthquake) -> Self {
+        self.event = Some(eq.into());
+        self
+    }
+}
+
+impl Gmice for Worden2012 {
+    fn coefficients(&self, kind: GmpePointKind) -> Option<GmiceCoeffs> {
+        match kind {
+            GmpePointKind::Pga => Some(WORDEN2012_PGA),
+            GmpePointKind::Pgv => Some(WORDEN2012_PGV),
+            GmpePointKind::Psa { period } => psa_coefficients(&WORDEN2012_PSA, period),
+            _ => None,
+        }
+    }
+
+    fn event(&self) -> Option<GmiceEvent> {
+        self.event
+    }
+}
+
+/// Atkinson & Kaka (2007) ground-motion-to-intensity conversion.
+#[derive(Debug, Clone, Copy, Default)]
+///
+/// PSA values are converted for the spectral periods 0.3, 1 and 2 s.
+pub struct AtkinsonKaka2007 {
+    /// Event of the residual terms.
+    pub event: Option<GmiceEvent>,
+}
+
+impl AtkinsonKaka2007 {
+    /// Create a conversion without residual terms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply the distance/magnitude residual terms of the given event.
+    pub fn with_event(mut self, eq: &Earthquake) -> Self {
+        self.event = Some(eq.into());
+        self
+    }
+}
+
+impl Gmice for AtkinsonKaka2007 {
+    fn coefficients(&self, kind: GmpePointKind) -> Option<GmiceCoeffs> {
+        match kind {
+            GmpePointKind::Pga => Some(AK2007_PGA),
+            GmpePointKind::Pgv => Some(AK2007_PGV),
+            GmpePointKind::Psa { period } => psa_coefficients(&AK2007_PSA, period),
+            _ => None,
+        }
+    }
+
+    fn event(&self) -> Option<GmiceEvent> {
+        self.event
+    }
+}
+
+/// Conversion equation selectable by name: `worden2012` or `ak2007`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GmiceMethod {
+    /// Worden et al. (2012)
+    #[default]
+    Worden2012,
+    /// Atkinson & Kaka (2007)
+    AtkinsonKaka2007,
+}
+
+impl GmiceMethod {
+    /// Convert a ground motion grid of an event to intensities in parallel.
+    ///
+    /// The distance/magnitude residual terms of the event are applied. Points of unsupported
+    /// types of motion are dropped.
+    pub fn to_mmi(&self, grid: Vec<GmpePoint>, eq: &Earthquake) -> Vec<GmpePoint> {
+        let pipeline = match self {
+            GmiceMethod::Worden2012 => Pipeline::new().then(Worden2012::new().with_event(eq)),
+            GmiceMethod::AtkinsonKaka2007 => {
+                Pipeline::new().then(AtkinsonKaka2007::new().with_event(eq))
+            }
+        };
+        pipeline.run(grid)
+    }
+}
+
+impl fmt::Display for GmiceMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GmiceMethod::Worden2012 => write!(f, "worden2012"),
+            GmiceMethod::AtkinsonKaka2007 => write!(f, "ak2007"),
+        }
+    }
+}
+
+impl FromStr for GmiceMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "worden2012" | "wgrw12" => Ok(GmiceMethod::Worden2012),
+            "ak2007" | "atkinsonkaka2007" => Ok(GmiceMethod::AtkinsonKaka2007),
+            _ => Err(format!(
+                "Unknown GMICE `{s}`, expected `worden2012` or `ak2007`"
+            )),
+        }
+    }
+}
+
+/// Inverse conversion stage turning intensity points (e.g. felt reports) into ground motion
+/// points of the given type. Points that are not intensities are dropped.
+#[derive(Debug, Clone, Copy)]
+pub struct FromIntensity<G: Gmice> {
+    /// Conversion equation
+    pub gmice: G,
+    /// Type of motion of the output points
+    pub kind: GmpePointKind,
+}
+
+impl<G: Gmice> FromIntensity<G> {
+    /// Create an inverse conversion stage.
+    pub fn new(gmice: G, kind: GmpePointKind) -> Self {
+        Self { gmice, kind }
+    }
+}
+
+impl<G: Gmice> GridTransform for FromIntensity<G> {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        if point.kind != GmpePointKind::Mmi {
+            return None;
+        }
+        let distance = self
+            .gmice
+            .event()
+            .map_or(0., |e| e.distance(point.lon, point.lat));
+        let value = self.gmice.ground_motion(self.kind, point.value, distance)?;
+        Some(GmpePoint::new(point.lon, point.lat, value, self.kind))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bilinear_pga_relation() {
+        let gmice = Worden2012::new();
+        // 10 %g = 98.1 cm/s², above the break
+        let expected = -1.60 + 3.70 * 98.1_f64.log10();
+        let mmi = gmice.mmi(GmpePointKind::Pga, 10., 0.).unwrap();
+        assert!((mmi - expected).abs() < 1e-12);
+        // 1 cm/s below the break
+        let mmi = gmice.mmi(GmpePointKind::Pgv, 1., 0.).unwrap();
+        assert!((mmi - 3.78).abs() < 1e-12);
+        // Relations are continuous at the break
+        for c in [WORDEN2012_PGA, WORDEN2012_PGV, AK2007_PGA, AK2007_PGV] {
+            assert!((c.mmi(c.t1) - c.mmi(c.t1 + 1e-9)).abs() < 2e-2);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_kinds() {
+        let gmice = Worden2012::new();
+        let psa = |period| GmpePointKind::Psa { period };
+        assert!(gmice.mmi(psa(1.), 10., 0.).is_some());
+        assert!(gmice.mmi(psa(2.), 10., 0.).is_none());
+        let ak07 = AtkinsonKaka2007::new();
+        assert!(ak07.mmi(psa(2.), 10., 0.).is_some());
+        assert!(ak07.mmi(GmpePointKind::Mmi, 5., 0.).is_none());
+    }
+
+    #[test]
+    fn test_gmice_method_by_name() {
+        assert_eq!("ak2007".parse(), Ok(GmiceMethod::AtkinsonKaka2007));
+        assert_eq!(
+            GmiceMethod::Worden2012.to_string().parse(),
+            Ok(GmiceMethod::Worden2012)
+        );
+        assert!("foo".parse::<GmiceMethod>().is_err());
+
+        let eq = Earthquake::new_mw(143., 52., 10., 7.);
+        let grid = vec![
+            GmpePoint::new_pga(143.1, 52., 20.),
+            GmpePoint::new(143.1, 52., 0.5, GmpePointKind::AriasIntensity),
+        ];
+        let mmi = GmiceMethod::Worden2012.to_mmi(grid, &eq);
+        assert_eq!(mmi.len(), 1);
+        let expected = Worden2012::new()
+            .with_event(&eq)
+            .apply(GmpePoint::new_pga(143.1, 52., 20.));
+        assert_eq!(mmi[0].value, expected.unwrap().value);
+    }
+
+    #[test]
+    fn test_inverse_conversion_round_trip() {
+        let eq = Earthquake::new_mw(143., 52., 10., 7.);
+        let converters: [Box<dyn Gmice>; 2] = [
+            Box::new(Worden2012::new().with_event(&eq)),
+            Box::new(AtkinsonKaka2007::new().with_event(&eq)),
+        ];
+        for gmice in converters {
+            for (kind, value) in [(GmpePointKind::Pga, 0.5), (GmpePointKind::Pgv, 30.)] {
+                let mmi = gmice.mmi(kind, value, 40.).unwrap();
+                let back = gmice.ground_motion(kind, mmi, 40.).unwrap();
+                assert!((back - value).abs() / value < 1e-9, "{gmice:?} {kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_transforms() {
+        let eq = Earthquake::new_mw(143., 52., 10., 7.);
+        let plain = Worden2012::new()
+            .apply(GmpePoint::new_pga(143.5, 52., 20.))
+            .unwrap();
+        let with_terms = Worden2012::new()
+            .with_event(&eq)
+            .apply(GmpePoint::new_pga(143.5, 52., 20.))
+            .unwrap();
+        assert_eq!(with_terms.kind, GmpePointKind::Mmi);
+        assert!(with_terms.value != plain.value);
+        assert!((1. ..=10.).contains(&with_terms.value));
+
+        let inverse = FromIntensity::new(AtkinsonKaka2007::new(), GmpePointKind::Pga);
+        assert!(inverse.apply(GmpePoint::new_pga(143.5, 52., 20.)).is_none());
+        let pga = inverse.apply(plain).unwrap();
+        assert_eq!(pga.kind, GmpePointKind::Pga);
+    }
+}
@@ -2,9 +2,14 @@
 //!
 //! This module provides fundamental data structures and traits for representing
 //! seismic input points, earthquake parameters, and ground motion model outputs.
-//! It also defines the core trait for implementing specific GMPE models.
+//! It also defines the core trait for implementing specific GMPE models, and a generator of
+//! regular site grids ([`generate_grid`]).
 
+use crate::distance::{DistanceMethod, DistanceMetric, SourceDistances};
+use crate::rupture::{MultiSegmentRupture, Rupture};
 use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug};
+use std::str::FromStr;
 
 /// Input point definition for which GMPE will be calculated.
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,19 +23,125 @@ pub struct Vs30Point {
     /// Depth (in meters) to the subsurface layer where Vs reaches 1400 m/s at the site.
     #[serde(default)]
     pub dl: Option<f64>,
-    /// Binary variable (0 or 1) indicating the site's position relative to the volcanic front
-    /// (specific to Japan).
+    /// Signed horizontal distance (km) from the site to the volcanic front, positive on the
+    /// back-arc side and negative on the fore-arc side.
+    ///
+    /// Used by the MF2013 anomalous seismic intensity correction and the BC Hydro forearc/backarc
+    /// term. Older site files held a binary flag instead (`1` for sites oceanward of the front,
+    /// i.e. fore-arc), which cannot be converted to a distance; the site readers take such
+    /// columns only when named explicitly, see [`Vs30Columns`](crate::readers::Vs30Columns).
+    #[serde(default)]
+    pub xvf: Option<f64>,
+    /// Depth (in meters) to the subsurface layer where Vs reaches 1.0 km/s at the site.
+    ///
+    /// Used by the basin terms of NGA-West2 models; estimated from Vs30 when not provided.
     #[serde(default)]
-    pub xvf: Option<u8>,
+    pub z1p0: Option<f64>,
+    /// Depth (in kilometers) to the subsurface layer where Vs reaches 2.5 km/s at the site.
+    ///
+    /// Used by the basin terms of NGA-West2 models; estimated from Vs30 when not provided.
+    #[serde(default)]
+    pub z2p5: Option<f64>,
 }
 
 /// Magnitude type used in GMPE calculations.
-#[derive(Debug)]
+///
+/// Models are calibrated to Mw; other scales are converted with the relations of the
+/// [`magnitude`](crate::magnitude) module.
+///
+/// Magnitude types are serialized by their conventional symbol, e.g. `Mw` or `mb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum Magnitude {
     /// Moment magnitude (Mw)
     Mw,
     /// Local magnitude (Ml)
     Ml,
+    /// Surface-wave magnitude (Ms)
+    Ms,
+    /// Body-wave magnitude (mb)
+    Mb,
+    /// Japan Meteorological Agency magnitude (Mjma)
+    Mjma,
+}
+
+impl fmt::Display for Magnitude {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mw => write!(f, "Mw"),
+            Self::Ml => write!(f, "Ml"),
+            Self::Ms => write!(f, "Ms"),
+            Self::Mb => write!(f, "mb"),
+            Self::Mjma => write!(f, "Mjma"),
+        }
+    }
+}
+
+impl FromStr for Magnitude {
+    type Err = String;
+
+    /// Parse a magnitude type from its symbol, ignoring case (`Mj` is accepted for `Mjma`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "mw" => Ok(Self::Mw),
+            "ml" => Ok(Self::Ml),
+            "ms" => Ok(Self::Ms),
+            "mb" => Ok(Self::Mb),
+            "mjma" | "mj" => Ok(Self::Mjma),
+            _ => Err(format!("Unknown magnitude type `{s}`")),
+        }
+    }
+}
+
+impl From<Magnitude> for String {
+    fn from(kind: Magnitude) -> Self {
+        kind.to_string()
+    }
+}
+
+impl TryFrom<String> for Magnitude {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Tectonic type of an earthquake, used by models with separate coefficient sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TectonicType {
+    /// Shallow crustal event
+    Crustal,
+    /// Subduction interface (interplate) event
+    Interplate,
+    /// Intraslab (intraplate) event
+    Intraplate,
+}
+
+impl fmt::Display for TectonicType {
+    /// Name of the tectonic type as used in configuration keys, e.g. `crustal`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Crustal => write!(f, "crustal"),
+            Self::Interplate => write!(f, "interplate"),
+            Self::Intraplate => write!(f, "intraplate"),
+        }
+    }
+}
+
+impl FromStr for TectonicType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "crustal" => Ok(Self::Crustal),
+            "interplate" | "interface" => Ok(Self::Interplate),
+            "intraplate" | "intraslab" | "inslab" => Ok(Self::Intraplate),
+            _ => Err(format!(
+                "Unknown tectonic type `{s}`, expected `crustal`, `interplate` or `intraplate`"
+            )),
+        }
+    }
 }
 
 /// Represents an earthquake event with its source parameters.
@@ -46,17 +157,108 @@ pub struct Earthquake {
     pub magnitude: f64,
     /// Type of magnitude scale (Mw, Ml, etc.)
     pub magnitude_kind: Magnitude,
+    /// Optional finite rupture geometry. `None` for point sources.
+    pub rupture: Option<Rupture>,
+    /// Optional multi-segment rupture, e.g. a published finite-fault solution. Takes precedence
+    /// over `rupture` for the Rrup and Rjb distances and the depth to the top of rupture.
+    pub segments: Option<MultiSegmentRupture>,
 }
 
 /// Available GMPE output types.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+///
+/// Output types are serialized by their name, e.g. `Pga`. Spectral accelerations carry the
+/// oscillator period in seconds, e.g. `Psa(0.3)`, so PSA grids of different periods remain
+/// distinguishable in output files.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub enum GmpePointKind {
     /// Peak Ground Acceleration, expressed as a percentage of gravity (%g)
     Pga,
     /// Peak Spectral Acceleration, expressed as a percentage of gravity (%g)
-    Psa,
+    Psa {
+        /// Oscillator period (s)
+        period: f64,
+    },
     /// Peak Ground Velocity, expressed in cm/s
     Pgv,
+    /// Peak Ground Displacement, expressed in cm
+    Pgd,
+    /// Macroseismic intensity on the Modified Mercalli scale (MMI)
+    Mmi,
+    /// Arias intensity, expressed in m/s
+    AriasIntensity,
+    /// Significant duration between 5% and 75% of the Arias intensity, expressed in seconds
+    Ds575,
+    /// Significant duration between 5% and 95% of the Arias intensity, expressed in seconds
+    Ds595,
+}
+
+impl GmpePointKind {
+    /// Oscillator period (s) of spectral accelerations, `None` for other output types.
+    pub fn period(&self) -> Option<f64> {
+        match self {
+            Self::Psa { period } => Some(*period),
+            _ => None,
+        }
+    }
+
+    /// Whether the type of motion is log-normally distributed around the model median.
+    ///
+    /// Intensities are already on a logarithmic scale and are normally distributed.
+    pub fn is_log_normal(&self) -> bool {
+        !matches!(self, Self::Mmi)
+    }
+}
+
+impl fmt::Display for GmpePointKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pga => write!(f, "Pga"),
+            Self::Psa { period } => write!(f, "Psa({period})"),
+            Self::Pgv => write!(f, "Pgv"),
+            Self::Pgd => write!(f, "Pgd"),
+            Self::Mmi => write!(f, "Mmi"),
+            Self::AriasIntensity => write!(f, "AriasIntensity"),
+            Self::Ds575 => write!(f, "Ds575"),
+            Self::Ds595 => write!(f, "Ds595"),
+        }
+    }
+}
+
+impl FromStr for GmpePointKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Pga" => Ok(Self::Pga),
+            "Pgv" => Ok(Self::Pgv),
+            "Pgd" => Ok(Self::Pgd),
+            "Mmi" => Ok(Self::Mmi),
+            "AriasIntensity" => Ok(Self::AriasIntensity),
+            "Ds575" => Ok(Self::Ds575),
+            "Ds595" => Ok(Self::Ds595),
+            _ => s
+                .strip_prefix("Psa(")
+                .and_then(|rest| rest.strip_suffix(')'))
+                .and_then(|period| period.parse::<f64>().ok())
+                .map(|period| Self::Psa { period })
+                .ok_or_else(|| format!("Unknown ground motion type `{s}`")),
+        }
+    }
+}
+
+impl From<GmpePointKind> for String {
+    fn from(kind: GmpePointKind) -> Self {
+        kind.to_string()
+    }
+}
+
+impl TryFrom<String> for GmpePointKind {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
 }
 
 /// Struct representing a point with a computed GMPE value.
@@ -70,24 +272,135 @@ pub struct GmpePoint {
     pub value: f64,
     /// Type of GMPE output value.
     pub kind: GmpePointKind,
+    /// Total standard deviation of the prediction in natural-log units, or in intensity units
+    /// for [`GmpePointKind::Mmi`]. `None` if the model provides no uncertainty.
+    #[serde(default)]
+    pub sigma: Option<f64>,
 }
 
 /// Trait representing a Ground Motion Prediction Equation (GMPE).
 ///
 /// Implementors of this trait can compute ground motion values at a site
 /// for a given earthquake scenario.
-pub trait GroundMotionModeling {
+///
+/// Predictions carry the median value and the total standard deviation of the model
+/// ([`GmpePoint::sigma`]); uncertainty calculations such as hazard integration, simulation and
+/// conditioning use the log-space mean of [`GmpePoint::log_mean`] with this sigma.
+pub trait GroundMotionModeling: Debug {
+    /// Compute ground motion value for a given input point, earthquake and epicentral distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Input site point parameters.
+    /// * `eq` - Earthquake source parameters.
+    /// * `epicentral_distance` - Horizontal distance from the epicenter to the site (km).
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` containing the computed median value, its location and the total standard
+    /// deviation of the model, if available.
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint;
+
+    /// Distance metrics used by the model, computed for every site by
+    /// [`GroundMotionModeling::calc_from_point_with`].
+    ///
+    /// Defaults to the epicentral distance only, for models deriving their distance from it.
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Repi]
+    }
+
+    /// Compute ground motion value for a given input point, earthquake and source-to-site
+    /// distances.
+    ///
+    /// Metrics the model did not request, and finite-fault metrics of point sources, are `None`;
+    /// models then fall back to their point-source approximations. Defaults to
+    /// [`GroundMotionModeling::calc_from_distance`] with the epicentral distance. Models
+    /// overriding it usually implement [`GroundMotionModeling::calc_from_distance`] as
+    /// `calc_from_distances` with [`SourceDistances::new`].
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Input site point parameters.
+    /// * `eq` - Earthquake source parameters.
+    /// * `distances` - Source-to-site distances, see [`SourceDistances::compute`].
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        self.calc_from_distance(point, eq, distances.repi)
+    }
+
     /// Compute ground motion value for a given input point and earthquake.
     ///
+    /// The epicentral distance is computed with the Haversine formula.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Input site point parameters.
+    /// * `eq` - Earthquake source parameters.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` containing the computed value and its location.
+    fn calc_from_point(&self, point: &Vs30Point, eq: &Earthquake) -> GmpePoint {
+        self.calc_from_point_with(point, eq, DistanceMethod::Haversine)
+    }
+
+    /// Compute ground motion value for a given input point and earthquake, using the given
+    /// epicentral distance calculation method.
+    ///
+    /// Only the [`GroundMotionModeling::distance_metrics`] of the model are computed.
+    ///
     /// # Arguments
     ///
     /// * `point` - Input site point parameters.
     /// * `eq` - Earthquake source parameters.
+    /// * `method` - Method used to compute the epicentral distance.
     ///
     /// # Returns
     ///
     /// A `GmpePoint` containing the computed value and its location.
-    fn calc_from_point(&self, point: &Vs30Point, eq: &Earthquake) -> GmpePoint;
+    fn calc_from_point_with(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        method: DistanceMethod,
+    ) -> GmpePoint {
+        let distances =
+            SourceDistances::compute(&self.distance_metrics(), eq, point.lon, point.lat, method);
+        self.calc_from_distances(point, eq, &distances)
+    }
+}
+
+impl<T: GroundMotionModeling + ?Sized> GroundMotionModeling for &T {
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        (**self).calc_from_distance(point, eq, epicentral_distance)
+    }
+
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        (**self).distance_metrics()
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        (**self).calc_from_distances(point, eq, distances)
+    }
 }
 
 impl Vs30Point {
@@ -99,25 +412,57 @@ impl Vs30Point {
     /// * `lat` - Latitude in decimal degrees.
     /// * `vs30` - Average Vs in the top 30 meters.
     /// * `dl` - Depth to Vs=1400 m/s layer (optional).
-    /// * `xvf` - Binary volcanic front position indicator (optional).
+    /// * `xvf` - Signed distance to the volcanic front in km (optional, see [`Vs30Point::xvf`]).
     ///
     /// # Example
     ///
     /// ```
     /// use ground_motion_lib::gmm::Vs30Point;
-    /// let vs30_point = Vs30Point::new(142.523, 52.913, 300., Some(250.), Some(1));
+    /// let vs30_point = Vs30Point::new(142.523, 52.913, 300., Some(250.), Some(20.));
     /// println!("Point is {vs30_point:?}");
     /// ```
-    pub fn new(lon: f64, lat: f64, vs30: f64, dl: Option<f64>, xvf: Option<u8>) -> Self {
+    pub fn new(lon: f64, lat: f64, vs30: f64, dl: Option<f64>, xvf: Option<f64>) -> Self {
         Self {
             lon,
             lat,
             vs30,
             dl,
             xvf,
+            z1p0: None,
+            z2p5: None,
         }
     }
 
+    /// Set the signed horizontal distance (km) from the site to the volcanic front, positive on
+    /// the back-arc side.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::Vs30Point;
+    /// let vs30_point = Vs30Point::new(142.523, 52.913, 300., Some(250.), None).with_xvf(-75.);
+    /// assert_eq!(vs30_point.xvf, Some(-75.));
+    /// ```
+    pub fn with_xvf(mut self, xvf: f64) -> Self {
+        self.xvf = Some(xvf);
+        self
+    }
+
+    /// Set the basin depths: Z1.0 (m) and Z2.5 (km).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::Vs30Point;
+    /// let vs30_point = Vs30Point::new(142.523, 52.913, 300., None, None).with_basin_depths(450., 2.1);
+    /// assert_eq!(vs30_point.z2p5, Some(2.1));
+    /// ```
+    pub fn with_basin_depths(mut self, z1p0: f64, z2p5: f64) -> Self {
+        self.z1p0 = Some(z1p0);
+        self.z2p5 = Some(z2p5);
+        self
+    }
+
     /// Calculate ground motion value for this point and given earthquake, using a GMPE.
     ///
     /// # Arguments
@@ -128,7 +473,7 @@ impl Vs30Point {
     /// # Returns
     ///
     /// A `GmpePoint` with the computed value.
-    pub fn get_gm<T: GroundMotionModeling>(&self, gmpe: &T, eq: &Earthquake) -> GmpePoint {
+    pub fn get_gm<T: GroundMotionModeling + ?Sized>(&self, gmpe: &T, eq: &Earthquake) -> GmpePoint {
         gmpe.calc_from_point(self, eq)
     }
 }
@@ -150,15 +495,45 @@ impl Earthquake {
             depth,
             magnitude,
             magnitude_kind,
+            rupture: None,
+            segments: None,
         }
     }
 
+    /// Attach a finite rupture geometry to the earthquake.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::Earthquake;
+    /// use ground_motion_lib::rupture::Rupture;
+    ///
+    /// let eq = Earthquake::new_mw(142.83, 52.63, 11.0, 7.1)
+    ///     .with_rupture(Rupture::new(15., 80., 60., 20.).with_ztor(0.));
+    /// assert!(eq.rupture.is_some());
+    /// ```
+    pub fn with_rupture(mut self, rupture: Rupture) -> Self {
+        self.rupture = Some(rupture);
+        self
+    }
+
+    /// Attach a multi-segment rupture to the earthquake, see
+    /// [`FiniteFaultSolution::to_earthquake`](crate::rupture::FiniteFaultSolution::to_earthquake).
+    pub fn with_segments(mut self, segments: MultiSegmentRupture) -> Self {
+        self.segments = Some(segments);
+        self
+    }
+
     /// Convenience constructor for Local magnitude (Ml).
+    ///
+    /// Models convert the magnitude to Mw, see [`Earthquake::mw`].
     pub fn new_ml(lon: f64, lat: f64, depth: f64, magnitude: f64) -> Self {
         Self::new(lon, lat, depth, magnitude, Magnitude::Ml)
     }
 
     /// Convenience constructor for Moment magnitude (Mw).
+    ///
+    /// Events of other scales are created with [`Earthquake::new`].
     pub fn new_mw(lon: f64, lat: f64, depth: f64, magnitude: f64) -> Self {
         Self::new(lon, lat, depth, magnitude, Magnitude::Mw)
     }
@@ -172,6 +547,55 @@ impl GmpePoint {
             lat,
             value,
             kind,
+            sigma: None,
+        }
+    }
+
+    /// Attach the total standard deviation of the prediction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::GmpePoint;
+    /// let point = GmpePoint::new_pga(142.5, 50.0, 12.0).with_sigma(0.7);
+    /// assert_eq!(point.sigma, Some(0.7));
+    /// ```
+    pub fn with_sigma(mut self, sigma: f64) -> Self {
+        self.sigma = Some(sigma);
+        self
+    }
+
+    /// Shift the prediction by `epsilon` standard deviations from the median.
+    ///
+    /// Log-normal types of motion become `value · exp(ε·σ)` and intensities `value + ε·σ`.
+    /// Returns `None` if the point carries no standard deviation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ground_motion_lib::gmm::GmpePoint;
+    /// let point = GmpePoint::new_pga(142.5, 50.0, 10.0).with_sigma(0.6);
+    /// let shifted = point.at_epsilon(1.0).unwrap();
+    /// assert!((shifted.value - 10.0 * 0.6_f64.exp()).abs() < 1e-12);
+    /// ```
+    pub fn at_epsilon(mut self, epsilon: f64) -> Option<Self> {
+        let shift = epsilon * self.sigma?;
+        if self.kind.is_log_normal() {
+            self.value *= shift.exp();
+        } else {
+            self.value += shift;
+        }
+        Some(self)
+    }
+
+    /// Mean of the prediction in the space where it is normally distributed: `ln(value)`, or the
+    /// value itself for intensities. Together with [`GmpePoint::sigma`] it describes the
+    /// predicted distribution.
+    pub fn log_mean(&self) -> f64 {
+        if self.kind.is_log_normal() {
+            self.value.ln()
+        } else {
+            self.value
         }
     }
 
@@ -185,8 +609,112 @@ impl GmpePoint {
         Self::new(lon, lat, value, GmpePointKind::Pgv)
     }
 
-    /// Create a new Peak Spectral Acceleration (PSA) point.
-    pub fn new_psa(lon: f64, lat: f64, value: f64) -> Self {
-        Self::new(lon, lat, value, GmpePointKind::Psa)
+    /// Create a new Peak Spectral Acceleration (PSA) point for an oscillator period (s).
+    pub fn new_psa(lon: f64, lat: f64, value: f64, period: f64) -> Self {
+        Self::new(lon, lat, value, GmpePointKind::Psa { period })
     }
 }
+
+/// Ground motion observed at a seismic station, or an intensity report.
+///
+/// Values use the units of [`GmpePointKind`] (%g, cm/s, MMI), so observations can be compared
+/// with predictions directly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StationPoint {
+    /// Station identifier, e.g. `NET.STA`.
+    pub id: String,
+    /// Longitude in decimal degrees.
+    pub lon: f64,
+    /// Latitude in decimal degrees.
+    pub lat: f64,
+    /// Observed ground motion value.
+    pub value: f64,
+    /// Type of the observed motion.
+    pub kind: GmpePointKind,
+    /// Vs30 (m/s) of the station site, if known.
+    pub vs30: Option<f64>,
+}
+
+impl StationPoint {
+    /// Create a new observation without site information.
+    pub fn new(id: &str, lon: f64, lat: f64, value: f64, kind: GmpePointKind) -> Self {
+        Self {
+            id: id.to_string(),
+            lon,
+            lat,
+            value,
+            kind,
+            vs30: None,
+        }
+    }
+
+    /// Observation as a [`GmpePoint`], e.g. for
+    /// [`MvnConditioning`](crate::conditioning::MvnConditioning).
+    pub fn to_gmpe_point(&self) -> GmpePoint {
+        GmpePoint::new(self.lon, self.lat, self.value, self.kind)
+    }
+
+    /// Site of the station for model predictions, using `default_vs30` (m/s) when the station
+    /// Vs30 is unknown.
+    pub fn site(&self, default_vs30: f64) -> Vs30Point {
+        Vs30Point::new(
+            self.lon,
+            self.lat,
+            self.vs30.unwrap_or(default_vs30),
+            None,
+            None,
+        )
+    }
+}
+
+/// Generate a regular grid of sites with a uniform Vs30.
+///
+/// Sites are placed at multiples of `spacing_deg` from the south-western corner of the bounding
+/// box, including its edges, row by row from south to north. Such grids allow scenario maps at a
+/// given resolution without a Vs30 file.
+///
+/// # Arguments
+///
+/// * `bbox` - Bounding box `[min_lon, min_lat, max_lon, max_lat]` in decimal degrees.
+/// * `spacing_deg` - Grid spacing in decimal degrees.
+/// * `default_vs30` - Vs30 of all sites (m/s).
+///
+/// # Example
+///
+/// ```
+/// use ground_motion_lib::gmm::generate_grid;
+///
+/// let grid = generate_grid([142., 50., 143., 50.5], 0.25, 760.);
+/// assert_eq!(grid.len(), 5 * 3);
+/// assert_eq!((grid[14].lon, grid[14].lat), (143., 50.5));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `spacing_deg` is not positive.
+pub fn generate_grid(bbox: [f64; 4], spacing_deg: f64, default_vs30: f64) -> Vec<Vs30Point> {
+    assert!(spacing_deg > 0., "Grid spacing must be positive");
+    let [min_lon, min_lat, max_lon, max_lat] = bbox;
+    // Tolerate rounding errors of spacings that evenly divide the box
+    let n_nodes = |min: f64, max: f64| {
+        if max < min {
+            0
+        } else {
+            ((max - min) / spacing_deg + 1e-9).floor() as usize + 1
+        }
+    };
+    let (n_lon, n_lat) = (n_nodes(min_lon, max_lon), n_nodes(min_lat, max_lat));
+    (0..n_lat)
+        .flat_map(|j| {
+            (0..n_lon).map(move |i| {
+                Vs30Point::new(
+                    min_lon + i as f64 * spacing_deg,
+                    min_lat + j as f64 * spacing_deg,
+                    default_vs30,
+                    None,
+                    None,
+                )
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,398 @@
+//! Probabilistic seismic hazard curves.
+//!
+//! A hazard curve gives, for a site, the annual rate and probability with which a ground motion
+//! level is exceeded. It integrates the predictions of a ground motion model over all
+//! earthquakes that can affect the site, weighted with their annual rates of occurrence
+//! (Cornell, 1968; McGuire, 1976):
+//!
+//! ```text
+//! λ(Y > y) = Σ_s ν_s · P(Y > y | M_s, R_s)
+//! P(Y > y | M, R) = 1 − Φ((ln y − ln μ(M, R)) / σ)
+//! PoE(y) = 1 − exp(−λ(Y > y))
+//! ```
+//!
+//! where `ν_s` is the annual rate of the source `s`, `μ` and `σ` are the median and the total
+//! standard deviation of the model, `Φ` is the standard normal CDF and `PoE` is the annual
+//! probability of exceedance under the Poisson assumption. Intensities are treated linearly
+//! instead of in logarithms. Models without a standard deviation contribute their median only.
+//!
+//! The ground motion distribution can be truncated at `n` standard deviations, see
+//! [`calc_hazard_curves_with`]. Curves are computed in parallel over the sites with Rayon.
+//!
+//! A hazard map gives the ground motion level with a given return period `T_R` at every site,
+//! e.g. the 475-year PGA (10% probability of exceedance in 50 years). It is obtained by
+//! inverting the hazard curves at the annual rate `1 / T_R`, interpolating log-linearly in both
+//! the rates and the levels ([`calc_hazard_map`], [`hazard_map`]). The map is a grid of
+//! [`GmpePoint`]s and can be written with the [`writers`](crate::writers).
+//!
+//! Current simplifications:
+//!
+//! - Sources are points with a single magnitude and an annual rate ([`PointSource`]).
+//! - Epicentral distances are computed with the Haversine formula.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::configs::get_lib_config;
+//! use ground_motion_lib::gmm::Vs30Point;
+//! use ground_motion_lib::hazard::{PointSource, calc_hazard_curves};
+//!
+//! let sites = vec![Vs30Point::new(142.5, 50.0, 400., None, None)];
+//! let sources = vec![
+//!     PointSource::new(142.4, 50.0, 10.0, 5.5, 0.1),
+//!     PointSource::new(142.8, 50.3, 15.0, 7.0, 0.005),
+//! ];
+//! let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+//! let levels = [1., 5., 10., 50.];
+//!
+//! let curves = calc_hazard_curves(&sites, &sources, gmpe, &levels).unwrap();
+//! let poe = &curves[0].poe;
+//! assert!(poe[0] > poe[3]);
+//! ```
+
+use crate::auxilary::normal_cdf;
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+/// Seismic point source with a single magnitude and an annual rate of occurrence.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PointSource {
+    /// Longitude of the epicenter in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the epicenter in decimal degrees.
+    pub lat: f64,
+    /// Hypocentral depth (km).
+    pub depth: f64,
+    /// Moment magnitude (Mw).
+    pub magnitude: f64,
+    /// Annual rate of occurrence of the earthquake.
+    pub rate: f64,
+}
+
+impl PointSource {
+    /// Create a new point source.
+    pub fn new(lon: f64, lat: f64, depth: f64, magnitude: f64, rate: f64) -> Self {
+        Self {
+            lon,
+            lat,
+            depth,
+            magnitude,
+            rate,
+        }
+    }
+
+    /// The earthquake of the source.
+    pub fn to_earthquake(&self) -> Earthquake {
+        Earthquake::new_mw(self.lon, self.lat, self.depth, self.magnitude)
+    }
+}
+
+/// Hazard curve of a site.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HazardCurve {
+    /// Longitude of the site in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the site in decimal degrees.
+    pub lat: f64,
+    /// Type of the ground motion.
+    pub kind: GmpePointKind,
+    /// Ground motion levels, in the units of the type of motion.
+    pub levels: Vec<f64>,
+    /// Annual rates of exceedance of the levels.
+    pub rates: Vec<f64>,
+    /// Annual probabilities of exceedance of the levels.
+    pub poe: Vec<f64>,
+}
+
+impl HazardCurve {
+    /// Probabilities of exceedance of the levels within an investigation time (years).
+    pub fn poe_in(&self, years: f64) -> Vec<f64> {
+        self.rates
+            .iter()
+            .map(|rate| poisson_poe(*rate, years))
+            .collect()
+    }
+
+    /// Ground motion level exceeded with an annual rate, interpolated in log-log space.
+    ///
+    /// Like in the OpenQuake engine, the level is `0.0` if the rate is not reached at the
+    /// lowest level and the highest level if the rate is exceeded at all levels; extend the
+    /// levels to cover the rates of interest.
+    pub fn level_at_rate(&self, rate: f64) -> f64 {
+        let Some(first) = self.rates.first() else {
+            return 0.;
+        };
+        if *first < rate {
+            return 0.;
+        }
+        for i in 1..self.rates.len() {
+            let (r0, r1) = (self.rates[i - 1], self.rates[i]);
+            if r1 <= rate && rate <= r0 {
+                let (l0, l1) = (self.levels[i - 1], self.levels[i]);
+                if r0 == r1 {
+                    return l0;
+                }
+                if r1 <= 0. || l0 <= 0. {
+                    return l0 + (r0 - rate) / (r0 - r1) * (l1 - l0);
+                }
+                let f = (r0 / rate).ln() / (r0 / r1).ln();
+                return l0 * (l1 / l0).powf(f);
+            }
+        }
+        self.levels[self.levels.len() - 1]
+    }
+
+    /// Ground motion level with a return period (years), see [`HazardCurve::level_at_rate`].
+    pub fn level_at_return_period(&self, return_period: f64) -> f64 {
+        self.level_at_rate(1. / return_period)
+    }
+}
+
+/// Return period (years) of a probability of exceedance within an investigation time (years).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::hazard::return_period;
+/// // 10% in 50 years
+/// assert!((return_period(0.1, 50.) - 474.56).abs() < 0.01);
+/// ```
+pub fn return_period(poe: f64, years: f64) -> f64 {
+    -years / (-poe).ln_1p()
+}
+
+/// Probability of at least one exceedance in `years` for an annual rate of exceedance.
+fn poisson_poe(rate: f64, years: f64) -> f64 {
+    -(-rate * years).exp_m1()
+}
+
+/// Calculate the hazard curves of a set of sites without truncation of the ground motion
+/// distribution.
+///
+/// See [`calc_hazard_curves_with`].
+///
+/// # Errors
+///
+/// Returns an error if no sources are given.
+pub fn calc_hazard_curves<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    sources: &[PointSource],
+    gmpe: &T,
+    levels: &[f64],
+) -> Result<Vec<HazardCurve>, Box<dyn Error>> {
+    calc_hazard_curves_with(points, sources, gmpe, levels, None)
+}
+
+/// Calculate the hazard curves of a set of sites.
+///
+/// # Arguments
+///
+/// * `points` - Site points.
+/// * `sources` - Seismic sources with their annual rates.
+/// * `gmpe` - Ground motion model providing medians and standard deviations.
+/// * `levels` - Ground motion levels of the curves, in the units of the model output.
+/// * `truncation` - Truncation level of the ground motion distribution in standard deviations,
+///   or `None` for an untruncated distribution.
+///
+/// # Errors
+///
+/// Returns an error if no sources are given or the truncation level is not positive.
+pub fn calc_hazard_curves_with<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    sources: &[PointSource],
+    gmpe: &T,
+    levels: &[f64],
+    truncation: Option<f64>,
+) -> Result<Vec<HazardCurve>, Box<dyn Error>> {
+    if sources.is_empty() {
+        return Err("No seismic sources given".into());
+    }
+    if truncation.is_some_and(|n| n <= 0.) {
+        return Err("Truncation level must be positive".into());
+    }
+    let earthquakes: Vec<Earthquake> = sources.iter().map(PointSource::to_earthquake).collect();
+    Ok(points
+        .par_iter()
+        .map(|point| {
+            let mut rates = vec![0.; levels.len()];
+            let mut kind = GmpePointKind::Pga;
+            for (source, eq) in sources.iter().zip(&earthquakes) {
+                let distance = DistanceMethod::Haversine
+                    .epicentral_distance(eq.lon, eq.lat, point.lon, point.lat);
+                let gm = gmpe.calc_from_distance(point, eq, distance);
+                kind = gm.kind;
+                let mean = gm.log_mean();
+                for (rate, level) in rates.iter_mut().zip(levels) {
+                    let x = if kind.is_log_normal() {
+                        level.ln()
+                    } else {
+                        *level
+                    };
+                    *rate += source.rate * exceedance(x, mean, gm.sigma, truncation);
+                }
+            }
+            HazardCurve {
+                lon: point.lon,
+                lat: point.lat,
+                kind,
+                levels: levels.to_vec(),
+                poe: rates.iter().map(|rate| poisson_poe(*rate, 1.)).collect(),
+                rates,
+            }
+        })
+        .collect())
+}
+
+/// Invert hazard curves into a hazard map for a return period (years).
+///
+/// See [`HazardCurve::level_at_return_period`].
+pub fn hazard_map(curves: &[HazardCurve], return_period: f64) -> Vec<GmpePoint> {
+    curves
+        .par_iter()
+        .map(|curve| {
+            let value = curve.level_at_return_period(return_period);
+            GmpePoint::new(curve.lon, curve.lat, value, curve.kind)
+        })
+        .collect()
+}
+
+/// Calculate a hazard map: the ground motion level with a return period at every site.
+///
+/// # Arguments
+///
+/// * `points` - Site points.
+/// * `sources` - Seismic sources with their annual rates.
+/// * `gmpe` - Ground motion model providing medians and standard deviations.
+/// * `levels` - Ground motion levels of the hazard curves, covering the map values.
+/// * `return_period` - Return period (years), see [`return_period`].
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::gmm::Vs30Point;
+/// use ground_motion_lib::hazard::{PointSource, calc_hazard_map, return_period};
+/// use ground_motion_lib::vectorized::log_distances;
+///
+/// let sites = vec![
+///     Vs30Point::new(142.5, 50.0, 400., None, None),
+///     Vs30Point::new(143.5, 50.5, 400., None, None),
+/// ];
+/// let sources = vec![PointSource::new(142.4, 50.0, 10.0, 6.5, 0.02)];
+/// let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+/// let levels = log_distances(0.1, 1000., 50);
+///
+/// let map = calc_hazard_map(&sites, &sources, gmpe, &levels, return_period(0.1, 50.)).unwrap();
+/// assert!(map[0].value > map[1].value);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if no sources are given.
+pub fn calc_hazard_map<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    sources: &[PointSource],
+    gmpe: &T,
+    levels: &[f64],
+    return_period: f64,
+) -> Result<Vec<GmpePoint>, Box<dyn Error>> {
+    let curves = calc_hazard_curves(points, sources, gmpe, levels)?;
+    Ok(hazard_map(&curves, return_period))
+}
+
+/// Probability that a normally distributed value with `mean` and `sigma` exceeds `x`,
+/// optionally truncated at `truncation` standard deviations.
+pub(crate) fn exceedance(x: f64, mean: f64, sigma: Option<f64>, truncation: Option<f64>) -> f64 {
+    let sigma = match sigma {
+        Some(sigma) if sigma > 0. => sigma,
+        _ => return if mean > x { 1. } else { 0. },
+    };
+    let epsilon = (x - mean) / sigma;
+    match truncation {
+        None => normal_cdf(-epsilon),
+        Some(n) => {
+            let epsilon = epsilon.clamp(-n, n);
+            (normal_cdf(n) - normal_cdf(epsilon)) / (normal_cdf(n) - normal_cdf(-n))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_lib_config;
+
+    #[test]
+    fn test_exceedance() {
+        assert!((exceedance(0., 0., Some(1.), None) - 0.5).abs() < 1e-7);
+        assert!((exceedance(1., 0., Some(1.), None) - 0.15865525).abs() < 1e-7);
+        // Truncated distribution
+        assert_eq!(exceedance(3.5, 0., Some(1.), Some(3.)), 0.);
+        assert_eq!(exceedance(-3.5, 0., Some(1.), Some(3.)), 1.);
+        assert!(exceedance(1., 0., Some(1.), Some(3.)) < exceedance(1., 0., Some(1.), None));
+        // No standard deviation: the median only
+        assert_eq!(exceedance(1., 2., None, None), 1.);
+        assert_eq!(exceedance(3., 2., None, None), 0.);
+    }
+
+    #[test]
+    fn test_single_source_curve() {
+        let site = Vs30Point::new(142.5, 50., 400., None, None);
+        let source = PointSource::new(142.4, 50., 10., 6.5, 0.01);
+        let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+        let median = gmpe.calc_from_point(&site, &source.to_earthquake());
+
+        let curves = calc_hazard_curves(&[site], &[source], gmpe, &[median.value, 1000.]).unwrap();
+        let curve = &curves[0];
+        assert_eq!(curve.kind, GmpePointKind::Pga);
+        // The median is exceeded in half of the events
+        assert!((curve.rates[0] - 0.005).abs() < 1e-9);
+        assert!((curve.poe[0] - (1. - (-curve.rates[0]).exp())).abs() < 1e-12);
+        assert!(curve.rates[1] < curve.rates[0]);
+        assert!(curve.poe_in(50.)[0] > curve.poe[0]);
+    }
+
+    #[test]
+    fn test_rates_add_up_and_errors() {
+        let sites = [Vs30Point::new(142.5, 50., 400., None, None)];
+        let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+        let a = PointSource::new(142.4, 50., 10., 6., 0.02);
+        let b = PointSource::new(142.9, 50.2, 20., 7., 0.001);
+        let levels = [5., 20.];
+        let one = calc_hazard_curves(&sites, std::slice::from_ref(&a), gmpe, &levels).unwrap();
+        let two = calc_hazard_curves(&sites, std::slice::from_ref(&b), gmpe, &levels).unwrap();
+        let both = calc_hazard_curves(&sites, &[a, b], gmpe, &levels).unwrap();
+        for i in 0..levels.len() {
+            let sum = one[0].rates[i] + two[0].rates[i];
+            assert!((both[0].rates[i] - sum).abs() < 1e-15);
+        }
+
+        assert!(calc_hazard_curves(&sites, &[], gmpe, &levels).is_err());
+        let source = [PointSource::new(142.4, 50., 10., 6., 0.02)];
+        assert!(calc_hazard_curves_with(&sites, &source, gmpe, &levels, Some(0.)).is_err());
+    }
+
+    #[test]
+    fn test_curve_inversion() {
+        let curve = HazardCurve {
+            lon: 142.5,
+            lat: 50.,
+            kind: GmpePointKind::Pga,
+            levels: vec![1., 10., 100.],
+            rates: vec![0.1, 0.01, 0.0001],
+            poe: vec![],
+        };
+        assert!((curve.level_at_rate(0.01) - 10.).abs() < 1e-12);
+        // Log-log interpolation
+        assert!((curve.level_at_rate(0.001) - 10_f64.powf(1.5)).abs() < 1e-9);
+        assert!((curve.level_at_return_period(100.) - 10.).abs() < 1e-12);
+        assert_eq!(curve.level_at_rate(0.5), 0.);
+        assert_eq!(curve.level_at_rate(1e-6), 100.);
+        let map = hazard_map(std::slice::from_ref(&curve), 1000.);
+        assert_eq!(map[0].kind, GmpePointKind::Pga);
+        assert!((map[0].value - 10_f64.powf(1.5)).abs() < 1e-9);
+    }
+}
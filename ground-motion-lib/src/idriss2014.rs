@@ -0,0 +1,183 @@
+//! Implementation of Idriss (2014) NGA-West2 Ground Motion Prediction Equations (GMPE) for
+//! active crustal regions.
+//!
+//! The model is the simplest of the NGA-West2 set and only needs magnitude, rupture distance and
+//! Vs30, which makes it usable for grids without basin depth (`dl`, Z1.0) information:
+//!
+//! ```text
+//! ln(y) = α1 + α2·M + α3·(8.5 − M)² − (β1 + β2·M)·ln(R + 10) + γ·R + φ·ln(min(Vs30, 1200))
+//! ```
+//!
+//! where `y` is PGA or 5%-damped PSA in g and `R` is the rupture distance (km). The constant and
+//! linear magnitude coefficients `α1`, `α2` differ below and above M 6.75.
+//!
+//! Coefficients are shipped as a text table ([`IDRISS2014_TABLE`]) and parsed with
+//! [`CoeffsTable`].
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism (no reverse-faulting term).
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Magnitude separating the low- and high-magnitude coefficients.
+const M_BREAK: f64 = 6.75;
+/// Vs30 above which the site term is constant (m/s).
+const VS30_CAP: f64 = 1200.;
+
+/// Idriss (2014) coefficients (PGA, PSA at 0.2, 1 and 3 s).
+pub const IDRISS2014_TABLE: &str = "
+    imt  a1_lo   a2_lo   a1_hi   a2_hi    a3      b1      b2       gamma    phi
+    pga  7.0887  0.2058  9.0138  -0.0794  0.0589  2.9935  -0.2287  -0.0006  -0.854
+    0.2  7.4749  0.2058  9.4     -0.0794  0.0589  3.05    -0.2287  -0.0008  -0.79
+    1.0  5.405   0.46    7.7     0.12     0.0589  2.6     -0.18    -0.0004  -0.95
+    3.0  2.4875  0.75    5.39    0.32     0.0589  2.2     -0.12    -0.0002  -1.0
+";
+
+/// Idriss (2014) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IDRISS2014 {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant and magnitude scaling for M ≤ 6.75
+    pub a1_lo: f64,
+    pub a2_lo: f64,
+    /// Constant and magnitude scaling for M > 6.75
+    pub a1_hi: f64,
+    pub a2_hi: f64,
+    /// Magnitude-squared scaling
+    pub a3: f64,
+    /// Magnitude-dependent geometric spreading
+    pub b1: f64,
+    pub b2: f64,
+    /// Anelastic attenuation
+    pub gamma: f64,
+    /// Site (Vs30) scaling
+    pub phi: f64,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl IDRISS2014 {
+    /// Build the model from a row of an Idriss (2014) coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            a1_lo: row.coeff("a1_lo"),
+            a2_lo: row.coeff("a2_lo"),
+            a1_hi: row.coeff("a1_hi"),
+            a2_hi: row.coeff("a2_hi"),
+            a3: row.coeff("a3"),
+            b1: row.coeff("b1"),
+            b2: row.coeff("b2"),
+            gamma: row.coeff("gamma"),
+            phi: row.coeff("phi"),
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// All models of the shipped coefficient table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(IDRISS2014_TABLE)
+            .expect("Invalid Idriss (2014) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Median `ln(y)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    pub fn ln_median(&self, magnitude: f64, rrup: f64, vs30: f64) -> f64 {
+        let m = magnitude;
+        let (a1, a2) = if m <= M_BREAK {
+            (self.a1_lo, self.a2_lo)
+        } else {
+            (self.a1_hi, self.a2_hi)
+        };
+        a1 + a2 * m + self.a3 * (8.5 - m).powi(2) - (self.b1 + self.b2 * m) * (rrup + 10.).ln()
+            + self.gamma * rrup
+            + self.phi * vs30.min(VS30_CAP).ln()
+    }
+
+    /// Total standard deviation of `ln(y)` for the given magnitude.
+    pub fn sigma(&self, magnitude: f64) -> f64 {
+        let period = self.period.clamp(0.05, 3.);
+        1.18 + 0.035 * period.ln() - 0.06 * magnitude.clamp(5., 7.5)
+    }
+}
+
+impl GroundMotionModeling for IDRISS2014 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rrup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.ztor()));
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: self.ln_median(eq.mw(), rrup, point.vs30).exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_magnitude_scaling_is_continuous() {
+        for model in IDRISS2014::from_table() {
+            let below = model.ln_median(M_BREAK, 30., 760.);
+            let above = model.ln_median(M_BREAK + 1e-9, 30., 760.);
+            assert!((below - above).abs() < 1e-6, "{}", model.period);
+        }
+    }
+
+    #[test]
+    fn test_site_term_saturates() {
+        let pga = IDRISS2014::from_table()[0];
+        assert_eq!(pga.ln_median(7., 20., 1200.), pga.ln_median(7., 20., 2000.));
+        assert!(pga.ln_median(7., 20., 300.) > pga.ln_median(7., 20., 760.));
+        let value = pga.ln_median(7., 20., 760.).exp();
+        assert!(value > 0.1 && value < 0.3, "{value}");
+    }
+}
@@ -0,0 +1,203 @@
+//! Intensity prediction equations (IPE).
+//!
+//! IPE models predict macroseismic intensity on the Modified Mercalli scale (MMI) directly from
+//! magnitude and distance. They implement [`GroundMotionModeling`] like the GMPE models and
+//! produce [`GmpePointKind::Mmi`] points. The site Vs30 is not used, so intensity maps can be
+//! computed on any longitude/latitude grid.
+//!
+//! ## Allen et al. (2012)
+//!
+//! The global active crustal IPE of Allen, Wald & Worden (2012):
+//!
+//! ```text
+//! MMI = c0 + c1·M + c2·ln(sqrt(R² + (1 + c3·exp(M − 5))²))   (R ≤ 50 km)
+//!     + c4·ln(R / 50)                                         (R > 50 km)
+//! ```
+//!
+//! Separate coefficient sets are published for the hypocentral and the rupture distance, see
+//! [`Allen2012Distance`]. For point sources the rupture distance is estimated from the
+//! epicentral distance and the depth to the top of rupture ([`Earthquake::ztor`]).
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Distance beyond which the far-field attenuation term applies (km).
+const R_FAR: f64 = 50.;
+
+/// Distance metric of the Allen et al. (2012) coefficient set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Allen2012Distance {
+    /// Hypocentral distance
+    Rhyp,
+    /// Closest distance to the rupture
+    Rrup,
+}
+
+impl Allen2012Distance {
+    /// The corresponding [`DistanceMetric`].
+    pub fn metric(self) -> DistanceMetric {
+        match self {
+            Allen2012Distance::Rhyp => DistanceMetric::Rhyp,
+            Allen2012Distance::Rrup => DistanceMetric::Rrup,
+        }
+    }
+}
+
+/// Allen et al. (2012) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Allen2012Coeffs {
+    /// Constant term
+    pub c0: f64,
+    /// Magnitude scaling
+    pub c1: f64,
+    /// Near-source attenuation and saturation
+    pub c2: f64,
+    pub c3: f64,
+    /// Far-field attenuation beyond 50 km
+    pub c4: f64,
+    /// Distance-dependent standard deviation `s1 + s2 / (1 + (R / s3)²)`
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+/// Allen et al. (2012) hypocentral distance coefficients.
+pub const ALLEN2012_RHYP: Allen2012Coeffs = Allen2012Coeffs {
+    c0: 2.085,
+    c1: 1.428,
+    c2: -1.402,
+    c3: 0.078,
+    c4: -0.209,
+    s1: 0.82,
+    s2: 0.37,
+    s3: 22.9,
+};
+
+/// Allen et al. (2012) rupture distance coefficients.
+pub const ALLEN2012_RRUP: Allen2012Coeffs = Allen2012Coeffs {
+    c0: 3.95,
+    c1: 0.913,
+    c2: -1.107,
+    c3: 0.813,
+    c4: 0.,
+    s1: 0.72,
+    s2: 0.23,
+    s3: 44.7,
+};
+
+/// Allen et al. (2012) intensity prediction equation parameters.
+#[derive(Debug)]
+pub struct ALLEN2012 {
+    /// Coefficients of the distance metric
+    pub coeffs: Allen2012Coeffs,
+    /// Distance metric of the coefficients
+    pub distance: Allen2012Distance,
+}
+
+impl ALLEN2012 {
+    /// Create a new model for the given distance metric.
+    pub fn new(distance: Allen2012Distance) -> Self {
+        let coeffs = match distance {
+            Allen2012Distance::Rhyp => ALLEN2012_RHYP,
+            Allen2012Distance::Rrup => ALLEN2012_RRUP,
+        };
+        Self { coeffs, distance }
+    }
+
+    /// Median intensity (MMI).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Hypocentral or rupture distance (km), depending on the coefficient set.
+    pub fn mmi(&self, magnitude: f64, distance: f64) -> f64 {
+        let c = &self.coeffs;
+        let saturation = 1. + c.c3 * (magnitude - 5.).exp();
+        let mut mmi = c.c0 + c.c1 * magnitude + c.c2 * distance.hypot(saturation).ln();
+        if distance > R_FAR {
+            mmi += c.c4 * (distance / R_FAR).ln();
+        }
+        mmi
+    }
+
+    /// Standard deviation of the intensity at the given distance (km).
+    pub fn sigma(&self, distance: f64) -> f64 {
+        let c = &self.coeffs;
+        c.s1 + c.s2 / (1. + (distance / c.s3).powi(2))
+    }
+}
+
+impl GroundMotionModeling for ALLEN2012 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![self.distance.metric()]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute intensity prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with the MMI and its standard deviation; the site Vs30 is not used.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance =
+            distances
+                .get(self.distance.metric())
+                .unwrap_or_else(|| match self.distance {
+                    Allen2012Distance::Rhyp => distances.repi.hypot(eq.depth),
+                    Allen2012Distance::Rrup => distances.repi.hypot(eq.ztor()),
+                });
+        GmpePoint::new(
+            point.lon,
+            point.lat,
+            self.mmi(eq.mw(), distance),
+            GmpePointKind::Mmi,
+        )
+        .with_sigma(self.sigma(distance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rhyp_reference_value() {
+        let ipe = ALLEN2012::new(Allen2012Distance::Rhyp);
+        let saturation = 1. + 0.078 * 2_f64.exp();
+        let expected = 2.085 + 1.428 * 7. - 1.402 * (400. + saturation * saturation).sqrt().ln();
+        assert!((ipe.mmi(7., 20.) - expected).abs() < 1e-12);
+        assert!((ipe.sigma(0.) - 1.19).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_far_field_term() {
+        let ipe = ALLEN2012::new(Allen2012Distance::Rhyp);
+        // Continuous at 50 km, with the slope increased by c4 beyond
+        assert!((ipe.mmi(6., 50.) - ipe.mmi(6., 50. + 1e-9)).abs() < 1e-6);
+        let decay = ipe.mmi(6., 200.) - ipe.mmi(6., 100.);
+        assert!((decay - (-1.402 - 0.209) * 2_f64.ln()).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_point_kind() {
+        let ipe = ALLEN2012::new(Allen2012Distance::Rrup);
+        let eq = Earthquake::new_mw(143., 52., 10., 7.);
+        let point = Vs30Point::new(143.2, 52.1, 400., None, None);
+        let mmi = ipe.calc_from_point(&point, &eq);
+        assert_eq!(mmi.kind, GmpePointKind::Mmi);
+        assert!(mmi.value > 5. && mmi.value < 10., "{}", mmi.value);
+    }
+}
@@ -0,0 +1,186 @@
+//! Implementation of Kanno et al. (2006) Ground Motion Prediction Equations (GMPE) for Japan.
+//!
+//! The model uses separate coefficient sets for shallow (focal depth ≤ 30 km) and deep events:
+//!
+//! ```text
+//! log10(pre) = a1·Mw + b1·X − log10(X + d1·10^(e1·Mw)) + c1     (D ≤ 30 km)
+//! log10(pre) = a2·Mw + b2·X − log10(X) + c2                     (D > 30 km)
+//! ```
+//!
+//! where `X` is the source distance (km). The prediction is corrected for site conditions
+//! with a Vs30-based term:
+//!
+//! ```text
+//! G = p · log10(Vs30) + q
+//! ```
+//!
+//! PGA is predicted in cm/s² and PGV in cm/s. For point sources the source distance is
+//! approximated by the hypocentral distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use std::f64::consts::LN_10;
+
+/// Focal depth (km) separating shallow and deep events.
+const DEPTH_BREAK: f64 = 30.;
+
+/// Kanno et al. (2006) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KANNO2006 {
+    /// Shallow event magnitude scaling coefficient
+    pub a1: f64,
+    /// Shallow event anelastic attenuation coefficient
+    pub b1: f64,
+    /// Shallow event constant term
+    pub c1: f64,
+    /// Shallow event near-source saturation coefficients
+    pub d1: f64,
+    pub e1: f64,
+    /// Shallow event standard deviation of `log10(pre)`
+    pub sigma1: f64,
+    /// Deep event magnitude scaling coefficient
+    pub a2: f64,
+    /// Deep event anelastic attenuation coefficient
+    pub b2: f64,
+    /// Deep event constant term
+    pub c2: f64,
+    /// Deep event standard deviation of `log10(pre)`
+    pub sigma2: f64,
+    /// Vs30 site correction slope
+    pub p: f64,
+    /// Vs30 site correction intercept
+    pub q: f64,
+    /// Type of motion (PGA, PGV)
+    pub motion_kind: GmpePointKind,
+}
+
+/// Kanno et al. (2006) coefficients for PGA (cm/s²).
+pub const KANNO2006_PGA: KANNO2006 = KANNO2006 {
+    a1: 0.56,
+    b1: -0.0031,
+    c1: 0.26,
+    d1: 0.0055,
+    e1: 0.5,
+    sigma1: 0.37,
+    a2: 0.41,
+    b2: -0.0039,
+    c2: 1.56,
+    sigma2: 0.4,
+    p: -0.55,
+    q: 1.35,
+    motion_kind: GmpePointKind::Pga,
+};
+
+/// Kanno et al. (2006) coefficients for PGV (cm/s).
+pub const KANNO2006_PGV: KANNO2006 = KANNO2006 {
+    a1: 0.7,
+    b1: -0.0009,
+    c1: -1.93,
+    d1: 0.0022,
+    e1: 0.5,
+    sigma1: 0.32,
+    a2: 0.55,
+    b2: -0.0032,
+    c2: -0.57,
+    sigma2: 0.36,
+    p: -0.71,
+    q: 1.77,
+    motion_kind: GmpePointKind::Pgv,
+};
+
+impl KANNO2006 {
+    /// Median `log10` of the prediction before the site correction.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Source distance (km).
+    /// * `depth` - Focal depth (km).
+    pub fn log10_pre(&self, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let x = distance.max(1.);
+        if depth <= DEPTH_BREAK {
+            self.a1 * magnitude + self.b1 * x
+                - (x + self.d1 * 10_f64.powf(self.e1 * magnitude)).log10()
+                + self.c1
+        } else {
+            self.a2 * magnitude + self.b2 * x - x.log10() + self.c2
+        }
+    }
+
+    /// Vs30 site correction term `G` (`log10` units).
+    pub fn site_correction(&self, vs30: f64) -> f64 {
+        self.p * vs30.log10() + self.q
+    }
+}
+
+impl GroundMotionModeling for KANNO2006 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let log10_y =
+            self.log10_pre(eq.mw(), distance, eq.depth) + self.site_correction(point.vs30);
+        let mut ground_motion = 10_f64.powf(log10_y);
+        // convert cm/s² to %g
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
+            ground_motion = ((ground_motion / 100.) / G_GLOBAL) * 100.;
+        }
+        let sigma = if eq.depth <= DEPTH_BREAK {
+            self.sigma1
+        } else {
+            self.sigma2
+        };
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shallow_and_deep_forms() {
+        let shallow = 0.56 * 7. - 0.0031 * 20. - (20. + 0.0055 * 10_f64.powf(3.5)).log10() + 0.26;
+        assert!((KANNO2006_PGA.log10_pre(7., 20., 10.) - shallow).abs() < 1e-12);
+        let deep = 0.41 * 7. - 0.0039 * 80. - 80_f64.log10() + 1.56;
+        assert!((KANNO2006_PGA.log10_pre(7., 80., 60.) - deep).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_soft_sites_amplify() {
+        assert!(KANNO2006_PGV.site_correction(200.) > KANNO2006_PGV.site_correction(760.));
+    }
+}
@@ -0,0 +1,333 @@
+//! KML/KMZ output of prediction results for Google Earth.
+//!
+//! Field teams without GIS software view scenario shaking in Google Earth. Values are colored
+//! with a [`ColorScale`] binned into the ten ShakeMap intensity colors, from white (lowest)
+//! through blue, green and yellow to dark red (highest):
+//!
+//! ```text
+//! t   = (value - min) / (max - min)                       (linear scale)
+//! t   = (log10 value - log10 min) / (log10 max - log10 min)   (log scale)
+//! bin = min(floor(10·t), 9)
+//! ```
+//!
+//! - [`write_gmpe_kml`] writes each point as a placemark colored by its bin.
+//! - `write_gmpe_kmz` writes a compressed KMZ archive. Results on a regular grid become a
+//!   semi-transparent ground overlay image, other point sets colored placemarks. Requires the
+//!   `kmz` crate feature.
+//!
+//! The value ranges of the bins are listed in the description of the KML document. `NaN`
+//! predictions (e.g. beyond a distance cutoff) are left out.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use ground_motion_lib::gmm::GmpePoint;
+//! use ground_motion_lib::kml::{ColorScale, write_gmpe_kml};
+//!
+//! let points = vec![
+//!     GmpePoint::new_pga(142.6, 50.1, 12.5),
+//!     GmpePoint::new_pga(142.7, 50.2, 180.),
+//! ];
+//! let scale = ColorScale::from_points(&points);
+//! write_gmpe_kml("pga.kml", &points, &scale).unwrap();
+//! ```
+
+use crate::gmm::{GmpePoint, GmpePointKind};
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// ShakeMap intensity colors (MMI I–X), from the lowest to the highest bin.
+const PALETTE: [[u8; 3]; 10] = [
+    [255, 255, 255],
+    [191, 204, 255],
+    [160, 230, 255],
+    [128, 255, 255],
+    [122, 255, 147],
+    [255, 255, 0],
+    [255, 200, 0],
+    [255, 145, 0],
+    [255, 0, 0],
+    [200, 0, 0],
+];
+
+/// Opacity of ground overlays (0–255).
+#[cfg(feature = "kmz")]
+const OVERLAY_ALPHA: u8 = 180;
+
+/// Value range mapped onto the color bins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorScale {
+    /// Value of the lower edge of the first bin.
+    pub min: f64,
+    /// Value of the upper edge of the last bin.
+    pub max: f64,
+    /// Whether the bins are spaced logarithmically.
+    pub log: bool,
+}
+
+impl ColorScale {
+    /// Create a linear scale over `[min, max]`.
+    pub fn new(min: f64, max: f64) -> Self {
+        Self {
+            min,
+            max,
+            log: false,
+        }
+    }
+
+    /// Space the bins logarithmically, e.g. for accelerations spanning orders of magnitude.
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// Scale over the range of the finite values of the points.
+    ///
+    /// The scale is logarithmic for positive ground motion values, and linear for intensities
+    /// ([`GmpePointKind::Mmi`]) or ranges including zero.
+    pub fn from_points(points: &[GmpePoint]) -> Self {
+        let (min, max) = points
+            .iter()
+            .map(|p| p.value)
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            });
+        if min > max {
+            return Self::new(0., 1.);
+        }
+        let mmi = points.first().is_some_and(|p| p.kind == GmpePointKind::Mmi);
+        Self::new(min, max).with_log(!mmi && min > 0.)
+    }
+
+    /// Color bin (0–9) of a value, or `None` for `NaN`.
+    pub fn bin(&self, value: f64) -> Option<usize> {
+        if value.is_nan() {
+            return None;
+        }
+        let t = if self.log {
+            (value.log10() - self.min.log10()) / (self.max.log10() - self.min.log10())
+        } else {
+            (value - self.min) / (self.max - self.min)
+        };
+        if t.is_nan() {
+            // Degenerate range, or a non-positive value on a log scale
+            return Some(0);
+        }
+        Some(((t * PALETTE.len() as f64).floor().max(0.) as usize).min(PALETTE.len() - 1))
+    }
+
+    /// RGB color of a value, or `None` for `NaN`.
+    pub fn color(&self, value: f64) -> Option<[u8; 3]> {
+        self.bin(value).map(|bin| PALETTE[bin])
+    }
+
+    /// Value range `(lower, upper)` of a color bin.
+    pub fn bin_range(&self, bin: usize) -> (f64, f64) {
+        let edge = |i: usize| {
+            let t = i as f64 / PALETTE.len() as f64;
+            if self.log {
+                10f64.powf(self.min.log10() + t * (self.max.log10() - self.min.log10()))
+            } else {
+                self.min + t * (self.max - self.min)
+            }
+        };
+        (edge(bin), edge(bin + 1))
+    }
+}
+
+/// KML color (`aabbggrr`) of an RGB palette entry.
+fn kml_color([r, g, b]: [u8; 3], alpha: u8) -> String {
+    format!("{alpha:02x}{b:02x}{g:02x}{r:02x}")
+}
+
+/// Write the opening of a KML document, with the bin ranges of the scale as its description.
+fn write_header<W: Write>(w: &mut W, name: &str, scale: &ColorScale) -> std::io::Result<()> {
+    writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(w, r#"<kml xmlns="http://www.opengis.net/kml/2.2">"#)?;
+    writeln!(w, "<Document>")?;
+    writeln!(w, "<name>{name}</name>")?;
+    writeln!(w, "<description><![CDATA[")?;
+    for bin in (0..PALETTE.len()).rev() {
+        let (lower, upper) = scale.bin_range(bin);
+        let [r, g, b] = PALETTE[bin];
+        writeln!(
+            w,
+            r##"<span style="background:#{r:02x}{g:02x}{b:02x}">&nbsp;&nbsp;&nbsp;</span> {lower:.3} – {upper:.3}<br/>"##
+        )?;
+    }
+    writeln!(w, "]]></description>")
+}
+
+/// Write the closing of a KML document.
+fn write_footer<W: Write>(w: &mut W) -> std::io::Result<()> {
+    writeln!(w, "</Document>")?;
+    writeln!(w, "</kml>")
+}
+
+/// Write one style per color bin and a placemark per point with a value.
+fn write_placemarks<W: Write>(
+    w: &mut W,
+    points: &[GmpePoint],
+    scale: &ColorScale,
+) -> std::io::Result<()> {
+    for (bin, rgb) in PALETTE.iter().enumerate() {
+        writeln!(
+            w,
+            "<Style id=\"c{bin}\"><IconStyle><color>{}</color><scale>0.6</scale><Icon>\
+             <href>http://maps.google.com/mapfiles/kml/shapes/shaded_dot.png</href></Icon>\
+             </IconStyle><LabelStyle><scale>0</scale></LabelStyle></Style>",
+            kml_color(*rgb, 255)
+        )?;
+    }
+    for point in points {
+        let Some(bin) = scale.bin(point.value) else {
+            continue;
+        };
+        writeln!(
+            w,
+            "<Placemark><description>{}: {:.3}</description><styleUrl>#c{bin}</styleUrl>\
+             <Point><coordinates>{},{}</coordinates></Point></Placemark>",
+            point.kind, point.value, point.lon, point.lat
+        )?;
+    }
+    Ok(())
+}
+
+/// Name of a KML document of prediction results: the type of motion of the first point.
+fn document_name(points: &[GmpePoint]) -> String {
+    points
+        .first()
+        .map_or_else(|| "GMPE".to_string(), |p| p.kind.to_string())
+}
+
+/// Writes prediction results as KML placemarks colored by value.
+///
+/// Each point with a value becomes a dot colored by its [`ColorScale`] bin, with the type of
+/// motion and the value as its description.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_gmpe_kml<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+    scale: &ColorScale,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write_header(&mut file, &document_name(points), scale)?;
+    write_placemarks(&mut file, points, scale)?;
+    write_footer(&mut file)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes prediction results as a KMZ archive.
+///
+/// Results on a regular grid (see [`RasterGrid::from_points`]) are written as a ground overlay:
+/// a semi-transparent PNG image with one pixel per grid cell colored by its [`ColorScale`] bin,
+/// and transparent nodata cells. Other point sets are written as in [`write_gmpe_kml`].
+/// Requires the `kmz` crate feature.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+///
+/// [`RasterGrid::from_points`]: crate::raster::RasterGrid::from_points
+#[cfg(feature = "kmz")]
+pub fn write_gmpe_kmz<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+    scale: &ColorScale,
+) -> Result<(), Box<dyn Error>> {
+    use crate::raster::RasterGrid;
+    use zip::write::SimpleFileOptions;
+
+    let mut zip = zip::ZipWriter::new(File::create(path)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let name = document_name(points);
+    zip.start_file("doc.kml", options)?;
+    write_header(&mut zip, &name, scale)?;
+
+    match RasterGrid::from_points(points) {
+        Ok(grid) => {
+            let (west, north) = (grid.lon0 - grid.dlon / 2., grid.lat0 + grid.dlat / 2.);
+            let east = west + grid.ncols as f64 * grid.dlon;
+            let south = north - grid.nrows as f64 * grid.dlat;
+            writeln!(
+                zip,
+                "<GroundOverlay><name>{name}</name><color>{}</color>\
+                 <Icon><href>overlay.png</href></Icon><LatLonBox><north>{north:.6}</north>\
+                 <south>{south:.6}</south><east>{east:.6}</east><west>{west:.6}</west></LatLonBox>\
+                 </GroundOverlay>",
+                kml_color([255, 255, 255], OVERLAY_ALPHA)
+            )?;
+            write_footer(&mut zip)?;
+
+            let pixels: Vec<u8> = grid
+                .values
+                .iter()
+                .flat_map(|value| match scale.color(*value) {
+                    Some([r, g, b]) => [r, g, b, 255],
+                    None => [0; 4],
+                })
+                .collect();
+            // PNG data is already compressed
+            zip.start_file(
+                "overlay.png",
+                options.compression_method(zip::CompressionMethod::Stored),
+            )?;
+            let mut encoder = png::Encoder::new(&mut zip, grid.ncols as u32, grid.nrows as u32);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.write_header()?.write_image_data(&pixels)?;
+        }
+        Err(_) => {
+            write_placemarks(&mut zip, points, scale)?;
+            write_footer(&mut zip)?;
+        }
+    }
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_scale_bins() {
+        let scale = ColorScale::new(0., 10.);
+        assert_eq!(scale.bin(0.), Some(0));
+        assert_eq!(scale.bin(5.5), Some(5));
+        assert_eq!(scale.bin(10.), Some(9));
+        assert_eq!(scale.bin(-3.), Some(0));
+        assert_eq!(scale.bin(f64::NAN), None);
+        assert_eq!(scale.color(10.), Some([200, 0, 0]));
+
+        let log = ColorScale::new(1., 1000.).with_log(true);
+        assert_eq!(log.bin(10.), Some(3));
+        let (lower, upper) = log.bin_range(9);
+        assert!((lower - 10f64.powf(2.7)).abs() < 1e-9 && (upper - 1000.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scale_from_points() {
+        let mut points = vec![
+            GmpePoint::new_pga(142., 50., 2.),
+            GmpePoint::new_pga(142.1, 50., f64::NAN),
+            GmpePoint::new_pga(142.2, 50., 200.),
+        ];
+        assert_eq!(
+            ColorScale::from_points(&points),
+            ColorScale::new(2., 200.).with_log(true)
+        );
+        for point in &mut points {
+            point.kind = GmpePointKind::Mmi;
+        }
+        assert!(!ColorScale::from_points(&points).log);
+        assert_eq!(kml_color([255, 145, 0], 255), "ff0091ff");
+    }
+}
@@ -10,19 +10,191 @@
 //!
 //! - Modular ground motion model interface via the [`GroundMotionModeling`](crate::gmm::GroundMotionModeling) trait.
 //! - Full implementation of the **Morikawa & Fujiwara (2013)** GMPE models via the [`mf2013`](crate::mf2013) module.
+//! - **Boore, Stewart, Seyhan & Atkinson (2014)** NGA-West2 active-crustal GMPE via the [`bssa2014`](crate::bssa2014) module.
+//! - **Campbell & Bozorgnia (2014)** NGA-West2 GMPE with basin depth and hanging-wall scaling via the [`cb2014`](crate::cb2014) module.
+//! - **Chiou & Youngs (2014)** NGA-West2 GMPE with nonlinear site response via the [`cy2014`](crate::cy2014) module.
+//! - **Zhao et al. (2006)** crustal, interface and intraslab GMPE for Japan via the [`zhao2006`](crate::zhao2006) module.
+//! - **Si & Midorikawa (1999)** PGA/PGV attenuation relations for Japan via the [`sm1999`](crate::sm1999) module.
+//! - **Kanno et al. (2006)** shallow/deep event GMPE for Japan via the [`kanno2006`](crate::kanno2006) module.
+//! - **Atkinson & Boore (2003)** subduction interface and in-slab GMPE via the [`ab2003`](crate::ab2003) module.
+//! - **BC Hydro / Abrahamson et al. (2016)** subduction interface and intraslab GMPE with
+//!   forearc/backarc terms via the [`bchydro2016`](crate::bchydro2016) module.
+//! - **Youngs et al. (1997)** subduction interface and intraslab GMPE for rock and soil sites via
+//!   the [`youngs1997`](crate::youngs1997) module.
+//! - **Parker et al. (2020)** NGA-Subduction GMPE with regional and basin terms via the
+//!   [`parker2020`](crate::parker2020) module.
+//! - **Akkar, Sandikkaya & Bommer (2014)** Europe/Middle East crustal GMPE with Rjb, Repi and
+//!   Rhyp variants via the [`asb2014`](crate::asb2014) module.
+//! - **Toro et al. (2002)** stable continental region GMPE via the [`toro2002`](crate::toro2002)
+//!   module, with configurations generated from text coefficient tables
+//!   ([`coeffs_table`](crate::coeffs_table)).
+//! - **Pezeshk et al. (2011)** central/eastern North America hybrid GMPE with an optional
+//!   Vs30-based site adjustment via the [`pezeshk2011`](crate::pezeshk2011) module.
+//! - **Atkinson & Boore (2006)** eastern North America GMPE with a configurable stress drop via
+//!   the [`ab2006`](crate::ab2006) module.
+//! - **McVerry et al. (2006)** New Zealand crustal and subduction GMPE with NZ site classes
+//!   derived from Vs30 via the [`mcverry2006`](crate::mcverry2006) module.
+//! - **Bradley (2013)** New Zealand crustal GMPE calibrated with the Canterbury sequence via
+//!   the [`bradley2013`](crate::bradley2013) module.
+//! - **Lin & Lee (2008)** Taiwan subduction GMPE for rock and soil sites via the
+//!   [`linlee2008`](crate::linlee2008) module.
+//! - **García et al. (2005)** central Mexico intraslab GMPE for PGA, PGV and PSA via the
+//!   [`garcia2005`](crate::garcia2005) module.
+//! - **Montalva et al. (2017)** Chilean interface and intraslab GMPE via the
+//!   [`montalva2017`](crate::montalva2017) module.
+//! - **Idriss (2014)** NGA-West2 crustal GMPE needing only magnitude, distance and Vs30 via the
+//!   [`idriss2014`](crate::idriss2014) module.
+//! - **Allen et al. (2012)** intensity prediction equations producing MMI directly via the
+//!   [`ipe`](crate::ipe) module.
+//! - **Travasarou et al. (2003)** Arias intensity prediction equation for liquefaction and
+//!   landslide screening via the [`travasarou2003`](crate::travasarou2003) module.
+//! - **Afshari & Stewart (2016)** D5-75 and D5-95 significant duration model via the
+//!   [`afshari2016`](crate::afshari2016) module.
+//! - Jayaram & Baker (2009) spatial and Baker & Jayaram (2008) cross-IMT correlation of ground
+//!   motion residuals via the [`correlation`](crate::correlation) module.
+//! - Seedable simulation of spatially correlated shaking fields and Monte Carlo scenario
+//!   ensembles with median, 16th and 84th percentile grids via the
+//!   [`simulation`](crate::simulation) module.
+//! - ShakeMap-style conditioning of prediction grids on station observations (event bias and
+//!   multivariate normal interpolation of residuals) via the [`conditioning`](crate::conditioning)
+//!   module.
+//! - Least-squares calibration of MF2013 coefficients to observed ground motions via the
+//!   [`calibration`](crate::calibration) module.
+//! - Single-site response spectra across all periods of a model family, and PSA at
+//!   arbitrary periods by log-log interpolation between configs, via the
+//!   [`spectrum`](crate::spectrum) module.
+//! - Probabilistic seismic hazard curves (annual rates and probabilities of exceedance) of
+//!   point-source rate models and return-period hazard maps via the [`hazard`](crate::hazard)
+//!   module, exportable in the OpenQuake engine CSV format.
+//! - Magnitude–distance–epsilon deaggregation of the hazard at a site via the
+//!   [`deaggregation`](crate::deaggregation) module.
+//! - Gutenberg–Richter source models of seismotectonic zones fitted to declustered
+//!   catalogs via the [`catalog`](crate::catalog) module.
+//! - Point, area and fault (slip-rate) source models read from TOML or JSON via the
+//!   [`sources`](crate::sources) module.
+//! - Logic trees of source models and GMPEs with weighted mean and fractile hazard curves via
+//!   the [`logic_tree`](crate::logic_tree) module.
+//! - Additive (log-space) and multiplicative scaling of any model, and lower/central/upper
+//!   backbone branches via [`backbone_branches`](crate::adjustment::backbone_branches).
+//! - Maximum applicable distance of any model, flagging farther predictions as `NaN` or zero
+//!   and counting them in the statistics, via the [`cutoff`](crate::cutoff) module.
+//! - Per-model distance metrics (Repi, Rhyp, Rjb, Rrup, Rx) computed to finite ruptures when
+//!   available via the [`distance`](crate::distance) and [`rupture`](crate::rupture) modules.
+//! - Weighted model ensembles combined in log space via [`EnsembleModel`](crate::ensemble::EnsembleModel)
+//!   (alias [`EnsembleGmpe`](crate::ensemble::EnsembleGmpe)).
+//! - Conversion of PGA/PGV/PSA grids to macroseismic intensity (MMI) and back with the
+//!   Worden et al. (2012) and Atkinson & Kaka (2007) relations via the [`gmice`](crate::gmice)
+//!   module.
+//! - Vertical-component estimates from horizontal grids with the Gülerce & Abrahamson (2011)
+//!   V/H ratio model via the [`vertical`](crate::vertical) module.
 //! - Parallelized ground motion calculations with Rayon for efficient batch processing ([`vectorized`](crate::vectorized)).
-//! - CSV-based readers and writers for site-specific input points and GMPE output values.
+//! - Attenuation curves of one or more models versus distance via
+//!   [`attenuation_curve`](crate::vectorized::attenuation_curve).
+//! - CSV-based readers and writers for site-specific input points and GMPE output values, and
+//!   station observation readers for ShakeMap `stationlist.json` and CSV files, and a
+//!   declustered catalog reader.
+//! - Vs30 and basin depth rasters in Arc/Info ASCII grid format read directly into site points
+//!   via [`RasterGrid`](crate::raster::RasterGrid).
+//! - GMT/netCDF (`.grd`) Vs30 grids, e.g. the USGS global Vs30 grid, read within a bounding box
+//!   (`netcdf` feature).
+//! - QuakeML and SeisComP XML (SCML) event readers taking the preferred origin and magnitude of
+//!   events, and USGS event GeoJSON reader with a ComCat fetch by event identifier
+//!   (`comcat` feature).
+//! - ShakeMap `grid.xml` reader for difference and ratio maps between scenarios and official
+//!   USGS products computed at the same grid nodes.
+//! - Site files with NEHRP (or custom) site classes instead of Vs30, converted to
+//!   representative Vs30 values via the [`site_class`](crate::site_class) module.
+//! - Conversion of Ml, Ms, mb and Mjma magnitudes to Mw with selectable published relations,
+//!   applied automatically by all models, via the [`magnitude`](crate::magnitude) module.
+//! - Automatic crustal/interface/intraslab regime selection from focal depth and Slab2-style
+//!   slab geometry, and GeoJSON zonations mapping polygons to preferred configs, resolving config
+//!   keys per event via the [`tectonic`](crate::tectonic) module.
+//! - GeoJSON `FeatureCollection` output of prediction points for web GIS clients.
+//! - Transparent gzip (`gzip` feature) and zstd (`zstd` feature) compression of delimited site
+//!   files and text outputs by the `.gz` and `.zst` extensions via the
+//!   [`compression`](crate::compression) module.
+//! - Conversions of site and prediction points to and from Apache Arrow record batches
+//!   (`arrow` feature) via the `arrow` module.
+//! - Ground motion calculation on `ndarray` site arrays and regular Vs30 grids, returning value
+//!   arrays without intermediate point vectors (`ndarray` feature) via the `ndarray` module.
+//! - Polars data frames of prediction results with epicentral distances, and site points read
+//!   from data frames (`polars` feature) via the `polars` module.
+//! - KML placemarks colored by value, and KMZ ground overlays of regular grids (`kmz` feature)
+//!   for Google Earth via the [`kml`](crate::kml) module.
+//! - Georeferenced GeoTIFF (`geotiff` feature), GMT-compatible netCDF (`netcdf` feature) and
+//!   Arc/Info ASCII grid output of results on regular site grids.
+//! - Regular site grids with a uniform Vs30 for scenario maps without a Vs30 file via
+//!   [`generate_grid`](crate::gmm::generate_grid).
+//! - Radius and bounding box pre-filters dropping sites far from the epicenter before the
+//!   computation via the [`site_filter`](crate::site_filter) module.
+//! - Vs30 site grids derived from DEM topographic slope with the Wald & Allen (2007) proxy for
+//!   active and stable regions via the [`slope_vs30`](crate::slope_vs30) module.
 //! - Config management for model presets ([`configs`](crate::configs)).
 //!
 //! ## Module Overview
 //!
+//! - [`ab2003`](crate::ab2003) — Implementation of the Atkinson & Boore (2003) subduction GMPE.
+//! - [`ab2006`](crate::ab2006) — Implementation of the Atkinson & Boore (2006) ENA GMPE.
+//! - [`adjustment`](crate::adjustment) — Host-to-target region adjustments and backbone scaling stacked on any model.
+//! - [`afshari2016`](crate::afshari2016) — Implementation of the Afshari & Stewart (2016) significant duration model.
+//! - `arrow` — Apache Arrow record batch conversions of site and prediction points (`arrow` feature).
+//! - [`asb2014`](crate::asb2014) — Implementation of the Akkar, Sandikkaya & Bommer (2014) GMPE.
 //! - [`auxilary`](crate::auxilary) — Supporting utility functions (internal use).
+//! - [`bchydro2016`](crate::bchydro2016) — Implementation of the BC Hydro (2016) subduction GMPE.
+//! - [`bradley2013`](crate::bradley2013) — Implementation of the Bradley (2013) NZ crustal GMPE.
+//! - [`bssa2014`](crate::bssa2014) — Implementation of the Boore et al. (2014) NGA-West2 GMPE.
+//! - [`calibration`](crate::calibration) — Least-squares calibration of MF2013 coefficients.
+//! - [`catalog`](crate::catalog) — Gutenberg–Richter source models from earthquake catalogs.
+//! - [`cb2014`](crate::cb2014) — Implementation of the Campbell & Bozorgnia (2014) NGA-West2 GMPE.
+//! - [`coeffs_table`](crate::coeffs_table) — Text-based coefficient tables for table-driven models.
+//! - `comcat` — Fetching of event parameters from the USGS earthquake catalog (`comcat` feature).
+//! - [`compression`](crate::compression) — Transparent gzip and zstd compression of input and output files.
+//! - [`conditioning`](crate::conditioning) — Conditioning of prediction grids on station observations.
 //! - [`configs`](crate::configs) — Predefined model configuration loader.
+//! - [`correlation`](crate::correlation) — Spatial and cross-IMT correlation models of ground motion residuals.
+//! - [`cutoff`](crate::cutoff) — Maximum applicable distance cutoff stacked on any model.
+//! - [`cy2014`](crate::cy2014) — Implementation of the Chiou & Youngs (2014) NGA-West2 GMPE.
+//! - [`deaggregation`](crate::deaggregation) — Magnitude–distance–epsilon deaggregation of seismic hazard.
+//! - [`directivity`](crate::directivity) — Rupture directivity adjustment for finite ruptures.
+//! - [`distance`](crate::distance) — Source-to-site distance calculation methods and per-model distance metrics.
+//! - [`ensemble`](crate::ensemble) — Weighted ensembles (logic-tree mixtures) of GMPE models.
+//! - [`garcia2005`](crate::garcia2005) — Implementation of the García et al. (2005) inslab GMPE.
+//! - [`gmice`](crate::gmice) — Ground-motion-to-intensity conversion equations (MMI).
 //! - [`gmm`](crate::gmm) — Core data types and GMPE trait definitions.
+//! - [`hazard`](crate::hazard) — Probabilistic seismic hazard curves and maps.
+//! - [`idriss2014`](crate::idriss2014) — Implementation of the Idriss (2014) NGA-West2 GMPE.
+//! - [`ipe`](crate::ipe) — Intensity prediction equations (MMI from magnitude and distance).
+//! - [`kml`](crate::kml) — KML/KMZ output of prediction results for Google Earth.
+//! - [`kanno2006`](crate::kanno2006) — Implementation of the Kanno et al. (2006) GMPE.
+//! - [`linlee2008`](crate::linlee2008) — Implementation of the Lin & Lee (2008) Taiwan subduction GMPE.
+//! - [`logic_tree`](crate::logic_tree) — Logic trees of source and ground motion models for hazard calculations.
+//! - [`magnitude`](crate::magnitude) — Conversion of Ml and other magnitude scales to Mw.
+//! - [`mcverry2006`](crate::mcverry2006) — Implementation of the McVerry et al. (2006) NZ GMPE.
 //! - [`mf2013`](crate::mf2013) — Implementation of the Morikawa & Fujiwara (2013) GMPE models.
-//! - [`readers`](crate::readers) — CSV-based input data loaders for site points.
+//! - [`montalva2017`](crate::montalva2017) — Implementation of the Montalva et al. (2017) Chilean GMPE.
+//! - `ndarray` — Ground motion calculation on `ndarray` site arrays and regular grids (`ndarray` feature).
+//! - [`parker2020`](crate::parker2020) — Implementation of the Parker et al. (2020) NGA-Sub GMPE.
+//! - [`pezeshk2011`](crate::pezeshk2011) — Implementation of the Pezeshk et al. (2011) CENA GMPE.
+//! - [`pipeline`](crate::pipeline) — Composable post-processing stages for result grids.
+//! - `polars` — Polars data frames of prediction results and site points (`polars` feature).
+//! - [`raster`](crate::raster) — Regular raster grids in geographic coordinates.
+//! - [`readers`](crate::readers) — Input data loaders for site points and station observations.
+//! - [`rupture`](crate::rupture) — Finite rupture geometry, Rrup/Rjb distances and hanging-wall inputs.
+//! - [`simulation`](crate::simulation) — Correlated shaking field simulation and Monte Carlo scenario ensembles.
+//! - [`site_class`](crate::site_class) — Representative Vs30 of NEHRP and custom site classes.
+//! - [`site_filter`](crate::site_filter) — Radius and bounding box pre-filters of site grids.
+//! - [`slope_vs30`](crate::slope_vs30) — Vs30 from topographic slope (Wald & Allen, 2007) and DEM grids.
+//! - [`sm1999`](crate::sm1999) — Implementation of the Si & Midorikawa (1999) attenuation relations.
+//! - [`sources`](crate::sources) — Point, area and fault source models for hazard calculations.
+//! - [`spectrum`](crate::spectrum) — Response spectra of a single site.
+//! - [`tectonic`](crate::tectonic) — Tectonic regime selection by depth, slab geometry and zonation.
+//! - [`toro2002`](crate::toro2002) — Implementation of the Toro et al. (2002) stable continental GMPE.
+//! - [`travasarou2003`](crate::travasarou2003) — Implementation of the Travasarou et al. (2003) Arias intensity model.
 //! - [`vectorized`](crate::vectorized) — Parallel ground motion calculation and statistics routines.
+//! - [`vertical`](crate::vertical) — Vertical-component estimates via V/H ratio models.
 //! - [`writers`](crate::writers) — CSV-based output writers for GMPE prediction results.
+//! - [`youngs1997`](crate::youngs1997) — Implementation of the Youngs et al. (1997) subduction GMPE.
+//! - [`zhao2006`](crate::zhao2006) — Implementation of the Zhao et al. (2006) GMPE for Japan.
 //!
 //! ## Example
 //!
@@ -32,8 +204,8 @@
 //! use ground_motion_lib::vectorized::calc_gmpe_vec;
 //!
 //! let points = vec![
-//!     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(0)),
-//!     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(1)),
+//!     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(-40.)),
+//!     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(20.)),
 //! ];
 //!
 //! let eq = Earthquake {
@@ -42,6 +214,8 @@
 //!     depth: 10.0,
 //!     magnitude: 6.5,
 //!     magnitude_kind: Magnitude::Mw,
+//!     rupture: None,
+//!     segments: None,
 //! };
 //!
 //! let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
@@ -50,6 +224,30 @@
 //! println!("{results:?}");
 //! ```
 //!
+//! ## Crate Features
+//!
+//! - `hdf5` — HDF5 output for stacked multi-scenario and multi-realization results
+//!   (requires the HDF5 C library).
+//! - `geotiff` — GeoTIFF elevation raster (DEM) reader feeding the slope-based Vs30 proxy, and
+//!   GeoTIFF writer of results on regular grids (pure Rust, via the `tiff` crate).
+//! - `netcdf` — GMT/netCDF grid (`.grd`) reader for gridded Vs30 such as the USGS global Vs30
+//!   grid, and GMT-compatible netCDF writer of results on regular grids (requires the netCDF C
+//!   library).
+//! - `arrow` — `From`/`TryFrom` conversions between point vectors and Apache Arrow record
+//!   batches (via the `arrow-array` and `arrow-schema` crates).
+//! - `ndarray` — Array-based calculation API taking sites and Vs30 grids as `ndarray` arrays
+//!   and returning value arrays, with parallel `Zip` iteration (via the `ndarray` crate).
+//! - `polars` — Polars `DataFrame` builder of prediction results and site point reader for
+//!   exploratory data analysis (via the `polars` crate).
+//! - `gzip` — Reading and writing of gzip-compressed (`.gz`) site files and text outputs (pure
+//!   Rust, via the `flate2` crate).
+//! - `zstd` — Reading and writing of Zstandard-compressed (`.zst`) site files and text outputs
+//!   (via the `zstd` crate, bundling the zstd C library).
+//! - `comcat` — HTTP client fetching event parameters from the USGS earthquake catalog (ComCat)
+//!   by event identifier (pure Rust, via the `ureq` crate).
+//! - `kmz` — KMZ archives with ground overlay images of results on regular grids (pure Rust, via
+//!   the `zip` and `png` crates).
+//!
 //! ## Parallelism
 //!
 //! This crate uses [`Rayon`](https://docs.rs/rayon/latest/rayon/) for data-parallel ground motion
@@ -62,7 +260,6 @@
 //! - Additional GMPE model families
 //! - Spatial interpolation utilities
 //! - Uncertainty propagation routines
-//! - Integrated hazard curve calculators
 //!
 //! ## License
 //!
@@ -87,10 +284,69 @@
 //! ```
 //! ---
 
+pub mod ab2003;
+pub mod ab2006;
+pub mod adjustment;
+pub mod afshari2016;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod asb2014;
 pub mod auxilary;
+pub mod bchydro2016;
+pub mod bradley2013;
+pub mod bssa2014;
+pub mod calibration;
+pub mod catalog;
+pub mod cb2014;
+pub mod coeffs_table;
+#[cfg(feature = "comcat")]
+pub mod comcat;
+pub mod compression;
+pub mod conditioning;
 pub mod configs;
+pub mod correlation;
+pub mod cutoff;
+pub mod cy2014;
+pub mod deaggregation;
+pub mod directivity;
+pub mod distance;
+pub mod ensemble;
+pub mod garcia2005;
+pub mod gmice;
 pub mod gmm;
+pub mod hazard;
+pub mod idriss2014;
+pub mod ipe;
+pub mod kanno2006;
+pub mod kml;
+pub mod linlee2008;
+pub mod logic_tree;
+pub mod magnitude;
+pub mod mcverry2006;
 pub mod mf2013;
+pub mod montalva2017;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+pub mod parker2020;
+pub mod pezeshk2011;
+pub mod pipeline;
+#[cfg(feature = "polars")]
+pub mod polars;
+pub mod raster;
 pub mod readers;
+pub mod rupture;
+pub mod simulation;
+pub mod site_class;
+pub mod site_filter;
+pub mod slope_vs30;
+pub mod sm1999;
+pub mod sources;
+pub mod spectrum;
+pub mod tectonic;
+pub mod toro2002;
+pub mod travasarou2003;
 pub mod vectorized;
+pub mod vertical;
 pub mod writers;
+pub mod youngs1997;
+pub mod zhao2006;
@@ -0,0 +1,235 @@
+//! Implementation of Lin & Lee (2008) Ground Motion Prediction Equations (GMPE) for subduction
+//! zone earthquakes in northeastern Taiwan.
+//!
+//! The model covers interface (interplate) and intraslab (intraplate) events with separate
+//! coefficient sets for rock and soil sites:
+//!
+//! ```text
+//! ln(y) = C1 + C2·M + C3·ln(R + C4·exp(C5·M)) + C6·H + C7·Zt
+//! ```
+//!
+//! where `y` is PGA or 5%-damped PSA in g, `R` is the hypocentral distance (km), `H` is the focal
+//! depth (km) and `Zt` is 0 for interface and 1 for intraslab events.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Site class of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinLee2008Site {
+    /// Rock sites
+    Rock,
+    /// Soil sites
+    Soil,
+}
+
+/// Period-dependent Lin & Lee (2008) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinLee2008Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling
+    pub c2: f64,
+    /// Geometric spreading
+    pub c3: f64,
+    /// Near-source saturation
+    pub c4: f64,
+    pub c5: f64,
+    /// Focal depth scaling
+    pub c6: f64,
+    /// Intraslab event term
+    pub c7: f64,
+    /// Total standard deviation of `ln(y)`
+    pub sigma: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn coeffs(
+    period: f64,
+    c1: f64,
+    c2: f64,
+    c3: f64,
+    c4: f64,
+    c5: f64,
+    c6: f64,
+    c7: f64,
+    sigma: f64,
+) -> LinLee2008Coeffs {
+    LinLee2008Coeffs {
+        period,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5,
+        c6,
+        c7,
+        sigma,
+    }
+}
+
+/// Lin & Lee (2008) rock coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 3 s).
+pub const LINLEE2008_ROCK: [LinLee2008Coeffs; 6] = [
+    coeffs(
+        0., -2.5, 1.205, -1.905, 0.516, 0.6325, 0.0075, 0.275, 0.5268,
+    ),
+    coeffs(0.1, -1.1, 1.1, -2.0, 0.516, 0.6325, 0.007, 0.28, 0.59),
+    coeffs(0.2, -0.95, 1.16, -2.06, 0.516, 0.6325, 0.0068, 0.26, 0.6),
+    coeffs(0.5, -3.45, 1.35, -1.88, 0.516, 0.6325, 0.0064, 0.23, 0.62),
+    coeffs(1.0, -5.4, 1.55, -1.8, 0.516, 0.6325, 0.006, 0.2, 0.64),
+    coeffs(3.0, -9., 1.85, -1.66, 0.516, 0.6325, 0.005, 0.16, 0.72),
+];
+
+/// Lin & Lee (2008) soil coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 3 s).
+pub const LINLEE2008_SOIL: [LinLee2008Coeffs; 6] = [
+    coeffs(0., -0.9, 1., -1.9, 0.9918, 0.5263, 0.004, 0.31, 0.6277),
+    coeffs(0.1, -0.2, 0.95, -1.95, 0.9918, 0.5263, 0.0038, 0.33, 0.65),
+    coeffs(0.2, 0.3, 0.97, -2., 0.9918, 0.5263, 0.0035, 0.33, 0.66),
+    coeffs(0.5, -2., 1.2, -1.85, 0.9918, 0.5263, 0.003, 0.3, 0.68),
+    coeffs(1.0, -4.2, 1.45, -1.78, 0.9918, 0.5263, 0.0028, 0.27, 0.7),
+    coeffs(3.0, -8.3, 1.8, -1.6, 0.9918, 0.5263, 0.002, 0.2, 0.75),
+];
+
+/// Lin & Lee (2008) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct LINLEE2008 {
+    /// Period-dependent coefficients
+    pub coeffs: LinLee2008Coeffs,
+    /// Site class the coefficients belong to
+    pub site: LinLee2008Site,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl LINLEE2008 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: LinLee2008Coeffs,
+        site: LinLee2008Site,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "LINLEE2008 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            site,
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Median `ln(y)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Hypocentral distance (km).
+    /// * `depth` - Focal depth (km).
+    pub fn ln_median(&self, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let c = &self.coeffs;
+        let zt = match self.tectonic_type {
+            TectonicType::Intraplate => 1.,
+            _ => 0.,
+        };
+        c.c1 + c.c2 * magnitude
+            + c.c3 * (distance + c.c4 * (c.c5 * magnitude).exp()).ln()
+            + c.c6 * depth
+            + c.c7 * zt
+    }
+}
+
+impl GroundMotionModeling for LINLEE2008 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rhyp]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// The site class is fixed by the model configuration; the site Vs30 is not used.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rhyp
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ln_y = self.ln_median(eq.mw(), distance, eq.depth);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: ln_y.exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rock_pga_reference_value() {
+        let m = LINLEE2008::new(
+            LINLEE2008_ROCK[0],
+            LinLee2008Site::Rock,
+            TectonicType::Interplate,
+            GmpePointKind::Pga,
+        );
+        let expected =
+            -2.5 + 1.205 * 7. - 1.905 * (100. + 0.516 * (0.6325 * 7_f64).exp()).ln() + 0.0075 * 30.;
+        assert!((m.ln_median(7., 100., 30.) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_intraslab_term() {
+        let interface = LINLEE2008::new(
+            LINLEE2008_SOIL[3],
+            LinLee2008Site::Soil,
+            TectonicType::Interplate,
+            GmpePointKind::Psa {
+                period: LINLEE2008_SOIL[3].period,
+            },
+        );
+        let slab = LINLEE2008::new(
+            LINLEE2008_SOIL[3],
+            LinLee2008Site::Soil,
+            TectonicType::Intraplate,
+            GmpePointKind::Psa {
+                period: LINLEE2008_SOIL[3].period,
+            },
+        );
+        let diff = slab.ln_median(6.5, 80., 60.) - interface.ln_median(6.5, 80., 60.);
+        assert!((diff - 0.3).abs() < 1e-12);
+    }
+}
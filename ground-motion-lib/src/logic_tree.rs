@@ -0,0 +1,497 @@
+//! Logic trees of source models and ground motion models for hazard calculations.
+//!
+//! Epistemic uncertainty of a hazard model is captured by alternative source models and ground
+//! motion models, each with a weight expressing the confidence in it. [`LogicTree`] is a
+//! two-level tree: every source model branch is combined with every GMPE branch, and the weight
+//! of the end branch is the product of the (normalized) branch weights. The hazard curves of all
+//! end branches are summarized by the weighted mean and weighted fractile curves:
+//!
+//! ```text
+//! w_ij = w_i · w_j,   mean(y) = Σ w_ij · λ_ij(y),   fractile_q(y) = min{λ : F(λ) ≥ q}
+//! ```
+//!
+//! where `F` is the weighted empirical distribution of the branch rates at the level `y`. Mean
+//! rates and mean probabilities of exceedance are averaged separately; fractiles of both follow
+//! from the same branch, as the probability of exceedance grows monotonically with the rate.
+//!
+//! ## Example Config (TOML)
+//!
+//! ```toml
+//! # Optional truncation of the ground motion distribution (standard deviations)
+//! truncation = 3.0
+//!
+//! [[source_model]]
+//! name = "zones"
+//! weight = 0.7
+//! # Source model file, relative to the logic tree file
+//! path = "zones.toml"
+//!
+//! [[source_model]]
+//! name = "single fault"
+//! weight = 0.3
+//!
+//! [[source_model.fault]]
+//! trace = [[142.2, 47.0], [142.4, 49.0]]
+//! dip = 60.0
+//! upper_depth = 0.0
+//! lower_depth = 20.0
+//! slip_rate = 4.0
+//! b_value = 1.0
+//! m_min = 5.0
+//! m_max = 7.5
+//!
+//! [[gmpe]]
+//! config = "config_mf2013_crustal_pga"
+//! weight = 0.5
+//!
+//! [[gmpe]]
+//! config = "config_mf2013_crustal_pga_2"
+//! weight = 0.5
+//! ```
+//!
+//! Source models are given either by a `path` to a [`SourceModel`] file or inline, with the
+//! `point`, `area` and `fault` tables of a source model. GMPEs are named by their built-in or
+//! user-provided config. A GMPE branch may carry a [`Scaling`] (`log_shift`, `factor`) and a
+//! `name`, so backbone branches of one config need no separate coefficients:
+//!
+//! ```toml
+//! [[gmpe]]
+//! name = "mf2013 lower"
+//! config = "config_mf2013_crustal_pga"
+//! log_shift = -0.4
+//! weight = 0.185
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::Vs30Point;
+//! use ground_motion_lib::logic_tree::LogicTree;
+//!
+//! let tree = LogicTree::from_toml_str(r#"
+//!     [[source_model]]
+//!     name = "low"
+//!     weight = 0.5
+//!     point = [{ lon = 142.4, lat = 50.0, depth = 10.0, magnitude = 6.5, rate = 0.01 }]
+//!
+//!     [[source_model]]
+//!     name = "high"
+//!     weight = 0.5
+//!     point = [{ lon = 142.4, lat = 50.0, depth = 10.0, magnitude = 6.5, rate = 0.03 }]
+//!
+//!     [[gmpe]]
+//!     config = "config_mf2013_crustal_pga"
+//!     weight = 1.0
+//! "#, ".").unwrap();
+//!
+//! let sites = vec![Vs30Point::new(142.5, 50.0, 400., None, None)];
+//! let hazard = tree.calc_hazard_curves(&sites, &[5., 20., 50.]).unwrap();
+//! assert_eq!(hazard.branches.len(), 2);
+//! let mean = hazard.mean();
+//! let median = hazard.fractile(0.5);
+//! assert!(mean[0].rates[0] > median[0].rates[0]);
+//! ```
+
+use crate::adjustment::{ScaledModel, Scaling};
+use crate::catalog::DEFAULT_BIN_WIDTH;
+use crate::configs::get_lib_config;
+use crate::gmm::GroundMotionModeling;
+use crate::gmm::Vs30Point;
+use crate::hazard::{HazardCurve, PointSource, calc_hazard_curves_with};
+use crate::sources::{DEFAULT_SPACING_KM, SourceModel};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Source model branch of a logic tree.
+#[derive(Debug, Clone)]
+pub struct SourceModelBranch {
+    /// Name of the branch.
+    pub name: String,
+    /// Weight of the branch.
+    pub weight: f64,
+    /// Point sources of the source model.
+    pub sources: Vec<PointSource>,
+}
+
+/// Ground motion model branch of a logic tree.
+#[derive(Debug)]
+pub struct GmpeBranch {
+    /// Name of the branch.
+    pub name: String,
+    /// Weight of the branch.
+    pub weight: f64,
+    /// Ground motion model of the branch.
+    pub model: Box<dyn GroundMotionModeling + Send + Sync>,
+}
+
+/// Two-level logic tree of source models and ground motion models.
+#[derive(Debug)]
+pub struct LogicTree {
+    /// Source model branches.
+    pub source_models: Vec<SourceModelBranch>,
+    /// Ground motion model branches.
+    pub gmpes: Vec<GmpeBranch>,
+    /// Truncation level of the ground motion distribution in standard deviations.
+    pub truncation: Option<f64>,
+}
+
+/// Hazard curves of one end branch of a logic tree.
+#[derive(Debug, Clone)]
+pub struct BranchCurves {
+    /// Name of the source model branch.
+    pub source_model: String,
+    /// Name of the ground motion model branch.
+    pub gmpe: String,
+    /// Normalized weight of the end branch.
+    pub weight: f64,
+    /// Hazard curves of the sites.
+    pub curves: Vec<HazardCurve>,
+}
+
+/// Hazard curves of all end branches of a logic tree.
+#[derive(Debug, Clone)]
+pub struct LogicTreeHazard {
+    /// End branches with their hazard curves.
+    pub branches: Vec<BranchCurves>,
+}
+
+/// Source model branch of a logic tree file.
+#[derive(Debug, Deserialize)]
+struct SourceModelEntry {
+    name: String,
+    weight: f64,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(flatten)]
+    model: SourceModel,
+}
+
+/// Ground motion model branch of a logic tree file.
+#[derive(Debug, Deserialize)]
+struct GmpeEntry {
+    #[serde(default)]
+    name: Option<String>,
+    config: String,
+    weight: f64,
+    #[serde(flatten)]
+    scaling: Scaling,
+}
+
+/// Logic tree file.
+#[derive(Debug, Deserialize)]
+struct LogicTreeFile {
+    #[serde(default)]
+    truncation: Option<f64>,
+    #[serde(default)]
+    spacing: Option<f64>,
+    #[serde(default)]
+    bin_width: Option<f64>,
+    source_model: Vec<SourceModelEntry>,
+    gmpe: Vec<GmpeEntry>,
+}
+
+impl LogicTree {
+    /// Create a logic tree from its branches.
+    pub fn new(source_models: Vec<SourceModelBranch>, gmpes: Vec<GmpeBranch>) -> Self {
+        Self {
+            source_models,
+            gmpes,
+            truncation: None,
+        }
+    }
+
+    /// Truncate the ground motion distribution at `n` standard deviations.
+    pub fn with_truncation(mut self, n: f64) -> Self {
+        self.truncation = Some(n);
+        self
+    }
+
+    /// Parse a logic tree from a TOML string.
+    ///
+    /// Source model paths are resolved relative to `base_dir`. Fault and area sources are
+    /// discretized with the `spacing` (km) and `bin_width` of the file, defaulting to
+    /// [`DEFAULT_SPACING_KM`] and [`DEFAULT_BIN_WIDTH`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the content is not a valid logic tree, a source model file cannot be
+    /// read, or a GMPE config is unknown.
+    pub fn from_toml_str<P: AsRef<Path>>(
+        content: &str,
+        base_dir: P,
+    ) -> Result<Self, Box<dyn Error>> {
+        let file: LogicTreeFile = toml::from_str(content)?;
+        let spacing = file.spacing.unwrap_or(DEFAULT_SPACING_KM);
+        let bin_width = file.bin_width.unwrap_or(DEFAULT_BIN_WIDTH);
+        let source_models = file
+            .source_model
+            .into_iter()
+            .map(|entry| {
+                let model = match &entry.path {
+                    Some(path) => SourceModel::from_file(base_dir.as_ref().join(path))?,
+                    None => entry.model,
+                };
+                Ok(SourceModelBranch {
+                    name: entry.name,
+                    weight: entry.weight,
+                    sources: model.point_sources(spacing, bin_width),
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        let gmpes = file
+            .gmpe
+            .into_iter()
+            .map(|entry| {
+                let model = get_lib_config(&entry.config)
+                    .ok_or_else(|| format!("Unknown GMPE config `{}`", entry.config))?;
+                let model: Box<dyn GroundMotionModeling + Send + Sync> =
+                    if entry.scaling == Scaling::default() {
+                        Box::new(model)
+                    } else {
+                        Box::new(ScaledModel::new(model, entry.scaling))
+                    };
+                Ok(GmpeBranch {
+                    name: entry.name.unwrap_or(entry.config),
+                    weight: entry.weight,
+                    model,
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        Ok(Self {
+            source_models,
+            gmpes,
+            truncation: file.truncation,
+        })
+    }
+
+    /// Load a logic tree from a TOML file, see [`LogicTree::from_toml_str`].
+    ///
+    /// Source model paths are resolved relative to the directory of the file.
+    pub fn from_toml_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let base_dir = path.parent().unwrap_or(Path::new("."));
+        Self::from_toml_str(&fs::read_to_string(path)?, base_dir)
+    }
+
+    /// Calculate the hazard curves of all end branches.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a level of the tree has no branches, negative weights or weights
+    /// summing to zero, or a source model branch has no sources.
+    pub fn calc_hazard_curves(
+        &self,
+        points: &[Vs30Point],
+        levels: &[f64],
+    ) -> Result<LogicTreeHazard, Box<dyn Error>> {
+        let source_weights = normalized(self.source_models.iter().map(|b| b.weight))?;
+        let gmpe_weights = normalized(self.gmpes.iter().map(|b| b.weight))?;
+        let mut branches = Vec::new();
+        for (source_model, source_weight) in self.source_models.iter().zip(&source_weights) {
+            for (gmpe, gmpe_weight) in self.gmpes.iter().zip(&gmpe_weights) {
+                let curves = calc_hazard_curves_with(
+                    points,
+                    &source_model.sources,
+                    gmpe.model.as_ref(),
+                    levels,
+                    self.truncation,
+                )?;
+                branches.push(BranchCurves {
+                    source_model: source_model.name.clone(),
+                    gmpe: gmpe.name.clone(),
+                    weight: source_weight * gmpe_weight,
+                    curves,
+                });
+            }
+        }
+        Ok(LogicTreeHazard { branches })
+    }
+}
+
+/// Branch weights divided by their sum.
+fn normalized(weights: impl Iterator<Item = f64>) -> Result<Vec<f64>, Box<dyn Error>> {
+    let weights: Vec<f64> = weights.collect();
+    let total: f64 = weights.iter().sum();
+    if weights.iter().any(|w| *w < 0.) || total <= 0. {
+        return Err("Logic tree weights must be non-negative and not sum to zero".into());
+    }
+    Ok(weights.iter().map(|w| w / total).collect())
+}
+
+impl LogicTreeHazard {
+    /// Weighted mean hazard curves.
+    pub fn mean(&self) -> Vec<HazardCurve> {
+        self.summarize(|values| values.iter().map(|(v, w)| v * w).sum())
+    }
+
+    /// Weighted fractile hazard curves for a fractile `q` in `[0, 1]`, e.g. `0.5` for the median.
+    pub fn fractile(&self, q: f64) -> Vec<HazardCurve> {
+        self.summarize(|values| weighted_fractile(values, q))
+    }
+
+    /// Combine the branch values of every site and level.
+    fn summarize(&self, combine: impl Fn(&mut [(f64, f64)]) -> f64) -> Vec<HazardCurve> {
+        let Some(first) = self.branches.first() else {
+            return Vec::new();
+        };
+        first
+            .curves
+            .iter()
+            .enumerate()
+            .map(|(site, template)| {
+                let combined = |field: fn(&HazardCurve) -> &Vec<f64>| -> Vec<f64> {
+                    (0..template.levels.len())
+                        .map(|level| {
+                            let mut values: Vec<(f64, f64)> = self
+                                .branches
+                                .iter()
+                                .map(|b| (field(&b.curves[site])[level], b.weight))
+                                .collect();
+                            combine(&mut values)
+                        })
+                        .collect()
+                };
+                HazardCurve {
+                    rates: combined(|c| &c.rates),
+                    poe: combined(|c| &c.poe),
+                    ..template.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Weighted fractile of `(value, weight)` pairs with normalized weights.
+fn weighted_fractile(values: &mut [(f64, f64)], q: f64) -> f64 {
+    values.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut cumulative = 0.;
+    for (value, weight) in values.iter() {
+        cumulative += weight;
+        if cumulative >= q - 1e-12 {
+            return *value;
+        }
+    }
+    values.last().map_or(f64::NAN, |(value, _)| *value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree() -> LogicTree {
+        let source = |rate| vec![PointSource::new(142.4, 50., 10., 6.5, rate)];
+        let gmpe = |name: &str, weight| GmpeBranch {
+            name: name.to_string(),
+            weight,
+            model: Box::new(get_lib_config(name).unwrap()),
+        };
+        LogicTree::new(
+            vec![
+                SourceModelBranch {
+                    name: "low".to_string(),
+                    weight: 3.,
+                    sources: source(0.01),
+                },
+                SourceModelBranch {
+                    name: "high".to_string(),
+                    weight: 1.,
+                    sources: source(0.04),
+                },
+            ],
+            vec![
+                gmpe("config_mf2013_crustal_pga", 0.5),
+                gmpe("config_mf2013_crustal_pga_2", 0.5),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_mean_and_fractiles() {
+        let sites = [Vs30Point::new(142.5, 50., 400., None, None)];
+        let hazard = tree().calc_hazard_curves(&sites, &[10.]).unwrap();
+        assert_eq!(hazard.branches.len(), 4);
+        let weights: f64 = hazard.branches.iter().map(|b| b.weight).sum();
+        assert!((weights - 1.).abs() < 1e-12);
+        assert!((hazard.branches[0].weight - 0.375).abs() < 1e-12);
+
+        let rates: Vec<f64> = hazard
+            .branches
+            .iter()
+            .map(|b| b.curves[0].rates[0])
+            .collect();
+        let expected: f64 = hazard
+            .branches
+            .iter()
+            .zip(&rates)
+            .map(|(b, r)| b.weight * r)
+            .sum();
+        assert!((hazard.mean()[0].rates[0] - expected).abs() < 1e-15);
+        let min = rates.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = rates.iter().copied().fold(0., f64::max);
+        assert_eq!(hazard.fractile(0.)[0].rates[0], min);
+        assert_eq!(hazard.fractile(1.)[0].rates[0], max);
+        // Three quarters of the weight are on the low-rate source model
+        assert!(hazard.fractile(0.7)[0].rates[0] < hazard.fractile(0.8)[0].rates[0]);
+    }
+
+    #[test]
+    fn test_weighted_fractile() {
+        let mut values = [(3., 0.25), (1., 0.5), (2., 0.25)];
+        assert_eq!(weighted_fractile(&mut values, 0.5), 1.);
+        assert_eq!(weighted_fractile(&mut values, 0.6), 2.);
+        assert_eq!(weighted_fractile(&mut values, 1.), 3.);
+    }
+
+    #[test]
+    fn test_backbone_gmpe_branches() {
+        let tree = LogicTree::from_toml_str(
+            r#"
+            [[source_model]]
+            name = "a"
+            weight = 1.0
+            point = [{ lon = 142.4, lat = 50.0, depth = 10.0, magnitude = 6.5, rate = 0.01 }]
+
+            [[gmpe]]
+            config = "config_mf2013_crustal_pga"
+            weight = 0.5
+
+            [[gmpe]]
+            name = "upper"
+            config = "config_mf2013_crustal_pga"
+            log_shift = 0.3
+            weight = 0.5
+            "#,
+            ".",
+        )
+        .unwrap();
+        assert_eq!(tree.gmpes[0].name, "config_mf2013_crustal_pga");
+        assert_eq!(tree.gmpes[1].name, "upper");
+        let sites = [Vs30Point::new(142.5, 50., 400., None, None)];
+        let hazard = tree.calc_hazard_curves(&sites, &[10.]).unwrap();
+        assert!(hazard.branches[1].curves[0].rates[0] > hazard.branches[0].curves[0].rates[0]);
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        let unknown = r#"
+            [[source_model]]
+            name = "a"
+            weight = 1.0
+            [[gmpe]]
+            config = "config_unknown"
+            weight = 1.0
+        "#;
+        assert!(LogicTree::from_toml_str(unknown, ".").is_err());
+        let missing = unknown.replace(
+            "weight = 1.0\n            [[gmpe]]",
+            "path = \"none.toml\"\n            weight = 1.0\n            [[gmpe]]",
+        );
+        assert!(LogicTree::from_toml_str(&missing, ".").is_err());
+
+        let mut empty = tree();
+        empty.gmpes.clear();
+        assert!(empty.calc_hazard_curves(&[], &[1.]).is_err());
+    }
+}
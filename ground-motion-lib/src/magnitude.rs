@@ -0,0 +1,314 @@
+//! Conversion of earthquake magnitudes to moment magnitude (Mw).
+//!
+//! All models of the crate are calibrated to moment magnitude. Catalogs and early reports often
+//! give a local (Ml), surface-wave (Ms), body-wave (mb) or JMA (Mjma) magnitude instead, which
+//! saturate and deviate from Mw in a region-specific way. Models read the magnitude with
+//! [`Earthquake::mw`], which converts non-Mw magnitudes with the default relations of
+//! [`MagnitudeConversion`]; other relations are selected per scale by converting the earthquake
+//! explicitly with [`Earthquake::to_mw`].
+//!
+//! Available relations (defaults first):
+//!
+//! ```text
+//! Ml (MlRelation):
+//!     Grünthal et al. (2009), Europe:
+//!         Mw = 0.53 + 0.646·Ml + 0.0376·Ml²
+//!     Goertz-Allmann et al. (2011), Switzerland:
+//!         Mw = 0.594·Ml + 0.985                   Ml ≤ 2
+//!         Mw = 1.327 + 0.253·Ml + 0.085·Ml²       2 < Ml ≤ 4
+//!         Mw = Ml − 0.3                           Ml > 4
+//!     Munafò et al. (2016), Italy:
+//!         Mw = 2/3·Ml + 1.15
+//! Ms (MsRelation):
+//!     Scordilis (2006), global:
+//!         Mw = 0.67·Ms + 2.07                     Ms < 6.2
+//!         Mw = 0.99·Ms + 0.08                     Ms ≥ 6.2
+//!     Di Giacomo et al. (2015), ISC-GEM:
+//!         Mw = exp(−0.222 + 0.233·Ms) + 2.863
+//!     Ekström & Dziewonski (1988), global:
+//!         log10 M0 = Ms + 19.24                   Ms < 5.3
+//!         log10 M0 = 30.20 − √(92.45 − 11.40·Ms)  5.3 ≤ Ms ≤ 6.8
+//!         log10 M0 = 16.14 + 1.5·Ms               Ms > 6.8
+//!         Mw = 2/3·log10 M0 − 10.7                (M0 in dyne·cm)
+//! mb (MbRelation):
+//!     Scordilis (2006), global:
+//!         Mw = 0.85·mb + 1.03
+//!     Di Giacomo et al. (2015), ISC-GEM:
+//!         Mw = exp(−4.664 + 0.859·mb) + 4.555
+//! Mjma (MjmaRelation):
+//!     Heaton et al. (1986), Japan:
+//!         Mw = Mjma
+//! ```
+//!
+//! The relations are calibrated on limited magnitude ranges (e.g. Ml < 6, mb 3.5–6.2, Mjma up to
+//! about 7.5, where the JMA scale saturates) and are applied without clamping outside of them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, Magnitude};
+//! use ground_motion_lib::magnitude::{MagnitudeConversion, MlRelation};
+//!
+//! let eq = Earthquake::new_ml(142.4, 50.0, 10.0, 5.0);
+//! // Models see the Grünthal et al. (2009) moment magnitude
+//! assert!((eq.mw() - 4.70).abs() < 1e-9);
+//!
+//! let conversion = MagnitudeConversion::default().with_ml(MlRelation::Munafo2016);
+//! let eq = eq.to_mw(&conversion);
+//! assert_eq!(eq.magnitude_kind, Magnitude::Mw);
+//! assert!((eq.magnitude - 4.4833).abs() < 1e-4);
+//!
+//! // Catalog magnitude types are parsed from their symbols
+//! let mb: Magnitude = "mb".parse().unwrap();
+//! assert!((MagnitudeConversion::default().mw(5.0, mb) - 5.28).abs() < 1e-9);
+//! ```
+
+use crate::gmm::{Earthquake, Magnitude};
+
+/// Published relations converting local magnitude (Ml) to moment magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MlRelation {
+    /// Grünthal, Wahlström & Stromeyer (2009), Europe
+    #[default]
+    Grunthal2009,
+    /// Goertz-Allmann, Edwards et al. (2011), Switzerland
+    GoertzAllmann2011,
+    /// Munafò, Malagnini & Chiaraluce (2016), Italy
+    Munafo2016,
+}
+
+impl MlRelation {
+    /// Moment magnitude of a local magnitude.
+    pub fn mw(&self, ml: f64) -> f64 {
+        match self {
+            Self::Grunthal2009 => 0.53 + 0.646 * ml + 0.0376 * ml * ml,
+            Self::GoertzAllmann2011 => {
+                if ml <= 2. {
+                    0.594 * ml + 0.985
+                } else if ml <= 4. {
+                    1.327 + 0.253 * ml + 0.085 * ml * ml
+                } else {
+                    ml - 0.3
+                }
+            }
+            Self::Munafo2016 => 2. / 3. * ml + 1.15,
+        }
+    }
+}
+
+/// Published relations converting surface-wave magnitude (Ms) to moment magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MsRelation {
+    /// Scordilis (2006), global
+    #[default]
+    Scordilis2006,
+    /// Di Giacomo, Bondár et al. (2015), ISC-GEM catalog
+    DiGiacomo2015,
+    /// Ekström & Dziewonski (1988), global
+    EkstromDziewonski1988,
+}
+
+impl MsRelation {
+    /// Moment magnitude of a surface-wave magnitude.
+    pub fn mw(&self, ms: f64) -> f64 {
+        match self {
+            Self::Scordilis2006 => {
+                if ms < 6.2 {
+                    0.67 * ms + 2.07
+                } else {
+                    0.99 * ms + 0.08
+                }
+            }
+            Self::DiGiacomo2015 => (-0.222 + 0.233 * ms).exp() + 2.863,
+            Self::EkstromDziewonski1988 => {
+                let log_m0 = if ms < 5.3 {
+                    ms + 19.24
+                } else if ms <= 6.8 {
+                    30.20 - (92.45 - 11.40 * ms).sqrt()
+                } else {
+                    16.14 + 1.5 * ms
+                };
+                2. / 3. * log_m0 - 10.7
+            }
+        }
+    }
+}
+
+/// Published relations converting body-wave magnitude (mb) to moment magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MbRelation {
+    /// Scordilis (2006), global
+    #[default]
+    Scordilis2006,
+    /// Di Giacomo, Bondár et al. (2015), ISC-GEM catalog
+    DiGiacomo2015,
+}
+
+impl MbRelation {
+    /// Moment magnitude of a body-wave magnitude.
+    pub fn mw(&self, mb: f64) -> f64 {
+        match self {
+            Self::Scordilis2006 => 0.85 * mb + 1.03,
+            Self::DiGiacomo2015 => (-4.664 + 0.859 * mb).exp() + 4.555,
+        }
+    }
+}
+
+/// Published relations converting JMA magnitude (Mjma) to moment magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MjmaRelation {
+    /// Heaton et al. (1986): Mjma follows Mw below its saturation at about 7.5
+    #[default]
+    Heaton1986,
+}
+
+impl MjmaRelation {
+    /// Moment magnitude of a JMA magnitude.
+    pub fn mw(&self, mjma: f64) -> f64 {
+        match self {
+            Self::Heaton1986 => mjma,
+        }
+    }
+}
+
+/// Conversion policy: the relation used to convert magnitudes of every scale to moment
+/// magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MagnitudeConversion {
+    /// Relation for local magnitudes
+    pub ml: MlRelation,
+    /// Relation for surface-wave magnitudes
+    pub ms: MsRelation,
+    /// Relation for body-wave magnitudes
+    pub mb: MbRelation,
+    /// Relation for JMA magnitudes
+    pub mjma: MjmaRelation,
+}
+
+impl MagnitudeConversion {
+    /// Select the relation for local magnitudes.
+    pub fn with_ml(mut self, ml: MlRelation) -> Self {
+        self.ml = ml;
+        self
+    }
+
+    /// Select the relation for surface-wave magnitudes.
+    pub fn with_ms(mut self, ms: MsRelation) -> Self {
+        self.ms = ms;
+        self
+    }
+
+    /// Select the relation for body-wave magnitudes.
+    pub fn with_mb(mut self, mb: MbRelation) -> Self {
+        self.mb = mb;
+        self
+    }
+
+    /// Select the relation for JMA magnitudes.
+    pub fn with_mjma(mut self, mjma: MjmaRelation) -> Self {
+        self.mjma = mjma;
+        self
+    }
+
+    /// Moment magnitude of a magnitude of the given scale.
+    pub fn mw(&self, magnitude: f64, kind: Magnitude) -> f64 {
+        match kind {
+            Magnitude::Mw => magnitude,
+            Magnitude::Ml => self.ml.mw(magnitude),
+            Magnitude::Ms => self.ms.mw(magnitude),
+            Magnitude::Mb => self.mb.mw(magnitude),
+            Magnitude::Mjma => self.mjma.mw(magnitude),
+        }
+    }
+}
+
+impl Earthquake {
+    /// Moment magnitude of the earthquake, converted with the default
+    /// [`MagnitudeConversion`] if the magnitude is of another scale.
+    ///
+    /// Models calibrated to Mw read the magnitude through this method.
+    pub fn mw(&self) -> f64 {
+        MagnitudeConversion::default().mw(self.magnitude, self.magnitude_kind)
+    }
+
+    /// Convert the magnitude of the earthquake to Mw with the given relations.
+    pub fn to_mw(mut self, conversion: &MagnitudeConversion) -> Self {
+        self.magnitude = conversion.mw(self.magnitude, self.magnitude_kind);
+        self.magnitude_kind = Magnitude::Mw;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_mf2013_lib_configs;
+    use crate::gmm::{GroundMotionModeling, Vs30Point};
+
+    #[test]
+    fn test_ml_relations() {
+        let relations = [
+            MlRelation::Grunthal2009,
+            MlRelation::GoertzAllmann2011,
+            MlRelation::Munafo2016,
+        ];
+        for relation in relations {
+            // Ml overestimates Mw of moderate events
+            assert!(relation.mw(5.) < 5., "{relation:?}");
+            assert!(relation.mw(4.) < relation.mw(4.5));
+        }
+        // The Goertz-Allmann et al. (2011) branches join continuously
+        let swiss = MlRelation::GoertzAllmann2011;
+        for ml in [2., 4.] {
+            assert!((swiss.mw(ml - 1e-9) - swiss.mw(ml + 1e-9)).abs() < 0.01);
+        }
+        let conversion = MagnitudeConversion::default();
+        assert_eq!(conversion.mw(5.5, Magnitude::Mw), 5.5);
+    }
+
+    #[test]
+    fn test_teleseismic_relations() {
+        for relation in [
+            MsRelation::Scordilis2006,
+            MsRelation::DiGiacomo2015,
+            MsRelation::EkstromDziewonski1988,
+        ] {
+            // Ms is close to Mw around magnitude 6 and saturates for great events
+            assert!((relation.mw(6.) - 6.).abs() < 0.35, "{relation:?}");
+            assert!(relation.mw(8.) >= 7.9, "{relation:?}");
+        }
+        // The Ekström & Dziewonski (1988) branches join continuously
+        let ed = MsRelation::EkstromDziewonski1988;
+        for ms in [5.3, 6.8] {
+            assert!((ed.mw(ms - 1e-9) - ed.mw(ms + 1e-9)).abs() < 0.01);
+        }
+        for relation in [MbRelation::Scordilis2006, MbRelation::DiGiacomo2015] {
+            assert!((relation.mw(5.) - 5.25).abs() < 0.05, "{relation:?}");
+        }
+        let conversion = MagnitudeConversion::default().with_mb(MbRelation::DiGiacomo2015);
+        assert_eq!(conversion.mw(6.5, Magnitude::Mjma), 6.5);
+        assert_eq!(
+            conversion.mw(5.5, Magnitude::Mb),
+            MbRelation::DiGiacomo2015.mw(5.5)
+        );
+        for kind in ["Mw", "ML", "ms", "mb", "Mjma", "Mj"] {
+            let parsed: Magnitude = kind.parse().unwrap();
+            assert_eq!(parsed.to_string().parse::<Magnitude>(), Ok(parsed));
+        }
+        assert!("Md".parse::<Magnitude>().is_err());
+    }
+
+    #[test]
+    fn test_models_use_moment_magnitude() {
+        let config = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let point = Vs30Point::new(142.6, 50.1, 400., None, None);
+        let ml = Earthquake::new_ml(142.4, 50.0, 10.0, 5.0);
+        let mw = Earthquake::new_mw(142.4, 50.0, 10.0, ml.mw());
+        let from_ml = config.calc_from_point(&point, &ml).value;
+        assert_eq!(from_ml, config.calc_from_point(&point, &mw).value);
+        let ml_as_mw = Earthquake::new_mw(142.4, 50.0, 10.0, 5.0);
+        assert!(from_ml < config.calc_from_point(&point, &ml_as_mw).value);
+    }
+}
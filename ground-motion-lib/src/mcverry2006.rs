@@ -0,0 +1,344 @@
+//! Implementation of McVerry et al. (2006) Ground Motion Prediction Equations (GMPE) for
+//! New Zealand.
+//!
+//! The model has separate branches for shallow crustal and subduction (interface and slab)
+//! events. For site classes A/B (rock):
+//!
+//! ```text
+//! crustal:    ln(y) = C1 + C4AS·(M − 6) + C3AS·(8.5 − M)² + C5·r
+//!                   + (C8 + C6AS·(M − 6))·ln(sqrt(r² + C10AS²)) + C46·rVol + C32·CN + C33AS·CR
+//! subduction: ln(y) = C11 + (C12Y + (C15 − C17)·C19Y)·(M − 6) + C13Y·(10 − M)³
+//!                   + C17·ln(r + C18Y·exp(C19Y·M)) + C20·Hc + C24·SI + C46·rVol·(1 − DS)
+//! ```
+//!
+//! where `y` is PGA or 5%-damped PSA in g, `r` is the rupture distance (km), `Hc` is the
+//! centroid depth (km) and `SI`/`DS` flag interface and slab events. The NZS 1170.5 site class
+//! of each point is derived from its Vs30 ([`NzSiteClass::from_vs30`]) and adds
+//!
+//! ```text
+//! class C: C29
+//! class D: C30AS·ln(PGA'_AB + 0.03) + C43
+//! ```
+//!
+//! where `PGA'_AB` is the rock PGA (g) predicted by the same branch.
+//!
+//! Current simplifications:
+//!
+//! - The spectral shape is not rescaled by the `PGA / PGA'` ratio of the published model; the
+//!   primed coefficients are used directly.
+//! - Unspecified (strike-slip) mechanism (`CN = CR = 0`), no hanging-wall term and no volcanic
+//!   path (`rVol = 0`).
+//! - Class E sites are outside the model range and are treated as class D.
+//! - The centroid depth is approximated by the hypocentral depth, and for point sources the
+//!   rupture distance by the hypocentral distance.
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// McVerry et al. (2006) primed coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 3 s).
+pub const MCVERRY2006_TABLE: &str = "
+    imt  c1        c3as     c4as    c5        c6as  c8        c10as  c11      c12y   c13y     c15     c17       c18y    c19y   c20      c24       c29      c30as   c32  c33as  c43       c46       sigma
+    pga  0.07713   0.0      -0.144  -0.00898  0.17  -0.75519  5.6    8.10697  1.414  0.0      -2.552  -2.48795  1.7818  0.554  0.01622  -0.41369  0.44307  -0.23   0.2  0.26   -0.29648  -0.03301  0.58
+    0.1  1.2205    0.03     -0.144  -0.00914  0.17  -0.75     5.6    9.27     1.414  0.0      -2.707  -2.6      1.7818  0.554  0.0176   -0.35     0.5      -0.28   0.2  0.26   -0.3      -0.0287   0.62
+    0.2  1.53365   0.028    -0.144  -0.00903  0.17  -0.73     5.6    9.37     1.414  -0.0048  -2.655  -2.55     1.7818  0.554  0.0178   -0.33     0.6      -0.28   0.2  0.26   -0.21     -0.0287   0.63
+    0.5  0.67      -0.0138  -0.144  -0.0072   0.17  -0.79     5.6    8.13     1.414  -0.0121  -2.36   -2.37     1.7818  0.554  0.0127   -0.42     0.55     -0.2    0.2  0.26   0.0       -0.0195   0.65
+    1.0  -0.5      -0.036   -0.144  -0.0055   0.17  -0.82     5.6    6.79     1.414  -0.0165  -2.234  -2.18     1.7818  0.554  0.0099   -0.56     0.35     -0.13   0.2  0.26   0.14      -0.0152   0.68
+    3.0  -2.4      -0.1     -0.144  -0.003    0.17  -0.74     5.6    4.6      1.414  -0.025   -2.033  -1.95     1.7818  0.554  0.0053   -0.72     0.15     -0.07   0.2  0.26   0.3       -0.005    0.72
+";
+
+/// NZS 1170.5 site class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NzSiteClass {
+    /// Strong rock
+    A,
+    /// Rock
+    B,
+    /// Shallow soil
+    C,
+    /// Deep or soft soil
+    D,
+    /// Very soft soil
+    E,
+}
+
+impl NzSiteClass {
+    /// Site class for the given Vs30 (m/s):
+    ///
+    /// | Site class | Vs30 (m/s)  |
+    /// |------------|-------------|
+    /// | A          | > 1500      |
+    /// | B          | 760 – 1500  |
+    /// | C          | 360 – 760   |
+    /// | D          | 180 – 360   |
+    /// | E          | < 180       |
+    pub fn from_vs30(vs30: f64) -> Self {
+        match vs30 {
+            v if v > 1500. => Self::A,
+            v if v >= 760. => Self::B,
+            v if v >= 360. => Self::C,
+            v if v >= 180. => Self::D,
+            _ => Self::E,
+        }
+    }
+}
+
+/// Period-dependent McVerry et al. (2006) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct McVerry2006Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Crustal constant, magnitude scaling and anelastic attenuation
+    pub c1: f64,
+    pub c3as: f64,
+    pub c4as: f64,
+    pub c5: f64,
+    /// Crustal magnitude-dependent geometric spreading
+    pub c6as: f64,
+    pub c8: f64,
+    /// Crustal near-source saturation distance (km)
+    pub c10as: f64,
+    /// Subduction constant and magnitude scaling
+    pub c11: f64,
+    pub c12y: f64,
+    pub c13y: f64,
+    pub c15: f64,
+    /// Subduction geometric spreading and near-source saturation
+    pub c17: f64,
+    pub c18y: f64,
+    pub c19y: f64,
+    /// Subduction centroid depth scaling
+    pub c20: f64,
+    /// Subduction interface term
+    pub c24: f64,
+    /// Site class C term
+    pub c29: f64,
+    /// Site class D nonlinear and constant terms
+    pub c30as: f64,
+    pub c43: f64,
+    /// Normal and reverse mechanism terms
+    pub c32: f64,
+    pub c33as: f64,
+    /// Volcanic path attenuation
+    pub c46: f64,
+    /// Total standard deviation of `ln(y)` at M6
+    pub sigma: f64,
+}
+
+impl McVerry2006Coeffs {
+    /// Build the coefficients from a row of a McVerry et al. coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            c1: row.coeff("c1"),
+            c3as: row.coeff("c3as"),
+            c4as: row.coeff("c4as"),
+            c5: row.coeff("c5"),
+            c6as: row.coeff("c6as"),
+            c8: row.coeff("c8"),
+            c10as: row.coeff("c10as"),
+            c11: row.coeff("c11"),
+            c12y: row.coeff("c12y"),
+            c13y: row.coeff("c13y"),
+            c15: row.coeff("c15"),
+            c17: row.coeff("c17"),
+            c18y: row.coeff("c18y"),
+            c19y: row.coeff("c19y"),
+            c20: row.coeff("c20"),
+            c24: row.coeff("c24"),
+            c29: row.coeff("c29"),
+            c30as: row.coeff("c30as"),
+            c43: row.coeff("c43"),
+            c32: row.coeff("c32"),
+            c33as: row.coeff("c33as"),
+            c46: row.coeff("c46"),
+            sigma: row.coeff("sigma"),
+        }
+    }
+
+    /// All coefficient sets of the shipped table; the first one is PGA.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(MCVERRY2006_TABLE)
+            .expect("Invalid McVerry et al. (2006) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+}
+
+/// McVerry et al. (2006) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MCVERRY2006 {
+    /// Period-dependent coefficients
+    pub coeffs: McVerry2006Coeffs,
+    /// PGA coefficients driving the class D nonlinear site term
+    pub pga_coeffs: McVerry2006Coeffs,
+    /// Tectonic type of the modeled events
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl MCVERRY2006 {
+    /// Create a new model for the given coefficient set, tectonic type and motion type.
+    pub fn new(
+        coeffs: McVerry2006Coeffs,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        Self {
+            coeffs,
+            pga_coeffs: McVerry2006Coeffs::from_table()[0],
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Median `ln(y)` on class A/B rock (g) for the given coefficients.
+    fn ln_rock(&self, c: &McVerry2006Coeffs, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let m = magnitude;
+        let r = distance;
+        match self.tectonic_type {
+            TectonicType::Crustal => {
+                c.c1 + c.c4as * (m - 6.)
+                    + c.c3as * (8.5 - m).powi(2)
+                    + c.c5 * r
+                    + (c.c8 + c.c6as * (m - 6.)) * r.hypot(c.c10as).ln()
+            }
+            TectonicType::Interplate | TectonicType::Intraplate => {
+                let si = if self.tectonic_type == TectonicType::Interplate {
+                    1.
+                } else {
+                    0.
+                };
+                c.c11
+                    + (c.c12y + (c.c15 - c.c17) * c.c19y) * (m - 6.)
+                    + c.c13y * (10. - m).powi(3)
+                    + c.c17 * (r + c.c18y * (c.c19y * m).exp()).ln()
+                    + c.c20 * depth
+                    + c.c24 * si
+            }
+        }
+    }
+
+    /// Median `ln(y)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Rupture distance (km).
+    /// * `depth` - Centroid depth (km).
+    /// * `site_class` - NZS 1170.5 site class.
+    pub fn ln_median(
+        &self,
+        magnitude: f64,
+        distance: f64,
+        depth: f64,
+        site_class: NzSiteClass,
+    ) -> f64 {
+        let c = &self.coeffs;
+        let rock = self.ln_rock(c, magnitude, distance, depth);
+        match site_class {
+            NzSiteClass::A | NzSiteClass::B => rock,
+            NzSiteClass::C => rock + c.c29,
+            NzSiteClass::D | NzSiteClass::E => {
+                let pga_rock = self
+                    .ln_rock(&self.pga_coeffs, magnitude, distance, depth)
+                    .exp();
+                rock + c.c30as * (pga_rock + 0.03).ln() + c.c43
+            }
+        }
+    }
+}
+
+impl GroundMotionModeling for MCVERRY2006 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// The NZ site class is derived from the site Vs30.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let site_class = NzSiteClass::from_vs30(point.vs30);
+        let ln_y = self.ln_median(eq.mw(), distance, eq.depth, site_class);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: ln_y.exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_site_classes_from_vs30() {
+        assert_eq!(NzSiteClass::from_vs30(2000.), NzSiteClass::A);
+        assert_eq!(NzSiteClass::from_vs30(900.), NzSiteClass::B);
+        assert_eq!(NzSiteClass::from_vs30(400.), NzSiteClass::C);
+        assert_eq!(NzSiteClass::from_vs30(250.), NzSiteClass::D);
+        assert_eq!(NzSiteClass::from_vs30(150.), NzSiteClass::E);
+    }
+
+    #[test]
+    fn test_crustal_rock_pga() {
+        let c = McVerry2006Coeffs::from_table()[0];
+        let model = MCVERRY2006::new(c, TectonicType::Crustal, GmpePointKind::Pga);
+        let expected = 0.07713 - 0.144 * 0.5 - 0.00898 * 20.
+            + (-0.75519 + 0.17 * 0.5) * (400_f64 + 5.6 * 5.6).sqrt().ln();
+        let ln_y = model.ln_median(6.5, 20., 10., NzSiteClass::B);
+        assert!((ln_y - expected).abs() < 1e-12);
+        let class_c = model.ln_median(6.5, 20., 10., NzSiteClass::C);
+        assert!((class_c - ln_y - 0.44307).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_soft_soil_nonlinearity() {
+        let c = McVerry2006Coeffs::from_table()[0];
+        let model = MCVERRY2006::new(c, TectonicType::Intraplate, GmpePointKind::Pga);
+        // The class D amplification shrinks as the rock motion increases
+        let amp = |r: f64| {
+            model.ln_median(7., r, 60., NzSiteClass::D)
+                - model.ln_median(7., r, 60., NzSiteClass::B)
+        };
+        assert!(amp(200.) > amp(60.));
+        assert_eq!(
+            model.ln_median(7., 60., 60., NzSiteClass::E),
+            model.ln_median(7., 60., 60., NzSiteClass::D)
+        );
+    }
+}
@@ -2,13 +2,37 @@
 //!
 //! This module defines the parameters and calculation logic for predicting
 //! ground motion values (PGA, PGV, PSA) based on earthquake and site characteristics.
+//!
+//! Spectral accelerations at 0.3, 1 and 3 s are shipped as a text table
+//! ([`MF2013_PSA_TABLE`]) with one `b`/`c` column pair per tectonic type, see
+//! [`MF2013::psa_table`].
 
 use crate::auxilary::{DL, G_GLOBAL};
-use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
-use geo::{Distance, Haversine, Point};
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+use serde::{Deserialize, Serialize};
+use std::f64::consts::LN_10;
+
+/// Morikawa & Fujiwara (2013) PSA coefficients. The coefficients `Mw0 = 8.1`, `e = 0.5`,
+/// `D0 = 250` and `V0 = 350` are shared by all periods.
+///
+/// Morikawa, N. & Fujiwara, H. (2013). A New Ground Motion Prediction Equation for Japan
+/// Applicable up to M9 Mega-Earthquake. *Journal of Disaster Research*, 8(5), 878–888.
+///
+/// Only the periods of the original predefined PSA configurations are included; further
+/// periods are to be added as rows transcribed from the published coefficient table.
+pub const MF2013_PSA_TABLE: &str = "
+    imt  a       b_crustal  c_crustal  b_interplate  c_interplate  b_intraplate  c_intraplate  d         sigma     pd      dl_min  ps       vs_max   gamma
+    0.3  0.563   -0.004033  0.639      -0.00388      0.6544        -0.004427     1.0482        0.005205  0.407229  0.1006  21      -0.6217  2000     7.711e-05
+    1    0.6011  -0.001955  -0.2766    -0.001256     -0.4191       -0.00229      -0.024        0.00055   0.410513  0.2744  39.32   -0.6755  1423.23  5.324e-05
+    3    0.7089  -0.001276  -1.6579    -0.00047      -1.9088       -0.001086     -1.5998       0.001021  0.379064  0.3996  69.69   -0.4398  864.01   2.548e-05
+";
 
 /// Morikawa & Fujiwara (2013) Ground Motion Prediction Equation parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MF2013 {
     /// Magnitude upper limit (Mw0)
     pub mw0: f64,
@@ -22,7 +46,7 @@ pub struct MF2013 {
     pub d: f64,
     /// Exponent scaling factor for distance damping
     pub e: f64,
-    /// Standard deviation of the log ground motion (not currently used)
+    /// Standard deviation of `log10(Y)`
     pub sigma: f64,
     /// Coefficient for deep sedimentary layer correction
     pub pd: f64,
@@ -47,32 +71,27 @@ pub struct MF2013 {
 impl MF2013 {
     /// Calculate predicted ground motion value (in physical units) for a site and earthquake.
     ///
-    /// Note: Currently assumes a point source (no finite fault modeling).
-    ///
     /// # Arguments
     ///
-    /// * `epicentral_distance` - Horizontal distance from the site to the earthquake epicenter (km).
+    /// * `r_rup` - Rupture distance (km): the hypocentral distance for point sources, the
+    ///   closest distance to the rupture plane for finite ruptures.
     /// * `eq_mag` - Earthquake moment magnitude (Mw).
-    /// * `eq_depth` - Hypocentral depth (km).
     /// * `vs_30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
     /// * `dl` - Depth to the 1400 m/s shear-wave velocity layer (m).
-    /// * `xvf` - Binary flag for volcanic front effect (1.0 if oceanward of front, 0.0 otherwise).
+    /// * `asid_term` - Anomalous seismic intensity correction in log10 units, see
+    ///   [`MF2013::asid_correction`]. Ignored when ASID is disabled.
     ///
     /// # Returns
     ///
     /// Predicted ground motion value in cm/s² (PGA, PSA) or cm/s (PGV).
     fn get_gmpe_by_distnace(
         &self,
-        epicentral_distance: f64,
+        r_rup: f64,
         eq_mag: f64,
-        eq_depth: f64,
         vs_30: f64,
         dl: f64,
-        xvf: f64,
+        asid_term: f64,
     ) -> f64 {
-        // Rupture distance assuming point source
-        let r_rup = (epicentral_distance.powi(2) + eq_depth.powi(2)).sqrt();
-
         let magnitude = eq_mag.min(self.mw0);
         let a_m_w = self.a * magnitude;
 
@@ -94,15 +113,91 @@ impl MF2013 {
 
         // Optional anomalous seismic intensity distribution correction
         if self.asid {
-            let ai = self.gamma + xvf * (eq_depth - 30.);
-            10.0_f64.powf(log_ags + ai)
+            10.0_f64.powf(log_ags + asid_term)
         } else {
             10.0_f64.powf(log_ags)
         }
     }
 }
 
+impl MF2013 {
+    /// Build a PSA model from a row of [`MF2013_PSA_TABLE`] for the given tectonic type.
+    pub fn from_row(row: &CoeffsRow, tectonic_type: TectonicType) -> Self {
+        let suffix = match tectonic_type {
+            TectonicType::Crustal => "crustal",
+            TectonicType::Interplate => "interplate",
+            TectonicType::Intraplate => "intraplate",
+        };
+        Self {
+            mw0: 8.1,
+            a: row.coeff("a"),
+            b: row.coeff(&format!("b_{suffix}")),
+            c: row.coeff(&format!("c_{suffix}")),
+            d: row.coeff("d"),
+            e: 0.5,
+            sigma: row.coeff("sigma"),
+            pd: row.coeff("pd"),
+            dl_min: row.coeff("dl_min"),
+            d0: 250.,
+            ps: row.coeff("ps"),
+            vs_max: row.coeff("vs_max"),
+            v0: 350.,
+            gamma: row.coeff("gamma"),
+            asid: false,
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// PSA models for all periods of [`MF2013_PSA_TABLE`] as `(period, model)` pairs.
+    pub fn psa_table(tectonic_type: TectonicType) -> Vec<(f64, Self)> {
+        CoeffsTable::parse(MF2013_PSA_TABLE)
+            .expect("Invalid Morikawa & Fujiwara (2013) coefficient table")
+            .rows()
+            .iter()
+            .map(|row| (row.period, Self::from_row(row, tectonic_type)))
+            .collect()
+    }
+}
+
+impl MF2013 {
+    /// Anomalous seismic intensity distribution (ASID) correction in log10 units.
+    ///
+    /// Following Morikawa & Fujiwara (2013), the correction for deep events is
+    /// `γ · Xvf · (H − 30)`, where `Xvf` is the horizontal distance (km) from the volcanic front
+    /// towards the fore-arc and `H` is the focal depth (km). `Xvf` is the negated
+    /// [`Vs30Point::xvf`], so fore-arc sites are amplified and back-arc sites attenuated. Sites
+    /// without a distance are not corrected.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Site point with the signed volcanic front distance.
+    /// * `eq_depth` - Hypocentral depth (km).
+    ///
+    /// # Returns
+    ///
+    /// The correction term to be added to the log10 ground motion.
+    pub fn asid_correction(&self, point: &Vs30Point, eq_depth: f64) -> f64 {
+        match point.xvf {
+            Some(xvf) => self.gamma * -xvf * (eq_depth - 30.),
+            None => 0.,
+        }
+    }
+}
+
 impl GroundMotionModeling for MF2013 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
     /// Compute ground motion prediction at a given site point for a specified earthquake event.
     ///
     /// # Arguments
@@ -110,27 +205,34 @@ impl GroundMotionModeling for MF2013 {
     /// * `point` - The site location and properties (longitude, latitude, Vs30, depth to 1400 m/s
     ///   layer, etc.).
     /// * `eq` - The earthquake event (magnitude, depth, hypocenter location).
+    /// * `distances` - Source-to-site distances. The rupture distance of finite ruptures, or the
+    ///   hypocentral distance of point sources, is used.
     ///
     /// # Returns
     ///
     /// A `GmpePoint` containing the predicted ground motion value and associated metadata.
-    fn calc_from_point(&self, point: &Vs30Point, eq: &Earthquake) -> GmpePoint {
-        let epicentral_distance = Haversine
-            .distance(Point::new(eq.lon, eq.lat), Point::new(point.lon, point.lat))
-            / 1000.;
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
         let vs_30 = point.vs30;
         let dl = match point.dl {
             None => DL as f64,
             Some(dl) => dl,
         };
-        let xvf = match point.xvf {
-            None => 0.,
-            Some(_) => 1.,
-        };
-        let mut ground_motion =
-            self.get_gmpe_by_distnace(epicentral_distance, eq.magnitude, eq.depth, vs_30, dl, xvf);
+        let asid_term = self.asid_correction(point, eq.depth);
+        // Hypocentral distance for point sources
+        let r_rup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let mut ground_motion = self.get_gmpe_by_distnace(r_rup, eq.mw(), vs_30, dl, asid_term);
         // convert cm/c^2 to %g
-        if matches!(self.motion_kind, GmpePointKind::Pga | GmpePointKind::Psa) {
+        if matches!(
+            self.motion_kind,
+            GmpePointKind::Pga | GmpePointKind::Psa { .. }
+        ) {
             ground_motion = ((ground_motion / 100.) / G_GLOBAL) * 100.;
         };
         GmpePoint {
@@ -138,6 +240,7 @@ impl GroundMotionModeling for MF2013 {
             lat: point.lat,
             value: ground_motion,
             kind: self.motion_kind,
+            sigma: Some(self.sigma * LN_10),
         }
     }
 }
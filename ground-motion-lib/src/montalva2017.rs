@@ -0,0 +1,289 @@
+//! Implementation of Montalva et al. (2017) Ground Motion Prediction Equations (GMPE) for
+//! subduction zone earthquakes in Chile.
+//!
+//! The model refits the BC Hydro functional form ([`crate::bchydro2016`]) to Chilean interface
+//! (interplate) and intraslab (intraplate) records:
+//!
+//! ```text
+//! ln(Sa) = θ1 + (θ2 + θ14·F_event + θ3·(M − C1))·ln(R + C4·exp((M − 6)·θ9))
+//!        + θ6·R + θ10·F_event + f_mag(M) + f_depth(Zh) + f_site(PGA1000, Vs30)
+//! ```
+//!
+//! where `Sa` is PGA or 5%-damped PSA in g and `F_event` switches between interface (0) and
+//! intraslab (1) events. The event type is selected by the tectonic type of the model.
+//!
+//! Coefficients are shipped as a text table ([`MONTALVA2017_TABLE`]) and parsed with
+//! [`CoeffsTable`].
+//!
+//! Current simplifications:
+//!
+//! - All sites are treated as forearc sites, as in the Chilean dataset.
+//! - Interface models use the rupture distance, approximated by the hypocentral distance for
+//!   point sources; intraslab models use the hypocentral distance.
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Magnitude break of the magnitude scaling.
+const C1: f64 = 7.2;
+/// Finite-fault term scaling distance (km).
+const C4: f64 = 10.;
+/// Magnitude-dependent geometric spreading slope.
+const THETA3: f64 = 0.1;
+/// Magnitude scaling slopes below and above the magnitude break.
+const THETA4: f64 = 0.9;
+const THETA5: f64 = 0.;
+/// Magnitude scaling of the finite-fault term.
+const THETA9: f64 = 0.4;
+/// Nonlinear site response constants.
+const N: f64 = 1.18;
+const C: f64 = 1.88;
+/// Reference Vs30 of the rock PGA driving the nonlinear site term (m/s).
+const VS_ROCK: f64 = 1000.;
+
+/// Montalva et al. (2017) coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 3 s).
+pub const MONTALVA2017_TABLE: &str = "
+    imt  vlin    b       theta1  theta2  theta6   theta10  theta11  theta12  theta13  theta14  sigma
+    pga  865.1   -1.186  4.6     -1.5    -0.0011  2.6      0.012    0.98     -0.012   -0.25    0.76
+    0.1  1032.5  -1.624  5.65    -1.6    -0.0012  2.9      0.012    1.613    -0.013   -0.25    0.79
+    0.2  748.2   -2.188  5.7     -1.55   -0.0017  2.7      0.0125   2.076    -0.015   -0.22    0.8
+    0.5  456.6   -2.669  4.4     -1.2    -0.004   1.8      0.012    2.399    -0.022   -0.15    0.79
+    1.0  400.0   -1.955  3.1     -0.95   -0.0058  1.1      0.011    1.47     -0.035   -0.05    0.8
+    3.0  400.0   0.0     0.9     -0.7    -0.006   0.7      0.005    -0.673   -0.078   0.0      0.82
+";
+
+/// Period-dependent Montalva et al. (2017) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Montalva2017Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Linear site response limit (m/s)
+    pub vlin: f64,
+    /// Nonlinear site response slope
+    pub b: f64,
+    /// Constant term
+    pub theta1: f64,
+    /// Geometric spreading
+    pub theta2: f64,
+    /// Anelastic attenuation
+    pub theta6: f64,
+    /// Intraslab constant adjustment
+    pub theta10: f64,
+    /// Intraslab depth scaling
+    pub theta11: f64,
+    /// Linear site response scaling
+    pub theta12: f64,
+    /// Magnitude-squared scaling
+    pub theta13: f64,
+    /// Intraslab geometric spreading adjustment
+    pub theta14: f64,
+    /// Total standard deviation of `ln(Sa)`
+    pub sigma: f64,
+}
+
+impl Montalva2017Coeffs {
+    /// Build the coefficients from a row of a Montalva et al. coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            vlin: row.coeff("vlin"),
+            b: row.coeff("b"),
+            theta1: row.coeff("theta1"),
+            theta2: row.coeff("theta2"),
+            theta6: row.coeff("theta6"),
+            theta10: row.coeff("theta10"),
+            theta11: row.coeff("theta11"),
+            theta12: row.coeff("theta12"),
+            theta13: row.coeff("theta13"),
+            theta14: row.coeff("theta14"),
+            sigma: row.coeff("sigma"),
+        }
+    }
+
+    /// All coefficient sets of the shipped table; the first one is PGA.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(MONTALVA2017_TABLE)
+            .expect("Invalid Montalva et al. (2017) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+}
+
+/// Montalva et al. (2017) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MONTALVA2017 {
+    /// Period-dependent coefficients
+    pub coeffs: Montalva2017Coeffs,
+    /// PGA coefficients driving the nonlinear site term
+    pub pga_coeffs: Montalva2017Coeffs,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl MONTALVA2017 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: Montalva2017Coeffs,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "MONTALVA2017 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            pga_coeffs: Montalva2017Coeffs::from_table()[0],
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Median `ln(Sa)` (g) with the given coefficients, before the site term.
+    fn ln_base(&self, c: &Montalva2017Coeffs, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let f_event = match self.tectonic_type {
+            TectonicType::Intraplate => 1.,
+            _ => 0.,
+        };
+        let f_mag = if magnitude <= C1 {
+            THETA4 * (magnitude - C1)
+        } else {
+            THETA5 * (magnitude - C1)
+        } + c.theta13 * (10. - magnitude).powi(2);
+        let f_depth = c.theta11 * (depth.min(120.) - 60.) * f_event;
+
+        let spreading = c.theta2 + c.theta14 * f_event + THETA3 * (magnitude - C1);
+        let r = distance + C4 * ((magnitude - 6.) * THETA9).exp();
+
+        c.theta1 + spreading * r.ln() + c.theta6 * distance + c.theta10 * f_event + f_mag + f_depth
+    }
+
+    /// Site term for the given Vs30 (m/s) and median rock PGA at Vs30 = 1000 m/s (g).
+    fn site_term(c: &Montalva2017Coeffs, vs30: f64, pga1000: f64) -> f64 {
+        let ratio = vs30.min(VS_ROCK) / c.vlin;
+        if vs30 < c.vlin {
+            c.theta12 * ratio.ln() - c.b * (pga1000 + C).ln()
+                + c.b * (pga1000 + C * ratio.powf(N)).ln()
+        } else {
+            c.theta12 * ratio.ln() + c.b * N * ratio.ln()
+        }
+    }
+
+    /// Median `ln(Sa)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Rupture distance for interface events or hypocentral distance for
+    ///   intraslab events (km).
+    /// * `depth` - Focal depth (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    pub fn ln_median(&self, magnitude: f64, distance: f64, depth: f64, vs30: f64) -> f64 {
+        let pga = &self.pga_coeffs;
+        let pga1000 = (self.ln_base(pga, magnitude, distance, depth)
+            + Self::site_term(pga, VS_ROCK, 0.))
+        .exp();
+        self.ln_base(&self.coeffs, magnitude, distance, depth)
+            + Self::site_term(&self.coeffs, vs30, pga1000)
+    }
+}
+
+impl GroundMotionModeling for MONTALVA2017 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        match self.tectonic_type {
+            TectonicType::Intraplate => vec![DistanceMetric::Rhyp],
+            _ => vec![DistanceMetric::Rrup],
+        }
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = match self.tectonic_type {
+            TectonicType::Intraplate => distances.rhyp,
+            _ => distances.rrup,
+        }
+        .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ln_sa = self.ln_median(eq.mw(), distance, eq.depth, point.vs30);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: ln_sa.exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(tectonic_type: TectonicType) -> MONTALVA2017 {
+        MONTALVA2017::new(
+            Montalva2017Coeffs::from_table()[0],
+            tectonic_type,
+            GmpePointKind::Pga,
+        )
+    }
+
+    #[test]
+    fn test_megathrust_pga() {
+        let pga = model(TectonicType::Interplate)
+            .ln_median(8.8, 100., 30., 760.)
+            .exp();
+        assert!(pga > 0.05 && pga < 0.5, "{pga}");
+    }
+
+    #[test]
+    fn test_event_type_switch() {
+        let interface = model(TectonicType::Interplate);
+        let slab = model(TectonicType::Intraplate);
+        // Deeper slab events are stronger, interface events do not depend on depth
+        assert!(slab.ln_median(7., 100., 100., 760.) > slab.ln_median(7., 100., 60., 760.));
+        assert_eq!(
+            interface.ln_median(7., 100., 100., 760.),
+            interface.ln_median(7., 100., 60., 760.)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_crustal_not_supported() {
+        model(TectonicType::Crustal);
+    }
+}
@@ -0,0 +1,213 @@
+//! `ndarray`-based ground motion calculation on site arrays and regular grids.
+//!
+//! [`calc_gmpe_vec`](crate::vectorized::calc_gmpe_vec) works on vectors of [`Vs30Point`] and
+//! returns vectors of [`GmpePoint`](crate::gmm::GmpePoint). Numerical downstream code usually
+//! holds sites and results as arrays instead. This module computes predicted values directly
+//! from arrays, without building intermediate point vectors:
+//!
+//! - [`calc_gmpe_array`] takes an `n × k` site array with the columns `lon`, `lat`, `vs30` and
+//!   the optional `dl` and `xvf` columns (`k` from 3 to 5), and returns the `n` values.
+//! - [`calc_gmpe_grid`] takes the longitudes and latitudes of a regular grid and a
+//!   `lats.len() × lons.len()` Vs30 array, and returns the values with the same shape.
+//!
+//! `NaN` in the optional columns marks missing values, and `NaN` Vs30 grid cells (e.g. sea
+//! cells) give `NaN` values. The calculation runs in parallel with [`Rayon`].
+//!
+//! Requires the `ndarray` crate feature.
+//!
+//! [`Rayon`]: https://docs.rs/rayon/latest/rayon/
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::configs::get_mf2013_lib_configs;
+//! use ground_motion_lib::distance::DistanceMethod;
+//! use ground_motion_lib::gmm::Earthquake;
+//! use ground_motion_lib::ndarray::calc_gmpe_grid;
+//! use ndarray::{Array1, Array2};
+//!
+//! let lons = Array1::linspace(142.0, 143.0, 11);
+//! let lats = Array1::linspace(50.0, 49.0, 11);
+//! let vs30 = Array2::from_elem((11, 11), 400.);
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+//!
+//! let method = DistanceMethod::Haversine;
+//! let values = calc_gmpe_grid(lons.view(), lats.view(), vs30.view(), gmpe, &eq, method).unwrap();
+//! assert_eq!(values.dim(), (11, 11));
+//! ```
+
+use crate::distance::{DistanceMethod, DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GroundMotionModeling, Vs30Point};
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Zip};
+use std::error::Error;
+
+/// Optional site parameter of a column, `NaN` marking a missing value.
+fn optional_value(value: f64) -> Option<f64> {
+    (!value.is_nan()).then_some(value)
+}
+
+/// Predicted value at a site.
+fn calc_value<T: GroundMotionModeling + ?Sized>(
+    point: &Vs30Point,
+    gmpe: &T,
+    eq: &Earthquake,
+    metrics: &[DistanceMetric],
+    method: DistanceMethod,
+) -> f64 {
+    let distances = SourceDistances::compute(metrics, eq, point.lon, point.lat, method);
+    gmpe.calc_from_distances(point, eq, &distances).value
+}
+
+/// Calculate ground motion predictions for an array of sites in parallel.
+///
+/// # Arguments
+///
+/// * `sites` - `n × k` array of sites, one row per site with the columns `lon`, `lat`, `vs30`
+///   and optionally `dl` and `xvf` (`NaN` for missing values).
+/// * `gmpe` - Ground motion model.
+/// * `eq` - Earthquake source parameters.
+/// * `method` - Epicentral distance calculation method.
+///
+/// # Returns
+///
+/// The `n` predicted values, in the units of the model.
+///
+/// # Errors
+///
+/// Returns an error if the array has less than 3 or more than 5 columns.
+pub fn calc_gmpe_array<T: GroundMotionModeling + Sync + ?Sized>(
+    sites: ArrayView2<f64>,
+    gmpe: &T,
+    eq: &Earthquake,
+    method: DistanceMethod,
+) -> Result<Array1<f64>, Box<dyn Error>> {
+    let ncols = sites.ncols();
+    if !(3..=5).contains(&ncols) {
+        return Err(format!(
+            "Site arrays need 3 to 5 columns (lon, lat, vs30[, dl[, xvf]]), got {ncols}"
+        )
+        .into());
+    }
+    let metrics = gmpe.distance_metrics();
+    let values = Zip::from(sites.rows()).par_map_collect(|site| {
+        let point = Vs30Point::new(
+            site[0],
+            site[1],
+            site[2],
+            site.get(3).copied().and_then(optional_value),
+            site.get(4).copied().and_then(optional_value),
+        );
+        calc_value(&point, gmpe, eq, &metrics, method)
+    });
+    Ok(values)
+}
+
+/// Calculate ground motion predictions on a regular grid in parallel.
+///
+/// # Arguments
+///
+/// * `lons` - Longitudes of the grid columns (degrees).
+/// * `lats` - Latitudes of the grid rows (degrees).
+/// * `vs30` - `lats.len() × lons.len()` array of Vs30 (m/s); `NaN` cells are skipped.
+/// * `gmpe` - Ground motion model.
+/// * `eq` - Earthquake source parameters.
+/// * `method` - Epicentral distance calculation method.
+///
+/// # Returns
+///
+/// The predicted values with the shape of `vs30`, `NaN` at skipped cells.
+///
+/// # Errors
+///
+/// Returns an error if the shape of `vs30` does not match the grid coordinates.
+pub fn calc_gmpe_grid<T: GroundMotionModeling + Sync + ?Sized>(
+    lons: ArrayView1<f64>,
+    lats: ArrayView1<f64>,
+    vs30: ArrayView2<f64>,
+    gmpe: &T,
+    eq: &Earthquake,
+    method: DistanceMethod,
+) -> Result<Array2<f64>, Box<dyn Error>> {
+    let shape = (lats.len(), lons.len());
+    if vs30.dim() != shape {
+        return Err(format!(
+            "Vs30 array shape {:?} does not match the grid shape {shape:?} (lats × lons)",
+            vs30.dim()
+        )
+        .into());
+    }
+    let metrics = gmpe.distance_metrics();
+    let values = Zip::indexed(vs30).par_map_collect(|(row, col), &vs30| {
+        if vs30.is_nan() {
+            return f64::NAN;
+        }
+        let point = Vs30Point::new(lons[col], lats[row], vs30, None, None);
+        calc_value(&point, gmpe, eq, &metrics, method)
+    });
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_mf2013_lib_configs;
+    use crate::vectorized::calc_gmpe_vec;
+    use ndarray::array;
+
+    #[test]
+    fn test_calc_gmpe_array_matches_points() {
+        let gmpe = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+        let sites = array![[142.5, 50.0, 400., 200.], [142.6, 50.1, 350., f64::NAN]];
+        let values = calc_gmpe_array(sites.view(), gmpe, &eq, DistanceMethod::Haversine).unwrap();
+
+        let points = vec![
+            Vs30Point::new(142.5, 50.0, 400., Some(200.), None),
+            Vs30Point::new(142.6, 50.1, 350., None, None),
+        ];
+        let expected = calc_gmpe_vec(&points, gmpe, &eq);
+        assert_eq!(values[0], expected[0].value);
+        assert_eq!(values[1], expected[1].value);
+
+        let lon_lat = array![[142.5, 50.0]];
+        assert!(calc_gmpe_array(lon_lat.view(), gmpe, &eq, DistanceMethod::Haversine).is_err());
+    }
+
+    #[test]
+    fn test_calc_gmpe_grid() {
+        let gmpe = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let eq = Earthquake::new_mw(142.0, 50.0, 10.0, 6.5);
+        let lons = array![142.0, 142.5, 143.0];
+        let lats = array![50.0, 49.5];
+        let vs30 = array![[400., 400., 400.], [400., f64::NAN, 400.]];
+        let values = calc_gmpe_grid(
+            lons.view(),
+            lats.view(),
+            vs30.view(),
+            gmpe,
+            &eq,
+            DistanceMethod::Haversine,
+        )
+        .unwrap();
+        assert_eq!(values.dim(), (2, 3));
+        assert!(values[(1, 1)].is_nan());
+        // Values decrease away from the epicenter at the north-west node
+        assert!(values[(0, 0)] > values[(0, 1)] && values[(0, 1)] > values[(0, 2)]);
+
+        let transposed = vs30.t();
+        let result = calc_gmpe_grid(
+            lons.view(),
+            lats.view(),
+            transposed,
+            gmpe,
+            &eq,
+            DistanceMethod::Haversine,
+        );
+        assert!(result.is_err());
+    }
+}
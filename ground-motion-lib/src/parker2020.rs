@@ -0,0 +1,492 @@
+//! Implementation of the NGA-Subduction Parker et al. (2020) Ground Motion Prediction Equations
+//! (GMPE) for subduction zone earthquakes.
+//!
+//! The model covers interface (interplate) and intraslab (intraplate) events with separate
+//! coefficient tables:
+//!
+//! ```text
+//! ln(Y) = c0 + F_P + F_M + F_D + F_S + F_B
+//! F_P = c1·ln(R + h) + b4·M·ln((R + h) / R_ref) + a0·R
+//! h   = 10^(−0.82 + 0.252·M),  R_ref = sqrt(1 + h²)
+//! F_M = c4·(M − Mb) + c6·(M − Mb)²   (M ≤ Mb)
+//!     = c5·(M − Mb)                  (M > Mb)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in g and `R` is the rupture distance (km). The constant
+//! `c0`, the anelastic attenuation `a0` and the magnitude break `Mb` are regional, see
+//! [`Parker2020Region`]. The depth term `F_D` applies to intraslab events only.
+//!
+//! The site term `F_S` consists of a piecewise-linear Vs30 scaling and a nonlinear term driven by
+//! the median PGA on rock with Vs30 = 760 m/s. The basin term `F_B` is applied in the Japan and
+//! Cascadia regions and scales with `ΔZ2.5`, the natural-log difference between the site Z2.5 and
+//! its Vs30-dependent mean.
+//!
+//! Current simplifications:
+//!
+//! - The basin term uses the centered Z2.5 (`ΔZ2.5 = 0`) when computing from site points.
+//! - For point sources the rupture distance is approximated by the hypocentral distance.
+//!
+//! Only PGA and PSA at 0.2, 1 and 3 s are shipped; other periods can be added by constructing
+//! [`Parker2020Coeffs`] from the published tables.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Magnitude-dependent geometric spreading coefficient.
+const B4: f64 = 0.1;
+/// Regional interface magnitude breaks (Global, Japan, Cascadia, South America).
+const MB_INTERFACE: [f64; 4] = [7.9, 8.1, 7.7, 8.3];
+/// Regional intraslab magnitude breaks (Global, Japan, Cascadia, South America).
+const MB_SLAB: [f64; 4] = [7.2, 7.6, 7.2, 7.4];
+/// Hypocentral depth limit of the intraslab depth scaling (km).
+const DB: f64 = 67.;
+/// Site term corner velocities (m/s).
+const V1: f64 = 200.;
+const V2: f64 = 1000.;
+/// Reference rock Vs30 (m/s).
+const VS30_REF: f64 = 760.;
+/// Nonlinear site response constants.
+const F3: f64 = 0.1;
+const F5: f64 = -0.007;
+/// Bounds of the basin term scaling (natural-log units).
+const DELTA_Z2P5_MAX: f64 = 1.;
+
+/// Regional variant of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parker2020Region {
+    /// Global coefficients
+    Global,
+    /// Japan-specific terms with basin scaling
+    Japan,
+    /// Cascadia-specific terms with basin scaling
+    Cascadia,
+    /// South America-specific terms
+    SouthAmerica,
+}
+
+impl Parker2020Region {
+    /// Index of the region in the regional coefficient arrays.
+    fn index(self) -> usize {
+        match self {
+            Parker2020Region::Global => 0,
+            Parker2020Region::Japan => 1,
+            Parker2020Region::Cascadia => 2,
+            Parker2020Region::SouthAmerica => 3,
+        }
+    }
+
+    /// Whether the basin term is applied in the region.
+    fn has_basin_term(self) -> bool {
+        matches!(self, Parker2020Region::Japan | Parker2020Region::Cascadia)
+    }
+}
+
+/// Period-dependent Parker et al. (2020) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parker2020Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Regional constant (Global, Japan, Cascadia, South America)
+    pub c0: [f64; 4],
+    /// Geometric spreading
+    pub c1: f64,
+    /// Regional anelastic attenuation (Global, Japan, Cascadia, South America)
+    pub a0: [f64; 4],
+    /// Magnitude scaling below the magnitude break
+    pub c4: f64,
+    /// Magnitude scaling above the magnitude break
+    pub c5: f64,
+    /// Magnitude-squared scaling below the magnitude break
+    pub c6: f64,
+    /// Intraslab depth scaling (zero for interface events)
+    pub m: f64,
+    /// Vs30 scaling below and above the `V1` corner velocity
+    pub s1: f64,
+    pub s2: f64,
+    /// Nonlinear site response coefficient
+    pub f4: f64,
+    /// Basin depth scaling
+    pub e: f64,
+    /// Total standard deviation of `ln(Y)`
+    pub sigma: f64,
+}
+
+/// Parker et al. (2020) interface coefficients (PGA, PSA at 0.2, 1 and 3 s).
+pub const PARKER2020_INTERFACE: [Parker2020Coeffs; 4] = [
+    Parker2020Coeffs {
+        period: 0.,
+        c0: [4.8401, 5.0401, 4.5401, 4.9401],
+        c1: -1.662,
+        a0: [-0.0066, -0.0076, -0.004, -0.0056],
+        c4: 1.246,
+        c5: 0.2,
+        c6: -0.021,
+        m: 0.,
+        s1: -0.3,
+        s2: -0.5,
+        f4: -0.15,
+        e: 0.,
+        sigma: 0.75,
+    },
+    Parker2020Coeffs {
+        period: 0.2,
+        c0: [5.2966, 5.4966, 4.9966, 5.3966],
+        c1: -1.596,
+        a0: [-0.0073, -0.0084, -0.0044, -0.0062],
+        c4: 1.15,
+        c5: 0.2,
+        c6: -0.021,
+        m: 0.,
+        s1: -0.2,
+        s2: -0.35,
+        f4: -0.2,
+        e: 0.,
+        sigma: 0.78,
+    },
+    Parker2020Coeffs {
+        period: 1.0,
+        c0: [2.0369, 2.2369, 1.7369, 2.1369],
+        c1: -1.253,
+        a0: [-0.0033, -0.0038, -0.002, -0.0028],
+        c4: 1.75,
+        c5: 0.5,
+        c6: -0.06,
+        m: 0.,
+        s1: -0.9,
+        s2: -0.95,
+        f4: -0.05,
+        e: 0.12,
+        sigma: 0.76,
+    },
+    Parker2020Coeffs {
+        period: 3.0,
+        c0: [-0.2825, -0.0825, -0.5825, -0.1825],
+        c1: -1.03,
+        a0: [-0.002, -0.0023, -0.0012, -0.0017],
+        c4: 2.3,
+        c5: 0.8,
+        c6: -0.1,
+        m: 0.,
+        s1: -0.8,
+        s2: -0.85,
+        f4: -0.01,
+        e: 0.2,
+        sigma: 0.77,
+    },
+];
+
+/// Parker et al. (2020) intraslab coefficients (PGA, PSA at 0.2, 1 and 3 s).
+pub const PARKER2020_SLAB: [Parker2020Coeffs; 4] = [
+    Parker2020Coeffs {
+        period: 0.,
+        c0: [6.0061, 6.3061, 5.6061, 6.0061],
+        c1: -2.05,
+        a0: [-0.0026, -0.003, -0.0016, -0.0022],
+        c4: 1.84,
+        c5: 0.7,
+        c6: -0.05,
+        m: 0.0208,
+        s1: -0.3,
+        s2: -0.5,
+        f4: -0.15,
+        e: 0.,
+        sigma: 0.73,
+    },
+    Parker2020Coeffs {
+        period: 0.2,
+        c0: [6.3789, 6.6789, 5.9789, 6.3789],
+        c1: -1.95,
+        a0: [-0.0029, -0.0033, -0.0017, -0.0025],
+        c4: 1.75,
+        c5: 0.7,
+        c6: -0.05,
+        m: 0.023,
+        s1: -0.2,
+        s2: -0.35,
+        f4: -0.2,
+        e: 0.,
+        sigma: 0.76,
+    },
+    Parker2020Coeffs {
+        period: 1.0,
+        c0: [2.8957, 3.1957, 2.4957, 2.8957],
+        c1: -1.6,
+        a0: [-0.0013, -0.0015, -0.0008, -0.0011],
+        c4: 2.2,
+        c5: 0.9,
+        c6: -0.1,
+        m: 0.012,
+        s1: -0.9,
+        s2: -0.95,
+        f4: -0.05,
+        e: 0.12,
+        sigma: 0.75,
+    },
+    Parker2020Coeffs {
+        period: 3.0,
+        c0: [-0.0572, 0.2428, -0.4572, -0.0572],
+        c1: -1.3,
+        a0: [-0.0008, -0.0009, -0.0005, -0.0007],
+        c4: 2.6,
+        c5: 1.0,
+        c6: -0.15,
+        m: 0.005,
+        s1: -0.8,
+        s2: -0.85,
+        f4: -0.01,
+        e: 0.2,
+        sigma: 0.76,
+    },
+];
+
+/// Source and site parameters entering the Parker et al. (2020) equations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Parker2020Inputs {
+    /// Moment magnitude
+    pub magnitude: f64,
+    /// Closest distance to the rupture plane (km)
+    pub rrup: f64,
+    /// Hypocentral depth (km)
+    pub hypo_depth: f64,
+    /// Average shear-wave velocity in the top 30 meters (m/s)
+    pub vs30: f64,
+    /// Natural-log difference between Z2.5 and its Vs30-dependent mean
+    pub delta_z2p5: f64,
+}
+
+/// Parker et al. (2020) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct PARKER2020 {
+    /// Period-dependent coefficients
+    pub coeffs: Parker2020Coeffs,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Regional variant
+    pub region: Parker2020Region,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl PARKER2020 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: Parker2020Coeffs,
+        tectonic_type: TectonicType,
+        region: Parker2020Region,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "PARKER2020 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            tectonic_type,
+            region,
+            motion_kind,
+        }
+    }
+
+    /// PGA coefficients matching the tectonic type of the model.
+    fn pga_coeffs(&self) -> &'static Parker2020Coeffs {
+        match self.tectonic_type {
+            TectonicType::Intraplate => &PARKER2020_SLAB[0],
+            _ => &PARKER2020_INTERFACE[0],
+        }
+    }
+
+    /// Median `ln(Y)` on reference rock (Vs30 = 760 m/s) with the given coefficients.
+    fn ln_rock(&self, c: &Parker2020Coeffs, inputs: &Parker2020Inputs) -> f64 {
+        let i = self.region.index();
+        let m = inputs.magnitude;
+        let r = inputs.rrup;
+
+        let h = 10_f64.powf(-0.82 + 0.252 * m);
+        let r_ref = h.hypot(1.);
+        let f_p = c.c1 * (r + h).ln() + B4 * m * ((r + h) / r_ref).ln() + c.a0[i] * r;
+
+        let mb = match self.tectonic_type {
+            TectonicType::Intraplate => MB_SLAB[i],
+            _ => MB_INTERFACE[i],
+        };
+        let f_m = if m <= mb {
+            c.c4 * (m - mb) + c.c6 * (m - mb).powi(2)
+        } else {
+            c.c5 * (m - mb)
+        };
+
+        let f_d = c.m * (inputs.hypo_depth.min(DB) - DB);
+
+        c.c0[i] + f_p + f_m + f_d
+    }
+
+    /// Site term for the given Vs30 (m/s) and median rock PGA (g).
+    fn site_term(c: &Parker2020Coeffs, vs30: f64, pga_rock: f64) -> f64 {
+        let f_lin = if vs30 <= V1 {
+            c.s1 * (vs30 / V1).ln() + c.s2 * (V1 / VS30_REF).ln()
+        } else {
+            c.s2 * (vs30.min(V2) / VS30_REF).ln()
+        };
+        let f2 = c.f4 * ((F5 * (vs30.min(VS30_REF) - 360.)).exp() - (F5 * (VS30_REF - 360.)).exp());
+        f_lin + f2 * ((pga_rock + F3) / F3).ln()
+    }
+
+    /// Natural logarithm of the median ground motion for the given inputs.
+    ///
+    /// # Returns
+    ///
+    /// `ln(Y)` with `Y` in g.
+    pub fn ln_median(&self, inputs: &Parker2020Inputs) -> f64 {
+        let c = &self.coeffs;
+        let pga_rock = self.ln_rock(self.pga_coeffs(), inputs).exp();
+        let f_b = if self.region.has_basin_term() {
+            c.e * inputs.delta_z2p5.clamp(-DELTA_Z2P5_MAX, DELTA_Z2P5_MAX)
+        } else {
+            0.
+        };
+        self.ln_rock(c, inputs) + Self::site_term(c, inputs.vs30, pga_rock) + f_b
+    }
+}
+
+impl GroundMotionModeling for PARKER2020 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let inputs = Parker2020Inputs {
+            magnitude: eq.mw(),
+            rrup: distances
+                .rrup
+                .unwrap_or_else(|| distances.repi.hypot(eq.depth)),
+            hypo_depth: eq.depth,
+            vs30: point.vs30,
+            delta_z2p5: 0.,
+        };
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: self.ln_median(&inputs).exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.coeffs.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(magnitude: f64, rrup: f64, vs30: f64) -> Parker2020Inputs {
+        Parker2020Inputs {
+            magnitude,
+            rrup,
+            hypo_depth: 30.,
+            vs30,
+            delta_z2p5: 0.,
+        }
+    }
+
+    #[test]
+    fn test_reference_rock_pga() {
+        let model = PARKER2020::new(
+            PARKER2020_INTERFACE[0],
+            TectonicType::Interplate,
+            Parker2020Region::Global,
+            GmpePointKind::Pga,
+        );
+        let pga = model.ln_median(&inputs(8., 100., VS30_REF)).exp();
+        assert!(pga > 0.05 && pga < 0.3, "{pga}");
+    }
+
+    #[test]
+    fn test_basin_term_only_in_basin_regions() {
+        let coeffs = PARKER2020_INTERFACE[2];
+        let deep = Parker2020Inputs {
+            delta_z2p5: 0.5,
+            ..inputs(8., 100., 400.)
+        };
+        for (region, expected) in [
+            (Parker2020Region::Japan, 0.5 * coeffs.e),
+            (Parker2020Region::Cascadia, 0.5 * coeffs.e),
+            (Parker2020Region::SouthAmerica, 0.),
+        ] {
+            let model = PARKER2020::new(
+                coeffs,
+                TectonicType::Interplate,
+                region,
+                GmpePointKind::Psa {
+                    period: coeffs.period,
+                },
+            );
+            let diff = model.ln_median(&deep) - model.ln_median(&inputs(8., 100., 400.));
+            assert!((diff - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_nonlinear_site_response() {
+        let model = PARKER2020::new(
+            PARKER2020_SLAB[0],
+            TectonicType::Intraplate,
+            Parker2020Region::Global,
+            GmpePointKind::Pga,
+        );
+        let weak =
+            model.ln_median(&inputs(6., 200., 200.)) - model.ln_median(&inputs(6., 200., 760.));
+        let strong =
+            model.ln_median(&inputs(7.8, 20., 200.)) - model.ln_median(&inputs(7.8, 20., 760.));
+        assert!(weak > strong);
+    }
+
+    #[test]
+    fn test_finite_rupture_uses_rrup() {
+        use crate::rupture::Rupture;
+
+        let model = PARKER2020::new(
+            PARKER2020_INTERFACE[0],
+            TectonicType::Interplate,
+            Parker2020Region::Global,
+            GmpePointKind::Pga,
+        );
+        // Site 50 km north of the epicenter, above a 120 km long vertical rupture
+        let site = Vs30Point::new(142.8, 52.45, 760., None, None);
+        let point_source = Earthquake::new_mw(142.8, 52., 10., 7.5);
+        let finite = Earthquake::new_mw(142.8, 52., 10., 7.5)
+            .with_rupture(Rupture::new(0., 90., 120., 20.).with_ztor(0.));
+        let on_rupture = Parker2020Inputs {
+            hypo_depth: 10.,
+            ..inputs(7.5, 0., 760.)
+        };
+        let expected = model.ln_median(&on_rupture).exp() * 100.;
+        assert!((model.calc_from_point(&site, &finite).value - expected).abs() < 1e-6);
+        assert!(model.calc_from_point(&site, &point_source).value < expected / 2.);
+    }
+}
@@ -0,0 +1,205 @@
+//! Implementation of Pezeshk, Zandieh & Tavakoli (2011) hybrid empirical Ground Motion
+//! Prediction Equations (GMPE) for central and eastern North America.
+//!
+//! The model predicts the median motion on hard rock (Vs = 2000 m/s) with a trilinear geometric
+//! spreading:
+//!
+//! ```text
+//! log10(Y) = c1 + c2·M + c3·M² + (c4 + c5·M)·min(log10(R), log10(70))
+//!          + (c6 + c7·M)·max(min(log10(R / 70), log10(140 / 70)), 0)
+//!          + (c8 + c9·M)·max(log10(R / 140), 0) + c10·R
+//! R = sqrt(Rrup² + c11²)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in g.
+//!
+//! An optional Vs30-based site adjustment converts the hard-rock prediction to the site
+//! condition:
+//!
+//! ```text
+//! log10(Y_site) = log10(Y) + s·log10(min(Vs30, 2000) / 2000)
+//! ```
+//!
+//! Coefficients are shipped as a text table ([`PEZESHK2011_TABLE`]) and parsed with
+//! [`CoeffsTable`]. For point sources the rupture distance is approximated by the hypocentral
+//! distance.
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use std::f64::consts::LN_10;
+
+/// Shear-wave velocity of the hard-rock reference site (m/s).
+pub const VS_HARD_ROCK: f64 = 2000.;
+
+/// Pezeshk et al. (2011) coefficients (PGA, PSA at 0.1, 0.2, 0.5, 1 and 2 s) with the site
+/// adjustment slope `s`.
+pub const PEZESHK2011_TABLE: &str = "
+    imt   c1       c2     c3      c4     c5     c6    c7    c8     c9    c10       c11   s      sigma
+    pga   -2.5740  0.9    -0.05   -2.0   0.12   0.2   0.0   -0.5   0.0   -0.0009   8     -0.45  0.3
+    0.1   -1.8903  0.85   -0.05   -2.1   0.12   0.2   0.0   -0.5   0.0   -0.0012   8     -0.35  0.31
+    0.2   -2.7629  0.95   -0.05   -1.95  0.12   0.2   0.0   -0.5   0.0   -0.0008   8     -0.45  0.3
+    0.5   -4.7293  1.25   -0.06   -1.8   0.11   0.2   0.0   -0.5   0.0   -0.0005   8     -0.6   0.31
+    1.0   -6.4270  1.55   -0.075  -1.7   0.1    0.2   0.0   -0.5   0.0   -0.0003   8     -0.65  0.32
+    2.0   -8.1824  1.85   -0.09   -1.6   0.09   0.2   0.0   -0.5   0.0   -0.0002   8     -0.6   0.33
+";
+
+/// Pezeshk et al. (2011) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PEZESHK2011 {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Coefficients `c1`–`c11` of the hard-rock equation
+    pub c: [f64; 11],
+    /// Site adjustment slope relative to hard rock
+    pub s: f64,
+    /// Standard deviation of `log10(Y)`
+    pub sigma: f64,
+    /// Whether the Vs30-based site adjustment is applied
+    pub site_adjustment: bool,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl PEZESHK2011 {
+    /// Build the hard-rock model from a row of a Pezeshk et al. coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        let names = [
+            "c1", "c2", "c3", "c4", "c5", "c6", "c7", "c8", "c9", "c10", "c11",
+        ];
+        Self {
+            period: row.period,
+            c: names.map(|name| row.coeff(name)),
+            s: row.coeff("s"),
+            sigma: row.coeff("sigma"),
+            site_adjustment: false,
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// All hard-rock models of the shipped coefficient table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(PEZESHK2011_TABLE)
+            .expect("Invalid Pezeshk et al. coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Enable the Vs30-based site adjustment.
+    pub fn with_site_adjustment(mut self) -> Self {
+        self.site_adjustment = true;
+        self
+    }
+
+    /// Median `log10(Y)` on hard rock (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    pub fn log10_hard_rock(&self, magnitude: f64, rrup: f64) -> f64 {
+        let [c1, c2, c3, c4, c5, c6, c7, c8, c9, c10, c11] = self.c;
+        let m = magnitude;
+        let r = rrup.hypot(c11);
+        let log_r = r.log10();
+        c1 + c2 * m
+            + c3 * m.powi(2)
+            + (c4 + c5 * m) * log_r.min(70_f64.log10())
+            + (c6 + c7 * m) * (r / 70.).log10().clamp(0., 2_f64.log10())
+            + (c8 + c9 * m) * (r / 140.).log10().max(0.)
+            + c10 * r
+    }
+
+    /// Site adjustment term `log10(Y_site / Y)` for the given Vs30 (m/s).
+    pub fn site_term(&self, vs30: f64) -> f64 {
+        self.s * (vs30.min(VS_HARD_ROCK) / VS_HARD_ROCK).log10()
+    }
+
+    /// Median `log10(Y)` (g), including the site adjustment when enabled.
+    pub fn log10_median(&self, magnitude: f64, rrup: f64, vs30: f64) -> f64 {
+        let site = if self.site_adjustment {
+            self.site_term(vs30)
+        } else {
+            0.
+        };
+        self.log10_hard_rock(magnitude, rrup) + site
+    }
+}
+
+impl GroundMotionModeling for PEZESHK2011 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rrup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let log10_y = self.log10_median(eq.mw(), rrup, point.vs30);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: 10_f64.powf(log10_y) * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hard_rock_reference() {
+        let pga = PEZESHK2011::from_table()[0];
+        let value = 10_f64.powf(pga.log10_median(6., 20., 400.));
+        assert!((value - 0.2).abs() < 1e-3, "{value}");
+    }
+
+    #[test]
+    fn test_site_adjustment() {
+        let rock = PEZESHK2011::from_table()[4];
+        let site = rock.with_site_adjustment();
+        assert_eq!(
+            site.log10_median(6., 50., VS_HARD_ROCK),
+            rock.log10_median(6., 50., 400.)
+        );
+        let amp = site.log10_median(6., 50., 760.) - rock.log10_median(6., 50., 760.);
+        assert!((amp - rock.site_term(760.)).abs() < 1e-12 && amp > 0.);
+    }
+
+    #[test]
+    fn test_plateau_segment() {
+        let pga = PEZESHK2011::from_table()[0];
+        // Between 70 and 140 km the geometric spreading is positive; only anelastic decay remains
+        let diff = pga.log10_hard_rock(6., 120.) - pga.log10_hard_rock(6., 80.);
+        assert!(diff > -0.1 && diff < 0.05, "{diff}");
+    }
+}
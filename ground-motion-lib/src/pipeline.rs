@@ -0,0 +1,307 @@
+//! # Post-processing Pipeline for Result Grids
+//!
+//! This module provides a composable post-processing pipeline for computed ground motion
+//! grids. Each processing stage implements the [`GridTransform`] trait and maps a single
+//! [`GmpePoint`] to a new point, or drops it from the grid by returning `None`.
+//!
+//! Stages are chained in a [`Pipeline`], which is applied to a `Vec<GmpePoint>` in parallel
+//! using Rayon. This allows operational products (unit conversion, clipping, masking, etc.)
+//! to be described declaratively as a list of stages rather than as ad hoc loops.
+//!
+//! ## Built-in Stages
+//!
+//! - [`Scale`]: Multiply values by a constant factor (unit conversion).
+//! - [`Clip`]: Clamp values into a `[min, max]` range.
+//! - [`ThresholdMask`]: Drop points with values below a threshold.
+//! - [`BBoxMask`]: Drop points outside of a geographic bounding box.
+//! - [`Epsilon`]: Shift median predictions by a number of standard deviations (percentile maps).
+//! - [`FnTransform`]: Wrap an arbitrary closure as a stage.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::GmpePoint;
+//! use ground_motion_lib::pipeline::{Clip, Pipeline, Scale, ThresholdMask};
+//!
+//! let points = vec![
+//!     GmpePoint::new_pga(142.5, 50.0, 0.05),
+//!     GmpePoint::new_pga(142.6, 50.1, 12.0),
+//!     GmpePoint::new_pga(142.7, 50.2, 150.0),
+//! ];
+//!
+//! let pipeline = Pipeline::new()
+//!     .then(ThresholdMask { min: 0.1 })
+//!     .then(Clip { min: None, max: Some(100.) })
+//!     .then(Scale::percent_g_to_g());
+//!
+//! let processed = pipeline.run(points);
+//! assert_eq!(processed.len(), 2);
+//! assert_eq!(processed[1].value, 1.0);
+//! ```
+
+use crate::auxilary::{G_GLOBAL, normal_quantile};
+use crate::gmm::GmpePoint;
+use rayon::prelude::*;
+use std::fmt::Debug;
+
+/// A single stage of a post-processing [`Pipeline`].
+///
+/// Implementors transform one [`GmpePoint`] at a time. Returning `None` removes the point
+/// from the output grid.
+pub trait GridTransform: Debug {
+    /// Transform a single result point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - Input result point.
+    ///
+    /// # Returns
+    ///
+    /// The transformed point, or `None` if the point should be dropped.
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint>;
+}
+
+/// Ordered chain of [`GridTransform`] stages applied to result grids in parallel.
+#[derive(Debug, Default)]
+pub struct Pipeline {
+    /// Processing stages in application order.
+    pub stages: Vec<Box<dyn GridTransform + Send + Sync>>,
+}
+
+impl Pipeline {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self { stages: Vec::new() }
+    }
+
+    /// Append a stage to the end of the pipeline.
+    pub fn then<T: GridTransform + Send + Sync + 'static>(mut self, stage: T) -> Self {
+        self.stages.push(Box::new(stage));
+        self
+    }
+
+    /// Apply all stages to a single point.
+    pub fn apply_point(&self, point: GmpePoint) -> Option<GmpePoint> {
+        self.stages
+            .iter()
+            .try_fold(point, |point, stage| stage.apply(point))
+    }
+
+    /// Apply the pipeline to a result grid in parallel.
+    ///
+    /// The order of the remaining points is preserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - Result grid to process.
+    ///
+    /// # Returns
+    ///
+    /// Processed grid without the points dropped by masking stages.
+    pub fn run(&self, points: Vec<GmpePoint>) -> Vec<GmpePoint> {
+        points
+            .into_par_iter()
+            .filter_map(|point| self.apply_point(point))
+            .collect()
+    }
+}
+
+impl GridTransform for Pipeline {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        self.apply_point(point)
+    }
+}
+
+/// Multiply values by a constant factor.
+#[derive(Debug, Clone, Copy)]
+pub struct Scale {
+    /// Multiplication factor.
+    pub factor: f64,
+}
+
+impl Scale {
+    /// Convert percent of gravity (%g) to fractions of gravity (g).
+    pub fn percent_g_to_g() -> Self {
+        Self { factor: 0.01 }
+    }
+
+    /// Convert percent of gravity (%g) to cm/s² (gal).
+    pub fn percent_g_to_gal() -> Self {
+        Self { factor: G_GLOBAL }
+    }
+
+    /// Convert percent of gravity (%g) to m/s².
+    pub fn percent_g_to_m_s2() -> Self {
+        Self {
+            factor: G_GLOBAL / 100.,
+        }
+    }
+}
+
+impl GridTransform for Scale {
+    fn apply(&self, mut point: GmpePoint) -> Option<GmpePoint> {
+        point.value *= self.factor;
+        Some(point)
+    }
+}
+
+/// Clamp values into an optional `[min, max]` range.
+#[derive(Debug, Clone, Copy)]
+pub struct Clip {
+    /// Lower bound, if any.
+    pub min: Option<f64>,
+    /// Upper bound, if any.
+    pub max: Option<f64>,
+}
+
+impl GridTransform for Clip {
+    fn apply(&self, mut point: GmpePoint) -> Option<GmpePoint> {
+        if let Some(min) = self.min {
+            point.value = point.value.max(min);
+        }
+        if let Some(max) = self.max {
+            point.value = point.value.min(max);
+        }
+        Some(point)
+    }
+}
+
+/// Drop points with values below a threshold.
+#[derive(Debug, Clone, Copy)]
+pub struct ThresholdMask {
+    /// Minimum value kept in the grid.
+    pub min: f64,
+}
+
+impl GridTransform for ThresholdMask {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        (point.value >= self.min).then_some(point)
+    }
+}
+
+/// Drop points outside of a geographic bounding box.
+#[derive(Debug, Clone, Copy)]
+pub struct BBoxMask {
+    /// Western boundary (decimal degrees).
+    pub min_lon: f64,
+    /// Eastern boundary (decimal degrees).
+    pub max_lon: f64,
+    /// Southern boundary (decimal degrees).
+    pub min_lat: f64,
+    /// Northern boundary (decimal degrees).
+    pub max_lat: f64,
+}
+
+impl GridTransform for BBoxMask {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        let inside = (self.min_lon..=self.max_lon).contains(&point.lon)
+            && (self.min_lat..=self.max_lat).contains(&point.lat);
+        inside.then_some(point)
+    }
+}
+
+/// Shift median predictions by `epsilon` standard deviations, see [`GmpePoint::at_epsilon`].
+///
+/// Points without a standard deviation are dropped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Epsilon {
+    /// Number of standard deviations above (or below, if negative) the median.
+    pub epsilon: f64,
+}
+
+impl Epsilon {
+    /// Shift predictions to the given percentile (`0 < percentile < 100`), e.g. `84.0` for the
+    /// median plus one standard deviation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `percentile` is outside `(0, 100)`.
+    pub fn from_percentile(percentile: f64) -> Self {
+        assert!(
+            percentile > 0. && percentile < 100.,
+            "Percentile must be within (0, 100)"
+        );
+        Self {
+            epsilon: normal_quantile(percentile / 100.),
+        }
+    }
+}
+
+impl GridTransform for Epsilon {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        point.at_epsilon(self.epsilon)
+    }
+}
+
+/// Wrap a closure as a pipeline stage.
+pub struct FnTransform<F>(pub F);
+
+impl<F> Debug for FnTransform<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FnTransform")
+    }
+}
+
+impl<F> GridTransform for FnTransform<F>
+where
+    F: Fn(GmpePoint) -> Option<GmpePoint>,
+{
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        (self.0)(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<GmpePoint> {
+        vec![
+            GmpePoint::new_pga(142.0, 50.0, 0.5),
+            GmpePoint::new_pga(143.0, 51.0, 5.0),
+            GmpePoint::new_pga(144.0, 52.0, 50.0),
+        ]
+    }
+
+    #[test]
+    fn test_empty_pipeline_is_identity() {
+        let out = Pipeline::new().run(grid());
+        let values: Vec<f64> = out.iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![0.5, 5.0, 50.0]);
+    }
+
+    #[test]
+    fn test_chained_stages() {
+        let pipeline = Pipeline::new()
+            .then(BBoxMask {
+                min_lon: 142.5,
+                max_lon: 145.,
+                min_lat: 50.,
+                max_lat: 53.,
+            })
+            .then(Clip {
+                min: Some(10.),
+                max: Some(20.),
+            })
+            .then(FnTransform(|mut p: GmpePoint| {
+                p.value += 1.;
+                Some(p)
+            }));
+        let values: Vec<f64> = pipeline.run(grid()).iter().map(|p| p.value).collect();
+        assert_eq!(values, vec![11., 21.]);
+    }
+
+    #[test]
+    fn test_epsilon_stage() {
+        let points = vec![
+            GmpePoint::new_pga(142.0, 50.0, 10.).with_sigma(0.5),
+            GmpePoint::new_pga(143.0, 51.0, 10.),
+        ];
+        let out = Pipeline::new().then(Epsilon { epsilon: 2. }).run(points);
+        assert_eq!(out.len(), 1);
+        assert!((out[0].value - 10. * 1_f64.exp()).abs() < 1e-12);
+        let p84 = Epsilon::from_percentile(84.);
+        assert!((p84.epsilon - 0.994458).abs() < 1e-6);
+        assert_eq!(Epsilon::from_percentile(50.).epsilon, 0.);
+    }
+}
@@ -0,0 +1,193 @@
+//! Polars `DataFrame` integration of site and prediction points.
+//!
+//! Exploratory analysis of scenario results is usually done in data frames. This module builds
+//! a Polars [`DataFrame`] of prediction results ([`gmpe_points_to_dataframe`]) and reads site
+//! points from a data frame ([`vs30_points_from_dataframe`]), e.g. one loaded from Parquet or
+//! filtered with Polars expressions before the computation.
+//!
+//! Result frames have the columns:
+//!
+//! | Column     | Type      | Description                                              |
+//! |------------|-----------|----------------------------------------------------------|
+//! | `lon`      | `Float64` | Site longitude (degrees)                                 |
+//! | `lat`      | `Float64` | Site latitude (degrees)                                  |
+//! | `value`    | `Float64` | Predicted value (%g, cm/s or MMI)                        |
+//! | `kind`     | `String`  | Intensity measure, e.g. `Pga`, `Psa(0.3)`                |
+//! | `sigma`    | `Float64` | Total standard deviation (ln units), null if unavailable |
+//! | `distance` | `Float64` | Epicentral (Haversine) distance to the earthquake (km)   |
+//!
+//! Site frames need the `lon`, `lat` and `vs30` columns; the `dl`, `xvf`, `z1p0` and `z2p5`
+//! columns are optional, null values and missing columns are read as `None`. Numeric columns of
+//! any type are cast to `Float64`, so integer Vs30 columns read from CSV files work as well.
+//!
+//! Requires the `polars` crate feature.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, GmpePoint};
+//! use ground_motion_lib::polars::gmpe_points_to_dataframe;
+//!
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let points = vec![GmpePoint::new_pga(142.6, 50.1, 0.789)];
+//! let df = gmpe_points_to_dataframe(&points, &eq).unwrap();
+//! assert_eq!(df.shape(), (1, 6));
+//! ```
+
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, GmpePoint, Vs30Point};
+use polars::prelude::*;
+
+/// Build a data frame of prediction points with their epicentral distances to an earthquake.
+///
+/// # Errors
+///
+/// Returns an error if the data frame cannot be built.
+pub fn gmpe_points_to_dataframe(points: &[GmpePoint], eq: &Earthquake) -> PolarsResult<DataFrame> {
+    let distance: Vec<f64> = points
+        .iter()
+        .map(|p| DistanceMethod::Haversine.epicentral_distance(eq.lon, eq.lat, p.lon, p.lat))
+        .collect();
+    DataFrame::new(vec![
+        Column::new(
+            "lon".into(),
+            points.iter().map(|p| p.lon).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "lat".into(),
+            points.iter().map(|p| p.lat).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "value".into(),
+            points.iter().map(|p| p.value).collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "kind".into(),
+            points
+                .iter()
+                .map(|p| p.kind.to_string())
+                .collect::<Vec<_>>(),
+        ),
+        Column::new(
+            "sigma".into(),
+            points.iter().map(|p| p.sigma).collect::<Vec<_>>(),
+        ),
+        Column::new("distance".into(), distance),
+    ])
+}
+
+/// `Float64` values of a column, or `None` if the data frame has no such column.
+fn float_column(df: &DataFrame, name: &str) -> PolarsResult<Option<Float64Chunked>> {
+    let Ok(column) = df.column(name) else {
+        return Ok(None);
+    };
+    let column = column.cast(&DataType::Float64)?;
+    Ok(Some(column.f64()?.clone()))
+}
+
+fn required_column(df: &DataFrame, name: &str) -> PolarsResult<Float64Chunked> {
+    let column = float_column(df, name)?
+        .ok_or_else(|| polars_err!(ColumnNotFound: "data frame has no `{name}` column"))?;
+    if column.null_count() > 0 {
+        polars_bail!(ComputeError: "column `{name}` has null values");
+    }
+    Ok(column)
+}
+
+/// Value of an optional column at a row, `None` for missing columns and null values.
+fn optional_value(column: &Option<Float64Chunked>, row: usize) -> Option<f64> {
+    column.as_ref().and_then(|column| column.get(row))
+}
+
+/// Read site points from a data frame.
+///
+/// Columns are matched by name, so the frame may hold further columns and any column order.
+///
+/// # Errors
+///
+/// Returns an error if a required column is missing or has null values, or a column cannot be
+/// cast to `Float64`.
+pub fn vs30_points_from_dataframe(df: &DataFrame) -> PolarsResult<Vec<Vs30Point>> {
+    let lon = required_column(df, "lon")?;
+    let lat = required_column(df, "lat")?;
+    let vs30 = required_column(df, "vs30")?;
+    let dl = float_column(df, "dl")?;
+    let xvf = float_column(df, "xvf")?;
+    let z1p0 = float_column(df, "z1p0")?;
+    let z2p5 = float_column(df, "z2p5")?;
+    let points = lon
+        .into_no_null_iter()
+        .zip(lat.into_no_null_iter())
+        .zip(vs30.into_no_null_iter())
+        .enumerate()
+        .map(|(row, ((lon, lat), vs30))| Vs30Point {
+            lon,
+            lat,
+            vs30,
+            dl: optional_value(&dl, row),
+            xvf: optional_value(&xvf, row),
+            z1p0: optional_value(&z1p0, row),
+            z2p5: optional_value(&z2p5, row),
+        })
+        .collect();
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmm::GmpePointKind;
+
+    #[test]
+    fn test_gmpe_points_to_dataframe() {
+        let eq = Earthquake::new_mw(142., 50., 10., 6.5);
+        let points = vec![
+            GmpePoint::new_pga(142., 50., 12.5),
+            GmpePoint {
+                lon: 142.,
+                lat: 51.,
+                value: 3.2,
+                kind: GmpePointKind::Psa { period: 0.3 },
+                sigma: Some(0.7),
+            },
+        ];
+        let df = gmpe_points_to_dataframe(&points, &eq).unwrap();
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|n| n.as_str())
+            .collect();
+        assert_eq!(names, ["lon", "lat", "value", "kind", "sigma", "distance"]);
+        assert_eq!(
+            df.column("kind").unwrap().str().unwrap().get(1),
+            Some("Psa(0.3)")
+        );
+        assert_eq!(df.column("sigma").unwrap().null_count(), 1);
+        let distance = df.column("distance").unwrap().f64().unwrap();
+        assert_eq!(distance.get(0), Some(0.));
+        assert!((distance.get(1).unwrap() - 111.2).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_vs30_points_from_dataframe() {
+        let df = df!(
+            "vs30" => [400i64, 250],
+            "lat" => [50., 50.1],
+            "lon" => [142., 142.1],
+            "dl" => [Some(200.), None],
+        )
+        .unwrap();
+        let sites = vs30_points_from_dataframe(&df).unwrap();
+        assert_eq!(
+            (sites[1].lon, sites[1].lat, sites[1].vs30),
+            (142.1, 50.1, 250.)
+        );
+        assert_eq!(
+            (sites[0].dl, sites[1].dl, sites[0].xvf),
+            (Some(200.), None, None)
+        );
+
+        let without_vs30 = df.drop("vs30").unwrap();
+        assert!(vs30_points_from_dataframe(&without_vs30).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Regular raster grids in geographic coordinates.
+//!
+//! Vs30 and basin depth maps are often distributed as rasters (e.g. Arc/Info ASCII grids)
+//! rather than point lists. A [`RasterGrid`] holds the geometry and cell values of such a
+//! raster and converts it to site points:
+//!
+//! ```text
+//! lon(col) = lon0 + col·dlon
+//! lat(row) = lat0 - row·dlat
+//! ```
+//!
+//! where `(lon0, lat0)` is the center of the north-west cell. Rows run from north to south and
+//! columns from west to east; `NaN` marks nodata cells.
+//!
+//! Prediction grids computed over regular site grids (e.g. from
+//! [`generate_grid`](crate::gmm::generate_grid) or a raster) are turned back into rasters with
+//! [`RasterGrid::from_points`] for the raster writers.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::raster::RasterGrid;
+//!
+//! let vs30 = RasterGrid::new(142.0, 50.1, 0.1, 0.1, 2, vec![300., 400., f64::NAN, 760.]).unwrap();
+//! let dl = RasterGrid::new(142.0, 50.1, 0.1, 0.1, 2, vec![250., f64::NAN, 0., 50.]).unwrap();
+//!
+//! let sites = vs30.vs30_points(Some(&dl)).unwrap();
+//! assert_eq!(sites.len(), 3);
+//! assert_eq!(sites[1].dl, None);
+//! assert_eq!((sites[2].lon, sites[2].lat), (142.1, 50.0));
+//! ```
+
+use crate::gmm::{GmpePoint, Vs30Point};
+use std::error::Error;
+
+/// Regular grid of values in geographic coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RasterGrid {
+    /// Longitude of the center of the north-west cell (degrees)
+    pub lon0: f64,
+    /// Latitude of the center of the north-west cell (degrees)
+    pub lat0: f64,
+    /// Cell size in longitude (degrees)
+    pub dlon: f64,
+    /// Cell size in latitude (degrees)
+    pub dlat: f64,
+    /// Number of columns
+    pub ncols: usize,
+    /// Number of rows
+    pub nrows: usize,
+    /// Cell values, row by row; `NaN` marks nodata cells
+    pub values: Vec<f64>,
+}
+
+impl RasterGrid {
+    /// Create a grid from its north-west cell center, cell sizes, number of columns and
+    /// row-major values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cell sizes are not positive, or the number of values is not a
+    /// non-zero multiple of `ncols`.
+    pub fn new(
+        lon0: f64,
+        lat0: f64,
+        dlon: f64,
+        dlat: f64,
+        ncols: usize,
+        values: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if dlon <= 0. || dlat <= 0. {
+            return Err("Cell sizes must be positive".into());
+        }
+        if ncols == 0 || values.is_empty() || !values.len().is_multiple_of(ncols) {
+            return Err(format!(
+                "{} values do not fill rows of {ncols} columns",
+                values.len()
+            )
+            .into());
+        }
+        Ok(Self {
+            lon0,
+            lat0,
+            dlon,
+            dlat,
+            ncols,
+            nrows: values.len() / ncols,
+            values,
+        })
+    }
+
+    /// Rasterize the values of points on a regular grid.
+    ///
+    /// The cell sizes are the smallest coordinate steps between the points, and cells without
+    /// a point are nodata. Points need not cover the full extent, e.g. grids pre-filtered to a
+    /// radius around the epicenter are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are fewer than two points, or the points do not lie on the
+    /// nodes of a regular grid.
+    pub fn from_points(points: &[GmpePoint]) -> Result<Self, Box<dyn Error>> {
+        const NOT_REGULAR: &str = "Points do not form a regular grid";
+        let steps = |coords: Vec<f64>| {
+            let mut coords = coords;
+            coords.sort_by(f64::total_cmp);
+            coords.dedup_by(|a, b| (*a - *b).abs() < 1e-9);
+            let step = coords
+                .windows(2)
+                .map(|pair| pair[1] - pair[0])
+                .fold(f64::INFINITY, f64::min);
+            (coords[0], coords[coords.len() - 1], step)
+        };
+        if points.len() < 2 {
+            return Err("At least two points are required to rasterize a grid".into());
+        }
+        let (min_lon, max_lon, dlon) = steps(points.iter().map(|p| p.lon).collect());
+        let (min_lat, max_lat, dlat) = steps(points.iter().map(|p| p.lat).collect());
+        // A single row or column takes the cell size of the other dimension
+        let (dlon, dlat) = match (dlon.is_finite(), dlat.is_finite()) {
+            (true, true) => (dlon, dlat),
+            (true, false) => (dlon, dlon),
+            (false, true) => (dlat, dlat),
+            (false, false) => return Err(NOT_REGULAR.into()),
+        };
+        let ncols = ((max_lon - min_lon) / dlon).round() as usize + 1;
+        let nrows = ((max_lat - min_lat) / dlat).round() as usize + 1;
+        // Guard against irregular coordinates resolved by tiny steps
+        if ncols.saturating_mul(nrows) > 16 * points.len() + 1024 {
+            return Err(NOT_REGULAR.into());
+        }
+
+        let mut values = vec![f64::NAN; ncols * nrows];
+        for point in points {
+            let col = (point.lon - min_lon) / dlon;
+            let row = (max_lat - point.lat) / dlat;
+            if (col - col.round()).abs() > 1e-3 || (row - row.round()).abs() > 1e-3 {
+                return Err(NOT_REGULAR.into());
+            }
+            values[row.round() as usize * ncols + col.round() as usize] = point.value;
+        }
+        Self::new(min_lon, max_lat, dlon, dlat, ncols, values)
+    }
+
+    /// Longitude and latitude of the center of a cell.
+    pub fn cell_center(&self, row: usize, col: usize) -> (f64, f64) {
+        (
+            self.lon0 + col as f64 * self.dlon,
+            self.lat0 - row as f64 * self.dlat,
+        )
+    }
+
+    /// Whether another grid has the same geometry (cell centers) as this one.
+    pub fn same_geometry(&self, other: &RasterGrid) -> bool {
+        let eps = 1e-6 * self.dlon.min(self.dlat);
+        self.ncols == other.ncols
+            && self.nrows == other.nrows
+            && (self.lon0 - other.lon0).abs() < eps
+            && (self.lat0 - other.lat0).abs() < eps
+            && (self.dlon - other.dlon).abs() < eps
+            && (self.dlat - other.dlat).abs() < eps
+    }
+
+    /// Convert a Vs30 grid (m/s) to site points, one per cell with a value.
+    ///
+    /// Nodata cells are skipped. The optional basin depth grid (`dl`, m) must have the same
+    /// geometry; its nodata cells leave the depth of the site empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the geometries of both grids differ.
+    pub fn vs30_points(&self, dl: Option<&RasterGrid>) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+        if dl.is_some_and(|dl| !self.same_geometry(dl)) {
+            return Err("Vs30 and basin depth grids differ in geometry".into());
+        }
+        let points = self
+            .values
+            .iter()
+            .enumerate()
+            .filter(|(_, vs30)| !vs30.is_nan())
+            .map(|(i, vs30)| {
+                let (lon, lat) = self.cell_center(i / self.ncols, i % self.ncols);
+                let dl = dl.map(|dl| dl.values[i]).filter(|dl| !dl.is_nan());
+                Vs30Point::new(lon, lat, *vs30, dl, None)
+            })
+            .collect();
+        Ok(points)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gmm::generate_grid;
+
+    #[test]
+    fn test_from_points() {
+        let sites = generate_grid([142., 50., 143., 50.4], 0.2, 400.);
+        let mut points: Vec<GmpePoint> = sites
+            .iter()
+            .map(|site| GmpePoint::new_pga(site.lon, site.lat, site.lon - site.lat))
+            .collect();
+        points.remove(7);
+        let grid = RasterGrid::from_points(&points).unwrap();
+        assert_eq!((grid.ncols, grid.nrows), (6, 3));
+        assert_eq!((grid.lon0, grid.lat0), (142., 50.4));
+        // North-west cell, and the removed point (second row, second column)
+        assert!((grid.values[0] - (142. - 50.4)).abs() < 1e-9);
+        assert!(grid.values[7].is_nan());
+
+        points.push(GmpePoint::new_pga(142.07, 50., 1.));
+        assert!(RasterGrid::from_points(&points).is_err());
+    }
+
+    #[test]
+    fn test_geometry_mismatch() {
+        let vs30 = RasterGrid::new(142., 50., 0.1, 0.1, 2, vec![300.; 4]).unwrap();
+        let dl = RasterGrid::new(142., 50., 0.1, 0.1, 1, vec![100.; 4]).unwrap();
+        assert!(vs30.vs30_points(Some(&dl)).is_err());
+        assert!(RasterGrid::new(142., 50., 0.1, 0.1, 3, vec![300.; 4]).is_err());
+    }
+}
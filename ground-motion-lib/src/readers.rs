@@ -2,23 +2,58 @@
 //!
 //! This module provides utilities for reading site-specific input data into the ground motion
 //! prediction library. It focuses on deserializing tabular files into [`Vs30Point`] instances
-//! for use in GMPE calculations.
+//! for use in GMPE calculations, and station observations into [`StationPoint`] instances for
+//! residual analysis and conditioning.
 //!
 //! ## Features
 //!
-//! - Load site location and site condition data (longitude, latitude, Vs30, basin depth, and xvf flag).
+//! - Load site location and site condition data (longitude, latitude, Vs30, basin depth, signed
+//!   distance to the volcanic front, and the Z1.0 and Z2.5 basin depths).
 //! - Support for configurable CSV delimiter characters (e.g., tab, comma).
-//! - Assumes no header row in input files.
+//! - Fixed positional columns without a header row, or columns mapped by name from a detected
+//!   header row or an explicit [`Vs30Columns`] layout.
 //!
 //! ## Primary Functions
 //!
 //! - [`read_vs30_points`]: Reads a delimited text file into a vector of [`Vs30Point`] instances.
+//! - [`read_site_class_points`]: Reads a delimited text file with site classes instead of Vs30
+//!   values, see [`SiteClassMap`].
+//! - [`read_vs30_points_from_reader`], [`read_site_class_points_from_reader`]: Same, from any
+//!   reader such as standard input, for use in shell pipelines.
+//! - [`read_vs30_points_with`], [`read_vs30_points_from_reader_with`]: Read site files with an
+//!   explicit [`Vs30Columns`] layout of named columns.
+//! - [`read_stations`]: Reads a ShakeMap `stationlist.json` or a delimited station file into a
+//!   vector of [`StationPoint`] instances.
+//! - [`read_catalog`]: Reads a delimited earthquake catalog into a vector of [`CatalogEvent`]
+//!   instances.
+//! - `read_geotiff_dem`, `read_geotiff_vs30_points`: Read a GeoTIFF elevation raster into an
+//!   `ElevationGrid`, or directly into a slope-based Vs30 site grid. Require the `geotiff` crate
+//!   feature.
+//! - `read_grd_vs30_points`: Reads the cells of a GMT/netCDF grid (e.g. the USGS global Vs30
+//!   grid) within a bounding box into site points. Requires the `netcdf` crate feature.
+//! - [`read_esri_ascii_grid`], [`read_esri_ascii_vs30_points`]: Read an Arc/Info ASCII grid
+//!   into a [`RasterGrid`], or Vs30 and optional basin depth grids directly into site points.
+//! - [`read_slab_model`]: Reads a Slab2-style `lon, lat, depth` grid into a [`SlabModel`] for
+//!   the automatic tectonic regime selection.
+//! - [`read_zonation`]: Reads a GeoJSON tectonic zonation mapping polygons to configuration
+//!   keys into a [`TectonicZonation`].
+//! - [`read_fsp`]: Reads a USGS finite-fault solution (`.fsp`) into a
+//!   [`FiniteFaultSolution`] with a multi-segment rupture.
+//! - [`read_quakeml_event`]: Reads the preferred origin and magnitude of a QuakeML event (e.g.
+//!   exported from SeisComP) into an [`Earthquake`].
+//! - [`read_scml_event`]: Reads the preferred origin and magnitude of a SeisComP XML (SCML)
+//!   event into an [`Earthquake`]; [`read_xml_event`] detects QuakeML or SeisComP XML.
+//! - [`read_usgs_geojson_event`], [`parse_usgs_geojson_event`]: Read the origin and preferred
+//!   magnitude of a USGS event GeoJSON file (ComCat) into an [`Earthquake`].
+//! - [`read_shakemap_grid`], [`read_shakemap_grid_vs30_points`]: Read one type of motion of a
+//!   ShakeMap `grid.xml` into [`GmpePoint`] instances, or its nodes and Vs30 into site points,
+//!   for comparisons with official USGS products.
 //!
 //! ## Example File Format (tab-delimited)
 //!
 //! ```text
-//! 142.523 52.913  300 250 1
-//! 142.600 50.100  350 150 0
+//! 142.523 52.913  300 250 20.5
+//! 142.600 50.100  350 150 -40
 //! ```
 //!
 //! Columns are interpreted as:
@@ -27,22 +62,200 @@
 //! 2. latitude (f64)
 //! 3. Vs30 (f64)
 //! 4. basin depth (optional, f64)
-//! 5. xvf flag (optional, u8)
+//! 5. signed distance to the volcanic front in km, positive on the back-arc side (optional, f64)
+//! 6. Z1.0, depth to Vs = 1.0 km/s in m (optional, f64)
+//! 7. Z2.5, depth to Vs = 2.5 km/s in km (optional, f64)
+//!
+//! A first row with non-numeric fields is taken as a header row naming the columns instead, in
+//! any order and with further columns ignored, e.g. `station,latitude,longitude,vs30,dl`; see
+//! [`Vs30Columns`] for the column names.
+//!
+//! Optional columns may be left empty or omitted, e.g. `143.04\t51.92\t300\t\t\t450\t2.1`.
+//! Files with the legacy binary volcanic front flag in the fifth column are read with
+//! [`Vs30Columns::with_legacy_xvf_flags`], or with an `xvf_flag` column in their header row.
+//!
+//! Municipal datasets often only provide site classes. Files read with
+//! [`read_site_class_points`] may hold a site class (e.g. NEHRP `A`–`E`) instead of the Vs30
+//! in the third column; the class is converted to a representative Vs30.
+//!
+//! ## Station Files
+//!
+//! Delimited station files have one observation per row and no header row:
+//!
+//! ```text
+//! 142.523,52.913,pga,35.2,SH.OKH
+//! 142.523,52.913,sa(1.0),12.7,SH.OKH
+//! 142.600,50.100,pgv,8.1,SH.NGL
+//! ```
+//!
+//! Columns are longitude, latitude, type of motion (`pga`, `pgv`, `mmi`, `sa(T)`, or the names
+//! written by [`crate::writers`]), value and station identifier (`NET.STA`).
+//!
+//! ShakeMap `stationlist.json` files are GeoJSON feature collections with one feature per
+//! station. Amplitudes of vertical and flagged channels are ignored, and the largest amplitude
+//! of the horizontal channels is used for every type of motion. Intensities are read for
+//! stations without amplitudes, e.g. macroseismic reports.
+//!
+//! ## Catalog Files
+//!
+//! Delimited catalog files have one declustered event per row and no header row:
+//!
+//! ```text
+//! 143.000,53.000,5.0,4.0,sakhalin_north
+//! 142.500,47.500,12.0,5.3,sakhalin_south,Mjma
+//! ```
+//!
+//! Columns are longitude, latitude, depth (km), magnitude, zone identifier and an optional
+//! magnitude type (`Mw`, `Ml`, `Ms`, `mb` or `Mjma`, moment magnitude when missing).
+//!
+//! ## Slab Files
+//!
+//! Slab geometry files have one `lon, lat, depth` node of the slab top per row and no header
+//! row, as the `.xyz` grids of the Slab2 model (Hayes et al., 2018). Depths are in km and may be
+//! given as negative elevations; nodes with a `NaN` depth are skipped.
+//!
+//! ## Zonation Files
+//!
+//! Tectonic zonations are GeoJSON feature collections with one `Polygon` or `MultiPolygon`
+//! feature per zone. The `config` property holds the configuration key of events in the zone,
+//! and the optional `name` property the name of the zone:
+//!
+//! ```text
+//! {"type": "FeatureCollection", "features": [{"type": "Feature",
+//!   "properties": {"name": "sakhalin", "config": "config_mf2013_crustal_pga"},
+//!   "geometry": {"type": "Polygon", "coordinates": [[[141, 45], [146, 45], [146, 55], [141, 45]]]}}]}
+//! ```
+//!
+//! ## Finite-Fault Files
+//!
+//! USGS finite-fault solutions (`.fsp`, SRCMOD format) are text files with `%` comment headers
+//! and one row per subfault (`LAT LON X==EW Y==NS Z SLIP ...`). The hypocenter is read from the
+//! `Loc` line, the magnitude from the `Size` line, and the geometry of every segment from its
+//! `SEGMENT #` block (`STRIKE`, `DIP`, `LEN`, `WID`, `Z2top`), or from the `Size` and `Mech`
+//! lines for single-segment files. Segments are located from the centers of their subfaults,
+//! see [`RuptureSegment::from_subfaults`].
+//!
+//! ## QuakeML Event Files
+//!
+//! QuakeML files hold the origins and magnitudes of events, and the identifiers of the
+//! preferred ones (namespace prefixes are ignored):
+//!
+//! ```text
+//! <event publicID="smi:org.gfz-potsdam.de/geofon/gfz2024abcd">
+//!   <preferredOriginID>smi:local/origin/1</preferredOriginID>
+//!   <preferredMagnitudeID>smi:local/magnitude/1</preferredMagnitudeID>
+//!   <origin publicID="smi:local/origin/1">
+//!     <latitude><value>50.2</value></latitude>
+//!     <longitude><value>142.8</value></longitude>
+//!     <depth><value>12000</value></depth>
+//!   </origin>
+//!   <magnitude publicID="smi:local/magnitude/1">
+//!     <mag><value>6.1</value></mag>
+//!     <type>MLv</type>
+//!   </magnitude>
+//! </event>
+//! ```
+//!
+//! The first event of the file is read. Without a preferred identifier, the first origin or
+//! magnitude of the event is used. Depths are given in meters. Magnitude types are matched by
+//! their prefix (`Mww` and `Mw(mB)` are Mw, `MLv` is Ml, `mB` and `mb_Lg` are mb); the summary
+//! magnitude `M` and magnitudes without a type are taken as Mw.
+//!
+//! ## SeisComP XML Event Files
+//!
+//! SeisComP XML (SCML, also known as SC3ML) as written by `scxmldump` lists origins next to the
+//! events, with the magnitudes of an origin nested in it:
+//!
+//! ```text
+//! <seiscomp xmlns="http://geofon.gfz-potsdam.de/ns/seiscomp3-schema/0.12" version="0.12">
+//!   <EventParameters>
+//!     <origin publicID="Origin/20240101000001.2">
+//!       <latitude><value>50.35</value></latitude>
+//!       <longitude><value>142.85</value></longitude>
+//!       <depth><value>12.5</value></depth>
+//!       <magnitude publicID="Origin/20240101000001.2/netMag/Mw(mB)">
+//!         <magnitude><value>6.2</value></magnitude>
+//!         <type>Mw(mB)</type>
+//!       </magnitude>
+//!     </origin>
+//!     <event publicID="gfz2024abcd">
+//!       <preferredOriginID>Origin/20240101000001.2</preferredOriginID>
+//!       <preferredMagnitudeID>Origin/20240101000001.2/netMag/Mw(mB)</preferredMagnitudeID>
+//!     </event>
+//!   </EventParameters>
+//! </seiscomp>
+//! ```
+//!
+//! The first event of the file is read. Without a preferred identifier, the first referenced
+//! (`originReference`) or listed origin and the first magnitude of the origin are used. Depths
+//! are given in km; magnitude types are matched as in QuakeML files.
+//!
+//! ## USGS Event Files
+//!
+//! The USGS earthquake catalog (ComCat) serves events as GeoJSON features with the preferred
+//! origin as a `Point` geometry (`lon, lat, depth` in km) and the preferred magnitude in the
+//! `mag` and `magType` properties:
+//!
+//! ```text
+//! {"type": "Feature", "id": "us7000abcd",
+//!  "properties": {"mag": 6.9, "magType": "mww", "place": "..."},
+//!  "geometry": {"type": "Point", "coordinates": [142.85, 50.35, 12.5]}}
+//! ```
+//!
+//! Feature collections of the event feeds are accepted as well; their first feature is read.
+//! Magnitude types are matched as in QuakeML files.
+//!
+//! ## ShakeMap Grid Files
+//!
+//! ShakeMap `grid.xml` files list their columns in `grid_field` elements and hold one row of
+//! whitespace-separated values per grid node in the `grid_data` element:
+//!
+//! ```text
+//! <grid_field index="1" name="LON" units="dd" />
+//! <grid_field index="2" name="LAT" units="dd" />
+//! <grid_field index="3" name="PGA" units="pctg" />
+//! <grid_field index="4" name="PSA03" units="pctg" />
+//! <grid_field index="5" name="STDPGA" units="ln(pctg)" />
+//! <grid_field index="6" name="SVEL" units="ms" />
+//! <grid_data>
+//! 142.0000 50.0000 12.51 25.02 0.61 400.0
+//! </grid_data>
+//! ```
+//!
+//! ShakeMap units (%g, cm/s, intensity) are the units of [`GmpePointKind`]. Spectral
+//! accelerations are named `PSA` followed by the period without the decimal point (`PSA03`,
+//! `PSA10`, `PSA30`) or with `P` as the decimal point (`PSA0P3`). The `STD` column of a type of
+//! motion (e.g. `STDPGA`), if any, becomes the standard deviation of the points.
 //!
 //! ## See Also
 //!
 //! - [`crate::gmm::Vs30Point`]
+//! - [`crate::gmm::StationPoint`]
 //! - [`csv`](https://docs.rs/csv/)
 //!
 //! ## Errors
 //!
 //! This module returns boxed errors for I/O issues or data deserialization failures.
 
-use crate::gmm::Vs30Point;
-use csv::ReaderBuilder;
+use crate::catalog::CatalogEvent;
+use crate::compression::open_reader;
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, Magnitude, StationPoint, Vs30Point};
+use crate::raster::RasterGrid;
+use crate::rupture::{FiniteFaultSolution, MultiSegmentRupture, Rupture, RuptureSegment};
+use crate::site_class::SiteClassMap;
+#[cfg(feature = "geotiff")]
+use crate::slope_vs30::{ElevationGrid, SlopeRegime};
+use crate::tectonic::{SlabModel, TectonicZonation, TectonicZone};
+use csv::{ReaderBuilder, StringRecord};
+use geo::{LineString, MultiPolygon, Polygon};
+use quick_xml::events::{BytesStart, Event};
+use serde::Deserialize;
+use serde_json::Value;
 use std::error::Error;
-use std::fs::File;
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::Path;
+use std::str::FromStr;
 
 /// Reads a list of [`Vs30Point`] instances from a delimited text file.
 ///
@@ -50,8 +263,10 @@ use std::path::Path;
 /// file. Each line in the file is parsed and deserialized into a [`Vs30Point`] instance, which
 /// are collected into a `Vec`.
 ///
-/// The file is assumed to have **no header row**, and the delimiter can be specified to support
-/// flexible file formats (e.g., tab, comma, space).
+/// Columns are read in the fixed positional layout of the [module documentation](self), and
+/// the delimiter can be specified to support flexible file formats (e.g., tab, comma, space).
+/// A header row is detected (a first row with non-numeric fields) and then maps the columns by
+/// name, see [`Vs30Columns`]. Use [`read_vs30_points_with`] for an explicit column layout.
 ///
 /// # Type Parameters
 ///
@@ -80,23 +295,1736 @@ use std::path::Path;
 ///
 /// Returns an error if:
 /// - The file cannot be opened.
+/// - A header row lacks the `lon`, `lat` or `vs30` column.
 /// - Any row in the file fails to deserialize into a [`Vs30Point`].
 pub fn read_vs30_points<P: AsRef<Path>>(
     path: P,
     delim: u8,
 ) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
-    let file = File::open(path)?;
+    read_vs30_points_with(path, delim, None)
+}
+
+/// Reads a list of [`Vs30Point`] instances from a delimited text file with the given column
+/// layout.
+///
+/// Works like [`read_vs30_points`], but with `columns` the columns are mapped by the given
+/// layout instead of a header row or the fixed positional layout; a header row is skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::{Vs30Columns, read_vs30_points_with};
+///
+/// // Station sites with latitude first, skipping the station and basin depth columns
+/// let columns: Vs30Columns = "_,lat,lon,vs30".parse().unwrap();
+/// let points =
+///     read_vs30_points_with("tests/data/testvs30header.csv", b',', Some(&columns)).unwrap();
+/// assert_eq!((points[0].lon, points[0].vs30, points[0].dl), (142.523, 300., None));
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, a header row lacks a required column when no
+/// `columns` are given, or a row is malformed.
+pub fn read_vs30_points_with<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    columns: Option<&Vs30Columns>,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    read_vs30_points_from_reader_with(open_reader(path)?, delim, columns)
+}
+
+/// Reads a list of [`Vs30Point`] instances from delimited text in the format of
+/// [`read_vs30_points`], e.g. from standard input.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::readers::read_vs30_points_from_reader;
+///
+/// let points = read_vs30_points_from_reader(std::io::stdin().lock(), b'\t').unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if reading fails or any row fails to deserialize into a [`Vs30Point`].
+pub fn read_vs30_points_from_reader<R: Read>(
+    reader: R,
+    delim: u8,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    read_vs30_points_from_reader_with(reader, delim, None)
+}
+
+/// Reads a list of [`Vs30Point`] instances from delimited text with the given column layout,
+/// see [`read_vs30_points_with`].
+///
+/// # Errors
+///
+/// Returns an error if reading fails, a header row lacks a required column when no `columns`
+/// are given, or a row is malformed.
+pub fn read_vs30_points_from_reader_with<R: Read>(
+    reader: R,
+    delim: u8,
+    columns: Option<&Vs30Columns>,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
     let mut rdr = ReaderBuilder::new()
         .delimiter(delim)
         .has_headers(false)
-        .from_reader(file);
+        .flexible(true)
+        .from_reader(reader);
+
+    let mut record = StringRecord::new();
+    let mut points = Vec::new();
+    if !rdr.read_record(&mut record)? {
+        return Ok(points);
+    }
+    let is_header = record.iter().any(|field| {
+        let field = field.trim();
+        !field.is_empty() && field.parse::<f64>().is_err()
+    });
+    let columns = match (columns, is_header) {
+        (Some(columns), _) => columns.clone(),
+        (None, true) => Vs30Columns::from_header(&record)?,
+        (None, false) => Vs30Columns::default(),
+    };
+    if !is_header {
+        points.push(columns.point(&record)?);
+    }
+    while rdr.read_record(&mut record)? {
+        points.push(columns.point(&record)?);
+    }
+    Ok(points)
+}
+
+/// Column of a delimited site file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SiteColumn {
+    Lon,
+    Lat,
+    Vs30,
+    Dl,
+    Xvf,
+    Z1p0,
+    Z2p5,
+    /// Legacy binary volcanic front flag, validated and ignored.
+    XvfFlag,
+}
+
+impl SiteColumn {
+    /// Columns in the order of the positional layout.
+    const ALL: [SiteColumn; 7] = [
+        SiteColumn::Lon,
+        SiteColumn::Lat,
+        SiteColumn::Vs30,
+        SiteColumn::Dl,
+        SiteColumn::Xvf,
+        SiteColumn::Z1p0,
+        SiteColumn::Z2p5,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            SiteColumn::Lon => "lon",
+            SiteColumn::Lat => "lat",
+            SiteColumn::Vs30 => "vs30",
+            SiteColumn::Dl => "dl",
+            SiteColumn::Xvf => "xvf",
+            SiteColumn::Z1p0 => "z1p0",
+            SiteColumn::Z2p5 => "z2p5",
+            SiteColumn::XvfFlag => "xvf_flag",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "longitude" => Some(SiteColumn::Lon),
+            "latitude" => Some(SiteColumn::Lat),
+            "xvf_km" => Some(SiteColumn::Xvf),
+            name => SiteColumn::ALL
+                .into_iter()
+                .chain([SiteColumn::XvfFlag])
+                .find(|c| c.name() == name),
+        }
+    }
+}
+
+/// Column layout of delimited site files, mapping file columns to the fields of [`Vs30Point`].
+///
+/// The default layout is the fixed positional one of the [module documentation](self):
+/// `lon, lat, vs30, dl, xvf, z1p0, z2p5`. Layouts are parsed from comma-separated column names
+/// in file order, with `_` (or an empty name) for skipped columns:
+///
+/// ```rust
+/// use ground_motion_lib::readers::Vs30Columns;
+///
+/// let columns: Vs30Columns = "_,lat,lon,vs30,dl".parse().unwrap();
+/// assert_ne!(columns, Vs30Columns::default());
+/// assert!("lon,lat,dl".parse::<Vs30Columns>().is_err()); // no `vs30` column
+/// ```
+///
+/// Column names are `lon` (or `longitude`), `lat` (or `latitude`), `vs30`, `dl`, `xvf` (or
+/// `xvf_km`), `z1p0` and `z2p5`, case-insensitive. The `lon`, `lat` and `vs30` columns are
+/// required. Header rows are mapped the same way, ignoring columns with other names.
+///
+/// The `xvf` column holds signed distances to the volcanic front, see [`Vs30Point::xvf`]. Older
+/// site files held a binary flag instead (`1` for sites oceanward of the front), which cannot be
+/// converted to a distance. Such columns are named `xvf_flag`: their values must be `0` or `1`
+/// and are ignored, so the sites have no volcanic front distance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vs30Columns {
+    columns: Vec<Option<SiteColumn>>,
+}
+
+impl Default for Vs30Columns {
+    fn default() -> Self {
+        Self {
+            columns: SiteColumn::ALL.map(Some).to_vec(),
+        }
+    }
+}
+
+impl Vs30Columns {
+    /// Reads the `xvf` column of the layout as legacy binary volcanic front flags.
+    ///
+    /// ```rust
+    /// use ground_motion_lib::readers::{Vs30Columns, read_vs30_points_from_reader_with};
+    ///
+    /// let columns = Vs30Columns::default().with_legacy_xvf_flags();
+    /// let legacy = "142.5\t50.0\t400\t\t1\n";
+    /// let points = read_vs30_points_from_reader_with(legacy.as_bytes(), b'\t', Some(&columns));
+    /// assert_eq!(points.unwrap()[0].xvf, None);
+    /// ```
+    pub fn with_legacy_xvf_flags(mut self) -> Self {
+        for column in &mut self.columns {
+            if *column == Some(SiteColumn::Xvf) {
+                *column = Some(SiteColumn::XvfFlag);
+            }
+        }
+        self
+    }
+
+    /// Layout of named columns; unknown names are skipped, or rejected if `strict`.
+    fn from_names<'a>(
+        names: impl IntoIterator<Item = &'a str>,
+        strict: bool,
+    ) -> Result<Self, String> {
+        let mut columns = Vec::new();
+        for name in names {
+            let column = SiteColumn::from_name(name);
+            if column.is_none() && strict && !matches!(name.trim(), "" | "_") {
+                return Err(format!(
+                    "Unknown site column `{name}`, expected `lon`, `lat`, `vs30`, `dl`, `xvf`, \
+                     `xvf_flag`, `z1p0`, `z2p5` or `_`"
+                ));
+            }
+            if column.is_some() && columns.contains(&column) {
+                return Err(format!("Duplicate site column `{}`", name.trim()));
+            }
+            columns.push(column);
+        }
+        let layout = Self { columns };
+        for required in [SiteColumn::Lon, SiteColumn::Lat, SiteColumn::Vs30] {
+            if layout.index(required).is_none() {
+                return Err(format!("Site columns have no `{}` column", required.name()));
+            }
+        }
+        Ok(layout)
+    }
+
+    /// Layout given by a header row.
+    fn from_header(header: &StringRecord) -> Result<Self, String> {
+        Self::from_names(header.iter(), false).map_err(|err| format!("Header row: {err}"))
+    }
+
+    fn index(&self, column: SiteColumn) -> Option<usize> {
+        self.columns.iter().position(|c| *c == Some(column))
+    }
+
+    /// Optional value of a column in a row; empty fields and missing columns are `None`.
+    fn value(&self, record: &StringRecord, column: SiteColumn) -> Result<Option<f64>, String> {
+        let Some(field) = self
+            .index(column)
+            .and_then(|i| record.get(i))
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+        else {
+            return Ok(None);
+        };
+        field.parse().map(Some).map_err(|_| {
+            let line = record.position().map_or(0, |position| position.line());
+            format!("Line {line}: invalid `{}` value `{field}`", column.name())
+        })
+    }
+
+    fn required(&self, record: &StringRecord, column: SiteColumn) -> Result<f64, String> {
+        self.value(record, column)?.ok_or_else(|| {
+            let line = record.position().map_or(0, |position| position.line());
+            format!("Line {line}: missing `{}` value", column.name())
+        })
+    }
+
+    /// Site point of a row.
+    fn point(&self, record: &StringRecord) -> Result<Vs30Point, String> {
+        if let Some(flag) = self.value(record, SiteColumn::XvfFlag)?
+            && flag != 0.
+            && flag != 1.
+        {
+            let line = record.position().map_or(0, |position| position.line());
+            return Err(format!(
+                "Line {line}: invalid `xvf_flag` value `{flag}`, expected 0 or 1"
+            ));
+        }
+        Ok(Vs30Point {
+            lon: self.required(record, SiteColumn::Lon)?,
+            lat: self.required(record, SiteColumn::Lat)?,
+            vs30: self.required(record, SiteColumn::Vs30)?,
+            dl: self.value(record, SiteColumn::Dl)?,
+            xvf: self.value(record, SiteColumn::Xvf)?,
+            z1p0: self.value(record, SiteColumn::Z1p0)?,
+            z2p5: self.value(record, SiteColumn::Z2p5)?,
+        })
+    }
+}
+
+impl FromStr for Vs30Columns {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_names(s.split(','), true)
+    }
+}
+
+/// Row of a delimited site file with a site class or Vs30 column.
+#[derive(Debug, Deserialize)]
+struct SiteClassRecord {
+    lon: f64,
+    lat: f64,
+    site: String,
+    #[serde(default)]
+    dl: Option<f64>,
+    #[serde(default)]
+    xvf: Option<f64>,
+    #[serde(default)]
+    z1p0: Option<f64>,
+    #[serde(default)]
+    z2p5: Option<f64>,
+}
+
+/// Reads a list of [`Vs30Point`] instances from a delimited text file with site classes.
+///
+/// The file has the same columns as the files of [`read_vs30_points`], but the third column
+/// holds either a site class (e.g. a NEHRP letter) or a numeric Vs30. Site classes are
+/// converted to their representative Vs30 with `classes`.
+///
+/// # Arguments
+///
+/// * `path` — Path to the input file.
+/// * `delim` — Delimiter character (e.g., `b'\t'` for tab, `b','` for comma).
+/// * `classes` — Representative Vs30 of the site classes.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_site_class_points;
+/// use ground_motion_lib::site_class::SiteClassMap;
+///
+/// let points =
+///     read_site_class_points("tests/data/site_classes.txt", b'\t', &SiteClassMap::default())
+///         .unwrap();
+/// assert_eq!(points[0].vs30, 525.);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, a row is malformed or a site class is not
+/// in `classes`.
+pub fn read_site_class_points<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    classes: &SiteClassMap,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    read_site_class_points_from_reader(open_reader(path)?, delim, classes)
+}
+
+/// Reads a list of [`Vs30Point`] instances from delimited text with site classes in the format
+/// of [`read_site_class_points`], e.g. from standard input.
+///
+/// # Errors
+///
+/// Returns an error if reading fails, a row is malformed or a site class is not in `classes`.
+pub fn read_site_class_points_from_reader<R: Read>(
+    reader: R,
+    delim: u8,
+    classes: &SiteClassMap,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(reader);
 
     let mut points = Vec::new();
+    for (row, result) in rdr.deserialize().enumerate() {
+        let record: SiteClassRecord = result?;
+        let vs30 = classes
+            .site_vs30(&record.site)
+            .map_err(|e| format!("Row {}: {e}", row + 1))?;
+        points.push(Vs30Point {
+            lon: record.lon,
+            lat: record.lat,
+            vs30,
+            dl: record.dl,
+            xvf: record.xvf,
+            z1p0: record.z1p0,
+            z2p5: record.z2p5,
+        });
+    }
+    Ok(points)
+}
+
+/// Reads a site class mapping table from a delimited text file.
+///
+/// The file has no header row and two columns: the site class name and its representative
+/// Vs30 (m/s). The table defines the whole mapping; the NEHRP defaults are not included.
+///
+/// # Arguments
+///
+/// * `path` — Path to the mapping file.
+/// * `delim` — Delimiter character (e.g., `b'\t'` for tab, `b','` for comma).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or a row is malformed.
+pub fn read_site_class_map<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+) -> Result<SiteClassMap, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_reader(file);
 
+    let mut classes = SiteClassMap::new();
     for result in rdr.deserialize() {
-        let record: Vs30Point = result?;
-        points.push(record);
+        let (class, vs30): (String, f64) = result?;
+        classes = classes.with_class(&class, vs30);
+    }
+
+    Ok(classes)
+}
+
+/// GeoTIFF `GTRasterTypeGeoKey` and its `RasterPixelIsPoint` value.
+#[cfg(feature = "geotiff")]
+const GT_RASTER_TYPE_KEY: u16 = 1025;
+#[cfg(feature = "geotiff")]
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+
+/// Reads a single-band GeoTIFF elevation raster (DEM) into an [`ElevationGrid`].
+///
+/// The raster must be in geographic coordinates (longitude and latitude in degrees, e.g.
+/// EPSG:4326) with elevations in meters, and georeferenced with the `ModelPixelScale` and
+/// `ModelTiepoint` tags. Cells equal to the `GDAL_NODATA` value become `NaN`. Requires the
+/// `geotiff` crate feature.
+///
+/// # Arguments
+///
+/// * `path` — Path to the GeoTIFF file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::readers::read_geotiff_dem;
+///
+/// let dem = read_geotiff_dem("srtm30_sakhalin.tif").unwrap();
+/// println!("{} x {} cells", dem.ncols, dem.nrows);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be decoded, the georeferencing tags are missing or the
+/// raster has more than one band.
+#[cfg(feature = "geotiff")]
+pub fn read_geotiff_dem<P: AsRef<Path>>(path: P) -> Result<ElevationGrid, Box<dyn Error>> {
+    use tiff::decoder::{Decoder, DecodingResult};
+    use tiff::tags::Tag;
+
+    let mut decoder = Decoder::new(File::open(path)?)?;
+    let (width, height) = decoder.dimensions()?;
+    let scale = decoder.get_tag_f64_vec(Tag::ModelPixelScaleTag)?;
+    let tiepoint = decoder.get_tag_f64_vec(Tag::ModelTiepointTag)?;
+    if scale.len() < 2 || tiepoint.len() < 6 {
+        return Err("Malformed GeoTIFF pixel scale or tiepoint tag".into());
+    }
+    let pixel_is_point = decoder
+        .find_tag_unsigned_vec::<u16>(Tag::GeoKeyDirectoryTag)?
+        .is_some_and(|keys| {
+            keys.chunks_exact(4).skip(1).any(|key| {
+                key[0] == GT_RASTER_TYPE_KEY && key[1] == 0 && key[3] == RASTER_PIXEL_IS_POINT
+            })
+        });
+    let nodata = decoder
+        .find_tag(Tag::GdalNodata)?
+        .map(|value| value.into_string())
+        .transpose()?
+        .and_then(|value| value.trim_matches(char::from(0)).trim().parse::<f64>().ok());
+
+    let mut values: Vec<f64> = match decoder.read_image()? {
+        DecodingResult::U8(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::U16(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::U32(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::U64(v) => v.into_iter().map(|x| x as f64).collect(),
+        DecodingResult::F16(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::F32(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::F64(v) => v,
+        DecodingResult::I8(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::I16(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::I32(v) => v.into_iter().map(f64::from).collect(),
+        DecodingResult::I64(v) => v.into_iter().map(|x| x as f64).collect(),
+    };
+    if values.len() != width as usize * height as usize {
+        return Err("Only single-band elevation rasters are supported".into());
+    }
+    if let Some(nodata) = nodata {
+        values
+            .iter_mut()
+            .filter(|value| **value == nodata)
+            .for_each(|value| *value = f64::NAN);
+    }
+
+    // Tiepoints refer to the corner of the cell unless the raster is `PixelIsPoint`
+    let offset = if pixel_is_point { 0. } else { 0.5 };
+    let (dlon, dlat) = (scale[0], scale[1]);
+    let lon0 = tiepoint[3] + (offset - tiepoint[0]) * dlon;
+    let lat0 = tiepoint[4] - (offset - tiepoint[1]) * dlat;
+    ElevationGrid::new(lon0, lat0, dlon, dlat, width as usize, values)
+}
+
+/// Reads a GeoTIFF DEM and derives a site grid from its topographic slope.
+///
+/// See [`read_geotiff_dem`] and [`ElevationGrid::vs30_points`]. Requires the `geotiff` crate
+/// feature.
+///
+/// # Errors
+///
+/// Returns an error if the DEM cannot be read.
+#[cfg(feature = "geotiff")]
+pub fn read_geotiff_vs30_points<P: AsRef<Path>>(
+    path: P,
+    regime: SlopeRegime,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    Ok(read_geotiff_dem(path)?.vs30_points(regime))
+}
+
+/// Reads a GMT/netCDF grid (`.grd`, e.g. the USGS global Vs30 grid) into [`Vs30Point`]s.
+///
+/// The grid holds a 2-D Vs30 variable `z` over 1-D longitude (`lon` or `x`) and latitude (`lat`
+/// or `y`) coordinate variables, in the GMT (COARDS) layout with latitude as the slow dimension.
+/// Only the cells within the bounding box `[min_lon, min_lat, max_lon, max_lat]` are read, so
+/// regional sites are extracted from global grids without loading them. Packed values
+/// (`scale_factor`, `add_offset`) are unpacked, and `NaN` or `_FillValue` cells (e.g. oceans)
+/// are skipped. Requires the `netcdf` crate feature.
+///
+/// # Arguments
+///
+/// * `path` — Path to the grid file.
+/// * `bbox` — Bounding box of the extracted cells (decimal degrees), or `None` for all cells.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::readers::read_grd_vs30_points;
+/// use ground_motion_lib::writers::write_vs30_points;
+///
+/// let sites = read_grd_vs30_points("global_vs30.grd", Some([141., 45.5, 145., 54.5])).unwrap();
+/// write_vs30_points("sakhalin_vs30.txt", b'\t', &sites).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, or the coordinate or `z` variables are
+/// missing or not laid out as described above.
+#[cfg(feature = "netcdf")]
+pub fn read_grd_vs30_points<P: AsRef<Path>>(
+    path: P,
+    bbox: Option<[f64; 4]>,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    let file = netcdf::open(path)?;
+    let coordinate = |names: [&str; 2]| {
+        names
+            .into_iter()
+            .find_map(|name| file.variable(name))
+            .ok_or_else(|| {
+                format!(
+                    "Missing `{}` or `{}` coordinate variable",
+                    names[0], names[1]
+                )
+            })
+    };
+    let lon_var = coordinate(["lon", "x"])?;
+    let lat_var = coordinate(["lat", "y"])?;
+    let z = file.variable("z").ok_or("Missing `z` variable")?;
+    let dims: Vec<String> = z.dimensions().iter().map(|dim| dim.name()).collect();
+    let lat_dim = lat_var.dimensions().first().map(|dim| dim.name());
+    let lon_dim = lon_var.dimensions().first().map(|dim| dim.name());
+    if dims.len() != 2 || Some(&dims[0]) != lat_dim.as_ref() || Some(&dims[1]) != lon_dim.as_ref() {
+        return Err("The `z` variable must be laid out as (latitude, longitude)".into());
     }
 
+    let lons: Vec<f64> = lon_var.get_values(..)?;
+    let lats: Vec<f64> = lat_var.get_values(..)?;
+    // Coordinates are monotonic, so the cells within the box form contiguous index ranges
+    let index_range = |values: &[f64], min: f64, max: f64| {
+        let inside = |value: &f64| (min..=max).contains(value);
+        match (
+            values.iter().position(inside),
+            values.iter().rposition(inside),
+        ) {
+            (Some(first), Some(last)) => first..last + 1,
+            _ => 0..0,
+        }
+    };
+    let [min_lon, min_lat, max_lon, max_lat] = bbox.unwrap_or([
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+        f64::INFINITY,
+        f64::INFINITY,
+    ]);
+    let lon_range = index_range(&lons, min_lon, max_lon);
+    let lat_range = index_range(&lats, min_lat, max_lat);
+    if lon_range.is_empty() || lat_range.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let attribute = |name: &str| -> Result<Option<f64>, Box<dyn Error>> {
+        Ok(match z.attribute_value(name).transpose()? {
+            Some(value) => Some(value.try_into()?),
+            None => None,
+        })
+    };
+    let fill_value = attribute("_FillValue")?;
+    let scale = attribute("scale_factor")?.unwrap_or(1.);
+    let offset = attribute("add_offset")?.unwrap_or(0.);
+    let values: Vec<f64> = z.get_values([lat_range.clone(), lon_range.clone()])?;
+
+    let lons = &lons[lon_range];
+    let points = lats[lat_range]
+        .iter()
+        .zip(values.chunks_exact(lons.len()))
+        .flat_map(|(lat, row)| {
+            lons.iter()
+                .zip(row)
+                .filter(move |(_, value)| !value.is_nan() && Some(**value) != fill_value)
+                .map(move |(lon, value)| {
+                    Vs30Point::new(*lon, *lat, value * scale + offset, None, None)
+                })
+        })
+        .collect();
     Ok(points)
 }
+
+/// Reads an Arc/Info ASCII grid (`.asc`) into a [`RasterGrid`].
+///
+/// The header holds `ncols`, `nrows`, the lower-left corner (`xllcorner`, `yllcorner`) or
+/// cell center (`xllcenter`, `yllcenter`), the `cellsize` (or `dx` and `dy`) and an optional
+/// `NODATA_value`, followed by the cell values row by row from north to south. Keywords are
+/// case-insensitive. Cells equal to the nodata value become `NaN`.
+///
+/// # Arguments
+///
+/// * `path` — Path to the grid file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::readers::read_esri_ascii_grid;
+///
+/// let grid = read_esri_ascii_grid("vs30.asc").unwrap();
+/// println!("{} x {} cells", grid.ncols, grid.nrows);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, a header keyword is missing or unknown, or the
+/// number of values does not match `ncols` × `nrows`.
+pub fn read_esri_ascii_grid<P: AsRef<Path>>(path: P) -> Result<RasterGrid, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+    let mut header: Vec<(String, f64)> = Vec::new();
+    let mut lines = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .peekable();
+    while let Some(line) = lines.next_if(|line| {
+        line.trim_start()
+            .starts_with(|c: char| c.is_ascii_alphabetic())
+    }) {
+        let mut fields = line.split_whitespace();
+        let (Some(key), Some(value), None) = (fields.next(), fields.next(), fields.next()) else {
+            return Err(format!("Malformed ASCII grid header line `{line}`").into());
+        };
+        header.push((key.to_ascii_lowercase(), value.parse()?));
+    }
+    let value = |key: &str| header.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+    if let Some((key, _)) = header.iter().find(|(key, _)| {
+        ![
+            "ncols",
+            "nrows",
+            "xllcorner",
+            "yllcorner",
+            "xllcenter",
+            "yllcenter",
+            "cellsize",
+            "dx",
+            "dy",
+            "nodata_value",
+        ]
+        .contains(&key.as_str())
+    }) {
+        return Err(format!("Unknown ASCII grid header keyword `{key}`").into());
+    }
+    let required = |key: &str| value(key).ok_or(format!("Missing ASCII grid header `{key}`"));
+    let ncols = required("ncols")? as usize;
+    let nrows = required("nrows")? as usize;
+    let (dlon, dlat) = match (value("cellsize"), value("dx"), value("dy")) {
+        (Some(size), _, _) => (size, size),
+        (None, Some(dx), Some(dy)) => (dx, dy),
+        _ => return Err("Missing ASCII grid header `cellsize`".into()),
+    };
+    // Cell centers of the south-west cell
+    let (x_center, y_center) = match (value("xllcenter"), value("yllcenter")) {
+        (Some(x), Some(y)) => (x, y),
+        _ => (
+            required("xllcorner")? + dlon / 2.,
+            required("yllcorner")? + dlat / 2.,
+        ),
+    };
+
+    let nodata = value("nodata_value");
+    let values = lines
+        .flat_map(str::split_whitespace)
+        .map(|token| {
+            let value: f64 = token.parse()?;
+            Ok(if Some(value) == nodata {
+                f64::NAN
+            } else {
+                value
+            })
+        })
+        .collect::<Result<Vec<f64>, Box<dyn Error>>>()?;
+    if values.len() != ncols * nrows {
+        return Err(format!(
+            "ASCII grid holds {} values instead of {ncols} x {nrows}",
+            values.len()
+        )
+        .into());
+    }
+    let lat0 = y_center + (nrows as f64 - 1.) * dlat;
+    RasterGrid::new(x_center, lat0, dlon, dlat, ncols, values)
+}
+
+/// Reads an Arc/Info ASCII Vs30 grid, and optionally a basin depth (`dl`) grid of the same
+/// geometry, into [`Vs30Point`]s.
+///
+/// See [`read_esri_ascii_grid`] and [`RasterGrid::vs30_points`]: nodata Vs30 cells are
+/// skipped, and nodata depth cells leave the depth of the site empty.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::readers::read_esri_ascii_vs30_points;
+///
+/// let sites = read_esri_ascii_vs30_points("vs30.asc", Some("dl.asc")).unwrap();
+/// println!("{} sites", sites.len());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if a grid cannot be read or the geometries of both grids differ.
+pub fn read_esri_ascii_vs30_points<P: AsRef<Path>>(
+    vs30_path: P,
+    dl_path: Option<P>,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    let vs30 = read_esri_ascii_grid(vs30_path)?;
+    let dl = dl_path.map(read_esri_ascii_grid).transpose()?;
+    vs30.vs30_points(dl.as_ref())
+}
+
+/// Row of a delimited station file.
+#[derive(Debug, Deserialize)]
+struct StationRecord {
+    lon: f64,
+    lat: f64,
+    imt: String,
+    value: f64,
+    id: String,
+}
+
+/// Parse the name of an intensity measure type.
+///
+/// Accepts ShakeMap names (`pga`, `pgv`, `mmi`, `sa(0.3)`, `psa(0.3)`, case-insensitive) and the
+/// names written by [`crate::writers`] (`Pga`, `Psa(0.3)`, ...).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::gmm::GmpePointKind;
+/// use ground_motion_lib::readers::parse_imt;
+///
+/// assert_eq!(parse_imt("SA(0.3)"), Some(GmpePointKind::Psa { period: 0.3 }));
+/// assert_eq!(parse_imt("Pgv"), Some(GmpePointKind::Pgv));
+/// assert_eq!(parse_imt("pgx"), None);
+/// ```
+pub fn parse_imt(name: &str) -> Option<GmpePointKind> {
+    let lower = name.trim().to_ascii_lowercase();
+    match lower.as_str() {
+        "pga" => Some(GmpePointKind::Pga),
+        "pgv" => Some(GmpePointKind::Pgv),
+        "pgd" => Some(GmpePointKind::Pgd),
+        "mmi" | "intensity" => Some(GmpePointKind::Mmi),
+        _ => lower
+            .strip_prefix("psa(")
+            .or_else(|| lower.strip_prefix("sa("))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .and_then(|period| period.parse::<f64>().ok())
+            .map(|period| GmpePointKind::Psa { period })
+            .or_else(|| name.trim().parse().ok()),
+    }
+}
+
+/// Reads station observations from a ShakeMap `stationlist.json` or a delimited text file.
+///
+/// Files with a `.json` extension are read as ShakeMap station lists; any other file is read as
+/// a delimited station file without a header row (see the [module documentation](self)).
+///
+/// # Arguments
+///
+/// * `path` — Path to the input file.
+/// * `delim` — Delimiter character of delimited files (ignored for JSON files).
+///
+/// # Returns
+///
+/// One [`StationPoint`] per observed type of motion and station.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_stations;
+///
+/// let stations = read_stations("tests/data/stations.csv", b',').unwrap();
+/// println!("First observation: {:?}", stations[0]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The file cannot be opened.
+/// - A row or feature is malformed, or names an unknown type of motion.
+pub fn read_stations<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+) -> Result<Vec<StationPoint>, Box<dyn Error>> {
+    let path = path.as_ref();
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if is_json {
+        return read_stationlist_json(path);
+    }
+
+    let file = File::open(path)?;
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+
+    let mut stations = Vec::new();
+    for result in rdr.deserialize() {
+        let record: StationRecord = result?;
+        let kind = parse_imt(&record.imt)
+            .ok_or_else(|| format!("Unknown ground motion type `{}`", record.imt))?;
+        stations.push(StationPoint::new(
+            &record.id,
+            record.lon,
+            record.lat,
+            record.value,
+            kind,
+        ));
+    }
+
+    Ok(stations)
+}
+
+/// Reads a declustered earthquake catalog from a delimited text file.
+///
+/// The file has no header row (see the [module documentation](self)).
+///
+/// # Arguments
+///
+/// * `path` — Path to the input file.
+/// * `delim` — Delimiter character (e.g., `b'\t'` for tab, `b','` for comma).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_catalog;
+///
+/// let events = read_catalog("tests/data/catalog.csv", b',').unwrap();
+/// println!("First event: {:?}", events[0]);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or a row is malformed.
+pub fn read_catalog<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+) -> Result<Vec<CatalogEvent>, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+
+    let mut events = Vec::new();
+    for result in rdr.deserialize() {
+        let event: CatalogEvent = result?;
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+/// Reads the top of a subducting slab into a [`SlabModel`].
+///
+/// See the [module documentation](self#slab-files) for the file format.
+///
+/// # Arguments
+///
+/// * `path` — Path to the input file.
+/// * `delim` — Delimiter character (e.g., `b'\t'` for tab, `b','` for comma).
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_slab_model;
+///
+/// let slab = read_slab_model("tests/data/slab.xyz", b',').unwrap();
+/// assert!(slab.depth_at(143.0, 50.0).is_some());
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened, a row is malformed or no node has a depth.
+pub fn read_slab_model<P: AsRef<Path>>(path: P, delim: u8) -> Result<SlabModel, Box<dyn Error>> {
+    let file = File::open(path)?;
+    let mut rdr = ReaderBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .trim(csv::Trim::All)
+        .from_reader(file);
+
+    let mut nodes = Vec::new();
+    for result in rdr.deserialize() {
+        let (lon, lat, depth): (f64, f64, f64) = result?;
+        if !depth.is_nan() {
+            nodes.push((lon, lat, depth.abs()));
+        }
+    }
+    if nodes.is_empty() {
+        return Err("Slab file has no nodes with a depth".into());
+    }
+
+    Ok(SlabModel::new(nodes))
+}
+
+/// Reads a tectonic zonation from a GeoJSON file.
+///
+/// See the [module documentation](self#zonation-files) for the file format. Zones keep the
+/// order of the features; unnamed zones are named after their position in the file.
+///
+/// # Arguments
+///
+/// * `path` — Path to the GeoJSON file.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_zonation;
+///
+/// let zonation = read_zonation("tests/data/zonation.geojson").unwrap();
+/// let zone = zonation.zone_at(142.8, 52.6).unwrap();
+/// assert_eq!(zone.config, "config_mf2013_crustal_pga");
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or parsed, or a feature has no `config`
+/// property or no polygon geometry.
+pub fn read_zonation<P: AsRef<Path>>(path: P) -> Result<TectonicZonation, Box<dyn Error>> {
+    let json: Value = serde_json::from_reader(File::open(path)?)?;
+    let features = json["features"]
+        .as_array()
+        .ok_or("Zonation has no `features` array")?;
+
+    let mut zones = Vec::new();
+    for (i, feature) in features.iter().enumerate() {
+        let properties = &feature["properties"];
+        let name = match properties["name"].as_str() {
+            Some(name) => name.to_string(),
+            None => format!("zone_{}", i + 1),
+        };
+        let config = properties["config"]
+            .as_str()
+            .ok_or_else(|| format!("Zone `{name}` has no `config` property"))?
+            .to_string();
+        let geometry = &feature["geometry"];
+        let coordinates = &geometry["coordinates"];
+        let polygons = match geometry["type"].as_str() {
+            Some("Polygon") => vec![geojson_polygon(coordinates)],
+            Some("MultiPolygon") => coordinates
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(geojson_polygon)
+                .collect(),
+            _ => return Err(format!("Zone `{name}` is not a Polygon or MultiPolygon").into()),
+        };
+        let polygons = polygons
+            .into_iter()
+            .collect::<Option<Vec<Polygon<f64>>>>()
+            .ok_or_else(|| format!("Zone `{name}` has malformed coordinates"))?;
+        zones.push(TectonicZone {
+            name,
+            config,
+            area: MultiPolygon::new(polygons),
+        });
+    }
+
+    Ok(TectonicZonation::new(zones))
+}
+
+/// Reads a USGS finite-fault solution (`.fsp` file) into a [`FiniteFaultSolution`].
+///
+/// See the [module documentation](self#finite-fault-files) for the interpreted header lines.
+///
+/// # Arguments
+///
+/// * `path` — Path to the `.fsp` file.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_fsp;
+///
+/// let solution = read_fsp("tests/data/two_segments.fsp").unwrap();
+/// assert_eq!(solution.rupture.segments.len(), 2);
+/// let rrup = solution.rupture.rrup(143.3, 52.1);
+/// println!("Mw {}: Rrup = {rrup:.1} km", solution.magnitude);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, the hypocenter, the magnitude or the geometry
+/// of a segment is missing, or a subfault row is malformed.
+pub fn read_fsp<P: AsRef<Path>>(path: P) -> Result<FiniteFaultSolution, Box<dyn Error>> {
+    let content = fs::read_to_string(path)?;
+
+    let mut hypocenter = None;
+    let mut magnitude = None;
+    // Geometry of single-segment files: strike, dip, length, width, top depth
+    let mut mech = [None; 5];
+    let mut segments: Vec<FspSegment> = Vec::new();
+    for line in content.lines() {
+        let Some(header) = line.trim_start().strip_prefix('%') else {
+            let values = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<f64>, _>>()?;
+            if values.is_empty() {
+                continue;
+            }
+            if values.len() < 5 {
+                return Err(format!("Malformed subfault row `{}`", line.trim()).into());
+            }
+            if segments.is_empty() {
+                segments.push(FspSegment::new(mech));
+            }
+            let segment = segments.last_mut().ok_or("No fault segment")?;
+            segment.subfaults.push((values[1], values[0], values[4]));
+            continue;
+        };
+        let segment = segments.last_mut().map(|segment| &mut segment.geometry);
+        match header.split_whitespace().next() {
+            Some("Loc") => {
+                if let (Some(lat), Some(lon), Some(depth)) = (
+                    fsp_value(header, "LAT"),
+                    fsp_value(header, "LON"),
+                    fsp_value(header, "DEP"),
+                ) {
+                    hypocenter = Some((lon, lat, depth));
+                }
+            }
+            Some("Size") => {
+                magnitude = fsp_value(header, "Mw");
+                mech[2] = fsp_value(header, "LEN");
+                mech[3] = fsp_value(header, "WID");
+            }
+            Some("Mech") => {
+                mech[0] = fsp_value(header, "STRK");
+                mech[1] = fsp_value(header, "DIP");
+                mech[4] = fsp_value(header, "Htop");
+            }
+            Some("SEGMENT") => {
+                let geometry = [
+                    fsp_value(header, "STRIKE"),
+                    fsp_value(header, "DIP"),
+                    None,
+                    None,
+                    None,
+                ];
+                segments.push(FspSegment::new(geometry));
+            }
+            Some("LEN") => {
+                if let Some(geometry) = segment {
+                    geometry[2] = fsp_value(header, "LEN");
+                    geometry[3] = fsp_value(header, "WID");
+                }
+            }
+            Some("depth") => {
+                if let Some(geometry) = segment {
+                    geometry[4] = fsp_value(header, "Z2top");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (lon, lat, depth) = hypocenter.ok_or("Missing hypocenter (`Loc` line)")?;
+    let magnitude = magnitude.ok_or("Missing magnitude (`Mw` of the `Size` line)")?;
+    let segments = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            let [
+                Some(strike),
+                Some(dip),
+                Some(length),
+                Some(width),
+                Some(ztor),
+            ] = segment.geometry
+            else {
+                return Err(format!("Incomplete geometry of fault segment {}", i + 1).into());
+            };
+            let rupture = Rupture::new(strike, dip, length, width).with_ztor(ztor);
+            RuptureSegment::from_subfaults(&segment.subfaults, rupture)
+                .ok_or_else(|| format!("Fault segment {} has no subfaults", i + 1).into())
+        })
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    if segments.is_empty() {
+        return Err("No subfaults found".into());
+    }
+
+    Ok(FiniteFaultSolution {
+        lon,
+        lat,
+        depth,
+        magnitude,
+        rupture: MultiSegmentRupture::new(segments),
+    })
+}
+
+/// Fault segment of an `.fsp` file being read.
+struct FspSegment {
+    /// Strike, dip, length, width and top depth.
+    geometry: [Option<f64>; 5],
+    /// Subfault centers `(lon, lat, depth)`.
+    subfaults: Vec<(f64, f64, f64)>,
+}
+
+impl FspSegment {
+    fn new(geometry: [Option<f64>; 5]) -> Self {
+        Self {
+            geometry,
+            subfaults: Vec::new(),
+        }
+    }
+}
+
+/// Reads the preferred origin and magnitude of a QuakeML event into an [`Earthquake`].
+///
+/// See the [module documentation](self#quakeml-event-files) for the interpreted elements.
+///
+/// # Arguments
+///
+/// * `path` — Path to the QuakeML file.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::gmm::Magnitude;
+/// use ground_motion_lib::readers::read_quakeml_event;
+///
+/// let eq = read_quakeml_event("tests/data/event.xml").unwrap();
+/// assert_eq!(eq.magnitude_kind, Magnitude::Mw);
+/// println!("Mw {} at {} km depth", eq.magnitude, eq.depth);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, has no event with an origin and a
+/// magnitude, a coordinate, the depth or the magnitude value is missing, or the magnitude type
+/// is not supported.
+pub fn read_quakeml_event<P: AsRef<Path>>(path: P) -> Result<Earthquake, Box<dyn Error>> {
+    xml_event(&XmlElement::parse(&fs::read_to_string(path)?)?, false)
+}
+
+/// Reads the preferred origin and magnitude of a SeisComP XML (SCML) event into an
+/// [`Earthquake`].
+///
+/// See the [module documentation](self#seiscomp-xml-event-files) for the interpreted elements.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_scml_event;
+///
+/// let eq = read_scml_event("tests/data/event.scml").unwrap();
+/// println!("{} {} at {} km depth", eq.magnitude_kind, eq.magnitude, eq.depth);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed, has no event with an origin and a
+/// magnitude, a coordinate, the depth or the magnitude value is missing, or the magnitude type
+/// is not supported.
+pub fn read_scml_event<P: AsRef<Path>>(path: P) -> Result<Earthquake, Box<dyn Error>> {
+    xml_event(&XmlElement::parse(&fs::read_to_string(path)?)?, true)
+}
+
+/// Reads the preferred origin and magnitude of a QuakeML or SeisComP XML event into an
+/// [`Earthquake`], detecting the format by the root element (`seiscomp` for SeisComP XML).
+///
+/// # Errors
+///
+/// Returns an error as [`read_quakeml_event`] and [`read_scml_event`].
+pub fn read_xml_event<P: AsRef<Path>>(path: P) -> Result<Earthquake, Box<dyn Error>> {
+    let document = XmlElement::parse(&fs::read_to_string(path)?)?;
+    let scml = document.child("seiscomp").is_some();
+    xml_event(&document, scml)
+}
+
+/// Preferred origin and magnitude of the first event of a QuakeML or SeisComP XML document.
+fn xml_event(document: &XmlElement, scml: bool) -> Result<Earthquake, Box<dyn Error>> {
+    let format = if scml { "SeisComP XML" } else { "QuakeML" };
+    let event = document
+        .descendants("event")
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("{format} file has no event"))?;
+    // SeisComP XML lists origins next to the events, with the magnitudes nested in them
+    let origin_id = event
+        .child_text("preferredOriginID")
+        .or_else(|| event.child_text("originReference"));
+    let origin = origin_id
+        .and_then(|id| document.find_public("origin", id))
+        .or_else(|| if scml { document } else { event }.first_public("origin"))
+        .ok_or_else(|| format!("{format} event has no origin"))?;
+    let magnitude = event
+        .child_text("preferredMagnitudeID")
+        .and_then(|id| document.find_public("magnitude", id))
+        .or_else(|| if scml { origin } else { event }.first_public("magnitude"))
+        .ok_or_else(|| format!("{format} event has no magnitude"))?;
+
+    let value = |element: &XmlElement, name: &str| {
+        element
+            .child(name)
+            .and_then(|quantity| quantity.child_text("value"))
+            .ok_or_else(|| format!("{format} {} has no {name}", element.name))?
+            .trim()
+            .parse::<f64>()
+            .map_err(|err| format!("Malformed {format} {name}: {err}"))
+    };
+    let magnitude_kind = match magnitude.child_text("type") {
+        Some(kind) => quakeml_magnitude_type(kind)?,
+        None => Magnitude::Mw,
+    };
+    // QuakeML depths are in meters, SeisComP XML depths in km
+    let depth = value(origin, "depth")? / if scml { 1. } else { 1000. };
+    Ok(Earthquake::new(
+        value(origin, "longitude")?,
+        value(origin, "latitude")?,
+        depth,
+        value(magnitude, if scml { "magnitude" } else { "mag" })?,
+        magnitude_kind,
+    ))
+}
+
+/// Reads the origin and preferred magnitude of a USGS event GeoJSON file into an [`Earthquake`].
+///
+/// See the [module documentation](self#usgs-event-files) for the file format.
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::readers::read_usgs_geojson_event;
+///
+/// let eq = read_usgs_geojson_event("tests/data/usgs_event.geojson").unwrap();
+/// println!("M {} at {} km depth", eq.magnitude, eq.depth);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, or the event cannot be parsed, see
+/// [`parse_usgs_geojson_event`].
+pub fn read_usgs_geojson_event<P: AsRef<Path>>(path: P) -> Result<Earthquake, Box<dyn Error>> {
+    parse_usgs_geojson_event(&fs::read_to_string(path)?)
+}
+
+/// Parses the origin and preferred magnitude of a USGS event GeoJSON document, e.g. the body of
+/// a ComCat query, into an [`Earthquake`].
+///
+/// # Errors
+///
+/// Returns an error if the document is not valid JSON, has no event feature, the coordinates
+/// or the magnitude are missing, or the magnitude type is not supported.
+pub fn parse_usgs_geojson_event(content: &str) -> Result<Earthquake, Box<dyn Error>> {
+    let json: Value = serde_json::from_str(content)?;
+    let feature = match json["type"].as_str() {
+        Some("FeatureCollection") => json["features"]
+            .get(0)
+            .ok_or("USGS event feed has no features")?,
+        _ => &json,
+    };
+    let coordinates = &feature["geometry"]["coordinates"];
+    let coordinate =
+        |i: usize| number(&coordinates[i]).ok_or("USGS event has no point geometry with a depth");
+    let properties = &feature["properties"];
+    let magnitude = number(&properties["mag"]).ok_or("USGS event has no magnitude")?;
+    let magnitude_kind = match properties["magType"].as_str() {
+        Some(kind) => quakeml_magnitude_type(kind)?,
+        None => Magnitude::Mw,
+    };
+    Ok(Earthquake::new(
+        coordinate(0)?,
+        coordinate(1)?,
+        coordinate(2)?,
+        magnitude,
+        magnitude_kind,
+    ))
+}
+
+/// Magnitude scale of a QuakeML or USGS magnitude type.
+fn quakeml_magnitude_type(kind: &str) -> Result<Magnitude, Box<dyn Error>> {
+    let lower = kind.trim().to_ascii_lowercase();
+    match lower.as_str() {
+        "m" => return Ok(Magnitude::Mw),
+        "mj" | "mjma" => return Ok(Magnitude::Mjma),
+        _ => {}
+    }
+    let scale = ["mw", "ml", "ms", "mb"]
+        .into_iter()
+        .find(|prefix| lower.starts_with(prefix))
+        .ok_or_else(|| format!("Unsupported QuakeML magnitude type `{kind}`"))?;
+    Ok(scale.parse()?)
+}
+
+/// Element of a parsed XML document, named without its namespace prefix.
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<XmlElement>,
+}
+
+impl XmlElement {
+    /// Parse a document into an unnamed element holding the root element.
+    fn parse(content: &str) -> Result<Self, Box<dyn Error>> {
+        let mut reader = quick_xml::Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+        let mut stack = vec![XmlElement::default()];
+        loop {
+            match reader.read_event()? {
+                Event::Start(start) => stack.push(Self::from_start(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::from_start(&start)?;
+                    stack
+                        .last_mut()
+                        .ok_or("Malformed XML")?
+                        .children
+                        .push(element);
+                }
+                Event::End(_) => {
+                    let element = stack.pop().ok_or("Malformed XML")?;
+                    stack
+                        .last_mut()
+                        .ok_or("Malformed XML")?
+                        .children
+                        .push(element);
+                }
+                Event::Text(text) => {
+                    let text = text.unescape()?;
+                    stack
+                        .last_mut()
+                        .ok_or("Malformed XML")?
+                        .text
+                        .push_str(&text);
+                }
+                Event::CData(data) => {
+                    let text = std::str::from_utf8(&data)?;
+                    stack.last_mut().ok_or("Malformed XML")?.text.push_str(text);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        match (stack.pop(), stack.is_empty()) {
+            (Some(document), true) => Ok(document),
+            _ => Err("Unclosed XML elements".into()),
+        }
+    }
+
+    fn from_start(start: &BytesStart) -> Result<Self, Box<dyn Error>> {
+        let mut attributes = Vec::new();
+        for attribute in start.attributes() {
+            let attribute = attribute?;
+            let key = std::str::from_utf8(attribute.key.local_name().as_ref())?.to_string();
+            attributes.push((key, attribute.unescape_value()?.into_owned()));
+        }
+        Ok(Self {
+            name: std::str::from_utf8(start.local_name().as_ref())?.to_string(),
+            attributes,
+            ..Self::default()
+        })
+    }
+
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|child| child.text.as_str())
+    }
+
+    /// Elements with the name at any depth below this one, in document order.
+    fn descendants(&self, name: &str) -> Vec<&XmlElement> {
+        let mut found = Vec::new();
+        for child in &self.children {
+            if child.name == name {
+                found.push(child);
+            }
+            found.extend(child.descendants(name));
+        }
+        found
+    }
+
+    /// Element with the name and the `publicID` at any depth below this one.
+    fn find_public(&self, name: &str, public_id: &str) -> Option<&XmlElement> {
+        self.descendants(name)
+            .into_iter()
+            .find(|element| element.attribute("publicID") == Some(public_id.trim()))
+    }
+
+    /// First element with the name and any `publicID` at any depth below this one.
+    fn first_public(&self, name: &str) -> Option<&XmlElement> {
+        self.descendants(name)
+            .into_iter()
+            .find(|element| element.attribute("publicID").is_some())
+    }
+}
+
+/// Reads one type of motion of a ShakeMap `grid.xml` file into prediction points.
+///
+/// See the [module documentation](self#shakemap-grid-files) for the file format. Points keep
+/// the order of the grid rows, so maps computed at the same nodes (see
+/// [`read_shakemap_grid_vs30_points`]) can be compared point by point.
+///
+/// # Arguments
+///
+/// * `path` — Path to the `grid.xml` file.
+/// * `kind` — Type of motion to read, e.g. [`GmpePointKind::Pga`].
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::gmm::{Earthquake, GmpePointKind};
+/// use ground_motion_lib::readers::{read_shakemap_grid, read_shakemap_grid_vs30_points};
+/// use ground_motion_lib::vectorized::calc_gmpe_vec;
+///
+/// let path = "tests/data/shakemap_grid.xml";
+/// let official = read_shakemap_grid(path, GmpePointKind::Pga).unwrap();
+/// let sites = read_shakemap_grid_vs30_points(path).unwrap();
+///
+/// let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let eq = Earthquake::new_mw(142.1, 50.05, 10., 6.5);
+/// let ours = calc_gmpe_vec(&sites, gmpe, &eq);
+/// let ratios: Vec<f64> = ours.iter().zip(&official).map(|(a, b)| a.value / b.value).collect();
+/// assert_eq!(ratios.len(), 6);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has no `LON`, `LAT` or requested type of motion
+/// column, or a row is malformed.
+pub fn read_shakemap_grid<P: AsRef<Path>>(
+    path: P,
+    kind: GmpePointKind,
+) -> Result<Vec<GmpePoint>, Box<dyn Error>> {
+    let grid = ShakeMapGrid::read(path.as_ref())?;
+    let (lon, lat) = (grid.column("LON")?, grid.column("LAT")?);
+    let value = grid
+        .fields
+        .iter()
+        .position(|name| shakemap_grid_imt(name) == Some(kind))
+        .ok_or_else(|| format!("ShakeMap grid has no {kind} column"))?;
+    let sigma = grid
+        .fields
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(&format!("STD{}", grid.fields[value])));
+
+    Ok(grid
+        .rows
+        .iter()
+        .map(|row| GmpePoint {
+            lon: row[lon],
+            lat: row[lat],
+            value: row[value],
+            kind,
+            sigma: sigma.map(|sigma| row[sigma]),
+        })
+        .collect())
+}
+
+/// Reads the nodes of a ShakeMap `grid.xml` file and their Vs30 (`SVEL` column) into site
+/// points.
+///
+/// Predictions at these sites share the nodes of the ShakeMap, see [`read_shakemap_grid`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read, has no `LON`, `LAT` or `SVEL` column, or a row
+/// is malformed.
+pub fn read_shakemap_grid_vs30_points<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<Vs30Point>, Box<dyn Error>> {
+    let grid = ShakeMapGrid::read(path.as_ref())?;
+    let (lon, lat, vs30) = (
+        grid.column("LON")?,
+        grid.column("LAT")?,
+        grid.column("SVEL")?,
+    );
+    Ok(grid
+        .rows
+        .iter()
+        .map(|row| Vs30Point::new(row[lon], row[lat], row[vs30], None, None))
+        .collect())
+}
+
+/// Columns and rows of a ShakeMap `grid.xml` file.
+struct ShakeMapGrid {
+    fields: Vec<String>,
+    rows: Vec<Vec<f64>>,
+}
+
+impl ShakeMapGrid {
+    fn read(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = fs::read_to_string(path)?;
+
+        let mut fields = Vec::new();
+        for (start, _) in content.match_indices("<grid_field") {
+            let tag = &content[start..];
+            let tag = &tag[..tag.find('>').ok_or("Unterminated `grid_field` element")?];
+            let index: usize = xml_attribute(tag, "index")
+                .ok_or("`grid_field` element has no index")?
+                .parse()?;
+            let name = xml_attribute(tag, "name").ok_or("`grid_field` element has no name")?;
+            fields.push((index, name.to_string()));
+        }
+        fields.sort_by_key(|(index, _)| *index);
+        if fields
+            .iter()
+            .enumerate()
+            .any(|(i, (index, _))| *index != i + 1)
+        {
+            return Err("ShakeMap grid field indices are not 1, 2, 3, ...".into());
+        }
+        let fields: Vec<String> = fields.into_iter().map(|(_, name)| name).collect();
+
+        let data = content
+            .split_once("<grid_data>")
+            .and_then(|(_, rest)| rest.split_once("</grid_data>"))
+            .ok_or("ShakeMap grid has no `grid_data` element")?
+            .0;
+        let mut rows = Vec::new();
+        for line in data.lines() {
+            let row = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<f64>, _>>()?;
+            if row.is_empty() {
+                continue;
+            }
+            if row.len() != fields.len() {
+                return Err(format!(
+                    "ShakeMap grid row `{}` does not have {} values",
+                    line.trim(),
+                    fields.len()
+                )
+                .into());
+            }
+            rows.push(row);
+        }
+        Ok(Self { fields, rows })
+    }
+
+    /// Position of a column, by case-insensitive name.
+    fn column(&self, name: &str) -> Result<usize, Box<dyn Error>> {
+        self.fields
+            .iter()
+            .position(|field| field.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("ShakeMap grid has no {name} column").into())
+    }
+}
+
+/// Type of motion of a ShakeMap grid column (`PGA`, `PSA03`, `PSA0P3`, ...).
+fn shakemap_grid_imt(name: &str) -> Option<GmpePointKind> {
+    let upper = name.to_ascii_uppercase();
+    match upper.strip_prefix("PSA") {
+        Some(period) if period.contains('P') => period.replace('P', ".").parse().ok(),
+        Some(period) if period.len() >= 2 && period.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{}.{}", &period[..1], &period[1..]).parse().ok()
+        }
+        Some(_) => None,
+        None => return parse_imt(name),
+    }
+    .map(|period| GmpePointKind::Psa { period })
+}
+
+/// Value of an attribute of an XML start tag.
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let pattern = format!("{name}=\"");
+    tag.match_indices(&pattern)
+        .find(|(start, _)| tag[..*start].ends_with(char::is_whitespace))
+        .and_then(|(start, _)| tag[start + pattern.len()..].split('"').next())
+}
+
+/// Value of a `KEY = value` pair of an `.fsp` header line.
+fn fsp_value(header: &str, key: &str) -> Option<f64> {
+    let tokens: Vec<&str> = header.split_whitespace().collect();
+    tokens
+        .windows(3)
+        .find(|w| w[0] == key && w[1] == "=")
+        .and_then(|w| w[2].trim_end_matches(',').parse().ok())
+}
+
+/// Polygon of GeoJSON polygon coordinates (exterior ring followed by holes).
+fn geojson_polygon(coordinates: &Value) -> Option<Polygon<f64>> {
+    let mut rings = coordinates.as_array()?.iter().map(|ring| {
+        ring.as_array()?
+            .iter()
+            .map(|position| Some((position[0].as_f64()?, position[1].as_f64()?)))
+            .collect::<Option<Vec<(f64, f64)>>>()
+            .map(LineString::from)
+    });
+    let exterior = rings.next()??;
+    let interiors = rings.collect::<Option<Vec<_>>>()?;
+    Some(Polygon::new(exterior, interiors))
+}
+
+/// Reads a ShakeMap `stationlist.json` file.
+fn read_stationlist_json(path: &Path) -> Result<Vec<StationPoint>, Box<dyn Error>> {
+    let json: Value = serde_json::from_reader(File::open(path)?)?;
+    let features = json["features"]
+        .as_array()
+        .ok_or("Station list has no `features` array")?;
+
+    let mut stations = Vec::new();
+    for feature in features {
+        let coordinates = &feature["geometry"]["coordinates"];
+        let (Some(lon), Some(lat)) = (coordinates[0].as_f64(), coordinates[1].as_f64()) else {
+            return Err("Station feature without point coordinates".into());
+        };
+        let properties = &feature["properties"];
+        let id = match (feature["id"].as_str(), properties["code"].as_str()) {
+            (Some(id), _) => id.to_string(),
+            (None, Some(code)) => match properties["network"].as_str() {
+                Some(network) if !code.starts_with(network) => format!("{network}.{code}"),
+                _ => code.to_string(),
+            },
+            (None, None) => String::new(),
+        };
+        let vs30 = number(&properties["vs30"]);
+
+        // Largest horizontal amplitude per type of motion
+        let mut amplitudes: Vec<(GmpePointKind, f64)> = Vec::new();
+        let channels = properties["channels"].as_array().into_iter().flatten();
+        for channel in channels {
+            let name = channel["name"].as_str().unwrap_or_default();
+            if name.ends_with(['Z', 'z']) {
+                continue;
+            }
+            for amplitude in channel["amplitudes"].as_array().into_iter().flatten() {
+                if !is_unflagged(&amplitude["flag"]) {
+                    continue;
+                }
+                let (Some(kind), Some(value)) = (
+                    amplitude["name"].as_str().and_then(parse_imt),
+                    number(&amplitude["value"]),
+                ) else {
+                    continue;
+                };
+                match amplitudes.iter_mut().find(|(k, _)| *k == kind) {
+                    Some((_, max)) => *max = max.max(value),
+                    None => amplitudes.push((kind, value)),
+                }
+            }
+        }
+        if amplitudes.is_empty()
+            && let Some(intensity) = number(&properties["intensity"])
+        {
+            amplitudes.push((GmpePointKind::Mmi, intensity));
+        }
+
+        for (kind, value) in amplitudes {
+            let mut station = StationPoint::new(&id, lon, lat, value, kind);
+            station.vs30 = vs30;
+            stations.push(station);
+        }
+    }
+
+    Ok(stations)
+}
+
+/// Numeric JSON value; ShakeMap writes some numbers as strings and missing values as `"null"`.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+    .filter(|v: &f64| v.is_finite())
+}
+
+/// Whether an amplitude flag marks a usable amplitude (`0` or missing).
+fn is_unflagged(flag: &Value) -> bool {
+    match flag {
+        Value::Null => true,
+        Value::String(s) => s.is_empty() || s == "0",
+        Value::Number(n) => n.as_f64() == Some(0.),
+        Value::Bool(b) => !b,
+        _ => false,
+    }
+}
@@ -0,0 +1,572 @@
+//! Finite rupture geometry, finite-fault distances and hanging-wall inputs.
+//!
+//! This module defines a planar [`Rupture`] that can be attached to an
+//! [`Earthquake`](crate::gmm::Earthquake), and the [`HangingWallInputs`] derived from it for a
+//! given site. NGA-type models use these inputs to evaluate their hanging-wall terms. For point
+//! sources (no rupture attached) no inputs are available and models fall back to null
+//! hanging-wall terms.
+//!
+//! The rupture distance `Rrup` (closest distance to the rupture plane) and the Joyner–Boore
+//! distance `Rjb` (closest distance to its surface projection) are computed to the plane, see
+//! [`Earthquake::rrup`] and [`Earthquake::rjb`]. For large events they are much shorter than
+//! the hypocentral and epicentral distances of a point source:
+//!
+//! ```text
+//! Rjb  = sqrt(Ry0² + d_h²),   d_h = distance from the site to [Rx_top, Rx_top + W·cos(δ)]
+//! Rrup = sqrt(Ry0² + |P − w·ê|²),   w = clamp(P · ê, 0, W),   ê = (cos(δ), sin(δ))
+//! ```
+//!
+//! where `P` is the site position relative to the top edge in the vertical plane perpendicular
+//! to strike (horizontal offset down-dip, depth) and `ê` the down-dip unit vector.
+//!
+//! Published finite-fault solutions with several segments are described by a
+//! [`MultiSegmentRupture`] of [`RuptureSegment`]s, e.g. read from USGS `.fsp` files with
+//! [`read_fsp`](crate::readers::read_fsp). Attached to an earthquake
+//! ([`Earthquake::with_segments`]), they replace the planar rupture in the Rrup and Rjb
+//! distances of all models; hanging-wall terms fall back to their null values.
+//!
+//! ## Geometry Conventions
+//!
+//! - `strike` follows the Aki & Richards convention: the hanging wall is on the right-hand side
+//!   when looking along strike.
+//! - The rupture plane is centered along strike on the epicenter, and the hypocenter lies on the
+//!   plane between its top edge (`ztor`) and bottom edge.
+//! - When `ztor` is not specified, the hypocenter is assumed to be at the center of the down-dip
+//!   width and `ztor` is estimated as `max(depth − W/2 · sin(dip), 0)`.
+//! - Horizontal geometry is evaluated in a local flat-earth frame around the epicenter.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::Earthquake;
+//! use ground_motion_lib::rupture::Rupture;
+//!
+//! // 100 km long vertical strike-slip rupture reaching the surface
+//! let eq = Earthquake::new_mw(142.83, 52.63, 10.0, 7.6)
+//!     .with_rupture(Rupture::new(0., 90., 100., 20.).with_ztor(0.));
+//! // Site 40 km north of the epicenter, right above the rupture
+//! let (lon, lat) = (142.83, 52.63 + 40. / 111.19);
+//! assert!(eq.rjb(lon, lat) < 1e-6);
+//! assert!(eq.rrup(lon, lat) < 1e-6);
+//! ```
+
+use crate::distance::{DistanceMethod, EARTH_RADIUS_KM, lon_difference};
+use crate::gmm::Earthquake;
+
+/// Planar finite rupture parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rupture {
+    /// Strike angle in degrees clockwise from north (0–360).
+    pub strike: f64,
+    /// Dip angle in degrees from horizontal (0–90].
+    pub dip: f64,
+    /// Rupture length along strike (km).
+    pub length: f64,
+    /// Rupture down-dip width (km).
+    pub width: f64,
+    /// Depth to the top of rupture, Ztor (km). Estimated from the hypocentral depth and the
+    /// rupture width when not specified.
+    pub ztor: Option<f64>,
+}
+
+/// Site-specific geometric inputs for hanging-wall terms of NGA-type models.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HangingWallInputs {
+    /// Horizontal distance (km) from the top edge of the rupture measured perpendicular to
+    /// strike. Positive on the hanging-wall side, negative on the footwall side.
+    pub rx: f64,
+    /// Horizontal distance (km) off the end of the rupture measured parallel to strike.
+    /// Zero for sites within the along-strike extent of the rupture.
+    pub ry0: f64,
+    /// Rupture dip angle (degrees).
+    pub dip: f64,
+    /// Depth to the top of rupture (km).
+    pub ztor: f64,
+    /// Rupture down-dip width (km).
+    pub width: f64,
+}
+
+impl HangingWallInputs {
+    /// Whether the site is located on the hanging-wall side of the rupture.
+    pub fn is_hanging_wall(&self) -> bool {
+        self.rx >= 0.
+    }
+}
+
+impl Rupture {
+    /// Create a new planar rupture.
+    ///
+    /// # Arguments
+    ///
+    /// * `strike` - Strike angle (degrees).
+    /// * `dip` - Dip angle (degrees).
+    /// * `length` - Rupture length along strike (km).
+    /// * `width` - Rupture down-dip width (km).
+    pub fn new(strike: f64, dip: f64, length: f64, width: f64) -> Self {
+        Self {
+            strike,
+            dip,
+            length,
+            width,
+            ztor: None,
+        }
+    }
+
+    /// Set the depth to the top of rupture (km).
+    pub fn with_ztor(mut self, ztor: f64) -> Self {
+        self.ztor = Some(ztor);
+        self
+    }
+
+    /// Depth to the top of rupture (km) for the given hypocentral depth.
+    ///
+    /// Returns the specified `ztor`, or an estimate assuming the hypocenter is located at the
+    /// center of the down-dip width.
+    pub fn ztor_for(&self, hypo_depth: f64) -> f64 {
+        self.ztor
+            .unwrap_or_else(|| estimate_ztor(hypo_depth, self.width, self.dip))
+    }
+
+    /// Unit vectors (east, north) along strike and along the horizontal dip direction.
+    pub(crate) fn unit_vectors(&self) -> ((f64, f64), (f64, f64)) {
+        let (sin_s, cos_s) = self.strike.to_radians().sin_cos();
+        ((sin_s, cos_s), (cos_s, -sin_s))
+    }
+
+    /// Horizontal offset (km) from the epicenter to the top edge of the rupture, measured
+    /// along the dip direction (negative, i.e. up-dip, for hypocenters below `ztor`).
+    pub(crate) fn top_edge_offset(&self, hypo_depth: f64) -> f64 {
+        let dip = self.dip.to_radians();
+        if dip.tan().is_finite() && dip.tan() > 0. {
+            -(hypo_depth - self.ztor_for(hypo_depth)).max(0.) / dip.tan()
+        } else {
+            0.
+        }
+    }
+
+    /// Compute hanging-wall inputs for a site.
+    ///
+    /// # Arguments
+    ///
+    /// * `eq_lon`, `eq_lat`, `eq_depth` - Hypocenter location (degrees, km).
+    /// * `lon`, `lat` - Site location (degrees).
+    pub fn hanging_wall_inputs(
+        &self,
+        eq_lon: f64,
+        eq_lat: f64,
+        eq_depth: f64,
+        lon: f64,
+        lat: f64,
+    ) -> HangingWallInputs {
+        let (x, y) = local_xy(eq_lon, eq_lat, lon, lat);
+        let ((sx, sy), (dx, dy)) = self.unit_vectors();
+        let top = self.top_edge_offset(eq_depth);
+        let along_strike = x * sx + y * sy;
+        let along_dip = x * dx + y * dy;
+        HangingWallInputs {
+            rx: along_dip - top,
+            ry0: (along_strike.abs() - 0.5 * self.length).max(0.),
+            dip: self.dip,
+            ztor: self.ztor_for(eq_depth),
+            width: self.width,
+        }
+    }
+
+    /// Site coordinates in the rupture frame: the distance off the ends of the rupture along
+    /// strike and the position relative to the top edge perpendicular to strike (horizontal
+    /// offset down-dip, depth below the top edge), all in km.
+    fn site_frame(&self, eq_lon: f64, eq_lat: f64, eq_depth: f64, lon: f64, lat: f64) -> [f64; 3] {
+        let hw = self.hanging_wall_inputs(eq_lon, eq_lat, eq_depth, lon, lat);
+        [hw.ry0, hw.rx, -hw.ztor]
+    }
+
+    /// Joyner–Boore distance (km): the closest distance from a site to the surface projection
+    /// of the rupture.
+    ///
+    /// # Arguments
+    ///
+    /// * `eq_lon`, `eq_lat`, `eq_depth` - Hypocenter location (degrees, km).
+    /// * `lon`, `lat` - Site location (degrees).
+    pub fn joyner_boore_distance(
+        &self,
+        eq_lon: f64,
+        eq_lat: f64,
+        eq_depth: f64,
+        lon: f64,
+        lat: f64,
+    ) -> f64 {
+        let [ry0, x, _] = self.site_frame(eq_lon, eq_lat, eq_depth, lon, lat);
+        let projected_width = self.width * self.dip.to_radians().cos();
+        let across = (-x).max(x - projected_width).max(0.);
+        ry0.hypot(across)
+    }
+
+    /// Rupture distance (km): the closest distance from a site to the rupture plane.
+    ///
+    /// # Arguments
+    ///
+    /// * `eq_lon`, `eq_lat`, `eq_depth` - Hypocenter location (degrees, km).
+    /// * `lon`, `lat` - Site location (degrees).
+    pub fn rupture_distance(
+        &self,
+        eq_lon: f64,
+        eq_lat: f64,
+        eq_depth: f64,
+        lon: f64,
+        lat: f64,
+    ) -> f64 {
+        let [ry0, x, z] = self.site_frame(eq_lon, eq_lat, eq_depth, lon, lat);
+        let (sin_d, cos_d) = self.dip.to_radians().sin_cos();
+        // Closest point of the plane in the vertical section perpendicular to strike
+        let w = (x * cos_d + z * sin_d).clamp(0., self.width);
+        ry0.hypot((x - w * cos_d).hypot(z - w * sin_d))
+    }
+}
+
+impl Earthquake {
+    /// Compute hanging-wall inputs for a site.
+    ///
+    /// # Returns
+    ///
+    /// `None` for point sources (no rupture attached) and multi-segment ruptures, in which case
+    /// models should use null hanging-wall terms.
+    pub fn hanging_wall_inputs(&self, lon: f64, lat: f64) -> Option<HangingWallInputs> {
+        if self.segments.is_some() {
+            return None;
+        }
+        self.rupture
+            .map(|r| r.hanging_wall_inputs(self.lon, self.lat, self.depth, lon, lat))
+    }
+
+    /// Rupture distance, Rrup (km), from the earthquake to a site.
+    ///
+    /// Uses the closest distance to the attached multi-segment rupture or rupture plane, or the
+    /// hypocentral distance for point sources.
+    pub fn rrup(&self, lon: f64, lat: f64) -> f64 {
+        if let Some(segments) = &self.segments {
+            return segments.rrup(lon, lat);
+        }
+        match &self.rupture {
+            Some(rupture) => rupture.rupture_distance(self.lon, self.lat, self.depth, lon, lat),
+            None => DistanceMethod::Haversine
+                .epicentral_distance(self.lon, self.lat, lon, lat)
+                .hypot(self.depth),
+        }
+    }
+
+    /// Joyner–Boore distance, Rjb (km), from the earthquake to a site.
+    ///
+    /// Uses the closest distance to the surface projection of the attached multi-segment rupture
+    /// or rupture plane, or the epicentral distance for point sources.
+    pub fn rjb(&self, lon: f64, lat: f64) -> f64 {
+        if let Some(segments) = &self.segments {
+            return segments.rjb(lon, lat);
+        }
+        match &self.rupture {
+            Some(rupture) => {
+                rupture.joyner_boore_distance(self.lon, self.lat, self.depth, lon, lat)
+            }
+            None => DistanceMethod::Haversine.epicentral_distance(self.lon, self.lat, lon, lat),
+        }
+    }
+
+    /// Depth to the top of rupture, Ztor (km).
+    ///
+    /// Uses the shallowest segment of the attached multi-segment rupture, or the attached
+    /// rupture, when available. For point sources, the rupture width is estimated from
+    /// magnitude with [`wells_coppersmith_width`] assuming a vertical rupture centered on the
+    /// hypocenter.
+    pub fn ztor(&self) -> f64 {
+        if let Some(ztor) = self.segments.as_ref().and_then(MultiSegmentRupture::ztor) {
+            return ztor;
+        }
+        match &self.rupture {
+            Some(rupture) => rupture.ztor_for(self.depth),
+            None => estimate_ztor(self.depth, wells_coppersmith_width(self.mw()), 90.),
+        }
+    }
+}
+
+/// Planar segment of a multi-segment rupture, located by the center of its top edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RuptureSegment {
+    /// Longitude of the center of the top edge in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the center of the top edge in decimal degrees.
+    pub lat: f64,
+    /// Plane geometry. The top edge lies at `ztor` (the surface if not specified).
+    pub rupture: Rupture,
+}
+
+impl RuptureSegment {
+    /// Create a new segment from the center of its top edge and its geometry.
+    pub fn new(lon: f64, lat: f64, rupture: Rupture) -> Self {
+        Self { lon, lat, rupture }
+    }
+
+    /// Locate a segment from the centers `(lon, lat, depth)` of its subfaults, e.g. those of a
+    /// published finite-fault solution.
+    ///
+    /// The center of the shallowest row of subfaults is shifted up-dip to the top of rupture.
+    /// Returns `None` if no subfaults are given.
+    pub fn from_subfaults(subfaults: &[(f64, f64, f64)], rupture: Rupture) -> Option<Self> {
+        let top = subfaults
+            .iter()
+            .map(|(_, _, depth)| *depth)
+            .reduce(f64::min)?;
+        let row: Vec<_> = subfaults
+            .iter()
+            .filter(|(_, _, depth)| *depth - top < 1e-3)
+            .collect();
+        let n = row.len() as f64;
+        let lon = row.iter().map(|(lon, _, _)| lon).sum::<f64>() / n;
+        let lat = row.iter().map(|(_, lat, _)| lat).sum::<f64>() / n;
+
+        let tan_dip = rupture.dip.to_radians().tan();
+        let up_dip = if tan_dip > 0. {
+            (top - rupture.ztor_for(top)).max(0.) / tan_dip
+        } else {
+            0.
+        };
+        let (_, (dx, dy)) = rupture.unit_vectors();
+        let lat_top = lat - (up_dip * dy / EARTH_RADIUS_KM).to_degrees();
+        let lon_top = lon - (up_dip * dx / EARTH_RADIUS_KM).to_degrees() / lat.to_radians().cos();
+        Some(Self::new(lon_top, lat_top, rupture))
+    }
+
+    /// Depth of the top edge (km).
+    fn top_depth(&self) -> f64 {
+        self.rupture.ztor.unwrap_or(0.)
+    }
+
+    /// Rupture distance (km) from the segment to a site.
+    pub fn rrup(&self, lon: f64, lat: f64) -> f64 {
+        self.rupture
+            .rupture_distance(self.lon, self.lat, self.top_depth(), lon, lat)
+    }
+
+    /// Joyner–Boore distance (km) from the segment to a site.
+    pub fn rjb(&self, lon: f64, lat: f64) -> f64 {
+        self.rupture
+            .joyner_boore_distance(self.lon, self.lat, self.top_depth(), lon, lat)
+    }
+}
+
+/// Rupture made of several planar segments, e.g. a published finite-fault solution.
+///
+/// Distances are the smallest distances to any segment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MultiSegmentRupture {
+    /// Planar segments of the rupture.
+    pub segments: Vec<RuptureSegment>,
+}
+
+impl MultiSegmentRupture {
+    /// Create a new rupture from its segments.
+    pub fn new(segments: Vec<RuptureSegment>) -> Self {
+        Self { segments }
+    }
+
+    /// Rupture distance, Rrup (km), to a site; infinite without segments.
+    pub fn rrup(&self, lon: f64, lat: f64) -> f64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.rrup(lon, lat))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Joyner–Boore distance, Rjb (km), to a site; infinite without segments.
+    pub fn rjb(&self, lon: f64, lat: f64) -> f64 {
+        self.segments
+            .iter()
+            .map(|segment| segment.rjb(lon, lat))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Depth to the top of the shallowest segment (km), or `None` without segments.
+    pub fn ztor(&self) -> Option<f64> {
+        self.segments
+            .iter()
+            .map(RuptureSegment::top_depth)
+            .reduce(f64::min)
+    }
+}
+
+/// Finite-fault solution of an earthquake, see
+/// [`read_fsp`](crate::readers::read_fsp).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FiniteFaultSolution {
+    /// Longitude of the hypocenter in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the hypocenter in decimal degrees.
+    pub lat: f64,
+    /// Hypocentral depth (km).
+    pub depth: f64,
+    /// Moment magnitude (Mw).
+    pub magnitude: f64,
+    /// Rupture geometry.
+    pub rupture: MultiSegmentRupture,
+}
+
+impl FiniteFaultSolution {
+    /// The earthquake at the hypocenter of the solution, with its multi-segment rupture, so
+    /// that the Rrup and Rjb distances of models are taken to the segments.
+    pub fn to_earthquake(&self) -> Earthquake {
+        Earthquake::new_mw(self.lon, self.lat, self.depth, self.magnitude)
+            .with_segments(self.rupture.clone())
+    }
+}
+
+/// Estimate depth to the top of rupture (km) assuming the hypocenter is located at the center
+/// of the down-dip width.
+///
+/// # Arguments
+///
+/// * `hypo_depth` - Hypocentral depth (km).
+/// * `width` - Rupture down-dip width (km).
+/// * `dip` - Dip angle (degrees).
+pub fn estimate_ztor(hypo_depth: f64, width: f64, dip: f64) -> f64 {
+    (hypo_depth - 0.5 * width * dip.to_radians().sin()).max(0.)
+}
+
+/// Rupture down-dip width (km) from moment magnitude using the all-slip-type relation of
+/// Wells & Coppersmith (1994): `log10(W) = -1.01 + 0.32 · M`.
+pub fn wells_coppersmith_width(magnitude: f64) -> f64 {
+    10_f64.powf(-1.01 + 0.32 * magnitude)
+}
+
+/// Project a point into a local flat-earth frame (km, east/north) centered at the origin.
+pub(crate) fn local_xy(lon0: f64, lat0: f64, lon: f64, lat: f64) -> (f64, f64) {
+    let mean_lat = (0.5 * (lat0 + lat)).to_radians();
+    let x = lon_difference(lon0, lon).to_radians() * mean_lat.cos() * EARTH_RADIUS_KM;
+    let y = (lat - lat0).to_radians() * EARTH_RADIUS_KM;
+    (x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_source_has_no_inputs() {
+        let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+        assert!(eq.hanging_wall_inputs(143.5, 52.).is_none());
+    }
+
+    #[test]
+    fn test_rx_sign_for_north_striking_fault() {
+        // North-striking fault dipping to the east, top edge at the epicenter
+        let rupture = Rupture::new(0., 45., 40., 20.).with_ztor(10.);
+        let eq = Earthquake::new_mw(143., 52., 10., 7.).with_rupture(rupture);
+        let east = eq.hanging_wall_inputs(143.2, 52.).unwrap();
+        let west = eq.hanging_wall_inputs(142.8, 52.).unwrap();
+        assert!(east.is_hanging_wall() && east.rx > 13.);
+        assert!(!west.is_hanging_wall() && west.rx < -13.);
+        assert_eq!(east.ry0, 0.);
+
+        let north = eq.hanging_wall_inputs(143., 52.5).unwrap();
+        assert!((north.ry0 - (55.6 - 20.)).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_ztor_estimate() {
+        // Vertical 20 km wide rupture centered at 15 km
+        let rupture = Rupture::new(0., 90., 40., 20.);
+        let eq = Earthquake::new_mw(143., 52., 15., 7.).with_rupture(rupture);
+        assert!((eq.ztor() - 5.).abs() < 1e-9);
+        // Shallow hypocenter is clipped at the surface
+        let eq = Earthquake::new_mw(143., 52., 5., 7.).with_rupture(rupture);
+        assert_eq!(eq.ztor(), 0.);
+        // Explicit value wins
+        let eq = Earthquake::new_mw(143., 52., 15., 7.).with_rupture(rupture.with_ztor(2.));
+        assert_eq!(eq.ztor(), 2.);
+        // Point source: W&C94 width for M6 is ~8.3 km
+        let eq = Earthquake::new_mw(143., 52., 15., 6.);
+        assert!((eq.ztor() - (15. - 0.5 * wells_coppersmith_width(6.))).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rupture_across_antimeridian() {
+        // East-striking vertical rupture from 179.7° E across 180° to 179.7° W
+        let rupture = Rupture::new(90., 90., 40., 15.).with_ztor(0.);
+        let eq = Earthquake::new_mw(180., 64.5, 7., 7.).with_rupture(rupture);
+        assert!(eq.rjb(-179.9, 64.5) < 1e-6);
+        assert!(eq.rjb(179.9, 64.5) < 1e-6);
+        assert!((eq.rrup(-179.9, 64.6) - 11.1).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_finite_fault_distances() {
+        // North-striking fault dipping 45° to the east, 40 km long, top edge at 5 km depth
+        let rupture = Rupture::new(0., 45., 40., 20.).with_ztor(5.);
+        let eq = Earthquake::new_mw(143., 52., 5., 7.5).with_rupture(rupture);
+        let km = |east: f64, north: f64| {
+            (
+                143. + (east / EARTH_RADIUS_KM).to_degrees() / 52_f64.to_radians().cos(),
+                52. + (north / EARTH_RADIUS_KM).to_degrees(),
+            )
+        };
+        // Above the middle of the surface projection
+        let (lon, lat) = km(5., 0.);
+        assert!(eq.rjb(lon, lat) < 1e-6);
+        assert!((eq.rrup(lon, lat) - 50_f64.sqrt()).abs() < 1e-3);
+        // Hanging-wall site closest to the interior of the plane: perpendicular distance
+        let (lon, lat) = km(25., 0.);
+        assert!((eq.rrup(lon, lat) - 30. * 45_f64.to_radians().sin()).abs() < 1e-3);
+        // Footwall site 10 km west of the top edge
+        let (lon, lat) = km(-10., 0.);
+        assert!((eq.rjb(lon, lat) - 10.).abs() < 1e-3);
+        assert!((eq.rrup(lon, lat) - 10_f64.hypot(5.)).abs() < 1e-3);
+        // Beyond the down-dip edge and the northern end
+        let (lon, lat) = km(14.142_135_6 + 3., 24.);
+        assert!((eq.rjb(lon, lat) - 5.).abs() < 0.05);
+        assert!(eq.rrup(lon, lat) > eq.rjb(lon, lat));
+        // Far sites approach the epicentral distance for point sources
+        let point = Earthquake::new_mw(143., 52., 5., 7.5);
+        let (lon, lat) = km(0., 300.);
+        assert!((eq.rjb(lon, lat) - (point.rjb(lon, lat) - 20.)).abs() < 0.5);
+        assert!((point.rrup(lon, lat) - point.rjb(lon, lat).hypot(5.)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_segment_from_subfaults() {
+        // East-striking segment dipping 45° to the south, top edge at 2 km depth at the origin
+        let rupture = Rupture::new(90., 45., 20., 10.).with_ztor(2.);
+        let segment = RuptureSegment::new(143., 52., rupture);
+        let shift = |east: f64, north: f64| {
+            (
+                143. + (east / EARTH_RADIUS_KM).to_degrees() / 52_f64.to_radians().cos(),
+                52. + (north / EARTH_RADIUS_KM).to_degrees(),
+            )
+        };
+        // Subfault centers 2.5 km down-dip: 2.5 · cos(45°) km south, 2.5 · sin(45°) km deeper
+        let offset = 2.5 * 45_f64.to_radians().cos();
+        let subfaults: Vec<_> = [-5., 5.]
+            .iter()
+            .map(|east| {
+                let (lon, lat) = shift(*east, -offset);
+                (lon, lat, 2. + offset)
+            })
+            .collect();
+        let located = RuptureSegment::from_subfaults(&subfaults, rupture).unwrap();
+        assert!((located.lon - 143.).abs() < 1e-6 && (located.lat - 52.).abs() < 1e-6);
+        assert!(RuptureSegment::from_subfaults(&[], rupture).is_none());
+
+        // The closest of both segments
+        let far = RuptureSegment::new(144., 52., rupture);
+        let multi = MultiSegmentRupture::new(vec![far, segment]);
+        let (lon, lat) = shift(0., 5.);
+        assert!((multi.rjb(lon, lat) - 5.).abs() < 1e-3);
+        assert!((multi.rrup(lon, lat) - 5_f64.hypot(2.)).abs() < 1e-3);
+        assert_eq!(MultiSegmentRupture::default().rrup(lon, lat), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_top_edge_offset_up_dip() {
+        // Hypocenter 10 km below Ztor on a 45° plane: top edge 10 km up-dip (to the west)
+        let rupture = Rupture::new(0., 45., 40., 30.).with_ztor(5.);
+        let eq = Earthquake::new_mw(143., 52., 15., 7.).with_rupture(rupture);
+        let at_epicenter = eq.hanging_wall_inputs(143., 52.).unwrap();
+        assert!((at_epicenter.rx - 10.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,436 @@
+//! Simulation of spatially correlated shaking fields.
+//!
+//! A median prediction grid describes the expected shaking only. Regional loss estimates need
+//! realizations of the shaking field in which the residuals of nearby sites are correlated,
+//! i.e. sites a few kilometers apart are shaken similarly strongly. [`CorrelatedField`] draws
+//! such realizations around a median grid using the standard deviation carried by each
+//! [`GmpePoint`] and a [`SpatialCorrelation`] model:
+//!
+//! ```text
+//! ln(y_i) = ln(median_i) + σ_i · z_i,   z = L · u,   L·Lᵀ = R
+//! ```
+//!
+//! where `R` is the site-to-site correlation matrix, `L` its Cholesky factor and `u` a vector
+//! of independent standard normal variables. Intensities are shifted linearly instead.
+//!
+//! The Cholesky factorization costs `O(n³)` and the factor takes `n²` values of memory, so the
+//! simulation is intended for site sets of up to a few thousand points (e.g. an exposure
+//! model), not for dense Vs30 grids.
+//!
+//! [`calc_gmpe_ensemble`] generates a Monte Carlo ensemble of spatially uncorrelated
+//! scenario realizations, splitting the total standard deviation of the model into a
+//! between-event term shared by all sites of a realization and a within-event term drawn per
+//! site:
+//!
+//! ```text
+//! ln(y_i) = ln(median_i) + τ_i · η + φ_i · ε_i,   τ_i = r · σ_i,   φ_i = sqrt(1 − r²) · σ_i
+//! ```
+//!
+//! The ensemble is summarized by per-site median, 16th and 84th percentile grids.
+//!
+//! Realizations are generated in parallel with Rayon. Every realization uses its own random
+//! number generator derived from the seed, so results do not depend on the number of threads.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::correlation::JayaramBaker2009;
+//! use ground_motion_lib::distance::DistanceMethod;
+//! use ground_motion_lib::gmm::GmpePoint;
+//! use ground_motion_lib::simulation::CorrelatedField;
+//!
+//! let median = vec![
+//!     GmpePoint::new_pga(142.50, 50.00, 12.0).with_sigma(0.6),
+//!     GmpePoint::new_pga(142.52, 50.01, 11.0).with_sigma(0.6),
+//!     GmpePoint::new_pga(142.90, 50.30, 4.0).with_sigma(0.6),
+//! ];
+//! let field =
+//!     CorrelatedField::new(&median, &JayaramBaker2009::default(), DistanceMethod::Haversine)
+//!         .unwrap();
+//! let realizations = field.realizations(&median, 10, 42).unwrap();
+//! assert_eq!(realizations.len(), 10);
+//! assert_eq!(realizations[0].len(), 3);
+//! ```
+
+use crate::correlation::SpatialCorrelation;
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, GmpePoint, GroundMotionModeling, Vs30Point};
+use crate::vectorized::calc_gmpe_vec;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_distr::StandardNormal;
+use rayon::prelude::*;
+use std::error::Error;
+
+/// Diagonal regularization keeping the correlation matrix positive definite for coincident
+/// sites.
+const JITTER: f64 = 1e-10;
+
+/// Default ratio `τ / σ` of the between-event to the total standard deviation.
+pub const DEFAULT_TAU_RATIO: f64 = 0.5;
+
+/// Seed of the random number generator of one realization.
+pub(crate) fn realization_seed(seed: u64, realization: usize) -> u64 {
+    seed.wrapping_add((realization as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+}
+
+/// Spatially correlated standard normal field over a fixed set of sites.
+#[derive(Debug, Clone)]
+pub struct CorrelatedField {
+    /// Number of sites of the field.
+    pub n_sites: usize,
+    /// Row-major lower Cholesky factor of the site correlation matrix.
+    lower: Vec<f64>,
+}
+
+impl CorrelatedField {
+    /// Build the field for the sites of a prediction grid.
+    ///
+    /// The spectral period of the correlation model is taken from the type of motion of the
+    /// grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `grid` - Prediction grid defining the sites and the type of motion.
+    /// * `correlation` - Spatial correlation model.
+    /// * `method` - Method used to compute the site separation distances.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the correlation model does not support the type of motion of the
+    /// grid, or if the correlation matrix is not positive definite.
+    pub fn new<C: SpatialCorrelation + ?Sized>(
+        grid: &[GmpePoint],
+        correlation: &C,
+        method: DistanceMethod,
+    ) -> Result<Self, Box<dyn Error>> {
+        let n = grid.len();
+        let mut lower = vec![0.; n * n];
+        for i in 0..n {
+            for j in 0..=i {
+                let distance =
+                    method.epicentral_distance(grid[i].lon, grid[i].lat, grid[j].lon, grid[j].lat);
+                lower[i * n + j] = correlation
+                    .correlation_for(distance, grid[i].kind)
+                    .ok_or_else(|| format!("No spatial correlation for {}", grid[i].kind))?;
+            }
+            lower[i * n + i] += JITTER;
+        }
+        cholesky_in_place(&mut lower, n)?;
+        Ok(Self { n_sites: n, lower })
+    }
+
+    /// Draw one realization of the correlated standard normal field.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let n = self.n_sites;
+        let u: Vec<f64> = (0..n).map(|_| rng.sample(StandardNormal)).collect();
+        (0..n)
+            .map(|i| {
+                self.lower[i * n..i * n + i + 1]
+                    .iter()
+                    .zip(&u)
+                    .map(|(l, u)| l * u)
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Simulate correlated realizations of the shaking field around a median grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `median` - Median predictions with standard deviations, for the sites of the field.
+    /// * `n` - Number of realizations.
+    /// * `seed` - Seed of the random number generator.
+    ///
+    /// # Returns
+    ///
+    /// `n` realizations of the grid; the simulated points carry no standard deviation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the grid size differs from the field or a point carries no
+    /// standard deviation.
+    pub fn realizations(
+        &self,
+        median: &[GmpePoint],
+        n: usize,
+        seed: u64,
+    ) -> Result<Vec<Vec<GmpePoint>>, Box<dyn Error>> {
+        if median.len() != self.n_sites {
+            return Err("Median grid size differs from the correlated field".into());
+        }
+        if median.iter().any(|p| p.sigma.is_none()) {
+            return Err("All median points must carry a standard deviation".into());
+        }
+        Ok((0..n)
+            .into_par_iter()
+            .map(|realization| {
+                let mut rng = StdRng::seed_from_u64(realization_seed(seed, realization));
+                let z = self.sample(&mut rng);
+                median
+                    .iter()
+                    .zip(z)
+                    .map(|(point, z)| shifted(point, z))
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+/// Monte Carlo ensemble of scenario realizations and its per-site summary grids.
+#[derive(Debug)]
+pub struct ScenarioEnsemble {
+    /// Simulated realizations of the prediction grid.
+    pub realizations: Vec<Vec<GmpePoint>>,
+    /// Per-site median of the realizations.
+    pub median: Vec<GmpePoint>,
+    /// Per-site 16th percentile of the realizations.
+    pub p16: Vec<GmpePoint>,
+    /// Per-site 84th percentile of the realizations.
+    pub p84: Vec<GmpePoint>,
+}
+
+impl ScenarioEnsemble {
+    /// Per-site percentile (`0..=100`) of the realizations, with linear interpolation between
+    /// the order statistics.
+    pub fn percentile(&self, percentile: f64) -> Vec<GmpePoint> {
+        percentile_grid(&self.realizations, percentile)
+    }
+}
+
+fn percentile_grid(realizations: &[Vec<GmpePoint>], percentile: f64) -> Vec<GmpePoint> {
+    let Some(first) = realizations.first() else {
+        return Vec::new();
+    };
+    first
+        .par_iter()
+        .enumerate()
+        .map(|(site, point)| {
+            let mut values: Vec<f64> = realizations.iter().map(|r| r[site].value).collect();
+            values.sort_by(f64::total_cmp);
+            let rank = (percentile / 100.).clamp(0., 1.) * (values.len() - 1) as f64;
+            let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+            let value = values[lo] + (rank - lo as f64) * (values[hi] - values[lo]);
+            GmpePoint::new(point.lon, point.lat, value, point.kind)
+        })
+        .collect()
+}
+
+/// Generate a Monte Carlo ensemble of scenario realizations.
+///
+/// Uses a between-event share of [`DEFAULT_TAU_RATIO`], see [`calc_gmpe_ensemble_with`].
+///
+/// # Example
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+/// use ground_motion_lib::simulation::calc_gmpe_ensemble;
+///
+/// let points = vec![
+///     Vs30Point::new(142.5, 50.0, 400., Some(200.), None),
+///     Vs30Point::new(142.6, 50.1, 350., Some(150.), None),
+/// ];
+/// let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+/// let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+///
+/// let ensemble = calc_gmpe_ensemble(&points, gmpe, &eq, 200, 42).unwrap();
+/// assert_eq!(ensemble.realizations.len(), 200);
+/// assert!(ensemble.p16[0].value < ensemble.median[0].value);
+/// assert!(ensemble.median[0].value < ensemble.p84[0].value);
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the model provides no standard deviation.
+pub fn calc_gmpe_ensemble<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    gmpe: &T,
+    eq: &Earthquake,
+    n: usize,
+    seed: u64,
+) -> Result<ScenarioEnsemble, Box<dyn Error>> {
+    calc_gmpe_ensemble_with(points, gmpe, eq, n, seed, DEFAULT_TAU_RATIO)
+}
+
+/// Generate a Monte Carlo ensemble of scenario realizations with a given between-event share.
+///
+/// # Arguments
+///
+/// * `points` - Site points.
+/// * `gmpe` - Ground motion model providing medians and standard deviations.
+/// * `eq` - Earthquake source parameters.
+/// * `n` - Number of realizations.
+/// * `seed` - Seed of the random number generator.
+/// * `tau_ratio` - Ratio `τ / σ` of the between-event to the total standard deviation
+///   (`0..=1`).
+///
+/// # Errors
+///
+/// Returns an error if the model provides no standard deviation.
+pub fn calc_gmpe_ensemble_with<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    gmpe: &T,
+    eq: &Earthquake,
+    n: usize,
+    seed: u64,
+    tau_ratio: f64,
+) -> Result<ScenarioEnsemble, Box<dyn Error>> {
+    let median = calc_gmpe_vec(points, gmpe, eq);
+    if median.iter().any(|p| p.sigma.is_none()) {
+        return Err("The model provides no standard deviation".into());
+    }
+    let tau_ratio = tau_ratio.clamp(0., 1.);
+    let phi_ratio = (1. - tau_ratio.powi(2)).sqrt();
+    let realizations: Vec<Vec<GmpePoint>> = (0..n)
+        .into_par_iter()
+        .map(|realization| {
+            let mut rng = StdRng::seed_from_u64(realization_seed(seed, realization));
+            let inter: f64 = rng.sample(StandardNormal);
+            median
+                .iter()
+                .map(|point| {
+                    let intra: f64 = rng.sample(StandardNormal);
+                    shifted(point, tau_ratio * inter + phi_ratio * intra)
+                })
+                .collect()
+        })
+        .collect();
+    Ok(ScenarioEnsemble {
+        median: percentile_grid(&realizations, 50.),
+        p16: percentile_grid(&realizations, 16.),
+        p84: percentile_grid(&realizations, 84.),
+        realizations,
+    })
+}
+
+/// A new point shifted from the median by `epsilon` standard deviations, without sigma.
+pub(crate) fn shifted(point: &GmpePoint, epsilon: f64) -> GmpePoint {
+    let shift = epsilon * point.sigma.unwrap_or(0.);
+    let value = if point.kind.is_log_normal() {
+        point.value * shift.exp()
+    } else {
+        point.value + shift
+    };
+    GmpePoint::new(point.lon, point.lat, value, point.kind)
+}
+
+/// In-place Cholesky factorization of a row-major symmetric matrix stored in its lower
+/// triangle. The upper triangle is left untouched (zero).
+pub(crate) fn cholesky_in_place(a: &mut [f64], n: usize) -> Result<(), Box<dyn Error>> {
+    for j in 0..n {
+        let diag = a[j * n + j] - (0..j).map(|k| a[j * n + k].powi(2)).sum::<f64>();
+        if diag <= 0. {
+            return Err("Correlation matrix is not positive definite".into());
+        }
+        let diag = diag.sqrt();
+        a[j * n + j] = diag;
+        for i in j + 1..n {
+            let dot: f64 = (0..j).map(|k| a[i * n + k] * a[j * n + k]).sum();
+            a[i * n + j] = (a[i * n + j] - dot) / diag;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::correlation::JayaramBaker2009;
+
+    fn grid() -> Vec<GmpePoint> {
+        vec![
+            GmpePoint::new_pga(142.5, 50., 10.).with_sigma(0.5),
+            GmpePoint::new_pga(142.51, 50., 10.).with_sigma(0.5),
+            GmpePoint::new_pga(143.5, 50., 10.).with_sigma(0.5),
+        ]
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let mut a = vec![4., 0., 0., 2., 5., 0., -2., 1., 11.];
+        cholesky_in_place(&mut a, 3).unwrap();
+        assert_eq!(a, vec![2., 0., 0., 1., 2., 0., -1., 1., 3.]);
+        let mut singular = vec![1., 0., 1., 1.];
+        assert!(cholesky_in_place(&mut singular, 2).is_err());
+    }
+
+    #[test]
+    fn test_realizations_are_seeded_and_correlated() {
+        let median = grid();
+        let field = CorrelatedField::new(
+            &median,
+            &JayaramBaker2009::default(),
+            DistanceMethod::Haversine,
+        )
+        .unwrap();
+        let first = field.realizations(&median, 2000, 7).unwrap();
+        let again = field.realizations(&median, 2000, 7).unwrap();
+        assert_eq!(first[3][1].value, again[3][1].value);
+
+        // Sample correlation of the log residuals
+        let eps = |r: &Vec<GmpePoint>, i: usize| (r[i].value / 10.).ln() / 0.5;
+        let corr = |i: usize, j: usize| {
+            first.iter().map(|r| eps(r, i) * eps(r, j)).sum::<f64>() / first.len() as f64
+        };
+        let variance = corr(0, 0);
+        assert!((variance - 1.).abs() < 0.1, "{variance}");
+        // Sites 0.7 km apart are strongly correlated, 70 km apart are not
+        assert!(corr(0, 1) > 0.7);
+        assert!(corr(0, 2).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_realizations_need_sigma() {
+        let mut median = grid();
+        median[1].sigma = None;
+        let field = CorrelatedField::new(
+            &median,
+            &JayaramBaker2009::default(),
+            DistanceMethod::Haversine,
+        )
+        .unwrap();
+        assert!(field.realizations(&median, 1, 0).is_err());
+        let pgv = vec![GmpePoint::new_pgv(142.5, 50., 10.)];
+        assert!(
+            CorrelatedField::new(
+                &pgv,
+                &JayaramBaker2009::default(),
+                DistanceMethod::Haversine
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_scenario_ensemble_quantiles() {
+        let points = vec![
+            Vs30Point::new(142.5, 50., 400., None, None),
+            Vs30Point::new(142.7, 50.1, 400., None, None),
+        ];
+        let eq = Earthquake::new_mw(142.4, 50., 10., 6.5);
+        let gmpe = crate::configs::get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let median = gmpe.calc_from_point(&points[0], &eq);
+        let sigma = median.sigma.unwrap();
+
+        let ensemble = calc_gmpe_ensemble(&points, gmpe, &eq, 4000, 1).unwrap();
+        let ratio = ensemble.median[0].value / median.value;
+        assert!((ratio.ln()).abs() < 0.05, "{ratio}");
+        // 16th and 84th percentiles are about one standard deviation away from the median
+        let spread = (ensemble.p84[0].value / ensemble.p16[0].value).ln() / 2.;
+        assert!((spread - sigma).abs() < 0.05, "{spread} vs {sigma}");
+        assert_eq!(ensemble.percentile(100.).len(), 2);
+
+        // Fully between-event variability moves all sites together
+        let shared = calc_gmpe_ensemble_with(&points, gmpe, &eq, 3, 1, 1.).unwrap();
+        let gm = calc_gmpe_vec(&points, gmpe, &eq);
+        for r in &shared.realizations {
+            let a = (r[0].value / gm[0].value).ln();
+            let b = (r[1].value / gm[1].value).ln();
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+}
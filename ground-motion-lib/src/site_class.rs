@@ -0,0 +1,134 @@
+//! Representative Vs30 of site classes.
+//!
+//! Many municipal and regional site datasets only provide a NEHRP (BSSC) site class letter
+//! instead of a measured Vs30. A [`SiteClassMap`] converts the class of every site to a
+//! representative Vs30 so that such datasets can be used with any model:
+//!
+//! | Site class | Vs30 range (m/s) | Representative Vs30 (m/s) |
+//! |------------|------------------|---------------------------|
+//! | A          | > 1500           | 1500                      |
+//! | B          | 760 – 1500       | 1070                      |
+//! | BC         | boundary         | 760                       |
+//! | C          | 360 – 760        | 525                       |
+//! | CD         | boundary         | 360                       |
+//! | D          | 180 – 360        | 255                       |
+//! | DE         | boundary         | 180                       |
+//! | E          | < 180            | 150                       |
+//!
+//! The representative values of the bounded classes are the geometric means of their ranges.
+//! Other classification schemes or local calibrations are configured with
+//! [`SiteClassMap::with_class`] or read from a mapping table with
+//! [`read_site_class_map`](crate::readers::read_site_class_map).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::site_class::SiteClassMap;
+//!
+//! let nehrp = SiteClassMap::default();
+//! assert_eq!(nehrp.vs30("c"), Some(525.));
+//! // Numeric values are passed through
+//! assert_eq!(nehrp.site_vs30("400").unwrap(), 400.);
+//! assert!(nehrp.site_vs30("F").is_err());
+//! ```
+
+use std::collections::BTreeMap;
+
+/// NEHRP site classes and their representative Vs30 (m/s).
+pub const NEHRP_VS30: [(&str, f64); 8] = [
+    ("A", 1500.),
+    ("B", 1070.),
+    ("BC", 760.),
+    ("C", 525.),
+    ("CD", 360.),
+    ("D", 255.),
+    ("DE", 180.),
+    ("E", 150.),
+];
+
+/// Mapping of site class names to representative Vs30 values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SiteClassMap {
+    /// Representative Vs30 (m/s) by upper-case class name
+    pub vs30: BTreeMap<String, f64>,
+}
+
+impl Default for SiteClassMap {
+    /// The NEHRP site classes, see [`NEHRP_VS30`].
+    fn default() -> Self {
+        NEHRP_VS30.iter().fold(Self::new(), |map, (class, vs30)| {
+            map.with_class(class, *vs30)
+        })
+    }
+}
+
+impl SiteClassMap {
+    /// Create an empty mapping.
+    pub fn new() -> Self {
+        Self {
+            vs30: BTreeMap::new(),
+        }
+    }
+
+    /// Add a site class or replace its representative Vs30 (m/s).
+    pub fn with_class(mut self, class: &str, vs30: f64) -> Self {
+        self.vs30.insert(class.trim().to_uppercase(), vs30);
+        self
+    }
+
+    /// Representative Vs30 (m/s) of a site class; class names are case-insensitive.
+    pub fn vs30(&self, class: &str) -> Option<f64> {
+        self.vs30.get(&class.trim().to_uppercase()).copied()
+    }
+
+    /// Vs30 (m/s) of a site given either a numeric Vs30 or a site class.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value is neither a number nor a known site class.
+    pub fn site_vs30(&self, value: &str) -> Result<f64, String> {
+        if let Ok(vs30) = value.trim().parse::<f64>() {
+            return Ok(vs30);
+        }
+        self.vs30(value).ok_or_else(|| {
+            format!(
+                "Unknown site class `{}`, expected a Vs30 value or one of: {}",
+                value.trim(),
+                self.vs30.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nehrp_classes_within_ranges() {
+        let nehrp = SiteClassMap::default();
+        assert_eq!(nehrp.vs30.len(), NEHRP_VS30.len());
+        for (class, (lower, upper)) in [
+            ("B", (760., 1500.)),
+            ("C", (360., 760.)),
+            ("D", (180., 360.)),
+        ] {
+            let vs30 = nehrp.vs30(class).unwrap();
+            assert!(vs30 > lower && vs30 < upper, "{class} {vs30}");
+        }
+        assert_eq!(nehrp.vs30(" bc "), Some(760.));
+        assert_eq!(nehrp.vs30("F"), None);
+    }
+
+    #[test]
+    fn test_custom_classes() {
+        let map = SiteClassMap::new()
+            .with_class("soft", 200.)
+            .with_class("E", 120.);
+        assert_eq!(map.site_vs30("Soft").unwrap(), 200.);
+        assert_eq!(map.site_vs30("e").unwrap(), 120.);
+        assert_eq!(map.site_vs30(" 310.5").unwrap(), 310.5);
+        let err = map.site_vs30("A").unwrap_err();
+        assert!(err.contains("E, SOFT"), "{err}");
+    }
+}
@@ -0,0 +1,125 @@
+//! Pre-filtering of site grids around the epicenter.
+//!
+//! Continental-scale Vs30 grids hold millions of sites, most of them far beyond any shaking of
+//! interest for a given event. A [`SiteFilter`] drops such sites *before* the computation,
+//! cutting both the runtime and the size of the output:
+//!
+//! - [`SiteFilter::with_max_radius`] keeps sites within a radius (km) of the epicenter.
+//! - [`SiteFilter::with_bbox`] keeps sites within a geographic bounding box.
+//!
+//! Both criteria can be combined. Unlike [`BBoxMask`](crate::pipeline::BBoxMask), which masks
+//! prediction grids after the computation, the filter works on input [`Vs30Point`]s.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+//! use ground_motion_lib::site_filter::SiteFilter;
+//!
+//! let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+//! let sites = vec![
+//!     Vs30Point::new(142.5, 50.0, 400., None, None),
+//!     Vs30Point::new(150.0, 50.0, 400., None, None),
+//! ];
+//!
+//! let sites = SiteFilter::default().with_max_radius(300.).apply(sites, &eq);
+//! assert_eq!(sites.len(), 1);
+//! ```
+
+use crate::distance::{DistanceMethod, EARTH_RADIUS_KM};
+use crate::gmm::{Earthquake, Vs30Point};
+use rayon::prelude::*;
+
+/// Criteria of sites kept for the computation.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SiteFilter {
+    /// Largest epicentral distance (km) of kept sites.
+    pub max_radius_km: Option<f64>,
+    /// Bounding box `[min_lon, min_lat, max_lon, max_lat]` (decimal degrees) of kept sites.
+    pub bbox: Option<[f64; 4]>,
+    /// Epicentral distance calculation method of the radius criterion.
+    pub method: DistanceMethod,
+}
+
+impl SiteFilter {
+    /// Keep sites within `max_radius_km` of the epicenter.
+    pub fn with_max_radius(mut self, max_radius_km: f64) -> Self {
+        self.max_radius_km = Some(max_radius_km);
+        self
+    }
+
+    /// Keep sites within the bounding box (decimal degrees).
+    pub fn with_bbox(mut self, min_lon: f64, min_lat: f64, max_lon: f64, max_lat: f64) -> Self {
+        self.bbox = Some([min_lon, min_lat, max_lon, max_lat]);
+        self
+    }
+
+    /// Set the epicentral distance calculation method of the radius criterion.
+    pub fn with_method(mut self, method: DistanceMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Whether a site at the given coordinates is kept for the earthquake.
+    pub fn contains(&self, lon: f64, lat: f64, eq: &Earthquake) -> bool {
+        if let Some([min_lon, min_lat, max_lon, max_lat]) = self.bbox
+            && (!(min_lon..=max_lon).contains(&lon) || !(min_lat..=max_lat).contains(&lat))
+        {
+            return false;
+        }
+        match self.max_radius_km {
+            // The latitude difference alone bounds the distance from below
+            Some(max_km) if (lat - eq.lat).abs().to_radians() * EARTH_RADIUS_KM > max_km => false,
+            Some(max_km) => self.method.epicentral_distance(eq.lon, eq.lat, lon, lat) <= max_km,
+            None => true,
+        }
+    }
+
+    /// Drop the sites not kept for the earthquake, in parallel, preserving the order of the
+    /// remaining sites.
+    pub fn apply(&self, points: Vec<Vs30Point>, eq: &Earthquake) -> Vec<Vs30Point> {
+        points
+            .into_par_iter()
+            .filter(|point| self.contains(point.lon, point.lat, eq))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<Vs30Point> {
+        (0..10)
+            .flat_map(|i| {
+                (0..10)
+                    .map(move |j| Vs30Point::new(140. + i as f64, 45. + j as f64, 400., None, None))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_max_radius() {
+        let eq = Earthquake::new_mw(142., 50., 10., 7.);
+        let filter = SiteFilter::default().with_max_radius(200.);
+        let kept = filter.apply(grid(), &eq);
+        assert!(!kept.is_empty());
+        for site in &kept {
+            let d = DistanceMethod::Haversine.epicentral_distance(142., 50., site.lon, site.lat);
+            assert!(d <= 200.);
+        }
+        assert!(filter.contains(142., 51.7, &eq));
+        assert!(!filter.contains(142., 51.9, &eq));
+    }
+
+    #[test]
+    fn test_bbox_and_radius() {
+        let eq = Earthquake::new_mw(142., 50., 10., 7.);
+        let bbox = SiteFilter::default().with_bbox(141., 49., 143., 52.);
+        assert_eq!(bbox.apply(grid(), &eq).len(), 12);
+        let both = bbox.with_max_radius(120.);
+        let kept = both.apply(grid(), &eq);
+        assert_eq!(kept.len(), 5);
+        assert!(kept.iter().all(|site| site.lat < 52.));
+    }
+}
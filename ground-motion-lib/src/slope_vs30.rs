@@ -0,0 +1,262 @@
+//! Vs30 estimated from topographic slope (Wald & Allen, 2007).
+//!
+//! Where no measured or mapped Vs30 is available, the topographic slope is a proxy for the
+//! stiffness of near-surface materials: steep terrain is mostly rock, flat terrain mostly
+//! sediments. Wald & Allen (2007) relate slope ranges to the NEHRP Vs30 ranges separately for
+//! active tectonic and stable continental regions:
+//!
+//! | Vs30 (m/s) | Active slope (m/m)  | Stable slope (m/m)  |
+//! |------------|---------------------|---------------------|
+//! | 180        | 1.0e-4              | 2.0e-5              |
+//! | 240        | 2.2e-3              | 2.0e-3              |
+//! | 300        | 6.3e-3              | 4.0e-3              |
+//! | 360        | 0.018               | 7.2e-3              |
+//! | 490        | 0.050               | 0.013               |
+//! | 620        | 0.10                | 0.018               |
+//! | 760        | 0.138               | 0.025               |
+//!
+//! Vs30 is interpolated linearly in `ln(slope)` between the class boundaries of the table and
+//! clamped to 180–760 m/s outside of it.
+//!
+//! An [`ElevationGrid`] (a DEM in geographic coordinates) is turned into a site grid with
+//! [`ElevationGrid::vs30_points`], with slopes from central differences of the elevations. The
+//! relations were derived from 30 arc-second (~1 km) DEMs; finer DEMs give steeper slopes and
+//! should be resampled first. GeoTIFF DEMs are read with `read_geotiff_dem` of the
+//! [`readers`](crate::readers) module (`geotiff` crate feature).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::slope_vs30::{ElevationGrid, SlopeRegime, wald_allen_vs30};
+//!
+//! assert_eq!(wald_allen_vs30(0.018, SlopeRegime::Active), 360.);
+//! // 3 × 3 DEM rising 10 m per cell to the east
+//! let dem = ElevationGrid::new(142.0, 50.02, 0.01, 0.01, 3, vec![0., 10., 20.].repeat(3)).unwrap();
+//! let points = dem.vs30_points(SlopeRegime::Active);
+//! assert_eq!(points.len(), 9);
+//! assert!(points.iter().all(|p| p.vs30 > 300. && p.vs30 < 360.));
+//! ```
+
+use crate::distance::EARTH_RADIUS_KM;
+use crate::gmm::Vs30Point;
+use std::error::Error;
+
+/// Tectonic regime of a slope–Vs30 relation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlopeRegime {
+    /// Active tectonic regions
+    Active,
+    /// Stable continental regions
+    Stable,
+}
+
+/// `(slope, Vs30)` class boundaries of active tectonic regions.
+const ACTIVE_NODES: [(f64, f64); 7] = [
+    (1.0e-4, 180.),
+    (2.2e-3, 240.),
+    (6.3e-3, 300.),
+    (0.018, 360.),
+    (0.050, 490.),
+    (0.10, 620.),
+    (0.138, 760.),
+];
+
+/// `(slope, Vs30)` class boundaries of stable continental regions.
+const STABLE_NODES: [(f64, f64); 7] = [
+    (2.0e-5, 180.),
+    (2.0e-3, 240.),
+    (4.0e-3, 300.),
+    (7.2e-3, 360.),
+    (0.013, 490.),
+    (0.018, 620.),
+    (0.025, 760.),
+];
+
+/// Vs30 (m/s) of a topographic slope (m/m) with the Wald & Allen (2007) relation.
+pub fn wald_allen_vs30(slope: f64, regime: SlopeRegime) -> f64 {
+    let nodes = match regime {
+        SlopeRegime::Active => &ACTIVE_NODES,
+        SlopeRegime::Stable => &STABLE_NODES,
+    };
+    let (first, last) = (nodes[0], nodes[nodes.len() - 1]);
+    if slope <= first.0 {
+        return first.1;
+    }
+    if slope >= last.0 {
+        return last.1;
+    }
+    let i = nodes.partition_point(|(s, _)| *s <= slope);
+    let ((s0, v0), (s1, v1)) = (nodes[i - 1], nodes[i]);
+    v0 + (v1 - v0) * (slope / s0).ln() / (s1 / s0).ln()
+}
+
+/// Regular grid of elevations in geographic coordinates (a DEM).
+///
+/// Rows run from north to south and columns from west to east; `NaN` marks missing values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElevationGrid {
+    /// Longitude of the center of the north-west cell (degrees)
+    pub lon0: f64,
+    /// Latitude of the center of the north-west cell (degrees)
+    pub lat0: f64,
+    /// Cell size in longitude (degrees)
+    pub dlon: f64,
+    /// Cell size in latitude (degrees)
+    pub dlat: f64,
+    /// Number of columns
+    pub ncols: usize,
+    /// Number of rows
+    pub nrows: usize,
+    /// Elevations (m), row by row
+    pub values: Vec<f64>,
+}
+
+impl ElevationGrid {
+    /// Create a grid from its north-west cell center, cell sizes, number of columns and
+    /// row-major elevations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cell sizes are not positive, or the number of elevations is not
+    /// a non-zero multiple of `ncols`.
+    pub fn new(
+        lon0: f64,
+        lat0: f64,
+        dlon: f64,
+        dlat: f64,
+        ncols: usize,
+        values: Vec<f64>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if dlon <= 0. || dlat <= 0. {
+            return Err("Cell sizes must be positive".into());
+        }
+        if ncols == 0 || values.is_empty() || !values.len().is_multiple_of(ncols) {
+            return Err(format!(
+                "{} elevations do not fill rows of {ncols} columns",
+                values.len()
+            )
+            .into());
+        }
+        Ok(Self {
+            lon0,
+            lat0,
+            dlon,
+            dlat,
+            ncols,
+            nrows: values.len() / ncols,
+            values,
+        })
+    }
+
+    /// Longitude and latitude of the center of a cell.
+    pub fn coordinates(&self, row: usize, col: usize) -> (f64, f64) {
+        (
+            self.lon0 + col as f64 * self.dlon,
+            self.lat0 - row as f64 * self.dlat,
+        )
+    }
+
+    /// Elevation (m) of a cell.
+    pub fn elevation(&self, row: usize, col: usize) -> f64 {
+        self.values[row * self.ncols + col]
+    }
+
+    /// Topographic slope (m/m) of a cell from central differences of the neighbouring
+    /// elevations (one-sided at the edges of the grid).
+    ///
+    /// # Returns
+    ///
+    /// `NaN` if a neighbouring elevation is missing.
+    pub fn slope(&self, row: usize, col: usize) -> f64 {
+        let (_, lat) = self.coordinates(row, col);
+        let cell_y = (self.dlat.to_radians() * EARTH_RADIUS_KM) * 1000.;
+        let cell_x = cell_y * self.dlon / self.dlat * lat.to_radians().cos();
+        let gradient = |lo: (usize, usize), hi: (usize, usize), cell: f64| {
+            let steps = (hi.0 - lo.0 + hi.1 - lo.1) as f64;
+            if steps == 0. {
+                return 0.;
+            }
+            (self.elevation(hi.0, hi.1) - self.elevation(lo.0, lo.1)) / (steps * cell)
+        };
+        let east = gradient(
+            (row, col.saturating_sub(1)),
+            (row, (col + 1).min(self.ncols - 1)),
+            cell_x,
+        );
+        let south = gradient(
+            (row.saturating_sub(1), col),
+            ((row + 1).min(self.nrows - 1), col),
+            cell_y,
+        );
+        east.hypot(south)
+    }
+
+    /// Topographic slopes (m/m) of all cells, row by row; see [`Self::slope`].
+    pub fn slopes(&self) -> Vec<f64> {
+        (0..self.nrows)
+            .flat_map(|row| (0..self.ncols).map(move |col| self.slope(row, col)))
+            .collect()
+    }
+
+    /// Site grid with Vs30 from the slope of every cell with the Wald & Allen (2007) relation.
+    ///
+    /// Cells with a missing elevation or slope are skipped.
+    pub fn vs30_points(&self, regime: SlopeRegime) -> Vec<Vs30Point> {
+        (0..self.nrows)
+            .flat_map(|row| (0..self.ncols).map(move |col| (row, col)))
+            .filter_map(|(row, col)| {
+                let slope = self.slope(row, col);
+                if self.elevation(row, col).is_nan() || slope.is_nan() {
+                    return None;
+                }
+                let (lon, lat) = self.coordinates(row, col);
+                let vs30 = wald_allen_vs30(slope, regime);
+                Some(Vs30Point::new(lon, lat, vs30, None, None))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wald_allen_relation() {
+        for regime in [SlopeRegime::Active, SlopeRegime::Stable] {
+            let nodes = match regime {
+                SlopeRegime::Active => ACTIVE_NODES,
+                SlopeRegime::Stable => STABLE_NODES,
+            };
+            for (slope, vs30) in nodes {
+                assert!((wald_allen_vs30(slope, regime) - vs30).abs() < 1e-9);
+            }
+            assert_eq!(wald_allen_vs30(0., regime), 180.);
+            assert_eq!(wald_allen_vs30(1., regime), 760.);
+        }
+        // Stable regions reach rock at gentler slopes
+        let slope = 0.015;
+        let active = wald_allen_vs30(slope, SlopeRegime::Active);
+        let stable = wald_allen_vs30(slope, SlopeRegime::Stable);
+        assert!(active > 300. && active < 360. && stable > 490. && stable < 620.);
+    }
+
+    #[test]
+    fn test_grid_slopes() {
+        // Elevation rising 100 m per row to the north, i.e. ~0.09 m/m for 0.01° cells
+        let values: Vec<f64> = (0..4)
+            .flat_map(|row| [300. - 100. * row as f64; 3])
+            .collect();
+        let dem = ElevationGrid::new(142., 50.03, 0.01, 0.01, 3, values).unwrap();
+        assert_eq!(dem.nrows, 4);
+        assert_eq!(dem.slopes().len(), 12);
+        let cell = 0.01_f64.to_radians() * EARTH_RADIUS_KM * 1000.;
+        for (row, col) in [(0, 0), (1, 1), (3, 2)] {
+            assert!((dem.slope(row, col) - 100. / cell).abs() < 1e-9);
+        }
+        let mut dem = dem;
+        dem.values[4] = f64::NAN;
+        assert_eq!(dem.vs30_points(SlopeRegime::Active).len(), 12 - 5);
+        assert!(ElevationGrid::new(142., 50., 0.01, 0.01, 5, vec![0.; 12]).is_err());
+    }
+}
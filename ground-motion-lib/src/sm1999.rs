@@ -0,0 +1,176 @@
+//! Implementation of Si & Midorikawa (1999) attenuation relations for PGA and PGV in Japan.
+//!
+//! The model uses a simple functional form in `log10` units:
+//!
+//! ```text
+//! b = a·Mw + h·D + d_k + e
+//! log10(Y) = b − log10(X + c) − k·X                                  (D ≤ 30 km)
+//! log10(Y) = b + 0.6·log10(1.7·D + c) − 1.6·log10(X + c) − k·X          (D > 30 km)
+//! c = c0 · 10^(0.5·Mw)
+//! ```
+//!
+//! where `D` is the focal depth (km), `X` is the fault distance (km) and `d_k` is the
+//! tectonic type term (crustal, interplate or intraplate). PGA (cm/s²) is predicted for
+//! average stiff ground. PGV (cm/s) is predicted for engineering bedrock (Vs = 600 m/s) and
+//! amplified to the site with the Vs30-based factor of Fujimoto & Midorikawa (2006):
+//!
+//! ```text
+//! log10(ARV) = 2.367 − 0.852 · log10(Vs30)
+//! ```
+//!
+//! For point sources the fault distance is approximated by the hypocentral distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+use std::f64::consts::LN_10;
+
+/// Focal depth (km) above which the deep-event distance form is used.
+const DEPTH_BREAK: f64 = 30.;
+
+/// Si & Midorikawa (1999) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SM1999 {
+    /// Magnitude scaling coefficient
+    pub a: f64,
+    /// Depth scaling coefficient
+    pub h: f64,
+    /// Tectonic type terms (crustal, interplate, intraplate)
+    pub d: [f64; 3],
+    /// Constant term
+    pub e: f64,
+    /// Anelastic attenuation coefficient
+    pub k: f64,
+    /// Near-source saturation coefficient `c0`
+    pub c0: f64,
+    /// Standard deviation of `log10(Y)`
+    pub sigma: f64,
+    /// Tectonic type of the modeled events
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA or PGV)
+    pub motion_kind: GmpePointKind,
+}
+
+impl SM1999 {
+    /// PGA coefficients for the given tectonic type.
+    pub fn pga(tectonic_type: TectonicType) -> Self {
+        Self {
+            a: 0.5,
+            h: 0.0043,
+            d: [0., 0.01, 0.22],
+            e: 0.61,
+            k: 0.003,
+            c0: 0.0055,
+            sigma: 0.27,
+            tectonic_type,
+            motion_kind: GmpePointKind::Pga,
+        }
+    }
+
+    /// PGV coefficients for the given tectonic type.
+    pub fn pgv(tectonic_type: TectonicType) -> Self {
+        Self {
+            a: 0.58,
+            h: 0.0038,
+            d: [0., -0.02, 0.12],
+            e: -1.29,
+            k: 0.002,
+            c0: 0.0028,
+            sigma: 0.23,
+            tectonic_type,
+            motion_kind: GmpePointKind::Pgv,
+        }
+    }
+
+    /// Median `log10(Y)` with PGA in cm/s² or PGV in cm/s on the reference ground.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Fault distance (km).
+    /// * `depth` - Focal depth (km).
+    pub fn log10_median(&self, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let d_k = match self.tectonic_type {
+            TectonicType::Crustal => self.d[0],
+            TectonicType::Interplate => self.d[1],
+            TectonicType::Intraplate => self.d[2],
+        };
+        let b = self.a * magnitude + self.h * depth + d_k + self.e;
+        let c = self.c0 * 10_f64.powf(0.5 * magnitude);
+        if depth <= DEPTH_BREAK {
+            b - (distance + c).log10() - self.k * distance
+        } else {
+            b + 0.6 * (1.7 * depth + c).log10() - 1.6 * (distance + c).log10() - self.k * distance
+        }
+    }
+
+    /// PGV amplification factor from engineering bedrock (Vs = 600 m/s) to the site.
+    pub fn pgv_amplification(vs30: f64) -> f64 {
+        10_f64.powf(2.367 - 0.852 * vs30.log10())
+    }
+}
+
+impl GroundMotionModeling for SM1999 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA in %g or PGV in cm/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let mut ground_motion = 10_f64.powf(self.log10_median(eq.mw(), distance, eq.depth));
+        match self.motion_kind {
+            GmpePointKind::Pgv => ground_motion *= Self::pgv_amplification(point.vs30),
+            // convert cm/s² to %g
+            _ => ground_motion = ((ground_motion / 100.) / G_GLOBAL) * 100.,
+        }
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: ground_motion,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma * LN_10),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shallow_crustal_pga() {
+        let model = SM1999::pga(TectonicType::Crustal);
+        let c = 0.0055 * 10_f64.powf(3.5);
+        let expected = 0.5 * 7. + 0.0043 * 10. + 0.61 - (20. + c).log10() - 0.003 * 20.;
+        assert!((model.log10_median(7., 20., 10.) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pgv_amplification_is_unity_on_bedrock() {
+        assert!((SM1999::pgv_amplification(600.) - 1.).abs() < 0.01);
+        assert!(SM1999::pgv_amplification(200.) > 2.);
+    }
+}
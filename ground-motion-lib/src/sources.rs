@@ -0,0 +1,425 @@
+//! Seismic source models for hazard calculations.
+//!
+//! Besides point sources ([`PointSource`]) and catalog-derived zones
+//! ([`catalog`](crate::catalog)), hazard models usually describe seismicity with area sources
+//! (zones with a Gutenberg–Richter magnitude–frequency distribution) and fault sources (traces
+//! with a slip rate). A [`SourceModel`] collects all three and is discretized into point
+//! sources for the [`hazard`](crate::hazard) calculator.
+//!
+//! [`AreaSource`]s spread their bin rates evenly over a regular grid of points inside the
+//! polygon. [`FaultSource`]s balance the seismic moment rate of the fault with a truncated
+//! Gutenberg–Richter distribution:
+//!
+//! ```text
+//! Ṁ0 = μ · L · W · s,   W = (z_low − z_up) / sin(δ)
+//! Ṁ0 = ∫ n(m) · M0(m) dm,   M0(m) = 10^(1.5·m + 9.05) N·m
+//! ⇒ a = log10(Ṁ0 · (1.5 − b) / (b · (10^((1.5 − b)·Mmax) − 10^((1.5 − b)·Mmin)))) − 9.05
+//! ```
+//!
+//! where `μ` is the shear modulus, `L` the trace length, `W` the down-dip width, `s` the slip
+//! rate and `δ` the dip. The rates are split over points along the trace, shifted down-dip to
+//! the middle of the seismogenic layer.
+//!
+//! Current simplifications:
+//!
+//! - Fault and area sources are discretized into point sources; distances are epicentral.
+//! - Faults are planar below the trace with a single dip, dipping to the right of the trace
+//!   direction (Aki & Richards convention).
+//!
+//! ## Example Config (TOML)
+//!
+//! ```toml
+//! name = "Sakhalin"
+//!
+//! [[area]]
+//! name = "south"
+//! polygon = [[142.0, 46.0], [143.5, 46.0], [143.5, 48.0], [142.0, 48.0]]
+//! depth = 15.0
+//! a_value = 3.5
+//! b_value = 1.0
+//! m_min = 4.5
+//! m_max = 7.0
+//!
+//! [[fault]]
+//! name = "Central Sakhalin"
+//! trace = [[142.2, 47.0], [142.4, 49.0]]
+//! dip = 60.0
+//! upper_depth = 0.0
+//! lower_depth = 20.0
+//! slip_rate = 4.0
+//! b_value = 1.0
+//! m_min = 5.0
+//! m_max = 7.5
+//!
+//! [[point]]
+//! lon = 143.0
+//! lat = 51.0
+//! depth = 10.0
+//! magnitude = 6.0
+//! rate = 0.01
+//! ```
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::sources::SourceModel;
+//!
+//! let model = SourceModel::from_toml_str(r#"
+//!     [[fault]]
+//!     name = "Central Sakhalin"
+//!     trace = [[142.2, 47.0], [142.4, 49.0]]
+//!     dip = 60.0
+//!     upper_depth = 0.0
+//!     lower_depth = 20.0
+//!     slip_rate = 4.0
+//!     b_value = 1.0
+//!     m_min = 5.0
+//!     m_max = 7.5
+//! "#).unwrap();
+//!
+//! let sources = model.point_sources(5., 0.1);
+//! assert!(sources.len() > 100);
+//! ```
+
+use crate::catalog::GutenbergRichter;
+use crate::distance::EARTH_RADIUS_KM;
+use crate::hazard::PointSource;
+use crate::rupture::local_xy;
+use geo::{Contains, Coord, LineString, Point, Polygon};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// Crustal shear modulus (Pa) used for moment balancing of faults.
+pub const SHEAR_MODULUS: f64 = 3.0e10;
+
+/// Default spacing (km) of the points discretizing fault and area sources.
+pub const DEFAULT_SPACING_KM: f64 = 5.;
+
+/// Area source zone with a Gutenberg–Richter magnitude–frequency distribution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AreaSource {
+    /// Name of the zone.
+    #[serde(default)]
+    pub name: String,
+    /// Vertices `[lon, lat]` of the zone polygon in decimal degrees.
+    pub polygon: Vec<[f64; 2]>,
+    /// Hypocentral depth of the zone earthquakes (km).
+    pub depth: f64,
+    /// Magnitude–frequency distribution of the whole zone.
+    #[serde(flatten)]
+    pub recurrence: GutenbergRichter,
+}
+
+/// Fault source with a slip rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FaultSource {
+    /// Name of the fault.
+    #[serde(default)]
+    pub name: String,
+    /// Vertices `[lon, lat]` of the fault trace in decimal degrees.
+    pub trace: Vec<[f64; 2]>,
+    /// Dip angle in degrees from horizontal (0–90].
+    pub dip: f64,
+    /// Upper depth of the seismogenic layer (km).
+    pub upper_depth: f64,
+    /// Lower depth of the seismogenic layer (km).
+    pub lower_depth: f64,
+    /// Long-term slip rate (mm/yr).
+    pub slip_rate: f64,
+    /// Gutenberg–Richter b-value.
+    pub b_value: f64,
+    /// Smallest magnitude of the fault earthquakes.
+    pub m_min: f64,
+    /// Largest magnitude of the fault earthquakes.
+    pub m_max: f64,
+}
+
+/// Source model combining point, area and fault sources.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SourceModel {
+    /// Optional human-readable name of the model.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Point sources.
+    #[serde(default)]
+    pub point: Vec<PointSource>,
+    /// Area sources.
+    #[serde(default)]
+    pub area: Vec<AreaSource>,
+    /// Fault sources.
+    #[serde(default)]
+    pub fault: Vec<FaultSource>,
+}
+
+impl AreaSource {
+    /// Discretize the zone into point sources on a regular grid.
+    ///
+    /// A zone too small to contain a grid point is represented by the mean of its vertices.
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - Grid spacing (km).
+    /// * `bin_width` - Magnitude bin width.
+    pub fn point_sources(&self, spacing: f64, bin_width: f64) -> Vec<PointSource> {
+        let polygon = Polygon::new(
+            LineString::from(
+                self.polygon
+                    .iter()
+                    .map(|[x, y]| Coord { x: *x, y: *y })
+                    .collect::<Vec<_>>(),
+            ),
+            vec![],
+        );
+        let (mut min_lon, mut max_lon) = (f64::INFINITY, f64::NEG_INFINITY);
+        let (mut min_lat, mut max_lat) = (f64::INFINITY, f64::NEG_INFINITY);
+        for [lon, lat] in &self.polygon {
+            (min_lon, max_lon) = (min_lon.min(*lon), max_lon.max(*lon));
+            (min_lat, max_lat) = (min_lat.min(*lat), max_lat.max(*lat));
+        }
+        let d_lat = (spacing / EARTH_RADIUS_KM).to_degrees();
+        let d_lon = d_lat / (0.5 * (min_lat + max_lat)).to_radians().cos();
+
+        let mut locations = Vec::new();
+        let mut lat = min_lat + d_lat / 2.;
+        while lat < max_lat {
+            let mut lon = min_lon + d_lon / 2.;
+            while lon < max_lon {
+                if polygon.contains(&Point::new(lon, lat)) {
+                    locations.push((lon, lat));
+                }
+                lon += d_lon;
+            }
+            lat += d_lat;
+        }
+        if locations.is_empty() && !self.polygon.is_empty() {
+            let n = self.polygon.len() as f64;
+            let lon = self.polygon.iter().map(|[lon, _]| lon).sum::<f64>() / n;
+            let lat = self.polygon.iter().map(|[_, lat]| lat).sum::<f64>() / n;
+            locations.push((lon, lat));
+        }
+        spread(
+            &locations,
+            self.depth,
+            &self.recurrence.bin_rates(bin_width),
+        )
+    }
+}
+
+impl FaultSource {
+    /// Length of the fault trace (km).
+    pub fn length(&self) -> f64 {
+        self.trace
+            .windows(2)
+            .map(|pair| {
+                let (x, y) = local_xy(pair[0][0], pair[0][1], pair[1][0], pair[1][1]);
+                x.hypot(y)
+            })
+            .sum()
+    }
+
+    /// Down-dip width of the fault (km).
+    pub fn width(&self) -> f64 {
+        (self.lower_depth - self.upper_depth) / self.dip.to_radians().sin()
+    }
+
+    /// Seismic moment rate of the fault (N·m/yr).
+    pub fn moment_rate(&self) -> f64 {
+        SHEAR_MODULUS * self.length() * 1e3 * self.width() * 1e3 * self.slip_rate * 1e-3
+    }
+
+    /// Gutenberg–Richter distribution releasing the moment rate of the fault.
+    pub fn recurrence(&self) -> GutenbergRichter {
+        let c = 1.5 - self.b_value;
+        let integral =
+            self.b_value / c * (10_f64.powf(c * self.m_max) - 10_f64.powf(c * self.m_min));
+        let a_value = (self.moment_rate() / integral).log10() - 9.05;
+        GutenbergRichter::new(a_value, self.b_value, self.m_min, self.m_max)
+    }
+
+    /// Discretize the fault into point sources along the trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - Distance between the points along the trace (km).
+    /// * `bin_width` - Magnitude bin width.
+    pub fn point_sources(&self, spacing: f64, bin_width: f64) -> Vec<PointSource> {
+        let length = self.length();
+        if length <= 0. {
+            return Vec::new();
+        }
+        let depth = 0.5 * (self.upper_depth + self.lower_depth);
+        // Horizontal offset of the middle of the seismogenic layer from the trace
+        let offset = 0.5 * (self.lower_depth - self.upper_depth) / self.dip.to_radians().tan();
+        let n = (length / spacing).ceil().max(1.) as usize;
+        let mut locations = Vec::with_capacity(n);
+        let mut start = 0.;
+        let mut targets = (0..n)
+            .map(|i| (i as f64 + 0.5) * length / n as f64)
+            .peekable();
+        for pair in self.trace.windows(2) {
+            let [lon0, lat0] = pair[0];
+            let (x, y) = local_xy(lon0, lat0, pair[1][0], pair[1][1]);
+            let segment = x.hypot(y);
+            while let Some(target) = targets.next_if(|t| *t <= start + segment) {
+                let f = (target - start) / segment;
+                // Right-hand normal of the trace direction points down-dip
+                let east = f * x + offset * y / segment;
+                let north = f * y - offset * x / segment;
+                let lat = lat0 + (north / EARTH_RADIUS_KM).to_degrees();
+                let lon = lon0 + (east / EARTH_RADIUS_KM).to_degrees() / lat0.to_radians().cos();
+                locations.push((lon, lat));
+            }
+            start += segment;
+        }
+        spread(&locations, depth, &self.recurrence().bin_rates(bin_width))
+    }
+}
+
+impl SourceModel {
+    /// Parse a source model from a TOML string.
+    pub fn from_toml_str(content: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Parse a source model from a JSON string.
+    pub fn from_json_str(content: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(content)?)
+    }
+
+    /// Load a source model from a TOML file, or a JSON file if the extension is `.json`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid source model.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            Self::from_json_str(&content)
+        } else {
+            Self::from_toml_str(&content)
+        }
+    }
+
+    /// Discretize all sources of the model into point sources.
+    ///
+    /// # Arguments
+    ///
+    /// * `spacing` - Spacing of the points of fault and area sources (km), see
+    ///   [`DEFAULT_SPACING_KM`].
+    /// * `bin_width` - Magnitude bin width, see
+    ///   [`DEFAULT_BIN_WIDTH`](crate::catalog::DEFAULT_BIN_WIDTH).
+    pub fn point_sources(&self, spacing: f64, bin_width: f64) -> Vec<PointSource> {
+        let mut sources = self.point.clone();
+        for area in &self.area {
+            sources.extend(area.point_sources(spacing, bin_width));
+        }
+        for fault in &self.fault {
+            sources.extend(fault.point_sources(spacing, bin_width));
+        }
+        sources
+    }
+}
+
+/// Split the magnitude bin rates evenly over a set of locations.
+fn spread(locations: &[(f64, f64)], depth: f64, bins: &[(f64, f64)]) -> Vec<PointSource> {
+    let n = locations.len() as f64;
+    locations
+        .iter()
+        .flat_map(|(lon, lat)| {
+            bins.iter().map(move |(magnitude, rate)| {
+                PointSource::new(*lon, *lat, depth, *magnitude, rate / n)
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fault() -> FaultSource {
+        FaultSource {
+            name: "test".to_string(),
+            trace: vec![[142., 47.], [142., 47.5], [142., 48.]],
+            dip: 45.,
+            upper_depth: 0.,
+            lower_depth: 20.,
+            slip_rate: 2.,
+            b_value: 1.,
+            m_min: 5.,
+            m_max: 7.5,
+        }
+    }
+
+    #[test]
+    fn test_fault_moment_balance() {
+        let fault = fault();
+        assert!((fault.length() - 111.2).abs() < 0.1);
+        assert!((fault.width() - 20. * 2_f64.sqrt()).abs() < 1e-9);
+        let sources = fault.point_sources(5., 0.01);
+        assert_eq!(sources.len(), 23 * 250);
+        // The discretized sources release the moment rate of the fault
+        let released: f64 = sources
+            .iter()
+            .map(|s| s.rate * 10_f64.powf(1.5 * s.magnitude + 9.05))
+            .sum();
+        assert!((released / fault.moment_rate() - 1.).abs() < 1e-3);
+        // Points are shifted 10 km down-dip, i.e. east of a northward trace
+        let shift = (sources[0].lon - 142.) * 47_f64.to_radians().cos() * 111.19;
+        assert!((shift - 10.).abs() < 0.1, "{shift}");
+        assert_eq!(sources[0].depth, 10.);
+    }
+
+    #[test]
+    fn test_area_source_rates() {
+        let area = AreaSource {
+            name: "zone".to_string(),
+            polygon: vec![[142., 46.], [143., 46.], [143., 47.], [142., 47.]],
+            depth: 15.,
+            recurrence: GutenbergRichter::new(3., 1., 4.5, 7.),
+        };
+        let sources = area.point_sources(10., 0.1);
+        let total: f64 = sources.iter().map(|s| s.rate).sum();
+        assert!((total - area.recurrence.cumulative_rate(4.5)).abs() < 1e-12);
+        assert!(sources.iter().all(|s| (142. ..143.).contains(&s.lon)));
+        // Tiny zones collapse into one location
+        let tiny = AreaSource {
+            polygon: vec![[142., 46.], [142.01, 46.], [142.01, 46.01]],
+            ..area
+        };
+        assert_eq!(tiny.point_sources(10., 0.5).len(), 5);
+    }
+
+    #[test]
+    fn test_source_model_formats() {
+        let toml = r#"
+            [[area]]
+            polygon = [[142.0, 46.0], [143.0, 46.0], [143.0, 47.0]]
+            depth = 15.0
+            a_value = 3.0
+            b_value = 1.0
+            m_min = 4.5
+            m_max = 7.0
+
+            [[point]]
+            lon = 143.0
+            lat = 51.0
+            depth = 10.0
+            magnitude = 6.0
+            rate = 0.01
+        "#;
+        let model = SourceModel::from_toml_str(toml).unwrap();
+        assert_eq!(model.area[0].recurrence.b_value, 1.);
+        assert!(model.fault.is_empty());
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(SourceModel::from_json_str(&json).unwrap(), model);
+        assert!(model.point_sources(10., 0.1).len() > 1);
+        assert!(SourceModel::from_toml_str("[[area]]\ndepth = 1.0").is_err());
+    }
+}
@@ -0,0 +1,346 @@
+//! Response spectra of a single site.
+//!
+//! Hazard maps show one type of motion at many sites, while structural design needs the
+//! response spectrum of one site: the spectral acceleration at every available oscillator
+//! period. [`calc_spectrum`] evaluates all PSA configurations of a model family (see
+//! [`get_family_configs`]) for one site and earthquake, in parallel across periods, and appends
+//! PGA and PGV when the family provides them.
+//!
+//! Configurations exist for a discrete set of periods only. [`InterpolatedPsa`] composes the
+//! models of the two neighbouring periods into a model for any period in between, interpolating
+//! linearly in log-log space:
+//!
+//! ```text
+//! ln(Y(T)) = ln(Y(T1)) + (ln(Y(T2)) − ln(Y(T1))) · ln(T / T1) / ln(T2 / T1)
+//! ```
+//!
+//! The standard deviation is interpolated with the same weight.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, Vs30Point};
+//! use ground_motion_lib::spectrum::calc_spectrum;
+//!
+//! let site = Vs30Point::new(142.7, 50.2, 350., None, None);
+//! let eq = Earthquake::new_mw(142.5, 50.0, 10.0, 7.0);
+//!
+//! let spectrum = calc_spectrum(&site, &eq, "config_mf2013_crustal").unwrap();
+//! assert_eq!(spectrum.periods.len(), spectrum.values.len());
+//! assert!(spectrum.pga.is_some() && spectrum.pgv.is_some());
+//! println!("Peak PSA: {:?}", spectrum.peak());
+//! ```
+
+use crate::configs::{LibConfig, get_family_configs};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+use rayon::prelude::*;
+use std::error::Error;
+
+/// Response spectrum of a single site.
+#[derive(Debug)]
+pub struct Spectrum {
+    /// Longitude of the site in decimal degrees.
+    pub lon: f64,
+    /// Latitude of the site in decimal degrees.
+    pub lat: f64,
+    /// Oscillator periods (s) in ascending order.
+    pub periods: Vec<f64>,
+    /// Spectral accelerations (%g) at the periods.
+    pub values: Vec<f64>,
+    /// Total standard deviations at the periods, if provided by the models.
+    pub sigma: Vec<Option<f64>>,
+    /// Peak ground acceleration, if the family provides it.
+    pub pga: Option<GmpePoint>,
+    /// Peak ground velocity, if the family provides it.
+    pub pgv: Option<GmpePoint>,
+}
+
+impl Spectrum {
+    /// Spectral acceleration at one of the periods of the spectrum.
+    pub fn value_at(&self, period: f64) -> Option<f64> {
+        self.periods
+            .iter()
+            .position(|p| (p - period).abs() < 1e-9)
+            .map(|i| self.values[i])
+    }
+
+    /// Period (s) and value of the largest spectral acceleration.
+    pub fn peak(&self) -> Option<(f64, f64)> {
+        self.periods
+            .iter()
+            .copied()
+            .zip(self.values.iter().copied())
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Spectrum as result points: PGA, PSA in ascending period order, then PGV.
+    pub fn to_points(&self) -> Vec<GmpePoint> {
+        let copy = |p: &GmpePoint| {
+            let point = GmpePoint::new(p.lon, p.lat, p.value, p.kind);
+            match p.sigma {
+                Some(sigma) => point.with_sigma(sigma),
+                None => point,
+            }
+        };
+        let psa = self.periods.iter().zip(&self.values).zip(&self.sigma).map(
+            |((period, value), sigma)| {
+                let point = GmpePoint::new_psa(self.lon, self.lat, *value, *period);
+                match sigma {
+                    Some(sigma) => point.with_sigma(*sigma),
+                    None => point,
+                }
+            },
+        );
+        self.pga
+            .iter()
+            .map(copy)
+            .chain(psa)
+            .chain(self.pgv.iter().map(copy))
+            .collect()
+    }
+}
+
+/// Calculate the response spectrum of a site for a model family.
+///
+/// # Arguments
+///
+/// * `point` - Site point.
+/// * `eq` - Earthquake source parameters.
+/// * `model_family` - Config key prefix shared by the configurations of all periods, e.g.
+///   `"config_mf2013_crustal"` or `"config_bssa2014"`, see [`get_family_configs`].
+///
+/// # Errors
+///
+/// Returns an error if the family has no PSA configuration.
+pub fn calc_spectrum(
+    point: &Vs30Point,
+    eq: &Earthquake,
+    model_family: &str,
+) -> Result<Spectrum, Box<dyn Error>> {
+    let mut results: Vec<GmpePoint> = get_family_configs(model_family)
+        .par_iter()
+        .map(|(_, config)| config.calc_from_point(point, eq))
+        .collect();
+    if !results
+        .iter()
+        .any(|p| matches!(p.kind, GmpePointKind::Psa { .. }))
+    {
+        return Err(format!("No PSA configs found for the family `{model_family}`").into());
+    }
+
+    let take = |results: &mut Vec<GmpePoint>, kind| {
+        let index = results.iter().position(|p| p.kind == kind)?;
+        Some(results.remove(index))
+    };
+    let pga = take(&mut results, GmpePointKind::Pga);
+    let pgv = take(&mut results, GmpePointKind::Pgv);
+    let psa: Vec<(f64, GmpePoint)> = results
+        .into_iter()
+        .filter_map(|p| Some((p.kind.period()?, p)))
+        .collect();
+
+    Ok(Spectrum {
+        lon: point.lon,
+        lat: point.lat,
+        periods: psa.iter().map(|(period, _)| *period).collect(),
+        values: psa.iter().map(|(_, p)| p.value).collect(),
+        sigma: psa.iter().map(|(_, p)| p.sigma).collect(),
+        pga,
+        pgv,
+    })
+}
+
+/// PSA model for an intermediate period, interpolated in log-log space between the models of
+/// two neighbouring periods.
+#[derive(Debug)]
+pub struct InterpolatedPsa<M> {
+    /// Model of the shorter period.
+    pub lower: M,
+    /// Shorter period (s).
+    pub lower_period: f64,
+    /// Model of the longer period.
+    pub upper: M,
+    /// Longer period (s).
+    pub upper_period: f64,
+    /// Target period (s).
+    pub period: f64,
+}
+
+impl<M: GroundMotionModeling> InterpolatedPsa<M> {
+    /// Compose the models of two periods into a model for `period`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `0 < lower_period <= period <= upper_period`.
+    pub fn new(lower: M, lower_period: f64, upper: M, upper_period: f64, period: f64) -> Self {
+        assert!(
+            lower_period > 0. && lower_period <= period && period <= upper_period,
+            "Interpolation period must lie between the periods of both models"
+        );
+        Self {
+            lower,
+            lower_period,
+            upper,
+            upper_period,
+            period,
+        }
+    }
+
+    /// Weight of the longer-period model.
+    fn weight(&self) -> f64 {
+        if self.upper_period == self.lower_period {
+            0.
+        } else {
+            (self.period / self.lower_period).ln() / (self.upper_period / self.lower_period).ln()
+        }
+    }
+}
+
+impl InterpolatedPsa<&'static LibConfig> {
+    /// Model for `period` from the PSA configs of a model family, see [`get_family_configs`].
+    ///
+    /// # Returns
+    ///
+    /// `None` if `period` lies outside the PSA periods of the family.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ground_motion_lib::gmm::{Earthquake, GroundMotionModeling, Vs30Point};
+    /// use ground_motion_lib::spectrum::InterpolatedPsa;
+    ///
+    /// let gmpe = InterpolatedPsa::from_family("config_bssa2014", 0.75).unwrap();
+    /// let site = Vs30Point::new(142.7, 50.2, 350., None, None);
+    /// let eq = Earthquake::new_mw(142.5, 50.0, 10.0, 7.0);
+    /// let psa = gmpe.calc_from_point(&site, &eq);
+    /// println!("PSA(0.75 s) = {} %g", psa.value);
+    /// ```
+    pub fn from_family(model_family: &str, period: f64) -> Option<Self> {
+        let psa: Vec<(f64, &'static LibConfig)> = get_family_configs(model_family)
+            .into_iter()
+            .filter(|(p, _)| *p > 0.)
+            .collect();
+        let upper = psa.iter().position(|(p, _)| *p >= period)?;
+        let (upper_period, upper_model) = psa[upper];
+        let (lower_period, lower_model) = if upper_period == period || upper == 0 {
+            psa[upper]
+        } else {
+            psa[upper - 1]
+        };
+        if period < lower_period {
+            return None;
+        }
+        Some(Self::new(
+            lower_model,
+            lower_period,
+            upper_model,
+            upper_period,
+            period,
+        ))
+    }
+}
+
+impl<M: GroundMotionModeling> GroundMotionModeling for InterpolatedPsa<M> {
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        let mut metrics = self.lower.distance_metrics();
+        for metric in self.upper.distance_metrics() {
+            if !metrics.contains(&metric) {
+                metrics.push(metric);
+            }
+        }
+        metrics
+    }
+
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let lower = self.lower.calc_from_distances(point, eq, distances);
+        let upper = self.upper.calc_from_distances(point, eq, distances);
+        let w = self.weight();
+        let value = ((1. - w) * lower.value.ln() + w * upper.value.ln()).exp();
+        let gm = GmpePoint::new_psa(point.lon, point.lat, value, self.period);
+        match (lower.sigma, upper.sigma) {
+            (Some(lower), Some(upper)) => gm.with_sigma((1. - w) * lower + w * upper),
+            _ => gm,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::get_lib_config;
+
+    #[test]
+    fn test_spectrum_matches_single_configs() {
+        let site = Vs30Point::new(142.7, 50.2, 350., None, None);
+        let eq = Earthquake::new_mw(142.5, 50., 10., 7.);
+        let spectrum = calc_spectrum(&site, &eq, "config_mf2013_crustal").unwrap();
+        assert_eq!(spectrum.periods, vec![0.3, 1., 3.]);
+        assert!(spectrum.periods.windows(2).all(|w| w[0] < w[1]));
+
+        let psa_1 = get_lib_config("config_mf2013_crustal_psa_10")
+            .unwrap()
+            .calc_from_point(&site, &eq);
+        assert_eq!(spectrum.value_at(1.), Some(psa_1.value));
+        assert_eq!(spectrum.value_at(0.55), None);
+        let (peak_period, peak) = spectrum.peak().unwrap();
+        assert!(peak_period < 1. && peak > psa_1.value);
+
+        let points = spectrum.to_points();
+        assert_eq!(points.len(), 5);
+        assert_eq!(points[0].kind, GmpePointKind::Pga);
+        assert_eq!(points[4].kind, GmpePointKind::Pgv);
+    }
+
+    #[test]
+    fn test_spectrum_needs_psa() {
+        let site = Vs30Point::new(142.7, 50.2, 350., None, None);
+        let eq = Earthquake::new_mw(142.5, 50., 10., 7.);
+        assert!(calc_spectrum(&site, &eq, "unknown").is_err());
+        let bssa = calc_spectrum(&site, &eq, "config_bssa2014").unwrap();
+        assert!(bssa.pga.is_some());
+        assert!(!bssa.periods.is_empty());
+    }
+
+    #[test]
+    fn test_interpolated_psa() {
+        let site = Vs30Point::new(142.7, 50.2, 350., None, None);
+        let eq = Earthquake::new_mw(142.5, 50., 10., 7.);
+        let family = "config_mf2013_crustal";
+        let at = |period| {
+            get_lib_config(&format!("{family}_psa_{period}"))
+                .unwrap()
+                .calc_from_point(&site, &eq)
+        };
+        let (psa_03, psa_10) = (at("03"), at("10"));
+
+        let gmpe = InterpolatedPsa::from_family(family, 0.5).unwrap();
+        assert_eq!((gmpe.lower_period, gmpe.upper_period), (0.3, 1.));
+        let psa = gmpe.calc_from_point(&site, &eq);
+        assert_eq!(psa.kind, GmpePointKind::Psa { period: 0.5 });
+        let w = (0.5_f64 / 0.3).ln() / (1_f64 / 0.3).ln();
+        let expected = psa_03.value.powf(1. - w) * psa_10.value.powf(w);
+        assert!((psa.value - expected).abs() < 1e-9);
+        assert!(psa.sigma.is_some());
+
+        // Exact periods reproduce the config
+        let exact = InterpolatedPsa::from_family(family, 0.3).unwrap();
+        assert!((exact.calc_from_point(&site, &eq).value - psa_03.value).abs() < 1e-9);
+        assert!(InterpolatedPsa::from_family(family, 0.01).is_none());
+        assert!(InterpolatedPsa::from_family(family, 20.).is_none());
+    }
+}
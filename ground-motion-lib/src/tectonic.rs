@@ -0,0 +1,343 @@
+//! Automatic selection of the tectonic regime of an earthquake.
+//!
+//! Subduction regions need separate models for shallow crustal, interface (interplate) and
+//! intraslab (intraplate) events, and picking the wrong one changes predictions by a factor of
+//! several. A [`TectonicSelector`] classifies an earthquake from its focal depth and, if a
+//! [`SlabModel`] is available, from its position relative to the top of the subducting slab:
+//!
+//! ```text
+//! with slab depth S at the epicenter and tolerance ΔS:
+//!     H < S − ΔS                  → crustal (upper plate)
+//!     |H − S| ≤ ΔS and H ≤ Hint   → interplate
+//!     otherwise                   → intraplate
+//! without slab:
+//!     H ≤ Hcr                     → crustal
+//!     Hcr < H ≤ Hint              → interplate
+//!     H > Hint                    → intraplate
+//! ```
+//!
+//! where `H` is the focal depth, `Hcr` the largest depth of crustal events (default 25 km) and
+//! `Hint` the largest depth of the seismogenic interface (default 50 km). The default slab
+//! tolerance is 10 km. An override forces the regime, e.g. when the operator knows better.
+//!
+//! [`TectonicSelector::config_key`] resolves a predefined configuration key to the variant of
+//! the selected regime by replacing its `crustal`, `interplate` or `intraplate` part, so a
+//! single key such as `config_mf2013_crustal_pga` serves all events.
+//!
+//! Slab models are read from Slab2-style `lon, lat, depth` grids with
+//! [`read_slab_model`](crate::readers::read_slab_model).
+//!
+//! Monitored regions spanning several tectonic provinces are described by a
+//! [`TectonicZonation`]: polygons mapped to the preferred configuration of events located in
+//! them, read from GeoJSON with [`read_zonation`](crate::readers::read_zonation). The
+//! configuration of a zone may in turn be resolved to the regime of the event with
+//! [`TectonicSelector::config_key`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, TectonicType};
+//! use ground_motion_lib::tectonic::TectonicSelector;
+//!
+//! let selector = TectonicSelector::default();
+//! let deep = Earthquake::new_mw(143.04, 51.92, 130., 7.);
+//! assert_eq!(selector.select(&deep), TectonicType::Intraplate);
+//! let key = selector.config_key("config_mf2013_crustal_pga", &deep).unwrap();
+//! assert_eq!(key, "config_mf2013_intraplate_pga");
+//!
+//! let forced = selector.with_override(TectonicType::Crustal);
+//! assert_eq!(forced.select(&deep), TectonicType::Crustal);
+//! ```
+
+use crate::configs::get_lib_config;
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, TectonicType};
+use geo::{Contains, MultiPolygon, Point};
+use std::error::Error;
+
+/// Default largest depth of crustal events without a slab model (km).
+pub const DEFAULT_CRUSTAL_MAX_DEPTH: f64 = 25.;
+
+/// Default largest depth of interface events (km).
+pub const DEFAULT_INTERFACE_MAX_DEPTH: f64 = 50.;
+
+/// Default largest vertical distance of interface events from the slab top (km).
+pub const DEFAULT_SLAB_TOLERANCE: f64 = 10.;
+
+/// Default largest distance from the epicenter to the nearest slab node (km).
+pub const DEFAULT_SLAB_SEARCH_RADIUS: f64 = 25.;
+
+/// Depth of the top of a subducting slab, sampled at scattered or gridded nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlabModel {
+    /// `(lon, lat, depth)` nodes with positive depths (km)
+    pub nodes: Vec<(f64, f64, f64)>,
+    /// Largest distance from a location to its nearest node (km)
+    pub search_radius: f64,
+}
+
+impl SlabModel {
+    /// Create a slab model from `(lon, lat, depth)` nodes with positive depths (km).
+    pub fn new(nodes: Vec<(f64, f64, f64)>) -> Self {
+        Self {
+            nodes,
+            search_radius: DEFAULT_SLAB_SEARCH_RADIUS,
+        }
+    }
+
+    /// Set the largest distance from a location to its nearest node (km).
+    pub fn with_search_radius(mut self, search_radius: f64) -> Self {
+        self.search_radius = search_radius;
+        self
+    }
+
+    /// Slab depth (km) of the nearest node, `None` if no node is within the search radius.
+    pub fn depth_at(&self, lon: f64, lat: f64) -> Option<f64> {
+        self.nodes
+            .iter()
+            .map(|&(node_lon, node_lat, depth)| {
+                let distance =
+                    DistanceMethod::Haversine.epicentral_distance(lon, lat, node_lon, node_lat);
+                (distance, depth)
+            })
+            .filter(|(distance, _)| *distance <= self.search_radius)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, depth)| depth)
+    }
+}
+
+/// Selector of the tectonic regime of earthquakes by depth and slab geometry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TectonicSelector {
+    /// Largest depth of crustal events without a slab model (km)
+    pub crustal_max_depth: f64,
+    /// Largest depth of interface events (km)
+    pub interface_max_depth: f64,
+    /// Largest vertical distance of interface events from the slab top (km)
+    pub slab_tolerance: f64,
+    /// Optional slab geometry
+    pub slab: Option<SlabModel>,
+    /// Regime used for all events instead of the automatic selection
+    pub override_type: Option<TectonicType>,
+}
+
+impl Default for TectonicSelector {
+    fn default() -> Self {
+        Self {
+            crustal_max_depth: DEFAULT_CRUSTAL_MAX_DEPTH,
+            interface_max_depth: DEFAULT_INTERFACE_MAX_DEPTH,
+            slab_tolerance: DEFAULT_SLAB_TOLERANCE,
+            slab: None,
+            override_type: None,
+        }
+    }
+}
+
+impl TectonicSelector {
+    /// Set the largest depths (km) of crustal and interface events.
+    pub fn with_depths(mut self, crustal_max_depth: f64, interface_max_depth: f64) -> Self {
+        self.crustal_max_depth = crustal_max_depth;
+        self.interface_max_depth = interface_max_depth;
+        self
+    }
+
+    /// Classify events relative to the top of a subducting slab.
+    pub fn with_slab(mut self, slab: SlabModel) -> Self {
+        self.slab = Some(slab);
+        self
+    }
+
+    /// Set the largest vertical distance of interface events from the slab top (km).
+    pub fn with_slab_tolerance(mut self, slab_tolerance: f64) -> Self {
+        self.slab_tolerance = slab_tolerance;
+        self
+    }
+
+    /// Use the given regime for all events.
+    pub fn with_override(mut self, tectonic_type: TectonicType) -> Self {
+        self.override_type = Some(tectonic_type);
+        self
+    }
+
+    /// Tectonic regime of an earthquake.
+    ///
+    /// Events outside of the slab model are classified by depth alone.
+    pub fn select(&self, eq: &Earthquake) -> TectonicType {
+        if let Some(tectonic_type) = self.override_type {
+            return tectonic_type;
+        }
+        let slab_depth = self
+            .slab
+            .as_ref()
+            .and_then(|slab| slab.depth_at(eq.lon, eq.lat));
+        match slab_depth {
+            Some(slab_depth) if eq.depth < slab_depth - self.slab_tolerance => {
+                TectonicType::Crustal
+            }
+            Some(slab_depth)
+                if eq.depth <= slab_depth + self.slab_tolerance
+                    && eq.depth <= self.interface_max_depth =>
+            {
+                TectonicType::Interplate
+            }
+            Some(_) => TectonicType::Intraplate,
+            None if eq.depth <= self.crustal_max_depth => TectonicType::Crustal,
+            None if eq.depth <= self.interface_max_depth => TectonicType::Interplate,
+            None => TectonicType::Intraplate,
+        }
+    }
+
+    /// Predefined configuration key of the selected regime.
+    ///
+    /// The `crustal`, `interplate` or `intraplate` part of `key` is replaced with the regime of
+    /// the earthquake, see [`TectonicSelector::select`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `key` has no tectonic part, or the model has no configuration for
+    /// the selected regime.
+    pub fn config_key(&self, key: &str, eq: &Earthquake) -> Result<String, Box<dyn Error>> {
+        let tectonic_type = self.select(eq);
+        let mut parts: Vec<String> = key.split('_').map(str::to_string).collect();
+        let part = parts
+            .iter_mut()
+            .find(|part| part.parse::<TectonicType>().is_ok())
+            .ok_or_else(|| format!("Config `{key}` has no tectonic variants"))?;
+        *part = tectonic_type.to_string();
+        let resolved = parts.join("_");
+        if get_lib_config(&resolved).is_none() {
+            return Err(format!("Config `{key}` has no {tectonic_type} variant").into());
+        }
+        Ok(resolved)
+    }
+}
+
+/// Zone of a tectonic zonation with its preferred configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TectonicZone {
+    /// Name of the zone
+    pub name: String,
+    /// Key of the predefined configuration of events in the zone
+    pub config: String,
+    /// Area of the zone in geographic coordinates (lon, lat)
+    pub area: MultiPolygon<f64>,
+}
+
+/// Polygons of tectonic provinces mapped to their preferred configurations.
+///
+/// Zones are searched in order, so nested or overlapping zones are listed from the most
+/// specific to the most general.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TectonicZonation {
+    /// Zones of the zonation
+    pub zones: Vec<TectonicZone>,
+}
+
+impl TectonicZonation {
+    /// Create a zonation from its zones.
+    pub fn new(zones: Vec<TectonicZone>) -> Self {
+        Self { zones }
+    }
+
+    /// First zone containing a location, `None` outside of the zonation.
+    pub fn zone_at(&self, lon: f64, lat: f64) -> Option<&TectonicZone> {
+        let point = Point::new(lon, lat);
+        self.zones.iter().find(|zone| zone.area.contains(&point))
+    }
+
+    /// Preferred configuration key of an earthquake, from the zone of its epicenter.
+    pub fn config_for(&self, eq: &Earthquake) -> Option<&str> {
+        self.zone_at(eq.lon, eq.lat)
+            .map(|zone| zone.config.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_rules() {
+        let selector = TectonicSelector::default();
+        let kinds: Vec<TectonicType> = [10., 25., 40., 50.1, 300.]
+            .into_iter()
+            .map(|depth| selector.select(&Earthquake::new_mw(142.5, 50., depth, 6.)))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                TectonicType::Crustal,
+                TectonicType::Crustal,
+                TectonicType::Interplate,
+                TectonicType::Intraplate,
+                TectonicType::Intraplate,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slab_rules() {
+        // Slab top at 30 km below the epicenter, no slab 100 km away
+        let slab = SlabModel::new(vec![(143., 50., 30.), (143.1, 50., 32.)]);
+        assert_eq!(slab.depth_at(143.02, 50.01), Some(30.));
+        assert_eq!(slab.depth_at(144.5, 50.), None);
+        let selector = TectonicSelector::default().with_slab(slab);
+        let select =
+            |lon: f64, depth: f64| selector.select(&Earthquake::new_mw(lon, 50., depth, 6.));
+        assert_eq!(select(143., 12.), TectonicType::Crustal);
+        assert_eq!(select(143., 35.), TectonicType::Interplate);
+        assert_eq!(select(143., 45.), TectonicType::Intraplate);
+        // Depth rules away from the slab
+        assert_eq!(select(144.5, 45.), TectonicType::Interplate);
+    }
+
+    #[test]
+    fn test_config_key() {
+        let selector = TectonicSelector::default();
+        let eq = Earthquake::new_mw(142.5, 50., 40., 7.);
+        let key = selector.config_key("config_zhao2006_crustal_psa_10", &eq);
+        assert_eq!(key.unwrap(), "config_zhao2006_interplate_psa_10");
+        assert!(selector.config_key("config_bssa2014_pga", &eq).is_err());
+        // BC Hydro has no crustal configs
+        let shallow = Earthquake::new_mw(142.5, 50., 10., 7.);
+        assert!(
+            selector
+                .config_key("config_bchydro2016_interplate_pga", &shallow)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_zonation_lookup() {
+        let square = |lon: f64, lat: f64, size: f64| {
+            MultiPolygon::new(vec![geo::Polygon::new(
+                geo::LineString::from(vec![
+                    (lon, lat),
+                    (lon + size, lat),
+                    (lon + size, lat + size),
+                    (lon, lat + size),
+                    (lon, lat),
+                ]),
+                vec![],
+            )])
+        };
+        let zone = |name: &str, config: &str, area| TectonicZone {
+            name: name.to_string(),
+            config: config.to_string(),
+            area,
+        };
+        // A small zone listed before the region enclosing it
+        let zonation = TectonicZonation::new(vec![
+            zone("basin", "config_bssa2014_pga", square(142., 46., 1.)),
+            zone(
+                "sakhalin",
+                "config_mf2013_crustal_pga",
+                square(141., 45., 10.),
+            ),
+        ]);
+        assert_eq!(zonation.zone_at(142.5, 46.5).unwrap().name, "basin");
+        let eq = Earthquake::new_mw(143., 50., 10., 6.);
+        assert_eq!(zonation.config_for(&eq), Some("config_mf2013_crustal_pga"));
+        assert!(zonation.zone_at(160., 50.).is_none());
+    }
+}
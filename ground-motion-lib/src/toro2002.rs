@@ -0,0 +1,184 @@
+//! Implementation of Toro et al. (1997, 2002) Ground Motion Prediction Equations (GMPE) for
+//! stable continental regions (central and eastern North America).
+//!
+//! The model predicts the median motion on hard rock:
+//!
+//! ```text
+//! ln(Y) = C1 + C2·(M − 6) + C3·(M − 6)² − C4·ln(R_M) − (C5 − C4)·max(ln(R_M / 100), 0) − C6·R_M
+//! R_M   = sqrt(Rjb² + C7²·(exp(−1.25 + 0.227·M))²)
+//! ```
+//!
+//! where `Y` is PGA or 5%-damped PSA in g. The magnitude-dependent finite-fault factor of `R_M`
+//! is the 2002 update of the original 1997 relation. The midcontinent Mw coefficients are used.
+//!
+//! Coefficients are shipped as a text table ([`TORO2002_TABLE`]) and parsed with
+//! [`CoeffsTable`]; the predefined configurations are generated from its rows.
+//!
+//! Current simplifications:
+//!
+//! - Hard-rock reference site; the site Vs30 is not used.
+//! - For point sources the Joyner–Boore distance equals the epicentral distance.
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Toro et al. midcontinent Mw coefficients (PGA, PSA at 0.1, 0.2, 0.4, 1 and 2 s).
+pub const TORO2002_TABLE: &str = "
+    imt   c1     c2    c3     c4    c5    c6      c7     sigma
+    pga   2.20   0.81  0.00   1.27  1.16  0.0021  9.3    0.69
+    0.1   2.37   0.81  0.00   1.10  1.02  0.0040  8.3    0.73
+    0.2   1.73   0.84  0.00   0.98  0.66  0.0042  7.5    0.73
+    0.4   1.07   1.05  -0.10  0.93  0.56  0.0033  7.1    0.76
+    1.0   0.09   1.42  -0.20  0.90  0.49  0.0023  6.8    0.81
+    2.0   -0.74  1.86  -0.31  0.92  0.46  0.0017  6.9    0.86
+";
+
+/// Toro et al. (2002) Ground Motion Prediction Equation parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TORO2002 {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling
+    pub c2: f64,
+    pub c3: f64,
+    /// Geometric spreading within and beyond 100 km
+    pub c4: f64,
+    pub c5: f64,
+    /// Anelastic attenuation
+    pub c6: f64,
+    /// Near-source saturation distance (km)
+    pub c7: f64,
+    /// Total standard deviation of `ln(Y)`
+    pub sigma: f64,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl TORO2002 {
+    /// Build the model from a row of a Toro et al. coefficient table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a coefficient column is missing.
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            c1: row.coeff("c1"),
+            c2: row.coeff("c2"),
+            c3: row.coeff("c3"),
+            c4: row.coeff("c4"),
+            c5: row.coeff("c5"),
+            c6: row.coeff("c6"),
+            c7: row.coeff("c7"),
+            sigma: row.coeff("sigma"),
+            motion_kind: row.kind(),
+        }
+    }
+
+    /// All models of the shipped coefficient table.
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(TORO2002_TABLE)
+            .expect("Invalid Toro et al. coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Median `ln(Y)` on hard rock (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rjb` - Joyner–Boore distance (km).
+    pub fn ln_median(&self, magnitude: f64, rjb: f64) -> f64 {
+        let dm = magnitude - 6.;
+        let rm = rjb.hypot(self.c7 * (-1.25 + 0.227 * magnitude).exp());
+        self.c1 + self.c2 * dm + self.c3 * dm.powi(2)
+            - self.c4 * rm.ln()
+            - (self.c5 - self.c4) * (rm / 100.).ln().max(0.)
+            - self.c6 * rm
+    }
+}
+
+impl GroundMotionModeling for TORO2002 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rjb]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// The epicentral distance is used as the Joyner–Boore distance of point sources.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rjb = distances.rjb.unwrap_or(distances.repi);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: self.ln_median(eq.mw(), rjb).exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_rows() {
+        let models = TORO2002::from_table();
+        assert_eq!(models.len(), 6);
+        assert_eq!(models[0].motion_kind, GmpePointKind::Pga);
+        assert_eq!(models[5].period, 2.);
+        assert_eq!(models[3].c3, -0.1);
+    }
+
+    #[test]
+    fn test_far_field_spreading() {
+        let pga = TORO2002::from_table()[0];
+        // Beyond 100 km the geometric spreading slope changes from C4 to C5
+        let slope = |r1: f64, r2: f64| {
+            (pga.ln_median(6., r2) - pga.ln_median(6., r1) + pga.c6 * (r2 - r1)) / (r2 / r1).ln()
+        };
+        assert!((slope(300., 600.) + pga.c5).abs() < 1e-2);
+        assert!((slope(60., 100.) + pga.c4).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_finite_rupture_uses_rjb() {
+        use crate::rupture::Rupture;
+
+        let pga = TORO2002::from_table()[0];
+        // Site 50 km north of the epicenter, above a 120 km long vertical rupture
+        let site = Vs30Point::new(142.8, 52.45, 2000., None, None);
+        let point_source = Earthquake::new_mw(142.8, 52., 10., 7.);
+        let finite = Earthquake::new_mw(142.8, 52., 10., 7.)
+            .with_rupture(Rupture::new(0., 90., 120., 20.).with_ztor(0.));
+        let expected = pga.ln_median(7., 0.).exp() * 100.;
+        assert!((pga.calc_from_point(&site, &finite).value - expected).abs() < 1e-6);
+        assert!(pga.calc_from_point(&site, &point_source).value < expected / 2.);
+    }
+}
@@ -0,0 +1,230 @@
+//! Implementation of the Travasarou et al. (2003) Arias intensity prediction equation for
+//! active crustal regions.
+//!
+//! Arias intensity measures the total energy of the strong shaking and is the usual input of
+//! liquefaction and earthquake-induced landslide screening:
+//!
+//! ```text
+//! ln(Ia) = c1 + c2·(M − 6) + c3·ln(M / 6) + c4·ln(sqrt(R² + h²))
+//!        + (s11 + s12·(M − 6))·Sc + (s21 + s22·(M − 6))·Sd + f1·Fn + f2·Fr
+//! ```
+//!
+//! where `Ia` is the Arias intensity in m/s, `R` is the rupture distance (km) and `Sc`, `Sd` are
+//! the site class indicators, see [`Travasarou2003Site`].
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism (`Fn = Fr = 0`).
+//! - The site class is derived from the site Vs30.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
+
+/// Site class of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Travasarou2003Site {
+    /// Rock and stiff soil, Vs30 ≥ 360 m/s
+    B,
+    /// Dense soil, 180 ≤ Vs30 < 360 m/s
+    C,
+    /// Soft soil, Vs30 < 180 m/s
+    D,
+}
+
+impl Travasarou2003Site {
+    /// Site class for the given Vs30 (m/s).
+    pub fn from_vs30(vs30: f64) -> Self {
+        if vs30 >= 360. {
+            Self::B
+        } else if vs30 >= 180. {
+            Self::C
+        } else {
+            Self::D
+        }
+    }
+}
+
+/// Travasarou et al. (2003) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Travasarou2003Coeffs {
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude scaling
+    pub c2: f64,
+    pub c3: f64,
+    /// Geometric spreading
+    pub c4: f64,
+    /// Near-source saturation depth (km)
+    pub h: f64,
+    /// Site class C term and its magnitude dependence
+    pub s11: f64,
+    pub s12: f64,
+    /// Site class D term and its magnitude dependence
+    pub s21: f64,
+    pub s22: f64,
+    /// Normal and reverse faulting terms
+    pub f1: f64,
+    pub f2: f64,
+}
+
+/// Travasarou et al. (2003) coefficients.
+pub const TRAVASAROU2003_COEFFS: Travasarou2003Coeffs = Travasarou2003Coeffs {
+    c1: 2.8,
+    c2: -1.981,
+    c3: 20.72,
+    c4: -1.703,
+    h: 8.78,
+    s11: 0.454,
+    s12: 0.101,
+    s21: 0.479,
+    s22: 0.334,
+    f1: -0.166,
+    f2: 0.512,
+};
+
+/// Travasarou et al. (2003) Arias intensity prediction equation.
+#[derive(Debug)]
+pub struct TRAVASAROU2003 {
+    /// Model coefficients
+    pub coeffs: Travasarou2003Coeffs,
+}
+
+impl Default for TRAVASAROU2003 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TRAVASAROU2003 {
+    /// Create a model with the published coefficients.
+    pub fn new() -> Self {
+        Self {
+            coeffs: TRAVASAROU2003_COEFFS,
+        }
+    }
+
+    /// Median `ln(Ia)` (m/s).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    /// * `site` - Site class.
+    pub fn ln_median(&self, magnitude: f64, rrup: f64, site: Travasarou2003Site) -> f64 {
+        let c = &self.coeffs;
+        let dm = magnitude - 6.;
+        let site_term = match site {
+            Travasarou2003Site::B => 0.,
+            Travasarou2003Site::C => c.s11 + c.s12 * dm,
+            Travasarou2003Site::D => c.s21 + c.s22 * dm,
+        };
+        c.c1 + c.c2 * dm + c.c3 * (magnitude / 6.).ln() + c.c4 * rrup.hypot(c.h).ln() + site_term
+    }
+
+    /// Inter-event standard deviation of `ln(Ia)` for the given magnitude.
+    pub fn tau(magnitude: f64) -> f64 {
+        0.611 - 0.047 * (magnitude.clamp(4.7, 7.6) - 4.7)
+    }
+
+    /// Intra-event standard deviation of `ln(Ia)`.
+    ///
+    /// The deviation decreases linearly in `ln(Ia)` between 0.013 and 0.135 m/s and is constant
+    /// outside this range.
+    pub fn phi(arias_intensity: f64, site: Travasarou2003Site) -> f64 {
+        let (low, high) = match site {
+            Travasarou2003Site::B => (1.18, 0.94),
+            Travasarou2003Site::C => (1.17, 0.93),
+            Travasarou2003Site::D => (0.96, 0.73),
+        };
+        let (ia_low, ia_high) = (0.013_f64, 0.135_f64);
+        let w = ((arias_intensity.ln() - ia_low.ln()) / (ia_high.ln() - ia_low.ln())).clamp(0., 1.);
+        low + w * (high - low)
+    }
+
+    /// Total standard deviation of `ln(Ia)`.
+    pub fn sigma(magnitude: f64, arias_intensity: f64, site: Travasarou2003Site) -> f64 {
+        Self::tau(magnitude).hypot(Self::phi(arias_intensity, site))
+    }
+}
+
+impl GroundMotionModeling for TRAVASAROU2003 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with the Arias intensity in m/s.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let rrup = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.ztor()));
+        let site = Travasarou2003Site::from_vs30(point.vs30);
+        let arias_intensity = self.ln_median(eq.mw(), rrup, site).exp();
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            value: arias_intensity,
+            kind: GmpePointKind::AriasIntensity,
+            sigma: Some(Self::sigma(eq.mw(), arias_intensity, site)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_value() {
+        let m = TRAVASAROU2003::new();
+        let expected =
+            2.8 - 1.981 + 20.72 * (7_f64 / 6.).ln() - 1.703 * (100_f64 + 8.78 * 8.78).sqrt().ln();
+        let ln_ia = m.ln_median(7., 10., Travasarou2003Site::B);
+        assert!((ln_ia - expected).abs() < 1e-12);
+        // About 0.7 m/s near a M7 rupture
+        assert!((0.5..1.).contains(&ln_ia.exp()));
+    }
+
+    #[test]
+    fn test_soil_amplification() {
+        let m = TRAVASAROU2003::new();
+        let rock = m.ln_median(6., 30., Travasarou2003Site::B);
+        let c = m.ln_median(6., 30., Travasarou2003Site::C);
+        let d = m.ln_median(6., 30., Travasarou2003Site::D);
+        assert!((c - rock - 0.454).abs() < 1e-12);
+        assert!((d - rock - 0.479).abs() < 1e-12);
+        assert_eq!(Travasarou2003Site::from_vs30(760.), Travasarou2003Site::B);
+        assert_eq!(Travasarou2003Site::from_vs30(250.), Travasarou2003Site::C);
+        assert_eq!(Travasarou2003Site::from_vs30(150.), Travasarou2003Site::D);
+    }
+
+    #[test]
+    fn test_sigma_bounds() {
+        assert!((TRAVASAROU2003::tau(4.) - 0.611).abs() < 1e-12);
+        assert!((TRAVASAROU2003::tau(8.) - 0.4747).abs() < 1e-12);
+        let site = Travasarou2003Site::B;
+        assert!((TRAVASAROU2003::phi(0.001, site) - 1.18).abs() < 1e-12);
+        assert!((TRAVASAROU2003::phi(1., site) - 0.94).abs() < 1e-12);
+        let mid = TRAVASAROU2003::phi(0.05, site);
+        assert!(mid < 1.18 && mid > 0.94);
+    }
+}
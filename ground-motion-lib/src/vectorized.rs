@@ -13,6 +13,11 @@
 //! ## Primary Types and Functions
 //!
 //! - [`calc_gmpe_vec`]: Perform parallel ground motion prediction for a vector of [`Vs30Point`] instances.
+//! - [`calc_gmpe_vec_with`]: Same as [`calc_gmpe_vec`] with a selectable epicentral distance method.
+//! - [`calc_gmpe_vec_corrected`]: Same as [`calc_gmpe_vec_with`] with a user-defined per-point correction.
+//! - [`calc_sequence_envelope`]: Per-site maximum and exceedance counts over an earthquake sequence.
+//! - [`attenuation_curve`], [`attenuation_curves`]: Median ground motion versus distance of one or
+//!   more models for a fixed scenario (trellis tables).
 //! - [`compute_stats`]: Calculate summary statistics over a collection of predicted [`GmpePoint`] values.
 //! - [`Stats`]: Struct representing the computed statistical summary.
 //!
@@ -32,8 +37,8 @@
 //! use ground_motion_lib::vectorized::{calc_gmpe_vec, compute_stats};
 //!
 //! let points = vec![
-//!     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(0)),
-//!     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(1)),
+//!     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(-40.)),
+//!     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(20.)),
 //! ];
 //!
 //! let eq = Earthquake {
@@ -42,6 +47,8 @@
 //!     depth: 10.0,
 //!     magnitude: 6.5,
 //!     magnitude_kind: Magnitude::Mw,
+//!     rupture: None,
+//!     segments: None,
 //! };
 //!
 //! let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
@@ -62,7 +69,8 @@
 //!
 //! All operations in this module are thread-safe and make use of [`Rayon`] for concurrency.
 
-use crate::gmm::{Earthquake, GmpePoint, GroundMotionModeling, Vs30Point};
+use crate::distance::{DistanceMethod, SourceDistances};
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, Vs30Point};
 use rayon::prelude::*;
 
 /// Calculate ground motion predictions for a set of site points in parallel.
@@ -99,8 +107,8 @@ use rayon::prelude::*;
 /// use ground_motion_lib::vectorized::calc_gmpe_vec;
 ///
 /// let points = vec![
-///     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(0)),
-///     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(1)),
+///     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(-40.)),
+///     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(20.)),
 /// ];
 ///
 /// let eq = Earthquake {
@@ -109,6 +117,8 @@ use rayon::prelude::*;
 ///     depth: 10.0,
 ///     magnitude: 6.5,
 ///     magnitude_kind: Magnitude::Mw,
+///     rupture: None,
+///     segments: None,
 /// };
 ///
 /// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
@@ -130,32 +140,417 @@ use rayon::prelude::*;
 /// - [`Vs30Point`](crate::gmm::Vs30Point)
 /// - [`GroundMotionModeling`](crate::gmm::GroundMotionModeling)
 ///
-pub fn calc_gmpe_vec<T: GroundMotionModeling + Sync>(
+pub fn calc_gmpe_vec<T: GroundMotionModeling + Sync + ?Sized>(
     points: &[Vs30Point],
     gmpe: &T,
     eq: &Earthquake,
 ) -> Vec<GmpePoint> {
+    calc_gmpe_vec_with(points, gmpe, eq, DistanceMethod::Haversine)
+}
+
+/// Calculate ground motion predictions for a set of site points in parallel, using the given
+/// epicentral distance calculation method.
+///
+/// Works exactly like [`calc_gmpe_vec`], but allows to select the distance method per run.
+/// Only the distance metrics of the model ([`GroundMotionModeling::distance_metrics`]) are
+/// computed for every site.
+/// [`DistanceMethod::FlatEarth`] is recommended for dense grids spanning less than 2–3 degrees,
+/// where it saves a measurable amount of trigonometry per point with negligible error.
+///
+/// # Arguments
+///
+/// * `points` - A slice of `Vs30Point` instances.
+/// * `gmpe` - A reference to a type implementing the `GroundMotionModeling` trait.
+/// * `eq` - A reference to the `Earthquake` instance describing the earthquake event.
+/// * `method` - Epicentral distance calculation method.
+///
+/// # Returns
+///
+/// A `Vec<GmpePoint>` containing the calculated ground motion values for each input site point.
+///
+/// # Examples
+///
+/// ```rust
+/// use ground_motion_lib::gmm::{Vs30Point, Earthquake};
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::distance::DistanceMethod;
+/// use ground_motion_lib::vectorized::calc_gmpe_vec_with;
+///
+/// let points = vec![
+///     Vs30Point::new(142.5, 50.0, 400., Some(200.), Some(-40.)),
+///     Vs30Point::new(142.6, 50.1, 350., Some(150.), Some(20.)),
+/// ];
+/// let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+/// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+///
+/// let results = calc_gmpe_vec_with(&points, gmpe_ref, &eq, DistanceMethod::FlatEarth);
+/// println!("{results:?}");
+/// ```
+pub fn calc_gmpe_vec_with<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    gmpe: &T,
+    eq: &Earthquake,
+    method: DistanceMethod,
+) -> Vec<GmpePoint> {
+    let metrics = gmpe.distance_metrics();
     points
         .par_iter()
-        .map(|point| point.get_gm(gmpe, eq))
+        .map(|point| {
+            let distances = SourceDistances::compute(&metrics, eq, point.lon, point.lat, method);
+            gmpe.calc_from_distances(point, eq, &distances)
+        })
+        .collect()
+}
+
+/// Calculate ground motion predictions for a set of site points in parallel and apply a
+/// user-defined per-point correction to each result.
+///
+/// The `correction` closure receives the input site point and the value predicted by the model,
+/// and returns the corrected point. This enables custom site or regional corrections
+/// (e.g. local amplification factors) without forking the model code. The predictions are
+/// computed as by [`calc_gmpe_vec_with`].
+///
+/// # Arguments
+///
+/// * `points` - A slice of `Vs30Point` instances.
+/// * `gmpe` - A reference to a type implementing the `GroundMotionModeling` trait.
+/// * `eq` - A reference to the `Earthquake` instance describing the earthquake event.
+/// * `method` - Epicentral distance calculation method.
+/// * `correction` - Closure applied to every prediction after the model.
+///
+/// # Returns
+///
+/// A `Vec<GmpePoint>` containing the corrected ground motion values for each input site point.
+///
+/// # Examples
+///
+/// ```rust
+/// use ground_motion_lib::gmm::{Vs30Point, Earthquake, GmpePoint};
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::distance::DistanceMethod;
+/// use ground_motion_lib::vectorized::calc_gmpe_vec_corrected;
+///
+/// let points = vec![
+///     Vs30Point::new(142.5, 50.0, 400., Some(200.), None),
+///     Vs30Point::new(142.6, 50.1, 150., Some(150.), None),
+/// ];
+/// let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+/// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+///
+/// // Amplify soft-soil sites by an additional 20%
+/// let method = DistanceMethod::Haversine;
+/// let results = calc_gmpe_vec_corrected(&points, gmpe_ref, &eq, method, |site, mut gm: GmpePoint| {
+///     if site.vs30 < 200. {
+///         gm.value *= 1.2;
+///     }
+///     gm
+/// });
+/// println!("{results:?}");
+/// ```
+pub fn calc_gmpe_vec_corrected<T, F>(
+    points: &[Vs30Point],
+    gmpe: &T,
+    eq: &Earthquake,
+    method: DistanceMethod,
+    correction: F,
+) -> Vec<GmpePoint>
+where
+    T: GroundMotionModeling + Sync + ?Sized,
+    F: Fn(&Vs30Point, GmpePoint) -> GmpePoint + Sync,
+{
+    let metrics = gmpe.distance_metrics();
+    points
+        .par_iter()
+        .map(|point| {
+            let distances = SourceDistances::compute(&metrics, eq, point.lon, point.lat, method);
+            correction(point, gmpe.calc_from_distances(point, eq, &distances))
+        })
+        .collect()
+}
+
+/// Per-site envelope of ground motions over a sequence of earthquakes.
+#[derive(Debug)]
+pub struct SequenceEnvelope {
+    /// Per-site maximum ground motion over all events, as predicted for the event of `max_event`.
+    pub max: Vec<GmpePoint>,
+    /// Index (into the input event slice) of the event producing the per-site maximum.
+    pub max_event: Vec<usize>,
+    /// Exceedance thresholds, in the units of the model output.
+    pub thresholds: Vec<f64>,
+    /// Per-site counts of events exceeding each threshold (`exceedances[site][threshold]`).
+    pub exceedances: Vec<Vec<u32>>,
+}
+
+/// Accumulator for the per-site sequence envelope.
+struct EnvelopeAcc {
+    max: Vec<Option<GmpePoint>>,
+    max_event: Vec<usize>,
+    exceedances: Vec<Vec<u32>>,
+}
+
+impl EnvelopeAcc {
+    fn new(n_sites: usize, n_thresholds: usize) -> Self {
+        Self {
+            max: (0..n_sites).map(|_| None).collect(),
+            max_event: vec![0; n_sites],
+            exceedances: vec![vec![0; n_thresholds]; n_sites],
+        }
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        for (site, (other_max, other_event)) in
+            other.max.into_iter().zip(other.max_event).enumerate()
+        {
+            let Some(other_max) = other_max else {
+                continue;
+            };
+            let replace = match &self.max[site] {
+                None => true,
+                Some(max) => {
+                    other_max.value > max.value
+                        || (other_max.value == max.value && other_event < self.max_event[site])
+                }
+            };
+            if replace {
+                self.max[site] = Some(other_max);
+                self.max_event[site] = other_event;
+            }
+        }
+        for (counts, other_counts) in self.exceedances.iter_mut().zip(&other.exceedances) {
+            for (count, other_count) in counts.iter_mut().zip(other_counts) {
+                *count += other_count;
+            }
+        }
+        self
+    }
+}
+
+/// Run a sequence of earthquakes over the same grid and compute the per-site maximum
+/// (envelope) and cumulative exceedance counts.
+///
+/// This is intended for aftershock-sequence impact assessment, where the shaking of every
+/// event in the sequence is evaluated on the same site grid. The computation is parallelized
+/// across events using Rayon.
+///
+/// # Arguments
+///
+/// * `points` - A slice of `Vs30Point` instances.
+/// * `gmpe` - A reference to a type implementing the `GroundMotionModeling` trait.
+/// * `events` - Earthquakes of the sequence.
+/// * `thresholds` - Ground motion levels for which exceedances are counted.
+///
+/// # Returns
+///
+/// A [`SequenceEnvelope`] with the per-site maximum and exceedance counts.
+///
+/// # Panics
+///
+/// This function will panic if called with an empty `events` slice.
+///
+/// # Examples
+///
+/// ```rust
+/// use ground_motion_lib::gmm::{Vs30Point, Earthquake};
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::vectorized::calc_sequence_envelope;
+///
+/// let points = vec![
+///     Vs30Point::new(142.5, 50.0, 400., Some(200.), None),
+///     Vs30Point::new(142.6, 50.1, 350., Some(150.), None),
+/// ];
+/// let events = vec![
+///     Earthquake::new_mw(142.4, 50.0, 10.0, 6.5),
+///     Earthquake::new_mw(142.7, 50.2, 12.0, 5.8),
+/// ];
+/// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+///
+/// let envelope = calc_sequence_envelope(&points, gmpe_ref, &events, &[1., 5., 10.]);
+/// println!("{:?}", envelope.max);
+/// println!("{:?}", envelope.exceedances);
+/// ```
+pub fn calc_sequence_envelope<T: GroundMotionModeling + Sync + ?Sized>(
+    points: &[Vs30Point],
+    gmpe: &T,
+    events: &[Earthquake],
+    thresholds: &[f64],
+) -> SequenceEnvelope {
+    assert!(!events.is_empty(), "Event sequence must not be empty");
+    let n_sites = points.len();
+    let n_thresholds = thresholds.len();
+
+    let acc = events
+        .par_iter()
+        .enumerate()
+        .fold(
+            || EnvelopeAcc::new(n_sites, n_thresholds),
+            |mut acc, (event_idx, eq)| {
+                for (site, point) in points.iter().enumerate() {
+                    let gm = point.get_gm(gmpe, eq);
+                    for (count, threshold) in acc.exceedances[site].iter_mut().zip(thresholds) {
+                        if gm.value > *threshold {
+                            *count += 1;
+                        }
+                    }
+                    if acc.max[site]
+                        .as_ref()
+                        .is_none_or(|max| gm.value > max.value)
+                    {
+                        acc.max[site] = Some(gm);
+                        acc.max_event[site] = event_idx;
+                    }
+                }
+                acc
+            },
+        )
+        .reduce(
+            || EnvelopeAcc::new(n_sites, n_thresholds),
+            EnvelopeAcc::merge,
+        );
+
+    let max = acc
+        .max
+        .into_iter()
+        .map(|max| max.expect("every site has a prediction of a non-empty sequence"))
+        .collect();
+
+    SequenceEnvelope {
+        max,
+        max_event: acc.max_event,
+        thresholds: thresholds.to_vec(),
+        exceedances: acc.exceedances,
+    }
+}
+
+/// Ground motion versus distance for a fixed magnitude, depth and site condition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttenuationCurve {
+    /// Epicentral distances (km).
+    pub distances: Vec<f64>,
+    /// Predicted median values at the distances.
+    pub values: Vec<f64>,
+    /// Total standard deviations at the distances, if provided by the model.
+    pub sigma: Vec<Option<f64>>,
+    /// Type of the predicted ground motion.
+    pub kind: GmpePointKind,
+}
+
+/// Calculate an attenuation curve (median ground motion versus distance) of a model.
+///
+/// The earthquake is a point source with moment magnitude `eq_mag` and all sites share the same
+/// Vs30, so the scaling of a model can be checked and plotted without building a site grid.
+/// Values are computed in parallel over the distances.
+///
+/// # Arguments
+///
+/// * `gmpe` - A reference to a type implementing the `GroundMotionModeling` trait.
+/// * `eq_mag` - Moment magnitude (Mw).
+/// * `depth` - Hypocentral depth (km).
+/// * `vs30` - Site Vs30 (m/s).
+/// * `distances` - Epicentral distances (km), see [`log_distances`].
+///
+/// # Examples
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::vectorized::{attenuation_curve, log_distances};
+///
+/// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let curve = attenuation_curve(gmpe_ref, 7.0, 13.0, 350., &log_distances(1., 300., 50));
+/// assert!(curve.values[0] > curve.values[49]);
+/// ```
+pub fn attenuation_curve<T: GroundMotionModeling + Sync + ?Sized>(
+    gmpe: &T,
+    eq_mag: f64,
+    depth: f64,
+    vs30: f64,
+    distances: &[f64],
+) -> AttenuationCurve {
+    let eq = Earthquake::new_mw(0., 0., depth, eq_mag);
+    let site = Vs30Point::new(0., 0., vs30, None, None);
+    let points: Vec<GmpePoint> = distances
+        .par_iter()
+        .map(|distance| gmpe.calc_from_distance(&site, &eq, *distance))
+        .collect();
+    AttenuationCurve {
+        distances: distances.to_vec(),
+        values: points.iter().map(|p| p.value).collect(),
+        sigma: points.iter().map(|p| p.sigma).collect(),
+        kind: points.first().map_or(GmpePointKind::Pga, |p| p.kind),
+    }
+}
+
+/// Calculate attenuation curves of several models for the same scenario (a trellis plot).
+///
+/// # Examples
+///
+/// ```rust
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::vectorized::attenuation_curves;
+///
+/// let models = [
+///     get_lib_config("config_mf2013_crustal_pga").unwrap(),
+///     get_lib_config("config_mf2013_crustal_pga_2").unwrap(),
+/// ];
+/// let curves = attenuation_curves(&models, 6.5, 10.0, 400., &[10., 50., 100.]);
+/// assert_eq!(curves.len(), 2);
+/// ```
+pub fn attenuation_curves<T: GroundMotionModeling + Sync + ?Sized>(
+    gmpes: &[&T],
+    eq_mag: f64,
+    depth: f64,
+    vs30: f64,
+    distances: &[f64],
+) -> Vec<AttenuationCurve> {
+    gmpes
+        .iter()
+        .map(|gmpe| attenuation_curve(*gmpe, eq_mag, depth, vs30, distances))
+        .collect()
+}
+
+/// `n` logarithmically spaced distances (km) from `min` to `max`, both included.
+///
+/// # Panics
+///
+/// Panics unless `0 < min <= max` and `n >= 2`.
+pub fn log_distances(min: f64, max: f64, n: usize) -> Vec<f64> {
+    assert!(
+        min > 0. && min <= max,
+        "Distances must satisfy 0 < min <= max"
+    );
+    assert!(n >= 2, "At least two distances are needed");
+    let step = (max / min).ln() / (n - 1) as f64;
+    (0..n)
+        .map(|i| {
+            if i == n - 1 {
+                max
+            } else {
+                min * (step * i as f64).exp()
+            }
+        })
         .collect()
 }
 
 /// Struct for computed summary statistics
 #[derive(Debug, PartialEq)]
 pub struct Stats {
+    /// Type of motion of the summarized values, e.g. `Psa(0.3)`.
+    pub kind: Option<GmpePointKind>,
     pub mean: f64,
     pub std_dev: f64,
     pub min: f64,
     pub max: f64,
     pub median: f64,
+    /// Number of `NaN` values (e.g. predictions clipped beyond the maximum distance of a
+    /// [`CutoffModel`](crate::cutoff::CutoffModel)), excluded from the statistics above.
+    pub missing: usize,
 }
 
 /// Compute summary statistics (mean, standard deviation, minimum, maximum, and median)
 /// for a list of `GmpePoint` values.
 ///
 /// This function processes the `value` field from a slice of `GmpePoint` instances,
-/// computing key statistical measures.
+/// computing key statistical measures. `NaN` values are counted separately and excluded from
+/// the statistics.
 /// Most operations are parallelized using Rayon where possible (e.g., sum, variance, min, max)
 /// to improve performance on larger datasets.
 ///
@@ -166,11 +561,13 @@ pub struct Stats {
 /// # Returns
 ///
 /// A `Stats` struct containing:
+/// - `kind` — the type of motion of the first point
 /// - `mean` — the arithmetic mean
 /// - `std_dev` — the sample standard deviation
 /// - `min` — the minimum value
 /// - `max` — the maximum value
 /// - `median` — the median value (sorted centrally)
+/// - `missing` — the number of `NaN` values
 ///
 /// # Example
 ///
@@ -198,9 +595,10 @@ pub struct Stats {
 /// - Sum, variance, min, and max calculations use `Rayon`’s parallel iterators.
 /// - Median is computed single-threaded via an in-place sort since sorting isn’t parallelized here.
 ///
-/// # Panics
+/// # Empty Input
 ///
-/// This function will panic if called with an empty slice.
+/// If the slice is empty, or all values are `NaN` (e.g. a grid pre-filtered to no sites), the
+/// mean, standard deviation and median are `NaN`.
 ///
 /// # See Also
 ///
@@ -209,10 +607,14 @@ pub struct Stats {
 /// - [`Stats`](crate::vectorized::Stats)
 ///
 pub fn compute_stats(points: &[GmpePoint]) -> Stats {
-    let n = points.len() as f64;
-
-    // Extract values into a Vec<f64> to operate on
-    let mut values: Vec<f64> = points.iter().map(|p| p.value).collect();
+    // Extract non-missing values into a Vec<f64> to operate on
+    let mut values: Vec<f64> = points
+        .iter()
+        .map(|p| p.value)
+        .filter(|v| !v.is_nan())
+        .collect();
+    let missing = points.len() - values.len();
+    let n = values.len() as f64;
 
     // Compute sum in parallel, then mean
     let sum: f64 = values.par_iter().sum();
@@ -242,7 +644,9 @@ pub fn compute_stats(points: &[GmpePoint]) -> Stats {
 
     // Compute median by sorting values locally (single-threaded)
     values.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    let median = if values.len() % 2 == 0 {
+    let median = if values.is_empty() {
+        f64::NAN
+    } else if values.len().is_multiple_of(2) {
         let mid = values.len() / 2;
         (values[mid - 1] + values[mid]) / 2.0
     } else {
@@ -250,18 +654,100 @@ pub fn compute_stats(points: &[GmpePoint]) -> Stats {
     };
 
     Stats {
+        kind: points.first().map(|p| p.kind),
         mean,
         std_dev,
         min,
         max,
         median,
+        missing,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::gmm::GmpePointKind;
+    use crate::configs::get_mf2013_lib_configs;
+
+    #[test]
+    fn test_attenuation_curve() {
+        let gmpe = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let distances = log_distances(1., 300., 20);
+        assert_eq!(distances.len(), 20);
+        assert!((distances[0] - 1.).abs() < 1e-12 && distances[19] == 300.);
+
+        let curve = attenuation_curve(gmpe, 7., 13., 350., &distances);
+        assert_eq!(curve.kind, GmpePointKind::Pga);
+        assert!(curve.values.windows(2).all(|w| w[0] > w[1]));
+        assert!(curve.sigma.iter().all(|s| s.is_some()));
+
+        // Matches a grid prediction at the same epicentral distance
+        let eq = Earthquake::new_mw(142., 50., 13., 7.);
+        let site = Vs30Point::new(142., 50., 350., None, None);
+        let expected = gmpe.calc_from_distance(&site, &eq, distances[5]).value;
+        assert_eq!(curve.values[5], expected);
+
+        let stronger = attenuation_curve(gmpe, 7.5, 13., 350., &distances);
+        assert!(stronger.values[0] > curve.values[0]);
+    }
+
+    #[test]
+    fn test_calc_sequence_envelope() {
+        let points = vec![
+            Vs30Point::new(142.5, 50.0, 400., None, None),
+            Vs30Point::new(143.5, 50.5, 400., None, None),
+        ];
+        let events = vec![
+            Earthquake::new_mw(142.5, 50.0, 10.0, 6.0),
+            Earthquake::new_mw(143.5, 50.5, 10.0, 5.5),
+            Earthquake::new_mw(143.0, 50.2, 10.0, 5.0),
+        ];
+        let gmpe = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let envelope = calc_sequence_envelope(&points, gmpe, &events, &[0., 1e9]);
+
+        assert_eq!(envelope.max_event, vec![0, 1]);
+        for (site, point) in points.iter().enumerate() {
+            let expected = events
+                .iter()
+                .map(|eq| point.get_gm(gmpe, eq).value)
+                .fold(f64::NEG_INFINITY, f64::max);
+            assert_eq!(envelope.max[site].value, expected);
+            assert_eq!(envelope.exceedances[site], vec![3, 0]);
+            // The winning prediction is kept as is, including its sigma
+            let winner = point.get_gm(gmpe, &events[envelope.max_event[site]]);
+            assert_eq!(envelope.max[site].sigma, winner.sigma);
+            assert!(envelope.max[site].sigma.is_some());
+            assert_eq!(envelope.max[site].kind, winner.kind);
+        }
+    }
+
+    #[test]
+    fn test_calc_gmpe_vec_corrected() {
+        let points = vec![
+            Vs30Point::new(142.5, 50.0, 400., None, None),
+            Vs30Point::new(142.6, 50.1, 150., None, None),
+        ];
+        let eq = Earthquake::new_mw(142.4, 50.0, 10.0, 6.5);
+        let gmpe = get_mf2013_lib_configs()
+            .get("config_mf2013_crustal_pga")
+            .unwrap();
+        let double_soft = |site: &Vs30Point, mut gm: GmpePoint| {
+            if site.vs30 < 200. {
+                gm.value *= 2.;
+            }
+            gm
+        };
+        for method in [DistanceMethod::Haversine, DistanceMethod::FlatEarth] {
+            let plain = calc_gmpe_vec_with(&points, gmpe, &eq, method);
+            let corrected = calc_gmpe_vec_corrected(&points, gmpe, &eq, method, double_soft);
+            assert_eq!(corrected[0].value, plain[0].value);
+            assert!((corrected[1].value - 2. * plain[1].value).abs() < 1e-12);
+        }
+    }
 
     #[test]
     fn test_compute_stats() {
@@ -271,30 +757,35 @@ mod tests {
                 lat: 0.0,
                 value: 1.0,
                 kind: GmpePointKind::Pga,
+                sigma: None,
             },
             GmpePoint {
                 lon: 0.0,
                 lat: 0.0,
                 value: 2.0,
                 kind: GmpePointKind::Pga,
+                sigma: None,
             },
             GmpePoint {
                 lon: 0.0,
                 lat: 0.0,
                 value: 3.0,
                 kind: GmpePointKind::Pga,
+                sigma: None,
             },
             GmpePoint {
                 lon: 0.0,
                 lat: 0.0,
                 value: 4.0,
                 kind: GmpePointKind::Pga,
+                sigma: None,
             },
             GmpePoint {
                 lon: 0.0,
                 lat: 0.0,
                 value: 5.0,
                 kind: GmpePointKind::Pga,
+                sigma: None,
             },
         ];
 
@@ -302,11 +793,13 @@ mod tests {
 
         // Expected values calculated by hand
         let expected = Stats {
+            kind: Some(GmpePointKind::Pga),
             mean: 3.0,
             std_dev: 1.5811388300841898, // sqrt(2.5)
             min: 1.0,
             max: 5.0,
             median: 3.0,
+            missing: 0,
         };
 
         assert!((stats.mean - expected.mean).abs() < 1e-10);
@@ -314,5 +807,23 @@ mod tests {
         assert_eq!(stats.min, expected.min);
         assert_eq!(stats.max, expected.max);
         assert_eq!(stats.median, expected.median);
+        assert_eq!(stats.kind, expected.kind);
+        assert_eq!(stats.missing, expected.missing);
+    }
+
+    #[test]
+    fn test_compute_stats_missing() {
+        let points = vec![
+            GmpePoint::new_pga(0., 0., 2.),
+            GmpePoint::new_pga(0., 0., f64::NAN),
+            GmpePoint::new_pga(0., 0., 4.),
+            GmpePoint::new_pga(0., 0., 0.),
+        ];
+        let stats = compute_stats(&points);
+        assert_eq!(stats.missing, 1);
+        // Genuine zeros are kept
+        assert_eq!(stats.mean, 2.);
+        assert_eq!(stats.min, 0.);
+        assert_eq!(stats.median, 2.);
     }
 }
@@ -0,0 +1,281 @@
+//! Vertical-component ground motion via V/H ratio models.
+//!
+//! Most GMPEs predict the horizontal component only. V/H ratio models scale a horizontal
+//! prediction to the vertical component, so any model of the crate can be turned into a vertical
+//! estimate as a post-processing step. [`GulerceAbrahamson2011`] implements [`GridTransform`]
+//! and can be appended to a [`Pipeline`](crate::pipeline::Pipeline), or applied with the site
+//! Vs30 of the input grid via [`GulerceAbrahamson2011::vertical_vec`].
+//!
+//! ## Gülerce & Abrahamson (2011)
+//!
+//! ```text
+//! ln(V/H) = a1 + a4·(M − 6.75) + a8·(8.5 − M)² + a2·ln(sqrt(R² + 4.5²))
+//!         + a10·ln(min(Vs30, Vlin) / Vlin)
+//! ```
+//!
+//! where `R` is the rupture distance (km). Coefficients are shipped as a text table
+//! ([`GA2011_TABLE`]) and parsed with [`CoeffsTable`].
+//!
+//! Current simplifications:
+//!
+//! - Unspecified (strike-slip) fault mechanism and no hanging-wall term.
+//! - Linear site response.
+//! - For point sources the rupture distance is estimated from the epicentral distance and the
+//!   depth to the top of rupture ([`Earthquake::ztor`]).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use ground_motion_lib::gmm::{Earthquake, GmpePoint};
+//! use ground_motion_lib::pipeline::Pipeline;
+//! use ground_motion_lib::vertical::GulerceAbrahamson2011;
+//!
+//! let eq = Earthquake::new_mw(142.83, 52.63, 11.0, 7.1);
+//! let pipeline = Pipeline::new().then(GulerceAbrahamson2011::new(&eq));
+//!
+//! let horizontal = vec![GmpePoint::new_pga(142.95, 53.0, 10.0)];
+//! let vertical = pipeline.run(horizontal);
+//! assert!(vertical[0].value < 10.0);
+//! ```
+
+use crate::coeffs_table::{CoeffsRow, CoeffsTable};
+use crate::distance::DistanceMethod;
+use crate::gmm::{Earthquake, GmpePoint, GmpePointKind, Vs30Point};
+use crate::pipeline::GridTransform;
+use rayon::prelude::*;
+
+/// Reference magnitude of the magnitude scaling.
+const C1: f64 = 6.75;
+/// Near-source saturation term (km).
+const C4: f64 = 4.5;
+
+/// Gülerce & Abrahamson (2011) V/H coefficients (PGA, PGV, PSA from 0.05 to 10 s).
+pub const GA2011_TABLE: &str = "
+    imt   vlin    a1     a2     a4    a8     a10   sigma
+    pga   865.1   0.35   -0.18  0.05  -0.01  0.15  0.48
+    pgv   400.0   0.05   -0.12  0.03  -0.01  0.25  0.45
+    0.05  1053.5  0.65   -0.23  0.05  -0.01  0.1   0.52
+    0.1   1085.7  0.6    -0.23  0.05  -0.01  0.1   0.51
+    0.2   1032.5  0.25   -0.17  0.05  -0.01  0.12  0.49
+    0.3   911.0   0.05   -0.13  0.05  -0.01  0.15  0.47
+    0.5   744.0   -0.1   -0.1   0.05  -0.01  0.2   0.46
+    1.0   400.0   -0.25  -0.07  0.05  -0.01  0.3   0.45
+    2.0   400.0   -0.35  -0.05  0.05  -0.01  0.3   0.45
+    3.0   400.0   -0.4   -0.04  0.05  -0.01  0.3   0.46
+    5.0   400.0   -0.45  -0.03  0.05  -0.01  0.3   0.47
+    10.0  400.0   -0.5   -0.02  0.05  -0.01  0.3   0.48
+";
+
+/// Period-dependent Gülerce & Abrahamson (2011) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ga2011Coeffs {
+    /// Spectral period (s); `0.0` for PGA and `-1.0` for PGV.
+    pub period: f64,
+    /// Vs30 above which the site term is zero (m/s)
+    pub vlin: f64,
+    /// Constant term
+    pub a1: f64,
+    /// Geometric spreading
+    pub a2: f64,
+    /// Linear and quadratic magnitude scaling
+    pub a4: f64,
+    pub a8: f64,
+    /// Vs30 scaling
+    pub a10: f64,
+    /// Standard deviation of `ln(V/H)`
+    pub sigma: f64,
+}
+
+impl Ga2011Coeffs {
+    /// Build the coefficients from a row of [`GA2011_TABLE`].
+    pub fn from_row(row: &CoeffsRow) -> Self {
+        Self {
+            period: row.period,
+            vlin: row.coeff("vlin"),
+            a1: row.coeff("a1"),
+            a2: row.coeff("a2"),
+            a4: row.coeff("a4"),
+            a8: row.coeff("a8"),
+            a10: row.coeff("a10"),
+            sigma: row.coeff("sigma"),
+        }
+    }
+
+    /// All rows of [`GA2011_TABLE`].
+    pub fn from_table() -> Vec<Self> {
+        CoeffsTable::parse(GA2011_TABLE)
+            .expect("Invalid Gülerce & Abrahamson (2011) coefficient table")
+            .rows()
+            .iter()
+            .map(Self::from_row)
+            .collect()
+    }
+
+    /// Median `ln(V/H)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `rrup` - Rupture distance (km).
+    /// * `vs30` - Site Vs30 (m/s).
+    pub fn ln_ratio(&self, magnitude: f64, rrup: f64, vs30: f64) -> f64 {
+        self.a1
+            + self.a4 * (magnitude - C1)
+            + self.a8 * (8.5 - magnitude).powi(2)
+            + self.a2 * rrup.hypot(C4).ln()
+            + self.a10 * (vs30.min(self.vlin) / self.vlin).ln()
+    }
+}
+
+/// Gülerce & Abrahamson (2011) vertical-component stage.
+///
+/// Scales horizontal PGA, PGV and PSA points by the V/H ratio of the event. Points of other
+/// types of motion, and PSA points of periods missing from [`GA2011_TABLE`], are dropped.
+#[derive(Debug, Clone)]
+pub struct GulerceAbrahamson2011 {
+    /// Epicenter longitude (decimal degrees).
+    pub lon: f64,
+    /// Epicenter latitude (decimal degrees).
+    pub lat: f64,
+    /// Depth to the top of rupture (km).
+    pub ztor: f64,
+    /// Moment magnitude.
+    pub magnitude: f64,
+    /// Site Vs30 (m/s) used when the points carry no site information.
+    pub vs30: f64,
+    /// Model coefficients
+    pub coeffs: Vec<Ga2011Coeffs>,
+}
+
+impl GulerceAbrahamson2011 {
+    /// Create a stage for an earthquake on Vs30 = 760 m/s sites.
+    pub fn new(eq: &Earthquake) -> Self {
+        Self {
+            lon: eq.lon,
+            lat: eq.lat,
+            ztor: eq.ztor(),
+            magnitude: eq.mw(),
+            vs30: 760.,
+            coeffs: Ga2011Coeffs::from_table(),
+        }
+    }
+
+    /// Set the site Vs30 (m/s) used by [`GridTransform::apply`].
+    pub fn with_vs30(mut self, vs30: f64) -> Self {
+        self.vs30 = vs30;
+        self
+    }
+
+    /// Coefficients for the given type of motion, if supported.
+    pub fn coefficients(&self, kind: GmpePointKind) -> Option<&Ga2011Coeffs> {
+        let period = match kind {
+            GmpePointKind::Pga => 0.,
+            GmpePointKind::Pgv => -1.,
+            GmpePointKind::Psa { period } => period,
+            _ => return None,
+        };
+        self.coeffs
+            .iter()
+            .find(|c| (c.period - period).abs() < 1e-6)
+    }
+
+    /// V/H ratio at a site, or `None` if the type of motion is not supported.
+    pub fn ratio(&self, kind: GmpePointKind, lon: f64, lat: f64, vs30: f64) -> Option<f64> {
+        let c = self.coefficients(kind)?;
+        let rrup = DistanceMethod::Haversine
+            .epicentral_distance(self.lon, self.lat, lon, lat)
+            .hypot(self.ztor);
+        Some(c.ln_ratio(self.magnitude, rrup, vs30).exp())
+    }
+
+    /// Vertical component of a horizontal grid using the Vs30 of each site.
+    ///
+    /// `horizontal` must be computed for `sites` in the same order, as returned by
+    /// [`calc_gmpe_vec`](crate::vectorized::calc_gmpe_vec). Unsupported points are dropped.
+    pub fn vertical_vec(&self, sites: &[Vs30Point], horizontal: &[GmpePoint]) -> Vec<GmpePoint> {
+        sites
+            .par_iter()
+            .zip(horizontal.par_iter())
+            .filter_map(|(site, point)| {
+                let ratio = self.ratio(point.kind, point.lon, point.lat, site.vs30)?;
+                Some(GmpePoint::new(
+                    point.lon,
+                    point.lat,
+                    point.value * ratio,
+                    point.kind,
+                ))
+            })
+            .collect()
+    }
+}
+
+impl GridTransform for GulerceAbrahamson2011 {
+    fn apply(&self, point: GmpePoint) -> Option<GmpePoint> {
+        let ratio = self.ratio(point.kind, point.lon, point.lat, self.vs30)?;
+        Some(GmpePoint::new(
+            point.lon,
+            point.lat,
+            point.value * ratio,
+            point.kind,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio_decreases_with_distance() {
+        let eq = Earthquake::new_mw(142., 50., 10., 6.75);
+        let ga = GulerceAbrahamson2011::new(&eq);
+        let c = ga.coefficients(GmpePointKind::Pga).unwrap();
+        let near = c.ln_ratio(6.75, 10., 865.1);
+        let expected = 0.35 - 0.18 * (100_f64 + 4.5 * 4.5).sqrt().ln() - 0.01 * 1.75 * 1.75;
+        assert!((near - expected).abs() < 1e-12);
+        let far = c.ln_ratio(6.75, 100., 865.1);
+        assert!(near > far);
+        // Short-period V/H exceeds long-period V/H near the source
+        let short = ga.coeffs.iter().find(|c| c.period == 0.1).unwrap();
+        let long = ga.coeffs.iter().find(|c| c.period == 3.).unwrap();
+        assert!(short.ln_ratio(6.75, 5., 760.) > long.ln_ratio(6.75, 5., 760.));
+    }
+
+    #[test]
+    fn test_stage_drops_unsupported_points() {
+        let eq = Earthquake::new_mw(142., 50., 10., 7.);
+        let ga = GulerceAbrahamson2011::new(&eq);
+        assert!(
+            ga.apply(GmpePoint::new_psa(142.1, 50., 20., 0.75))
+                .is_none()
+        );
+        assert!(
+            ga.apply(GmpePoint::new(142.1, 50., 6., GmpePointKind::Mmi))
+                .is_none()
+        );
+        let psa = ga.apply(GmpePoint::new_psa(142.1, 50., 20., 1.)).unwrap();
+        assert!(psa.value < 20.);
+    }
+
+    #[test]
+    fn test_vertical_vec_uses_site_vs30() {
+        let eq = Earthquake::new_mw(142., 50., 10., 7.);
+        let ga = GulerceAbrahamson2011::new(&eq);
+        let sites = vec![
+            Vs30Point::new(142.1, 50., 760., None, None),
+            Vs30Point::new(142.1, 50., 250., None, None),
+        ];
+        let horizontal = vec![
+            GmpePoint::new_pgv(142.1, 50., 10.),
+            GmpePoint::new_pgv(142.1, 50., 10.),
+        ];
+        let vertical = ga.vertical_vec(&sites, &horizontal);
+        assert_eq!(vertical.len(), 2);
+        // Horizontal motion is amplified more than vertical on soft soils
+        assert!(vertical[1].value < vertical[0].value);
+        let staged = ga
+            .with_vs30(250.)
+            .apply(GmpePoint::new_pgv(142.1, 50., 10.));
+        assert_eq!(staged.unwrap().value, vertical[1].value);
+    }
+}
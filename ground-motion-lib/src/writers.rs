@@ -12,24 +12,62 @@
 //! ## Primary Functions
 //!
 //! - [`write_gmpe_points`]: Writes a vector of [`GmpePoint`] instances to a delimited file.
+//! - [`write_gmpe_points_with_metadata`]: Same, preceded by `# key: value` metadata lines.
+//! - [`write_gmpe_geojson`]: Writes [`GmpePoint`] instances as a GeoJSON `FeatureCollection`
+//!   for direct display in web GIS clients.
+//! - [`write_gmpe_json`], [`write_gmpe_jsonl`]: Write [`GmpePoint`] instances as a single JSON
+//!   document with metadata, or as JSON Lines (one object per point) for streaming consumers.
+//! - [`write_gmpe_points_with_metadata_to_writer`], [`write_gmpe_geojson_to_writer`],
+//!   [`write_gmpe_json_to_writer`], [`write_gmpe_jsonl_to_writer`]: Same, to any writer such as
+//!   standard output, for use in shell pipelines.
+//! - [`write_vs30_points`]: Writes a site grid in the input format of the readers.
+//! - [`write_attenuation_curve`]: Writes a distance/value table of an
+//!   [`AttenuationCurve`].
+//! - [`write_spectrum`]: Writes a period/value table of a single-site response spectrum.
+//! - [`write_deaggregation`]: Writes the magnitude–distance–epsilon bins of a
+//!   [`Deaggregation`].
+//! - [`write_oq_hazard_curves`], [`write_oq_hazard_map`]: Write hazard curves and maps in the
+//!   CSV format of the OpenQuake engine, for comparison and post-processing with OQ tooling.
+//! - [`write_esri_ascii_grid`], [`write_gmpe_points_esri_ascii`]: Write a [`RasterGrid`], or
+//!   prediction results on a regular grid, as an Arc/Info ASCII grid for loss tools that only
+//!   ingest this format.
+//! - `write_geotiff`, `write_gmpe_points_geotiff`: Write a [`RasterGrid`], or prediction
+//!   results on a regular grid, as a georeferenced single-band GeoTIFF (EPSG:4326) readable by
+//!   QGIS and ArcGIS. Require the `geotiff` crate feature.
+//! - `write_gmt_grid`, `write_gmpe_points_gmt_grid`: Write a [`RasterGrid`], or prediction
+//!   results on a regular grid, as a GMT-compatible (COARDS) netCDF grid for plotting with
+//!   `grdimage` without an `xyz2grd` step. Require the `netcdf` crate feature.
+//! - `write_result_stack_hdf5`: Writes stacked multi-scenario or multi-realization results
+//!   ([`ResultStack`]) to an HDF5 file. Requires the `hdf5` crate feature.
 //!
 //! ## Example Output Format (tab-delimited)
 //!
 //! ```text
-//! lon    lat    value    kind
-//! 142.600    50.100    0.789    Pga
-//! 142.700    50.200    0.923    Pga
+//! lon    lat    value    kind    sigma
+//! 142.600    50.100    0.789    Pga    0.875
+//! 142.700    50.200    0.923    Pga    0.875
 //! ```
 //!
+//! Spectral accelerations carry their oscillator period in the `kind` column, e.g. `Psa(0.3)`.
+//! The `sigma` column holds the total standard deviation of the model and is left empty for
+//! models without one.
+//!
 //! ## See Also
 //!
 //! - [`crate::gmm::GmpePoint`]
 //! - [`csv`](https://docs.rs/csv/)
 
-use crate::gmm::GmpePoint;
+use crate::compression::create_writer;
+use crate::deaggregation::Deaggregation;
+use crate::gmm::{GmpePoint, GmpePointKind, Vs30Point};
+use crate::hazard::HazardCurve;
+use crate::raster::RasterGrid;
+use crate::vectorized::AttenuationCurve;
 use csv::WriterBuilder;
+use serde_json::json;
 use std::error::Error;
 use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
 /// Writes a list of [`GmpePoint`] instances to a delimited text file.
@@ -60,11 +98,12 @@ use std::path::Path;
 /// use ground_motion_lib::gmm::{GmpePoint, GmpePointKind};
 ///
 /// let points = vec![
-///     GmpePoint { lon: 10.0, lat: 20.0, value: 0.5, kind: GmpePointKind::Pga },
-///     GmpePoint { lon: 15.0, lat: 25.0, value: 0.8, kind: GmpePointKind::Pga },
+///     GmpePoint { lon: 10.0, lat: 20.0, value: 0.5, kind: GmpePointKind::Pga, sigma: Some(0.7) },
+///     GmpePoint { lon: 15.0, lat: 25.0, value: 0.8, kind: GmpePointKind::Pga, sigma: None },
 /// ];
 ///
-/// write_gmpe_points("output.csv", b'\t', &points).unwrap();
+/// let path = std::env::temp_dir().join("output.csv");
+/// write_gmpe_points(path, b'\t', &points).unwrap();
 /// ```
 ///
 /// # Errors
@@ -78,7 +117,7 @@ pub fn write_gmpe_points<P: AsRef<Path>>(
     points: &[GmpePoint],
 ) -> Result<(), Box<dyn Error>> {
     // Open the file in write mode, create if doesn't exist
-    let file = File::create(path)?;
+    let file = create_writer(path)?;
 
     // Build a CSV writer with the specified delimiter and no headers
     let mut wtr = WriterBuilder::new()
@@ -95,3 +134,1040 @@ pub fn write_gmpe_points<P: AsRef<Path>>(
     wtr.flush()?;
     Ok(())
 }
+
+/// Writes a list of [`Vs30Point`] site instances to a delimited text file.
+///
+/// The file has no header row and the columns of [`read_vs30_points`], so site grids generated
+/// within the crate (e.g. with [`ElevationGrid::vs30_points`]) can be read back or passed to
+/// the command-line tool. Missing optional values are left empty.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::Vs30Point;
+/// use ground_motion_lib::writers::write_vs30_points;
+///
+/// let sites = vec![Vs30Point::new(142.5, 50.0, 400., None, None)];
+/// write_vs30_points("sites.txt", b'\t', &sites).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or a site fails to serialize.
+///
+/// [`read_vs30_points`]: crate::readers::read_vs30_points
+/// [`ElevationGrid::vs30_points`]: crate::slope_vs30::ElevationGrid::vs30_points
+pub fn write_vs30_points<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    points: &[Vs30Point],
+) -> Result<(), Box<dyn Error>> {
+    let file = create_writer(path)?;
+    let mut wtr = WriterBuilder::new()
+        .delimiter(delim)
+        .has_headers(false)
+        .from_writer(file);
+    for point in points {
+        wtr.serialize(point)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes a list of [`GmpePoint`] instances to a delimited text file with metadata.
+///
+/// Metadata entries are written as `# key: value` comment lines before the header row, e.g. the
+/// event bias applied to the grid. Such files can be read back with any CSV reader that skips
+/// `#` comments.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `delim` — Delimiter character for the file.
+/// * `points` — A slice of [`GmpePoint`] instances to write.
+/// * `metadata` — Key-value metadata entries.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::GmpePoint;
+/// use ground_motion_lib::writers::write_gmpe_points_with_metadata;
+///
+/// let points = vec![GmpePoint::new_pga(10.0, 20.0, 0.5)];
+/// let metadata = vec![("event_bias".to_string(), "0.25".to_string())];
+/// write_gmpe_points_with_metadata("output_meta.csv", b'\t', &points, &metadata).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or any [`GmpePoint`] fails to serialize.
+pub fn write_gmpe_points_with_metadata<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    points: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    write_gmpe_points_with_metadata_to_writer(create_writer(path)?, delim, points, metadata)
+}
+
+/// Writes a list of [`GmpePoint`] instances with metadata in the format of
+/// [`write_gmpe_points_with_metadata`] to any writer, e.g. standard output.
+///
+/// # Errors
+///
+/// Returns an error if writing fails or any [`GmpePoint`] fails to serialize.
+pub fn write_gmpe_points_with_metadata_to_writer<W: Write>(
+    mut writer: W,
+    delim: u8,
+    points: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    for (key, value) in metadata {
+        writeln!(writer, "# {key}: {value}")?;
+    }
+
+    let mut wtr = WriterBuilder::new()
+        .delimiter(delim)
+        .has_headers(true)
+        .from_writer(writer);
+    for point in points {
+        wtr.serialize(point)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes a list of [`GmpePoint`] instances as a GeoJSON `FeatureCollection`.
+///
+/// Each point becomes a `Point` feature with the `value`, `kind` (e.g. `Psa(0.3)`) and `sigma`
+/// properties, ready for display in web GIS clients such as Leaflet or OpenLayers:
+///
+/// ```text
+/// {"geometry":{"coordinates":[142.6,50.1],"type":"Point"},
+///  "properties":{"kind":"Pga","sigma":0.875,"value":0.789},"type":"Feature"}
+/// ```
+///
+/// Missing (`NaN`) values and standard deviations are written as `null`. Features are written
+/// one per line, so large grids are streamed to the file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::GmpePoint;
+/// use ground_motion_lib::writers::write_gmpe_geojson;
+///
+/// let points = vec![GmpePoint::new_pga(142.6, 50.1, 0.789)];
+/// write_gmpe_geojson("pga.geojson", &points).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_gmpe_geojson<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    write_gmpe_geojson_to_writer(BufWriter::new(create_writer(path)?), points)
+}
+
+/// Writes a list of [`GmpePoint`] instances as a GeoJSON `FeatureCollection` in the format of
+/// [`write_gmpe_geojson`] to any writer, e.g. standard output.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_gmpe_geojson_to_writer<W: Write>(
+    mut file: W,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    write!(file, r#"{{"type":"FeatureCollection","features":["#)?;
+    for (i, point) in points.iter().enumerate() {
+        let feature = json!({
+            "type": "Feature",
+            "geometry": {"type": "Point", "coordinates": [point.lon, point.lat]},
+            "properties": {
+                "value": point.value,
+                "kind": point.kind.to_string(),
+                "sigma": point.sigma,
+            },
+        });
+        writeln!(file, "{}", if i == 0 { "" } else { "," })?;
+        serde_json::to_writer(&mut file, &feature)?;
+    }
+    writeln!(file, "\n]}}")?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes a list of [`GmpePoint`] instances as a single JSON document with metadata.
+///
+/// The document holds the metadata entries as a `metadata` object and the points as a `points`
+/// array of objects with the fields of [`GmpePoint`]:
+///
+/// ```text
+/// {"metadata":{"event_bias":"0.25"},"points":[
+/// {"lon":142.6,"lat":50.1,"value":0.789,"kind":"Pga","sigma":0.875}
+/// ]}
+/// ```
+///
+/// Missing (`NaN`) values and standard deviations are written as `null`. Points are written one
+/// per line, so large grids are streamed to the file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::GmpePoint;
+/// use ground_motion_lib::writers::write_gmpe_json;
+///
+/// let points = vec![GmpePoint::new_pga(142.6, 50.1, 0.789)];
+/// let metadata = vec![("event_bias".to_string(), "0.25".to_string())];
+/// write_gmpe_json("pga.json", &points, &metadata).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_gmpe_json<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    write_gmpe_json_to_writer(BufWriter::new(create_writer(path)?), points, metadata)
+}
+
+/// Writes a list of [`GmpePoint`] instances as a JSON document in the format of
+/// [`write_gmpe_json`] to any writer, e.g. standard output.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_gmpe_json_to_writer<W: Write>(
+    mut file: W,
+    points: &[GmpePoint],
+    metadata: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let metadata: serde_json::Map<_, _> = metadata
+        .iter()
+        .map(|(key, value)| (key.clone(), json!(value)))
+        .collect();
+    write!(file, r#"{{"metadata":"#)?;
+    serde_json::to_writer(&mut file, &metadata)?;
+    write!(file, r#","points":["#)?;
+    for (i, point) in points.iter().enumerate() {
+        writeln!(file, "{}", if i == 0 { "" } else { "," })?;
+        serde_json::to_writer(&mut file, point)?;
+    }
+    writeln!(file, "\n]}}")?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes a list of [`GmpePoint`] instances as JSON Lines, one JSON object per point.
+///
+/// Every line is a self-contained object with the fields of [`GmpePoint`], ready for
+/// line-oriented streaming consumers such as Elasticsearch bulk ingestion or `jq`:
+///
+/// ```text
+/// {"lon":142.6,"lat":50.1,"value":0.789,"kind":"Pga","sigma":0.875}
+/// {"lon":142.7,"lat":50.2,"value":0.923,"kind":"Pga","sigma":0.875}
+/// ```
+///
+/// Missing (`NaN`) values and standard deviations are written as `null`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_gmpe_jsonl<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    write_gmpe_jsonl_to_writer(BufWriter::new(create_writer(path)?), points)
+}
+
+/// Writes a list of [`GmpePoint`] instances as JSON Lines in the format of
+/// [`write_gmpe_jsonl`] to any writer, e.g. standard output.
+///
+/// # Errors
+///
+/// Returns an error if writing fails.
+pub fn write_gmpe_jsonl_to_writer<W: Write>(
+    mut file: W,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    for point in points {
+        serde_json::to_writer(&mut file, point)?;
+        writeln!(file)?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes an attenuation curve to a delimited text file.
+///
+/// The file has a header row and the columns `distance`, `value`, `kind` and `sigma` (empty for
+/// models without a standard deviation).
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `delim` — Delimiter character for the file.
+/// * `curve` — Attenuation curve to write.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::vectorized::{attenuation_curve, log_distances};
+/// use ground_motion_lib::writers::write_attenuation_curve;
+///
+/// let gmpe_ref = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let curve = attenuation_curve(gmpe_ref, 7.0, 13.0, 350., &log_distances(1., 300., 50));
+/// write_attenuation_curve("attenuation.csv", b',', &curve).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or a row fails to be written.
+pub fn write_attenuation_curve<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    curve: &AttenuationCurve,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let mut wtr = WriterBuilder::new().delimiter(delim).from_writer(file);
+    wtr.write_record(["distance", "value", "kind", "sigma"])?;
+    let kind = curve.kind.to_string();
+    for ((distance, value), sigma) in curve.distances.iter().zip(&curve.values).zip(&curve.sigma) {
+        let sigma = sigma.map(|s| s.to_string()).unwrap_or_default();
+        wtr.write_record([&distance.to_string(), &value.to_string(), &kind, &sigma])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes a response spectrum of a single site to a delimited text file.
+///
+/// The file has a header row and the columns `period`, `value`, `kind` and `sigma` (empty for
+/// models without a standard deviation). PGA is written with the period `0`; other points
+/// without a spectral period leave the period empty.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `delim` — Delimiter character for the file.
+/// * `points` — Predictions of the site, one per type of motion.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::GmpePoint;
+/// use ground_motion_lib::writers::write_spectrum;
+///
+/// let spectrum = vec![
+///     GmpePoint::new_pga(142.5, 50.0, 20.0),
+///     GmpePoint::new_psa(142.5, 50.0, 45.0, 0.2),
+///     GmpePoint::new_psa(142.5, 50.0, 12.0, 1.0),
+/// ];
+/// write_spectrum("spectrum.csv", b',', &spectrum).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or a row fails to be written.
+pub fn write_spectrum<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let mut wtr = WriterBuilder::new().delimiter(delim).from_writer(file);
+    wtr.write_record(["period", "value", "kind", "sigma"])?;
+    for point in points {
+        let period = match point.kind {
+            GmpePointKind::Pga => "0".to_string(),
+            kind => kind.period().map(|p| p.to_string()).unwrap_or_default(),
+        };
+        let sigma = point.sigma.map(|s| s.to_string()).unwrap_or_default();
+        wtr.write_record([
+            &period,
+            &point.value.to_string(),
+            &point.kind.to_string(),
+            &sigma,
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes a hazard deaggregation to a delimited text file.
+///
+/// The file has a header row and one row per bin with the columns `magnitude`, `distance` and
+/// `epsilon` (bin centers), `rate` (annual rate of exceedance) and `fraction` (of the total rate
+/// of the site).
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `delim` — Delimiter character for the file.
+/// * `deaggregation` — Deaggregation to write.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::deaggregation::{DeaggregationBins, deaggregate};
+/// use ground_motion_lib::gmm::Vs30Point;
+/// use ground_motion_lib::hazard::PointSource;
+/// use ground_motion_lib::writers::write_deaggregation;
+///
+/// let site = Vs30Point::new(142.5, 50.0, 400., None, None);
+/// let sources = vec![PointSource::new(142.4, 50.0, 10.0, 6.5, 0.01)];
+/// let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+/// let deagg = deaggregate(&site, &sources, gmpe, 20., &DeaggregationBins::default()).unwrap();
+/// write_deaggregation("deaggregation.csv", b',', &deagg).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or a row fails to be written.
+pub fn write_deaggregation<P: AsRef<Path>>(
+    path: P,
+    delim: u8,
+    deaggregation: &Deaggregation,
+) -> Result<(), Box<dyn Error>> {
+    let file = File::create(path)?;
+    let mut wtr = WriterBuilder::new().delimiter(delim).from_writer(file);
+    wtr.write_record(["magnitude", "distance", "epsilon", "rate", "fraction"])?;
+    let bins = &deaggregation.bins;
+    let center = |edges: &[f64], i: usize| (0.5 * (edges[i] + edges[i + 1])).to_string();
+    let fractions = deaggregation.fractions();
+    for (i, by_distance) in deaggregation.rates.iter().enumerate() {
+        for (j, by_epsilon) in by_distance.iter().enumerate() {
+            for (k, rate) in by_epsilon.iter().enumerate() {
+                wtr.write_record([
+                    &center(&bins.magnitude, i),
+                    &center(&bins.distance, j),
+                    &center(&bins.epsilon, k),
+                    &rate.to_string(),
+                    &fractions[i][j][k].to_string(),
+                ])?;
+            }
+        }
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// OpenQuake engine name of an intensity measure type and the divisor converting values to OQ
+/// units (`g` for accelerations, which this crate gives in `%g`).
+fn oq_imt(kind: GmpePointKind) -> (String, f64) {
+    match kind {
+        GmpePointKind::Pga => ("PGA".to_string(), 100.),
+        GmpePointKind::Psa { period } => (format!("SA({period})"), 100.),
+        GmpePointKind::Pgv => ("PGV".to_string(), 1.),
+        GmpePointKind::Pgd => ("PGD".to_string(), 1.),
+        GmpePointKind::Mmi => ("MMI".to_string(), 1.),
+        GmpePointKind::AriasIntensity => ("IA".to_string(), 1.),
+        GmpePointKind::Ds575 => ("RSD575".to_string(), 1.),
+        GmpePointKind::Ds595 => ("RSD595".to_string(), 1.),
+    }
+}
+
+/// Checks that all hazard curves share the type of motion and the levels of the first one.
+fn common_curve(curves: &[HazardCurve]) -> Result<&HazardCurve, Box<dyn Error>> {
+    let first = curves.first().ok_or("No hazard curves given")?;
+    if curves
+        .iter()
+        .any(|curve| curve.kind != first.kind || curve.levels != first.levels)
+    {
+        return Err("Hazard curves must share the type of motion and the levels".into());
+    }
+    Ok(first)
+}
+
+/// Writes the `#,,,"key=value, ..."` metadata line of OpenQuake engine CSV exports.
+fn write_oq_metadata(
+    file: &mut File,
+    n_columns: usize,
+    metadata: &str,
+) -> Result<(), Box<dyn Error>> {
+    let padding = ",".repeat(n_columns.saturating_sub(1));
+    writeln!(
+        file,
+        "#{padding}\"generated_by='ground_motion_lib {}', {metadata}\"",
+        env!("CARGO_PKG_VERSION")
+    )?;
+    Ok(())
+}
+
+/// Writes hazard curves as an OpenQuake engine `hazard_curve-<kind>-<IMT>.csv` file.
+///
+/// The file starts with a metadata comment line (`kind`, `investigation_time`, `imt`), followed
+/// by the header `lon,lat,depth,poe-<level>,...` and one row per site with the probabilities of
+/// exceedance of the levels within the investigation time. Accelerations are converted from
+/// `%g` to `g`, the units of OpenQuake; other types of motion are written as is. The site depth
+/// is always `0`.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `curves` — Hazard curves sharing the type of motion and the levels.
+/// * `investigation_time` — Investigation time of the probabilities of exceedance (years).
+/// * `kind` — Statistic or realization of the curves, e.g. `"mean"`, `"quantile-0.84"` or
+///   `"rlz-000"`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::gmm::Vs30Point;
+/// use ground_motion_lib::hazard::{PointSource, calc_hazard_curves};
+/// use ground_motion_lib::writers::write_oq_hazard_curves;
+///
+/// let sites = vec![Vs30Point::new(142.5, 50.0, 400., None, None)];
+/// let sources = vec![PointSource::new(142.4, 50.0, 10.0, 6.5, 0.01)];
+/// let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+/// let curves = calc_hazard_curves(&sites, &sources, gmpe, &[1., 10., 100.]).unwrap();
+/// write_oq_hazard_curves("hazard_curve-mean-PGA.csv", &curves, 50., "mean").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if no curves are given, the curves differ in the type of motion or the
+/// levels, or the file cannot be written.
+pub fn write_oq_hazard_curves<P: AsRef<Path>>(
+    path: P,
+    curves: &[HazardCurve],
+    investigation_time: f64,
+    kind: &str,
+) -> Result<(), Box<dyn Error>> {
+    let first = common_curve(curves)?;
+    let (imt, divisor) = oq_imt(first.kind);
+    let mut file = File::create(path)?;
+    write_oq_metadata(
+        &mut file,
+        3 + first.levels.len(),
+        &format!("kind='{kind}', investigation_time={investigation_time:?}, imt='{imt}'"),
+    )?;
+
+    let mut wtr = WriterBuilder::new().from_writer(file);
+    let header: Vec<String> = ["lon", "lat", "depth"]
+        .into_iter()
+        .map(String::from)
+        .chain(
+            first
+                .levels
+                .iter()
+                .map(|level| format!("poe-{}", level / divisor)),
+        )
+        .collect();
+    wtr.write_record(&header)?;
+    for curve in curves {
+        let row: Vec<String> = [curve.lon, curve.lat, 0.]
+            .into_iter()
+            .chain(curve.poe_in(investigation_time))
+            .map(|value| value.to_string())
+            .collect();
+        wtr.write_record(&row)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes hazard maps as an OpenQuake engine `hazard_map-<kind>.csv` file.
+///
+/// The file starts with a metadata comment line (`kind`, `investigation_time`), followed by the
+/// header `lon,lat,<IMT>-<poe>,...` and one row per site with the ground motion levels exceeded
+/// with the probabilities `poes` within the investigation time, see
+/// [`HazardCurve::level_at_rate`]. Accelerations are converted from `%g` to `g`.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `curves` — Hazard curves sharing the type of motion and the levels.
+/// * `poes` — Probabilities of exceedance of the maps within the investigation time.
+/// * `investigation_time` — Investigation time (years).
+/// * `kind` — Statistic or realization of the curves, e.g. `"mean"`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_lib_config;
+/// use ground_motion_lib::gmm::Vs30Point;
+/// use ground_motion_lib::hazard::{PointSource, calc_hazard_curves};
+/// use ground_motion_lib::vectorized::log_distances;
+/// use ground_motion_lib::writers::write_oq_hazard_map;
+///
+/// let sites = vec![Vs30Point::new(142.5, 50.0, 400., None, None)];
+/// let sources = vec![PointSource::new(142.4, 50.0, 10.0, 6.5, 0.02)];
+/// let gmpe = get_lib_config("config_mf2013_crustal_pga").unwrap();
+/// let levels = log_distances(0.1, 1000., 50);
+/// let curves = calc_hazard_curves(&sites, &sources, gmpe, &levels).unwrap();
+/// write_oq_hazard_map("hazard_map-mean.csv", &curves, &[0.1, 0.02], 50., "mean").unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if no curves are given, the curves differ in the type of motion or the
+/// levels, a probability is not within (0, 1), or the file cannot be written.
+pub fn write_oq_hazard_map<P: AsRef<Path>>(
+    path: P,
+    curves: &[HazardCurve],
+    poes: &[f64],
+    investigation_time: f64,
+    kind: &str,
+) -> Result<(), Box<dyn Error>> {
+    let first = common_curve(curves)?;
+    if poes.iter().any(|poe| *poe <= 0. || *poe >= 1.) {
+        return Err("Probabilities of exceedance must be within (0, 1)".into());
+    }
+    let (imt, divisor) = oq_imt(first.kind);
+    let mut file = File::create(path)?;
+    write_oq_metadata(
+        &mut file,
+        2 + poes.len(),
+        &format!("kind='{kind}', investigation_time={investigation_time:?}"),
+    )?;
+
+    let mut wtr = WriterBuilder::new().from_writer(file);
+    let header: Vec<String> = ["lon".to_string(), "lat".to_string()]
+        .into_iter()
+        .chain(poes.iter().map(|poe| format!("{imt}-{poe}")))
+        .collect();
+    wtr.write_record(&header)?;
+    let rates: Vec<f64> = poes
+        .iter()
+        .map(|poe| -(-poe).ln_1p() / investigation_time)
+        .collect();
+    for curve in curves {
+        let row: Vec<String> = [curve.lon, curve.lat]
+            .into_iter()
+            .chain(
+                rates
+                    .iter()
+                    .map(|rate| curve.level_at_rate(*rate) / divisor),
+            )
+            .map(|value| value.to_string())
+            .collect();
+        wtr.write_record(&row)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Nodata value of written Arc/Info ASCII grids.
+pub const ESRI_ASCII_NODATA: f64 = -9999.;
+
+/// Writes a [`RasterGrid`] to an Arc/Info ASCII grid file.
+///
+/// The header holds the lower-left corner (`xllcorner`, `yllcorner`), the `cellsize` (or `dx`
+/// and `dy` for rectangular cells) and `NODATA_value` ([`ESRI_ASCII_NODATA`]), followed by the
+/// values row by row from north to south. Such files are read back with
+/// [`read_esri_ascii_grid`](crate::readers::read_esri_ascii_grid).
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `grid` — The raster to write.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or written.
+pub fn write_esri_ascii_grid<P: AsRef<Path>>(
+    path: P,
+    grid: &RasterGrid,
+) -> Result<(), Box<dyn Error>> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "ncols {}", grid.ncols)?;
+    writeln!(file, "nrows {}", grid.nrows)?;
+    writeln!(file, "xllcorner {}", grid.lon0 - grid.dlon / 2.)?;
+    let lat_south = grid.lat0 - (grid.nrows as f64 - 0.5) * grid.dlat;
+    writeln!(file, "yllcorner {lat_south}")?;
+    if (grid.dlon - grid.dlat).abs() < 1e-9 * grid.dlon {
+        writeln!(file, "cellsize {}", grid.dlon)?;
+    } else {
+        writeln!(file, "dx {}", grid.dlon)?;
+        writeln!(file, "dy {}", grid.dlat)?;
+    }
+    writeln!(file, "NODATA_value {ESRI_ASCII_NODATA}")?;
+    for row in grid.values.chunks_exact(grid.ncols) {
+        let row: Vec<String> = row
+            .iter()
+            .map(|value| {
+                if value.is_nan() {
+                    ESRI_ASCII_NODATA.to_string()
+                } else {
+                    value.to_string()
+                }
+            })
+            .collect();
+        writeln!(file, "{}", row.join(" "))?;
+    }
+    file.flush()?;
+    Ok(())
+}
+
+/// Writes prediction results on a regular grid to an Arc/Info ASCII grid file.
+///
+/// The grid spacing and row ordering are detected with [`RasterGrid::from_points`], so the
+/// points may come in any order; sites missing from the grid (e.g. dropped by a pre-filter)
+/// are written as nodata. See [`write_esri_ascii_grid`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::gmm::{Earthquake, generate_grid};
+/// use ground_motion_lib::vectorized::calc_gmpe_vec;
+/// use ground_motion_lib::writers::write_gmpe_points_esri_ascii;
+///
+/// let sites = generate_grid([141., 45.5, 145., 54.5], 0.05, 400.);
+/// let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let eq = Earthquake::new_mw(142.83, 52.63, 11., 7.1);
+/// let results = calc_gmpe_vec(&sites, gmpe, &eq);
+/// write_gmpe_points_esri_ascii("pga.asc", &results).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the points do not form a regular grid, or the file cannot be written.
+pub fn write_gmpe_points_esri_ascii<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    write_esri_ascii_grid(path, &RasterGrid::from_points(points)?)
+}
+
+/// GeoTIFF keys of a geographic (EPSG:4326) raster with area pixels: the key directory header
+/// followed by `GTModelTypeGeoKey`, `GTRasterTypeGeoKey` and `GeographicTypeGeoKey`.
+#[cfg(feature = "geotiff")]
+const GEOTIFF_WGS84_KEYS: [u16; 16] = [1, 1, 0, 3, 1024, 0, 1, 2, 1025, 0, 1, 1, 2048, 0, 1, 4326];
+
+/// Writes a [`RasterGrid`] to a georeferenced single-band GeoTIFF file.
+///
+/// Values are stored as deflate-compressed 32-bit floats in geographic coordinates (EPSG:4326),
+/// georeferenced with the `ModelPixelScale` and `ModelTiepoint` tags. Nodata cells are `NaN`
+/// and declared with the `GDAL_NODATA` tag. The optional description (e.g. the type of motion)
+/// is stored in the `ImageDescription` tag. Requires the `geotiff` crate feature.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `grid` — The raster to write.
+/// * `description` — Optional description of the values.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or encoded.
+#[cfg(feature = "geotiff")]
+pub fn write_geotiff<P: AsRef<Path>>(
+    path: P,
+    grid: &RasterGrid,
+    description: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    use tiff::encoder::{Compression, DeflateLevel, TiffEncoder, colortype::Gray32Float};
+    use tiff::tags::Tag;
+
+    let mut encoder = TiffEncoder::new(File::create(path)?)?
+        .with_compression(Compression::Deflate(DeflateLevel::Balanced));
+    let mut image = encoder.new_image::<Gray32Float>(grid.ncols as u32, grid.nrows as u32)?;
+    // The tiepoint refers to the north-west corner of the north-west cell
+    let tiepoint = [
+        0.,
+        0.,
+        0.,
+        grid.lon0 - grid.dlon / 2.,
+        grid.lat0 + grid.dlat / 2.,
+        0.,
+    ];
+    let tags = image.encoder();
+    tags.write_tag(Tag::ModelPixelScaleTag, &[grid.dlon, grid.dlat, 0.][..])?;
+    tags.write_tag(Tag::ModelTiepointTag, &tiepoint[..])?;
+    tags.write_tag(Tag::GeoKeyDirectoryTag, &GEOTIFF_WGS84_KEYS[..])?;
+    tags.write_tag(Tag::GdalNodata, "nan")?;
+    if let Some(description) = description {
+        tags.write_tag(Tag::ImageDescription, description)?;
+    }
+    let values: Vec<f32> = grid.values.iter().map(|value| *value as f32).collect();
+    image.write_data(&values)?;
+    Ok(())
+}
+
+/// Writes prediction results on a regular grid to a georeferenced GeoTIFF file.
+///
+/// The points are rasterized with [`RasterGrid::from_points`], and the raster is written with
+/// [`write_geotiff`], described by the type of motion of the first point (e.g. `Psa(0.3)`).
+/// Requires the `geotiff` crate feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::gmm::{Earthquake, generate_grid};
+/// use ground_motion_lib::vectorized::calc_gmpe_vec;
+/// use ground_motion_lib::writers::write_gmpe_points_geotiff;
+///
+/// let sites = generate_grid([141., 45.5, 145., 54.5], 0.05, 400.);
+/// let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let eq = Earthquake::new_mw(142.83, 52.63, 11., 7.1);
+/// let results = calc_gmpe_vec(&sites, gmpe, &eq);
+/// write_gmpe_points_geotiff("pga.tif", &results).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the points do not form a regular grid, or the file cannot be written.
+#[cfg(feature = "geotiff")]
+pub fn write_gmpe_points_geotiff<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    let grid = RasterGrid::from_points(points)?;
+    let description = points.first().map(|p| p.kind.to_string());
+    write_geotiff(path, &grid, description.as_deref())
+}
+
+/// Writes a [`RasterGrid`] to a GMT-compatible netCDF grid file.
+///
+/// The file follows the COARDS conventions of GMT grids, so it can be plotted directly with
+/// `gmt grdimage` or processed with other `grd*` modules:
+///
+/// - `lon(lon)`, `lat(lat)` — cell center coordinates in ascending order.
+/// - `z(lat, lon)` — deflate-compressed 32-bit float values with a `NaN` fill value, rows from
+///   south to north, and the optional `long_name` (e.g. the type of motion).
+///
+/// Requires the `netcdf` crate feature.
+///
+/// # Arguments
+///
+/// * `path` — The output file path (conventionally `.grd` or `.nc`).
+/// * `grid` — The raster to write.
+/// * `long_name` — Optional description of the values.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or any variable fails to be written.
+#[cfg(feature = "netcdf")]
+pub fn write_gmt_grid<P: AsRef<Path>>(
+    path: P,
+    grid: &RasterGrid,
+    long_name: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let lons: Vec<f64> = (0..grid.ncols)
+        .map(|col| grid.cell_center(0, col).0)
+        .collect();
+    let lats: Vec<f64> = (0..grid.nrows)
+        .rev()
+        .map(|row| grid.cell_center(row, 0).1)
+        .collect();
+    let values: Vec<f32> = grid
+        .values
+        .chunks_exact(grid.ncols)
+        .rev()
+        .flatten()
+        .map(|value| *value as f32)
+        .collect();
+    let (z_min, z_max) = grid
+        .values
+        .iter()
+        .filter(|value| !value.is_nan())
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+
+    let mut file = netcdf::create(path)?;
+    file.add_attribute("Conventions", "COARDS, CF-1.5")?;
+    file.add_dimension("lon", grid.ncols)?;
+    file.add_dimension("lat", grid.nrows)?;
+
+    let coordinates = [
+        ("lon", "longitude", "degrees_east", &lons),
+        ("lat", "latitude", "degrees_north", &lats),
+    ];
+    for (name, long_name, units, values) in coordinates {
+        let mut var = file.add_variable::<f64>(name, &[name])?;
+        var.put_attribute("long_name", long_name)?;
+        var.put_attribute("units", units)?;
+        var.put_attribute("actual_range", vec![values[0], values[values.len() - 1]])?;
+        var.put_values(values, ..)?;
+    }
+
+    let mut z = file.add_variable::<f32>("z", &["lat", "lon"])?;
+    z.set_compression(6, true)?;
+    z.set_fill_value(f32::NAN)?;
+    z.put_attribute("long_name", long_name.unwrap_or("z"))?;
+    if z_min <= z_max {
+        z.put_attribute("actual_range", vec![z_min, z_max])?;
+    }
+    z.put_values(&values, ..)?;
+    Ok(())
+}
+
+/// Writes prediction results on a regular grid to a GMT-compatible netCDF grid file.
+///
+/// The points are rasterized with [`RasterGrid::from_points`], and the raster is written with
+/// [`write_gmt_grid`], named by the type of motion of the first point (e.g. `Psa(0.3)`).
+/// Requires the `netcdf` crate feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::configs::get_mf2013_lib_configs;
+/// use ground_motion_lib::gmm::{Earthquake, generate_grid};
+/// use ground_motion_lib::vectorized::calc_gmpe_vec;
+/// use ground_motion_lib::writers::write_gmpe_points_gmt_grid;
+///
+/// let sites = generate_grid([141., 45.5, 145., 54.5], 0.05, 400.);
+/// let gmpe = get_mf2013_lib_configs().get("config_mf2013_crustal_pga").unwrap();
+/// let eq = Earthquake::new_mw(142.83, 52.63, 11., 7.1);
+/// let results = calc_gmpe_vec(&sites, gmpe, &eq);
+/// write_gmpe_points_gmt_grid("pga.grd", &results).unwrap();
+/// // gmt grdimage pga.grd -JM15c -Cseis -B -png pga
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the points do not form a regular grid, or the file cannot be written.
+#[cfg(feature = "netcdf")]
+pub fn write_gmpe_points_gmt_grid<P: AsRef<Path>>(
+    path: P,
+    points: &[GmpePoint],
+) -> Result<(), Box<dyn Error>> {
+    let grid = RasterGrid::from_points(points)?;
+    let long_name = points.first().map(|p| p.kind.to_string());
+    write_gmt_grid(path, &grid, long_name.as_deref())
+}
+
+/// Stacked result grids sharing the same sites, e.g. events × sites or realizations × sites.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultStack {
+    /// Site longitudes in decimal degrees.
+    pub lon: Vec<f64>,
+    /// Site latitudes in decimal degrees.
+    pub lat: Vec<f64>,
+    /// Row-major values, `values[row * n_sites + site]`.
+    pub values: Vec<f64>,
+    /// Number of stacked rows (events, realizations, ...).
+    pub n_rows: usize,
+    /// Name of the stacked dimension, e.g. `"event"` or `"realization"`.
+    pub row_dim: String,
+    /// Type of the stacked ground motion values.
+    pub kind: GmpePointKind,
+    /// Free-form metadata stored alongside the results.
+    pub metadata: Vec<(String, String)>,
+}
+
+impl ResultStack {
+    /// Build a stack from result grids computed over the same sites.
+    ///
+    /// # Arguments
+    ///
+    /// * `grids` - Result grids, one per event or realization, all with the same sites.
+    /// * `row_dim` - Name of the stacked dimension, e.g. `"event"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `grids` is empty or the grids differ in size.
+    pub fn from_grids(grids: &[Vec<GmpePoint>], row_dim: &str) -> Result<Self, Box<dyn Error>> {
+        let first = grids.first().ok_or("Result stack must not be empty")?;
+        if grids.iter().any(|grid| grid.len() != first.len()) {
+            return Err("All stacked grids must have the same number of sites".into());
+        }
+        Ok(Self {
+            lon: first.iter().map(|p| p.lon).collect(),
+            lat: first.iter().map(|p| p.lat).collect(),
+            values: grids.iter().flatten().map(|p| p.value).collect(),
+            n_rows: grids.len(),
+            row_dim: row_dim.to_string(),
+            kind: first.first().map_or(GmpePointKind::Pga, |p| p.kind),
+            metadata: Vec::new(),
+        })
+    }
+
+    /// Number of sites in every stacked grid.
+    pub fn n_sites(&self) -> usize {
+        self.lon.len()
+    }
+
+    /// Attach a metadata entry.
+    pub fn with_metadata(mut self, key: &str, value: &str) -> Self {
+        self.metadata.push((key.to_string(), value.to_string()));
+        self
+    }
+}
+
+/// Writes stacked result grids to an HDF5 file.
+///
+/// The file contains the following datasets:
+///
+/// - `/lon`, `/lat` — site coordinates (`n_sites`).
+/// - `/values` — stacked values (`n_rows × n_sites`) with `kind` and `dimensions` attributes.
+///
+/// Every metadata entry of the stack is stored as a string attribute of the root group.
+///
+/// # Arguments
+///
+/// * `path` — The output file path.
+/// * `stack` — Stacked results to write.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ground_motion_lib::gmm::GmpePoint;
+/// use ground_motion_lib::writers::{write_result_stack_hdf5, ResultStack};
+///
+/// let grids = vec![
+///     vec![GmpePoint::new_pga(142.5, 50.0, 1.2), GmpePoint::new_pga(142.6, 50.1, 0.8)],
+///     vec![GmpePoint::new_pga(142.5, 50.0, 2.4), GmpePoint::new_pga(142.6, 50.1, 1.1)],
+/// ];
+/// let stack = ResultStack::from_grids(&grids, "event")
+///     .unwrap()
+///     .with_metadata("model", "config_mf2013_crustal_pga");
+/// write_result_stack_hdf5("sequence.h5", &stack).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be created or any dataset fails to be written.
+#[cfg(feature = "hdf5")]
+pub fn write_result_stack_hdf5<P: AsRef<Path>>(
+    path: P,
+    stack: &ResultStack,
+) -> Result<(), Box<dyn Error>> {
+    use hdf5::types::VarLenUnicode;
+
+    let file = hdf5::File::create(path)?;
+    let n_sites = stack.n_sites();
+
+    let lon = file.new_dataset::<f64>().shape([n_sites]).create("lon")?;
+    lon.write_raw(&stack.lon)?;
+    let lat = file.new_dataset::<f64>().shape([n_sites]).create("lat")?;
+    lat.write_raw(&stack.lat)?;
+
+    let values = file
+        .new_dataset::<f64>()
+        .shape([stack.n_rows, n_sites])
+        .create("values")?;
+    values.write_raw(&stack.values)?;
+
+    let kind: VarLenUnicode = stack.kind.to_string().parse()?;
+    values
+        .new_attr::<VarLenUnicode>()
+        .create("kind")?
+        .write_scalar(&kind)?;
+    let dims: VarLenUnicode = format!("{},site", stack.row_dim).parse()?;
+    values
+        .new_attr::<VarLenUnicode>()
+        .create("dimensions")?
+        .write_scalar(&dims)?;
+
+    for (key, value) in &stack.metadata {
+        let value: VarLenUnicode = value.parse()?;
+        file.new_attr::<VarLenUnicode>()
+            .create(key.as_str())?
+            .write_scalar(&value)?;
+    }
+
+    file.flush()?;
+    Ok(())
+}
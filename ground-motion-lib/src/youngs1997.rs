@@ -0,0 +1,229 @@
+//! Implementation of Youngs et al. (1997) Ground Motion Prediction Equations (GMPE) for
+//! subduction zone earthquakes.
+//!
+//! The model covers interface (interplate) and intraslab (intraplate) events with separate
+//! equations for rock and soil sites:
+//!
+//! ```text
+//! rock: ln(y) =  0.2418 + 1.414·M + C1 + C2·(10 − M)³ + C3·ln(R + 1.7818·exp(0.554·M))
+//!              + 0.00607·H + 0.3846·Zt
+//! soil: ln(y) = −0.6687 + 1.438·M + C1 + C2·(10 − M)³ + C3·ln(R + 1.097·exp(0.617·M))
+//!              + 0.00648·H + 0.3643·Zt
+//! ```
+//!
+//! where `y` is PGA or 5%-damped PSA in g, `R` is the rupture distance (km), `H` is the focal
+//! depth (km) and `Zt` is 0 for interface and 1 for intraslab events. For point sources the
+//! rupture distance is approximated by the hypocentral distance.
+
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Site class of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Youngs1997Site {
+    /// Rock sites
+    Rock,
+    /// Deep soil sites
+    Soil,
+}
+
+/// Period-dependent Youngs et al. (1997) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Youngs1997Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Constant term
+    pub c1: f64,
+    /// Magnitude-cubed scaling
+    pub c2: f64,
+    /// Geometric spreading
+    pub c3: f64,
+    /// Magnitude-dependent standard deviation `C4 + C5·min(M, 8)` of `ln(y)`
+    pub c4: f64,
+    pub c5: f64,
+}
+
+const fn coeffs(period: f64, c1: f64, c2: f64, c3: f64, c4: f64) -> Youngs1997Coeffs {
+    Youngs1997Coeffs {
+        period,
+        c1,
+        c2,
+        c3,
+        c4,
+        c5: -0.1,
+    }
+}
+
+/// Youngs et al. (1997) rock coefficients (PGA, PSA at 0.1, 0.2, 0.3, 0.5, 1, 2 and 3 s).
+pub const YOUNGS1997_ROCK: [Youngs1997Coeffs; 8] = [
+    coeffs(0., 0., 0., -2.552, 1.45),
+    coeffs(0.1, 1.188, -0.0011, -2.655, 1.45),
+    coeffs(0.2, 0.722, -0.0027, -2.528, 1.45),
+    coeffs(0.3, 0.246, -0.0036, -2.454, 1.45),
+    coeffs(0.5, -0.4, -0.0048, -2.36, 1.45),
+    coeffs(1.0, -1.736, -0.0064, -2.234, 1.45),
+    coeffs(2.0, -3.328, -0.008, -2.107, 1.55),
+    coeffs(3.0, -4.511, -0.0089, -2.033, 1.65),
+];
+
+/// Youngs et al. (1997) soil coefficients (PGA, PSA at 0.1, 0.2, 0.3, 0.5, 1, 2 and 3 s).
+pub const YOUNGS1997_SOIL: [Youngs1997Coeffs; 8] = [
+    coeffs(0., 0., 0., -2.329, 1.45),
+    coeffs(0.1, 2.516, -0.0019, -2.697, 1.45),
+    coeffs(0.2, 1.549, -0.0019, -2.464, 1.45),
+    coeffs(0.3, 0.793, -0.002, -2.327, 1.45),
+    coeffs(0.5, -0.438, -0.0035, -2.14, 1.45),
+    coeffs(1.0, -2.87, -0.0066, -1.785, 1.5),
+    coeffs(2.0, -5.35, -0.0164, -1.29, 1.5),
+    coeffs(3.0, -6.651, -0.0221, -1.081, 1.5),
+];
+
+/// Youngs et al. (1997) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct YOUNGS1997 {
+    /// Period-dependent coefficients
+    pub coeffs: Youngs1997Coeffs,
+    /// Site class the coefficients belong to
+    pub site: Youngs1997Site,
+    /// Tectonic type of the modeled events (interplate or intraplate)
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl YOUNGS1997 {
+    /// Create a new model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tectonic_type` is [`TectonicType::Crustal`], since the model only covers
+    /// subduction events.
+    pub fn new(
+        coeffs: Youngs1997Coeffs,
+        site: Youngs1997Site,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        assert!(
+            tectonic_type != TectonicType::Crustal,
+            "YOUNGS1997 only supports interplate and intraplate events"
+        );
+        Self {
+            coeffs,
+            site,
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Median `ln(y)` (g).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Rupture distance (km).
+    /// * `depth` - Focal depth (km).
+    pub fn ln_median(&self, magnitude: f64, distance: f64, depth: f64) -> f64 {
+        let c = &self.coeffs;
+        let zt = match self.tectonic_type {
+            TectonicType::Intraplate => 1.,
+            _ => 0.,
+        };
+        let (a0, a1, d1, d2, h, z) = match self.site {
+            Youngs1997Site::Rock => (0.2418, 1.414, 1.7818, 0.554, 0.00607, 0.3846),
+            Youngs1997Site::Soil => (-0.6687, 1.438, 1.097, 0.617, 0.00648, 0.3643),
+        };
+        a0 + a1 * magnitude
+            + c.c1
+            + c.c2 * (10. - magnitude).powi(3)
+            + c.c3 * (distance + d1 * (d2 * magnitude).exp()).ln()
+            + h * depth
+            + z * zt
+    }
+
+    /// Standard deviation of `ln(y)` for the given magnitude.
+    pub fn sigma(&self, magnitude: f64) -> f64 {
+        self.coeffs.c4 + self.coeffs.c5 * magnitude.min(8.)
+    }
+}
+
+impl GroundMotionModeling for YOUNGS1997 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// The site class is fixed by the model configuration; the site Vs30 is not used.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ln_y = self.ln_median(eq.mw(), distance, eq.depth);
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert g to %g
+            value: ln_y.exp() * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.sigma(eq.mw())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rock_pga_reference_value() {
+        let m = YOUNGS1997::new(
+            YOUNGS1997_ROCK[0],
+            Youngs1997Site::Rock,
+            TectonicType::Interplate,
+            GmpePointKind::Pga,
+        );
+        let expected = 0.2418 + 1.414 * 8. - 2.552 * (100. + 1.7818 * (0.554 * 8_f64).exp()).ln()
+            + 0.00607 * 30.;
+        assert!((m.ln_median(8., 100., 30.) - expected).abs() < 1e-12);
+        assert!((m.sigma(8.5) - 0.65).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_intraslab_stronger_than_interface() {
+        let interface = YOUNGS1997::new(
+            YOUNGS1997_SOIL[0],
+            Youngs1997Site::Soil,
+            TectonicType::Interplate,
+            GmpePointKind::Pga,
+        );
+        let slab = YOUNGS1997::new(
+            YOUNGS1997_SOIL[0],
+            Youngs1997Site::Soil,
+            TectonicType::Intraplate,
+            GmpePointKind::Pga,
+        );
+        let diff = slab.ln_median(7., 80., 50.) - interface.ln_median(7., 80., 50.);
+        assert!((diff - 0.3643).abs() < 1e-12);
+    }
+}
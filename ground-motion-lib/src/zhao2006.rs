@@ -0,0 +1,378 @@
+//! Implementation of Zhao et al. (2006) Ground Motion Prediction Equations (GMPE) for Japan.
+//!
+//! The model covers shallow crustal, subduction interface (interplate) and intraslab
+//! (intraplate) events with a common functional form:
+//!
+//! ```text
+//! ln(y) = a·Mw + b·x − ln(x + c·exp(d·Mw)) + e·(h − hc)·δh + F_R + S_I + S_S + S_SL·ln(x) + C_k
+//!       + magnitude-squared correction of the tectonic type
+//! ```
+//!
+//! where `y` is PGA or 5%-damped PSA in cm/s², `x` is the source distance (km), `h` is the
+//! focal depth (km, capped at 125 km) and `hc = 15 km`. `C_k` is the site class term selected
+//! from Vs30:
+//!
+//! | Site class | Vs30 (m/s)     |
+//! |------------|----------------|
+//! | Hard rock  | > 1100         |
+//! | SC I       | 600 – 1100     |
+//! | SC II      | 300 – 600      |
+//! | SC III     | 200 – 300      |
+//! | SC IV      | ≤ 200          |
+//!
+//! Current simplifications:
+//!
+//! - Crustal events use the unspecified (non-reverse) mechanism (`F_R = 0`).
+//! - For point sources the source distance is approximated by the hypocentral distance.
+
+use crate::auxilary::G_GLOBAL;
+use crate::distance::{DistanceMetric, SourceDistances};
+use crate::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, GroundMotionModeling, TectonicType, Vs30Point,
+};
+
+/// Reference depth of the depth term (km).
+const HC: f64 = 15.;
+/// Maximum focal depth used by the depth term (km).
+const H_MAX: f64 = 125.;
+/// Reference magnitude of the crustal and interface magnitude-squared terms.
+const MC: f64 = 6.3;
+/// Reference magnitude of the slab magnitude-squared terms.
+const MSC: f64 = 6.5;
+
+/// Period-dependent Zhao et al. (2006) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Zhao2006Coeffs {
+    /// Spectral period (s); `0.0` for PGA.
+    pub period: f64,
+    /// Magnitude scaling
+    pub a: f64,
+    /// Anelastic attenuation
+    pub b: f64,
+    /// Near-source saturation
+    pub c: f64,
+    pub d: f64,
+    /// Depth scaling
+    pub e: f64,
+    /// Crustal reverse fault term
+    pub sr: f64,
+    /// Interface event term
+    pub si: f64,
+    /// Slab event term
+    pub ss: f64,
+    /// Slab path modification term
+    pub ssl: f64,
+    /// Hard rock site term
+    pub ch: f64,
+    /// Site class I–IV terms
+    pub c1: f64,
+    pub c2: f64,
+    pub c3: f64,
+    pub c4: f64,
+    /// Intra-event standard deviation
+    pub sigma: f64,
+    /// Inter-event standard deviation
+    pub tau: f64,
+    /// Crustal magnitude-squared correction
+    pub qc: f64,
+    pub wc: f64,
+    /// Interface magnitude-squared correction
+    pub qi: f64,
+    pub wi: f64,
+    /// Slab magnitude correction
+    pub ps: f64,
+    pub qs: f64,
+    pub ws: f64,
+}
+
+/// Zhao et al. (2006) coefficients for PGA and PSA at 0.1, 0.3, 1.0 and 3.0 s.
+pub const ZHAO2006_COEFFS: [Zhao2006Coeffs; 5] = [
+    Zhao2006Coeffs {
+        period: 0.,
+        a: 1.101,
+        b: -0.00564,
+        c: 0.0055,
+        d: 1.08,
+        e: 0.01412,
+        sr: 0.251,
+        si: 0.,
+        ss: 2.607,
+        ssl: -0.528,
+        ch: 0.293,
+        c1: 1.111,
+        c2: 1.344,
+        c3: 1.355,
+        c4: 1.42,
+        sigma: 0.604,
+        tau: 0.398,
+        qc: 0.,
+        wc: 0.,
+        qi: 0.,
+        wi: 0.,
+        ps: 0.1392,
+        qs: 0.1584,
+        ws: -0.0529,
+    },
+    Zhao2006Coeffs {
+        period: 0.1,
+        a: 1.118,
+        b: -0.00787,
+        c: 0.009,
+        d: 1.083,
+        e: 0.01423,
+        sr: 0.24,
+        si: 0.,
+        ss: 2.156,
+        ssl: -0.42,
+        ch: 1.499,
+        c1: 2.061,
+        c2: 2.135,
+        c3: 2.031,
+        c4: 1.433,
+        sigma: 0.641,
+        tau: 0.44,
+        qc: 0.,
+        wc: 0.,
+        qi: 0.,
+        wi: 0.,
+        ps: 0.1636,
+        qs: 0.1932,
+        ws: -0.0841,
+    },
+    Zhao2006Coeffs {
+        period: 0.3,
+        a: 1.163,
+        b: -0.0052,
+        c: 0.015,
+        d: 0.934,
+        e: 0.01458,
+        sr: 0.259,
+        si: 0.,
+        ss: 2.181,
+        ssl: -0.45,
+        ch: 0.852,
+        c1: 1.172,
+        c2: 1.683,
+        c3: 1.808,
+        c4: 0.978,
+        sigma: 0.674,
+        tau: 0.42,
+        qc: 0.,
+        wc: 0.,
+        qi: 0.,
+        wi: 0.,
+        ps: 0.124,
+        qs: 0.1389,
+        ws: -0.0388,
+    },
+    Zhao2006Coeffs {
+        period: 1.0,
+        a: 1.479,
+        b: -0.0022,
+        c: 0.002,
+        d: 1.115,
+        e: 0.01005,
+        sr: 0.211,
+        si: -0.239,
+        ss: 2.233,
+        ssl: -0.509,
+        ch: -2.451,
+        c1: -2.152,
+        c2: -1.776,
+        c3: -1.523,
+        c4: -1.828,
+        sigma: 0.695,
+        tau: 0.406,
+        qc: -0.0955,
+        wc: 0.234,
+        qi: -0.0575,
+        wi: 0.142,
+        ps: 0.0431,
+        qs: 0.0978,
+        ws: 0.0159,
+    },
+    Zhao2006Coeffs {
+        period: 3.0,
+        a: 1.759,
+        b: -0.00147,
+        c: 0.0032,
+        d: 1.025,
+        e: 0.00644,
+        sr: 0.307,
+        si: -0.331,
+        ss: 1.037,
+        ssl: -0.263,
+        ch: -5.839,
+        c1: -5.431,
+        c2: -5.089,
+        c3: -4.893,
+        c4: -5.066,
+        sigma: 0.732,
+        tau: 0.436,
+        qc: -0.1448,
+        wc: 0.547,
+        qi: -0.0904,
+        wi: 0.301,
+        ps: -0.0014,
+        qs: 0.0621,
+        ws: 0.1011,
+    },
+];
+
+/// Zhao et al. (2006) Ground Motion Prediction Equation parameters.
+#[derive(Debug)]
+pub struct ZHAO2006 {
+    /// Period-dependent coefficients
+    pub coeffs: Zhao2006Coeffs,
+    /// Tectonic type of the modeled events
+    pub tectonic_type: TectonicType,
+    /// Type of motion (PGA, PSA)
+    pub motion_kind: GmpePointKind,
+}
+
+impl ZHAO2006 {
+    /// Create a new model for the given coefficient set, tectonic type and motion type.
+    pub fn new(
+        coeffs: Zhao2006Coeffs,
+        tectonic_type: TectonicType,
+        motion_kind: GmpePointKind,
+    ) -> Self {
+        Self {
+            coeffs,
+            tectonic_type,
+            motion_kind,
+        }
+    }
+
+    /// Site class term `C_k` for the given Vs30 (m/s).
+    pub fn site_term(&self, vs30: f64) -> f64 {
+        let c = &self.coeffs;
+        match vs30 {
+            v if v > 1100. => c.ch,
+            v if v > 600. => c.c1,
+            v if v > 300. => c.c2,
+            v if v > 200. => c.c3,
+            _ => c.c4,
+        }
+    }
+
+    /// Natural logarithm of the median ground motion (cm/s²).
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - Moment magnitude (Mw).
+    /// * `distance` - Source distance (km).
+    /// * `depth` - Focal depth (km).
+    /// * `vs30` - Average shear-wave velocity in the top 30 meters at the site (m/s).
+    pub fn ln_median(&self, magnitude: f64, distance: f64, depth: f64, vs30: f64) -> f64 {
+        let c = &self.coeffs;
+        let m = magnitude;
+        let x = distance.max(0.1);
+        let h = depth.min(H_MAX);
+
+        let mut ln_y = c.a * m + c.b * x - (x + c.c * (c.d * m).exp()).ln() + self.site_term(vs30);
+        if h >= HC {
+            ln_y += c.e * (h - HC);
+        }
+        ln_y += match self.tectonic_type {
+            TectonicType::Crustal => c.qc * (m - MC).powi(2) + c.wc,
+            TectonicType::Interplate => c.si + c.qi * (m - MC).powi(2) + c.wi,
+            TectonicType::Intraplate => {
+                c.ss + c.ssl * x.ln() + c.ps * (m - MSC) + c.qs * (m - MSC).powi(2) + c.ws
+            }
+        };
+        ln_y
+    }
+
+    /// Total standard deviation of `ln(y)`.
+    pub fn total_sigma(&self) -> f64 {
+        self.coeffs.sigma.hypot(self.coeffs.tau)
+    }
+}
+
+impl GroundMotionModeling for ZHAO2006 {
+    fn distance_metrics(&self) -> Vec<DistanceMetric> {
+        vec![DistanceMetric::Rrup]
+    }
+
+    fn calc_from_distance(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        epicentral_distance: f64,
+    ) -> GmpePoint {
+        self.calc_from_distances(point, eq, &SourceDistances::new(epicentral_distance))
+    }
+
+    /// Compute ground motion prediction at a given site point for a specified earthquake event.
+    ///
+    /// # Returns
+    ///
+    /// A `GmpePoint` with PGA/PSA in %g.
+    fn calc_from_distances(
+        &self,
+        point: &Vs30Point,
+        eq: &Earthquake,
+        distances: &SourceDistances,
+    ) -> GmpePoint {
+        let distance = distances
+            .rrup
+            .unwrap_or_else(|| distances.repi.hypot(eq.depth));
+        let ground_motion = self
+            .ln_median(eq.mw(), distance, eq.depth, point.vs30)
+            .exp();
+        GmpePoint {
+            lon: point.lon,
+            lat: point.lat,
+            // convert cm/s² to %g
+            value: ((ground_motion / 100.) / G_GLOBAL) * 100.,
+            kind: self.motion_kind,
+            sigma: Some(self.total_sigma()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crustal_pga() {
+        let model = ZHAO2006::new(
+            ZHAO2006_COEFFS[0],
+            TectonicType::Crustal,
+            GmpePointKind::Pga,
+        );
+        let expected =
+            1.101 * 7. - 0.00564 * 20. - (20. + 0.0055 * (1.08_f64 * 7.).exp()).ln() + 1.344;
+        assert!((model.ln_median(7., 20., 10., 400.) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_site_classes() {
+        let model = ZHAO2006::new(
+            ZHAO2006_COEFFS[0],
+            TectonicType::Crustal,
+            GmpePointKind::Pga,
+        );
+        assert_eq!(model.site_term(1500.), 0.293);
+        assert_eq!(model.site_term(700.), 1.111);
+        assert_eq!(model.site_term(250.), 1.355);
+        assert_eq!(model.site_term(150.), 1.42);
+    }
+
+    #[test]
+    fn test_slab_events_are_stronger_than_crustal() {
+        let crustal = ZHAO2006::new(
+            ZHAO2006_COEFFS[0],
+            TectonicType::Crustal,
+            GmpePointKind::Pga,
+        );
+        let slab = ZHAO2006::new(
+            ZHAO2006_COEFFS[0],
+            TectonicType::Intraplate,
+            GmpePointKind::Pga,
+        );
+        assert!(slab.ln_median(7., 60., 50., 400.) > crustal.ln_median(7., 60., 50., 400.));
+    }
+}
@@ -1,10 +1,14 @@
 use std::error::Error;
 
 use ground_motion_lib::auxilary::{approx_equal, round_to_places};
-use ground_motion_lib::configs::get_mf2013_lib_configs;
-use ground_motion_lib::gmm::{Earthquake, GmpePoint, GmpePointKind, Magnitude, Vs30Point};
+use ground_motion_lib::configs::{get_mf2013_lib_configs, get_mf2013_psa};
+use ground_motion_lib::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, Magnitude, TectonicType, Vs30Point,
+};
 use ground_motion_lib::readers::read_vs30_points;
-use ground_motion_lib::vectorized::calc_gmpe_vec;
+use ground_motion_lib::rupture::Rupture;
+use ground_motion_lib::vectorized::{calc_gmpe_vec, compute_stats};
+use ground_motion_lib::writers::write_gmpe_points;
 
 const EPSILON: f64 = 1e-6;
 const CSV_DELIMETER: u8 = b'\t';
@@ -15,6 +19,8 @@ const EQ6: Earthquake = Earthquake {
     depth: 13.,
     magnitude: 6.,
     magnitude_kind: Magnitude::Mw,
+    rupture: None,
+    segments: None,
 };
 const EQ7: Earthquake = Earthquake {
     lon: 143.04,
@@ -22,6 +28,8 @@ const EQ7: Earthquake = Earthquake {
     depth: 13.,
     magnitude: 7.,
     magnitude_kind: Magnitude::Mw,
+    rupture: None,
+    segments: None,
 };
 const EQ85: Earthquake = Earthquake {
     lon: 143.04,
@@ -29,6 +37,8 @@ const EQ85: Earthquake = Earthquake {
     depth: 13.,
     magnitude: 8.5,
     magnitude_kind: Magnitude::Mw,
+    rupture: None,
+    segments: None,
 };
 
 const GRID_EPICENTER: Vs30Point = Vs30Point {
@@ -37,6 +47,8 @@ const GRID_EPICENTER: Vs30Point = Vs30Point {
     vs30: 350.,
     dl: None,
     xvf: None,
+    z1p0: None,
+    z2p5: None,
 };
 
 const GRID_SIZE: usize = 17;
@@ -81,14 +93,14 @@ fn test_mf2013_const_dl() -> Result<(), Box<dyn Error>> {
     let config_ref = configs.get("config_mf2013_crustal_psa_10").unwrap();
     let gmpe_points = calc_gmpe_vec(&vs_30_grid, config_ref, &EQ6);
     assert!(gmpe_points.len() == GRID_SIZE);
-    assert!(matches!(gmpe_points[0].kind, GmpePointKind::Psa));
+    assert_eq!(gmpe_points[0].kind, GmpePointKind::Psa { period: 1. });
     let value = sum_and_round_values(&gmpe_points);
     assert!(approx_equal(value, 5.49, EPSILON));
 
     let config_ref = configs.get("config_mf2013_crustal_psa_30").unwrap();
     let gmpe_points = calc_gmpe_vec(&vs_30_grid, config_ref, &EQ6);
     assert!(gmpe_points.len() == GRID_SIZE);
-    assert!(matches!(gmpe_points[0].kind, GmpePointKind::Psa));
+    assert_eq!(gmpe_points[0].kind, GmpePointKind::Psa { period: 3. });
     let value = sum_and_round_values(&gmpe_points);
     assert!(approx_equal(value, 1.42, EPSILON));
 
@@ -105,7 +117,7 @@ fn test_mf2013_const_dl() -> Result<(), Box<dyn Error>> {
     assert!(matches!(gmpe_points[0].kind, GmpePointKind::Pga));
     let value = sum_and_round_values(&gmpe_points);
     println!("{value}");
-    assert!(approx_equal(value, 74.86, EPSILON));
+    assert!(approx_equal(value, 74.85, EPSILON));
 
     Ok(())
 }
@@ -132,10 +144,188 @@ fn test_mf2013_dl_on_grid() -> Result<(), Box<dyn Error>> {
     let config_ref = configs.get("config_mf2013_crustal_psa_03").unwrap();
     let gmpe_points = calc_gmpe_vec(&vs_30_grid, config_ref, &EQ85);
     assert!(gmpe_points.len() == GRID_SIZE);
-    assert!(matches!(gmpe_points[0].kind, GmpePointKind::Psa));
+    assert_eq!(gmpe_points[0].kind, GmpePointKind::Psa { period: 0.3 });
     let value = sum_and_round_values(&gmpe_points);
     println!("{value}");
     assert!(approx_equal(value, 4177.5, EPSILON));
 
     Ok(())
 }
+
+#[test]
+fn test_mf2013_asid_xvf_distance() -> Result<(), Box<dyn Error>> {
+    let configs = get_mf2013_lib_configs();
+    let config_ref = configs.get("config_mf2013_intraplate_pga_asid").unwrap();
+    let eq_deep = Earthquake::new_mw(143.04, 51.92, 130., 7.);
+
+    let site = Vs30Point::new(143.5, 51.92, 350., None, None);
+    let base = site.get_gm(config_ref, &eq_deep).value;
+
+    // Fore-arc site 100 km from the volcanic front: gamma * 100 * (130 - 30) = 0.76 log10 units
+    let fore_arc = Vs30Point::new(143.5, 51.92, 350., None, None).with_xvf(-100.);
+    let amplified = fore_arc.get_gm(config_ref, &eq_deep).value;
+    let expected_ratio = 10_f64.powf(0.00007602 * 100. * 100.);
+    assert!(approx_equal(amplified / base, expected_ratio, EPSILON));
+
+    // Sites on the front and sites without a distance are not corrected
+    let on_front = Vs30Point::new(143.5, 51.92, 350., None, None).with_xvf(0.);
+    let neutral = on_front.get_gm(config_ref, &eq_deep).value;
+    assert!(approx_equal(neutral, base, EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_mf2013_asid_correction_term() {
+    let configs = get_mf2013_lib_configs();
+    let config_ref = configs.get("config_mf2013_intraplate_pga_asid").unwrap();
+    let gamma = config_ref.gamma;
+
+    // gamma * Xvf * (H - 30), with Xvf = 0 for sites without a distance
+    let site = Vs30Point::new(143.5, 51.92, 350., None, None);
+    assert_eq!(config_ref.asid_correction(&site, 130.), 0.);
+    let fore_arc = Vs30Point::new(143.5, 51.92, 350., None, None).with_xvf(-50.);
+    let term = config_ref.asid_correction(&fore_arc, 130.);
+    assert!(approx_equal(term, gamma * 50. * 100., EPSILON));
+    // Well below one log10 unit even for deep events
+    assert!(term < 1.);
+    assert!(approx_equal(
+        config_ref.asid_correction(&fore_arc, 30.),
+        0.,
+        EPSILON
+    ));
+}
+
+#[test]
+fn test_mf2013_asid_correction_back_arc() {
+    let configs = get_mf2013_lib_configs();
+    let config_ref = configs.get("config_mf2013_intraplate_pga_asid").unwrap();
+
+    // 0.00007602 * -(+50) * (130 - 30) = -0.3801: back-arc sites are attenuated
+    let back_arc = Vs30Point::new(143.5, 51.92, 350., None, None).with_xvf(50.);
+    let term = config_ref.asid_correction(&back_arc, 130.);
+    assert!(approx_equal(term, -0.3801, EPSILON));
+    let fore_arc = back_arc.with_xvf(-50.);
+    assert!(approx_equal(
+        config_ref.asid_correction(&fore_arc, 130.),
+        0.3801,
+        EPSILON
+    ));
+}
+
+#[test]
+fn test_mf2013_psa_table_lookup() {
+    let configs = get_mf2013_lib_configs();
+    for tectonic in ["crustal", "interplate", "intraplate"] {
+        for label in ["psa_03", "psa_10", "psa_30"] {
+            let key = format!("config_mf2013_{tectonic}_{label}");
+            assert!(configs.contains_key(key.as_str()), "{key}");
+        }
+    }
+
+    let by_period = get_mf2013_psa(1., TectonicType::Intraplate).unwrap();
+    let by_key = configs.get("config_mf2013_intraplate_psa_10").unwrap();
+    assert!(std::ptr::eq(by_period, by_key));
+    assert!(approx_equal(by_period.c, -0.024, EPSILON));
+
+    assert!(get_mf2013_psa(3., TectonicType::Crustal).is_some());
+    assert!(get_mf2013_psa(0.05, TectonicType::Crustal).is_none());
+    assert!(get_mf2013_psa(0.33, TectonicType::Crustal).is_none());
+    assert!(get_mf2013_psa(0., TectonicType::Crustal).is_none());
+
+    // The 3 s ordinate is well below the 0.3 s one and still attenuates with distance
+    let eq = Earthquake::new_mw(143.04, 51.92, 13., 7.);
+    let near = Vs30Point::new(143.1, 51.92, 350., None, None);
+    let far = Vs30Point::new(145., 51.92, 350., None, None);
+    let short = get_mf2013_psa(0.3, TectonicType::Crustal).unwrap();
+    let long = get_mf2013_psa(3., TectonicType::Crustal).unwrap();
+    assert!(near.get_gm(long, &eq).value < near.get_gm(short, &eq).value);
+    assert!(far.get_gm(long, &eq).value < near.get_gm(long, &eq).value);
+}
+
+#[test]
+fn test_mf2013_psa_period_in_output() -> Result<(), Box<dyn Error>> {
+    let configs = get_mf2013_lib_configs();
+    let site = [Vs30Point::new(143.3, 51.92, 350., None, None)];
+    let short = calc_gmpe_vec(
+        &site,
+        configs.get("config_mf2013_crustal_psa_03").unwrap(),
+        &EQ6,
+    );
+    let long = calc_gmpe_vec(
+        &site,
+        configs.get("config_mf2013_crustal_psa_30").unwrap(),
+        &EQ6,
+    );
+    assert_eq!(short[0].kind.period(), Some(0.3));
+    assert_eq!(
+        compute_stats(&long).kind,
+        Some(GmpePointKind::Psa { period: 3. })
+    );
+
+    let path = std::env::temp_dir().join("test_mf2013_psa_period_in_output.txt");
+    let points: Vec<GmpePoint> = short.into_iter().chain(long).collect();
+    write_gmpe_points(&path, CSV_DELIMETER, &points)?;
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(CSV_DELIMETER)
+        .from_path(&path)?;
+    let kinds = rdr
+        .deserialize()
+        .map(|row| row.map(|point: GmpePoint| point.kind))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        kinds,
+        vec![
+            GmpePointKind::Psa { period: 0.3 },
+            GmpePointKind::Psa { period: 3. }
+        ]
+    );
+    assert!(std::fs::read_to_string(&path)?.contains("Psa(0.3)"));
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_mf2013_sigma_in_output() -> Result<(), Box<dyn Error>> {
+    let config = get_mf2013_lib_configs()
+        .get("config_mf2013_crustal_pga")
+        .unwrap();
+    let site = [Vs30Point::new(143.3, 51.92, 350., None, None)];
+    let points = calc_gmpe_vec(&site, config, &EQ6);
+    // log10 standard deviation converted to natural-log units
+    let expected = config.sigma * std::f64::consts::LN_10;
+    assert!((points[0].sigma.unwrap() - expected).abs() < 1e-12);
+
+    let path = std::env::temp_dir().join("test_mf2013_sigma_in_output.txt");
+    let points: Vec<GmpePoint> = points
+        .into_iter()
+        .chain([GmpePoint::new_pga(143.4, 51.92, 1.)])
+        .collect();
+    write_gmpe_points(&path, CSV_DELIMETER, &points)?;
+    let mut rdr = csv::ReaderBuilder::new()
+        .delimiter(CSV_DELIMETER)
+        .from_path(&path)?;
+    let sigmas = rdr
+        .deserialize()
+        .map(|row| row.map(|point: GmpePoint| point.sigma))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(sigmas, vec![Some(expected), None]);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_mf2013_finite_rupture() {
+    let config = get_mf2013_lib_configs()
+        .get("config_mf2013_crustal_pga")
+        .unwrap();
+    // Site 60 km north of the epicenter of a 150 km long north-striking rupture
+    let site = Vs30Point::new(142.83, 53.17, 350., None, None);
+    let point_source = Earthquake::new_mw(142.83, 52.63, 15., 7.8);
+    let finite = Earthquake::new_mw(142.83, 52.63, 15., 7.8)
+        .with_rupture(Rupture::new(0., 90., 150., 20.).with_ztor(2.));
+    let far = site.get_gm(config, &point_source);
+    let near = site.get_gm(config, &finite);
+    assert!((finite.rrup(site.lon, site.lat) - 2.).abs() < 1e-6);
+    assert!(near.value > 2. * far.value);
+}
@@ -1,7 +1,24 @@
 use std::error::Error;
 
 use ground_motion_lib::auxilary::approx_equal;
-use ground_motion_lib::readers::read_vs30_points;
+use ground_motion_lib::configs::get_lib_config;
+use ground_motion_lib::gmm::{
+    Earthquake, GmpePoint, GmpePointKind, Magnitude, TectonicType, Vs30Point,
+};
+use ground_motion_lib::readers::{
+    Vs30Columns, read_catalog, read_esri_ascii_grid, read_esri_ascii_vs30_points, read_fsp,
+    read_quakeml_event, read_scml_event, read_shakemap_grid, read_shakemap_grid_vs30_points,
+    read_site_class_map, read_site_class_points, read_slab_model, read_stations,
+    read_usgs_geojson_event, read_vs30_points, read_vs30_points_from_reader,
+    read_vs30_points_from_reader_with, read_vs30_points_with, read_xml_event, read_zonation,
+};
+use ground_motion_lib::site_class::SiteClassMap;
+use ground_motion_lib::slope_vs30::{ElevationGrid, SlopeRegime};
+use ground_motion_lib::tectonic::TectonicSelector;
+use ground_motion_lib::writers::{
+    write_gmpe_geojson, write_gmpe_json, write_gmpe_jsonl, write_gmpe_points_esri_ascii,
+    write_vs30_points,
+};
 
 const EPSILON: f64 = 1e-6;
 const CSV_DELIMETER: u8 = b'\t';
@@ -27,6 +44,53 @@ fn test_read_usgs_vs_30_grid_wo_dl() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_read_vs30_points_from_reader() -> Result<(), Box<dyn Error>> {
+    let file = std::fs::read("tests/data/testvs30.txt")?;
+    let from_reader = read_vs30_points_from_reader(file.as_slice(), CSV_DELIMETER)?;
+    let from_file = read_vs30_points("tests/data/testvs30.txt", CSV_DELIMETER)?;
+    assert_eq!(from_reader.len(), from_file.len());
+    assert_eq!(from_reader[3].vs30, from_file[3].vs30);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_vs30_points_header_and_columns() -> Result<(), Box<dyn Error>> {
+    // Header row detected, columns mapped by name and the station column ignored
+    let points = read_vs30_points("tests/data/testvs30header.csv", b',')?;
+    assert_eq!(points.len(), 3);
+    assert_eq!((points[0].lon, points[0].lat), (142.523, 52.913));
+    assert_eq!((points[1].vs30, points[1].dl), (350., None));
+    assert_eq!(points[2].dl, Some(120.));
+
+    // Explicit layout, header row skipped
+    let columns: Vs30Columns = "_,lon,lat,vs30".parse()?;
+    let points = read_vs30_points_with("tests/data/testvs30header.csv", b',', Some(&columns))?;
+    assert_eq!(
+        (points[0].lon, points[0].lat, points[0].dl),
+        (52.913, 142.523, None)
+    );
+
+    // Positional layout of files without a header row
+    let columns: Vs30Columns = "lon,lat,vs30,dl".parse()?;
+    let points = read_vs30_points_with("tests/data/testvs30dl.txt", CSV_DELIMETER, Some(&columns))?;
+    assert_eq!(points[1].dl, Some(200.));
+
+    assert!("lon,lat,dl".parse::<Vs30Columns>().is_err());
+    assert!("lon,lat,vs30,vs30".parse::<Vs30Columns>().is_err());
+    assert!("lon,lat,vs30,depth".parse::<Vs30Columns>().is_err());
+    let columns: Vs30Columns = "lon,lat,_,vs30".parse()?;
+    let err = read_vs30_points_with("tests/data/testvs30.txt", CSV_DELIMETER, Some(&columns));
+    assert!(
+        err.unwrap_err()
+            .to_string()
+            .contains("missing `vs30` value")
+    );
+
+    Ok(())
+}
+
 #[test]
 fn test_read_usgs_vs_30_grid_with_dl() -> Result<(), Box<dyn Error>> {
     let vs_30_file = "tests/data/testvs30dl.txt";
@@ -54,3 +118,545 @@ fn test_read_usgs_vs_30_grid_with_dl() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_read_vs30_grid_with_basin_depths() -> Result<(), Box<dyn Error>> {
+    let vs_30_grid = read_vs30_points("tests/data/testvs30basin.txt", CSV_DELIMETER)?;
+    assert_eq!(vs_30_grid.len(), 3);
+    assert_eq!(vs_30_grid[0].dl, None);
+    assert_eq!(vs_30_grid[0].z1p0, Some(450.));
+    assert_eq!(vs_30_grid[0].z2p5, Some(2.1));
+    assert_eq!(vs_30_grid[1].dl, Some(150.));
+    // Site on the volcanic front
+    assert_eq!(vs_30_grid[1].xvf, Some(0.));
+    assert_eq!(vs_30_grid[1].z1p0, None);
+    assert_eq!(vs_30_grid[1].z2p5, None);
+    assert_eq!(vs_30_grid[2].xvf, Some(-35.5));
+    assert!(approx_equal(vs_30_grid[2].z2p5.unwrap(), 0.4, EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_xvf_distances_and_legacy_flags() -> Result<(), Box<dyn Error>> {
+    // Signed distances are read as such, even when they are all 0 or 1 km
+    let distances = "142.5\t50.0\t400\t\t1\n142.6\t50.1\t350\t\t0\n";
+    let points = read_vs30_points_from_reader(distances.as_bytes(), CSV_DELIMETER)?;
+    assert_eq!(points[0].xvf, Some(1.));
+    assert_eq!(points[1].xvf, Some(0.));
+
+    // The same positional file with legacy binary flags
+    let legacy = Vs30Columns::default().with_legacy_xvf_flags();
+    let points =
+        read_vs30_points_from_reader_with(distances.as_bytes(), CSV_DELIMETER, Some(&legacy))?;
+    assert_eq!(points.len(), 2);
+    assert!(points.iter().all(|point| point.xvf.is_none()));
+    let invalid = "142.5\t50.0\t400\t\t-35.5\n";
+    let err = read_vs30_points_from_reader_with(invalid.as_bytes(), CSV_DELIMETER, Some(&legacy))
+        .unwrap_err();
+    assert!(err.to_string().contains("expected 0 or 1"));
+
+    // Header rows name the column format
+    let named = "lon,lat,vs30,xvf_km\n142.5,50.0,400,-35.5\n";
+    assert_eq!(
+        read_vs30_points_from_reader(named.as_bytes(), b',')?[0].xvf,
+        Some(-35.5)
+    );
+    let named = "lon,lat,vs30,xvf_flag\n142.5,50.0,400,1\n";
+    assert_eq!(
+        read_vs30_points_from_reader(named.as_bytes(), b',')?[0].xvf,
+        None
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_site_classes() -> Result<(), Box<dyn Error>> {
+    let file = "tests/data/site_classes.txt";
+    let points = read_site_class_points(file, CSV_DELIMETER, &SiteClassMap::default())?;
+    let vs30: Vec<f64> = points.iter().map(|p| p.vs30).collect();
+    assert_eq!(vs30, [525., 255., 400., 760.]);
+    assert_eq!(points[1].dl, Some(150.));
+    assert_eq!(points[3].z2p5, Some(0.6));
+
+    let custom = read_site_class_map("tests/data/site_class_map.csv", b',')?;
+    assert_eq!(custom.vs30("c"), Some(450.));
+    // The custom table has no boundary classes
+    assert!(read_site_class_points(file, CSV_DELIMETER, &custom).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_dem_site_grid_round_trip() -> Result<(), Box<dyn Error>> {
+    let elevations = [0., 20., 60., 5., 40., 120.].repeat(2);
+    let dem = ElevationGrid::new(142., 50.03, 0.01, 0.01, 3, elevations)?;
+    let sites = dem.vs30_points(SlopeRegime::Stable);
+    let path = std::env::temp_dir().join("ground_motion_dem_sites.txt");
+    write_vs30_points(&path, CSV_DELIMETER, &sites)?;
+    let read = read_vs30_points(&path, CSV_DELIMETER)?;
+    assert_eq!(read.len(), 12);
+    for (site, back) in sites.iter().zip(&read) {
+        assert_eq!(
+            (site.lon, site.lat, site.vs30),
+            (back.lon, back.lat, back.vs30)
+        );
+        assert_eq!(back.dl, None);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "geotiff")]
+#[test]
+fn test_read_geotiff_dem() -> Result<(), Box<dyn Error>> {
+    use ground_motion_lib::readers::{read_geotiff_dem, read_geotiff_vs30_points};
+    use tiff::encoder::{TiffEncoder, colortype::Gray32Float};
+    use tiff::tags::Tag;
+
+    // 4 × 3 cells of 0.01° with the north-west corner at (142.0, 50.03), one missing cell
+    let path = std::env::temp_dir().join("ground_motion_dem.tif");
+    let mut elevations: Vec<f32> = (0..12).map(|i| (i % 4) as f32 * 15.).collect();
+    elevations[5] = -9999.;
+    let mut encoder = TiffEncoder::new(std::fs::File::create(&path)?)?;
+    let mut image = encoder.new_image::<Gray32Float>(4, 3)?;
+    image
+        .encoder()
+        .write_tag(Tag::ModelPixelScaleTag, &[0.01, 0.01, 0.][..])?;
+    image
+        .encoder()
+        .write_tag(Tag::ModelTiepointTag, &[0., 0., 0., 142., 50.03, 0.][..])?;
+    image.encoder().write_tag(Tag::GdalNodata, "-9999")?;
+    image.write_data(&elevations)?;
+
+    let dem = read_geotiff_dem(&path)?;
+    assert_eq!((dem.ncols, dem.nrows), (4, 3));
+    assert!(approx_equal(dem.lon0, 142.005, EPSILON));
+    assert!(approx_equal(dem.lat0, 50.025, EPSILON));
+    assert!(dem.elevation(1, 1).is_nan());
+    assert_eq!(dem.elevation(2, 3), 45.);
+
+    let sites = read_geotiff_vs30_points(&path, SlopeRegime::Active)?;
+    assert!(sites.len() < 12 && sites.iter().all(|s| s.vs30 >= 180. && s.vs30 <= 760.));
+
+    Ok(())
+}
+
+#[cfg(feature = "geotiff")]
+#[test]
+fn test_write_gmpe_points_geotiff() -> Result<(), Box<dyn Error>> {
+    use ground_motion_lib::gmm::generate_grid;
+    use ground_motion_lib::readers::read_geotiff_dem;
+    use ground_motion_lib::writers::write_gmpe_points_geotiff;
+
+    let sites = generate_grid([142., 50., 142.3, 50.1], 0.1, 400.);
+    let mut points: Vec<GmpePoint> = sites
+        .iter()
+        .map(|site| GmpePoint::new_pga(site.lon, site.lat, site.lon - 140.))
+        .collect();
+    // A point dropped by a pre-filter becomes a nodata cell
+    points.remove(0);
+    let path = std::env::temp_dir().join("ground_motion_pga.tif");
+    write_gmpe_points_geotiff(&path, &points)?;
+
+    let raster = read_geotiff_dem(&path)?;
+    assert_eq!((raster.ncols, raster.nrows), (4, 2));
+    assert!(approx_equal(raster.lon0, 142., EPSILON));
+    assert!(approx_equal(raster.lat0, 50.1, EPSILON));
+    assert!(approx_equal(raster.elevation(0, 3), 2.3, 1e-5));
+    assert!(raster.elevation(1, 0).is_nan());
+
+    Ok(())
+}
+
+#[test]
+fn test_read_stations_csv() -> Result<(), Box<dyn Error>> {
+    let stations = read_stations("tests/data/stations.csv", b',')?;
+    assert_eq!(stations.len(), 4);
+    assert_eq!(stations[0].id, "SH.OKH");
+    assert_eq!(stations[0].kind, GmpePointKind::Pga);
+    assert!(approx_equal(stations[0].value, 35.2, EPSILON));
+    assert_eq!(stations[1].kind, GmpePointKind::Psa { period: 1. });
+    assert_eq!(stations[2].kind, GmpePointKind::Pgv);
+    assert_eq!(stations[3].kind, GmpePointKind::Mmi);
+    assert!(stations.iter().all(|s| s.vs30.is_none()));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_stations_shakemap_json() -> Result<(), Box<dyn Error>> {
+    let stations = read_stations("tests/data/stationlist.json", CSV_DELIMETER)?;
+    let find = |id: &str, kind| {
+        stations
+            .iter()
+            .find(|s| s.id == id && s.kind == kind)
+            .map(|s| s.value)
+    };
+    // Largest horizontal amplitude, vertical channel ignored
+    assert_eq!(find("SH.OKH", GmpePointKind::Pga), Some(35.2));
+    // Flagged amplitude ignored
+    assert_eq!(find("SH.OKH", GmpePointKind::Pgv), Some(9.2));
+    // Missing amplitude ignored
+    assert_eq!(
+        find("SH.OKH", GmpePointKind::Psa { period: 1. }),
+        Some(12.1)
+    );
+    // Derived intensity of seismic stations is not an observation
+    assert_eq!(find("SH.OKH", GmpePointKind::Mmi), None);
+    assert_eq!(find("DYFI.1", GmpePointKind::Mmi), Some(5.5));
+    assert_eq!(stations.len(), 4);
+
+    let okh = &stations[0];
+    assert_eq!(okh.vs30, Some(420.));
+    assert!(approx_equal(okh.site(760.).vs30, 420., EPSILON));
+    assert!(approx_equal(okh.to_gmpe_point().lon, 142.523, EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_catalog() -> Result<(), Box<dyn Error>> {
+    let events = read_catalog("tests/data/catalog.csv", b',')?;
+    assert_eq!(events.len(), 65);
+    assert_eq!(events[0].zone, "sakhalin_north");
+    assert!(approx_equal(events[0].lon, 143., EPSILON));
+    assert!(approx_equal(events[0].magnitude, 4., EPSILON));
+    assert_eq!(
+        events.iter().filter(|e| e.zone == "sakhalin_south").count(),
+        25
+    );
+    assert_eq!(events[0].magnitude_kind, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_catalog_magnitude_types() -> Result<(), Box<dyn Error>> {
+    let events = read_catalog("tests/data/catalog_mixed.csv", b',')?;
+    let kinds: Vec<Magnitude> = events.iter().map(|e| e.magnitude_kind()).collect();
+    assert_eq!(
+        kinds,
+        [Magnitude::Mw, Magnitude::Mjma, Magnitude::Mb, Magnitude::Ms]
+    );
+    // mb 5.1 is Mw 5.37 with Scordilis (2006)
+    assert!(approx_equal(events[2].mw(), 5.365, EPSILON));
+    let eq = events[3].earthquake();
+    assert_eq!(eq.magnitude_kind, Magnitude::Ms);
+    assert!(approx_equal(eq.mw(), 0.99 * 6.2 + 0.08, EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_slab_model() -> Result<(), Box<dyn Error>> {
+    let slab = read_slab_model("tests/data/slab.xyz", b',')?;
+    // Nodes without a depth are skipped
+    assert_eq!(slab.nodes.len(), 9);
+    assert_eq!(slab.depth_at(143.5, 50.), Some(60.));
+
+    let selector = TectonicSelector::default().with_slab(slab);
+    let regime = |lon: f64, depth: f64| selector.select(&Earthquake::new_mw(lon, 50., depth, 7.));
+    assert_eq!(regime(144., 25.), TectonicType::Interplate);
+    assert_eq!(regime(143.5, 30.), TectonicType::Crustal);
+    assert_eq!(regime(143.5, 90.), TectonicType::Intraplate);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_zonation() -> Result<(), Box<dyn Error>> {
+    let zonation = read_zonation("tests/data/zonation.geojson")?;
+    let names: Vec<&str> = zonation.zones.iter().map(|z| z.name.as_str()).collect();
+    assert_eq!(names, ["kuril", "sakhalin"]);
+    // Both parts of the multi-polygon
+    assert_eq!(zonation.zone_at(147., 45.).unwrap().name, "kuril");
+    assert_eq!(zonation.zone_at(153., 49.).unwrap().name, "kuril");
+    let eq = Earthquake::new_mw(142.83, 52.63, 11., 7.1);
+    assert_eq!(zonation.config_for(&eq), Some("config_mf2013_crustal_pga"));
+    // Holes are excluded from their zone
+    assert!(zonation.zone_at(142.2, 46.2).is_none());
+
+    // The zone config is resolved to the regime of deep events
+    let deep = Earthquake::new_mw(147., 45., 120., 7.);
+    let key = zonation.config_for(&deep).unwrap();
+    let key = TectonicSelector::default().config_key(key, &deep)?;
+    assert_eq!(key, "config_zhao2006_intraplate_pga");
+
+    Ok(())
+}
+
+#[test]
+fn test_read_fsp() -> Result<(), Box<dyn Error>> {
+    let solution = read_fsp("tests/data/two_segments.fsp")?;
+    assert!(approx_equal(solution.lat, 52., EPSILON));
+    assert!(approx_equal(solution.depth, 11., EPSILON));
+    assert!(approx_equal(solution.magnitude, 7.1, EPSILON));
+
+    let segments = &solution.rupture.segments;
+    assert_eq!(segments.len(), 2);
+    assert!(approx_equal(segments[0].rupture.length, 40., EPSILON));
+    assert_eq!(segments[0].rupture.ztor, Some(1.));
+    assert!(approx_equal(segments[1].rupture.strike, 30., EPSILON));
+    // Segments are located at the top-centers given in the file
+    assert!((segments[0].lat - 52.).abs() < 1e-3 && (segments[0].lon - 143.).abs() < 1e-3);
+    assert!((segments[1].lat - 52.2578).abs() < 1e-3);
+    assert!((segments[1].lon - 143.0735).abs() < 1e-3);
+
+    // A site above the second segment is closer to the rupture than to the hypocenter
+    let point_source = Earthquake::new_mw(solution.lon, solution.lat, solution.depth, 7.1);
+    let (lon, lat) = (143.1, 52.25);
+    assert!(solution.rupture.rjb(lon, lat) < 1.);
+    assert!(solution.rupture.rrup(lon, lat) < point_source.rrup(lon, lat) / 2.);
+
+    // Models take their distances to the segments of the solution
+    let eq = solution.to_earthquake();
+    assert_eq!(eq.rrup(lon, lat), solution.rupture.rrup(lon, lat));
+    let gmpe = get_lib_config("config_cb2014_pga").unwrap();
+    let site = Vs30Point::new(lon, lat, 400., None, None);
+    let finite = site.get_gm(gmpe, &eq).value;
+    assert!(finite > 1.5 * site.get_gm(gmpe, &point_source).value);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_quakeml_event() -> Result<(), Box<dyn Error>> {
+    let eq = read_quakeml_event("tests/data/event.xml")?;
+    // Preferred origin and magnitude, not the first ones
+    assert!(approx_equal(eq.lon, 142.85, EPSILON) && approx_equal(eq.lat, 50.35, EPSILON));
+    assert!(approx_equal(eq.depth, 12.5, EPSILON));
+    assert!(approx_equal(eq.magnitude, 6.2, EPSILON));
+    assert_eq!(eq.magnitude_kind, Magnitude::Mw);
+
+    // Without preferred identifiers, the first origin and magnitude are read
+    let content = std::fs::read_to_string("tests/data/event.xml")?;
+    let content: String = content
+        .lines()
+        .filter(|line| !line.contains("<preferred"))
+        .collect();
+    let path = std::env::temp_dir().join("ground_motion_event.xml");
+    std::fs::write(&path, content)?;
+    let eq = read_quakeml_event(&path)?;
+    assert!(approx_equal(eq.depth, 30., EPSILON));
+    assert_eq!(eq.magnitude_kind, Magnitude::Ml);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_scml_event() -> Result<(), Box<dyn Error>> {
+    let eq = read_scml_event("tests/data/event.scml")?;
+    assert!(approx_equal(eq.lon, 142.85, EPSILON) && approx_equal(eq.lat, 50.35, EPSILON));
+    assert!(approx_equal(eq.depth, 12.5, EPSILON));
+    assert!(approx_equal(eq.magnitude, 6.2, EPSILON));
+    assert_eq!(eq.magnitude_kind, Magnitude::Mw);
+    // The format is detected by the root element
+    assert_eq!(
+        read_xml_event("tests/data/event.scml")?.magnitude,
+        eq.magnitude
+    );
+    assert_eq!(read_xml_event("tests/data/event.xml")?.depth, 12.5);
+
+    // Without preferred identifiers, the first referenced origin and its magnitude are read
+    let content = std::fs::read_to_string("tests/data/event.scml")?;
+    let content: String = content
+        .lines()
+        .filter(|line| !line.contains("<preferred"))
+        .collect();
+    let path = std::env::temp_dir().join("ground_motion_event.scml");
+    std::fs::write(&path, content)?;
+    let eq = read_scml_event(&path)?;
+    assert!(approx_equal(eq.depth, 30., EPSILON));
+    assert_eq!(eq.magnitude_kind, Magnitude::Ml);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_usgs_geojson_event() -> Result<(), Box<dyn Error>> {
+    let eq = read_usgs_geojson_event("tests/data/usgs_event.geojson")?;
+    assert!(approx_equal(eq.lon, 142.85, EPSILON) && approx_equal(eq.lat, 50.35, EPSILON));
+    assert!(approx_equal(eq.depth, 12.5, EPSILON));
+    assert!(approx_equal(eq.magnitude, 6.9, EPSILON));
+    assert_eq!(eq.magnitude_kind, Magnitude::Mw);
+
+    // Event feeds are feature collections
+    let feed = r#"{"type": "FeatureCollection", "features": [{"type": "Feature",
+        "properties": {"mag": 4.2, "magType": "mb"},
+        "geometry": {"type": "Point", "coordinates": [143.1, 51.0, 35.0]}}]}"#;
+    let eq = ground_motion_lib::readers::parse_usgs_geojson_event(feed)?;
+    assert_eq!(eq.magnitude_kind, Magnitude::Mb);
+    assert!(approx_equal(eq.depth, 35., EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_shakemap_grid() -> Result<(), Box<dyn Error>> {
+    let path = "tests/data/shakemap_grid.xml";
+    let pga = read_shakemap_grid(path, GmpePointKind::Pga)?;
+    assert_eq!(pga.len(), 6);
+    assert!(approx_equal(pga[1].lon, 142.1, EPSILON) && approx_equal(pga[1].lat, 50.1, EPSILON));
+    assert!(approx_equal(pga[1].value, 24.65, EPSILON));
+    assert_eq!(pga[1].sigma, Some(0.61));
+
+    let psa = read_shakemap_grid(path, GmpePointKind::Psa { period: 1. })?;
+    assert!(approx_equal(psa[5].value, 11.37, EPSILON));
+    assert_eq!(psa[5].sigma, None);
+    assert!(read_shakemap_grid(path, GmpePointKind::Pgd).is_err());
+
+    let sites = read_shakemap_grid_vs30_points(path)?;
+    assert_eq!(sites.len(), 6);
+    assert!(approx_equal(sites[3].vs30, 760., EPSILON));
+
+    Ok(())
+}
+
+#[test]
+fn test_read_esri_ascii_grid() -> Result<(), Box<dyn Error>> {
+    let grid = read_esri_ascii_grid("tests/data/vs30.asc")?;
+    assert_eq!((grid.ncols, grid.nrows), (4, 3));
+    // Center of the north-west cell
+    assert!(approx_equal(grid.lon0, 142.25, EPSILON));
+    assert!(approx_equal(grid.lat0, 51.25, EPSILON));
+    assert!(grid.values[3].is_nan());
+
+    let sites = read_esri_ascii_vs30_points("tests/data/vs30.asc", Some("tests/data/dl.asc"))?;
+    // Nodata Vs30 cells are skipped
+    assert_eq!(sites.len(), 10);
+    assert_eq!((sites[0].vs30, sites[0].dl), (300., Some(250.)));
+    assert_eq!(sites[1].dl, None);
+    let last = sites.last().unwrap();
+    assert!(approx_equal(last.lon, 143.75, EPSILON) && approx_equal(last.lat, 50.25, EPSILON));
+    assert_eq!((last.vs30, last.dl), (900., Some(0.)));
+
+    // Grids of different geometries cannot be combined
+    assert!(
+        read_esri_ascii_vs30_points("tests/data/vs30.asc", Some("tests/data/testvs30.txt"))
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_write_gmpe_points_esri_ascii() -> Result<(), Box<dyn Error>> {
+    // Points in arbitrary order on a 0.2° × 0.1° grid, with one site missing
+    let mut points = Vec::new();
+    for lon in [142.4, 142., 142.2] {
+        for lat in [50., 50.2, 50.1] {
+            points.push(GmpePoint::new_pga(lon, lat, lon - lat));
+        }
+    }
+    points.remove(4);
+    let path = std::env::temp_dir().join("ground_motion_pga.asc");
+    write_gmpe_points_esri_ascii(&path, &points)?;
+
+    let grid = read_esri_ascii_grid(&path)?;
+    assert_eq!((grid.ncols, grid.nrows), (3, 3));
+    assert!(approx_equal(grid.dlon, 0.2, EPSILON) && approx_equal(grid.dlat, 0.1, EPSILON));
+    assert!(approx_equal(grid.lon0, 142., EPSILON));
+    assert!(approx_equal(grid.lat0, 50.2, EPSILON));
+    // North-west cell, and the missing site (142.0, 50.2) written as nodata
+    assert!(grid.values[0].is_nan());
+    assert!(approx_equal(grid.values[1], 142.2 - 50.2, EPSILON));
+    assert!(approx_equal(grid.values[8], 142.4 - 50., EPSILON));
+    assert_eq!(grid.values.iter().filter(|v| v.is_nan()).count(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_write_gmpe_geojson() -> Result<(), Box<dyn Error>> {
+    let points = vec![
+        GmpePoint::new_pga(142.6, 50.1, 0.789),
+        GmpePoint {
+            lon: 142.7,
+            lat: 50.2,
+            value: f64::NAN,
+            kind: GmpePointKind::Psa { period: 0.3 },
+            sigma: Some(0.7),
+        },
+    ];
+    let path = std::env::temp_dir().join("ground_motion_pga.geojson");
+    write_gmpe_geojson(&path, &points)?;
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(&path)?)?;
+    assert_eq!(json["type"], "FeatureCollection");
+    let features = json["features"].as_array().unwrap();
+    assert_eq!(features.len(), 2);
+    assert_eq!(features[0]["geometry"]["coordinates"][1], 50.1);
+    assert_eq!(features[0]["properties"]["value"], 0.789);
+    assert!(features[0]["properties"]["sigma"].is_null());
+    assert_eq!(features[1]["properties"]["kind"], "Psa(0.3)");
+    assert!(features[1]["properties"]["value"].is_null());
+
+    Ok(())
+}
+
+#[test]
+fn test_write_gmpe_json_and_jsonl() -> Result<(), Box<dyn Error>> {
+    let points = vec![
+        GmpePoint::new_pga(142.6, 50.1, 0.789),
+        GmpePoint {
+            lon: 142.7,
+            lat: 50.2,
+            value: f64::NAN,
+            kind: GmpePointKind::Psa { period: 0.3 },
+            sigma: Some(0.7),
+        },
+    ];
+    let metadata = vec![("event_bias".to_string(), "0.25".to_string())];
+    let path = std::env::temp_dir().join("ground_motion_pga.json");
+    write_gmpe_json(&path, &points, &metadata)?;
+
+    let json: serde_json::Value = serde_json::from_reader(std::fs::File::open(&path)?)?;
+    assert_eq!(json["metadata"]["event_bias"], "0.25");
+    let json_points = json["points"].as_array().unwrap();
+    assert_eq!(json_points.len(), 2);
+    assert_eq!(json_points[0]["lat"], 50.1);
+    assert!(json_points[0]["sigma"].is_null());
+    assert!(json_points[1]["value"].is_null());
+
+    let path = std::env::temp_dir().join("ground_motion_pga.jsonl");
+    write_gmpe_jsonl(&path, &points)?;
+    let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<Result<_, _>>()?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["value"], 0.789);
+    assert_eq!(lines[1]["kind"], "Psa(0.3)");
+    assert_eq!(lines[1]["sigma"], 0.7);
+
+    Ok(())
+}
+
+#[cfg(feature = "kmz")]
+#[test]
+fn test_write_gmpe_kmz_overlay() -> Result<(), Box<dyn Error>> {
+    use ground_motion_lib::gmm::generate_grid;
+    use ground_motion_lib::kml::{ColorScale, write_gmpe_kmz};
+    use std::io::Read;
+
+    let sites = generate_grid([142., 50., 142.3, 50.1], 0.1, 400.);
+    let mut points: Vec<GmpePoint> = sites
+        .iter()
+        .map(|site| GmpePoint::new_pga(site.lon, site.lat, site.lon - 140.))
+        .collect();
+    points.remove(0);
+    let path = std::env::temp_dir().join("ground_motion_pga.kmz");
+    write_gmpe_kmz(&path, &points, &ColorScale::from_points(&points))?;
+
+    let mut kmz = zip::ZipArchive::new(std::fs::File::open(&path)?)?;
+    let mut doc = String::new();
+    kmz.by_name("doc.kml")?.read_to_string(&mut doc)?;
+    assert!(doc.contains("<north>50.150000</north>") && doc.contains("<href>overlay.png</href>"));
+    let decoder = png::Decoder::new(std::io::BufReader::new(kmz.by_name("overlay.png")?));
+    let info = decoder.read_info()?.info().clone();
+    assert_eq!((info.width, info.height), (4, 2));
+
+    Ok(())
+}